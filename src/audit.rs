@@ -0,0 +1,168 @@
+// src/audit.rs
+//! Append-only change log of backlog items in `.env-coach/history.jsonl`.
+//!
+//! Every save compares the backlog with the saved one and appends a line per status,
+//! priority or effort change, naming the user and the command that made it. Unlike the
+//! per-item `history` in project.json, the log is never rewritten, so it survives archiving
+//! and hand edits of project.json and can be read back for cycle times.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+use crate::config::{BacklogItem, Status};
+
+pub const HISTORY_FILE: &str = ".env-coach/history.jsonl";
+
+/// One line of the log.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEvent {
+    pub at: DateTime<Utc>,
+    pub item: String,
+    /// `status`, `priority` or `effort`; `created` for an item first seen in this save
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    pub to: String,
+    pub user: String,
+    /// The env-coach command line, e.g. `set-status US-003 done`
+    pub command: String,
+}
+
+/// This process's command line, without the program name.
+pub fn command_line() -> String {
+    std::env::args().skip(1).collect::<Vec<_>>().join(" ")
+}
+
+/// Events for every item in `current` that is new or whose status, priority or effort
+/// differs from `previous`.
+pub fn changes(previous: &[BacklogItem], current: &[BacklogItem], at: DateTime<Utc>, user: &str, command: &str) -> Vec<HistoryEvent> {
+    let event = |item: &BacklogItem, field: &str, from: Option<String>, to: String| HistoryEvent {
+        at,
+        item: item.id.clone(),
+        field: field.to_string(),
+        from,
+        to,
+        user: user.to_string(),
+        command: command.to_string(),
+    };
+    let mut events = Vec::new();
+    for item in current {
+        let Some(before) = previous.iter().find(|before| before.id == item.id) else {
+            events.push(event(item, "created", None, format!("{:?}", item.status)));
+            continue;
+        };
+        if before.status != item.status {
+            events.push(event(item, "status", Some(format!("{:?}", before.status)), format!("{:?}", item.status)));
+        }
+        if before.priority != item.priority {
+            events.push(event(item, "priority", Some(format!("{:?}", before.priority)), format!("{:?}", item.priority)));
+        }
+        if before.effort != item.effort {
+            events.push(event(item, "effort", Some(before.effort.to_string()), item.effort.to_string()));
+        }
+    }
+    events
+}
+
+/// Add `events` to the log of the project in `root`.
+pub fn append(root: &Path, events: &[HistoryEvent]) -> Result<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let path = root.join(HISTORY_FILE);
+    let mut lines = String::new();
+    for event in events {
+        lines.push_str(&serde_json::to_string(event)?);
+        lines.push('\n');
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(lines.as_bytes()).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Every event in the log, oldest first. Lines that do not parse (e.g. cut off by a crash)
+/// are skipped.
+pub fn load(root: &Path) -> Result<Vec<HistoryEvent>> {
+    let path = root.join(HISTORY_FILE);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    Ok(content.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(n, line)| match serde_json::from_str(line) {
+            Ok(event) => Some(event),
+            Err(e) => {
+                log::warn!("Skipping line {} of {}: {}", n + 1, path.display(), e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Days from an item's first move to in-progress until it was last done, from its events.
+pub fn cycle_time_days(events: &[&HistoryEvent]) -> Option<f64> {
+    let status_event = |status: Status| {
+        let label = format!("{:?}", status);
+        move |event: &&&HistoryEvent| event.field == "status" && event.to == label
+    };
+    let started = events.iter().find(status_event(Status::InProgress))?;
+    let done = events.iter().rev().find(status_event(Status::Done))?;
+    (done.at > started.at).then(|| (done.at - started.at).num_minutes() as f64 / (60.0 * 24.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ItemType, Priority};
+    use chrono::Duration;
+
+    #[test]
+    fn test_changes_cover_status_priority_effort_and_new_items() {
+        let previous = vec![BacklogItem::test("US-001"), BacklogItem::test("US-002")];
+        let mut current = previous.clone();
+        current[0].status = Status::InProgress;
+        current[0].priority = Priority::High;
+        current[1].title = "Renamed".to_string();
+        current.push(BacklogItem::test("US-003"));
+        current[2].item_type = ItemType::Bug;
+
+        let events = changes(&previous, &current, Utc::now(), "alice", "set-status US-001 in-progress");
+        let summary: Vec<_> = events.iter().map(|e| (e.item.as_str(), e.field.as_str(), e.from.as_deref(), e.to.as_str())).collect();
+        assert_eq!(summary, vec![
+            ("US-001", "status", Some("Todo"), "InProgress"),
+            ("US-001", "priority", Some("Medium"), "High"),
+            ("US-003", "created", None, "Todo"),
+        ]);
+        assert!(events.iter().all(|e| e.user == "alice" && e.command.starts_with("set-status")));
+    }
+
+    #[test]
+    fn test_log_appends_and_skips_broken_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".env-coach")).unwrap();
+        let start = Utc::now() - Duration::days(3);
+        let mut story = BacklogItem::test("US-001");
+        let step = |story: &BacklogItem, status: Status, at| {
+            let mut next = story.clone();
+            next.status = status;
+            (changes(std::slice::from_ref(story), std::slice::from_ref(&next), at, "bob", "set-status"), next)
+        };
+        let (started, next) = step(&story, Status::InProgress, start);
+        append(dir.path(), &started).unwrap();
+        story = next;
+        std::fs::OpenOptions::new().append(true).open(dir.path().join(HISTORY_FILE)).unwrap().write_all(b"{\"at\": \n").unwrap();
+        let (done, _) = step(&story, Status::Done, start + Duration::hours(36));
+        append(dir.path(), &done).unwrap();
+
+        let events = load(dir.path()).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(cycle_time_days(&events.iter().collect::<Vec<_>>()), Some(1.5));
+        assert!(load(&dir.path().join("missing")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+// src/auto_commit.rs
+//! Opt-in git commits of the planning state (`"auto_commit": true` in project.json `meta`).
+//!
+//! After a command that changes the project, the project file, its split or SQLite storage,
+//! the change log and sprint reports are committed on their own, with the command line as
+//! the subject and the item changes it logged as the body. Other staged or modified files
+//! are left alone; README.md and CHANGELOG.md, which `complete-task` updates, are only
+//! included when they had no uncommitted changes before the command ran.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+use crate::audit::{self, HistoryEvent};
+use crate::context::AppContext;
+use crate::git;
+
+/// Paths env-coach owns; committed whenever they changed.
+const PLANNING_PATHS: [&str; 7] = [
+    "project.json",
+    "project.toml",
+    ".env-coach/backlog",
+    ".env-coach/sprints",
+    ".env-coach/project.db",
+    audit::HISTORY_FILE,
+    ".env-coach/reports",
+];
+/// Documents env-coach updates alongside hand edits.
+const DOC_PATHS: [&str; 2] = ["README.md", "CHANGELOG.md"];
+/// Subject lines longer than this are cut.
+const MAX_SUBJECT: usize = 72;
+/// Item changes listed in the body; the rest are counted.
+const MAX_BODY_LINES: usize = 20;
+
+/// A command run under auto-commit, started by [`AutoCommit::start`].
+#[derive(Debug)]
+pub struct AutoCommit {
+    started: DateTime<Utc>,
+    /// Documents without uncommitted changes before the command
+    clean_docs: Vec<&'static str>,
+}
+
+impl AutoCommit {
+    /// Before a mutating command: `None` unless the project here has auto-commit on and
+    /// lives in a git work tree.
+    pub fn start(ctx: &AppContext) -> Option<Self> {
+        if ctx.dry_run || !crate::config::Project::is_initialized() {
+            return None;
+        }
+        let project = crate::config::Project::load_meta_with(&ctx.global_config, None).ok()?;
+        if !project.meta.auto_commit {
+            return None;
+        }
+        if git::run(&["rev-parse", "--is-inside-work-tree"]).is_err() {
+            println!("⚠️  auto_commit is on, but this project is not in a git repository");
+            return None;
+        }
+        let clean_docs = DOC_PATHS.into_iter()
+            .filter(|path| git::run(&["status", "--porcelain", "--", path]).is_ok_and(|status| status.trim().is_empty()))
+            .collect();
+        Some(Self { started: Utc::now(), clean_docs })
+    }
+
+    /// After the command succeeded: commit what it changed. Failures are reported but do
+    /// not fail the command, whose changes are already saved.
+    pub fn finish(self) {
+        match self.commit() {
+            Ok(Some(summary)) => println!("📝 Committed project state: {}", summary),
+            Ok(None) => {}
+            Err(e) => println!("⚠️  Could not commit the project state: {}", e),
+        }
+    }
+
+    fn commit(&self) -> Result<Option<String>> {
+        let paths: Vec<&str> = PLANNING_PATHS.iter().chain(&self.clean_docs).copied()
+            .filter(|path| Path::new(path).exists() || git::run(&["ls-files", "--", path]).is_ok_and(|files| !files.trim().is_empty()))
+            .collect();
+        if paths.is_empty() {
+            return Ok(None);
+        }
+        let mut add = vec!["add", "-A", "--"];
+        add.extend(&paths);
+        git::run(&add)?;
+        let mut staged = vec!["diff", "--cached", "--name-only", "--"];
+        staged.extend(&paths);
+        let staged = git::run(&staged)?;
+        let files: Vec<&str> = staged.lines().filter(|line| !line.is_empty()).collect();
+        if files.is_empty() {
+            return Ok(None);
+        }
+
+        let command = audit::command_line();
+        let events: Vec<HistoryEvent> = audit::load(&std::env::current_dir()?)?
+            .into_iter()
+            .filter(|event| event.at >= self.started && event.command == command)
+            .collect();
+        let (subject, body) = message(&command, &events);
+        // With paths, only they are committed; anything else staged stays staged
+        let mut commit = vec!["commit", "-q", "-m", subject.as_str()];
+        if !body.is_empty() {
+            commit.extend(["-m", body.as_str()]);
+        }
+        commit.push("--");
+        commit.extend(files);
+        git::run(&commit)?;
+        let hash = git::run(&["rev-parse", "--short", "HEAD"])?;
+        Ok(Some(format!("{} {}", hash.trim(), subject)))
+    }
+}
+
+/// Commit subject and body for `command` and the item changes it logged.
+fn message(command: &str, events: &[HistoryEvent]) -> (String, String) {
+    let mut subject = format!("env-coach {}", command);
+    if subject.chars().count() > MAX_SUBJECT {
+        subject = format!("{}…", subject.chars().take(MAX_SUBJECT - 1).collect::<String>().trim_end());
+    }
+    let mut lines: Vec<String> = events.iter().take(MAX_BODY_LINES).map(|event| match (event.field.as_str(), &event.from) {
+        ("created", _) => format!("- {}: created", event.item),
+        (field, Some(from)) => format!("- {}: {} {} → {}", event.item, field, from, event.to),
+        (field, None) => format!("- {}: {} → {}", event.item, field, event.to),
+    }).collect();
+    if events.len() > MAX_BODY_LINES {
+        lines.push(format!("- … and {} more change(s)", events.len() - MAX_BODY_LINES));
+    }
+    (subject, lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(item: &str, field: &str, from: Option<&str>, to: &str) -> HistoryEvent {
+        HistoryEvent {
+            at: Utc::now(),
+            item: item.to_string(),
+            field: field.to_string(),
+            from: from.map(str::to_string),
+            to: to.to_string(),
+            user: "alice".to_string(),
+            command: "complete-task US-003".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_message_lists_item_changes() {
+        let events = vec![event("US-003", "status", Some("InProgress"), "Done"), event("US-009", "created", None, "Todo")];
+        let (subject, body) = message("complete-task US-003", &events);
+        assert_eq!(subject, "env-coach complete-task US-003");
+        assert_eq!(body, "- US-003: status InProgress → Done\n- US-009: created");
+
+        let long = format!("add-requirement \"{}\"", "x".repeat(100));
+        let (subject, body) = message(&long, &vec![event("US-001", "effort", Some("3"), "5"); 25]);
+        assert_eq!(subject.chars().count(), MAX_SUBJECT);
+        assert!(subject.ends_with('…'));
+        assert_eq!(body.lines().count(), MAX_BODY_LINES + 1);
+        assert!(body.ends_with("and 5 more change(s)"));
+    }
+}
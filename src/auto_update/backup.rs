@@ -0,0 +1,94 @@
+// src/auto_update/backup.rs
+//! Per-file backups taken before a suggestion overwrites or extends an existing file:
+//! `.env-coach/backups/<path>.<timestamp>`, restored with `env-coach restore <file>`.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const BACKUP_DIR: &str = ".env-coach/backups";
+/// Backups kept per file; older ones are deleted when a new one is taken.
+pub const KEEP_PER_FILE: usize = 10;
+
+/// `path` relative to `root`, which is how backups are named.
+fn relative<'a>(root: &Path, path: &'a Path) -> &'a Path {
+    path.strip_prefix(root).unwrap_or(path)
+}
+
+/// A backup timestamp such as `20260101T120000123Z`.
+fn is_stamp(stamp: &str) -> bool {
+    stamp.len() == 19 && stamp.ends_with('Z') && stamp.as_bytes()[8] == b'T'
+}
+
+/// Backups of `file` (relative to `root`), oldest first.
+pub fn backups_of(root: &Path, file: &Path) -> Result<Vec<PathBuf>> {
+    let file = relative(root, file);
+    let Some(name) = file.file_name().and_then(|name| name.to_str()) else { return Ok(vec![]) };
+    let dir = root.join(BACKUP_DIR).join(file.parent().unwrap_or(Path::new("")));
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+    let prefix = format!("{}.", name);
+    let mut backups: Vec<PathBuf> = fs::read_dir(&dir)
+        .with_context(|| format!("Failed to read {:?}", dir))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            // Only `<name>.<timestamp>`, not other files starting with the same name
+            path.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_prefix(&prefix))
+                .is_some_and(is_stamp)
+        })
+        .collect();
+    // Timestamps sort chronologically
+    backups.sort();
+    Ok(backups)
+}
+
+/// Copy `path` into the backup directory if it exists, keeping the newest
+/// [`KEEP_PER_FILE`] backups of it. Returns the backup's path.
+pub fn backup_file(root: &Path, path: &Path) -> Result<Option<PathBuf>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let file = relative(root, path);
+    let stamp = Utc::now().format("%Y%m%dT%H%M%S%3fZ");
+    let name = file.file_name().and_then(|name| name.to_str()).unwrap_or("file");
+    let backup = root.join(BACKUP_DIR).join(file.parent().unwrap_or(Path::new(""))).join(format!("{}.{}", name, stamp));
+    if let Some(parent) = backup.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+    }
+    fs::copy(path, &backup).with_context(|| format!("Failed to back up {:?} to {:?}", path, backup))?;
+
+    let backups = backups_of(root, file)?;
+    for old in backups.iter().take(backups.len().saturating_sub(KEEP_PER_FILE)) {
+        fs::remove_file(old).with_context(|| format!("Failed to remove old backup {:?}", old))?;
+    }
+    Ok(Some(backup))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_file_keeps_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let file = root.join("src/main.rs");
+        assert_eq!(backup_file(root, &file).unwrap(), None);
+
+        fs::create_dir_all(root.join("src")).unwrap();
+        for version in 0..KEEP_PER_FILE + 2 {
+            fs::write(&file, format!("version {}", version)).unwrap();
+            let backup = backup_file(root, &file).unwrap().unwrap();
+            assert!(backup.starts_with(root.join(".env-coach/backups/src")));
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        fs::write(root.join(".env-coach/backups/src/main.rs.notes"), "").unwrap();
+
+        let backups = backups_of(root, Path::new("src/main.rs")).unwrap();
+        assert_eq!(backups.len(), KEEP_PER_FILE);
+        assert_eq!(fs::read_to_string(backups.last().unwrap()).unwrap(), format!("version {}", KEEP_PER_FILE + 1));
+        assert_eq!(fs::read_to_string(&backups[0]).unwrap(), "version 2");
+    }
+}
@@ -2,32 +2,23 @@
 use std::fs;
 use std::path::Path;
 use anyhow::{Context, Result, anyhow};
+use super::file_change::FileChange;
 use toml_edit::{DocumentMut, table, value as toml_value}; // Removed Item, Value
 
-/// Adds specified dependencies to the `Cargo.toml` file in the project root.
-/// `dependency_lines` should be an array of strings, where each string is a
-/// full dependency line as it would appear in Cargo.toml, e.g.,
-/// `clap = { version = "4.0", features = ["derive"] }` or `serde = "1.0"`.
-pub fn add_cargo_dependencies(project_root: &Path, dependency_lines: &[String]) -> Result<()> {
-    let cargo_toml_path = project_root.join("Cargo.toml");
-
-    if !cargo_toml_path.exists() {
-        return Err(anyhow!("Cargo.toml not found at {:?}", cargo_toml_path));
-    }
-
-    let content = fs::read_to_string(&cargo_toml_path) // Removed mut
-        .with_context(|| format!("Failed to read Cargo.toml from {:?}", cargo_toml_path))?;
-
+/// `content` (a Cargo.toml) with the dependencies added to `[dependencies]`, and the lines
+/// that were added. `dependency_lines` are full dependency lines as they would appear in
+/// Cargo.toml, e.g. `clap = { version = "4.0", features = ["derive"] }` or `serde = "1.0"`.
+fn with_dependencies(content: &str, dependency_lines: &[String]) -> Result<(String, Vec<String>)> {
     let mut doc = content.parse::<DocumentMut>()
-        .with_context(|| format!("Failed to parse Cargo.toml at {:?}", cargo_toml_path))?;
+        .context("Failed to parse Cargo.toml")?;
 
     let deps_table = doc
         .entry("dependencies")
-        .or_insert_with(|| table()) // Creates an empty table if [dependencies] doesn't exist
+        .or_insert_with(table) // Creates an empty table if [dependencies] doesn't exist
         .as_table_mut()
         .ok_or_else(|| anyhow!("[dependencies] section in Cargo.toml is not a table"))?;
 
-    let mut added_any = false;
+    let mut added = vec![];
     for line in dependency_lines {
         let line = line.trim();
         if line.is_empty() || line.starts_with('#') {
@@ -57,8 +48,7 @@ pub fn add_cargo_dependencies(project_root: &Path, dependency_lines: &[String])
             Ok(dummy_doc) => {
                 if let Some(item) = dummy_doc.get("temp_key").cloned() {
                     deps_table.insert(key, item);
-                    println!("✅ Added dependency to Cargo.toml: {}", line);
-                    added_any = true;
+                    added.push(line.to_string());
                 } else {
                     eprintln!("⚠️ Failed to parse value for dependency line (internal error): {}", line);
                 }
@@ -68,8 +58,7 @@ pub fn add_cargo_dependencies(project_root: &Path, dependency_lines: &[String])
                 if value_str.starts_with('"') && value_str.ends_with('"') {
                     let simple_value_str = value_str.trim_matches('"').to_string();
                     deps_table.insert(key, toml_value(simple_value_str));
-                    println!("✅ Added dependency to Cargo.toml (as simple string): {}", line);
-                    added_any = true;
+                    added.push(line.to_string());
                 } else {
                     eprintln!("⚠️ Failed to parse value for dependency line '{}': {}. It might be a malformed inline table or require specific formatting.", line, e);
                 }
@@ -77,13 +66,43 @@ pub fn add_cargo_dependencies(project_root: &Path, dependency_lines: &[String])
         }
     }
 
-    if added_any {
-        fs::write(&cargo_toml_path, doc.to_string())
-            .with_context(|| format!("Failed to write updated Cargo.toml to {:?}", cargo_toml_path))?;
-        println!("Successfully updated Cargo.toml.");
-    } else {
-        println!("No new dependencies were added to Cargo.toml.");
+    Ok((doc.to_string(), added))
+}
+
+fn read_manifest(project_root: &Path) -> Result<(std::path::PathBuf, String)> {
+    let cargo_toml_path = project_root.join("Cargo.toml");
+    if !cargo_toml_path.exists() {
+        return Err(anyhow!("Cargo.toml not found at {:?}", cargo_toml_path));
     }
+    let content = fs::read_to_string(&cargo_toml_path)
+        .with_context(|| format!("Failed to read Cargo.toml from {:?}", cargo_toml_path))?;
+    Ok((cargo_toml_path, content))
+}
+
+/// What `add_cargo_dependencies` would do to Cargo.toml, without writing it.
+pub fn plan_cargo_dependencies(project_root: &Path, dependency_lines: &[String]) -> Result<FileChange> {
+    let (path, content) = read_manifest(project_root)?;
+    let (after, _) = with_dependencies(&content, dependency_lines)
+        .with_context(|| format!("Failed to update {:?}", path))?;
+    Ok(FileChange { path, before: Some(content), after })
+}
+
+/// Adds specified dependencies to the `Cargo.toml` file in the project root, skipping
+/// ones that are already there.
+pub fn add_cargo_dependencies(project_root: &Path, dependency_lines: &[String]) -> Result<()> {
+    let (cargo_toml_path, content) = read_manifest(project_root)?;
+    let (updated, added) = with_dependencies(&content, dependency_lines)
+        .with_context(|| format!("Failed to update {:?}", cargo_toml_path))?;
 
+    if added.is_empty() {
+        println!("No new dependencies were added to Cargo.toml.");
+        return Ok(());
+    }
+    fs::write(&cargo_toml_path, updated)
+        .with_context(|| format!("Failed to write updated Cargo.toml to {:?}", cargo_toml_path))?;
+    for line in &added {
+        println!("✅ Added dependency to Cargo.toml: {}", line);
+    }
+    println!("Successfully updated Cargo.toml.");
     Ok(())
 }
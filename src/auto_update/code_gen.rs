@@ -1,7 +1,12 @@
 // src/auto_update/code_gen.rs
+use anyhow::Context;
 use std::fs;
 use std::path::Path;
 use crate::config::{Project, ProjectMeta}; // ProjectMeta for get_primary_language
+use crate::output::{self, Level, Topic};
+use super::file_change::FileChange;
+use super::{backup, imports, rust_ast};
+use super::llm_parsers::{LlmPatchSuggestion, LlmSourceCodeSuggestion, SuggestionAction};
 
 // --- Pure helper functions ---
 fn extract_language_from_line(line: &str) -> Option<String> {
@@ -111,7 +116,9 @@ pub fn generate_code_files(project: &Project, task_id: &str, llm_response: &str)
         }
         if !file_path.exists() {
             fs::write(file_path, code)?;
-            println!("✅ Generated: {}", filename);
+            if output::enabled(Topic::Files, Level::Normal) {
+                println!("✅ Generated: {}", filename);
+            }
         } else {
             println!("⚠️ File {} already exists - skipping generation", filename);
             println!("💡 To regenerate, delete the file and run assist-task again");
@@ -119,3 +126,327 @@ pub fn generate_code_files(project: &Project, task_id: &str, llm_response: &str)
     }
     Ok(())
 }
+
+// --- Applying structured source_code suggestions ---
+
+/// Resolve a suggestion's `target_file` under `root`, rejecting absolute paths and `..`
+/// so an LLM suggestion can never write outside the project.
+pub fn resolve_target(root: &Path, target_file: &str) -> anyhow::Result<std::path::PathBuf> {
+    let relative = Path::new(target_file);
+    if target_file.trim().is_empty()
+        || relative.is_absolute()
+        || relative.components().any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        anyhow::bail!("Refusing to touch '{}': target must be a relative path inside the project", target_file);
+    }
+    Ok(root.join(relative))
+}
+
+/// `content` appended to `existing`, each ending with a newline.
+fn appended(existing: &str, content: &str) -> String {
+    let mut out = existing.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(content);
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// What applying one structured suggestion under `root` would do, without writing anything.
+pub fn plan_source_suggestion(root: &Path, suggestion: &LlmSourceCodeSuggestion) -> anyhow::Result<FileChange> {
+    let target = &suggestion.target_file;
+    let path = resolve_target(root, target)?;
+    let before = fs::read_to_string(&path).ok();
+    let existing = before.as_deref().unwrap_or_default();
+    let after = match suggestion.action {
+        SuggestionAction::Create => {
+            if path.exists() {
+                anyhow::bail!("{} already exists; use a 'replace' or 'append_to_file' action instead", target);
+            }
+            suggestion.content.clone()
+        }
+        SuggestionAction::Replace => suggestion.content.clone(),
+        SuggestionAction::AppendToFile => appended(existing, &suggestion.content),
+        SuggestionAction::AddImport => {
+            let import = suggestion.import_statement.as_deref()
+                .filter(|i| !i.trim().is_empty())
+                .unwrap_or(suggestion.content.as_str());
+            if import.trim().is_empty() {
+                anyhow::bail!("add_import suggestion for {} has no import_statement", target);
+            }
+            imports::add_import(existing, import, &path)?
+        }
+        SuggestionAction::ReplaceFunction if path.extension().is_some_and(|ext| ext == "rs") => {
+            let function_name = suggestion.function_name.as_deref()
+                .filter(|name| !name.trim().is_empty())
+                .with_context(|| format!("replace_function suggestion for {} has no function_name", target))?;
+            rust_ast::replace_function(existing, function_name.trim(), &suggestion.content)
+                .with_context(|| format!("Failed to replace {} in {}", function_name, target))?
+        }
+        SuggestionAction::ReplaceFunction | SuggestionAction::AppendToFunction => {
+            anyhow::bail!(
+                "{:?} on function '{}' in {} is not automated yet; please apply it manually",
+                suggestion.action,
+                suggestion.function_name.as_deref().unwrap_or("?"),
+                target
+            )
+        }
+    };
+    Ok(FileChange { path, before, after })
+}
+
+/// Write `change`, backing up the file it overwrites first.
+fn write_with_backup(root: &Path, change: &FileChange) -> anyhow::Result<()> {
+    if !change.is_noop() {
+        backup::backup_file(root, &change.path)?;
+    }
+    change.write()
+}
+
+/// Apply one structured suggestion to the files under `root`.
+/// Returns a short description of what was done.
+pub fn apply_source_suggestion(root: &Path, suggestion: &LlmSourceCodeSuggestion) -> anyhow::Result<String> {
+    write_with_backup(root, &plan_source_suggestion(root, suggestion)?)?;
+    let target = &suggestion.target_file;
+    Ok(match suggestion.action {
+        SuggestionAction::Create => format!("Created {}", target),
+        SuggestionAction::Replace => format!("Replaced {}", target),
+        SuggestionAction::AddImport => format!("Added import to {}", target),
+        SuggestionAction::ReplaceFunction => format!("Replaced {} in {}", suggestion.function_name.as_deref().unwrap_or("function"), target),
+        _ => format!("Appended to {}", target),
+    })
+}
+
+// --- Applying patch suggestions ---
+
+/// Context lines that may be dropped from each end of a hunk that does not match as
+/// written, like `patch --fuzz=2`.
+const MAX_FUZZ: usize = 2;
+
+#[derive(Debug, Clone, PartialEq)]
+enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+#[derive(Debug, Default)]
+struct Hunk {
+    /// 1-based start line from the `@@ -N` header, only a hint for where to look
+    old_start: Option<usize>,
+    lines: Vec<HunkLine>,
+}
+
+impl Hunk {
+    fn old_lines(&self) -> Vec<&str> {
+        self.lines.iter().filter_map(|line| match line {
+            HunkLine::Context(text) | HunkLine::Remove(text) => Some(text.as_str()),
+            HunkLine::Add(_) => None,
+        }).collect()
+    }
+
+    /// The hunk without up to `fuzz` context lines at either end.
+    fn trimmed(&self, fuzz: usize) -> Hunk {
+        let lead = self.lines.iter().take(fuzz).take_while(|line| matches!(line, HunkLine::Context(_))).count();
+        let trail = self.lines[lead..].iter().rev().take(fuzz).take_while(|line| matches!(line, HunkLine::Context(_))).count();
+        Hunk {
+            old_start: self.old_start.map(|start| start + lead),
+            lines: self.lines[lead..self.lines.len() - trail].to_vec(),
+        }
+    }
+}
+
+/// Hunks of a unified diff. File headers are skipped, and since LLMs often get the
+/// `@@` line counts wrong, those are ignored.
+fn parse_hunks(diff: &str) -> anyhow::Result<Vec<Hunk>> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    for line in diff.lines() {
+        if line.starts_with("@@") {
+            let old_start = line.trim_start_matches('@').trim_start().strip_prefix('-')
+                .and_then(|rest| rest.split(|c: char| !c.is_ascii_digit()).next())
+                .and_then(|digits| digits.parse().ok());
+            hunks.push(Hunk { old_start, lines: vec![] });
+            continue;
+        }
+        let Some(hunk) = hunks.last_mut() else { continue }; // `---`/`+++` and other headers
+        if line.starts_with('\\') {
+            continue; // "\ No newline at end of file"
+        }
+        let hunk_line = match line.chars().next() {
+            Some('+') => HunkLine::Add(line[1..].to_string()),
+            Some('-') => HunkLine::Remove(line[1..].to_string()),
+            Some(' ') => HunkLine::Context(line[1..].to_string()),
+            // Blank context lines often lose their leading space
+            _ => HunkLine::Context(line.to_string()),
+        };
+        hunk.lines.push(hunk_line);
+    }
+    // Trailing blank lines are usually an artefact of how the diff was quoted
+    for hunk in &mut hunks {
+        while hunk.lines.last() == Some(&HunkLine::Context(String::new())) {
+            hunk.lines.pop();
+        }
+    }
+    hunks.retain(|hunk| !hunk.lines.is_empty());
+    if hunks.is_empty() {
+        anyhow::bail!("The patch contains no hunks (expected '@@ -N,M +N,M @@' sections)");
+    }
+    Ok(hunks)
+}
+
+/// Where `old` occurs in `lines`, preferring the position closest to `hint`.
+fn find_lines(lines: &[String], old: &[&str], hint: usize, same: fn(&str, &str) -> bool) -> Option<usize> {
+    if old.len() > lines.len() {
+        return None;
+    }
+    (0..=lines.len() - old.len())
+        .filter(|&pos| old.iter().zip(&lines[pos..]).all(|(want, have)| same(want, have)))
+        .min_by_key(|&pos| pos.abs_diff(hint))
+}
+
+/// Apply a unified diff to `original`. Each hunk is looked for near its `@@` line number
+/// (shifted by earlier hunks), first as written, then ignoring indentation and trailing
+/// whitespace, then with up to [`MAX_FUZZ`] context lines dropped from its ends. Context
+/// lines keep the file's own text.
+pub fn apply_patch(original: &str, diff: &str) -> anyhow::Result<String> {
+    let exact: fn(&str, &str) -> bool = |a, b| a == b;
+    let loose: fn(&str, &str) -> bool = |a, b| a.trim() == b.trim();
+
+    let mut lines: Vec<String> = original.lines().map(String::from).collect();
+    let mut shift: isize = 0;
+    for (idx, hunk) in parse_hunks(diff)?.iter().enumerate() {
+        let found = (0..=MAX_FUZZ).find_map(|fuzz| {
+            let hunk = hunk.trimmed(fuzz);
+            let hint = hunk.old_start.map_or(0, |start| (start as isize - 1 + shift).max(0) as usize);
+            let old = hunk.old_lines();
+            if old.is_empty() {
+                // Pure insertion: there is nothing to match, so trust the line number
+                let pos = if hunk.old_start.is_some() { hint.min(lines.len()) } else { lines.len() };
+                return Some((pos, hunk));
+            }
+            [exact, loose].into_iter()
+                .find_map(|same| find_lines(&lines, &old, hint, same))
+                .map(|pos| (pos, hunk))
+        });
+        let Some((pos, hunk)) = found else {
+            let first = hunk.old_lines().first().map(|line| line.trim().to_string()).unwrap_or_default();
+            anyhow::bail!("Hunk {} does not match the file (it expects a line '{}')", idx + 1, first);
+        };
+
+        let mut replacement = Vec::new();
+        let mut cursor = pos;
+        for line in &hunk.lines {
+            match line {
+                HunkLine::Context(_) => {
+                    replacement.push(lines[cursor].clone());
+                    cursor += 1;
+                }
+                HunkLine::Remove(_) => cursor += 1,
+                HunkLine::Add(text) => replacement.push(text.clone()),
+            }
+        }
+        shift += replacement.len() as isize - (cursor - pos) as isize;
+        lines.splice(pos..cursor, replacement);
+    }
+
+    let mut out = lines.join("\n");
+    if !lines.is_empty() && (original.is_empty() || original.ends_with('\n')) {
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// What applying a patch suggestion under `root` would do, without writing anything.
+pub fn plan_patch_suggestion(root: &Path, suggestion: &LlmPatchSuggestion) -> anyhow::Result<FileChange> {
+    let path = resolve_target(root, &suggestion.target_file)?;
+    let before = fs::read_to_string(&path).ok();
+    let after = apply_patch(before.as_deref().unwrap_or_default(), &suggestion.diff)
+        .with_context(|| format!("Failed to patch {}", suggestion.target_file))?;
+    Ok(FileChange { path, before, after })
+}
+
+/// Apply a patch suggestion to the files under `root`.
+/// Returns a short description of what was done.
+pub fn apply_patch_suggestion(root: &Path, suggestion: &LlmPatchSuggestion) -> anyhow::Result<String> {
+    write_with_backup(root, &plan_patch_suggestion(root, suggestion)?)?;
+    Ok(format!("Patched {}", suggestion.target_file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn suggestion(action: SuggestionAction, target: &str, content: &str) -> LlmSourceCodeSuggestion {
+        LlmSourceCodeSuggestion {
+            target_file: target.to_string(),
+            action,
+            content: content.to_string(),
+            function_name: None,
+            import_statement: None,
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_resolve_target_rejects_escaping_paths() {
+        let root = Path::new("/project");
+        assert!(resolve_target(root, "src/main.rs").is_ok());
+        assert!(resolve_target(root, "../outside.rs").is_err());
+        assert!(resolve_target(root, "/etc/passwd").is_err());
+        assert!(resolve_target(root, "").is_err());
+    }
+
+    #[test]
+    fn test_apply_source_suggestion_file_actions() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        apply_source_suggestion(root, &suggestion(SuggestionAction::Create, "src/util.rs", "pub fn a() {}")).unwrap();
+        assert!(apply_source_suggestion(root, &suggestion(SuggestionAction::Create, "src/util.rs", "")).is_err(), "Create must not overwrite");
+
+        apply_source_suggestion(root, &suggestion(SuggestionAction::AppendToFile, "src/util.rs", "pub fn b() {}")).unwrap();
+        assert_eq!(fs::read_to_string(root.join("src/util.rs")).unwrap(), "pub fn a() {}\npub fn b() {}\n");
+
+        let mut import = suggestion(SuggestionAction::AddImport, "src/util.rs", "");
+        import.import_statement = Some("use std::fs;".to_string());
+        apply_source_suggestion(root, &import).unwrap();
+        apply_source_suggestion(root, &import).unwrap();
+        assert_eq!(fs::read_to_string(root.join("src/util.rs")).unwrap().matches("use std::fs;").count(), 1);
+
+        apply_source_suggestion(root, &suggestion(SuggestionAction::Replace, "src/util.rs", "// new")).unwrap();
+        assert_eq!(fs::read_to_string(root.join("src/util.rs")).unwrap(), "// new");
+
+        assert!(apply_source_suggestion(root, &suggestion(SuggestionAction::ReplaceFunction, "src/util.rs", "fn a() {}")).is_err());
+
+        apply_source_suggestion(root, &suggestion(SuggestionAction::Replace, "src/util.rs", "fn a() -> u8 {\n    1\n}\n")).unwrap();
+        let mut replace_fn = suggestion(SuggestionAction::ReplaceFunction, "src/util.rs", "2");
+        replace_fn.function_name = Some("a".to_string());
+        apply_source_suggestion(root, &replace_fn).unwrap();
+        assert_eq!(fs::read_to_string(root.join("src/util.rs")).unwrap(), "fn a() -> u8 {\n    2\n}\n");
+        replace_fn.target_file = "app.py".to_string();
+        assert!(apply_source_suggestion(root, &replace_fn).is_err(), "Only Rust functions are replaced automatically");
+    }
+
+    #[test]
+    fn test_apply_patch() {
+        let original = "fn main() {\n    let a = 1;\n    let b = 2;\n    println!(\"{}\", a + b);\n}\n";
+        let diff = "--- a/src/main.rs\n+++ b/src/main.rs\n@@ -2,3 +2,3 @@\n     let a = 1;\n-    let b = 2;\n+    let b = 3;\n     println!(\"{}\", a + b);\n";
+        let expected = original.replace("let b = 2", "let b = 3");
+        assert_eq!(apply_patch(original, diff).unwrap(), expected);
+
+        // Wrong line numbers and lost indentation in the context still apply
+        let sloppy = "@@ -40,2 +40,2 @@\n let a = 1;\n-    let b = 2;\n+    let b = 3;\n";
+        assert_eq!(apply_patch(original, sloppy).unwrap(), expected);
+
+        // A context line that no longer matches is dropped as fuzz
+        let stale = "@@ -1,3 +1,3 @@\n fn start() {\n     let a = 1;\n-    let b = 2;\n+    let b = 3;\n";
+        assert_eq!(apply_patch(original, stale).unwrap(), expected);
+
+        assert!(apply_patch(original, "@@ -1 +1 @@\n-fn other() {\n+fn main() {\n").is_err());
+        assert!(apply_patch(original, "just some text").is_err());
+    }
+}
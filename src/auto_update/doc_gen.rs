@@ -4,55 +4,54 @@ use std::fs;
 use std::path::Path;
 use crate::config::{Project, BacklogItem};
 use chrono::Utc; // Ensure chrono is imported for Utc::now() if used here, or rely on Project's created times.
+use super::file_change::FileChange;
 
-fn update_readme(project: &Project, task: &BacklogItem) -> anyhow::Result<()> { // project is used
-    let readme_path = "README.md";
-    let mut content = if Path::new(readme_path).exists() {
-        fs::read_to_string(readme_path)?
+fn read_existing(path: &str) -> anyhow::Result<Option<String>> {
+    if Path::new(path).exists() {
+        Ok(Some(fs::read_to_string(path)?))
     } else {
+        Ok(None)
+    }
+}
+
+fn readme_change(project: &Project, task: &BacklogItem) -> anyhow::Result<FileChange> { // project is used
+    let readme_path = "README.md";
+    let before = read_existing(readme_path)?;
+    let mut content = before.clone().unwrap_or_else(|| {
         format!("# {}\n\n## Features\n\n", project.meta.name) // Use project.meta.name
-    };
+    });
 
     let feature_line = format!("- ✅ {} ({})\n", task.title, task.id);
 
-    if content.contains(&task.id) { // Avoid duplicates
-        return Ok(());
+    if !content.contains(&task.id) { // Avoid duplicates
+        if let Some(features_pos) = content.find("## Features") {
+            let insert_pos = features_pos + content[features_pos..].find('\n').unwrap_or(0) + 1;
+            content.insert_str(insert_pos, &feature_line);
+        } else {
+            content.push_str(&format!("\n## Features\n{}", feature_line));
+        }
     }
 
-    if let Some(features_pos) = content.find("## Features") {
-        let insert_pos = features_pos + content[features_pos..].find('\n').unwrap_or(0) + 1;
-        content.insert_str(insert_pos, &feature_line);
-    } else {
-        content.push_str(&format!("\n## Features\n{}", feature_line));
-    }
-
-    fs::write(readme_path, content)?;
-    Ok(())
+    Ok(FileChange { path: readme_path.into(), before, after: content })
 }
 
-fn update_changelog(_project: &Project, task: &BacklogItem) -> anyhow::Result<()> { // _project
+fn changelog_change(_project: &Project, task: &BacklogItem) -> anyhow::Result<FileChange> { // _project
     let changelog_path = "CHANGELOG.md";
-    let mut content = if Path::new(changelog_path).exists() {
-        fs::read_to_string(changelog_path)?
-    } else {
-        "# Changelog\n\n".to_string()
-    };
+    let before = read_existing(changelog_path)?;
+    let mut content = before.clone().unwrap_or_else(|| "# Changelog\n\n".to_string());
 
     let today = Utc::now().format("%Y-%m-%d"); // task.completed_at would be better if available
     let entry = format!("## {} - {}\n- Completed: {} ({})\n\n", today, task.title, task.story, task.id);
 
-    if content.contains(&task.id) { // Avoid duplicates
-        return Ok(());
-    }
-
-    if let Some(first_newline) = content.find('\n') {
-        content.insert_str(first_newline + 1, &entry);
-    } else {
-        content.push_str(&entry);
+    if !content.contains(&task.id) { // Avoid duplicates
+        if let Some(first_newline) = content.find('\n') {
+            content.insert_str(first_newline + 1, &entry);
+        } else {
+            content.push_str(&entry);
+        }
     }
 
-    fs::write(changelog_path, content)?;
-    Ok(())
+    Ok(FileChange { path: changelog_path.into(), before, after: content })
 }
 
 /// Placeholder for future, more comprehensive documentation updates.
@@ -62,15 +61,26 @@ pub fn update_documentation(_project: &Project, _llm_response: &str) -> anyhow::
     Ok(())
 }
 
+/// README.md and CHANGELOG.md as they would be after completing `task_id`; empty when the
+/// task does not exist.
+pub fn plan_docs_for_task_completion(project: &Project, task_id: &str) -> anyhow::Result<Vec<FileChange>> {
+    match project.backlog.iter().find(|item| item.id == task_id) {
+        Some(task) => Ok(vec![readme_change(project, task)?, changelog_change(project, task)?]),
+        None => Ok(vec![]),
+    }
+}
+
 /// Main function for this module, called by AutoUpdater.
 pub fn update_docs_for_task_completion(project: &Project, task_id: &str, _llm_response: &str) -> anyhow::Result<()> {
     println!("📝 Auto-updating documentation for completed task {}...", task_id);
-    if let Some(task) = project.backlog.iter().find(|item| item.id == task_id) {
-        update_readme(project, task)?;
-        update_changelog(project, task)?;
-        println!("✅ Documentation auto-updated for {}", task_id);
-    } else {
+    let changes = plan_docs_for_task_completion(project, task_id)?;
+    if changes.is_empty() {
         println!("⚠️ Task {} not found for documentation update.", task_id);
+        return Ok(());
+    }
+    for change in changes.iter().filter(|change| !change.is_noop()) {
+        change.write()?;
     }
+    println!("✅ Documentation auto-updated for {}", task_id);
     Ok(())
 }
@@ -0,0 +1,145 @@
+// src/auto_update/duplicates.rs
+//! Near-duplicate detection for stories generated from a requirement, so `add-requirement`
+//! does not silently create a twin of an item already in the backlog. Items are compared by
+//! the words of their title and story, or by Ollama embeddings when `duplicates.embeddings`
+//! is set in project.json.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::config::{BacklogItem, ItemType, Project, Status};
+use crate::ollama;
+
+/// Word-overlap score from which two items count as duplicates.
+pub const TEXT_THRESHOLD: f32 = 0.5;
+/// Embedding similarity from which two items count as duplicates.
+pub const EMBEDDING_THRESHOLD: f32 = 0.9;
+
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "are", "with", "that", "this", "want", "can", "from", "into", "not",
+    "have", "has", "user", "users", "able",
+];
+
+/// Embedding vectors keyed by `item_text`.
+pub type Embeddings = HashMap<String, Vec<f32>>;
+
+/// The text an item is compared (and embedded) by.
+pub fn item_text(title: &str, story: &str) -> String {
+    format!("{}\n{}", title, story)
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.len() >= 3 && !STOPWORDS.contains(&word.as_str()))
+        .collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+/// How alike two items read (0.0 - 1.0): title overlap and overall overlap count equally,
+/// since twins usually share a title even when the stories are worded differently.
+pub fn text_similarity(a: (&str, &str), b: (&str, &str)) -> f32 {
+    let title = jaccard(&words(a.0), &words(b.0));
+    let whole = jaccard(&words(&item_text(a.0, a.1)), &words(&item_text(b.0, b.1)));
+    0.5 * title + 0.5 * whole
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 { 0.0 } else { dot / (norm_a * norm_b) }
+}
+
+/// Open items a new story may duplicate: everything not done, epics aside.
+pub fn candidates(project: &Project) -> impl Iterator<Item = &BacklogItem> {
+    project.backlog.iter()
+        .filter(|item| !matches!(item.status, Status::Done) && !matches!(item.item_type, ItemType::Epic))
+}
+
+/// Embeddings of the new stories and of every open item, when the project asks for them.
+/// `None` (with a warning) when the model cannot produce embeddings, so the check falls
+/// back to comparing words.
+pub async fn embed_for_check(project: &Project, new_stories: &[(String, String)]) -> Option<Embeddings> {
+    if !project.meta.duplicates.as_ref().is_some_and(|config| config.embeddings) || new_stories.is_empty() {
+        return None;
+    }
+    let texts: HashSet<String> = new_stories.iter()
+        .map(|(title, story)| item_text(title, story))
+        .chain(candidates(project).map(|item| item_text(&item.title, &item.story)))
+        .collect();
+    let mut embeddings = Embeddings::new();
+    for text in texts {
+        match ollama::generate_embedding(project.llm(), &text).await {
+            Ok(embedding) => {
+                embeddings.insert(text, embedding);
+            }
+            Err(e) => {
+                println!("⚠️  Embeddings unavailable ({}); comparing stories by their words.", e);
+                return None;
+            }
+        }
+    }
+    Some(embeddings)
+}
+
+/// The open item most like a new story with `title` and `story`, with its score, if it is
+/// at least `threshold` alike. Embeddings are used when both texts have one.
+pub fn find_duplicate<'a>(
+    project: &'a Project,
+    title: &str,
+    story: &str,
+    embeddings: Option<&Embeddings>,
+    threshold: Option<f32>,
+) -> Option<(&'a BacklogItem, f32)> {
+    let new_embedding = embeddings.and_then(|embeddings| embeddings.get(&item_text(title, story)));
+    candidates(project)
+        .map(|item| {
+            let existing = embeddings.and_then(|embeddings| embeddings.get(&item_text(&item.title, &item.story)));
+            let (score, default_threshold) = match (new_embedding, existing) {
+                (Some(new), Some(existing)) => (cosine_similarity(new, existing), EMBEDDING_THRESHOLD),
+                _ => (text_similarity((title, story), (&item.title, &item.story)), TEXT_THRESHOLD),
+            };
+            (item, score, threshold.unwrap_or(default_threshold))
+        })
+        .filter(|(_, score, threshold)| score >= threshold)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(item, score, _)| (item, score))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_duplicate_by_text() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog = vec![
+            BacklogItem::test("US-001").with_title("User login").with_story("As a user I want to log in with email and password"),
+            BacklogItem::test("US-002").with_title("Export orders").with_story("As an admin I want to export orders as CSV").with_status(Status::Done),
+        ];
+
+        let (twin, score) = find_duplicate(&project, "User login", "As a visitor I want to log in with my email and password", None, None).unwrap();
+        assert_eq!(twin.id, "US-001");
+        assert!(score > TEXT_THRESHOLD);
+        assert!(find_duplicate(&project, "Password reset", "As a user I want to reset my password by email", None, None).is_none());
+        // Done items are not offered for merging
+        assert!(find_duplicate(&project, "Export orders", "As an admin I want to export orders as CSV", None, None).is_none());
+
+        let embeddings = Embeddings::from([
+            (item_text("Sign in", "Log me in"), vec![1.0, 0.1]),
+            (item_text("User login", "As a user I want to log in with email and password"), vec![1.0, 0.0]),
+        ]);
+        assert_eq!(find_duplicate(&project, "Sign in", "Log me in", Some(&embeddings), None).map(|(item, _)| item.id.as_str()), Some("US-001"));
+        assert!(find_duplicate(&project, "Sign in", "Log me in", Some(&embeddings), Some(0.999)).is_none());
+    }
+}
@@ -0,0 +1,77 @@
+// src/auto_update/file_change.rs
+//! A planned edit to one file, so it can either be written or shown as a diff (`--dry-run`).
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+/// Diff lines shown per file before the preview is cut short.
+const MAX_PREVIEW_LINES: usize = 200;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    /// Current content; `None` when the file does not exist yet
+    pub before: Option<String>,
+    pub after: String,
+}
+
+impl FileChange {
+    pub fn is_noop(&self) -> bool {
+        self.before.as_deref() == Some(self.after.as_str())
+    }
+
+    pub fn write(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+        }
+        fs::write(&self.path, &self.after).with_context(|| format!("Failed to write {:?}", self.path))
+    }
+
+    /// The change as a unified diff, without the `---`/`+++` header.
+    pub fn diff(&self) -> String {
+        let patch = diffy::create_patch(self.before.as_deref().unwrap_or_default(), &self.after).to_string();
+        patch.lines().skip(2).map(|line| format!("{}\n", line)).collect()
+    }
+
+    /// Print what writing the change would do, for `--dry-run`.
+    pub fn print_preview(&self) {
+        let path = self.path.display();
+        if self.is_noop() {
+            println!("🔍 Dry run: {} would not change", path);
+            return;
+        }
+        match self.before {
+            Some(_) => println!("🔍 Dry run: {} would be modified:", path),
+            None => println!("🔍 Dry run: {} would be created:", path),
+        }
+        let diff = self.diff();
+        for line in diff.lines().take(MAX_PREVIEW_LINES) {
+            println!("   {}", line);
+        }
+        let total = diff.lines().count();
+        if total > MAX_PREVIEW_LINES {
+            println!("   ... ({} more diff lines)", total - MAX_PREVIEW_LINES);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_and_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let change = FileChange {
+            path: dir.path().join("src/lib.rs"),
+            before: Some("fn a() {}\nfn b() {}\n".to_string()),
+            after: "fn a() {}\nfn c() {}\n".to_string(),
+        };
+        assert!(!change.is_noop());
+        assert_eq!(change.diff(), "@@ -1,2 +1,2 @@\n fn a() {}\n-fn b() {}\n+fn c() {}\n");
+
+        change.write().unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(), "fn a() {}\nfn c() {}\n");
+    }
+}
@@ -0,0 +1,294 @@
+// src/auto_update/imports.rs
+//! Adding an import where a person would put it: next to the imports of the same group
+//! (standard library, external, local), in sorted order, and only if it is not there yet.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use syn::spanned::Spanned;
+use syn::{Item, ItemUse, UseTree};
+
+/// An import already in the file, as 0-based line numbers.
+struct ExistingImport {
+    first_line: usize,
+    last_line: usize,
+    group: u8,
+    key: String,
+}
+
+/// Python modules common enough to be worth grouping as standard library.
+const PYTHON_STDLIB: &[&str] = &[
+    "abc", "argparse", "asyncio", "base64", "collections", "contextlib", "copy", "csv", "dataclasses",
+    "datetime", "decimal", "enum", "functools", "glob", "hashlib", "http", "importlib", "inspect", "io",
+    "itertools", "json", "logging", "math", "os", "pathlib", "pickle", "random", "re", "shutil",
+    "socket", "sqlite3", "string", "subprocess", "sys", "tempfile", "threading", "time", "typing",
+    "unittest", "urllib", "uuid", "warnings",
+];
+
+/// `source` with `import` added, or unchanged if an equivalent import exists. Rust and
+/// Python files get grouped, sorted placement; other files get the line appended.
+pub fn add_import(source: &str, import: &str, path: &Path) -> Result<String> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("rs") => add_rust_import(source, import),
+        Some("py") => Ok(add_python_import(source, import)),
+        _ => Ok(append_line(source, import)),
+    }
+}
+
+fn append_line(source: &str, line: &str) -> String {
+    if source.lines().any(|existing| existing.trim() == line.trim()) {
+        return source.to_string();
+    }
+    let mut out = source.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(line.trim());
+    out.push('\n');
+    out
+}
+
+/// Place `import` among `existing`: sorted within its group, or as a new group (set off by
+/// blank lines) between lower and higher groups. `header_end` is where the first import
+/// goes when there is none.
+fn insert_import(source: &str, import: &str, group: u8, key: &str, existing: &[ExistingImport], header_end: usize) -> String {
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+    let same_group: Vec<&ExistingImport> = existing.iter().filter(|i| i.group == group).collect();
+    let new_lines: Vec<String>;
+    let at;
+    if !same_group.is_empty() {
+        at = same_group.iter().find(|i| i.key.as_str() > key).map_or(same_group.last().unwrap().last_line + 1, |i| i.first_line);
+        new_lines = vec![import.to_string()];
+    } else if let Some(before) = existing.iter().rfind(|i| i.group < group) {
+        at = before.last_line + 1;
+        new_lines = vec![String::new(), import.to_string()];
+    } else if let Some(after) = existing.iter().find(|i| i.group > group) {
+        at = after.first_line;
+        new_lines = vec![import.to_string(), String::new()];
+    } else {
+        at = header_end.min(lines.len());
+        let mut block = vec![];
+        if at > 0 && !lines[at - 1].trim().is_empty() {
+            block.push(String::new());
+        }
+        block.push(import.to_string());
+        if lines.get(at).is_some_and(|next| !next.trim().is_empty()) {
+            block.push(String::new());
+        }
+        new_lines = block;
+    }
+    lines.splice(at..at, new_lines);
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+// --- Rust ---
+
+/// Every path a `use` tree brings into scope, e.g. `std::io::{self, Read}` gives
+/// `std::io` and `std::io::Read`.
+fn use_paths(tree: &UseTree, prefix: &str, out: &mut Vec<String>) {
+    let join = |name: &str| if prefix.is_empty() { name.to_string() } else { format!("{}::{}", prefix, name) };
+    match tree {
+        UseTree::Path(path) => use_paths(&path.tree, &join(&path.ident.to_string()), out),
+        UseTree::Name(name) if name.ident == "self" => out.push(prefix.to_string()),
+        UseTree::Name(name) => out.push(join(&name.ident.to_string())),
+        UseTree::Rename(rename) => out.push(format!("{} as {}", join(&rename.ident.to_string()), rename.rename)),
+        UseTree::Glob(_) => out.push(join("*")),
+        UseTree::Group(group) => group.items.iter().for_each(|item| use_paths(item, prefix, out)),
+    }
+}
+
+fn rust_group(item: &ItemUse) -> u8 {
+    let root = match &item.tree {
+        UseTree::Path(path) => path.ident.to_string(),
+        UseTree::Name(name) => name.ident.to_string(),
+        _ => String::new(),
+    };
+    match root.as_str() {
+        "std" | "core" | "alloc" => 0,
+        "crate" | "self" | "super" => 2,
+        _ => 1,
+    }
+}
+
+fn rust_key(item: &ItemUse) -> String {
+    let mut paths = vec![];
+    use_paths(&item.tree, "", &mut paths);
+    paths.join(",")
+}
+
+fn add_rust_import(source: &str, import: &str) -> Result<String> {
+    let import = import.trim();
+    let import = if import.ends_with(';') { import.to_string() } else { format!("{};", import) };
+    let new_use: ItemUse = syn::parse_str(&import).with_context(|| format!("'{}' is not a Rust use statement", import))?;
+    let Ok(file) = syn::parse_file(source) else {
+        // Half-written files still get the import, just not placed as carefully
+        return Ok(append_line(source, &import));
+    };
+
+    let mut imported = HashSet::new();
+    let mut existing = vec![];
+    for item in &file.items {
+        if let Item::Use(item) = item {
+            let mut paths = vec![];
+            use_paths(&item.tree, "", &mut paths);
+            imported.extend(paths);
+            existing.push(ExistingImport {
+                first_line: item.span().start().line - 1,
+                last_line: item.span().end().line - 1,
+                group: rust_group(item),
+                key: rust_key(item),
+            });
+        }
+    }
+    let mut wanted = vec![];
+    use_paths(&new_use.tree, "", &mut wanted);
+    let covered = |path: &String| {
+        imported.contains(path)
+            || path.rsplit_once("::").is_some_and(|(parent, _)| imported.contains(&format!("{}::*", parent)))
+    };
+    if wanted.iter().all(covered) {
+        return Ok(source.to_string());
+    }
+
+    // Inner attributes and `//!` docs stay above the imports
+    let header_end = file.attrs.iter().map(|attr| attr.span().end().line).max().unwrap_or(0);
+    Ok(insert_import(source, &import, rust_group(&new_use), &rust_key(&new_use), &existing, header_end))
+}
+
+// --- Python ---
+
+/// (module, name) pairs an import binds: `from a import b, c as d` gives `(a, b)` and
+/// `(a, c as d)`; `import x` gives `("", x)`.
+fn python_names(statement: &str) -> Vec<(String, String)> {
+    let statement = statement.replace(['(', ')', '\\', '\n'], " ");
+    let statement = statement.split('#').next().unwrap_or_default().trim().to_string();
+    let split = |names: &str| names.split(',').map(|name| name.split_whitespace().collect::<Vec<_>>().join(" ")).filter(|name| !name.is_empty()).collect::<Vec<_>>();
+    if let Some(rest) = statement.strip_prefix("from ") {
+        let Some((module, names)) = rest.split_once(" import ") else { return vec![] };
+        split(names).into_iter().map(|name| (module.trim().to_string(), name)).collect()
+    } else if let Some(names) = statement.strip_prefix("import ") {
+        split(names).into_iter().map(|name| (String::new(), name)).collect()
+    } else {
+        vec![]
+    }
+}
+
+fn python_group(statement: &str) -> u8 {
+    let module = statement.trim_start_matches("from ").trim_start_matches("import ");
+    let root = module.split(|c: char| c == '.' || c.is_whitespace() || c == ',').next().unwrap_or_default();
+    if module.starts_with('.') {
+        3
+    } else if root == "__future__" {
+        0
+    } else if PYTHON_STDLIB.contains(&root) {
+        1
+    } else {
+        2
+    }
+}
+
+fn is_python_import(line: &str) -> bool {
+    line.starts_with("import ") || (line.starts_with("from ") && line.contains(" import"))
+}
+
+/// Top-level imports at the head of a Python file, and where the header (shebang,
+/// encoding line, module docstring) ends.
+fn python_imports(lines: &[&str]) -> (Vec<ExistingImport>, usize) {
+    let mut idx = 0;
+    while idx < lines.len() && lines[idx].starts_with('#') {
+        idx += 1;
+    }
+    let header_end = match lines.get(idx).map(|line| line.trim_start()) {
+        Some(line) if line.starts_with("\"\"\"") || line.starts_with("'''") => {
+            let quote = &line[..3];
+            let closes_same_line = line.len() > 3 && line[3..].contains(quote);
+            let end = if closes_same_line { idx } else { (idx + 1..lines.len()).find(|&i| lines[i].contains(quote)).unwrap_or(idx) };
+            end + 1
+        }
+        _ => idx,
+    };
+
+    let mut existing = vec![];
+    let mut idx = header_end;
+    while idx < lines.len() {
+        let line = lines[idx];
+        if is_python_import(line) {
+            let first = idx;
+            let mut statement = line.to_string();
+            while (statement.contains('(') && !statement.contains(')')) || statement.trim_end().ends_with('\\') {
+                idx += 1;
+                let Some(next) = lines.get(idx) else { break };
+                statement.push('\n');
+                statement.push_str(next);
+            }
+            existing.push(ExistingImport { first_line: first, last_line: idx, group: python_group(&statement), key: statement.trim().to_string() });
+        } else if !line.trim().is_empty() && !line.starts_with('#') {
+            break; // First statement that is not an import ends the import section
+        }
+        idx += 1;
+    }
+    (existing, header_end)
+}
+
+fn add_python_import(source: &str, import: &str) -> String {
+    let import = import.trim();
+    let lines: Vec<&str> = source.lines().collect();
+    let (existing, header_end) = python_imports(&lines);
+
+    let imported: HashSet<(String, String)> = existing.iter()
+        .flat_map(|i| python_names(&lines[i.first_line..=i.last_line].join("\n")))
+        .collect();
+    let wanted = python_names(import);
+    if !wanted.is_empty() && wanted.iter().all(|name| imported.contains(name)) {
+        return source.to_string();
+    }
+    insert_import(source, import, python_group(import), import, &existing, header_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_import_grouped_and_deduped() {
+        let source = "//! Config loading.\n\nuse std::fs;\nuse std::path::Path;\n\nuse serde::Deserialize;\n\nuse crate::config::Project;\n\nfn main() {}\n";
+        let path = Path::new("src/lib.rs");
+
+        let result = add_import(source, "use std::io::Read", path).unwrap();
+        assert_eq!(result, source.replace("use std::fs;\n", "use std::fs;\nuse std::io::Read;\n"));
+
+        let result = add_import(source, "use anyhow::Result;", path).unwrap();
+        assert_eq!(result, source.replace("use serde::Deserialize;\n", "use anyhow::Result;\nuse serde::Deserialize;\n"));
+
+        assert_eq!(add_import(source, "use std::path::Path;", path).unwrap(), source);
+        let grouped = "use std::io::{self, Read};\nuse std::fmt::*;\n";
+        assert_eq!(add_import(grouped, "use std::io::Read;", path).unwrap(), grouped);
+        assert_eq!(add_import(grouped, "use std::fmt::Display;", path).unwrap(), grouped);
+    }
+
+    #[test]
+    fn test_rust_import_without_existing_imports() {
+        let path = Path::new("src/lib.rs");
+        let source = "//! Module docs.\n#![allow(dead_code)]\nfn main() {}\n";
+        assert_eq!(add_import(source, "use std::fs;", path).unwrap(), "//! Module docs.\n#![allow(dead_code)]\n\nuse std::fs;\n\nfn main() {}\n");
+        assert_eq!(add_import("fn main() {}\n", "use std::fs;", path).unwrap(), "use std::fs;\n\nfn main() {}\n");
+        assert!(add_import("fn main() {}\n", "import os", path).is_err());
+    }
+
+    #[test]
+    fn test_python_import_grouped_and_deduped() {
+        let source = "\"\"\"Tools.\"\"\"\nimport os\nimport sys\n\nimport requests\n\nfrom .models import (\n    User,\n    Group,\n)\n\n\ndef main():\n    import json\n";
+        let path = Path::new("app/tools.py");
+
+        assert_eq!(add_python_import(source, "import json"), source.replace("import os\n", "import json\nimport os\n"));
+        assert_eq!(add_import(source, "import numpy as np", path).unwrap(), source.replace("import requests\n", "import numpy as np\nimport requests\n"));
+        assert_eq!(add_import(source, "from .models import Group", path).unwrap(), source);
+        assert_eq!(
+            add_import("\"\"\"Docs.\"\"\"\n\nx = 1\n", "import os", path).unwrap(),
+            "\"\"\"Docs.\"\"\"\n\nimport os\n\nx = 1\n"
+        );
+    }
+}
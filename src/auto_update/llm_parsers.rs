@@ -44,6 +44,16 @@ pub fn convert_llm_story_to_backlog_item(llm_story: LlmUserStory, id: String) ->
         created: Utc::now(),
         sprint: None,
         dependencies: Vec::new(),
+        started_at: None,
+        completed_at: None,
+        component: None,
+        parent: None,
+        tags: vec![],
+        external_ref: None,
+        history: vec![],
+        review: None,
+        criteria_check: None,
+        extra: Default::default(),
     })
 }
 
@@ -74,6 +84,15 @@ pub struct LlmSourceCodeSuggestion {
     pub notes: Option<String>,
 }
 
+/// A unified diff against one existing file, for edits of a few lines.
+#[derive(Deserialize, Debug, Clone)]
+pub struct LlmPatchSuggestion {
+    pub target_file: String,
+    pub diff: String,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct LlmCargoDependencySuggestion {
     pub dependency_lines: Vec<String>, // Expecting full lines like "serde = \"1.0\""
@@ -93,9 +112,26 @@ pub struct LlmGeneralAdviceSuggestion {
 pub enum LlmSingleSuggestion {
     CargoDependency(LlmCargoDependencySuggestion),
     SourceCode(LlmSourceCodeSuggestion),
+    Patch(LlmPatchSuggestion),
     GeneralAdvice(LlmGeneralAdviceSuggestion),
 }
 
+impl LlmSingleSuggestion {
+    /// One line naming what the suggestion changes, e.g. `ReplaceFunction src/lib.rs`;
+    /// advice changes nothing.
+    pub fn change_summary(&self) -> Option<String> {
+        match self {
+            Self::CargoDependency(deps) => Some(format!("Cargo.toml: {}", deps.dependency_lines.iter()
+                .map(|line| line.split('=').next().unwrap_or(line).trim())
+                .collect::<Vec<_>>()
+                .join(", "))),
+            Self::SourceCode(code) => Some(format!("{:?} {}", code.action, code.target_file)),
+            Self::Patch(patch) => Some(format!("Patch {}", patch.target_file)),
+            Self::GeneralAdvice(_) => None,
+        }
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct LlmAssistTaskResponse {
     // This is the top-level structure expected from the LLM for assist-task
@@ -281,4 +317,18 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_parse_patch_suggestion() -> Result<()> {
+        let json_str = r#"{"type": "patch", "target_file": "src/lib.rs", "diff": "@@ -1 +1 @@\n-a\n+b\n"}"#;
+        let suggestion: LlmSingleSuggestion = serde_json::from_str(json_str)?;
+        if let LlmSingleSuggestion::Patch(patch) = suggestion {
+            assert_eq!(patch.target_file, "src/lib.rs");
+            assert_eq!(patch.diff, "@@ -1 +1 @@\n-a\n+b\n");
+            assert!(patch.notes.is_none());
+        } else {
+            panic!("Not a Patch suggestion");
+        }
+        Ok(())
+    }
 }
@@ -6,9 +6,16 @@ pub mod text_utils;
 pub mod code_gen;
 pub mod doc_gen;
 pub mod cargo_toml_updater; // Added new module
+pub mod file_change;
+pub mod undo;
+pub mod rust_ast;
+pub mod imports;
+pub mod backup;
+pub mod verify;
+pub mod duplicates;
 
 // Optional: re-export key items if needed directly from `crate::auto_update::Item`
-pub use updater::{AutoUpdater, UpdateContext};
+pub use updater::{AutoApprove, AutoUpdater, SuggestionOutcome, UpdateContext};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,191 @@
+// src/auto_update/rust_ast.rs
+//! Edits to Rust source located with `syn`, so a suggestion can replace one function and
+//! leave every other item, comment and blank line of the file byte for byte as it was.
+
+use anyhow::{Context, Result};
+use proc_macro2::LineColumn;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{Attribute, Block, ImplItemFn, ItemFn, ItemImpl, Signature, Visibility};
+
+/// One function found in the file, as byte ranges of the source.
+struct FoundFn {
+    /// `Type` for methods, from the `impl` block
+    owner: Option<String>,
+    /// From the first token after the attributes (doc comments included) to the end
+    item: (LineColumn, LineColumn),
+    /// Start of the first outer attribute or doc comment, if any
+    attrs_start: Option<LineColumn>,
+    body: (LineColumn, LineColumn),
+}
+
+struct FnFinder<'a> {
+    name: &'a str,
+    owner: Option<String>,
+    found: Vec<FoundFn>,
+}
+
+impl FnFinder<'_> {
+    fn record(&mut self, attrs: &[Attribute], vis: &Visibility, sig: &Signature, block: &Block) {
+        if sig.ident != self.name {
+            return;
+        }
+        let start = match vis {
+            Visibility::Inherited => sig.span().start(),
+            _ => vis.span().start(),
+        };
+        self.found.push(FoundFn {
+            owner: self.owner.clone(),
+            item: (start, block.span().end()),
+            attrs_start: attrs.iter().find(|attr| is_outer(attr)).map(|attr| attr.span().start()),
+            body: (block.span().start(), block.span().end()),
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for FnFinder<'_> {
+    fn visit_item_fn(&mut self, item: &'ast ItemFn) {
+        self.record(&item.attrs, &item.vis, &item.sig, &item.block);
+        syn::visit::visit_item_fn(self, item);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        let outer = self.owner.take();
+        self.owner = match &*item.self_ty {
+            syn::Type::Path(path) => path.path.segments.last().map(|segment| segment.ident.to_string()),
+            _ => None,
+        };
+        syn::visit::visit_item_impl(self, item);
+        self.owner = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &'ast ImplItemFn) {
+        self.record(&item.attrs, &item.vis, &item.sig, &item.block);
+        syn::visit::visit_impl_item_fn(self, item);
+    }
+}
+
+/// Byte offset of a span position; columns count characters, not bytes.
+fn offset(source: &str, position: LineColumn) -> usize {
+    let line_start: usize = source.split_inclusive('\n').take(position.line - 1).map(str::len).sum();
+    source[line_start..].char_indices().nth(position.column).map_or(source.len(), |(idx, _)| line_start + idx)
+}
+
+/// `text` with its common indentation replaced by `indent` on every line but the first,
+/// which goes where the old text started.
+fn reindent(text: &str, indent: &str) -> String {
+    let common = text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    text.trim().lines().enumerate()
+        .map(|(idx, line)| {
+            let line = line.get(common..).unwrap_or(line.trim_start());
+            match idx {
+                0 => line.trim_start().to_string(),
+                _ if line.trim().is_empty() => String::new(),
+                _ => format!("{}{}", indent, line),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_outer(attr: &Attribute) -> bool {
+    matches!(attr.style, syn::AttrStyle::Outer)
+}
+
+/// Replace the function `function_name` in `source` with `content`, which is either a
+/// whole function (signature and body) or just its body. `function_name` may be qualified
+/// as `Type::method` to pick a method out of several with the same name.
+///
+/// A whole function keeps the original doc comments and attributes unless it brings its
+/// own; a body replaces only the braces and what is between them.
+pub fn replace_function(source: &str, function_name: &str, content: &str) -> Result<String> {
+    let file = syn::parse_file(source).context("The file is not valid Rust, so the function cannot be located")?;
+    let (owner, name) = match function_name.rsplit_once("::") {
+        Some((owner, name)) => (Some(owner.rsplit("::").next().unwrap_or(owner)), name),
+        None => (None, function_name),
+    };
+    let mut finder = FnFinder { name, owner: None, found: vec![] };
+    finder.visit_file(&file);
+    let mut found: Vec<FoundFn> = finder.found.into_iter()
+        .filter(|f| owner.is_none() || f.owner.as_deref() == owner)
+        .collect();
+    let target = match found.len() {
+        0 => anyhow::bail!("Function '{}' not found", function_name),
+        1 => found.remove(0),
+        _ => {
+            let owners: Vec<String> = found.iter().map(|f| f.owner.clone().unwrap_or_else(|| "free function".to_string())).collect();
+            anyhow::bail!("'{}' is ambiguous ({}); qualify it as Type::{}", function_name, owners.join(", "), name)
+        }
+    };
+
+    let content = content.trim();
+    let (range, replacement) = if let Ok(new_fn) = syn::parse_str::<ImplItemFn>(content) {
+        // A suggestion that brings its own docs/attributes replaces the old ones too
+        let start = match new_fn.attrs.iter().any(is_outer) {
+            true => target.attrs_start.unwrap_or(target.item.0),
+            false => target.item.0,
+        };
+        ((start, target.item.1), content.to_string())
+    } else if syn::parse_str::<Block>(&format!("{{\n{}\n}}", content)).is_ok() {
+        let body = if content.starts_with('{') && syn::parse_str::<Block>(content).is_ok() {
+            content.to_string()
+        } else {
+            format!("{{\n    {}\n}}", reindent(content, "    "))
+        };
+        (target.body, body)
+    } else {
+        anyhow::bail!("The replacement for '{}' is neither a Rust function nor a function body", function_name);
+    };
+
+    let (start, end) = (offset(source, range.0), offset(source, range.1));
+    let line_start = source[..start].rfind('\n').map_or(0, |idx| idx + 1);
+    let indent: String = source[line_start..start].chars().take_while(|c| c.is_whitespace()).collect();
+    Ok(format!("{}{}{}", &source[..start], reindent(&replacement, &indent), &source[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+use std::fmt;
+
+/// Adds two numbers.
+pub fn add(a: i32, b: i32) -> i32 {
+    a - b // wrong
+}
+
+struct Counter(u32);
+
+impl Counter {
+    // Keep this comment
+    fn next(&mut self) -> u32 {
+        0
+    }
+}
+";
+
+    #[test]
+    fn test_replace_whole_function_keeps_docs() {
+        let result = replace_function(SOURCE, "add", "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}").unwrap();
+        assert_eq!(result, SOURCE.replace("    a - b // wrong\n", "    a + b\n"));
+    }
+
+    #[test]
+    fn test_replace_method_body_reindents() {
+        let result = replace_function(SOURCE, "Counter::next", "self.0 += 1;\nself.0").unwrap();
+        assert!(result.contains("    // Keep this comment\n    fn next(&mut self) -> u32 {\n        self.0 += 1;\n        self.0\n    }\n}\n"), "{}", result);
+        assert!(result.starts_with("use std::fmt;\n\n/// Adds two numbers.\n"));
+    }
+
+    #[test]
+    fn test_replace_function_errors() {
+        assert!(replace_function(SOURCE, "missing", "fn missing() {}").is_err());
+        assert!(replace_function(SOURCE, "Other::next", "0").is_err());
+        assert!(replace_function("fn broken(", "broken", "fn broken() {}").is_err());
+    }
+}
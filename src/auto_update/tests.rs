@@ -1,8 +1,9 @@
 // src/auto_update/tests.rs
 
 use crate::config::{GlobalConfig, Project, Priority, Status, ItemType};
-use crate::auto_update::updater::{AutoUpdater, UpdateContext};
+use crate::auto_update::updater::{AutoUpdater, SuggestionOutcome, UpdateContext};
 use crate::auto_update::llm_parsers::LlmUserStory;
+use crate::context::{AppContext, Prompter};
 
 fn create_test_project() -> Project {
     let global_config = GlobalConfig::load().unwrap_or_default();
@@ -19,7 +20,7 @@ fn test_process_llm_requirement_analysis_valid_json() {
     let updated_project = updater.get_project();
     assert_eq!(updated_project.backlog.len(), 2);
 
-    let story1 = updated_project.backlog.get(0).unwrap();
+    let story1 = updated_project.backlog.first().unwrap();
     assert_eq!(story1.id, "US-001");
     assert_eq!(story1.title, "Login"); // Title from simplified JSON
     assert_eq!(story1.priority, Priority::High);
@@ -32,6 +33,24 @@ fn test_process_llm_requirement_analysis_valid_json() {
     assert_eq!(story2.priority, Priority::Critical);
 }
 
+#[test]
+fn test_process_llm_requirement_analysis_skips_archived_ids() {
+    let mut updater = AutoUpdater::new(create_test_project());
+    let three = r#"{"user_stories": [{"title": "A","story": "As a user I want a","priority": "High","effort": 1,"acceptance_criteria": []},{"title": "B","story": "As a user I want b","priority": "High","effort": 1,"acceptance_criteria": []},{"title": "C","story": "As a user I want c","priority": "High","effort": 1,"acceptance_criteria": []}]}"#;
+    updater.process_llm_response(three, UpdateContext::RequirementAnalysis).unwrap();
+    crate::scripts::archive::archive_item(updater.get_project_mut(), "US-001").unwrap();
+
+    let one = r#"{"user_stories": [{"title": "D","story": "As an admin I want reports","priority": "Low","effort": 2,"acceptance_criteria": []}]}"#;
+    updater.process_llm_response(one, UpdateContext::RequirementAnalysis).unwrap();
+    let ids: Vec<&str> = updater.get_project().backlog.iter().map(|item| item.id.as_str()).collect();
+    assert_eq!(ids, vec!["US-002", "US-003", "US-004"]);
+
+    // The text fallback numbers after the archive too
+    crate::scripts::archive::archive_item(updater.get_project_mut(), "US-004").unwrap();
+    updater.process_llm_response("As a user, I want an export.", UpdateContext::RequirementAnalysis).unwrap();
+    assert_eq!(updater.get_project().backlog.last().unwrap().id, "US-005");
+}
+
 #[test]
 fn test_process_llm_requirement_analysis_empty_stories_array() {
     let project = create_test_project();
@@ -57,6 +76,31 @@ fn test_process_llm_requirement_analysis_malformed_json_fallback() {
     assert_eq!(updater2.get_project().backlog.len(), 1);
 }
 
+#[test]
+fn test_process_llm_requirement_analysis_skips_or_merges_duplicates() {
+    let response = r#"{"user_stories": [
+        {"title": "User login","story": "As a visitor I want to log in with my email and password","priority": "High","effort": 3,"acceptance_criteria": ["Wrong passwords are rejected"]},
+        {"title": "Password reset","story": "As a user I want to reset my password","priority": "Medium","effort": 2,"acceptance_criteria": []}
+    ]}"#;
+    let existing = r#"{"user_stories": [{"title": "User login","story": "As a user I want to log in with email and password","priority": "High","effort": 3,"acceptance_criteria": ["AC1"]}]}"#;
+
+    // Not interactive: the duplicate is skipped
+    let mut updater = AutoUpdater::new(create_test_project());
+    updater.process_llm_response(existing, UpdateContext::RequirementAnalysis).unwrap();
+    updater.process_llm_response(response, UpdateContext::RequirementAnalysis).unwrap();
+    let titles: Vec<&str> = updater.get_project().backlog.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(titles, vec!["User login", "Password reset"]);
+
+    // --yes merges its acceptance criteria into the existing story (dry run, so nothing is written)
+    let ctx = AppContext { prompter: Prompter::new(Some(true), false), dry_run: true, ..AppContext::default() };
+    let mut updater = AutoUpdater::with_context(create_test_project(), &ctx);
+    updater.process_llm_response(existing, UpdateContext::RequirementAnalysis).unwrap();
+    updater.process_llm_response(response, UpdateContext::RequirementAnalysis).unwrap();
+    let backlog = &updater.get_project().backlog;
+    assert_eq!(backlog.len(), 2);
+    assert_eq!(backlog[0].acceptance_criteria, vec!["AC1", "Wrong passwords are rejected"]);
+}
+
 #[test]
 fn test_convert_llm_story_priority_mapping() {
     let prio_map = vec![("High", Priority::High), ("Critical", Priority::Critical)];
@@ -76,6 +120,8 @@ fn test_assist_task_valid_structured_json_cargo_and_code() {
     let llm_response_json = r#"{"suggestions": [{"type": "cargo_dependency","dependency_lines": ["toml_edit = \"0.22\""]},{"type": "source_code","target_file": "src/new_util.rs","action": "create","content": "pub fn new_helper() {}"},{"type": "general_advice","content": "Remember to add mod new_util;"}],"overall_summary": "Suggestions."}"#;
     let result = updater.process_llm_response(llm_response_json, UpdateContext::CodeGeneration(task_id.clone()));
     assert!(result.is_ok(), "Processing valid structured JSON failed: {:?}", result.err());
+    // Declined (not interactive) dependencies are recorded for the task's history; listed code is not
+    assert_eq!(updater.outcomes().iter().collect::<Vec<_>>(), vec![(&0, &SuggestionOutcome::Skipped)]);
 }
 
 #[test]
@@ -107,13 +153,14 @@ fn test_assist_task_non_json_response_triggers_fallback() {
     if src_path.exists() && src_path.is_dir() {
         for entry in std::fs::read_dir(src_path).unwrap() {
             let path = entry.unwrap().path();
-            if path.is_file() && path.extension().map_or(false, |ext| ext == "rs") {
-                if path.file_name().unwrap_or_default().to_string_lossy().contains("generated_") {
-                    let content = std::fs::read_to_string(path).unwrap_or_default();
-                    if content.contains("fn example_func_fallback()") {
-                        generated_file_found_and_correct = true;
-                        break;
-                    }
+            if path.is_file()
+                && path.extension().is_some_and(|ext| ext == "rs")
+                && path.file_name().unwrap_or_default().to_string_lossy().contains("generated_")
+            {
+                let content = std::fs::read_to_string(path).unwrap_or_default();
+                if content.contains("fn example_func_fallback()") {
+                    generated_file_found_and_correct = true;
+                    break;
                 }
             }
         }
@@ -1,5 +1,6 @@
 // src/auto_update/text_utils.rs
 use crate::config::{Project, BacklogItem, ItemType, Priority, Status};
+use crate::scripts::import;
 use chrono::Utc;
 
 // This function is pure and doesn't need Project state.
@@ -26,12 +27,8 @@ pub fn extract_stories_from_text(project: &mut Project, text: &str) -> anyhow::R
            line.to_lowercase().contains("user story") ||
            line.contains("US-") {
 
-            // Determine next available US-ID for text extraction
-            let story_id_num = project.backlog // Use the passed-in project
-                .iter()
-                .filter(|item| item.id.starts_with("US-"))
-                .count() + 1 + stories_found;
-            let story_id = format!("US-{:03}", story_id_num);
+            // Next available US-ID, counting archived items and the stories added so far
+            let story_id = import::next_id(project, import::id_prefix(&ItemType::UserStory));
 
             // Call the local (or imported if moved elsewhere) extract_title_from_context
             let title = extract_title_from_context(&lines, i);
@@ -52,6 +49,16 @@ pub fn extract_stories_from_text(project: &mut Project, text: &str) -> anyhow::R
                 created: Utc::now(),
                 sprint: None,
                 dependencies: Vec::new(),
+                started_at: None,
+                completed_at: None,
+                component: None,
+                parent: None,
+                tags: vec![],
+                external_ref: None,
+                history: vec![],
+                review: None,
+                criteria_check: None,
+                extra: Default::default(),
             };
 
             project.backlog.push(backlog_item);
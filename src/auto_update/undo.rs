@@ -0,0 +1,128 @@
+// src/auto_update/undo.rs
+//! Snapshots of the files an auto-update touches, kept in `.env-coach/undo/<timestamp>/`
+//! so `env-coach undo` can put them back when a suggestion turns out to be wrong.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const UNDO_DIR: &str = ".env-coach/undo";
+const MANIFEST: &str = "manifest.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotFile {
+    pub path: PathBuf,
+    /// `false` when the transaction created the file, so undo deletes it
+    pub existed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Manifest {
+    pub created: DateTime<Utc>,
+    /// What the transaction was, e.g. "code generation for US-001"
+    pub label: String,
+    pub files: Vec<SnapshotFile>,
+}
+
+/// One auto-update run. Nothing is written to disk until the first snapshot.
+#[derive(Debug)]
+pub struct Transaction {
+    dir: PathBuf,
+    manifest: Manifest,
+}
+
+impl Transaction {
+    pub fn begin(root: &Path, label: &str) -> Self {
+        let created = Utc::now();
+        let dir = root.join(UNDO_DIR).join(created.format("%Y%m%dT%H%M%S%.3fZ").to_string());
+        Self { dir, manifest: Manifest { created, label: label.to_string(), files: vec![] } }
+    }
+
+    /// Record `path` as it is now, before it is modified. Later calls for the same path are
+    /// ignored so the snapshot keeps the state from before the transaction.
+    pub fn snapshot(&mut self, path: &Path) -> Result<()> {
+        if self.manifest.files.iter().any(|file| file.path == path) {
+            return Ok(());
+        }
+        let files_dir = self.dir.join("files");
+        fs::create_dir_all(&files_dir).with_context(|| format!("Failed to create undo snapshot {:?}", self.dir))?;
+        let existed = path.exists();
+        if existed {
+            let copy = files_dir.join(self.manifest.files.len().to_string());
+            fs::copy(path, &copy).with_context(|| format!("Failed to snapshot {:?} for undo", path))?;
+        }
+        self.manifest.files.push(SnapshotFile { path: path.to_path_buf(), existed });
+        // Rewritten after every file so an interrupted run can still be undone
+        fs::write(self.dir.join(MANIFEST), serde_json::to_string_pretty(&self.manifest)?)
+            .with_context(|| format!("Failed to write undo manifest in {:?}", self.dir))
+    }
+}
+
+/// The most recent transaction under `root`, if any.
+pub fn last(root: &Path) -> Result<Option<(PathBuf, Manifest)>> {
+    let undo_dir = root.join(UNDO_DIR);
+    if !undo_dir.exists() {
+        return Ok(None);
+    }
+    let mut dirs: Vec<PathBuf> = fs::read_dir(&undo_dir)
+        .with_context(|| format!("Failed to read {:?}", undo_dir))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(MANIFEST).is_file())
+        .collect();
+    // Directory names are timestamps, so they sort chronologically
+    dirs.sort();
+    let Some(dir) = dirs.pop() else { return Ok(None) };
+    let content = fs::read_to_string(dir.join(MANIFEST)).with_context(|| format!("Failed to read undo manifest in {:?}", dir))?;
+    let manifest = serde_json::from_str(&content).with_context(|| format!("Corrupt undo manifest in {:?}", dir))?;
+    Ok(Some((dir, manifest)))
+}
+
+/// Put every file of the transaction in `dir` back as it was (deleting files it created),
+/// then remove the snapshot so the next `undo` goes one transaction further back.
+pub fn restore(dir: &Path, manifest: &Manifest) -> Result<()> {
+    for (idx, file) in manifest.files.iter().enumerate() {
+        if file.existed {
+            if let Some(parent) = file.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+            }
+            fs::copy(dir.join("files").join(idx.to_string()), &file.path)
+                .with_context(|| format!("Failed to restore {:?}", file.path))?;
+        } else if file.path.exists() {
+            fs::remove_file(&file.path).with_context(|| format!("Failed to remove {:?}", file.path))?;
+        }
+    }
+    fs::remove_dir_all(dir).with_context(|| format!("Failed to remove undo snapshot {:?}", dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        let existing = root.join("Cargo.toml");
+        let created = root.join("src/new.rs");
+        fs::write(&existing, "[package]\n").unwrap();
+
+        let mut transaction = Transaction::begin(root, "code generation for US-001");
+        transaction.snapshot(&existing).unwrap();
+        transaction.snapshot(&created).unwrap();
+        fs::write(&existing, "[package]\n[dependencies]\n").unwrap();
+        transaction.snapshot(&existing).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(&created, "fn main() {}\n").unwrap();
+
+        let (dir, manifest) = last(root).unwrap().unwrap();
+        assert_eq!(manifest.label, "code generation for US-001");
+        assert_eq!(manifest.files.len(), 2);
+
+        restore(&dir, &manifest).unwrap();
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "[package]\n");
+        assert!(!created.exists());
+        assert!(last(root).unwrap().is_none());
+    }
+}
@@ -1,7 +1,17 @@
 // src/auto_update/updater.rs
 
-use crate::config::Project;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ItemType, Project};
+use crate::context::{AppContext, Prompter};
+use crate::scripts::import;
 use super::{llm_parsers, text_utils, code_gen, doc_gen}; // Import other submodules
+use super::duplicates::{self, Embeddings};
+use super::file_change::FileChange;
+use super::undo::Transaction;
 
 #[derive(Debug)]
 pub enum UpdateContext {
@@ -12,27 +22,160 @@ pub enum UpdateContext {
     Documentation, // General documentation update context
 }
 
+/// What happened to one suggestion during `apply_suggestions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SuggestionOutcome {
+    Applied,
+    Skipped,
+    Failed(String),
+}
+
+/// Kinds of suggestion applied without asking, for unattended runs (`execute-sprint`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AutoApprove {
+    /// Source changes and patches
+    pub code: bool,
+    /// Cargo.toml dependencies
+    pub dependencies: bool,
+}
+
+/// Show a planned change for `--dry-run`; a change that cannot be planned is only reported.
+fn preview_change(change: anyhow::Result<FileChange>) {
+    match change {
+        Ok(change) => change.print_preview(),
+        Err(e) => eprintln!("⚠️ {}", e),
+    }
+}
+
 pub struct AutoUpdater {
     project: Project,
+    /// Saves go through `AppContext::save_project`; an updater without one (see `new`) only
+    /// changes the project in memory
+    ctx: Option<AppContext>,
+    prompter: Prompter,
+    dry_run: bool,
+    /// Epic that stories added from a requirement are attached to
+    epic: Option<String>,
+    /// Snapshot files into `.env-coach/undo/` before changing them (commands only)
+    record_undo: bool,
+    transaction: Option<Transaction>,
+    auto_approve: AutoApprove,
+    /// Embeddings for the duplicate check of stories added from a requirement
+    embeddings: Option<Embeddings>,
+    /// What became of each suggestion of the last code generation response, by index
+    outcomes: BTreeMap<usize, SuggestionOutcome>,
+}
+
+/// What to do with a generated story that looks like an existing item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DuplicateChoice {
+    /// Add its acceptance criteria to the existing item
+    Merge,
+    Skip,
+    Add,
 }
 
 impl AutoUpdater {
+    /// An updater that changes `project` in memory only; commands use `with_context`.
     pub fn new(project: Project) -> Self {
-        Self { project }
+        Self { project, ctx: None, prompter: Prompter::default(), dry_run: false, epic: None, record_undo: false, transaction: None, auto_approve: AutoApprove::default(), embeddings: None, outcomes: BTreeMap::new() }
+    }
+
+    /// Create an updater that honours the CLI-wide flags (`--yes`, `--dry-run`) and records
+    /// what it changes so `env-coach undo` can revert it.
+    pub fn with_context(project: Project, ctx: &AppContext) -> Self {
+        Self { ctx: Some(ctx.clone()), prompter: ctx.prompter.clone(), dry_run: ctx.dry_run, record_undo: !ctx.dry_run, ..Self::new(project) }
+    }
+
+    /// Snapshot `path` before it is changed. All snapshots of one updater form a single
+    /// transaction under `root`, labelled by the first change.
+    fn snapshot(&mut self, root: &Path, label: &str, path: &Path) -> anyhow::Result<()> {
+        if !self.record_undo {
+            return Ok(());
+        }
+        self.transaction.get_or_insert_with(|| Transaction::begin(root, label)).snapshot(path)
+    }
+
+    /// Attach stories generated from a requirement to `epic`.
+    pub fn with_epic(self, epic: Option<String>) -> Self {
+        Self { epic, ..self }
+    }
+
+    /// Compare stories added from a requirement with the backlog by these embeddings
+    /// (see `duplicates::embed_for_check`) instead of by their words.
+    pub fn with_embeddings(self, embeddings: Option<Embeddings>) -> Self {
+        Self { embeddings, ..self }
+    }
+
+    /// Apply the kinds of suggestion in `auto_approve` without asking.
+    pub fn with_auto_approve(self, auto_approve: AutoApprove) -> Self {
+        Self { auto_approve, ..self }
+    }
+
+    /// What became of the suggestions `process_llm_response` handled for code generation;
+    /// suggestions it only listed are absent.
+    pub fn outcomes(&self) -> &BTreeMap<usize, SuggestionOutcome> {
+        &self.outcomes
+    }
+
+    /// Ask `question`, or answer yes when the suggestion's kind is auto-approved.
+    fn confirm(&self, auto_approved: bool, question: &str) -> anyhow::Result<bool> {
+        if auto_approved {
+            println!("{} (yes/no): yes [auto-approved]", question);
+            return Ok(true);
+        }
+        self.prompter.confirm(question)
+    }
+
+    /// Ask what to do with a story that looks like `twin`; skipped unless answered otherwise.
+    fn choose_for_duplicate(&self, title: &str, twin_id: &str, twin_title: &str, score: f32) -> anyhow::Result<DuplicateChoice> {
+        println!("🔁 '{}' looks like {} - {} ({:.0}% alike)", title, twin_id, twin_title, score * 100.0);
+        let question = format!("👉 Merge it into {}, skip it, or add it anyway? (merge/skip/add): ", twin_id);
+        if self.prompter.assume_yes() {
+            println!("{}merge [--yes]", question);
+            return Ok(DuplicateChoice::Merge);
+        }
+        Ok(match self.prompter.ask(&question)?.to_lowercase().as_str() {
+            "merge" | "m" => DuplicateChoice::Merge,
+            "add" | "a" => DuplicateChoice::Add,
+            _ => DuplicateChoice::Skip,
+        })
     }
 
     pub fn process_llm_response(&mut self, llm_response: &str, context: UpdateContext) -> anyhow::Result<()> {
+        let project_before = serde_json::to_string_pretty(&self.project)?;
+        let label = match &context {
+            UpdateContext::RequirementAnalysis => "requirement analysis".to_string(),
+            UpdateContext::TaskCompletion(task_id) => format!("completion of {}", task_id),
+            UpdateContext::CodeGeneration(task_id) => format!("code generation for {}", task_id),
+            UpdateContext::Documentation => "documentation update".to_string(),
+        };
         match context {
             UpdateContext::RequirementAnalysis => {
                 // This method will parse and add stories to self.project.backlog
+                let existing: HashSet<String> = self.project.backlog.iter().map(|item| item.id.clone()).collect();
                 self.update_project_from_requirement_analysis(llm_response)?;
+                if let Some(epic) = &self.epic {
+                    for item in self.project.backlog.iter_mut().filter(|item| !existing.contains(&item.id)) {
+                        item.parent = Some(epic.clone());
+                    }
+                }
             },
             UpdateContext::TaskCompletion(task_id) => {
                 // This method will update docs based on self.project and task_id
-                doc_gen::update_docs_for_task_completion(&self.project, &task_id, llm_response)?;
+                if self.dry_run {
+                    for change in doc_gen::plan_docs_for_task_completion(&self.project, &task_id)? {
+                        change.print_preview();
+                    }
+                } else {
+                    for change in doc_gen::plan_docs_for_task_completion(&self.project, &task_id)? {
+                        self.snapshot(Path::new(""), &label, &change.path)?;
+                    }
+                    doc_gen::update_docs_for_task_completion(&self.project, &task_id, llm_response)?;
+                }
             },
             UpdateContext::CodeGeneration(task_id) => {
-                self.handle_code_generation_suggestions(&task_id, llm_response)?;
+                self.handle_code_generation_suggestions(&task_id, llm_response, &label)?;
             },
             UpdateContext::Documentation => { // General documentation update
                 // Assuming this might also use a structured response in the future or specific logic
@@ -40,7 +183,20 @@ impl AutoUpdater {
             }
         }
 
-        self.project.save()?; // Save project after any modification
+        if self.dry_run {
+            FileChange {
+                path: "project.json".into(),
+                before: Some(project_before),
+                after: serde_json::to_string_pretty(&self.project)?,
+            }.print_preview();
+        } else {
+            for path in self.project.storage_files()? {
+                self.snapshot(Path::new(""), &label, &path)?;
+            }
+            if let Some(ctx) = &self.ctx {
+                ctx.save_project(&self.project)?; // Save project after any modification
+            }
+        }
         Ok(())
     }
 
@@ -57,14 +213,34 @@ impl AutoUpdater {
                 }
 
                 let mut added_count = 0;
-                let initial_story_count = self.project.backlog
-                    .iter()
-                    .filter(|item| item.id.starts_with("US-"))
-                    .count();
+                let mut merged: Vec<String> = vec![];
+                let mut skipped = 0;
+                let threshold = self.project.meta.duplicates.as_ref().and_then(|config| config.threshold);
 
                 for llm_story in parsed_response.user_stories {
-                    let story_id_num = initial_story_count + 1 + added_count;
-                    let story_id = format!("US-{:03}", story_id_num);
+                    let twin = duplicates::find_duplicate(&self.project, &llm_story.title, &llm_story.story, self.embeddings.as_ref(), threshold)
+                        .map(|(twin, score)| (twin.id.clone(), twin.title.clone(), score));
+                    if let Some((twin_id, twin_title, score)) = twin {
+                        match self.choose_for_duplicate(&llm_story.title, &twin_id, &twin_title, score)? {
+                            DuplicateChoice::Merge => {
+                                let twin = self.project.backlog.iter_mut().find(|item| item.id == twin_id).expect("duplicate is in the backlog");
+                                for criterion in llm_story.acceptance_criteria {
+                                    if !twin.acceptance_criteria.contains(&criterion) {
+                                        twin.acceptance_criteria.push(criterion);
+                                    }
+                                }
+                                merged.push(twin_id);
+                                continue;
+                            }
+                            DuplicateChoice::Skip => {
+                                skipped += 1;
+                                continue;
+                            }
+                            DuplicateChoice::Add => {}
+                        }
+                    }
+
+                    let story_id = import::next_id(&self.project, import::id_prefix(&ItemType::UserStory));
 
                     // Call the function from llm_parsers module
                     match llm_parsers::convert_llm_story_to_backlog_item(llm_story, story_id) {
@@ -83,6 +259,12 @@ impl AutoUpdater {
                 } else {
                     println!("ℹ️ No user stories were added from the LLM response despite successful parsing.");
                 }
+                if !merged.is_empty() {
+                    println!("🔁 Merged acceptance criteria of {} duplicate(s) into {}", merged.len(), merged.join(", "));
+                }
+                if skipped > 0 {
+                    println!("⏭️  Skipped {} duplicate(s)", skipped);
+                }
             }
             Err(e) => {
                 println!("⚠️ Failed to parse LLM response as structured JSON: {}", e);
@@ -105,8 +287,122 @@ impl AutoUpdater {
         &mut self.project
     }
 
-    fn handle_code_generation_suggestions(&mut self, task_id: &str, llm_response_str: &str) -> anyhow::Result<()> {
-        use std::io::{self, Write};
+    /// Apply structured suggestions one by one under `root`, asking before each change.
+    /// Indices in `already_decided` (from an interrupted run) are skipped, and `on_decided`
+    /// is called after every decision so the caller can persist progress.
+    pub fn apply_suggestions(
+        &mut self,
+        root: &Path,
+        suggestions: &[llm_parsers::LlmSingleSuggestion],
+        already_decided: &HashSet<usize>,
+        mut on_decided: impl FnMut(usize, &SuggestionOutcome) -> anyhow::Result<()>,
+    ) -> anyhow::Result<Vec<(usize, SuggestionOutcome)>> {
+        use super::cargo_toml_updater;
+
+        let mut outcomes = Vec::new();
+        for (idx, suggestion) in suggestions.iter().enumerate() {
+            if already_decided.contains(&idx) {
+                continue;
+            }
+            println!();
+            println!("[{}/{}]", idx + 1, suggestions.len());
+
+            let outcome = match suggestion {
+                llm_parsers::LlmSingleSuggestion::GeneralAdvice(advice) => {
+                    println!("💡 {}", advice.content);
+                    if let Some(notes) = &advice.notes { println!("   Notes: {}", notes); }
+                    SuggestionOutcome::Applied
+                }
+                llm_parsers::LlmSingleSuggestion::CargoDependency(deps) => {
+                    println!("📦 Cargo.toml dependencies:");
+                    for dep in &deps.dependency_lines {
+                        println!("   - {}", dep);
+                    }
+                    if let Some(notes) = &deps.notes { println!("   Notes: {}", notes); }
+                    if self.dry_run {
+                        preview_change(cargo_toml_updater::plan_cargo_dependencies(root, &deps.dependency_lines));
+                        SuggestionOutcome::Skipped
+                    } else if !self.confirm(self.auto_approve.dependencies, "👉 Add these dependencies to Cargo.toml?")? {
+                        SuggestionOutcome::Skipped
+                    } else if let Err(e) = self.snapshot(root, "execute-task suggestions", &root.join("Cargo.toml")) {
+                        SuggestionOutcome::Failed(e.to_string())
+                    } else {
+                        match cargo_toml_updater::add_cargo_dependencies(root, &deps.dependency_lines) {
+                            Ok(()) => SuggestionOutcome::Applied,
+                            Err(e) => SuggestionOutcome::Failed(e.to_string()),
+                        }
+                    }
+                }
+                llm_parsers::LlmSingleSuggestion::SourceCode(code) => {
+                    println!("📝 {:?} {}", code.action, code.target_file);
+                    if let Some(function_name) = &code.function_name { println!("   Function: {}", function_name); }
+                    if let Some(import_statement) = &code.import_statement { println!("   Import: {}", import_statement); }
+                    if let Some(notes) = &code.notes { println!("   Notes: {}", notes); }
+                    for line in code.content.lines().take(20) {
+                        println!("   | {}", line);
+                    }
+                    if code.content.lines().count() > 20 {
+                        println!("   | ... ({} more lines)", code.content.lines().count() - 20);
+                    }
+                    if self.dry_run {
+                        preview_change(code_gen::plan_source_suggestion(root, code));
+                        SuggestionOutcome::Skipped
+                    } else if !self.confirm(self.auto_approve.code, "👉 Apply this change?")? {
+                        SuggestionOutcome::Skipped
+                    } else if let Err(e) = code_gen::resolve_target(root, &code.target_file)
+                        .and_then(|path| self.snapshot(root, "execute-task suggestions", &path))
+                    {
+                        SuggestionOutcome::Failed(e.to_string())
+                    } else {
+                        match code_gen::apply_source_suggestion(root, code) {
+                            Ok(description) => {
+                                println!("✅ {}", description);
+                                SuggestionOutcome::Applied
+                            }
+                            Err(e) => SuggestionOutcome::Failed(e.to_string()),
+                        }
+                    }
+                }
+                llm_parsers::LlmSingleSuggestion::Patch(patch) => {
+                    println!("🩹 Patch {}", patch.target_file);
+                    if let Some(notes) = &patch.notes { println!("   Notes: {}", notes); }
+                    for line in patch.diff.lines().take(20) {
+                        println!("   | {}", line);
+                    }
+                    if patch.diff.lines().count() > 20 {
+                        println!("   | ... ({} more lines)", patch.diff.lines().count() - 20);
+                    }
+                    if self.dry_run {
+                        preview_change(code_gen::plan_patch_suggestion(root, patch));
+                        SuggestionOutcome::Skipped
+                    } else if !self.confirm(self.auto_approve.code, "👉 Apply this patch?")? {
+                        SuggestionOutcome::Skipped
+                    } else if let Err(e) = code_gen::resolve_target(root, &patch.target_file)
+                        .and_then(|path| self.snapshot(root, "execute-task suggestions", &path))
+                    {
+                        SuggestionOutcome::Failed(e.to_string())
+                    } else {
+                        match code_gen::apply_patch_suggestion(root, patch) {
+                            Ok(description) => {
+                                println!("✅ {}", description);
+                                SuggestionOutcome::Applied
+                            }
+                            Err(e) => SuggestionOutcome::Failed(format!("{:#}", e)),
+                        }
+                    }
+                }
+            };
+
+            if let SuggestionOutcome::Failed(reason) = &outcome {
+                eprintln!("⚠️ {}", reason);
+            }
+            on_decided(idx, &outcome)?;
+            outcomes.push((idx, outcome));
+        }
+        Ok(outcomes)
+    }
+
+    fn handle_code_generation_suggestions(&mut self, task_id: &str, llm_response_str: &str, label: &str) -> anyhow::Result<()> {
         use super::cargo_toml_updater; // To call add_cargo_dependencies
 
         println!("💻 Processing LLM suggestions for task {}...", task_id);
@@ -119,20 +415,29 @@ impl AutoUpdater {
 
                 let mut cargo_deps_to_add: Vec<String> = Vec::new();
                 let mut source_code_suggestions: Vec<&llm_parsers::LlmSourceCodeSuggestion> = Vec::new();
+                let mut patches: Vec<&llm_parsers::LlmPatchSuggestion> = Vec::new();
                 let mut general_advice: Vec<String> = Vec::new();
+                let mut dependency_suggestions: Vec<usize> = Vec::new();
+                self.outcomes.clear();
 
-                for suggestion in &parsed_response.suggestions {
+                for (idx, suggestion) in parsed_response.suggestions.iter().enumerate() {
                     match suggestion {
                         llm_parsers::LlmSingleSuggestion::CargoDependency(deps) => {
                             println!("  - LLM suggests adding {} Cargo dependenc(ies).", deps.dependency_lines.len());
                             if let Some(notes) = &deps.notes { println!("    Notes: {}", notes); }
                             cargo_deps_to_add.extend(deps.dependency_lines.iter().cloned());
+                            dependency_suggestions.push(idx);
                         }
                         llm_parsers::LlmSingleSuggestion::SourceCode(code_sugg) => {
                             println!("  - LLM suggests code for '{}' (action: {:?}).", code_sugg.target_file, code_sugg.action);
                             if let Some(notes) = &code_sugg.notes { println!("    Notes: {}", notes); }
                             source_code_suggestions.push(code_sugg);
                         }
+                        llm_parsers::LlmSingleSuggestion::Patch(patch) => {
+                            println!("  - LLM suggests a patch for '{}'.", patch.target_file);
+                            if let Some(notes) = &patch.notes { println!("    Notes: {}", notes); }
+                            patches.push(patch);
+                        }
                         llm_parsers::LlmSingleSuggestion::GeneralAdvice(advice) => {
                             println!("  - LLM general advice: {}", advice.content);
                             if let Some(notes) = &advice.notes { println!("    Notes: {}", notes); }
@@ -147,20 +452,27 @@ impl AutoUpdater {
                     for dep in &cargo_deps_to_add {
                         println!("  - {}", dep);
                     }
-                    print!("👉 Add these dependencies to Cargo.toml? (yes/no): ");
-                    io::stdout().flush()?;
-                    let mut user_choice = String::new();
-                    io::stdin().read_line(&mut user_choice)?;
-
-                    if user_choice.trim().to_lowercase() == "yes" || user_choice.trim().to_lowercase() == "y" {
+                    if self.dry_run {
+                        let project_root = std::env::current_dir().map_err(anyhow::Error::from)?;
+                        preview_change(cargo_toml_updater::plan_cargo_dependencies(&project_root, &cargo_deps_to_add));
+                    } else if self.prompter.confirm("👉 Add these dependencies to Cargo.toml?")? {
                         // Assuming current directory is project root for finding Cargo.toml
                         let project_root = std::env::current_dir().map_err(anyhow::Error::from)?;
-                        match cargo_toml_updater::add_cargo_dependencies(&project_root, &cargo_deps_to_add) {
-                            Ok(_) => println!("✅ Cargo.toml updated successfully."),
-                            Err(e) => eprintln!("⚠️ Failed to update Cargo.toml: {}", e),
-                        }
+                        self.snapshot(Path::new(""), label, &project_root.join("Cargo.toml"))?;
+                        let outcome = match cargo_toml_updater::add_cargo_dependencies(&project_root, &cargo_deps_to_add) {
+                            Ok(_) => {
+                                println!("✅ Cargo.toml updated successfully.");
+                                SuggestionOutcome::Applied
+                            }
+                            Err(e) => {
+                                eprintln!("⚠️ Failed to update Cargo.toml: {}", e);
+                                SuggestionOutcome::Failed(e.to_string())
+                            }
+                        };
+                        self.outcomes.extend(dependency_suggestions.iter().map(|idx| (*idx, outcome.clone())));
                     } else {
                         println!("Skipped adding Cargo dependencies.");
+                        self.outcomes.extend(dependency_suggestions.iter().map(|idx| (*idx, SuggestionOutcome::Skipped)));
                     }
                 }
 
@@ -169,6 +481,12 @@ impl AutoUpdater {
                     println!("\nLLM suggested the following source code changes:");
                     for (idx, code_sugg) in source_code_suggestions.iter().enumerate() {
                         println!("  {}. Action: {:?} for file: {}", idx + 1, code_sugg.action, code_sugg.target_file);
+                        if let Some(function_name) = &code_sugg.function_name {
+                            println!("     Function: {}", function_name);
+                        }
+                        if let Some(import_statement) = &code_sugg.import_statement {
+                            println!("     Import: {}", import_statement);
+                        }
                         println!("     Content (first 80 chars): {:.80}...", code_sugg.content.chars().take(80).collect::<String>());
                          // Here, you would implement logic to apply these changes,
                          // potentially with user confirmation for each.
@@ -197,10 +515,23 @@ impl AutoUpdater {
                 // If the JSON is valid and contains only general_advice, that's what we process.
                 // The fallback to `code_gen::generate_code_files` should *only* happen if `parse_assist_task_response` itself fails.
 
-                if cargo_deps_to_add.is_empty() && source_code_suggestions.is_empty() && !general_advice.is_empty() {
+                // 3. Patches are listed with their diff; execute-task applies them
+                if !patches.is_empty() {
+                    println!("\nLLM suggested the following patches:");
+                    for patch in &patches {
+                        println!("  🩹 {}", patch.target_file);
+                        for line in patch.diff.lines() {
+                            println!("     {}", line);
+                        }
+                    }
+                    println!("👉 Run 'env-coach execute-task {}' to apply them one by one.", task_id);
+                }
+
+                let no_file_changes = cargo_deps_to_add.is_empty() && source_code_suggestions.is_empty() && patches.is_empty();
+                if no_file_changes && !general_advice.is_empty() {
                     println!("\nℹ️ LLM provided general advice. No direct file modifications or dependency changes were suggested in the structured response.");
                 }
-                if cargo_deps_to_add.is_empty() && source_code_suggestions.is_empty() && general_advice.is_empty() {
+                if no_file_changes && general_advice.is_empty() {
                      println!("\nℹ️ LLM response parsed successfully but contained no actionable suggestions (dependencies, source code, or general advice).");
                 }
             }
@@ -208,6 +539,9 @@ impl AutoUpdater {
                 // This is the ONLY place where fallback to old raw code block extraction should occur.
                 eprintln!("⚠️ Failed to parse LLM response as structured JSON for task {}: {}", task_id, e);
                 eprintln!("   Falling back to raw code block extraction for task {}...", task_id);
+                for (filename, _) in code_gen::extract_code_blocks(&self.project.meta, llm_response_str) {
+                    self.snapshot(Path::new(""), label, Path::new(&filename))?;
+                }
                 code_gen::generate_code_files(&self.project, task_id, llm_response_str)?;
             }
         }
@@ -0,0 +1,145 @@
+// src/auto_update/verify.rs
+//! Checks run after code suggestions are applied, so a change that breaks the build or
+//! the linter is caught right away: `cargo fmt --check`, `cargo check` and `cargo clippy`
+//! for Rust, with built-in commands for other stacks and per-project overrides.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::VerifyConfig;
+
+/// Lines of a failing check's output shown in the summary.
+const SUMMARY_LINES: usize = 5;
+
+struct Stack {
+    name: &'static str,
+    /// Any of these files marks the stack
+    manifests: &'static [&'static str],
+    /// Built-in checks run by `--verify`
+    checks: &'static [&'static str],
+    /// Quickest command that reports compile errors, for `execute-task --auto-fix`
+    build: &'static str,
+}
+
+const STACKS: &[Stack] = &[
+    Stack {
+        name: "rust",
+        manifests: &["Cargo.toml"],
+        checks: &["cargo fmt --check", "cargo check --all-targets", "cargo clippy --all-targets -- -D warnings"],
+        build: "cargo check --all-targets",
+    },
+    Stack { name: "node", manifests: &["package.json"], checks: &["npm run lint --if-present"], build: "npm run build --if-present" },
+    Stack { name: "go", manifests: &["go.mod"], checks: &["test -z \"$(gofmt -l .)\"", "go vet ./..."], build: "go build ./..." },
+    Stack {
+        name: "python",
+        manifests: &["pyproject.toml", "setup.py"],
+        checks: &["python -m compileall -q ."],
+        build: "python -m compileall -q .",
+    },
+];
+
+/// The project's tech stack, judged by its manifest files.
+fn stack_of(root: &Path) -> Option<&'static Stack> {
+    STACKS.iter().find(|stack| stack.manifests.iter().any(|manifest| root.join(manifest).exists()))
+}
+
+#[derive(Debug)]
+pub struct CheckResult {
+    pub command: String,
+    pub passed: bool,
+    pub output: String,
+}
+
+impl CheckResult {
+    /// The diagnostic lines of the output (`error...`, `warning...`), or its last lines
+    /// when there are none.
+    pub fn summary(&self) -> Vec<&str> {
+        let lines: Vec<&str> = self.output.lines().map(str::trim_end).filter(|line| !line.trim().is_empty()).collect();
+        let diagnostics: Vec<&str> = lines.iter()
+            .copied()
+            .filter(|line| {
+                let lower = line.trim_start().to_lowercase();
+                lower.starts_with("error") || lower.starts_with("warning") || lower.starts_with("diff in")
+            })
+            // Cargo's closing tallies repeat what the diagnostics already say
+            .filter(|line| !line.starts_with("warning: `") && !line.starts_with("error: could not compile"))
+            .take(SUMMARY_LINES)
+            .collect();
+        match diagnostics.is_empty() {
+            true => lines[lines.len().saturating_sub(SUMMARY_LINES)..].to_vec(),
+            false => diagnostics,
+        }
+    }
+}
+
+/// Checks for the project under `root`: the configured ones for its stack, else the built-in ones.
+pub fn commands_for(root: &Path, config: Option<&VerifyConfig>) -> Vec<String> {
+    let Some(stack) = stack_of(root) else { return vec![] };
+    match config.and_then(|config| config.commands.get(stack.name)) {
+        Some(commands) => commands.clone(),
+        None => stack.checks.iter().map(|command| command.to_string()).collect(),
+    }
+}
+
+/// The command that reports compile errors for the project under `root`.
+pub fn build_command(root: &Path) -> Option<&'static str> {
+    stack_of(root).map(|stack| stack.build)
+}
+
+/// Run one check through the shell in `root`.
+pub fn run_check(root: &Path, command: &str) -> Result<CheckResult> {
+    println!("🔧 Running {}...", command);
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(root)
+        .output()
+        .with_context(|| format!("Failed to run '{}'", command))?;
+    Ok(CheckResult {
+        command: command.to_string(),
+        passed: output.status.success(),
+        output: format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr)),
+    })
+}
+
+/// Run every check in order; later checks still run when one fails so the summary
+/// covers all of them.
+pub fn run_checks(root: &Path, commands: &[String]) -> Result<Vec<CheckResult>> {
+    commands.iter().map(|command| run_check(root, command)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_commands_for_stack_and_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(commands_for(dir.path(), None).is_empty());
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        assert_eq!(stack_of(dir.path()).map(|stack| stack.name), Some("rust"));
+        assert_eq!(commands_for(dir.path(), None)[1], "cargo check --all-targets");
+        assert_eq!(build_command(dir.path()), Some("cargo check --all-targets"));
+
+        let config = VerifyConfig {
+            always: false,
+            commands: BTreeMap::from([("rust".to_string(), vec!["cargo build".to_string()])]),
+        };
+        assert_eq!(commands_for(dir.path(), Some(&config)), vec!["cargo build"]);
+    }
+
+    #[test]
+    fn test_summary_prefers_diagnostics() {
+        let check = CheckResult {
+            command: "cargo check".to_string(),
+            passed: false,
+            output: "    Checking demo v0.1.0\nerror[E0425]: cannot find value `x` in this scope\n --> src/main.rs:2:5\nerror: could not compile `demo` (bin \"demo\") due to 1 previous error\n".to_string(),
+        };
+        assert_eq!(check.summary(), vec!["error[E0425]: cannot find value `x` in this scope"]);
+
+        let check = CheckResult { command: "make".to_string(), passed: false, output: "a\nb\n\nc\n".to_string() };
+        assert_eq!(check.summary(), vec!["a", "b", "c"]);
+    }
+}
@@ -136,8 +136,7 @@ mod tests {
             effort: 3,
             status: Status::Done,
             created: Utc::now(),
-            sprint: None,
-            dependencies: Vec::new(),
+            ..Default::default()
         };
 
         // This would normally update README.md and CHANGELOG.md
@@ -0,0 +1,261 @@
+// src/code_context.rs
+//! Codebase context for prompts, configured per project in `.env-coach/context.toml`:
+//! which files to include or exclude (globs relative to the project root), how much of
+//! each file to show, and files that are always shown first (e.g. `Cargo.toml`). Used by
+//! `add-requirement`, `plan-sprint` and `assist-task` to fill `{{code_context}}`. It also
+//! chooses how prompts that exceed the model's context are made to fit (`tokens::TrimStrategy`).
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::tokens::TrimStrategy;
+
+pub const CONTEXT_FILE: &str = ".env-coach/context.toml";
+
+/// Directories never walked, whatever the globs say.
+const SKIPPED_DIRS: &[&str] = &[".git", ".env-coach", "target", "node_modules"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContextConfig {
+    /// Files shown when they match one of these globs (`*` within a path segment, `**` across segments)
+    pub include: Vec<String>,
+    /// Files left out even when included
+    pub exclude: Vec<String>,
+    /// Files shown before any other, when they exist
+    pub always_include: Vec<String>,
+    /// Bytes shown of each file; longer files are cut
+    pub max_file_bytes: usize,
+    /// Bytes of context in all; files past it are left out
+    pub max_total_bytes: usize,
+    /// What `plan-sprint` and `assist-task` do when the backlog or code do not fit the context window
+    pub strategy: TrimStrategy,
+    /// Model used by the `summarize` strategy; the project's model when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary_model: Option<String>,
+    /// Show `assist-task` the uncommitted diff of an In Progress task
+    pub git_diff: bool,
+    /// Show `assist-task` the `git status` of an In Progress task
+    pub git_status: bool,
+}
+
+impl Default for ContextConfig {
+    fn default() -> Self {
+        Self {
+            include: vec!["src/**".to_string(), "lib/**".to_string()],
+            exclude: vec!["**/*.lock".to_string(), "**/*.min.js".to_string()],
+            always_include: ["Cargo.toml", "package.json", "pyproject.toml", "go.mod", "src/lib.rs", "src/main.rs"]
+                .iter().map(|file| file.to_string()).collect(),
+            max_file_bytes: 4000,
+            max_total_bytes: 24000,
+            strategy: TrimStrategy::default(),
+            summary_model: None,
+            git_diff: true,
+            git_status: true,
+        }
+    }
+}
+
+impl ContextConfig {
+    /// The project's context config, or the defaults when it has none.
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(CONTEXT_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display())),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// `load`, reporting a broken config and using the defaults instead.
+    pub fn load_or_default(root: &Path) -> Self {
+        Self::load(root).unwrap_or_else(|e| {
+            println!("⚠️ {:#}. Using the default context settings.", e);
+            Self::default()
+        })
+    }
+
+    /// The defaults as written by `init`, with a header explaining them.
+    pub fn default_file_content() -> String {
+        format!(
+            "# Codebase context shown to the LLM by add-requirement, plan-sprint and assist-task.\n\
+             # Globs are relative to the project root: `*` matches within a path segment, `**` across segments.\n\
+             # strategy: truncate, drop_low_priority, summarize (with summary_model = \"...\") or chunk.\n\n{}",
+            toml::to_string_pretty(&Self::default()).expect("default context config serializes")
+        )
+    }
+
+    fn included(&self, path: &str) -> bool {
+        self.include.iter().any(|pattern| glob_match(pattern, path))
+            && !self.exclude.iter().any(|pattern| glob_match(pattern, path))
+    }
+}
+
+fn segment_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => (0..=text.len()).any(|i| segment_match(rest, &text[i..])),
+        Some(('?', rest)) => !text.is_empty() && segment_match(rest, &text[1..]),
+        Some((c, rest)) => text.first() == Some(c) && segment_match(rest, &text[1..]),
+    }
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|i| segments_match(rest, &path[i..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((first, path_rest)) => {
+                let pattern: Vec<char> = segment.chars().collect();
+                let text: Vec<char> = first.chars().collect();
+                segment_match(&pattern, &text) && segments_match(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Whether `path` (relative, `/`-separated) matches the glob `pattern`.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.trim_start_matches("./").split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    segments_match(&pattern, &path)
+}
+
+pub(crate) fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn walk(root: &Path, dir: &Path, config: &ContextConfig, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|e| e.path())).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+            if !SKIPPED_DIRS.contains(&name.as_str()) {
+                walk(root, &path, config, files);
+            }
+        } else if config.included(&relative(root, &path)) {
+            files.push(path);
+        }
+    }
+}
+
+/// Files to show, in order: the always-included ones that exist, then the included ones
+/// sorted by path.
+pub fn collect_files(root: &Path, config: &ContextConfig) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = config.always_include.iter()
+        .map(|file| root.join(file))
+        .filter(|path| path.is_file())
+        .collect();
+    let mut matched = vec![];
+    walk(root, root, config, &mut matched);
+    for path in matched {
+        if !files.contains(&path) {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// The first `max_bytes` of `text`, cut at a character boundary.
+fn cut(text: &str, max_bytes: usize) -> &str {
+    let mut end = max_bytes.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// The files' contents as prompt sections, one per file, within the config's limits.
+/// Files that are not text are skipped.
+pub fn file_sections(root: &Path, files: &[PathBuf], config: &ContextConfig) -> Vec<String> {
+    let mut sections: Vec<String> = vec![];
+    let mut total = 0;
+    let mut left_out = 0;
+    for path in files {
+        let Ok(content) = std::fs::read_to_string(path) else { continue };
+        let shown = cut(&content, config.max_file_bytes);
+        if total + shown.len() > config.max_total_bytes {
+            left_out += 1;
+            continue;
+        }
+        total += shown.len();
+        let marker = if shown.len() < content.len() { "\n... (cut)" } else { "" };
+        sections.push(format!("### {}\n```\n{}{}\n```", relative(root, path), shown.trim_end(), marker));
+    }
+    if sections.is_empty() {
+        return vec![format!("No source files matched {}.", CONTEXT_FILE)];
+    }
+    if left_out > 0 {
+        sections.push(format!("({} more file(s) left out to keep the context short)", left_out));
+    }
+    sections
+}
+
+pub fn format_files(root: &Path, files: &[PathBuf], config: &ContextConfig) -> String {
+    file_sections(root, files, config).join("\n\n")
+}
+
+/// `{{code_context}}` for a prompt, one entry per file: the files `config` selects under
+/// `root`.
+pub fn code_context_entries(root: &Path, config: &ContextConfig) -> Vec<String> {
+    file_sections(root, &collect_files(root, config), config)
+}
+
+/// `{{code_context}}` for a prompt: the files `.env-coach/context.toml` selects under the
+/// current directory. A broken config is reported and the defaults are used.
+pub fn code_context() -> String {
+    let root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let config = ContextConfig::load_or_default(&root);
+    format_files(&root, &collect_files(&root, &config), &config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("src/**", "src/main.rs"));
+        assert!(glob_match("src/**", "src/scripts/tasks.rs"));
+        assert!(glob_match("**/*.rs", "main.rs"));
+        assert!(glob_match("src/*.rs", "src/lib.rs"));
+        assert!(!glob_match("src/*.rs", "src/scripts/tasks.rs"));
+        assert!(glob_match("src/**/test?.rs", "src/a/b/test1.rs"));
+        assert!(!glob_match("lib/**", "src/lib.rs"));
+    }
+
+    #[test]
+    fn test_collect_and_format_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/generated")).unwrap();
+        std::fs::create_dir_all(root.join("target/debug")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"demo\"").unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub mod parser;").unwrap();
+        std::fs::write(root.join("src/parser.rs"), "pub fn parse() {}\n".repeat(10)).unwrap();
+        std::fs::write(root.join("src/generated/schema.rs"), "// generated").unwrap();
+        std::fs::write(root.join("target/debug/out.rs"), "// build output").unwrap();
+        std::fs::create_dir_all(root.join(".env-coach")).unwrap();
+        std::fs::write(root.join(CONTEXT_FILE), "include = [\"src/**\", \"**/*.rs\"]\nexclude = [\"src/generated/**\"]\nalways_include = [\"Cargo.toml\", \"README.md\"]\nmax_file_bytes = 30\n").unwrap();
+
+        let config = ContextConfig::load(root).unwrap();
+        assert_eq!(config.max_total_bytes, ContextConfig::default().max_total_bytes);
+        let files: Vec<String> = collect_files(root, &config).iter().map(|path| relative(root, path)).collect();
+        assert_eq!(files, vec!["Cargo.toml", "src/lib.rs", "src/parser.rs"]);
+
+        let context = format_files(root, &collect_files(root, &config), &config);
+        assert!(context.starts_with("### Cargo.toml\n```\n[package]"));
+        assert!(context.contains("### src/parser.rs\n```\npub fn parse() {}\npub fn parse\n... (cut)\n```"));
+
+        let tight = ContextConfig { max_total_bytes: 45, ..config };
+        assert!(format_files(root, &collect_files(root, &tight), &tight).ends_with("(1 more file(s) left out to keep the context short)"));
+        assert_eq!(toml::from_str::<ContextConfig>(&ContextConfig::default_file_content()).unwrap(), ContextConfig::default());
+    }
+}
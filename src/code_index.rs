@@ -0,0 +1,347 @@
+// src/code_index.rs
+//! Embeddings index of the codebase, built by `env-coach index` and stored in
+//! `.env-coach/cache/embeddings.bin`. Source files (as selected by `.env-coach/context.toml`)
+//! are cut into chunks of lines, each embedded with Ollama, so prompts can include the
+//! chunks most relevant to a story instead of whole files. Backlog items are embedded too,
+//! for `find`.
+
+use anyhow::{Context, Result};
+use std::io::{Read, Write};
+use std::path::Path;
+
+use crate::auto_update::duplicates::cosine_similarity;
+use crate::config::{BacklogItem, Project};
+use crate::ollama;
+
+pub const INDEX_FILE: &str = ".env-coach/cache/embeddings.bin";
+/// Lines per chunk; consecutive chunks share `CHUNK_OVERLAP` lines.
+pub const CHUNK_LINES: usize = 40;
+const CHUNK_OVERLAP: usize = 5;
+/// Chunks given to `assist-task` and `execute-task`.
+pub const PROMPT_CHUNKS: usize = 5;
+/// How far below a file's best chunk its other chunks may score and still be listed by `find`.
+const RANGE_MARGIN: f32 = 0.05;
+
+const MAGIC: &[u8; 4] = b"ECIX";
+const FORMAT_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chunk {
+    /// Path relative to the project root, `/`-separated
+    pub path: String,
+    /// First and last line, 1-based
+    pub start_line: usize,
+    pub end_line: usize,
+    pub text: String,
+    pub embedding: Vec<f32>,
+}
+
+/// A file as it was when indexed, so unchanged files are not embedded again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedFile {
+    pub path: String,
+    pub hash: u64,
+}
+
+/// A backlog item's embedding, with the hash of the text it was made from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedItem {
+    pub id: String,
+    pub hash: u64,
+    pub embedding: Vec<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CodeIndex {
+    /// Model the embeddings come from; another model means a full re-index
+    pub model: String,
+    pub files: Vec<IndexedFile>,
+    pub chunks: Vec<Chunk>,
+    pub items: Vec<IndexedItem>,
+}
+
+/// A file found by `CodeIndex::top_files`: its best chunk score and the line ranges of its
+/// chunks that scored within `RANGE_MARGIN` of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileMatch {
+    pub path: String,
+    pub score: f32,
+    pub ranges: Vec<(usize, usize)>,
+}
+
+/// The text a backlog item is embedded by.
+pub fn item_text(item: &BacklogItem) -> String {
+    format!("{}\n{}\n{}", item.title, item.story, item.acceptance_criteria.join("\n"))
+}
+
+/// FNV-1a, stable across builds unlike `DefaultHasher`.
+pub fn content_hash(content: &str) -> u64 {
+    content.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Cut `content` into overlapping windows of `CHUNK_LINES` lines: (start line, end line, text).
+/// Blank windows are skipped.
+pub fn chunk_lines(content: &str) -> Vec<(usize, usize, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut chunks = vec![];
+    let mut start = 0;
+    while start < lines.len() {
+        let end = (start + CHUNK_LINES).min(lines.len());
+        let text = lines[start..end].join("\n");
+        if !text.trim().is_empty() {
+            chunks.push((start + 1, end, text));
+        }
+        if end == lines.len() {
+            break;
+        }
+        start = end - CHUNK_OVERLAP;
+    }
+    chunks
+}
+
+fn write_u32(out: &mut impl Write, value: u32) -> std::io::Result<()> {
+    out.write_all(&value.to_le_bytes())
+}
+
+fn write_str(out: &mut impl Write, value: &str) -> std::io::Result<()> {
+    write_u32(out, value.len() as u32)?;
+    out.write_all(value.as_bytes())
+}
+
+fn read_u32(input: &mut impl Read) -> std::io::Result<u32> {
+    let mut bytes = [0; 4];
+    input.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn write_embedding(out: &mut impl Write, embedding: &[f32]) -> std::io::Result<()> {
+    write_u32(out, embedding.len() as u32)?;
+    for value in embedding {
+        out.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn read_u64(input: &mut impl Read) -> std::io::Result<u64> {
+    let mut bytes = [0; 8];
+    input.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_embedding(input: &mut impl Read) -> std::io::Result<Vec<f32>> {
+    (0..read_u32(input)?)
+        .map(|_| {
+            let mut value = [0; 4];
+            input.read_exact(&mut value)?;
+            Ok(f32::from_le_bytes(value))
+        })
+        .collect()
+}
+
+fn read_str(input: &mut impl Read) -> Result<String> {
+    let mut bytes = vec![0; read_u32(input)? as usize];
+    input.read_exact(&mut bytes)?;
+    String::from_utf8(bytes).context("Index contains invalid text")
+}
+
+impl CodeIndex {
+    pub fn write_to(&self, out: &mut impl Write) -> Result<()> {
+        out.write_all(MAGIC)?;
+        write_u32(out, FORMAT_VERSION)?;
+        write_str(out, &self.model)?;
+        write_u32(out, self.files.len() as u32)?;
+        for file in &self.files {
+            write_str(out, &file.path)?;
+            out.write_all(&file.hash.to_le_bytes())?;
+        }
+        write_u32(out, self.chunks.len() as u32)?;
+        for chunk in &self.chunks {
+            write_str(out, &chunk.path)?;
+            write_u32(out, chunk.start_line as u32)?;
+            write_u32(out, chunk.end_line as u32)?;
+            write_str(out, &chunk.text)?;
+            write_embedding(out, &chunk.embedding)?;
+        }
+        write_u32(out, self.items.len() as u32)?;
+        for item in &self.items {
+            write_str(out, &item.id)?;
+            out.write_all(&item.hash.to_le_bytes())?;
+            write_embedding(out, &item.embedding)?;
+        }
+        Ok(())
+    }
+
+    pub fn read_from(input: &mut impl Read) -> Result<Self> {
+        let mut magic = [0; 4];
+        input.read_exact(&mut magic).context("Index file is empty")?;
+        if &magic != MAGIC {
+            anyhow::bail!("Not an env-coach index file");
+        }
+        let version = read_u32(input)?;
+        if version != FORMAT_VERSION {
+            anyhow::bail!("Index format {} is not supported; run `env-coach index` again", version);
+        }
+        let model = read_str(input)?;
+        let files = (0..read_u32(input)?)
+            .map(|_| {
+                let path = read_str(input)?;
+                Ok(IndexedFile { path, hash: read_u64(input)? })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let chunks = (0..read_u32(input)?)
+            .map(|_| {
+                let path = read_str(input)?;
+                let start_line = read_u32(input)? as usize;
+                let end_line = read_u32(input)? as usize;
+                let text = read_str(input)?;
+                Ok(Chunk { path, start_line, end_line, text, embedding: read_embedding(input)? })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let items = (0..read_u32(input)?)
+            .map(|_| {
+                let id = read_str(input)?;
+                Ok(IndexedItem { id, hash: read_u64(input)?, embedding: read_embedding(input)? })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { model, files, chunks, items })
+    }
+
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(INDEX_FILE);
+        let bytes = std::fs::read(&path).with_context(|| format!("No index at {}; run `env-coach index`", path.display()))?;
+        Self::read_from(&mut bytes.as_slice()).with_context(|| format!("Failed to read {}", path.display()))
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let path = root.join(INDEX_FILE);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let mut bytes = vec![];
+        self.write_to(&mut bytes)?;
+        std::fs::write(&path, bytes).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// The `k` chunks most similar to `query`, best first.
+    pub fn top_k(&self, query: &[f32], k: usize) -> Vec<(&Chunk, f32)> {
+        let mut scored: Vec<(&Chunk, f32)> = self.chunks.iter()
+            .map(|chunk| (chunk, cosine_similarity(query, &chunk.embedding)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(k);
+        scored
+    }
+
+    /// The `k` files with the chunks most similar to `query`, best first.
+    pub fn top_files(&self, query: &[f32], k: usize) -> Vec<FileMatch> {
+        let mut files: Vec<FileMatch> = vec![];
+        for (chunk, score) in self.top_k(query, self.chunks.len()) {
+            match files.iter_mut().find(|file| file.path == chunk.path) {
+                Some(file) => {
+                    if file.score - score <= RANGE_MARGIN {
+                        file.ranges.push((chunk.start_line, chunk.end_line));
+                    }
+                }
+                None => files.push(FileMatch { path: chunk.path.clone(), score, ranges: vec![(chunk.start_line, chunk.end_line)] }),
+            }
+        }
+        files.truncate(k);
+        for file in files.iter_mut() {
+            file.ranges.sort();
+        }
+        files
+    }
+
+    /// Embed the backlog items that are new or changed since they were indexed, and drop
+    /// items no longer in the backlog. Returns how many were embedded.
+    pub async fn embed_items(&mut self, project: &Project) -> Result<usize> {
+        let mut items = vec![];
+        let mut embedded = 0;
+        for item in &project.backlog {
+            let text = item_text(item);
+            let hash = content_hash(&text);
+            match self.items.iter().find(|indexed| indexed.id == item.id && indexed.hash == hash) {
+                Some(indexed) => items.push(indexed.clone()),
+                None => {
+                    let embedding = ollama::generate_embedding(project.llm(), &text)
+                        .await
+                        .with_context(|| format!("Failed to embed {}", item.id))?;
+                    items.push(IndexedItem { id: item.id.clone(), hash, embedding });
+                    embedded += 1;
+                }
+            }
+        }
+        self.items = items;
+        Ok(embedded)
+    }
+}
+
+fn format_chunk(chunk: &Chunk) -> String {
+    format!("### {} (lines {}-{})\n```\n{}\n```", chunk.path, chunk.start_line, chunk.end_line, chunk.text)
+}
+
+/// `{{relevant_code}}` for a task prompt, one entry per chunk, best first: the indexed
+/// chunks closest to the item's title, story and criteria. Without an index (or
+/// embeddings) a hint to build one is given instead.
+pub async fn relevant_code(project: &Project, item: &BacklogItem) -> Vec<String> {
+    let root = std::env::current_dir().unwrap_or_else(|_| ".".into());
+    let Ok(index) = CodeIndex::load(&root) else {
+        return vec!["No code index yet (run `env-coach index`).".to_string()];
+    };
+    if index.chunks.is_empty() {
+        return vec!["The code index is empty.".to_string()];
+    }
+    let query = format!("{}\n{}\n{}", item.title, item.story, item.acceptance_criteria.join("\n"));
+    match ollama::generate_embedding(project.llm(), &query).await {
+        Ok(embedding) => {
+            let chunks = index.top_k(&embedding, PROMPT_CHUNKS);
+            println!("📇 Adding {} relevant code chunk(s) from the index", chunks.len());
+            chunks.iter().map(|(chunk, _)| format_chunk(chunk)).collect()
+        }
+        Err(e) => {
+            println!("⚠️  Could not search the code index ({}); continuing without it.", e);
+            vec!["Code search unavailable.".to_string()]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_lines() {
+        let content: String = (1..=80).map(|i| format!("line {}\n", i)).collect();
+        let chunks = chunk_lines(&content);
+        let ranges: Vec<(usize, usize)> = chunks.iter().map(|(start, end, _)| (*start, *end)).collect();
+        assert_eq!(ranges, vec![(1, 40), (36, 75), (71, 80)]);
+        assert!(chunks[2].2.starts_with("line 71\n"));
+        assert!(chunk_lines("\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_index_round_trip_and_search() {
+        let index = CodeIndex {
+            model: "nomic-embed-text".to_string(),
+            files: vec![IndexedFile { path: "src/auth.rs".to_string(), hash: content_hash("fn login() {}") }],
+            chunks: vec![
+                Chunk { path: "src/auth.rs".to_string(), start_line: 1, end_line: 1, text: "fn login() {}".to_string(), embedding: vec![1.0, 0.0] },
+                Chunk { path: "src/cart.rs".to_string(), start_line: 3, end_line: 9, text: "fn add_item() {}".to_string(), embedding: vec![0.0, 1.0] },
+                Chunk { path: "src/cart.rs".to_string(), start_line: 40, end_line: 60, text: "fn total() {}".to_string(), embedding: vec![0.1, 1.0] },
+            ],
+            items: vec![IndexedItem { id: "US-001".to_string(), hash: 7, embedding: vec![0.5, 0.5] }],
+        };
+        let mut bytes = vec![];
+        index.write_to(&mut bytes).unwrap();
+        assert_eq!(CodeIndex::read_from(&mut bytes.as_slice()).unwrap(), index);
+        assert!(CodeIndex::read_from(&mut &b"{\"json\": true}"[..]).is_err());
+
+        let found = index.top_k(&[0.0, 1.0], 1);
+        assert_eq!(found[0].0.path, "src/cart.rs");
+        assert_eq!(format_chunk(found[0].0), "### src/cart.rs (lines 3-9)\n```\nfn add_item() {}\n```");
+
+        let files = index.top_files(&[0.0, 1.0], 5);
+        let files: Vec<(&str, Vec<(usize, usize)>)> = files.iter().map(|file| (file.path.as_str(), file.ranges.clone())).collect();
+        assert_eq!(files, vec![("src/cart.rs", vec![(3, 9), (40, 60)]), ("src/auth.rs", vec![(1, 1)])]);
+    }
+}
@@ -0,0 +1,436 @@
+// src/context.rs
+//! Shared runtime context handed to every command.
+//!
+//! `main.rs` builds a single `AppContext` from the CLI-wide flags and the global
+//! configuration, and scripts use it instead of loading configuration on their own.
+
+use anyhow::{Context, Result};
+use clap::Args;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+
+use chrono::Utc;
+
+use crate::audit;
+use crate::config::{self, BacklogItem, FinalLlmConfig, GlobalConfig, PartialLlmConfig, Project, SettingSource};
+use crate::output::{self, Format, Writer};
+use crate::recovery;
+use crate::scripts::filter::BacklogFilter;
+use crate::storage;
+use crate::team;
+use crate::workspace::{self, Workspace};
+
+/// Flags accepted by every subcommand.
+#[derive(Args, Debug, Default, Clone)]
+pub struct GlobalArgs {
+    /// Run as if env-coach was started in <DIR>
+    #[arg(long, global = true, value_name = "DIR")]
+    pub project: Option<PathBuf>,
+    /// Answer "yes" to every confirmation prompt
+    #[arg(short = 'y', long, global = true, conflicts_with = "no")]
+    pub yes: bool,
+    /// Answer "no" to every confirmation prompt, skipping every proposed change
+    #[arg(long, global = true)]
+    pub no: bool,
+    /// Never read from stdin: confirmations are answered "no" (unless --yes) and questions
+    /// take their default. Implied when stdin is not a terminal
+    #[arg(long, global = true)]
+    pub non_interactive: bool,
+    /// Show what would happen without writing project.json
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+    /// When another command is changing the project, wait for it instead of failing
+    #[arg(long, global = true)]
+    pub wait: bool,
+    /// Override the LLM model for this invocation
+    #[arg(long, global = true)]
+    pub model: Option<String>,
+    /// Override the Ollama host for this invocation
+    #[arg(long, global = true)]
+    pub host: Option<String>,
+    /// Override the LLM request timeout (milliseconds) for this invocation
+    #[arg(long, global = true, value_name = "MS")]
+    pub timeout_ms: Option<u64>,
+    /// Use a named LLM preset (profile) from the global config (model, timeout, temperature...)
+    #[arg(long, global = true, value_name = "NAME", visible_alias = "profile")]
+    pub preset: Option<String>,
+    /// Acknowledge running a maintainer-only operation as a contributor (team mode)
+    #[arg(long, global = true)]
+    pub as_maintainer: bool,
+    /// Output of read commands: text, or structured json/yaml for scripts and editors
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub format: Format,
+    /// Write the command's result to FILE instead of stdout: --format json/yaml data, an
+    /// export or a sprint report
+    #[arg(short, long, global = true, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+/// Asks the user questions on stdin, honouring `--yes`, `--no` and `--non-interactive`.
+/// The default never reads stdin, so code driven by tests cannot hang on a prompt.
+#[derive(Debug, Clone, Default)]
+pub struct Prompter {
+    /// Fixed answer to confirmations (`--yes` / `--no`)
+    answer: Option<bool>,
+    interactive: bool,
+}
+
+impl Prompter {
+    pub fn new(answer: Option<bool>, interactive: bool) -> Self {
+        Self { answer, interactive }
+    }
+
+    /// Ask a yes/no question. Answers without asking under `--yes`/`--no`, and "no" when
+    /// not interactive.
+    pub fn confirm(&self, question: &str) -> Result<bool> {
+        match self.answer {
+            Some(true) => println!("{} (yes/no): yes [--yes]", question),
+            Some(false) => println!("{} (yes/no): no [--no]", question),
+            None if !self.interactive => println!("{} (yes/no): no [non-interactive]", question),
+            None => {
+                let answer = self.ask(&format!("{} (yes/no): ", question))?;
+                return Ok(matches!(answer.to_lowercase().as_str(), "yes" | "y"));
+            }
+        }
+        Ok(self.answer.unwrap_or(false))
+    }
+
+    /// Ask a free-form question and return the trimmed answer (empty on EOF, and without
+    /// reading stdin when not interactive).
+    pub fn ask(&self, question: &str) -> Result<String> {
+        if !self.interactive {
+            println!("{}[non-interactive]", question);
+            return Ok(String::new());
+        }
+        print!("{}", question);
+        io::stdout().flush()?;
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        Ok(answer.trim().to_string())
+    }
+
+    pub fn assume_yes(&self) -> bool {
+        self.answer == Some(true)
+    }
+}
+
+/// Everything a command needs besides its own arguments.
+#[derive(Debug, Clone, Default)]
+pub struct AppContext {
+    pub global_config: GlobalConfig,
+    /// Per-invocation LLM settings; these win over project and global config.
+    pub llm_overrides: PartialLlmConfig,
+    /// `--preset` given on the command line; it replaces the project's default preset
+    pub preset: Option<String>,
+    pub dry_run: bool,
+    pub prompter: Prompter,
+    /// `--as-maintainer` was given
+    pub as_maintainer: bool,
+    /// `--format` of read commands
+    pub format: Format,
+    /// Where the command's result goes (`--output`)
+    pub output: Writer,
+}
+
+impl AppContext {
+    pub fn new(args: GlobalArgs) -> Result<Self> {
+        // Relative to where env-coach was started, not to --project
+        let output = args.output.as_deref().map(std::path::absolute).transpose().context("Invalid --output path")?;
+        if let Some(dir) = &args.project {
+            std::env::set_current_dir(dir)
+                .with_context(|| format!("Failed to switch to project directory {:?}", dir))?;
+        }
+
+        let global_config = GlobalConfig::load().context("Failed to load global env-coach configuration")?;
+        // A preset replaces the configured settings it names; --model, --host and --timeout-ms
+        // still win over it
+        let mut llm_overrides = match &args.preset {
+            Some(name) => global_config.preset(name)?.clone(),
+            None => PartialLlmConfig::default(),
+        };
+        if args.model.is_some() {
+            llm_overrides.model = args.model;
+            llm_overrides.roles.clear();
+        }
+        if args.host.is_some() {
+            llm_overrides.host = args.host;
+        }
+        if args.timeout_ms.is_some() {
+            llm_overrides.timeout_ms = args.timeout_ms;
+        }
+
+        Ok(Self {
+            global_config,
+            llm_overrides,
+            preset: args.preset,
+            dry_run: args.dry_run,
+            prompter: Prompter::new(
+                if args.yes { Some(true) } else if args.no { Some(false) } else { None },
+                !args.non_interactive && io::stdin().is_terminal(),
+            ),
+            as_maintainer: args.as_maintainer,
+            format: args.format,
+            output: Writer::new(output),
+        })
+    }
+
+    /// Print a command's data as JSON or YAML (`--format`), or write it to the `--output`
+    /// file. Field names are part of env-coach's interface: add fields freely, but don't
+    /// rename or remove them.
+    pub fn print_structured<T: serde::Serialize>(&self, value: &T) -> Result<()> {
+        self.output.write(&output::to_structured(self.format, value)?)?;
+        // On stderr, so stdout stays clean for pipes
+        if let Some(path) = self.output.path().filter(|_| output::enabled(output::Topic::Files, output::Level::Normal)) {
+            eprintln!("📄 Wrote {}", path.display());
+        }
+        Ok(())
+    }
+
+    /// Load project.json with the LLM configuration resolved against global config and CLI overrides.
+    /// Inside a workspace, the repository's shared backlog items are merged in.
+    pub fn load_project(&self) -> Result<Project> {
+        let mut project = Project::load_with(&self.global_config, Some(&self.llm_overrides))?;
+        self.apply_project_preset(&mut project)?;
+        let cwd = std::env::current_dir().context("Failed to determine project directory")?;
+        if let Some(workspace_path) = workspace::find_workspace_file(&cwd) {
+            let shared = Workspace::load(&workspace_path)?;
+            workspace::attach(&mut project, &workspace_path, &shared, &cwd);
+        }
+        Ok(project)
+    }
+
+    /// For read commands over part of the backlog: the project with only the items matching
+    /// `filter` and `terms` (see `Storage::query`), and the size of the whole backlog, when
+    /// its storage can look them up by index. `None` means use `load_project` and filter.
+    pub fn load_matching(&self, filter: &BacklogFilter, terms: &[&str]) -> Result<Option<(Project, usize)>> {
+        let cwd = std::env::current_dir().context("Failed to determine project directory")?;
+        // Shared workspace items are only known once merged in
+        if workspace::find_workspace_file(&cwd).is_some() {
+            return Ok(None);
+        }
+        let mut project = Project::load_meta_with(&self.global_config, Some(&self.llm_overrides))?;
+        // Items still in the project file are not indexed yet
+        if !project.backlog.is_empty() {
+            return Ok(None);
+        }
+        let Some(matches) = storage::for_project(&project.meta, &Project::file()?)?.query(filter, terms)? else {
+            return Ok(None);
+        };
+        self.apply_project_preset(&mut project)?;
+        project.backlog = matches.items;
+        Ok(Some((project, matches.total)))
+    }
+
+    /// Resolve the LLM settings of the project's default preset unless `--preset` was given.
+    fn apply_project_preset(&self, project: &mut Project) -> Result<()> {
+        if self.preset.is_none() {
+            if let Some(preset) = self.project_preset(project)? {
+                // Applied like --preset: over the project's settings, under --model
+                project.resolved_llm_config.apply_overrides(preset);
+                project.resolved_llm_config.apply_overrides(&self.llm_overrides);
+            }
+        }
+        Ok(())
+    }
+
+    /// The preset in use for `project`: `--preset`, else the project's `preset`.
+    pub fn active_preset<'a>(&'a self, project: Option<&'a Project>) -> Option<&'a str> {
+        self.preset.as_deref().or(project.and_then(|project| project.meta.preset.as_deref()))
+    }
+
+    /// Where each resolved LLM setting comes from, for `status` and `config`.
+    pub fn llm_setting_sources(&self, project: Option<&Project>, resolved: &FinalLlmConfig) -> Vec<SettingSource> {
+        let preset = self.active_preset(project).and_then(|name| self.global_config.presets.get(name));
+        config::llm_setting_sources(self.global_config.llm.as_ref(), project.and_then(|project| project.meta.llm.as_ref()), preset, resolved)
+    }
+
+    /// Settings of the project's default preset, when it names one.
+    fn project_preset(&self, project: &Project) -> Result<Option<&PartialLlmConfig>> {
+        match &project.meta.preset {
+            Some(name) => self.global_config.preset(name)
+                .map(Some)
+                .context("project.json names a preset the global config does not define"),
+            None => Ok(None),
+        }
+    }
+
+    /// Persist the project unless `--dry-run` was given. Shared workspace items go back to
+    /// the workspace file, everything else to project.json; both writes are journaled so a
+    /// crash in between can be rolled back on the next start.
+    pub fn save_project(&self, project: &Project) -> Result<()> {
+        if self.dry_run {
+            println!("🔍 Dry run: project.json not written");
+            return Ok(());
+        }
+        let mut project = project.clone();
+        let previous = previous_items(&project);
+        let now = Utc::now();
+        let user = self.acting_user();
+        team::record_history(&mut project, &previous, &user, now);
+        let events = audit::changes(&previous, &project.backlog, now, &user, &audit::command_line());
+        let (local, shared) = workspace::detach(&project);
+
+        let mut files = local.storage_files()?;
+        files.extend(project.workspace.as_ref().map(|slice| slice.path.clone()));
+        let root = std::env::current_dir().context("Failed to determine project directory")?;
+        let transaction = recovery::Transaction::begin(&root, &files)?;
+        if let Some(slice) = &project.workspace {
+            let mut current = Workspace::load(&slice.path)?;
+            workspace::write_back(&mut current, slice, shared);
+            current.save(&slice.path)?;
+        }
+        local.save()?;
+        transaction.commit()?;
+        // Projects set up by `init` have the directory; the log is not worth creating it for
+        if root.join(".env-coach").is_dir() {
+            audit::append(&root, &events)?;
+        }
+        Ok(())
+    }
+
+    /// Who is running this command, for history and team roles.
+    pub fn acting_user(&self) -> String {
+        team::resolve_user(&self.global_config)
+    }
+
+    /// `require_maintainer` against the project in the current directory, for commands gated
+    /// before they run (see `Commands::maintainer_operation`); passes without a project.
+    pub fn require_maintainer_here(&self, operation: &str) -> Result<()> {
+        if !Project::is_initialized() {
+            return Ok(());
+        }
+        let project = Project::load_meta_with(&self.global_config, None)?;
+        self.require_maintainer(&project, operation)
+    }
+
+    /// True for a maintainer of `project` (everyone outside team mode) and under
+    /// `--as-maintainer`; for offers such as closing an expired sprint, which contributors
+    /// should leave to a maintainer rather than be warned or refused halfway.
+    pub fn acts_as_maintainer(&self, project: &Project) -> bool {
+        self.as_maintainer || team::is_maintainer(project.meta.team.as_ref(), &self.acting_user())
+    }
+
+    /// Gate a destructive operation in team mode. Maintainers pass; contributors need
+    /// `--as-maintainer` unless the team config only asks for a warning.
+    pub fn require_maintainer(&self, project: &Project, operation: &str) -> Result<()> {
+        let user = self.acting_user();
+        let team = project.meta.team.as_ref();
+        if team::is_maintainer(team, &user) {
+            return Ok(());
+        }
+        let require_acknowledgment = team.is_some_and(|t| t.require_acknowledgment);
+        if self.as_maintainer || !require_acknowledgment {
+            println!("⚠️  {} is not a maintainer of this project; running '{}' anyway", user, operation);
+            return Ok(());
+        }
+        anyhow::bail!(
+            "'{}' is a maintainer operation and {} is a contributor. Re-run with --as-maintainer to acknowledge.",
+            operation, user
+        )
+    }
+}
+
+/// Backlog items as currently saved (project.json plus this repository's workspace slice),
+/// used to work out what a save changes.
+fn previous_items(project: &Project) -> Vec<BacklogItem> {
+    let mut items = Project::file()
+        .and_then(|path| Project::read_file(&path))
+        .map(|saved| saved.backlog)
+        .unwrap_or_default();
+    if let Some(slice) = &project.workspace {
+        if let Ok(shared) = Workspace::load(&slice.path) {
+            items.extend(shared.items_for(&slice.repo).cloned());
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TeamConfig;
+
+    fn ctx_for(user: &str, as_maintainer: bool) -> AppContext {
+        AppContext {
+            global_config: GlobalConfig { user: Some(user.to_string()), ..Default::default() },
+            as_maintainer,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_require_maintainer_gates_contributors() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        assert!(ctx_for("bob", false).require_maintainer(&project, "remove-item").is_ok());
+
+        project.meta.team = Some(TeamConfig { maintainers: vec!["alice".to_string()], require_acknowledgment: true });
+        assert!(ctx_for("alice", false).require_maintainer(&project, "remove-item").is_ok());
+        assert!(ctx_for("bob", false).require_maintainer(&project, "remove-item").is_err());
+        assert!(ctx_for("bob", true).require_maintainer(&project, "remove-item").is_ok());
+
+        project.meta.team.as_mut().unwrap().require_acknowledgment = false;
+        assert!(ctx_for("bob", false).require_maintainer(&project, "remove-item").is_ok());
+    }
+
+    #[test]
+    fn test_acts_as_maintainer_ignores_require_acknowledgment() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        assert!(ctx_for("bob", false).acts_as_maintainer(&project));
+
+        project.meta.team = Some(TeamConfig { maintainers: vec!["alice".to_string()], require_acknowledgment: false });
+        assert!(ctx_for("Alice", false).acts_as_maintainer(&project));
+        assert!(!ctx_for("bob", false).acts_as_maintainer(&project));
+        assert!(ctx_for("bob", true).acts_as_maintainer(&project));
+    }
+
+    #[test]
+    fn test_project_preset_unless_given_on_command_line() {
+        let fast = PartialLlmConfig { model: Some("llama3.2:3b".to_string()), ..Default::default() };
+        let mut ctx = AppContext {
+            global_config: GlobalConfig { presets: [("fast".to_string(), fast)].into_iter().collect(), ..Default::default() },
+            ..Default::default()
+        };
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        assert_eq!(ctx.active_preset(Some(&project)), None);
+        assert!(ctx.project_preset(&project).unwrap().is_none());
+
+        project.meta.preset = Some("fast".to_string());
+        assert_eq!(ctx.active_preset(Some(&project)), Some("fast"));
+        assert_eq!(ctx.project_preset(&project).unwrap().unwrap().model.as_deref(), Some("llama3.2:3b"));
+        let mut resolved = project.llm().clone();
+        resolved.apply_overrides(ctx.project_preset(&project).unwrap().unwrap());
+        let model = ctx.llm_setting_sources(Some(&project), &resolved).remove(0);
+        assert_eq!((model.value.as_str(), model.source), ("llama3.2:3b", "Preset (--preset or the project's preset)"));
+
+        ctx.preset = Some("thorough".to_string());
+        assert_eq!(ctx.active_preset(Some(&project)), Some("thorough"));
+        project.meta.preset = Some("slow".to_string());
+        assert!(ctx.project_preset(&project).is_err());
+    }
+
+    #[test]
+    fn test_llm_flags_override_for_one_invocation() {
+        let ctx = AppContext::new(GlobalArgs {
+            model: Some("qwen2.5-coder:7b".to_string()),
+            host: Some("gpu.lan".to_string()),
+            timeout_ms: Some(300000),
+            ..Default::default()
+        }).unwrap();
+        let mut resolved = Project::new("P".to_string(), "".to_string(), None).llm().clone();
+        resolved.apply_overrides(&ctx.llm_overrides);
+        assert_eq!((resolved.model.as_str(), resolved.host.as_str(), resolved.timeout_ms), ("qwen2.5-coder:7b", "gpu.lan", 300000));
+        let sources = ctx.llm_setting_sources(None, &resolved);
+        for key in ["model", "host", "timeout_ms"] {
+            let setting = sources.iter().find(|setting| setting.key == key).unwrap();
+            assert_eq!(setting.source, "Command line (--model/--host/--timeout-ms)");
+        }
+    }
+
+    #[test]
+    fn test_prompter_answers_without_stdin() {
+        assert!(Prompter::new(Some(true), false).confirm("Apply?").unwrap());
+        assert!(!Prompter::new(Some(false), true).confirm("Apply?").unwrap());
+        assert!(!Prompter::default().confirm("Apply?").unwrap());
+        assert_eq!(Prompter::default().ask("Goal: ").unwrap(), "");
+    }
+}
@@ -0,0 +1,90 @@
+// src/git.rs
+//! Thin wrappers around the `git` command line used by env-coach commands.
+
+use anyhow::{Context, Result};
+use std::process::Command;
+
+/// Run `git <args>` in the current directory and return its stdout.
+pub fn run(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run 'git {}'. Is git installed?", args.join(" ")))?;
+    if !output.status.success() {
+        anyhow::bail!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Paths of the files currently staged for commit.
+pub fn staged_files() -> Result<Vec<String>> {
+    Ok(run(&["diff", "--cached", "--name-only"])?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Paths with uncommitted changes, staged or not.
+pub fn changed_files() -> Result<Vec<String>> {
+    Ok(run(&["diff", "HEAD", "--name-only"])?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Run `git <args> -- .`, leaving out the `excluded` paths.
+fn run_excluding(args: &[&str], excluded: &[&str]) -> Result<String> {
+    let pathspecs: Vec<String> = excluded.iter().map(|path| format!(":(exclude){}", path)).collect();
+    let mut full = args.to_vec();
+    full.extend(["--", "."]);
+    full.extend(pathspecs.iter().map(String::as_str));
+    run(&full)
+}
+
+/// `git status --short`: changed and untracked paths, one per line, leaving out `excluded`.
+pub fn status_short(excluded: &[&str]) -> Result<String> {
+    run_excluding(&["status", "--short"], excluded)
+}
+
+/// Uncommitted changes to tracked files, staged or not (`git diff` before the first commit),
+/// leaving out `excluded`.
+pub fn uncommitted_diff(excluded: &[&str]) -> Result<String> {
+    run_excluding(&["diff", "HEAD"], excluded).or_else(|_| run_excluding(&["diff"], excluded))
+}
+
+/// Paths changed by commits whose message mentions `id` (e.g. `Refs: US-003`), most
+/// recently changed first.
+pub fn files_changed_for(id: &str) -> Result<Vec<String>> {
+    let mut files: Vec<String> = vec![];
+    for line in run(&["log", "--fixed-strings", &format!("--grep={}", id), "--name-only", "--format="])?.lines() {
+        if !line.is_empty() && !files.iter().any(|file| file == line) {
+            files.push(line.to_string());
+        }
+    }
+    Ok(files)
+}
+
+/// Patches of the commits whose message mentions `id`, oldest first, followed by the
+/// uncommitted changes.
+pub fn diff_for(id: &str) -> Result<String> {
+    let committed = run(&["log", "--reverse", "--fixed-strings", &format!("--grep={}", id), "--patch", "--format=commit %h %s"])?;
+    let uncommitted = run(&["diff", "HEAD"]).unwrap_or_default();
+    Ok(format!("{}{}", committed, uncommitted))
+}
+
+/// The branch work is merged into: `origin/HEAD` if configured, otherwise `main` or `master`.
+pub fn default_branch() -> Result<String> {
+    if let Ok(head) = run(&["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]) {
+        if let Some(branch) = head.trim().strip_prefix("origin/") {
+            return Ok(branch.to_string());
+        }
+    }
+    for candidate in ["main", "master"] {
+        if run(&["rev-parse", "--verify", "--quiet", &format!("refs/heads/{}", candidate)]).is_ok() {
+            return Ok(candidate.to_string());
+        }
+    }
+    anyhow::bail!("Could not determine the default branch (no origin/HEAD, main or master)")
+}
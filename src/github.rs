@@ -0,0 +1,140 @@
+// src/github.rs
+//! Minimal client for the GitHub Issues REST API, used by `github sync` and `complete-task`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::git;
+use crate::links;
+
+const API_URL: &str = "https://api.github.com";
+const TIMEOUT_MS: u64 = 30000;
+/// Environment variables checked for an API token, in order.
+pub const TOKEN_VARS: [&str; 2] = ["GITHUB_TOKEN", "GH_TOKEN"];
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Issue {
+    pub number: u64,
+    /// `open` or `closed`
+    pub state: String,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    #[serde(default)]
+    pub closed_at: Option<DateTime<Utc>>,
+    pub html_url: String,
+}
+
+impl Issue {
+    pub fn is_closed(&self) -> bool {
+        self.state == "closed"
+    }
+}
+
+/// Issue number of a `github:42` (or `github:#42`) reference.
+pub fn issue_number(external_ref: &str) -> Option<u64> {
+    let (tracker, id) = external_ref.trim().split_once(':')?;
+    if !tracker.trim().eq_ignore_ascii_case("github") {
+        return None;
+    }
+    id.trim().trim_start_matches('#').parse().ok()
+}
+
+pub fn issue_ref(number: u64) -> String {
+    format!("github:{}", number)
+}
+
+pub struct GitHubClient {
+    client: Client,
+    token: String,
+    repo: String,
+}
+
+impl GitHubClient {
+    /// Client for the repository `origin` points at (or `$GITHUB_REPOSITORY`, as set in
+    /// GitHub Actions), authenticated with the first token found in [`TOKEN_VARS`].
+    pub fn from_env() -> Result<Self> {
+        let token = TOKEN_VARS.iter()
+            .find_map(|var| std::env::var(var).ok().filter(|token| !token.trim().is_empty()))
+            .with_context(|| format!("No GitHub token found. Set {} to a token with access to the repository's issues.", TOKEN_VARS.join(" or ")))?;
+        let repo = match std::env::var("GITHUB_REPOSITORY") {
+            Ok(repo) if repo.contains('/') => repo,
+            _ => {
+                let remote = git::run(&["remote", "get-url", "origin"])
+                    .context("Failed to read the 'origin' remote to find the GitHub repository")?;
+                links::remote_path(remote.trim(), "github.com")
+                    .with_context(|| format!("'origin' ({}) is not a GitHub repository", remote.trim()))?
+                    .to_string()
+            }
+        };
+        let client = Client::builder()
+            .timeout(Duration::from_millis(TIMEOUT_MS))
+            .user_agent(concat!("env-coach/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to build HTTP client for GitHub")?;
+        Ok(Self { client, token: token.trim().to_string(), repo })
+    }
+
+    /// `owner/repo`
+    pub fn repo(&self) -> &str {
+        &self.repo
+    }
+
+    fn request(&self, builder: RequestBuilder) -> RequestBuilder {
+        builder
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    }
+
+    async fn send(&self, builder: RequestBuilder, action: &str) -> Result<reqwest::Response> {
+        let res = self.request(builder).send().await.with_context(|| format!("Failed to reach GitHub to {}", action))?;
+        let status = res.status();
+        if !status.is_success() {
+            let body = res.text().await.unwrap_or_default();
+            anyhow::bail!("GitHub refused to {} (HTTP {}): {}", action, status, body.trim());
+        }
+        Ok(res)
+    }
+
+    pub async fn issue(&self, number: u64) -> Result<Issue> {
+        let url = format!("{}/repos/{}/issues/{}", API_URL, self.repo, number);
+        let res = self.send(self.client.get(&url), &format!("read issue #{}", number)).await?;
+        res.json().await.with_context(|| format!("Failed to parse GitHub issue #{}", number))
+    }
+
+    pub async fn create_issue(&self, title: &str, body: &str, labels: &[String]) -> Result<Issue> {
+        let url = format!("{}/repos/{}/issues", API_URL, self.repo);
+        let payload = serde_json::json!({ "title": title, "body": body, "labels": labels });
+        let res = self.send(self.client.post(&url).json(&payload), &format!("create an issue for '{}'", title)).await?;
+        res.json().await.context("Failed to parse the created GitHub issue")
+    }
+
+    pub async fn close_issue(&self, number: u64) -> Result<()> {
+        let url = format!("{}/repos/{}/issues/{}", API_URL, self.repo, number);
+        let payload = serde_json::json!({ "state": "closed", "state_reason": "completed" });
+        self.send(self.client.patch(&url).json(&payload), &format!("close issue #{}", number)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_number() {
+        assert_eq!(issue_number("github:42"), Some(42));
+        assert_eq!(issue_number("GitHub:#7"), Some(7));
+        assert_eq!(issue_number("jira:PROJ-7"), None);
+        assert_eq!(issue_number("github:abc"), None);
+        assert_eq!(issue_number(&issue_ref(12)), Some(12));
+    }
+}
@@ -0,0 +1,137 @@
+// src/gitlab.rs
+//! Minimal client for the GitLab REST API (v4), used by `gitlab push`.
+
+use anyhow::{Context, Result};
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::git;
+use crate::links;
+
+const DEFAULT_URL: &str = "https://gitlab.com";
+const TIMEOUT_MS: u64 = 30000;
+/// Environment variable holding the API token.
+pub const TOKEN_VAR: &str = "GITLAB_TOKEN";
+/// Environment variable with the instance URL, for self-managed GitLab.
+pub const URL_VAR: &str = "GITLAB_URL";
+
+/// An issue or epic as returned on creation: `id` is global, `iid` is what the UI shows.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Created {
+    pub id: u64,
+    pub iid: u64,
+    pub web_url: String,
+}
+
+/// Project-level issue reference, e.g. `gitlab:12`.
+pub fn issue_ref(iid: u64) -> String {
+    format!("gitlab:{}", iid)
+}
+
+/// Group-level epic reference, e.g. `gitlab-epic:3`.
+pub fn epic_ref(iid: u64) -> String {
+    format!("gitlab-epic:{}", iid)
+}
+
+/// Number of a `<tracker>:<iid>` reference for the given tracker.
+pub fn ref_iid(external_ref: &str, tracker: &str) -> Option<u64> {
+    let (prefix, id) = external_ref.trim().split_once(':')?;
+    if !prefix.trim().eq_ignore_ascii_case(tracker) {
+        return None;
+    }
+    id.trim().trim_start_matches(['#', '&']).parse().ok()
+}
+
+/// Path segments are sent URL-encoded as a single ID (`acme%2Fapi`).
+fn encode_path(path: &str) -> String {
+    path.replace('/', "%2F")
+}
+
+pub struct GitLabClient {
+    client: Client,
+    token: String,
+    base_url: String,
+    /// `group/project`, possibly with subgroups
+    project: String,
+}
+
+impl GitLabClient {
+    /// Client for the project `origin` points at, on gitlab.com or the instance in
+    /// `$GITLAB_URL`, authenticated with `$GITLAB_TOKEN`.
+    pub fn from_env() -> Result<Self> {
+        let token = std::env::var(TOKEN_VAR).ok().filter(|token| !token.trim().is_empty())
+            .with_context(|| format!("No GitLab token found. Set {} to a token with the 'api' scope.", TOKEN_VAR))?;
+        let base_url = std::env::var(URL_VAR).ok().filter(|url| !url.trim().is_empty()).unwrap_or_else(|| DEFAULT_URL.to_string());
+        let base_url = base_url.trim().trim_end_matches('/').to_string();
+        let host = base_url.trim_start_matches("https://").trim_start_matches("http://");
+
+        let remote = git::run(&["remote", "get-url", "origin"])
+            .context("Failed to read the 'origin' remote to find the GitLab project")?;
+        let project = links::remote_path(remote.trim(), host)
+            .with_context(|| format!("'origin' ({}) is not a project on {}. Set {} for self-managed GitLab.", remote.trim(), host, URL_VAR))?
+            .to_string();
+        let client = Client::builder()
+            .timeout(Duration::from_millis(TIMEOUT_MS))
+            .user_agent(concat!("env-coach/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to build HTTP client for GitLab")?;
+        Ok(Self { client, token: token.trim().to_string(), base_url, project })
+    }
+
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+
+    /// Group owning the project, where epics live.
+    pub fn group(&self) -> &str {
+        self.project.rsplit_once('/').map(|(group, _)| group).unwrap_or(&self.project)
+    }
+
+    async fn send(&self, builder: RequestBuilder, action: &str) -> Result<reqwest::Response> {
+        let res = builder.header("PRIVATE-TOKEN", &self.token).send().await
+            .with_context(|| format!("Failed to reach GitLab to {}", action))?;
+        let status = res.status();
+        if !status.is_success() {
+            let body = res.text().await.unwrap_or_default();
+            anyhow::bail!("GitLab refused to {} (HTTP {}): {}", action, status, body.trim());
+        }
+        Ok(res)
+    }
+
+    pub async fn create_issue(&self, title: &str, description: &str, labels: &[String]) -> Result<Created> {
+        let url = format!("{}/api/v4/projects/{}/issues", self.base_url, encode_path(&self.project));
+        let payload = serde_json::json!({ "title": title, "description": description, "labels": labels.join(",") });
+        let res = self.send(self.client.post(&url).json(&payload), &format!("create an issue for '{}'", title)).await?;
+        res.json().await.context("Failed to parse the created GitLab issue")
+    }
+
+    /// Epics are a Premium feature; on other tiers this fails with 403 or 404.
+    pub async fn create_epic(&self, title: &str, description: &str, labels: &[String]) -> Result<Created> {
+        let url = format!("{}/api/v4/groups/{}/epics", self.base_url, encode_path(self.group()));
+        let payload = serde_json::json!({ "title": title, "description": description, "labels": labels.join(",") });
+        let res = self.send(self.client.post(&url).json(&payload), &format!("create an epic for '{}'", title)).await?;
+        res.json().await.context("Failed to parse the created GitLab epic")
+    }
+
+    /// Attach an issue (by global ID) to an epic (by IID).
+    pub async fn add_issue_to_epic(&self, epic_iid: u64, issue_id: u64) -> Result<()> {
+        let url = format!("{}/api/v4/groups/{}/epics/{}/issues/{}", self.base_url, encode_path(self.group()), epic_iid, issue_id);
+        self.send(self.client.post(&url), &format!("add an issue to epic &{}", epic_iid)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refs() {
+        assert_eq!(ref_iid(&issue_ref(12), "gitlab"), Some(12));
+        assert_eq!(ref_iid("gitlab-epic:&3", "gitlab-epic"), Some(3));
+        assert_eq!(ref_iid("gitlab-epic:3", "gitlab"), None);
+        assert_eq!(ref_iid("github:3", "gitlab"), None);
+        assert_eq!(encode_path("acme/infra/api"), "acme%2Finfra%2Fapi");
+    }
+}
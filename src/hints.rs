@@ -0,0 +1,344 @@
+// src/hints.rs
+//! Coaching hints: what to do next, derived from the project state.
+//!
+//! Each rule looks at the project and proposes commands with a priority (lower is more
+//! urgent). `env-coach coach` lists every hint; the "Next steps" block printed by other
+//! commands shows the command's own steps followed by the most urgent hints. Projects
+//! switch rules off or add their own hints under `meta.hints` in project.json.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::config::{BacklogItem, ItemType, Project, SprintStatus, Status};
+use crate::output;
+
+/// Engine hints appended to a command's own "Next steps".
+const NEXT_STEP_HINTS: usize = 2;
+/// A risk register older than this is suggested for a refresh.
+const STALE_RISKS_DAYS: i64 = 30;
+/// Disables every hint, including the per-command "Next steps".
+pub const DISABLE_ALL: &str = "*";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Hint {
+    /// Rule that produced the hint, or "custom"
+    pub rule: &'static str,
+    pub priority: u8,
+    pub command: String,
+    pub why: String,
+}
+
+type RuleFn = fn(&Project, DateTime<Utc>) -> Vec<Hint>;
+
+/// Built-in rules: ID, what it suggests, and the rule itself.
+pub const RULES: &[(&str, &str, RuleFn)] = &[
+    ("first-requirement", "Add requirements to an empty backlog", first_requirement),
+    ("expired-sprint", "Complete an active sprint past its end date", expired_sprint),
+    ("review", "Close items waiting in review", review),
+    ("in-progress", "Finish items in progress", in_progress),
+    ("next-task", "Start the most urgent ready item of the active sprint", next_task),
+    ("start-sprint", "Start a planned sprint when none is active", start_sprint),
+    ("plan-sprint", "Plan a sprint when ready items have none", plan_sprint),
+    ("not-ready", "Refine items that fail the definition of ready", not_ready),
+    ("retro", "Hold a retrospective for the last completed sprint", retro),
+    ("risks", "Create or refresh the risk register", risks),
+    ("prd-changed", "Reconcile the backlog after the PRD was edited", prd_changed),
+    ("prd-check", "Check finished work against the PRD success metrics", prd_check),
+];
+
+fn hint(priority: u8, command: String, why: String) -> Hint {
+    Hint { rule: "", priority, command, why }
+}
+
+fn is_open_work(item: &BacklogItem) -> bool {
+    !matches!(item.item_type, ItemType::Epic) && !matches!(item.status, Status::Done)
+}
+
+fn active_sprint(project: &Project, now: DateTime<Utc>) -> Option<&crate::config::Sprint> {
+    project.sprints.iter().find(|s| matches!(s.status, SprintStatus::Active) && s.end_date >= now)
+}
+
+fn first_requirement(project: &Project, _now: DateTime<Utc>) -> Vec<Hint> {
+    if !project.backlog.is_empty() {
+        return vec![];
+    }
+    vec![hint(10, "env-coach add-requirement \"...\"".to_string(), "The backlog is empty; add your first requirement".to_string())]
+}
+
+fn expired_sprint(project: &Project, now: DateTime<Utc>) -> Vec<Hint> {
+    project.sprints.iter()
+        .filter(|s| matches!(s.status, SprintStatus::Active) && s.end_date < now)
+        .map(|s| hint(10, format!("env-coach complete-sprint {}", s.id), format!("{} ended on {}", s.id, s.end_date.format("%Y-%m-%d"))))
+        .collect()
+}
+
+fn review(project: &Project, _now: DateTime<Utc>) -> Vec<Hint> {
+    project.backlog.iter()
+        .filter(|item| matches!(item.status, Status::Review))
+        .map(|item| hint(20, format!("env-coach set-status {} done", item.id), format!("{} is waiting for review: {}", item.id, item.title)))
+        .collect()
+}
+
+fn in_progress(project: &Project, _now: DateTime<Utc>) -> Vec<Hint> {
+    project.backlog.iter()
+        .filter(|item| matches!(item.status, Status::InProgress))
+        .map(|item| hint(30, format!("env-coach complete-task {}", item.id), format!("Finish {}: {}", item.id, item.title)))
+        .collect()
+}
+
+fn next_task(project: &Project, now: DateTime<Utc>) -> Vec<Hint> {
+    let Some(sprint) = active_sprint(project, now) else { return vec![] };
+    let definition = project.meta.definition_of_ready.clone().unwrap_or_default();
+    project.backlog.iter()
+        .filter(|item| matches!(item.status, Status::Todo) && item.sprint.as_deref() == Some(sprint.id.as_str()))
+        .filter(|item| !matches!(item.item_type, ItemType::Epic) && definition.unmet(item, &project.backlog).is_empty())
+        .min_by_key(|item| item.priority.rank())
+        .map(|item| vec![hint(40, format!("env-coach start-task {}", item.id), format!("Next in {}: {}", sprint.id, item.title))])
+        .unwrap_or_default()
+}
+
+fn start_sprint(project: &Project, now: DateTime<Utc>) -> Vec<Hint> {
+    if project.sprints.iter().any(|s| matches!(s.status, SprintStatus::Active)) {
+        return vec![];
+    }
+    project.sprints.iter()
+        .filter(|s| matches!(s.status, SprintStatus::Planning))
+        .min_by_key(|s| s.start_date)
+        .map(|s| {
+            let when = if s.start_date <= now { "was due to start".to_string() } else { format!("starts {}", s.start_date.format("%Y-%m-%d")) };
+            vec![hint(40, format!("env-coach start-sprint {}", s.id), format!("{} is planned and {}", s.id, when))]
+        })
+        .unwrap_or_default()
+}
+
+fn plan_sprint(project: &Project, _now: DateTime<Utc>) -> Vec<Hint> {
+    if project.sprints.iter().any(|s| matches!(s.status, SprintStatus::Active | SprintStatus::Planning)) {
+        return vec![];
+    }
+    let unplanned = project.backlog.iter()
+        .filter(|item| is_open_work(item) && matches!(item.status, Status::Todo) && item.sprint.is_none())
+        .count();
+    if unplanned == 0 {
+        return vec![];
+    }
+    vec![hint(50, "env-coach plan-sprint --goal \"...\"".to_string(), format!("No sprint is planned; {} item(s) are waiting", unplanned))]
+}
+
+fn not_ready(project: &Project, _now: DateTime<Utc>) -> Vec<Hint> {
+    let definition = project.meta.definition_of_ready.clone().unwrap_or_default();
+    let unready: Vec<(&BacklogItem, Vec<String>)> = project.backlog.iter()
+        .filter(|item| is_open_work(item) && matches!(item.status, Status::Todo) && item.sprint.is_none())
+        .map(|item| (item, definition.unmet(item, &project.backlog)))
+        .filter(|(_, reasons)| !reasons.is_empty())
+        .collect();
+    let Some((first, reasons)) = unready.iter().min_by_key(|(item, _)| item.priority.rank()) else { return vec![] };
+    let others = match unready.len() {
+        1 => String::new(),
+        n => format!(" ({} more not ready)", n - 1),
+    };
+    vec![hint(60, format!("env-coach edit-item {} --editor", first.id), format!("{} is not ready: {}{}", first.id, reasons.join(", "), others))]
+}
+
+fn retro(project: &Project, _now: DateTime<Utc>) -> Vec<Hint> {
+    project.sprints.iter()
+        .filter(|s| matches!(s.status, SprintStatus::Completed | SprintStatus::Complete))
+        .max_by_key(|s| s.end_date)
+        .filter(|s| s.retrospective.is_none())
+        .map(|s| vec![hint(60, format!("env-coach retro {}", s.id), format!("{} has no retrospective yet", s.id))])
+        .unwrap_or_default()
+}
+
+fn risks(project: &Project, now: DateTime<Utc>) -> Vec<Hint> {
+    if !project.backlog.iter().any(is_open_work) {
+        return vec![];
+    }
+    match &project.meta.risks {
+        None => vec![hint(70, "env-coach risks".to_string(), "No risk register yet".to_string())],
+        Some(register) if now - register.generated > Duration::days(STALE_RISKS_DAYS) => {
+            vec![hint(70, "env-coach risks --refresh".to_string(), format!("Risks were last assessed on {}", register.generated.format("%Y-%m-%d")))]
+        }
+        Some(_) => vec![],
+    }
+}
+
+fn prd_changed(project: &Project, _now: DateTime<Utc>) -> Vec<Hint> {
+    let changed = project.meta.prd.as_ref().is_some_and(|prd| prd.changed_since_reconcile());
+    if !changed || !project.backlog.iter().any(is_open_work) {
+        return vec![];
+    }
+    vec![hint(45, "env-coach reconcile".to_string(), "The PRD changed since the backlog was last checked against it".to_string())]
+}
+
+fn prd_check(project: &Project, _now: DateTime<Utc>) -> Vec<Hint> {
+    let has_metrics = project.meta.prd.as_ref().is_some_and(|prd| !prd.success_metrics.is_empty());
+    let has_done = project.backlog.iter().any(|item| matches!(item.status, Status::Done));
+    if !has_metrics || !has_done {
+        return vec![];
+    }
+    vec![hint(80, "env-coach prd check".to_string(), "See how finished work moves the success metrics".to_string())]
+}
+
+fn disabled(project: &Project) -> &[String] {
+    project.meta.hints.as_ref().map(|hints| hints.disabled.as_slice()).unwrap_or_default()
+}
+
+/// True when the project switched off all hints with `"*"`.
+pub fn all_disabled(project: &Project) -> bool {
+    disabled(project).iter().any(|rule| rule == DISABLE_ALL)
+}
+
+/// Hints from the enabled rules and the project's custom hints, most urgent first.
+pub fn collect(project: &Project, now: DateTime<Utc>) -> Vec<Hint> {
+    if all_disabled(project) {
+        return vec![];
+    }
+    let disabled = disabled(project);
+    let mut hints: Vec<Hint> = RULES.iter()
+        .filter(|(id, _, _)| !disabled.iter().any(|rule| rule == id))
+        .flat_map(|&(id, _, rule)| rule(project, now).into_iter().map(move |hint| Hint { rule: id, ..hint }))
+        .collect();
+    if let Some(config) = &project.meta.hints {
+        hints.extend(config.custom.iter().map(|custom| Hint {
+            rule: "custom",
+            priority: custom.priority,
+            command: custom.command.clone(),
+            why: custom.why.clone(),
+        }));
+    }
+    hints.sort_by_key(|hint| hint.priority);
+    hints
+}
+
+/// `env-coach start-task US-003` and `env-coach start-task <id>` suggest the same command.
+fn same_command(a: &str, b: &str) -> bool {
+    a.split_whitespace().take(2).eq(b.split_whitespace().take(2))
+}
+
+/// A command's own steps, then the most urgent hints for other commands.
+fn next_steps(steps: &[(&str, &str)], hints: Vec<Hint>) -> Vec<(String, String)> {
+    let mut lines: Vec<(String, String)> = steps.iter().map(|(command, why)| (command.to_string(), why.to_string())).collect();
+    let extra: Vec<(String, String)> = hints.into_iter()
+        .filter(|hint| !lines.iter().any(|(command, _)| same_command(command, &hint.command)))
+        .take(NEXT_STEP_HINTS)
+        .map(|hint| (hint.command, hint.why))
+        .collect();
+    lines.extend(extra);
+    lines
+}
+
+/// Print the "🎯 Next steps" block after a command. `project` is the state after the
+/// command ran; pass `None` where there is no project (e.g. outside one).
+pub fn print_next_steps(project: Option<&Project>, steps: &[(&str, &str)]) {
+    if !output::show_hints() || project.is_some_and(all_disabled) {
+        return;
+    }
+    let hints = project.map(|project| collect(project, Utc::now())).unwrap_or_default();
+    let lines = next_steps(steps, hints);
+    if lines.is_empty() {
+        return;
+    }
+    println!();
+    println!("🎯 Next steps:");
+    for (command, why) in lines {
+        println!("   {:<35} # {}", command, why);
+    }
+}
+
+/// Numbered list of hints, as shown by `coach` and `status`.
+pub fn print_ranked(hints: &[Hint]) {
+    for (i, hint) in hints.iter().enumerate() {
+        println!("   {}. {:<35} # {}", i + 1, hint.command, hint.why);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CustomHint, HintsConfig, Priority, Sprint};
+
+    fn sprint(id: &str, status: SprintStatus, now: DateTime<Utc>, ends_in_days: i64) -> Sprint {
+        Sprint {
+            id: id.to_string(),
+            goal: String::new(),
+            start_date: now - Duration::days(14),
+            end_date: now + Duration::days(ends_in_days),
+            status,
+            total_points: 0,
+            completed_points: 0,
+            tasks: vec![],
+            stories: vec![],
+            planned_velocity: 0,
+            actual_velocity: 0,
+            retrospective: None,
+        }
+    }
+
+    fn commands(hints: &[Hint]) -> Vec<&str> {
+        hints.iter().map(|hint| hint.command.as_str()).collect()
+    }
+
+    #[test]
+    fn test_rules_follow_project_state() {
+        let now = Utc::now();
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        assert_eq!(commands(&collect(&project, now)), vec!["env-coach add-requirement \"...\""]);
+
+        let ready = |id: &str| BacklogItem::test(id).with_criteria(&["Works"]);
+        project.backlog = vec![
+            ready("US-001").with_status(Status::Review).with_sprint("SPRINT-001"),
+            ready("US-002").with_sprint("SPRINT-001"),
+            ready("US-003").with_priority(Priority::High).with_sprint("SPRINT-001"),
+            ready("US-004").with_status(Status::InProgress).with_sprint("SPRINT-001"),
+            BacklogItem::test("US-005"),
+        ];
+        project.sprints = vec![sprint("SPRINT-001", SprintStatus::Active, now, 7)];
+        project.meta.risks = Some(crate::config::RiskRegister { generated: now, risks: vec![] });
+
+        let hints = collect(&project, now);
+        assert_eq!(commands(&hints), vec![
+            "env-coach set-status US-001 done",
+            "env-coach complete-task US-004",
+            "env-coach start-task US-003",
+            "env-coach edit-item US-005 --editor",
+        ]);
+        assert_eq!(hints[3].why, "US-005 is not ready: no acceptance criteria");
+
+        // Once the sprint is over, closing it comes first and nothing new is started
+        let hints = collect(&project, now + Duration::days(10));
+        assert_eq!(hints[0].command, "env-coach complete-sprint SPRINT-001");
+        assert!(!commands(&hints).contains(&"env-coach start-task US-003"));
+    }
+
+    #[test]
+    fn test_project_config_disables_and_adds_hints() {
+        let now = Utc::now();
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog = vec![BacklogItem::test("US-001").with_criteria(&["Works"])];
+        project.sprints = vec![sprint("SPRINT-001", SprintStatus::Completed, now, -1)];
+        assert_eq!(commands(&collect(&project, now)), vec!["env-coach plan-sprint --goal \"...\"", "env-coach retro SPRINT-001", "env-coach risks"]);
+
+        project.meta.hints = Some(HintsConfig {
+            disabled: vec!["retro".to_string(), "risks".to_string()],
+            custom: vec![CustomHint { command: "make release".to_string(), why: "Ship it".to_string(), priority: 5 }],
+        });
+        let hints = collect(&project, now);
+        assert_eq!(commands(&hints), vec!["make release", "env-coach plan-sprint --goal \"...\""]);
+        assert_eq!(hints[0].rule, "custom");
+        assert_eq!(hints[1].rule, "plan-sprint");
+
+        project.meta.hints.as_mut().unwrap().disabled.push(DISABLE_ALL.to_string());
+        assert!(collect(&project, now).is_empty());
+    }
+
+    #[test]
+    fn test_next_steps_skip_commands_already_suggested() {
+        let hints = vec![
+            hint(10, "env-coach start-task US-002".to_string(), "Next".to_string()),
+            hint(20, "env-coach retro SPRINT-001".to_string(), "Retro".to_string()),
+            hint(30, "env-coach risks".to_string(), "Risks".to_string()),
+        ];
+        let lines = next_steps(&[("env-coach start-task <id>", "Start the next task")], hints);
+        let commands: Vec<&str> = lines.iter().map(|(command, _)| command.as_str()).collect();
+        assert_eq!(commands, vec!["env-coach start-task <id>", "env-coach retro SPRINT-001", "env-coach risks"]);
+    }
+}
@@ -0,0 +1,36 @@
+// src/lib.rs
+//! env-coach as a library, for reporting tools and editors that read a project without
+//! shelling out to the CLI.
+//!
+//! Load the project in the current directory with `config::Project::load`, then call
+//! `stats::project_stats` or `stats::sprint_stats` for the same counts, point sums,
+//! completion percentages and velocity that `status`, `list-backlog`, `show-sprint` and
+//! `stats --format json` print. The `env-coach` binary is the command-line layer over
+//! these modules.
+
+pub mod code_context;
+pub mod code_index;
+pub mod config;
+pub mod context;
+pub mod git;
+pub mod github;
+pub mod gitlab;
+pub mod hints;
+pub mod linear;
+pub mod links;
+pub mod scripts;
+pub mod stats;
+pub mod storage;
+pub mod audit;
+pub mod auto_commit;
+pub mod auto_update;
+pub mod ollama;
+pub mod output;
+pub mod project_summary;
+pub mod prompts;
+pub mod lock;
+pub mod recovery;
+pub mod team;
+pub mod tokens;
+pub mod templates;
+pub mod workspace;
@@ -0,0 +1,150 @@
+// src/linear.rs
+//! Minimal client for the Linear GraphQL API, used by `linear push`.
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::config::Priority;
+
+const API_URL: &str = "https://api.linear.app/graphql";
+const TIMEOUT_MS: u64 = 30000;
+/// Environment variable holding a personal API key.
+pub const TOKEN_VAR: &str = "LINEAR_API_KEY";
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Team {
+    pub id: String,
+    pub key: String,
+    pub name: String,
+    /// `notUsed`, `exponential`, `fibonacci`, `linear` or `tShirt`
+    pub issue_estimation_type: String,
+}
+
+impl Team {
+    /// Estimates the team accepts; empty when it does not estimate.
+    pub fn estimate_scale(&self) -> &'static [u32] {
+        match self.issue_estimation_type.as_str() {
+            "notUsed" => &[],
+            "exponential" => &[1, 2, 4, 8, 16],
+            "linear" => &[1, 2, 3, 4, 5],
+            // Fibonacci, and T-shirt sizes (XS..XL), which Linear stores as 1, 2, 3, 5, 8
+            _ => &[1, 2, 3, 5, 8],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Issue {
+    pub identifier: String,
+    pub url: String,
+}
+
+/// Linear priorities: 1 urgent, 2 high, 3 medium, 4 low (0 is "no priority").
+pub fn priority_value(priority: &Priority) -> u8 {
+    match priority {
+        Priority::Critical => 1,
+        Priority::High => 2,
+        Priority::Medium => 3,
+        Priority::Low => 4,
+    }
+}
+
+/// Closest estimate on the team's scale; ties go to the larger value.
+pub fn nearest_estimate(effort: u32, scale: &[u32]) -> Option<u32> {
+    scale.iter().copied().min_by_key(|value| (value.abs_diff(effort), u32::MAX - value))
+}
+
+#[derive(Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+pub struct LinearClient {
+    client: Client,
+    api_key: String,
+}
+
+impl LinearClient {
+    pub fn from_env() -> Result<Self> {
+        let api_key = std::env::var(TOKEN_VAR).ok().filter(|key| !key.trim().is_empty())
+            .with_context(|| format!("No Linear API key found. Create one under Settings → API and set {}.", TOKEN_VAR))?;
+        let client = Client::builder()
+            .timeout(Duration::from_millis(TIMEOUT_MS))
+            .build()
+            .context("Failed to build HTTP client for Linear")?;
+        Ok(Self { client, api_key: api_key.trim().to_string() })
+    }
+
+    async fn query<T: DeserializeOwned>(&self, query: &str, variables: serde_json::Value, action: &str) -> Result<T> {
+        let res = self.client.post(API_URL)
+            .header("Authorization", &self.api_key)
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach Linear to {}", action))?;
+        let status = res.status();
+        let body = res.text().await.unwrap_or_default();
+        let response: GraphQlResponse<T> = serde_json::from_str(&body)
+            .with_context(|| format!("Linear refused to {} (HTTP {}): {}", action, status, body.trim()))?;
+        if !response.errors.is_empty() {
+            let messages: Vec<&str> = response.errors.iter().map(|e| e.message.as_str()).collect();
+            anyhow::bail!("Linear refused to {}: {}", action, messages.join("; "));
+        }
+        response.data.with_context(|| format!("Linear returned no data to {}", action))
+    }
+
+    /// The team with the given key (the `ENG` in `ENG-123`).
+    pub async fn team(&self, key: &str) -> Result<Team> {
+        #[derive(Deserialize)]
+        struct Data { teams: Nodes }
+        #[derive(Deserialize)]
+        struct Nodes { nodes: Vec<Team> }
+
+        let query = "query($key: String!) { teams(filter: { key: { eqIgnoreCase: $key } }) { nodes { id key name issueEstimationType } } }";
+        let data: Data = self.query(query, serde_json::json!({ "key": key }), &format!("look up team {}", key)).await?;
+        data.teams.nodes.into_iter().next()
+            .with_context(|| format!("No Linear team with key '{}' is visible to this API key", key))
+    }
+
+    pub async fn create_issue(&self, team: &Team, title: &str, description: &str, priority: u8, estimate: Option<u32>) -> Result<Issue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data { issue_create: Payload }
+        #[derive(Deserialize)]
+        struct Payload { issue: Option<Issue> }
+
+        let query = "mutation($input: IssueCreateInput!) { issueCreate(input: $input) { success issue { identifier url } } }";
+        let mut input = serde_json::json!({ "teamId": team.id, "title": title, "description": description, "priority": priority });
+        if let Some(estimate) = estimate {
+            input["estimate"] = estimate.into();
+        }
+        let data: Data = self.query(query, serde_json::json!({ "input": input }), &format!("create an issue for '{}'", title)).await?;
+        data.issue_create.issue.context("Linear did not return the created issue")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_estimate() {
+        let fibonacci = [1, 2, 3, 5, 8];
+        assert_eq!(nearest_estimate(4, &fibonacci), Some(5));
+        assert_eq!(nearest_estimate(13, &fibonacci), Some(8));
+        assert_eq!(nearest_estimate(6, &[1, 2, 4, 8, 16]), Some(8));
+        assert_eq!(nearest_estimate(3, &[]), None);
+        assert_eq!(priority_value(&Priority::Critical), 1);
+    }
+}
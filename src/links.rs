@@ -0,0 +1,107 @@
+// src/links.rs
+//! Turn an item's `external_ref` into a URL for its issue tracker.
+//!
+//! References are written as `<tracker>:<id>` (`github:42`, `jira:PROJ-7`) or as a full
+//! URL. Each tracker maps to a URL template with an `{id}` placeholder, configured under
+//! `links` in the global config and in project.json (project entries win). GitHub and
+//! GitLab work without configuration when `origin` points at them.
+
+use std::collections::BTreeMap;
+
+use crate::config::{BacklogItem, GlobalConfig, Project};
+use crate::git;
+
+/// Placeholder replaced by the tracker-side ID in link templates.
+const ID_PLACEHOLDER: &str = "{id}";
+
+#[derive(Debug, Default)]
+pub struct Linker {
+    templates: BTreeMap<String, String>,
+}
+
+impl Linker {
+    /// Templates for `project`: built-ins from the `origin` remote, then the global
+    /// config, then project.json.
+    pub fn new(project: &Project, global: &GlobalConfig) -> Self {
+        let mut templates = git::run(&["remote", "get-url", "origin"])
+            .ok()
+            .map(|remote| remote_templates(remote.trim()))
+            .unwrap_or_default();
+        templates.extend(global.links.iter().map(|(tracker, template)| (tracker.to_lowercase(), template.clone())));
+        templates.extend(project.meta.links.iter().map(|(tracker, template)| (tracker.to_lowercase(), template.clone())));
+        Self { templates }
+    }
+
+    /// URL for a reference, or `None` when its tracker has no template.
+    pub fn url(&self, external_ref: &str) -> Option<String> {
+        let external_ref = external_ref.trim();
+        if external_ref.starts_with("https://") || external_ref.starts_with("http://") {
+            return Some(external_ref.to_string());
+        }
+        let (tracker, id) = external_ref.split_once(':')?;
+        let template = self.templates.get(&tracker.trim().to_lowercase())?;
+        Some(template.replace(ID_PLACEHOLDER, id.trim().trim_start_matches('#')))
+    }
+
+    /// `github:42 → https://…` for display; the bare reference when it cannot be linked.
+    pub fn describe(&self, item: &BacklogItem) -> Option<String> {
+        let external_ref = item.external_ref.as_deref()?;
+        Some(match self.url(external_ref) {
+            Some(url) if url != external_ref => format!("{} → {}", external_ref, url),
+            _ => external_ref.to_string(),
+        })
+    }
+}
+
+/// `owner/repo` of a GitHub/GitLab remote, in SSH or HTTPS form.
+pub(crate) fn remote_path<'a>(remote: &'a str, host: &str) -> Option<&'a str> {
+    let path = remote.strip_prefix(&format!("git@{}:", host))
+        .or_else(|| remote.strip_prefix(&format!("https://{}/", host)))
+        .or_else(|| remote.strip_prefix(&format!("ssh://git@{}/", host)))?;
+    let path = path.trim_end_matches('/');
+    Some(path.strip_suffix(".git").unwrap_or(path)).filter(|path| path.contains('/'))
+}
+
+/// Issue URL templates derived from the `origin` remote.
+fn remote_templates(remote: &str) -> BTreeMap<String, String> {
+    let mut templates = BTreeMap::new();
+    if let Some(path) = remote_path(remote, "github.com") {
+        templates.insert("github".to_string(), format!("https://github.com/{}/issues/{}", path, ID_PLACEHOLDER));
+    }
+    if let Some(path) = remote_path(remote, "gitlab.com") {
+        templates.insert("gitlab".to_string(), format!("https://gitlab.com/{}/-/issues/{}", path, ID_PLACEHOLDER));
+        if let Some((group, _)) = path.rsplit_once('/') {
+            templates.insert("gitlab-epic".to_string(), format!("https://gitlab.com/groups/{}/-/epics/{}", group, ID_PLACEHOLDER));
+        }
+    }
+    templates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_templates() {
+        assert_eq!(remote_templates("git@github.com:acme/api.git").get("github").unwrap(), "https://github.com/acme/api/issues/{id}");
+        assert_eq!(remote_templates("https://github.com/acme/api").get("github").unwrap(), "https://github.com/acme/api/issues/{id}");
+        assert_eq!(remote_templates("https://gitlab.com/acme/infra/api.git").get("gitlab").unwrap(), "https://gitlab.com/acme/infra/api/-/issues/{id}");
+        assert_eq!(remote_templates("https://gitlab.com/acme/infra/api.git").get("gitlab-epic").unwrap(), "https://gitlab.com/groups/acme/infra/-/epics/{id}");
+        assert!(remote_templates("git@example.com:acme/api.git").is_empty());
+    }
+
+    #[test]
+    fn test_url_from_templates() {
+        let linker = Linker {
+            templates: BTreeMap::from([
+                ("github".to_string(), "https://github.com/acme/api/issues/{id}".to_string()),
+                ("jira".to_string(), "https://acme.atlassian.net/browse/{id}".to_string()),
+            ]),
+        };
+        assert_eq!(linker.url("github:#42").as_deref(), Some("https://github.com/acme/api/issues/42"));
+        assert_eq!(linker.url("JIRA:PROJ-7").as_deref(), Some("https://acme.atlassian.net/browse/PROJ-7"));
+        assert_eq!(linker.url("https://tracker.example/t/9").as_deref(), Some("https://tracker.example/t/9"));
+        assert_eq!(linker.url("linear:ENG-1"), None);
+        assert_eq!(linker.url("no-tracker"), None);
+    }
+}
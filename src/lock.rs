@@ -0,0 +1,148 @@
+// src/lock.rs
+//! Advisory lock for commands that change the project.
+//!
+//! A mutating command creates `.env-coach/.lock` naming its process before it loads
+//! project.json and removes it when it exits, so two commands cannot load the same state
+//! and overwrite each other's changes. The lock is advisory: editors and other tools do not
+//! look at it. A lock whose process is gone (killed, or crashed) is taken over.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::{audit, recovery};
+
+pub const LOCK_FILE: &str = ".env-coach/.lock";
+
+/// How often `--wait` looks at the lock again.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Who holds the lock, as written to the lock file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holder {
+    pub pid: u32,
+    pub command: String,
+    pub started: DateTime<Utc>,
+}
+
+/// A held lock; dropping it removes the lock file.
+#[derive(Debug)]
+pub struct ProjectLock {
+    path: PathBuf,
+}
+
+impl ProjectLock {
+    /// Take the lock of the project in `root`. When another live process holds it, fail
+    /// naming that process, or with `wait` poll until it is released.
+    pub fn acquire(root: &Path, wait: bool) -> Result<Self> {
+        let path = root.join(LOCK_FILE);
+        let mut announced = false;
+        loop {
+            let holder = match Self::try_acquire(&path)? {
+                Ok(lock) => return Ok(lock),
+                Err(holder) => holder,
+            };
+            if !wait {
+                anyhow::bail!(
+                    "Another env-coach command (pid {}, 'env-coach {}', started {}) is changing this project. \
+                     Wait for it to finish, or re-run with --wait.",
+                    holder.pid, holder.command, holder.started.format("%Y-%m-%d %H:%M UTC")
+                );
+            }
+            if !announced {
+                println!("⏳ Waiting for 'env-coach {}' (pid {}) to finish...", holder.command, holder.pid);
+                announced = true;
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// The lock, or who holds it. A lock left by a process that is gone is removed first.
+    fn try_acquire(path: &Path) -> Result<std::result::Result<Self, Holder>> {
+        let holder = Holder {
+            pid: std::process::id(),
+            command: audit::command_line(),
+            started: Utc::now(),
+        };
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(mut file) => {
+                let lock = Self { path: path.to_path_buf() };
+                file.write_all(serde_json::to_string_pretty(&holder)?.as_bytes())
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                Ok(Ok(lock))
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => match read_holder(path) {
+                Some(holder) if recovery::process_alive(holder.pid, holder.started) => Ok(Err(holder)),
+                // Gone, released between our two looks, or left half-written by a crash
+                _ => {
+                    match std::fs::remove_file(path) {
+                        Ok(()) => log::info!("Removed stale lock {}", path.display()),
+                        Err(e) if e.kind() == ErrorKind::NotFound => {}
+                        Err(e) => return Err(e).with_context(|| format!("Failed to remove stale lock {}", path.display())),
+                    }
+                    Self::try_acquire(path)
+                }
+            },
+            Err(e) => Err(e).with_context(|| format!("Failed to create {}", path.display())),
+        }
+    }
+}
+
+impl Drop for ProjectLock {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            log::warn!("Failed to remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// The holder named in the lock file; `None` when it is missing or was cut off mid-write.
+/// A file that does not parse is read once more, as its owner may still be writing it.
+fn read_holder(path: &Path) -> Option<Holder> {
+    let parse = || serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok();
+    parse().or_else(|| {
+        std::thread::sleep(POLL_INTERVAL);
+        parse()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_is_exclusive_and_released_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".env-coach")).unwrap();
+        let path = dir.path().join(LOCK_FILE);
+
+        let lock = ProjectLock::acquire(dir.path(), false).unwrap();
+        assert_eq!(read_holder(&path).unwrap().pid, std::process::id());
+        // Our own lock still counts: a second command in this process would lose updates too
+        let error = ProjectLock::acquire(dir.path(), false).unwrap_err().to_string();
+        assert!(error.contains(&format!("pid {}", std::process::id())));
+        assert!(error.contains("--wait"));
+        drop(lock);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_stale_and_unreadable_locks_are_taken_over() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".env-coach")).unwrap();
+        let path = dir.path().join(LOCK_FILE);
+
+        // A pid far above any pid_max, started long enough ago to count as stale without /proc
+        let gone = Holder { pid: u32::MAX, command: "groom".to_string(), started: Utc::now() - chrono::Duration::days(1) };
+        std::fs::write(&path, serde_json::to_string(&gone).unwrap()).unwrap();
+        drop(ProjectLock::acquire(dir.path(), false).unwrap());
+
+        std::fs::write(&path, "{\"pid\": 12").unwrap();
+        let lock = ProjectLock::acquire(dir.path(), false).unwrap();
+        assert_eq!(read_holder(&path).unwrap().pid, std::process::id());
+        drop(lock);
+    }
+}
@@ -1,17 +1,15 @@
 // src/main.rs
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 
-mod config;
-mod scripts;
-mod auto_update;
-mod ollama;
-mod templates;
+use env_coach::{auto_commit, auto_update, config, context, lock, output, recovery, scripts};
 
 #[derive(Parser)]
 #[command(name = "env-coach")]
 #[command(about = "Environment Coach - AI-powered project management")]
 struct Cli {
+    #[command(flatten)]
+    global: context::GlobalArgs,
     #[command(subcommand)]
     command: Commands,
 }
@@ -39,19 +37,85 @@ enum Commands {
     AddRequirement {
         /// Requirement description
         requirement: String,
+        /// Attach the generated stories to this epic
+        #[arg(long)]
+        epic: Option<String>,
+        /// Use this template instead of .env-coach/prompts/requirements_analyst.md for this run
+        #[arg(long)]
+        prompt_file: Option<std::path::PathBuf>,
+    },
+    /// Add an epic that groups related stories
+    AddEpic {
+        /// Epic title
+        #[arg(short, long)]
+        title: String,
+        /// Epic description
+        #[arg(short, long, default_value = "")]
+        description: String,
+        /// Priority (critical, high, medium, low)
+        #[arg(long, default_value = "medium")]
+        priority: config::Priority,
+    },
+    /// Report a bug; the LLM turns the report into repro steps and acceptance criteria
+    AddBug {
+        /// Short bug title
+        #[arg(long)]
+        title: String,
+        /// Free-text report: what was done, what was expected, what happened
+        #[arg(long)]
+        repro: Option<String>,
+        /// Severity, used as the item's priority (critical, high, medium, low)
+        #[arg(long, default_value = "medium")]
+        severity: config::Priority,
+        /// Component the bug belongs to
+        #[arg(long)]
+        component: Option<String>,
+        /// Record the report as written without asking the LLM
+        #[arg(long)]
+        no_llm: bool,
     },
     /// List backlog items
-    ListBacklog,
+    ListBacklog {
+        /// Show archived items instead
+        #[arg(long)]
+        archived: bool,
+        #[command(flatten)]
+        filter: scripts::filter::BacklogFilter,
+    },
+    /// Search titles, stories and acceptance criteria
+    Search {
+        /// Words that must all appear in an item (case-insensitive)
+        query: String,
+        #[command(flatten)]
+        filter: scripts::filter::BacklogFilter,
+    },
     /// Show project status
     Status,
+    /// Show backlog, sprint and velocity statistics (use --format json for reporting tools)
+    Stats,
+    /// List recommended next actions for the project, most urgent first
+    Coach {
+        /// List the hint rules and whether this project disables them
+        #[arg(long)]
+        rules: bool,
+    },
     /// Plan a new sprint
     PlanSprint {
-        /// Sprint goal
+        /// Sprint goal (the LLM suggests candidates when omitted)
         #[arg(short, long)]
-        goal: String,
+        goal: Option<String>,
+        /// Let the LLM propose 2-3 goals from the backlog and PRD to pick from
+        #[arg(long, conflicts_with = "goal")]
+        suggest_goal: bool,
         /// Sprint duration in days
         #[arg(short, long, default_value = "14")]
         days: u32,
+        /// Offer items that fail the definition of ready to the LLM and the picker without asking
+        #[arg(long)]
+        include_unready: bool,
+        /// Use this template instead of .env-coach/prompts/sprint_planner.md for this run
+        #[arg(long)]
+        prompt_file: Option<std::path::PathBuf>,
     },
     /// Start a sprint
     StartSprint {
@@ -60,6 +124,149 @@ enum Commands {
     },
     /// Show current sprint
     ShowSprint,
+    /// Adjust the scope of a planned or active sprint
+    Sprint {
+        #[command(subcommand)]
+        action: SprintCommands,
+    },
+    /// Complete a sprint, record its velocity and carry over unfinished items
+    CompleteSprint {
+        /// Sprint ID
+        sprint_id: String,
+        /// Move unfinished items into this sprint instead of back to the backlog
+        #[arg(long, value_name = "SPRINT_ID")]
+        carry_over: Option<String>,
+        /// Ask the LLM to split In Progress stories into a done part and a "remaining work" story
+        #[arg(long)]
+        split: bool,
+    },
+    /// Generate a shareable sprint review report
+    SprintReport {
+        /// Sprint ID
+        sprint_id: String,
+        /// Report format
+        #[arg(long = "report-format", value_enum, default_value = "md")]
+        format: scripts::sprint_report::ReportFormat,
+    },
+    /// Run an LLM-facilitated retrospective for a sprint
+    Retro {
+        /// Sprint ID
+        sprint_id: String,
+    },
+    /// Show an ASCII burndown chart for a sprint (defaults to the active one)
+    Burndown {
+        /// Sprint ID
+        sprint_id: Option<String>,
+    },
+    /// Compare latency and JSON parse success of local models on a standard suite
+    BenchModels {
+        /// Comma-separated models to benchmark (defaults to the configured model)
+        #[arg(long, value_delimiter = ',')]
+        models: Vec<String>,
+        /// Runs per test case
+        #[arg(long, default_value = "1")]
+        runs: u32,
+    },
+    /// Export project.json, optionally anonymized for sharing in bug reports
+    Export {
+        /// Replace names, story text and other content with placeholders
+        #[arg(long)]
+        anonymized: bool,
+        /// Write BACKLOG.md and SPRINT-<id>.md (open sprints) instead of JSON
+        #[arg(long, conflicts_with_all = ["org", "obsidian"])]
+        md: bool,
+        /// Write an org-mode file with a heading per item and sprint (default BACKLOG.org)
+        #[arg(long, conflicts_with = "obsidian")]
+        org: bool,
+        /// Write one linked note per item and sprint into <VAULT>/<project name>/
+        #[arg(long, value_name = "VAULT")]
+        obsidian: Option<std::path::PathBuf>,
+    },
+    /// Embed the codebase into .env-coach/cache/embeddings.bin for assist-task to search
+    Index {
+        /// Embed every file again, not just the changed ones
+        #[arg(long)]
+        full: bool,
+    },
+    /// Find the backlog items and source files most related to a query (needs `index`)
+    Find {
+        /// What to look for, e.g. "where do we parse LLM json"
+        query: String,
+        /// Results to show of each kind
+        #[arg(long, default_value = "5")]
+        limit: usize,
+    },
+    /// Chat with the project's model; /add-story and /apply turn answers into items or code
+    Chat {
+        /// Task to focus the conversation on
+        #[arg(long)]
+        task: Option<String>,
+    },
+    /// Read and change settings of the global config and project.json
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommands,
+    },
+    /// Check project.json, prompts, the LLM server and models, git and manifests, with fixes
+    Doctor,
+    /// Rewrite project.json as project.toml or back (--global: the global config)
+    Convert {
+        /// Format to convert to
+        #[arg(value_enum)]
+        to: config::FileFormat,
+        /// Convert the global config instead of the project file
+        #[arg(long)]
+        global: bool,
+    },
+    /// Show an item's status, priority and effort changes: when, by whom and by which command
+    History {
+        /// Backlog or archived item ID
+        item_id: String,
+    },
+    /// Render a prompt template against sample data and check how often the LLM's answer parses
+    PromptTest {
+        /// Prompt name (e.g. task_assistant) or path to a template file
+        template: String,
+        /// JSON object with a value for each placeholder, e.g. {"project_name": "demo"}
+        #[arg(long)]
+        fixture: std::path::PathBuf,
+        /// Send the prompt this many times (0 prints the rendered prompt)
+        #[arg(long, default_value = "0")]
+        runs: u32,
+        /// Check answers with this parser instead of the one for the prompt
+        #[arg(long, value_enum)]
+        parser: Option<scripts::prompt_test::ParserKind>,
+    },
+    /// Ask a question about the project's history (ADRs, logs, retrospectives, completed stories)
+    Ask {
+        /// The question to answer
+        question: String,
+    },
+    /// Show backlog dependencies; --mermaid emits a flowchart with epics as subgraphs
+    Graph {
+        /// Print a Mermaid flowchart for embedding in docs and PRs
+        #[arg(long)]
+        mermaid: bool,
+    },
+    /// Show planned vs actual velocity for recent sprints
+    Velocity {
+        /// Number of completed sprints to include
+        #[arg(long, default_value = "5")]
+        last: usize,
+    },
+    /// Show the project risk register, generating it with the LLM if needed
+    Risks {
+        /// Re-assess risks even if a register already exists
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Manage the workspace-level shared backlog of a multi-repo product
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceCommands,
+    },
+    /// Aggregate the shared and per-repository backlogs of the current workspace
+    Portfolio,
     /// Start working on a task
     StartTask {
         /// Task ID
@@ -72,11 +279,182 @@ enum Commands {
         /// Specific question or prompt for the LLM assistant
         #[arg(short, long)]
         prompt: Option<String>,
+        /// Use this template instead of .env-coach/prompts/task_assistant.md for this run
+        #[arg(long)]
+        prompt_file: Option<std::path::PathBuf>,
     },
     /// Complete a task
     CompleteTask {
         /// Task ID
         task_id: String,
+        /// Have the LLM check each acceptance criterion against the diff and test results first
+        #[arg(long)]
+        verify: bool,
+    },
+    /// Ask the LLM for an implementation plan and apply its suggestions one by one
+    ExecuteTask {
+        /// Task ID
+        task_id: String,
+        /// Specific request for the LLM
+        #[arg(short, long)]
+        prompt: Option<String>,
+        /// Continue an interrupted run without calling the LLM again
+        #[arg(long)]
+        resume: bool,
+        /// Run the build and lint checks after applying code changes
+        #[arg(long)]
+        verify: bool,
+        /// Send compile errors left after applying back to the LLM and apply its fixes
+        #[arg(long)]
+        auto_fix: bool,
+        /// Rounds of fixes --auto-fix may try before giving up
+        #[arg(long, default_value_t = 3, requires = "auto_fix")]
+        max_iterations: u32,
+    },
+    /// Run execute-task for each open item of a sprint, dependencies first
+    ExecuteSprint {
+        /// Sprint ID
+        sprint_id: String,
+        /// Apply source changes and patches without asking
+        #[arg(long)]
+        auto_approve_code: bool,
+        /// Add Cargo.toml dependencies without asking
+        #[arg(long)]
+        auto_approve_deps: bool,
+        /// Run the build and lint checks after each story
+        #[arg(long)]
+        verify: bool,
+        /// Send compile errors left after each story back to the LLM and apply its fixes
+        #[arg(long)]
+        auto_fix: bool,
+        /// Rounds of fixes --auto-fix may try per story before giving up
+        #[arg(long, default_value_t = 3, requires = "auto_fix")]
+        max_iterations: u32,
+    },
+    /// Apply suggestions saved by assist-task, after reviewing them
+    ApplySession {
+        /// Session file, e.g. .env-coach/sessions/US-001/20260101T120000Z.json
+        file: std::path::PathBuf,
+        /// Only these suggestion numbers, e.g. 1,3 or 2-4
+        #[arg(long)]
+        only: Option<String>,
+        /// Run the build and lint checks after applying code changes
+        #[arg(long)]
+        verify: bool,
+    },
+    /// Ask the LLM for tests of a task's acceptance criteria and apply them, one by one
+    TestTask {
+        /// Task ID
+        task_id: String,
+        /// Source file the tests are about (repeatable); by default, files changed by commits mentioning the task
+        #[arg(long = "file")]
+        files: Vec<String>,
+    },
+    /// Have the LLM review the code written for a task and store the review on it
+    ReviewTask {
+        /// Task ID
+        task_id: String,
+        /// Files to review; by default, files changed by commits mentioning the task, or uncommitted changes
+        files: Vec<String>,
+    },
+    /// Run the tests and check them against a task's acceptance criteria
+    VerifyTask {
+        /// Task ID
+        task_id: String,
+        /// Test command to run (detected from the project files by default)
+        #[arg(long)]
+        command: Option<String>,
+    },
+    /// Run the test suite and apply LLM-suggested fixes for the failures, one by one
+    FixTests {
+        /// Test command to run (detected from Cargo.toml, package.json, go.mod or pyproject.toml by default)
+        #[arg(long)]
+        command: Option<String>,
+    },
+    /// Mark items Done whose task/<ID> branch was merged into the default branch
+    SyncGit,
+    /// Draft a conventional-commit message for the staged changes (pipe into `git commit -F -`)
+    CommitMsg {
+        /// Backlog item ID
+        item_id: String,
+    },
+    /// Show everything about one item: story, criteria, dependencies, history, commits and sessions
+    ShowItem {
+        /// Backlog or archived item ID
+        item_id: String,
+    },
+    /// Edit a backlog item's fields, or open it in $EDITOR
+    EditItem {
+        /// Backlog item ID
+        item_id: String,
+        /// New title
+        #[arg(long)]
+        title: Option<String>,
+        /// New story text
+        #[arg(long)]
+        story: Option<String>,
+        /// New effort in story points
+        #[arg(long)]
+        effort: Option<u32>,
+        /// New priority (critical, high, medium, low)
+        #[arg(long)]
+        priority: Option<config::Priority>,
+        /// Component the item belongs to (empty string clears it)
+        #[arg(long)]
+        component: Option<String>,
+        /// Epic the item belongs to (empty string detaches it)
+        #[arg(long)]
+        epic: Option<String>,
+        /// Issue in an external tracker, e.g. github:42 or jira:PROJ-7 (empty string clears it)
+        #[arg(long = "external-ref", value_name = "REF")]
+        external_ref: Option<String>,
+        /// Append an acceptance criterion - can be specified multiple times
+        #[arg(long = "add-ac", value_name = "TEXT")]
+        add_ac: Vec<String>,
+        /// Remove acceptance criterion number N (1-based) - can be specified multiple times
+        #[arg(long = "remove-ac", value_name = "N")]
+        remove_ac: Vec<usize>,
+        /// Set a custom field, e.g. customer=Acme or severity=2 (null removes it) - can be specified multiple times
+        #[arg(long = "field", value_name = "KEY=VALUE")]
+        fields: Vec<String>,
+        /// Edit the item as TOML in $EDITOR instead of using flags
+        #[arg(long, conflicts_with_all = ["title", "story", "effort", "priority", "component", "epic", "external_ref", "add_ac", "remove_ac", "fields"])]
+        editor: bool,
+    },
+    /// Set a backlog item's status, keeping sprint points in sync
+    SetStatus {
+        /// Backlog item IDs: comma/space separated, ranges like US-001..US-005, or - for stdin
+        #[arg(required = true)]
+        item_ids: Vec<String>,
+        /// New status (todo, in-progress, review, done)
+        status: config::Status,
+    },
+    /// Set a backlog item's priority
+    SetPriority {
+        /// Backlog item IDs: comma/space separated, ranges like US-001..US-005, or - for stdin
+        #[arg(required = true)]
+        item_ids: Vec<String>,
+        /// New priority (critical, high, medium, low)
+        priority: config::Priority,
+    },
+    /// Set a backlog item's effort, keeping sprint points in sync
+    SetEffort {
+        /// Backlog item IDs: comma/space separated, ranges like US-001..US-005, or - for stdin
+        #[arg(required = true)]
+        item_ids: Vec<String>,
+        /// Story points (1-100)
+        effort: u32,
+    },
+    /// Permanently remove a backlog item (asks for confirmation)
+    RemoveItem {
+        /// Backlog item ID
+        item_id: String,
+    },
+    /// Move a backlog item to the archive, keeping its history
+    ArchiveItem {
+        /// Backlog item IDs: comma/space separated, ranges like US-001..US-005, or - for stdin
+        #[arg(required = true)]
+        item_ids: Vec<String>,
     },
     /// Add a user story manually
     AddStory {
@@ -86,9 +464,85 @@ enum Commands {
         /// Story description
         #[arg(short, long)]
         description: String,
+        /// Epic the story belongs to
+        #[arg(long)]
+        epic: Option<String>,
     },
+    /// Have the LLM split a story into tasks the story depends on
+    Breakdown {
+        /// Story ID
+        item_id: String,
+    },
+    /// Have the LLM suggest story points, accepted item by item
+    Estimate {
+        /// Backlog item IDs: comma/space separated, ranges like US-001..US-005, or - for stdin (all Todo items by default)
+        item_ids: Vec<String>,
+    },
+    /// Have the LLM review the Todo backlog and apply the accepted fixes
+    Groom,
     /// List all user stories
-    ListStories,
+    ListStories {
+        #[command(flatten)]
+        filter: scripts::filter::BacklogFilter,
+    },
+    /// Work with the project's PRD (problem statement and success metrics)
+    Prd {
+        #[command(subcommand)]
+        action: PrdCommands,
+    },
+    /// Re-check the backlog against the PRD after it changed, confirming each change
+    Reconcile,
+    /// Revert the files and project.json changed by the last auto-update
+    Undo,
+    /// Zip project.json and .env-coach (prompts, sessions, storage) into .env-coach/backups
+    Backup {
+        /// Number of backups to keep; older ones are removed
+        #[arg(long, default_value_t = scripts::backup::DEFAULT_KEEP)]
+        keep: usize,
+        /// List the backups instead
+        #[arg(long)]
+        list: bool,
+    },
+    /// Restore a file from the backup taken before a suggestion changed it, or the whole
+    /// project from a `backup` archive
+    Restore {
+        /// File path relative to the project root, or a backup archive (.zip)
+        file: String,
+        /// List the file's backups instead
+        #[arg(long)]
+        list: bool,
+    },
+    /// Check project.json for bad values, unknown fields and dangling references
+    Validate {
+        /// Print the JSON Schema of project.json instead
+        #[arg(long)]
+        schema: bool,
+    },
+    /// Import backlog items from another tracker
+    Import {
+        #[command(subcommand)]
+        source: ImportCommands,
+    },
+    /// Sync the backlog with GitHub Issues (token from GITHUB_TOKEN)
+    Github {
+        #[command(subcommand)]
+        action: GithubCommands,
+    },
+    /// Create GitLab issues and epics from the backlog (token from GITLAB_TOKEN)
+    Gitlab {
+        #[command(subcommand)]
+        action: GitlabCommands,
+    },
+    /// Push user stories to Linear (API key from LINEAR_API_KEY)
+    Linear {
+        #[command(subcommand)]
+        action: LinearCommands,
+    },
+    /// Manage the LLM prompt files in .env-coach/prompts
+    Prompts {
+        #[command(subcommand)]
+        action: PromptsCommands,
+    },
     /// Send custom prompt to LLM
     LlmCycle {
         /// Prompt text or file path
@@ -97,51 +551,501 @@ enum Commands {
     },
 }
 
+impl Commands {
+    /// Read commands that can print JSON or YAML with `--format`, by subcommand name; the
+    /// check and its error message both use this list.
+    const STRUCTURED: &'static [&'static str] = &[
+        "status", "stats", "list-backlog", "list-stories", "search", "show-sprint", "show-item", "coach", "history",
+    ];
+
+    fn supports_structured_output(name: &str) -> bool {
+        Self::STRUCTURED.contains(&name)
+    }
+
+    /// Whether the command has a result for `--output` to write: its `--format` data, an
+    /// export or a sprint report.
+    fn writes_output(&self, structured: bool) -> bool {
+        matches!(self, Commands::Export { .. } | Commands::SprintReport { .. }) || structured
+    }
+
+    /// The maintainer-only operation a destructive command performs; in team mode main gates
+    /// it with `AppContext::require_maintainer_here` before it runs.
+    fn maintainer_operation(&self) -> Option<&'static str> {
+        match self {
+            Commands::RemoveItem { .. } => Some("remove-item"),
+            Commands::CompleteSprint { .. } => Some("complete-sprint"),
+            Commands::Undo => Some("undo"),
+            Commands::Restore { file, list: false } if scripts::backup::is_archive_name(file) => Some("project restore"),
+            Commands::Restore { list: false, .. } => Some("restore"),
+            _ => None,
+        }
+    }
+
+    /// Commands that never write project.json, so they run without taking the project lock.
+    /// `chat` writes only through its slash commands, which take the lock themselves rather
+    /// than holding it for a whole conversation. `status` and `show-sprint` are not listed:
+    /// they may close an expired sprint.
+    fn is_read_only(&self) -> bool {
+        matches!(self,
+            Commands::Stats | Commands::ListBacklog { .. } | Commands::ListStories { .. } | Commands::Search { .. }
+                | Commands::ShowItem { .. } | Commands::Coach { .. } | Commands::SprintReport { .. }
+                | Commands::Burndown { .. } | Commands::BenchModels { .. } | Commands::Export { .. } | Commands::Index { .. }
+                | Commands::Find { .. } | Commands::Chat { .. } | Commands::Doctor | Commands::Ask { .. } | Commands::History { .. } | Commands::Graph { .. }
+                | Commands::Velocity { .. } | Commands::Portfolio | Commands::Validate { .. } | Commands::LlmCycle { .. } | Commands::PromptTest { .. }
+                | Commands::Config { action: ConfigCommands::Get { .. } | ConfigCommands::Show { .. } }
+                | Commands::Prompts { action: PromptsCommands::List | PromptsCommands::Show { .. } })
+    }
+}
+
+#[derive(Subcommand)]
+enum SprintCommands {
+    /// Add backlog items to a sprint
+    AddItem {
+        /// Sprint ID
+        sprint_id: String,
+        /// Backlog item IDs: comma/space separated, ranges like US-001..US-005, or - for stdin
+        #[arg(required = true)]
+        item_ids: Vec<String>,
+    },
+    /// Remove items from a sprint and return them to the backlog
+    RemoveItem {
+        /// Sprint ID
+        sprint_id: String,
+        /// Backlog item IDs: comma/space separated, ranges like US-001..US-005, or - for stdin
+        #[arg(required = true)]
+        item_ids: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorkspaceCommands {
+    /// Create env-coach-workspace.json here, registering subdirectories with a project.json
+    Init {
+        /// Workspace name (defaults to the current directory name)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Add an item to the shared backlog for one repository
+    AddItem {
+        /// Target repository name
+        repo: String,
+        /// Item title
+        title: String,
+        /// Item description
+        #[arg(short, long)]
+        description: Option<String>,
+        /// Story points
+        #[arg(long, default_value = "3")]
+        effort: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum PrdCommands {
+    /// Check completed and planned work against the PRD's success metrics
+    Check,
+}
+
+#[derive(Subcommand)]
+enum ImportCommands {
+    /// Import issues from a Jira CSV export, keeping their keys in external_ref
+    Jira {
+        /// CSV file exported from Jira
+        file: std::path::PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum GithubCommands {
+    /// Create issues for unlinked items, pull closed issues and labels, close issues of completed items
+    Sync,
+}
+
+#[derive(Subcommand)]
+enum GitlabCommands {
+    /// Create issues (and group epics) for open items not linked to a tracker yet
+    Push {
+        /// Create epics as issues labelled "epic" (group epics need GitLab Premium)
+        #[arg(long)]
+        epics_as_issues: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum LinearCommands {
+    /// Create Linear issues for open user stories not linked to a tracker yet
+    Push {
+        /// Key of the Linear team (the ENG in ENG-123)
+        #[arg(long)]
+        team: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum PromptsCommands {
+    /// List the prompts and whether each is customized
+    List,
+    /// Print a prompt as the project uses it
+    Show {
+        /// Prompt name, e.g. task_assistant
+        name: String,
+        /// Print the built-in default instead of the project's copy
+        #[arg(long)]
+        default: bool,
+    },
+    /// Open a prompt in $EDITOR
+    Edit {
+        /// Prompt name, e.g. sprint_planner
+        name: String,
+    },
+    /// Replace a prompt with the built-in default
+    Reset {
+        /// Prompt name, e.g. sprint_planner
+        name: String,
+    },
+    /// Compare your prompts with this version's defaults and merge the changes
+    Upgrade,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print a setting by dotted key, e.g. llm.model (LLM settings as resolved for this project)
+    Get {
+        key: String,
+        /// Read the global config only
+        #[arg(long, conflicts_with = "local")]
+        global: bool,
+        /// Read project.json only (--project names the project directory)
+        #[arg(long)]
+        local: bool,
+    },
+    /// Change a setting, e.g. `config set llm.timeout_ms 60000`; `null` unsets it
+    Set {
+        key: String,
+        value: String,
+        /// Write the global config (default outside a project)
+        #[arg(long, conflicts_with = "local")]
+        global: bool,
+        /// Write project.json (default inside a project)
+        #[arg(long)]
+        local: bool,
+    },
+    /// List the settings of the global config and project.json
+    Show {
+        /// Show the LLM settings in effect and where each comes from instead
+        #[arg(long)]
+        resolved: bool,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+    let wait = cli.global.wait;
+    let ctx = context::AppContext::new(cli.global)?;
+    output::init(&ctx.global_config);
+    if ctx.format.is_structured() && !matches.subcommand_name().is_some_and(Commands::supports_structured_output) {
+        anyhow::bail!("Structured output (--format) is only available for {}", Commands::STRUCTURED.join(", "));
+    }
+    if ctx.output.path().is_some() && !cli.command.writes_output(ctx.format.is_structured()) {
+        anyhow::bail!("--output writes a command's result: use it with export, sprint-report, or --format json|yaml");
+    }
+    let _lock = if cli.command.is_read_only() || !std::path::Path::new(".env-coach").is_dir() {
+        None
+    } else {
+        Some(lock::ProjectLock::acquire(&std::env::current_dir()?, wait)?)
+    };
+    recovery::check_on_startup(&ctx)?;
+    if let Some(operation) = cli.command.maintainer_operation() {
+        ctx.require_maintainer_here(operation)?;
+    }
+    let auto_commit = if cli.command.is_read_only() { None } else { auto_commit::AutoCommit::start(&ctx) };
 
     match cli.command {
         Commands::Init { name, description, problem, metrics, description_file } => { // Added description_file
-            scripts::init::run(name, description, problem, metrics, description_file)?; // Pass new args
+            scripts::init::run(&ctx, name, description, problem, metrics, description_file)?; // Pass new args
+        }
+        Commands::AddRequirement { requirement, epic, prompt_file } => {
+            scripts::requirements::process_requirement(&ctx, requirement, epic, prompt_file.as_deref()).await?;
+        }
+        Commands::AddEpic { title, description, priority } => {
+            scripts::epics::add_epic(&ctx, title, description, priority)?;
         }
-        Commands::AddRequirement { requirement } => {
-            scripts::requirements::process_requirement(requirement).await?;
+        Commands::AddBug { title, repro, severity, component, no_llm } => {
+            scripts::bugs::run(&ctx, title, repro, severity, component, no_llm).await?;
         }
-        Commands::ListBacklog => {
-            scripts::backlog::run()?;
+        Commands::ListBacklog { archived, filter } => {
+            scripts::backlog::run(&ctx, archived, &filter)?;
+        }
+        Commands::Search { query, filter } => {
+            scripts::search::run(&ctx, &query, &filter)?;
         }
         Commands::Status => {
-            scripts::status::run().await?;
+            scripts::status::run(&ctx).await?;
+        }
+        Commands::Stats => {
+            scripts::stats::run(&ctx)?;
+        }
+        Commands::Coach { rules } => {
+            scripts::coach::run(&ctx, rules)?;
         }
-        Commands::PlanSprint { goal, days } => {
-            scripts::sprint::plan(goal, days).await?; // Added .await
+        Commands::PlanSprint { goal, suggest_goal: _, days, include_unready, prompt_file } => {
+            // --suggest-goal conflicts with --goal, so a missing goal covers both
+            let goal = match goal {
+                Some(goal) => goal,
+                None => scripts::sprint_goal::suggest_goal(&ctx, days).await?,
+            };
+            scripts::sprint::plan(&ctx, goal, days, include_unready, prompt_file.as_deref()).await?; // Added .await
         }
         Commands::StartSprint { sprint_id } => {
-            scripts::sprint::start_sprint(sprint_id)?;
+            scripts::sprint::start_sprint(&ctx, sprint_id)?;
         }
         Commands::ShowSprint => {
-            scripts::sprint::show_current_sprint()?;
+            scripts::sprint::show_current_sprint(&ctx)?;
+        }
+        Commands::Sprint { action } => match action {
+            SprintCommands::AddItem { sprint_id, item_ids } => {
+                scripts::sprint::add_items(&ctx, sprint_id, item_ids)?;
+            }
+            SprintCommands::RemoveItem { sprint_id, item_ids } => {
+                scripts::sprint::remove_items(&ctx, sprint_id, item_ids)?;
+            }
+        },
+        Commands::CompleteSprint { sprint_id, carry_over, split } => {
+            if split {
+                scripts::sprint::complete_sprint_with_split(&ctx, sprint_id, carry_over).await?;
+            } else {
+                scripts::sprint::complete_sprint(&ctx, sprint_id, carry_over)?;
+            }
+        }
+        Commands::SprintReport { sprint_id, format } => {
+            scripts::sprint_report::run(&ctx, sprint_id, format)?;
+        }
+        Commands::Retro { sprint_id } => {
+            scripts::retro::run(&ctx, sprint_id).await?;
+        }
+        Commands::Burndown { sprint_id } => {
+            scripts::burndown::run(&ctx, sprint_id)?;
+        }
+        Commands::BenchModels { models, runs } => {
+            scripts::bench::run(&ctx, models, runs).await?;
+        }
+        Commands::Export { anonymized, md, org, obsidian } => {
+            let format = match (md, org, obsidian) {
+                (true, _, _) => scripts::export::ExportFormat::Markdown,
+                (_, true, _) => scripts::export::ExportFormat::Org,
+                (_, _, Some(vault)) => scripts::export::ExportFormat::Obsidian(vault),
+                _ => scripts::export::ExportFormat::Json,
+            };
+            scripts::export::run(&ctx, anonymized, format)?;
+        }
+        Commands::Index { full } => {
+            scripts::index::run(&ctx, full).await?;
+        }
+        Commands::Find { query, limit } => {
+            scripts::find::run(&ctx, query, limit).await?;
+        }
+        Commands::Chat { task } => {
+            scripts::chat::run(&ctx, task).await?;
+        }
+        Commands::Config { action } => match action {
+            ConfigCommands::Get { key, global, local } => {
+                scripts::config::get(&ctx, &key, scripts::config::Scope::from_flags(global, local))?;
+            }
+            ConfigCommands::Set { key, value, global, local } => {
+                scripts::config::set(&ctx, &key, &value, scripts::config::Scope::from_flags(global, local))?;
+            }
+            ConfigCommands::Show { resolved } => {
+                scripts::config::show(&ctx, resolved)?;
+            }
+        },
+        Commands::Doctor => {
+            scripts::doctor::run(&ctx).await?;
+        }
+        Commands::Convert { to, global } => {
+            scripts::convert::run(&ctx, to, global)?;
+        }
+        Commands::History { item_id } => {
+            scripts::history::run(&ctx, item_id)?;
+        }
+        Commands::PromptTest { template, fixture, runs, parser } => {
+            scripts::prompt_test::run(&ctx, template, &fixture, runs, parser).await?;
+        }
+        Commands::Ask { question } => {
+            scripts::ask::run(&ctx, question).await?;
+        }
+        Commands::Graph { mermaid } => {
+            scripts::graph::run(&ctx, mermaid)?;
+        }
+        Commands::Velocity { last } => {
+            scripts::velocity::run(&ctx, last)?;
+        }
+        Commands::Risks { refresh } => {
+            scripts::risks::run(&ctx, refresh).await?;
+        }
+        Commands::Workspace { action } => match action {
+            WorkspaceCommands::Init { name } => {
+                scripts::workspace::init(&ctx, name)?;
+            }
+            WorkspaceCommands::AddItem { repo, title, description, effort } => {
+                scripts::workspace::add_item(&ctx, repo, title, description, effort)?;
+            }
+        },
+        Commands::Portfolio => {
+            scripts::workspace::portfolio(&ctx)?;
         }
         Commands::StartTask { task_id } => {
-            scripts::tasks::start_task(task_id)?;
+            scripts::tasks::start_task(&ctx, task_id)?;
+        }
+        Commands::AssistTask { task_id, prompt, prompt_file } => { // Added prompt
+            scripts::tasks::assist_task(&ctx, task_id, prompt, prompt_file.as_deref()).await?; // Pass prompt
+        }
+        Commands::CompleteTask { task_id, verify } => {
+            scripts::tasks::complete_task(&ctx, task_id, verify).await?;
+        }
+        Commands::ExecuteTask { task_id, prompt, resume, verify, auto_fix, max_iterations } => {
+            let options = scripts::tasks::ExecuteOptions {
+                prompt,
+                resume,
+                verify,
+                auto_fix: auto_fix.then_some(max_iterations),
+                ..Default::default()
+            };
+            scripts::tasks::execute_task(&ctx, task_id, options).await?;
+        }
+        Commands::ExecuteSprint { sprint_id, auto_approve_code, auto_approve_deps, verify, auto_fix, max_iterations } => {
+            let options = scripts::tasks::ExecuteOptions {
+                verify,
+                auto_fix: auto_fix.then_some(max_iterations),
+                auto_approve: auto_update::AutoApprove { code: auto_approve_code, dependencies: auto_approve_deps },
+                ..Default::default()
+            };
+            scripts::execute_sprint::run(&ctx, sprint_id, options).await?;
+        }
+        Commands::ApplySession { file, only, verify } => {
+            scripts::session::apply(&ctx, &file, only.as_deref(), verify)?;
+        }
+        Commands::TestTask { task_id, files } => {
+            scripts::test_task::run(&ctx, task_id, files).await?;
+        }
+        Commands::ReviewTask { task_id, files } => {
+            scripts::review_task::run(&ctx, task_id, files).await?;
+        }
+        Commands::VerifyTask { task_id, command } => {
+            scripts::verify_task::run(&ctx, task_id, command).await?;
+        }
+        Commands::FixTests { command } => {
+            scripts::fix_tests::run(&ctx, command).await?;
+        }
+        Commands::SyncGit => {
+            scripts::sync_git::run(&ctx)?;
+        }
+        Commands::CommitMsg { item_id } => {
+            scripts::commit_msg::run(&ctx, item_id)?;
         }
-        Commands::AssistTask { task_id, prompt } => { // Added prompt
-            scripts::tasks::assist_task(task_id, prompt).await?; // Pass prompt
+        Commands::ShowItem { item_id } => {
+            scripts::show_item::run(&ctx, item_id)?;
         }
-        Commands::CompleteTask { task_id } => {
-            scripts::tasks::complete_task(task_id)?;
+        Commands::EditItem { item_id, title, story, effort, priority, component, epic, external_ref, add_ac, remove_ac, fields, editor } => {
+            let edits = scripts::edit_item::ItemEdits { title, story, effort, priority, component, epic, external_ref, add_ac, remove_ac, fields };
+            scripts::edit_item::run(&ctx, item_id, edits, editor)?;
         }
-        Commands::AddStory { title, description } => {
-            scripts::stories::add_manual_story(title, description)?;
+        Commands::SetStatus { item_ids, status } => {
+            scripts::set_field::set_status(&ctx, item_ids, status)?;
         }
-        Commands::ListStories => {
-            scripts::stories::list_stories()?;
+        Commands::SetPriority { item_ids, priority } => {
+            scripts::set_field::set_priority(&ctx, item_ids, priority)?;
         }
+        Commands::SetEffort { item_ids, effort } => {
+            scripts::set_field::set_effort(&ctx, item_ids, effort)?;
+        }
+        Commands::RemoveItem { item_id } => {
+            scripts::archive::remove_item(&ctx, item_id)?;
+        }
+        Commands::ArchiveItem { item_ids } => {
+            scripts::archive::archive_items(&ctx, item_ids)?;
+        }
+        Commands::AddStory { title, description, epic } => {
+            scripts::stories::add_manual_story(&ctx, title, description, epic)?;
+        }
+        Commands::Breakdown { item_id } => {
+            scripts::breakdown::run(&ctx, item_id).await?;
+        }
+        Commands::Estimate { item_ids } => {
+            scripts::estimate::run(&ctx, item_ids).await?;
+        }
+        Commands::Groom => {
+            scripts::groom::run(&ctx).await?;
+        }
+        Commands::ListStories { filter } => {
+            scripts::stories::list_stories(&ctx, &filter)?;
+        }
+        Commands::Import { source } => match source {
+            ImportCommands::Jira { file } => {
+                scripts::import::jira::run(&ctx, &file)?;
+            }
+        },
+        Commands::Github { action } => match action {
+            GithubCommands::Sync => {
+                scripts::github::sync(&ctx).await?;
+            }
+        },
+        Commands::Gitlab { action } => match action {
+            GitlabCommands::Push { epics_as_issues } => {
+                scripts::gitlab::push(&ctx, epics_as_issues).await?;
+            }
+        },
+        Commands::Linear { action } => match action {
+            LinearCommands::Push { team } => {
+                scripts::linear::push(&ctx, &team).await?;
+            }
+        },
+        Commands::Undo => {
+            scripts::undo::run(&ctx)?;
+        }
+        Commands::Backup { keep, list } => {
+            scripts::backup::run(&ctx, keep, list)?;
+        }
+        Commands::Restore { file, list } => {
+            scripts::restore::run(&ctx, &file, list)?;
+        }
+        Commands::Validate { schema } => {
+            scripts::validate::run(&ctx, schema)?;
+        }
+        Commands::Reconcile => {
+            scripts::reconcile::run(&ctx).await?;
+        }
+        Commands::Prd { action } => match action {
+            PrdCommands::Check => {
+                scripts::prd::check(&ctx).await?;
+            }
+        },
+        Commands::Prompts { action } => match action {
+            PromptsCommands::List => {
+                scripts::prompts::list()?;
+            }
+            PromptsCommands::Show { name, default } => {
+                scripts::prompts::show(&name, default)?;
+            }
+            PromptsCommands::Edit { name } => {
+                scripts::prompts::edit(&ctx, &name)?;
+            }
+            PromptsCommands::Reset { name } => {
+                scripts::prompts::reset(&ctx, &name)?;
+            }
+            PromptsCommands::Upgrade => {
+                scripts::prompts::upgrade(&ctx)?;
+            }
+        },
         Commands::LlmCycle { prompt } => {
-            scripts::llm_cycle::run(prompt).await?;
+            scripts::llm_cycle::run(&ctx, prompt).await?;
         }
     }
 
+    if let Some(auto_commit) = auto_commit {
+        auto_commit.finish();
+    }
     Ok(())
 }
\ No newline at end of file
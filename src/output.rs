@@ -0,0 +1,226 @@
+// src/output.rs
+//! How much env-coach prints.
+//!
+//! Verbosity is set per topic with a `RUST_LOG`-style spec such as `quiet`, `verbose` or
+//! `normal,hints=off,llm=debug`: a bare level sets the default, `topic=level` overrides one
+//! topic. The spec comes from `ENV_COACH_VERBOSITY`, else from `output.verbosity` in the
+//! global config. Diagnostics from the `log` crate are separate and follow `RUST_LOG`.
+//!
+//! Read commands can also print structured data instead of text with `--format json|yaml`,
+//! and the global `--output <FILE>` sends a command's result to a file through `Writer`.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::config::GlobalConfig;
+
+pub const VERBOSITY_ENV: &str = "ENV_COACH_VERBOSITY";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Quiet,
+    Normal,
+    Verbose,
+    Debug,
+}
+
+impl std::str::FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "quiet" | "off" => Ok(Level::Quiet),
+            "normal" | "on" => Ok(Level::Normal),
+            "verbose" => Ok(Level::Verbose),
+            "debug" => Ok(Level::Debug),
+            _ => Err(format!("unknown verbosity '{}' (expected quiet, normal, verbose or debug)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Topic {
+    /// "Next steps" coaching blocks and tips
+    Hints,
+    /// Raw LLM responses (verbose) and the prompts sent (debug)
+    Llm,
+    /// One line per file created or written
+    Files,
+}
+
+impl std::str::FromStr for Topic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "hints" => Ok(Topic::Hints),
+            "llm" => Ok(Topic::Llm),
+            "files" => Ok(Topic::Files),
+            _ => Err(format!("unknown output topic '{}' (expected hints, llm or files)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verbosity {
+    default: Level,
+    topics: BTreeMap<Topic, Level>,
+}
+
+impl Default for Verbosity {
+    fn default() -> Self {
+        Self { default: Level::Normal, topics: BTreeMap::new() }
+    }
+}
+
+impl Verbosity {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut verbosity = Verbosity::default();
+        for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            match part.split_once('=') {
+                Some((topic, level)) => {
+                    verbosity.topics.insert(topic.parse()?, level.parse()?);
+                }
+                None => verbosity.default = part.parse()?,
+            }
+        }
+        Ok(verbosity)
+    }
+
+    pub fn level(&self, topic: Topic) -> Level {
+        self.topics.get(&topic).copied().unwrap_or(self.default)
+    }
+
+    pub fn enabled(&self, topic: Topic, level: Level) -> bool {
+        self.level(topic) >= level
+    }
+}
+
+static VERBOSITY: OnceLock<Verbosity> = OnceLock::new();
+
+/// Resolve the verbosity once at startup. An invalid spec is reported and ignored.
+pub fn init(global_config: &GlobalConfig) {
+    let spec = std::env::var(VERBOSITY_ENV).ok()
+        .or_else(|| global_config.output.as_ref().and_then(|output| output.verbosity.clone()));
+    let verbosity = match spec.as_deref().map(Verbosity::parse) {
+        Some(Ok(verbosity)) => verbosity,
+        Some(Err(e)) => {
+            eprintln!("⚠️ Ignoring verbosity setting: {}", e);
+            Verbosity::default()
+        }
+        None => Verbosity::default(),
+    };
+    let _ = VERBOSITY.set(verbosity);
+}
+
+/// Normal verbosity until `init` runs, e.g. in tests.
+pub fn enabled(topic: Topic, level: Level) -> bool {
+    VERBOSITY.get_or_init(Verbosity::default).enabled(topic, level)
+}
+
+/// Whether to print "Next steps" blocks and other coaching.
+pub fn show_hints() -> bool {
+    enabled(Topic::Hints, Level::Normal)
+}
+
+/// Output of read commands, chosen with the global `--format` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Format {
+    /// Human-readable text with emoji (the default)
+    #[default]
+    Text,
+    Json,
+    Yaml,
+}
+
+impl Format {
+    pub fn is_structured(self) -> bool {
+        !matches!(self, Format::Text)
+    }
+}
+
+pub(crate) fn to_structured<T: Serialize>(format: Format, value: &T) -> Result<String> {
+    match format {
+        Format::Json => serde_json::to_string_pretty(value).context("Failed to serialize output as JSON"),
+        Format::Yaml => serde_yaml::to_string(value).map(|yaml| yaml.trim_end().to_string()).context("Failed to serialize output as YAML"),
+        Format::Text => anyhow::bail!("Text output is printed by each command"),
+    }
+}
+
+/// Where a command's result goes: stdout, or the file given with the global `--output`.
+/// Progress messages and hints are not part of the result and are printed as usual.
+#[derive(Debug, Clone, Default)]
+pub struct Writer {
+    path: Option<PathBuf>,
+}
+
+impl Writer {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self { path }
+    }
+
+    /// The `--output` path, for commands that write a file (or directory) of their own.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Print `content`, or write it to the `--output` file.
+    pub fn write(&self, content: &str) -> Result<()> {
+        match &self.path {
+            Some(path) => std::fs::write(path, format!("{}\n", content)).with_context(|| format!("Failed to write {}", path.display())),
+            None => {
+                println!("{}", content);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verbosity_spec() {
+        let verbosity = Verbosity::parse("quiet, llm=debug").unwrap();
+        assert!(!verbosity.enabled(Topic::Hints, Level::Normal));
+        assert!(!verbosity.enabled(Topic::Files, Level::Normal));
+        assert!(verbosity.enabled(Topic::Llm, Level::Debug));
+
+        let verbosity = Verbosity::parse("hints=off").unwrap();
+        assert_eq!(verbosity.level(Topic::Hints), Level::Quiet);
+        assert_eq!(verbosity.level(Topic::Files), Level::Normal);
+        assert!(!verbosity.enabled(Topic::Llm, Level::Verbose));
+
+        assert_eq!(Verbosity::parse("").unwrap(), Verbosity::default());
+        assert!(Verbosity::parse("loud").is_err());
+        assert!(Verbosity::parse("colors=on").is_err());
+    }
+
+    #[test]
+    fn test_structured_output() {
+        #[derive(Serialize)]
+        struct Listing {
+            total: usize,
+            ids: Vec<&'static str>,
+        }
+        let listing = Listing { total: 2, ids: vec!["US-001", "BUG-001"] };
+        assert_eq!(to_structured(Format::Json, &listing).unwrap(), "{\n  \"total\": 2,\n  \"ids\": [\n    \"US-001\",\n    \"BUG-001\"\n  ]\n}");
+        assert_eq!(to_structured(Format::Yaml, &listing).unwrap(), "total: 2\nids:\n- US-001\n- BUG-001");
+        assert!(to_structured(Format::Text, &listing).is_err());
+    }
+
+    #[test]
+    fn test_writer_writes_to_the_output_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("status.json");
+        let writer = Writer::new(Some(path.clone()));
+        assert_eq!(writer.path(), Some(path.as_path()));
+        writer.write("{}").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{}\n");
+        assert_eq!(Writer::default().path(), None);
+    }
+}
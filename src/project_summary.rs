@@ -0,0 +1,173 @@
+// src/project_summary.rs
+//! A one-paragraph summary of the codebase (entry points, modules, key types), written by
+//! the LLM from an outline of the source files and cached in `.env-coach/cache/summary.md`.
+//! The cache records a fingerprint of the sources it was made from and is written again
+//! when they change. Prompts ask for it with `{{project_summary}}`, which
+//! `ollama::send_generation_prompt` and `ollama::send_chat` fill just before sending.
+
+use std::path::{Path, PathBuf};
+
+use crate::code_context::{self, ContextConfig};
+use crate::code_index::content_hash;
+use crate::config::FinalLlmConfig;
+use crate::ollama;
+use crate::templates::Templates;
+use crate::tokens;
+
+pub const SUMMARY_FILE: &str = ".env-coach/cache/summary.md";
+pub const PLACEHOLDER: &str = "{{project_summary}}";
+const NO_SUMMARY: &str = "No project summary available.";
+/// Declarations listed per file in the outline.
+const OUTLINE_LINES_PER_FILE: usize = 25;
+/// A longer answer is cut, so the summary stays cheap in every prompt.
+const SUMMARY_MAX_TOKENS: usize = 300;
+
+const DECLARATION_PREFIXES: &[&str] = &[
+    "pub ", "fn ", "struct ", "enum ", "trait ", "impl ", "impl<", "mod ", "async fn ",
+    "class ", "def ", "async def ", "function ", "export ", "interface ", "type ", "func ",
+];
+
+fn is_declaration(line: &str) -> bool {
+    DECLARATION_PREFIXES.iter().any(|prefix| line.starts_with(prefix))
+}
+
+/// Each file's path followed by its top-level declarations, without bodies.
+pub fn outline(root: &Path, files: &[PathBuf]) -> String {
+    files.iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(path).ok()?;
+            let declarations: Vec<String> = content.lines()
+                .filter(|line| is_declaration(line))
+                .take(OUTLINE_LINES_PER_FILE)
+                .map(|line| format!("  {}", line.trim_end().trim_end_matches('{').trim_end()))
+                .collect();
+            Some(format!("{}\n{}", code_context::relative(root, path), declarations.join("\n")).trim_end().to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Changes whenever a source file is added, removed or edited.
+pub fn fingerprint(root: &Path, files: &[PathBuf]) -> u64 {
+    let sources = files.iter()
+        .map(|path| {
+            let content = std::fs::read_to_string(path).unwrap_or_default();
+            format!("{}\n{:016x}", code_context::relative(root, path), content_hash(&content))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    content_hash(&sources)
+}
+
+fn header(fingerprint: u64) -> String {
+    format!("<!-- sources: {:016x} -->", fingerprint)
+}
+
+/// The fingerprint and summary of a cached summary file.
+fn parse_cached(content: &str) -> Option<(u64, String)> {
+    let (first, summary) = content.split_once('\n')?;
+    let hash = first.strip_prefix("<!-- sources: ")?.strip_suffix(" -->")?;
+    Some((u64::from_str_radix(hash, 16).ok()?, summary.trim().to_string()))
+}
+
+async fn generate(cfg: &FinalLlmConfig, root: &Path, outline: &str) -> anyhow::Result<String> {
+    let template = std::fs::read_to_string(root.join(".env-coach/prompts/project_summary.md"))
+        .unwrap_or_else(|_| Templates::default_project_summary_prompt_content());
+    let outline = tokens::trim_to_tokens(outline, tokens::prompt_budget(cfg) / 2);
+    let summary = ollama::generate(cfg, &template.replace("{{outline}}", &outline)).await?;
+    Ok(tokens::trim_to_tokens(summary.trim(), SUMMARY_MAX_TOKENS).trim().to_string())
+}
+
+/// The summary of the sources under `root`, from the cache when they have not changed since
+/// it was written. Otherwise it is written again; when the LLM fails, the stale summary (or a
+/// note that there is none) is used.
+pub async fn current(cfg: &FinalLlmConfig, root: &Path) -> String {
+    let config = ContextConfig::load_or_default(root);
+    let files = code_context::collect_files(root, &config);
+    let sources = fingerprint(root, &files);
+    let cached = std::fs::read_to_string(root.join(SUMMARY_FILE)).ok().and_then(|content| parse_cached(&content));
+    if let Some((hash, summary)) = &cached {
+        if *hash == sources {
+            return summary.clone();
+        }
+    }
+    let stale = cached.map(|(_, summary)| summary).unwrap_or_else(|| NO_SUMMARY.to_string());
+    if files.is_empty() {
+        return stale;
+    }
+
+    println!("🗺️  Summarizing the codebase for prompts ({})...",
+        if stale == NO_SUMMARY { "no summary cached yet" } else { "sources changed since the last summary" });
+    match generate(cfg, root, &outline(root, &files)).await {
+        Ok(summary) if !summary.is_empty() => {
+            // Only a project's own .env-coach is written to
+            if root.join(".env-coach").is_dir() {
+                let path = root.join(SUMMARY_FILE);
+                let written = path.parent().map(std::fs::create_dir_all).transpose()
+                    .and_then(|_| std::fs::write(&path, format!("{}\n{}\n", header(sources), summary)));
+                if let Err(e) = written {
+                    println!("⚠️ Could not cache the project summary in {}: {}", SUMMARY_FILE, e);
+                }
+            }
+            summary
+        }
+        Ok(_) => stale,
+        Err(e) => {
+            println!("⚠️ Could not summarize the codebase ({:#}); using {}", e,
+                if stale == NO_SUMMARY { "no summary" } else { "the previous summary" });
+            stale
+        }
+    }
+}
+
+/// `prompt` with `{{project_summary}}` filled from the current directory's summary.
+/// Prompts without the placeholder are returned as they are, without reading the sources.
+pub async fn fill(cfg: &FinalLlmConfig, prompt: &str) -> String {
+    if !prompt.contains(PLACEHOLDER) {
+        return prompt.to_string();
+    }
+    let root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    prompt.replace(PLACEHOLDER, &current(cfg, &root).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outline_and_fingerprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "mod cart;\n\nfn main() {\n    cart::run();\n}\n").unwrap();
+        std::fs::write(root.join("src/cart.rs"), "pub struct Cart {\n    items: Vec<u32>,\n}\n\nimpl Cart {\n    pub fn total(&self) -> u32 { 0 }\n}\n").unwrap();
+        let files = code_context::collect_files(root, &ContextConfig::default());
+
+        assert_eq!(outline(root, &files), "src/main.rs\n  mod cart;\n  fn main()\nsrc/cart.rs\n  pub struct Cart\n  impl Cart");
+
+        let before = fingerprint(root, &files);
+        assert_eq!(fingerprint(root, &files), before);
+        std::fs::write(root.join("src/cart.rs"), "pub struct Cart;\n").unwrap();
+        assert_ne!(fingerprint(root, &files), before);
+    }
+
+    #[tokio::test]
+    async fn test_current_uses_cache_until_sources_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join(".env-coach/cache")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub fn parse() {}\n").unwrap();
+        let files = code_context::collect_files(root, &ContextConfig::default());
+        let cached = format!("{}\nA parser library.\n", header(fingerprint(root, &files)));
+        assert_eq!(parse_cached(&cached), Some((fingerprint(root, &files), "A parser library.".to_string())));
+        std::fs::write(root.join(SUMMARY_FILE), &cached).unwrap();
+
+        // Nothing listens on port 9: a fresh cache is used without asking, a stale one is kept
+        let cfg = FinalLlmConfig { port: 9, timeout_ms: 500, ..FinalLlmConfig::default() };
+        assert_eq!(current(&cfg, root).await, "A parser library.");
+        std::fs::write(root.join("src/lib.rs"), "pub fn parse() {}\npub fn render() {}\n").unwrap();
+        assert_eq!(current(&cfg, root).await, "A parser library.");
+        assert_eq!(parse_cached("no header\ntext"), None);
+    }
+}
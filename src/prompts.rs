@@ -1,5 +1,261 @@
 // src/prompts.rs
-//! Prompt management utilities
-//! This module is currently placeholder for future prompt management features
+//! Default prompt registry and upgrade support.
+//!
+//! The defaults are embedded from `assets/prompts/` at build time. When a default is
+//! installed into `.env-coach/prompts/`, a copy is kept in `.env-coach/prompts/.defaults/`
+//! together with its version, so `prompts upgrade` can tell the user's edits apart from
+//! changes to the default and merge the two.
 
-// Currently empty - used for future prompt customization functionality
\ No newline at end of file
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::{BacklogItem, Project};
+use crate::templates::Templates;
+
+pub struct DefaultPrompt {
+    pub file_name: &'static str,
+    /// Bump whenever the asset changes so installed copies can be upgraded
+    pub version: u32,
+    pub content: fn() -> String,
+}
+
+pub const DEFAULT_PROMPTS: &[DefaultPrompt] = &[
+    DefaultPrompt { file_name: "requirements_analyst.md", version: 3, content: Templates::default_requirements_analyst_prompt_content },
+    DefaultPrompt { file_name: "sprint_planner.md", version: 3, content: Templates::default_sprint_planner_prompt_content },
+    DefaultPrompt { file_name: "task_assistant.md", version: 8, content: Templates::default_task_assistant_prompt_content },
+    DefaultPrompt { file_name: "risk_analyst.md", version: 2, content: Templates::default_risk_analyst_prompt_content },
+    DefaultPrompt { file_name: "retrospective.md", version: 2, content: Templates::default_retrospective_prompt_content },
+    DefaultPrompt { file_name: "project_qa.md", version: 2, content: Templates::default_project_qa_prompt_content },
+    DefaultPrompt { file_name: "story_splitter.md", version: 2, content: Templates::default_story_splitter_prompt_content },
+    DefaultPrompt { file_name: "bug_analyst.md", version: 2, content: Templates::default_bug_analyst_prompt_content },
+    DefaultPrompt { file_name: "sprint_goal.md", version: 2, content: Templates::default_sprint_goal_prompt_content },
+    DefaultPrompt { file_name: "metrics_check.md", version: 2, content: Templates::default_metrics_check_prompt_content },
+    DefaultPrompt { file_name: "backlog_reconcile.md", version: 2, content: Templates::default_backlog_reconcile_prompt_content },
+    DefaultPrompt { file_name: "fix_tests.md", version: 3, content: Templates::default_fix_tests_prompt_content },
+    DefaultPrompt { file_name: "fix_build.md", version: 2, content: Templates::default_fix_build_prompt_content },
+    DefaultPrompt { file_name: "test_writer.md", version: 2, content: Templates::default_test_writer_prompt_content },
+    DefaultPrompt { file_name: "code_reviewer.md", version: 2, content: Templates::default_code_reviewer_prompt_content },
+    DefaultPrompt { file_name: "task_breakdown.md", version: 2, content: Templates::default_task_breakdown_prompt_content },
+    DefaultPrompt { file_name: "effort_estimator.md", version: 2, content: Templates::default_effort_estimator_prompt_content },
+    DefaultPrompt { file_name: "backlog_groomer.md", version: 2, content: Templates::default_backlog_groomer_prompt_content },
+    DefaultPrompt { file_name: "criteria_checker.md", version: 2, content: Templates::default_criteria_checker_prompt_content },
+    DefaultPrompt { file_name: "chat.md", version: 2, content: Templates::default_chat_prompt_content },
+    DefaultPrompt { file_name: "project_summary.md", version: 1, content: Templates::default_project_summary_prompt_content },
+];
+
+/// The default prompt called `name`, with or without its `.md` extension.
+pub fn find_default(name: &str) -> Option<&'static DefaultPrompt> {
+    let name = name.strip_suffix(".md").unwrap_or(name);
+    DEFAULT_PROMPTS.iter().find(|prompt| prompt.file_name.strip_suffix(".md") == Some(name))
+}
+
+/// Prompts the code commands read through `read_template`; only these have language variants.
+pub const VARIANT_PROMPTS: &[&str] = &["task_assistant.md", "fix_build.md", "fix_tests.md", "test_writer.md", "code_reviewer.md", "criteria_checker.md"];
+
+/// Languages `get_primary_language` can return, i.e. the variants that are ever read.
+pub const VARIANT_LANGUAGES: &[&str] = &["rust", "javascript", "python", "go", "java"];
+
+/// The `language` variant of a prompt file: `task_assistant.md` → `task_assistant.python.md`.
+pub fn variant_file_name(file_name: &str, language: &str) -> String {
+    format!("{}.{}.md", file_name.strip_suffix(".md").unwrap_or(file_name), language)
+}
+
+/// The project's prompt at `path`, preferring its variant for `language` (see
+/// `get_primary_language`) next to it. `None` when neither file exists.
+pub fn read_template(path: &Path, language: &str) -> Option<String> {
+    let file_name = path.file_name()?.to_string_lossy();
+    let variant = path.with_file_name(variant_file_name(&file_name, language));
+    std::fs::read_to_string(&variant)
+        .inspect(|_| log::info!("Using {}", variant.display()))
+        .or_else(|_| std::fs::read_to_string(path))
+        .ok()
+}
+
+/// Fill the placeholders every prompt can use: `{{prd.problem}}` and `{{prd.success_metrics}}`
+/// from the project's PRD and, in prompts about one item, `{{extra.<field>}}` with that
+/// item's custom fields.
+pub fn fill_project_context(template: &str, project: &Project, item: Option<&BacklogItem>) -> String {
+    let prd = project.meta.prd.as_ref();
+    let problem = prd.map(|prd| prd.problem.trim()).filter(|problem| !problem.is_empty()).unwrap_or("Not recorded.");
+    let metrics = prd.filter(|prd| !prd.success_metrics.is_empty())
+        .map(|prd| prd.success_metrics.iter().map(|metric| format!("- {}", metric)).collect::<Vec<_>>().join("\n"))
+        .unwrap_or_else(|| "None recorded.".to_string());
+    let mut filled = template
+        .replace("{{prd.problem}}", problem)
+        .replace("{{prd.success_metrics}}", &metrics);
+    let Some(item) = item else { return filled };
+    // Scan past each filled value, so a value containing `{{extra.` is left as it is
+    let mut from = 0;
+    while let Some(start) = filled[from..].find("{{extra.").map(|offset| from + offset) {
+        let Some(len) = filled[start..].find("}}") else { break };
+        let key = &filled[start + "{{extra.".len()..start + len];
+        let value = item.field_text(key).unwrap_or_else(|| "Not set".to_string());
+        filled.replace_range(start..start + len + 2, &value);
+        from = start + value.len();
+    }
+    filled
+}
+
+/// The template given with `--prompt-file`, used instead of the project's prompt for one run.
+pub fn read_override(path: &Path) -> Result<String> {
+    let template = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read prompt file {}", path.display()))?;
+    println!("📝 Using prompt from {}", path.display());
+    Ok(template)
+}
+
+const DEFAULTS_DIR: &str = ".defaults";
+const VERSIONS_FILE: &str = "versions.json";
+
+/// Where installed defaults are remembered: `<prompts_dir>/.defaults/`.
+fn defaults_dir(prompts_dir: &Path) -> PathBuf {
+    prompts_dir.join(DEFAULTS_DIR)
+}
+
+/// Installed default versions by file name.
+pub fn installed_versions(prompts_dir: &Path) -> BTreeMap<String, u32> {
+    std::fs::read_to_string(defaults_dir(prompts_dir).join(VERSIONS_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// The default as it was when the user's copy was installed or last upgraded.
+pub fn installed_base(prompts_dir: &Path, file_name: &str) -> Option<String> {
+    std::fs::read_to_string(defaults_dir(prompts_dir).join(file_name)).ok()
+}
+
+/// Remember `prompt` as the base of the user's copy.
+pub fn record_base(prompts_dir: &Path, prompt: &DefaultPrompt) -> Result<()> {
+    let dir = defaults_dir(prompts_dir);
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    std::fs::write(dir.join(prompt.file_name), (prompt.content)())
+        .with_context(|| format!("Failed to record default for {}", prompt.file_name))?;
+
+    let mut versions = installed_versions(prompts_dir);
+    versions.insert(prompt.file_name.to_string(), prompt.version);
+    std::fs::write(dir.join(VERSIONS_FILE), serde_json::to_string_pretty(&versions)?)
+        .context("Failed to write prompt versions")
+}
+
+/// Write the default prompt unless the user already has one, as `init` does.
+pub fn install_default(prompts_dir: &Path, prompt: &DefaultPrompt) -> Result<()> {
+    if prompts_dir.join(prompt.file_name).exists() {
+        return Ok(());
+    }
+    Templates::create_default_prompt_if_missing(prompts_dir, prompt.file_name, (prompt.content)())
+        .with_context(|| format!("Failed to create default {} prompt", prompt.file_name))?;
+    record_base(prompts_dir, prompt)
+}
+
+/// How the user's copy of a prompt relates to the current default.
+#[derive(Debug, PartialEq)]
+pub enum UpgradeStatus {
+    Missing,
+    UpToDate,
+    /// Edited, but the default has not changed since it was installed
+    Customized,
+    /// Never edited; the new default can simply replace it
+    Unmodified,
+    /// Edited, and the edits merge cleanly onto the new default
+    Mergeable(String),
+    /// Edited in a way that overlaps the default's changes (or no base is known);
+    /// the text holds conflict markers when a base was available
+    Conflict(Option<String>),
+}
+
+/// Compare the user's copy with the new default, using the recorded base for a
+/// three-way merge.
+pub fn upgrade_status(current: Option<&str>, base: Option<&str>, new_default: &str) -> UpgradeStatus {
+    let Some(current) = current else { return UpgradeStatus::Missing };
+    if current == new_default {
+        return UpgradeStatus::UpToDate;
+    }
+    let Some(base) = base else { return UpgradeStatus::Conflict(None) };
+    if base == new_default {
+        return UpgradeStatus::Customized;
+    }
+    if current == base {
+        return UpgradeStatus::Unmodified;
+    }
+    match diffy::merge(base, current, new_default) {
+        Ok(merged) => UpgradeStatus::Mergeable(merged),
+        Err(with_markers) => UpgradeStatus::Conflict(Some(with_markers)),
+    }
+}
+
+/// Unified diff from the user's copy to the new default.
+pub fn diff(current: &str, new_default: &str) -> String {
+    diffy::create_patch(current, new_default).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_template_prefers_the_language_variant() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("task_assistant.md");
+        assert_eq!(read_template(&path, "python"), None);
+        std::fs::write(&path, "generic").unwrap();
+        std::fs::write(dir.path().join(variant_file_name("task_assistant.md", "python")), "pythonic").unwrap();
+        assert_eq!(read_template(&path, "python").as_deref(), Some("pythonic"));
+        assert_eq!(read_template(&path, "go").as_deref(), Some("generic"));
+    }
+
+    #[test]
+    fn test_fill_project_context_uses_prd_and_item_fields() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        let template = "{{prd.problem}}\n{{prd.success_metrics}}\n{{extra.customer}}/{{extra.tier}}";
+        assert_eq!(fill_project_context(template, &project, None), "Not recorded.\nNone recorded.\n{{extra.customer}}/{{extra.tier}}");
+
+        project.meta.prd = Some(crate::config::Prd {
+            problem: "Orders get lost".to_string(),
+            success_metrics: vec!["No lost orders".to_string(), "Checkout < 2s".to_string()],
+            ..Default::default()
+        });
+        let mut item = BacklogItem::test("US-001");
+        item.extra.insert("customer".to_string(), serde_json::json!("{{extra.customer}} Inc"));
+        assert_eq!(fill_project_context(template, &project, Some(&item)),
+            "Orders get lost\n- No lost orders\n- Checkout < 2s\n{{extra.customer}} Inc/Not set");
+    }
+
+    #[test]
+    fn test_find_default_accepts_names_with_or_without_extension() {
+        assert_eq!(find_default("task_assistant").map(|p| p.file_name), Some("task_assistant.md"));
+        assert_eq!(find_default("sprint_planner.md").map(|p| p.file_name), Some("sprint_planner.md"));
+        assert!(find_default("task").is_none());
+    }
+
+    #[test]
+    fn test_upgrade_status() {
+        let base = "intro\nrules\noutput\n";
+        let new_default = "intro\nrules v2\noutput\n";
+        assert_eq!(upgrade_status(None, None, new_default), UpgradeStatus::Missing);
+        assert_eq!(upgrade_status(Some(new_default), Some(base), new_default), UpgradeStatus::UpToDate);
+        assert_eq!(upgrade_status(Some(base), Some(base), new_default), UpgradeStatus::Unmodified);
+        assert_eq!(upgrade_status(Some("mine\n"), Some(new_default), new_default), UpgradeStatus::Customized);
+        assert_eq!(
+            upgrade_status(Some("intro\nrules\noutput\nmy note\n"), Some(base), new_default),
+            UpgradeStatus::Mergeable("intro\nrules v2\noutput\nmy note\n".to_string())
+        );
+        assert!(matches!(upgrade_status(Some("intro\nmy rules\noutput\n"), Some(base), new_default), UpgradeStatus::Conflict(Some(_))));
+        assert_eq!(upgrade_status(Some("custom\n"), None, new_default), UpgradeStatus::Conflict(None));
+    }
+
+    #[test]
+    fn test_install_default_records_base_and_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let prompt = &DEFAULT_PROMPTS[0];
+        install_default(dir.path(), prompt).unwrap();
+        assert_eq!(installed_base(dir.path(), prompt.file_name), Some((prompt.content)()));
+        assert_eq!(installed_versions(dir.path()).get(prompt.file_name), Some(&prompt.version));
+
+        // An existing user file is left alone
+        std::fs::write(dir.path().join(prompt.file_name), "mine").unwrap();
+        install_default(dir.path(), prompt).unwrap();
+        assert_eq!(std::fs::read_to_string(dir.path().join(prompt.file_name)).unwrap(), "mine");
+    }
+}
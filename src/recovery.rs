@@ -0,0 +1,263 @@
+// src/recovery.rs
+//! Crash recovery for saves that touch more than one file.
+//!
+//! Before a save writes project.json (and the workspace file, if any), the current
+//! contents are copied to `.env-coach/recovery/` together with a journal naming the
+//! running process. A successful save removes the directory again, so a journal found on
+//! startup means a previous run died half-way and its files may be out of step.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::config::FileFormat;
+use crate::context::AppContext;
+
+pub const RECOVERY_DIR: &str = ".env-coach/recovery";
+const JOURNAL_FILE: &str = "journal.json";
+
+/// Without /proc we cannot ask whether the owner is alive, so a journal younger than
+/// this is assumed to belong to a save still in progress.
+const STALE_AFTER_MINUTES: i64 = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Journal {
+    pub pid: u32,
+    pub command: String,
+    pub started: DateTime<Utc>,
+    pub files: Vec<JournalFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalFile {
+    pub path: PathBuf,
+    /// Copy taken before the save; `None` when the file did not exist yet
+    pub backup: Option<PathBuf>,
+}
+
+/// What a previous run left behind.
+#[derive(Debug)]
+pub enum Leftover {
+    None,
+    /// The journal's process still seems to be running
+    InProgress(Journal),
+    Crashed(Journal),
+}
+
+/// An open save. Dropping it without `commit` leaves the journal in place on purpose.
+pub struct Transaction {
+    dir: PathBuf,
+}
+
+impl Transaction {
+    /// Record the current state of `files` before they are overwritten.
+    pub fn begin(root: &Path, files: &[PathBuf]) -> Result<Self> {
+        let dir = root.join(RECOVERY_DIR);
+        match inspect(root)? {
+            Leftover::None => {}
+            Leftover::InProgress(journal) => anyhow::bail!(
+                "Another env-coach process (pid {}, '{}') is saving this project. Try again in a moment.",
+                journal.pid, journal.command
+            ),
+            Leftover::Crashed(_) => anyhow::bail!(
+                "A previous env-coach run crashed while saving. Re-run any command to review and clean up {}.",
+                dir.display()
+            ),
+        }
+        std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let mut journal_files = Vec::new();
+        for (index, path) in files.iter().enumerate() {
+            let backup = if path.exists() {
+                let backup = dir.join(format!("{}.bak", index));
+                std::fs::copy(path, &backup)
+                    .with_context(|| format!("Failed to back up {} before saving", path.display()))?;
+                Some(backup)
+            } else {
+                None
+            };
+            journal_files.push(JournalFile { path: path.clone(), backup });
+        }
+
+        let journal = Journal {
+            pid: std::process::id(),
+            command: std::env::args().skip(1).collect::<Vec<_>>().join(" "),
+            started: Utc::now(),
+            files: journal_files,
+        };
+        std::fs::write(dir.join(JOURNAL_FILE), serde_json::to_string_pretty(&journal)?)
+            .context("Failed to write recovery journal")?;
+        Ok(Self { dir })
+    }
+
+    /// The save completed; forget the backups.
+    pub fn commit(self) -> Result<()> {
+        std::fs::remove_dir_all(&self.dir).with_context(|| format!("Failed to clean up {}", self.dir.display()))
+    }
+}
+
+/// Whether process `pid`, which started its work at `started`, is still running.
+pub(crate) fn process_alive(pid: u32, started: DateTime<Utc>) -> bool {
+    let proc_dir = Path::new("/proc");
+    if proc_dir.is_dir() {
+        return proc_dir.join(pid.to_string()).exists();
+    }
+    Utc::now() - started < Duration::minutes(STALE_AFTER_MINUTES)
+}
+
+/// Look for a journal left by an earlier run.
+pub fn inspect(root: &Path) -> Result<Leftover> {
+    let dir = root.join(RECOVERY_DIR);
+    if !dir.exists() {
+        return Ok(Leftover::None);
+    }
+    let journal_path = dir.join(JOURNAL_FILE);
+    // Without a complete journal the run died before it overwrote anything
+    let journal = std::fs::read_to_string(&journal_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| Journal { pid: 0, command: "unknown".to_string(), started: Utc::now(), files: vec![] });
+    if journal.pid != 0 && journal.pid != std::process::id() && process_alive(journal.pid, journal.started) {
+        return Ok(Leftover::InProgress(journal));
+    }
+    Ok(Leftover::Crashed(journal))
+}
+
+/// Files of the journal that no longer parse (as JSON, or TOML for `.toml` files) and were
+/// probably cut off mid-write.
+pub fn damaged_files(journal: &Journal) -> Vec<PathBuf> {
+    journal.files.iter()
+        .filter(|file| {
+            std::fs::read_to_string(&file.path)
+                .map(|content| FileFormat::of(&file.path).parse::<serde_json::Value>(&content).is_err())
+                .unwrap_or(false)
+        })
+        .map(|file| file.path.clone())
+        .collect()
+}
+
+/// Put every journaled file back the way it was before the interrupted save.
+pub fn restore(root: &Path, journal: &Journal) -> Result<()> {
+    for file in &journal.files {
+        match &file.backup {
+            Some(backup) => {
+                std::fs::copy(backup, &file.path)
+                    .with_context(|| format!("Failed to restore {} from {}", file.path.display(), backup.display()))?;
+            }
+            None if file.path.exists() => {
+                std::fs::remove_file(&file.path)
+                    .with_context(|| format!("Failed to remove {} created by the interrupted save", file.path.display()))?;
+            }
+            None => {}
+        }
+    }
+    discard(root)
+}
+
+/// Drop the recovery data and keep the files as they are.
+pub fn discard(root: &Path) -> Result<()> {
+    let dir = root.join(RECOVERY_DIR);
+    std::fs::remove_dir_all(&dir).with_context(|| format!("Failed to remove {}", dir.display()))
+}
+
+/// Execute-task runs that were interrupted and can be resumed.
+fn interrupted_task_runs(root: &Path) -> Vec<String> {
+    let mut ids: Vec<String> = std::fs::read_dir(root.join(".env-coach/progress"))
+        .map(|entries| {
+            entries.filter_map(|entry| entry.ok())
+                .filter_map(|entry| entry.path().file_stem().map(|s| s.to_string_lossy().into_owned()))
+                .collect()
+        })
+        .unwrap_or_default();
+    ids.sort();
+    ids
+}
+
+/// Explain anything a crashed run left behind and offer to clean it up.
+pub fn check_on_startup(ctx: &AppContext) -> Result<()> {
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+
+    for task_id in interrupted_task_runs(&root) {
+        println!("ℹ️  execute-task for {} was interrupted. Continue with 'env-coach execute-task {} --resume'", task_id, task_id);
+    }
+
+    let journal = match inspect(&root)? {
+        Leftover::None => return Ok(()),
+        Leftover::InProgress(journal) => {
+            println!("⚠️  Another env-coach process (pid {}, '{}') is saving this project right now", journal.pid, journal.command);
+            return Ok(());
+        }
+        Leftover::Crashed(journal) => journal,
+    };
+
+    println!("⚠️  A previous env-coach run did not finish saving");
+    println!("   Command: env-coach {} (pid {}, started {})", journal.command, journal.pid, journal.started.format("%Y-%m-%d %H:%M UTC"));
+    let damaged = damaged_files(&journal);
+    for file in &journal.files {
+        let state = if damaged.contains(&file.path) { "damaged" } else { "readable" };
+        println!("   - {} ({})", file.path.display(), state);
+    }
+    if ctx.dry_run {
+        println!("🔍 Dry run: recovery data in {} left untouched", RECOVERY_DIR);
+        return Ok(());
+    }
+
+    if !damaged.is_empty() {
+        if ctx.prompter.confirm("👉 Restore these files from the copies taken before that save?")? {
+            restore(&root, &journal)?;
+            println!("✅ Restored the project to its state before the interrupted save");
+        } else {
+            println!("❌ Left as is; commands reading the damaged files will fail until they are fixed");
+        }
+    } else if ctx.prompter.confirm("👉 All files are readable. Keep them and clear the recovery data?")? {
+        discard(&root)?;
+        println!("✅ Cleared stale recovery data");
+    } else if ctx.prompter.confirm("👉 Roll back to the state before the interrupted save instead?")? {
+        restore(&root, &journal)?;
+        println!("✅ Restored the project to its state before the interrupted save");
+    } else {
+        println!("❌ Left as is; saving stays blocked until {} is cleared", RECOVERY_DIR);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_committed_transaction_leaves_nothing_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("project.json");
+        std::fs::write(&file, "{}").unwrap();
+
+        let transaction = Transaction::begin(dir.path(), std::slice::from_ref(&file)).unwrap();
+        assert!(Transaction::begin(dir.path(), std::slice::from_ref(&file)).is_err());
+        transaction.commit().unwrap();
+        assert!(matches!(inspect(dir.path()).unwrap(), Leftover::None));
+    }
+
+    #[test]
+    fn test_crashed_save_is_detected_and_restored() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("project.json");
+        let created = dir.path().join("workspace.json");
+        std::fs::write(&existing, r#"{"ok": true}"#).unwrap();
+
+        // Simulate a run that died after writing half of project.json
+        let transaction = Transaction::begin(dir.path(), &[existing.clone(), created.clone()]).unwrap();
+        std::mem::forget(transaction);
+        std::fs::write(&existing, r#"{"ok": tr"#).unwrap();
+        std::fs::write(&created, "{}").unwrap();
+
+        // Our own pid never counts as a concurrent save
+        let Leftover::Crashed(journal) = inspect(dir.path()).unwrap() else { panic!("expected a crashed save") };
+        assert_eq!(damaged_files(&journal), vec![existing.clone()]);
+
+        restore(dir.path(), &journal).unwrap();
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), r#"{"ok": true}"#);
+        assert!(!created.exists());
+        assert!(matches!(inspect(dir.path()).unwrap(), Leftover::None));
+    }
+}
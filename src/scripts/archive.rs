@@ -0,0 +1,187 @@
+// src/scripts/archive.rs
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::config::{ArchivedItem, BacklogItem, Project, SprintStatus};
+use crate::context::AppContext;
+use crate::hints;
+use crate::scripts::item_ids;
+use crate::scripts::sprint::remove_item_from_sprint;
+
+/// Take an item out of the backlog while keeping sprints and dependencies consistent.
+/// Open sprints lose the item and its points. Completed sprints keep their reference when
+/// `keep_history` is set (archiving), otherwise the reference is dropped but the recorded
+/// points are left alone so past velocity does not change. Children move up to the item's
+/// own parent (a story's tasks to its epic) or lose their parent. Returns the item and notes
+/// describing every side effect.
+fn take_item(project: &mut Project, item_id: &str, keep_history: bool) -> Result<(BacklogItem, Vec<String>)> {
+    let item = project.backlog.iter()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| anyhow::anyhow!("Backlog item '{}' not found", item_id))?
+        .clone();
+    let mut notes = Vec::new();
+
+    let open_sprints: Vec<String> = project.sprints.iter()
+        .filter(|s| !matches!(s.status, SprintStatus::Completed | SprintStatus::Complete))
+        .filter(|s| s.stories.iter().chain(s.tasks.iter()).any(|id| id == item_id))
+        .map(|s| s.id.clone())
+        .collect();
+    for sprint_id in open_sprints {
+        remove_item_from_sprint(project, &sprint_id, item_id)?;
+        notes.push(format!("removed from sprint {} ({} pts)", sprint_id, item.effort));
+    }
+    if !keep_history {
+        for sprint in project.sprints.iter_mut().filter(|s| s.stories.iter().chain(s.tasks.iter()).any(|id| id == item_id)) {
+            sprint.stories.retain(|id| id != item_id);
+            sprint.tasks.retain(|id| id != item_id);
+            notes.push(format!("dropped from completed sprint {} (its points are unchanged)", sprint.id));
+        }
+    }
+
+    for other in project.backlog.iter_mut().filter(|other| other.dependencies.iter().any(|d| d == item_id)) {
+        other.dependencies.retain(|d| d != item_id);
+        notes.push(format!("no longer a dependency of {}", other.id));
+    }
+    for child in project.backlog.iter_mut().filter(|other| other.parent.as_deref() == Some(item_id)) {
+        child.parent = item.parent.clone();
+        match &child.parent {
+            Some(parent) => notes.push(format!("{} moved up to {}", child.id, parent)),
+            None => notes.push(format!("{} no longer has a parent", child.id)),
+        }
+    }
+
+    let position = project.backlog.iter().position(|i| i.id == item_id).expect("item existence checked above");
+    let item = project.backlog.remove(position);
+    Ok((item, notes))
+}
+
+/// Move one item to the archive, returning its title and the side effects.
+pub(crate) fn archive_item(project: &mut Project, item_id: &str) -> Result<(String, Vec<String>)> {
+    let (item, notes) = take_item(project, item_id, true)?;
+    let title = item.title.clone();
+    project.archive.push(ArchivedItem { archived: Utc::now(), item });
+    Ok((title, notes))
+}
+
+pub(crate) fn print_notes(notes: &[String]) {
+    for note in notes {
+        println!("   - {}", note);
+    }
+}
+
+/// Delete an item for good, after confirmation.
+pub fn remove_item(ctx: &AppContext, item_id: String) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let item = project.backlog.iter()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| anyhow::anyhow!("Backlog item '{}' not found", item_id))?;
+
+    println!("🗑️  {} - {} [{}pts, {:?}]", item.id, item.title, item.effort, item.status);
+    if !ctx.prompter.confirm(&format!("👉 Permanently remove {}? (use archive-item to keep history)", item_id))? {
+        println!("❌ Removal cancelled");
+        return Ok(());
+    }
+
+    let (item, notes) = take_item(&mut project, &item_id, false)?;
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    println!("✅ Removed {}: {}", item.id, item.title);
+    print_notes(&notes);
+    hints::print_next_steps(Some(&project), &[("env-coach list-backlog", "Review the backlog")]);
+    Ok(())
+}
+
+/// Move items to the archive section of project.json; nothing is saved unless every
+/// item can be archived.
+pub fn archive_items(ctx: &AppContext, item_args: Vec<String>) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let item_ids = item_ids::expand(&item_args, &project)?;
+
+    let mut archived = vec![];
+    for item_id in &item_ids {
+        let (title, notes) = archive_item(&mut project, item_id).context("No items were archived")?;
+        archived.push((item_id.clone(), title, notes));
+    }
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    for (item_id, title, notes) in &archived {
+        println!("📦 Archived {}: {}", item_id, title);
+        print_notes(notes);
+    }
+    hints::print_next_steps(Some(&project), &[("env-coach list-backlog --archived", "See archived items")]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ItemType, Sprint, Status};
+
+    fn sprint(id: &str, status: SprintStatus, stories: &[&str]) -> Sprint {
+        Sprint {
+            id: id.to_string(),
+            goal: "Goal".to_string(),
+            start_date: Utc::now(),
+            end_date: Utc::now(),
+            status,
+            total_points: 3 * stories.len() as u32,
+            completed_points: 0,
+            tasks: stories.iter().map(|s| s.to_string()).collect(),
+            stories: stories.iter().map(|s| s.to_string()).collect(),
+            planned_velocity: 0,
+            actual_velocity: 0,
+            retrospective: None,
+        }
+    }
+
+    fn project() -> Project {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog.push(BacklogItem::test("US-001").with_status(Status::Done).with_sprint("S-001"));
+        project.backlog.push(BacklogItem::test("US-002").with_sprint("S-002"));
+        project.backlog.push(BacklogItem::test("US-003").with_dependencies(&["US-001", "US-002"]));
+        project.sprints.push(sprint("S-001", SprintStatus::Completed, &["US-001"]));
+        project.sprints.push(sprint("S-002", SprintStatus::Planning, &["US-002"]));
+        project
+    }
+
+    #[test]
+    fn test_take_item_updates_open_sprint_and_dependencies() {
+        let mut project = project();
+        let (item, notes) = take_item(&mut project, "US-002", false).unwrap();
+        assert_eq!(item.id, "US-002");
+        assert_eq!(notes.len(), 2);
+        assert_eq!(project.sprints[1].stories, Vec::<String>::new());
+        assert_eq!(project.sprints[1].total_points, 0);
+        assert_eq!(project.backlog.iter().find(|i| i.id == "US-003").unwrap().dependencies, vec!["US-001"]);
+        assert!(take_item(&mut project, "US-404", false).is_err());
+    }
+
+    #[test]
+    fn test_completed_sprint_keeps_history_only_when_archiving() {
+        let mut archived = project();
+        take_item(&mut archived, "US-001", true).unwrap();
+        assert_eq!(archived.sprints[0].stories, vec!["US-001"]);
+
+        let mut removed = project();
+        take_item(&mut removed, "US-001", false).unwrap();
+        assert!(removed.sprints[0].stories.is_empty());
+        assert_eq!(removed.sprints[0].total_points, 3);
+    }
+
+    #[test]
+    fn test_children_move_up_or_lose_their_parent() {
+        let mut project = project();
+        project.backlog.push(BacklogItem::test("EPIC-001").with_type(ItemType::Epic).with_effort(0));
+        project.backlog.iter_mut().find(|i| i.id == "US-003").unwrap().parent = Some("EPIC-001".to_string());
+        project.backlog.push(BacklogItem::test("TASK-001").with_type(ItemType::Task).with_parent("US-003"));
+
+        take_item(&mut project, "US-003", true).unwrap();
+        let task = project.backlog.iter().find(|i| i.id == "TASK-001").unwrap();
+        assert_eq!(task.parent.as_deref(), Some("EPIC-001"));
+
+        let (_, notes) = take_item(&mut project, "EPIC-001", true).unwrap();
+        assert!(project.backlog.iter().all(|i| i.parent.is_none()));
+        assert_eq!(notes, vec!["TASK-001 no longer has a parent"]);
+        crate::scripts::edit_item::validate_item(&project, project.backlog.iter().find(|i| i.id == "TASK-001").unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,217 @@
+// src/scripts/ask.rs
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::auto_update::duplicates::cosine_similarity;
+use crate::config::{Project, Status};
+use crate::context::AppContext;
+use crate::ollama;
+use crate::prompts;
+use crate::templates::Templates;
+
+const MAX_SOURCE_CHARS: usize = 2000;
+/// How many keyword matches are re-ranked with embeddings.
+const RERANK_CANDIDATES: usize = 20;
+const ANSWER_SOURCES: usize = 5;
+
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "are", "was", "were", "did", "does", "why", "what", "how", "who", "when",
+    "which", "with", "that", "this", "from", "have", "has", "had", "our", "we", "you", "use", "used",
+];
+
+/// A piece of project history that can be cited in an answer.
+#[derive(Debug, Clone)]
+struct Source {
+    /// Citation label, e.g. `US-003` or `docs/adr/0001-storage.md`
+    label: String,
+    text: String,
+}
+
+fn truncate(text: &str) -> String {
+    text.chars().take(MAX_SOURCE_CHARS).collect()
+}
+
+/// Markdown/text files directly inside `dir`, sorted by name. Missing directories yield nothing.
+fn read_text_files(dir: &Path) -> Vec<(String, String)> {
+    let Ok(entries) = std::fs::read_dir(dir) else { return vec![] };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| matches!(path.extension().and_then(|e| e.to_str()), Some("md" | "txt" | "log" | "jsonl")))
+        .collect();
+    paths.sort();
+    paths.into_iter()
+        .filter_map(|path| std::fs::read_to_string(&path).ok().map(|text| (path.display().to_string(), text)))
+        .collect()
+}
+
+/// ADRs, session logs, retrospectives and completed stories, relative to `root`.
+fn collect_sources(project: &Project, root: &Path) -> Vec<Source> {
+    let mut sources = Vec::new();
+
+    for (path, text) in read_text_files(&root.join("docs/adr")).into_iter().chain(read_text_files(&root.join(".env-coach/logs"))) {
+        let label = Path::new(&path).strip_prefix(root).map(|p| p.display().to_string()).unwrap_or(path);
+        sources.push(Source { label, text: truncate(&text) });
+    }
+
+    for sprint in &project.sprints {
+        if let Some(retro) = &sprint.retrospective {
+            let text = format!(
+                "Retrospective for {} ({}): {}\nWent well: {}\nTo improve: {}\nActions: {}\nBlockers: {}",
+                sprint.id, sprint.goal, retro.summary,
+                retro.went_well.join("; "), retro.to_improve.join("; "), retro.action_items.join("; "), retro.blockers
+            );
+            sources.push(Source { label: format!("{} retrospective", sprint.id), text: truncate(&text) });
+        }
+    }
+
+    for item in project.backlog.iter().filter(|item| matches!(item.status, Status::Done)) {
+        let text = format!("{}: {}\n{}\nAcceptance criteria: {}", item.id, item.title, item.story, item.acceptance_criteria.join("; "));
+        sources.push(Source { label: item.id.clone(), text: truncate(&text) });
+    }
+
+    sources
+}
+
+fn keywords(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.len() >= 3 && !STOPWORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Fraction of the question's keywords that appear in `text` (0.0 - 1.0).
+fn keyword_score(question_terms: &HashSet<String>, text: &str) -> f32 {
+    if question_terms.is_empty() {
+        return 0.0;
+    }
+    let text_terms = keywords(text);
+    question_terms.iter().filter(|term| text_terms.contains(*term)).count() as f32 / question_terms.len() as f32
+}
+
+/// Sources ordered by keyword score, best first.
+fn rank_by_keywords(question: &str, sources: Vec<Source>) -> Vec<(Source, f32)> {
+    let terms = keywords(question);
+    let mut ranked: Vec<(Source, f32)> = sources.into_iter()
+        .map(|source| {
+            let score = keyword_score(&terms, &format!("{} {}", source.label, source.text));
+            (source, score)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+}
+
+/// Blend keyword scores with embedding similarity. Falls back to keywords alone when the
+/// model cannot produce embeddings.
+async fn rerank_with_embeddings(project: &Project, question: &str, mut candidates: Vec<(Source, f32)>) -> Vec<(Source, f32)> {
+    let question_embedding = match ollama::generate_embedding(project.llm(), question).await {
+        Ok(embedding) => embedding,
+        Err(e) => {
+            println!("⚠️  Embedding search unavailable ({}); using keyword search only.", e);
+            return candidates;
+        }
+    };
+    for (source, score) in candidates.iter_mut() {
+        if let Ok(embedding) = ollama::generate_embedding(project.llm(), &source.text).await {
+            *score = 0.5 * *score + 0.5 * cosine_similarity(&question_embedding, &embedding);
+        }
+    }
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+    candidates
+}
+
+fn format_sources_for_prompt(sources: &[Source]) -> String {
+    sources.iter()
+        .enumerate()
+        .map(|(i, source)| format!("[{}] ({})\n{}", i + 1, source.label, source.text))
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+pub async fn run(ctx: &AppContext, question: String) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    println!("🔎 Searching project history for: {}", question);
+    let sources = collect_sources(&project, Path::new("."));
+    if sources.is_empty() {
+        println!("📭 Nothing to search yet: no ADRs, logs, retrospectives or completed stories.");
+        return Ok(());
+    }
+
+    let mut candidates = rank_by_keywords(&question, sources);
+    candidates.truncate(RERANK_CANDIDATES);
+    let ranked = rerank_with_embeddings(&project, &question, candidates).await;
+    let selected: Vec<Source> = ranked.into_iter().take(ANSWER_SOURCES).map(|(source, _)| source).collect();
+
+    let prompt_template_path = Path::new(".env-coach/prompts/project_qa.md");
+    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
+        Ok(template) => template,
+        Err(_) => Templates::default_project_qa_prompt_content(),
+    };
+    let prompt = prompts::fill_project_context(&prompt_template, &project, None)
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{question}}", &question)
+        .replace("{{sources}}", &format_sources_for_prompt(&selected));
+
+    println!("🤖 Asking LLM...");
+    let answer = ollama::send_generation_prompt(project.llm(), &prompt)
+        .await
+        .context("LLM call for project question failed")?;
+
+    println!();
+    println!("{}", answer.trim());
+    println!();
+    println!("📚 Sources:");
+    for (i, source) in selected.iter().enumerate() {
+        println!("   [{}] {}", i + 1, source.label);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BacklogItem, Priority};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_keyword_score_ignores_stopwords() {
+        let terms = keywords("Why did we choose SQLite?");
+        assert_eq!(terms, HashSet::from(["choose".to_string(), "sqlite".to_string()]));
+        assert_eq!(keyword_score(&terms, "We store data in sqlite"), 0.5);
+        assert_eq!(keyword_score(&HashSet::new(), "anything"), 0.0);
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[1.0, 0.0]) - 1.0).abs() < f32::EPSILON);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 2.0]), 0.0);
+    }
+
+    #[test]
+    fn test_collect_and_rank_sources() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("docs/adr")).unwrap();
+        std::fs::write(dir.path().join("docs/adr/0001-storage.md"), "# Use SQLite\nWe chose sqlite because it needs no server.").unwrap();
+
+        let mut project = Project::new("Ask".to_string(), "".to_string(), None);
+        project.backlog.push(BacklogItem::test("US-001")
+            .with_title("Login page")
+            .with_story("As a user I want to log in")
+            .with_priority(Priority::High)
+            .with_status(Status::Done));
+
+        let sources = collect_sources(&project, dir.path());
+        let labels: Vec<&str> = sources.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["docs/adr/0001-storage.md", "US-001"]);
+
+        let ranked = rank_by_keywords("why did we choose sqlite?", sources);
+        assert_eq!(ranked[0].0.label, "docs/adr/0001-storage.md");
+        assert_eq!(ranked[1].1, 0.0);
+    }
+}
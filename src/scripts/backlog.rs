@@ -1,31 +1,88 @@
 // src/scripts/backlog.rs
-use crate::config::{Project, Priority, Status, ItemType};
+use serde::Serialize;
 
-pub fn run() -> anyhow::Result<()> {
-    let project = Project::load()?;
+use crate::config::{ArchivedItem, Priority, Project, Status, ItemType};
+use crate::context::AppContext;
+use crate::links::Linker;
+use crate::output;
+use crate::scripts::epics;
+use crate::scripts::filter::BacklogFilter;
+use crate::stats::WorkStats;
+
+/// `--format json|yaml` output of the listing commands.
+#[derive(Serialize)]
+pub(crate) struct Listing<'a, T> {
+    /// Items before filtering
+    pub total: usize,
+    pub items: Vec<&'a T>,
+}
+
+pub fn run(ctx: &AppContext, archived: bool, filter: &BacklogFilter) -> anyhow::Result<()> {
+    // A filtered listing only needs the matching items, which an indexed storage finds directly
+    let queried = if archived || filter.is_empty() { None } else { ctx.load_matching(filter, &[])? };
+    let (project, total) = match queried {
+        Some(found) => found,
+        None => {
+            let project = ctx.load_project()?;
+            let total = project.backlog.len();
+            (project, total)
+        }
+    };
+
+    if ctx.format.is_structured() {
+        return if archived {
+            ctx.print_structured(&Listing { total: project.archive.len(), items: archived_matches(&project, filter) })
+        } else {
+            ctx.print_structured(&Listing { total, items: filter.apply(&project.backlog) })
+        };
+    }
+    let linker = Linker::new(&project, &ctx.global_config);
+    if archived {
+        return list_archive(&project, filter, &linker);
+    }
     
-    if project.backlog.is_empty() {
+    if total == 0 {
         println!("📋 Backlog is empty");
-        println!();
-        println!("🎯 Get started:");
-        println!("   env-coach add-requirement \"I want to build a web API\"");
-        println!("   env-coach add-story --title \"Story\" --description \"Description\"");
+        if output::show_hints() {
+            println!();
+            println!("🎯 Get started:");
+            println!("   env-coach add-requirement \"I want to build a web API\"");
+            println!("   env-coach add-story --title \"Story\" --description \"Description\"");
+        }
         return Ok(());
     }
     
-    println!("📋 Project Backlog ({} items)", project.backlog.len());
+    if filter.is_empty() {
+        println!("📋 Project Backlog ({} items)", project.backlog.len());
+    } else {
+        println!("📋 Project Backlog ({} of {} items match)", project.backlog.iter().filter(|item| filter.matches(item)).count(), total);
+    }
     println!();
     
-    // Group items by status
-    let todo_items: Vec<_> = project.backlog.iter().filter(|item| matches!(item.status, Status::Todo)).collect();
-    let in_progress_items: Vec<_> = project.backlog.iter().filter(|item| matches!(item.status, Status::InProgress)).collect();
-    let review_items: Vec<_> = project.backlog.iter().filter(|item| matches!(item.status, Status::Review)).collect();
-    let done_items: Vec<_> = project.backlog.iter().filter(|item| matches!(item.status, Status::Done)).collect();
+    if filter.is_empty() && epics::epics(&project).next().is_some() {
+        println!("🎯 Epics:");
+        epics::print_epic_rollup(&project);
+        println!();
+    }
+
+    // Group items by status; epics are shown with their rollup above unless asked for by type
+    let show_epics = filter.item_type == Some(ItemType::Epic);
+    let work_items: Vec<_> = filter.apply(&project.backlog).into_iter()
+        .filter(|item| show_epics || !matches!(item.item_type, ItemType::Epic))
+        .collect();
+    if work_items.is_empty() && !filter.is_empty() {
+        println!("🔍 No items match the given filters");
+        return Ok(());
+    }
+    let todo_items: Vec<_> = work_items.iter().filter(|item| matches!(item.status, Status::Todo)).collect();
+    let in_progress_items: Vec<_> = work_items.iter().filter(|item| matches!(item.status, Status::InProgress)).collect();
+    let review_items: Vec<_> = work_items.iter().filter(|item| matches!(item.status, Status::Review)).collect();
+    let done_items: Vec<_> = work_items.iter().filter(|item| matches!(item.status, Status::Done)).collect();
 
     if !in_progress_items.is_empty() {
         println!("🚧 In Progress ({}):", in_progress_items.len());
         for item in &in_progress_items {
-            print_backlog_item(item);
+            print_backlog_item(item, &linker);
         }
         println!();
     }
@@ -33,7 +90,7 @@ pub fn run() -> anyhow::Result<()> {
     if !review_items.is_empty() {
         println!("👀 In Review ({}):", review_items.len());
         for item in &review_items {
-            print_backlog_item(item);
+            print_backlog_item(item, &linker);
         }
         println!();
     }
@@ -41,7 +98,7 @@ pub fn run() -> anyhow::Result<()> {
     if !todo_items.is_empty() {
         println!("⏳ To Do ({}):", todo_items.len());
         for item in &todo_items {
-            print_backlog_item(item);
+            print_backlog_item(item, &linker);
         }
         println!();
     }
@@ -49,25 +106,23 @@ pub fn run() -> anyhow::Result<()> {
     if !done_items.is_empty() {
         println!("✅ Done ({}):", done_items.len());
         for item in &done_items {
-            print_backlog_item(item);
+            print_backlog_item(item, &linker);
         }
         println!();
     }
 
     // Show summary statistics
-    let total_effort: u32 = project.backlog.iter().map(|item| item.effort).sum();
-    let completed_effort: u32 = done_items.iter().map(|item| item.effort).sum();
+    let summary = WorkStats::of(work_items.iter().copied());
     
     println!("📊 Summary:");
-    println!("   Total effort: {} points", total_effort);
-    println!("   Completed: {} points", completed_effort);
-    if total_effort > 0 {
-        let completion_percent = (completed_effort * 100) / total_effort;
+    println!("   Total effort: {} points", summary.points.total);
+    println!("   Completed: {} points", summary.points.done);
+    if let Some(completion_percent) = summary.completion_percent {
         println!("   Progress: {}%", completion_percent);
     }
     
     // Show next action
-    if !todo_items.is_empty() {
+    if !todo_items.is_empty() && output::show_hints() {
         println!();
         println!("🎯 Next action:");
         if let Some(next_item) = todo_items.first() {
@@ -78,7 +133,8 @@ pub fn run() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn print_backlog_item(item: &crate::config::BacklogItem) {
+/// Priority and type emoji used when listing an item.
+pub(crate) fn item_badges(item: &crate::config::BacklogItem) -> (&'static str, &'static str) {
     let priority_emoji = match item.priority {
         Priority::Critical => "🔴",
         Priority::High => "🟠",
@@ -92,13 +148,47 @@ fn print_backlog_item(item: &crate::config::BacklogItem) {
         ItemType::Epic => "🎯",   // Changed from Feature to Epic
         ItemType::Task => "📋",
     };
-    
+    (priority_emoji, type_emoji)
+}
+
+fn print_backlog_item(item: &crate::config::BacklogItem, linker: &Linker) {
+    let (priority_emoji, type_emoji) = item_badges(item);
     println!("  {} {} {} - {} [{}pts]", priority_emoji, type_emoji, item.id, item.title, item.effort);
     println!("     {}", item.story);
     if let Some(sprint) = &item.sprint {
         println!("     🏃 Sprint: {}", sprint);
     }
+    if let Some(parent) = &item.parent {
+        match item.item_type {
+            ItemType::Task => println!("     🧩 Part of: {}", parent),
+            _ => println!("     🎯 Epic: {}", parent),
+        }
+    }
     if !item.dependencies.is_empty() {
         println!("     🔗 Dependencies: {}", item.dependencies.join(", "));
     }
-}
\ No newline at end of file
+    if let Some(link) = linker.describe(item) {
+        println!("     🌐 {}", link);
+    }
+}
+
+fn archived_matches<'a>(project: &'a Project, filter: &BacklogFilter) -> Vec<&'a ArchivedItem> {
+    let mut shown: Vec<_> = project.archive.iter().filter(|entry| filter.matches(&entry.item)).collect();
+    filter.sort(&mut shown, |entry| &entry.item);
+    shown
+}
+
+fn list_archive(project: &Project, filter: &BacklogFilter, linker: &Linker) -> anyhow::Result<()> {
+    if project.archive.is_empty() {
+        println!("📦 Archive is empty");
+        return Ok(());
+    }
+    let shown = archived_matches(project, filter);
+    println!("📦 Archived Items ({} items)", shown.len());
+    println!();
+    for entry in shown {
+        print_backlog_item(&entry.item, linker);
+        println!("     📦 Archived: {} ({:?})", entry.archived.format("%Y-%m-%d"), entry.item.status);
+    }
+    Ok(())
+}
@@ -0,0 +1,304 @@
+// src/scripts/backup.rs
+//! `backup [--keep N]`: zip the project file and `.env-coach` (prompts, sessions, logs,
+//! split or SQLite storage...) into `.env-coach/backups/`, and `restore <archive>` to put
+//! such a snapshot back. Restoring first backs up the current state, so it can be undone
+//! the same way.
+
+use anyhow::{Context, Result};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+use crate::auto_update::backup::BACKUP_DIR;
+use crate::context::AppContext;
+use crate::hints;
+
+pub const DEFAULT_KEEP: usize = 10;
+const ARCHIVE_PREFIX: &str = "project-";
+const ARCHIVE_EXTENSION: &str = "zip";
+const PROJECT_FILES: [&str; 2] = ["project.json", "project.toml"];
+/// Entries of `.env-coach` left out: the backups themselves, caches that are rebuilt on
+/// demand, crash-recovery data and the project lock.
+const SKIPPED: [&str; 4] = ["backups", "cache", "recovery", ".lock"];
+
+/// Whether `path` (relative to the project root) is part of a backup.
+fn in_scope(path: &Path) -> bool {
+    let mut components = path.components().map(|c| c.as_os_str().to_string_lossy());
+    match (components.next().as_deref(), components.next()) {
+        (Some(name), None) => PROJECT_FILES.contains(&name),
+        (Some(".env-coach"), Some(entry)) => !SKIPPED.contains(&entry.as_ref()),
+        _ => false,
+    }
+}
+
+/// Files to back up, relative to `root`, in a stable order.
+fn backed_up_files(root: &Path) -> Result<Vec<PathBuf>> {
+    fn walk(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        let entries = std::fs::read_dir(root.join(dir)).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries {
+            let entry = entry?;
+            let path = dir.join(entry.file_name());
+            if !in_scope(&path) {
+                continue;
+            }
+            if entry.file_type()?.is_dir() {
+                walk(root, &path, files)?;
+            } else {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files: Vec<PathBuf> = PROJECT_FILES.iter().map(PathBuf::from).filter(|file| root.join(file).is_file()).collect();
+    if root.join(".env-coach").is_dir() {
+        walk(root, Path::new(".env-coach"), &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// The project's backup archives, oldest first.
+pub fn archives(root: &Path) -> Result<Vec<PathBuf>> {
+    let dir = root.join(BACKUP_DIR);
+    let mut archives: Vec<PathBuf> = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok().map(|e| e.path())).collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    archives.retain(|path| {
+        path.is_file()
+            && path.extension().is_some_and(|ext| ext == ARCHIVE_EXTENSION)
+            && path.file_name().is_some_and(|name| name.to_string_lossy().starts_with(ARCHIVE_PREFIX))
+    });
+    // Names carry the time they were taken
+    archives.sort();
+    Ok(archives)
+}
+
+/// Zip the project in `root` into a new archive. Returns its path and how many files it holds.
+fn create(root: &Path) -> Result<(PathBuf, usize)> {
+    let files = backed_up_files(root)?;
+    if files.is_empty() {
+        anyhow::bail!("Nothing to back up: no project.json or project.toml here. Run 'env-coach init' first.");
+    }
+    let dir = root.join(BACKUP_DIR);
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S-%3f");
+    let path = dir.join(format!("{}{}.{}", ARCHIVE_PREFIX, stamp, ARCHIVE_EXTENSION));
+
+    let file = std::fs::File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for relative in &files {
+        // Archive names use `/` whatever the platform
+        let name = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+        let content = std::fs::read(root.join(relative)).with_context(|| format!("Failed to read {}", relative.display()))?;
+        zip.start_file(name, options)?;
+        zip.write_all(&content)?;
+    }
+    zip.finish().with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok((path, files.len()))
+}
+
+/// Remove the oldest archives beyond `keep`, returning the removed ones.
+fn rotate(root: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+    let archives = archives(root)?;
+    let excess = archives.len().saturating_sub(keep);
+    let removed: Vec<PathBuf> = archives.into_iter().take(excess).collect();
+    for path in &removed {
+        std::fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(removed)
+}
+
+/// The files in `archive` with their content, checked to belong to a project backup.
+fn read_archive(archive: &Path) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let file = std::fs::File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    let mut zip = zip::ZipArchive::new(file).with_context(|| format!("{} is not a zip archive", archive.display()))?;
+    let mut files = Vec::new();
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let path = entry.enclosed_name()
+            .filter(|path| in_scope(path))
+            .ok_or_else(|| anyhow::anyhow!("{} is not an env-coach backup: it contains '{}'", archive.display(), entry.name()))?;
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        files.push((path, content));
+    }
+    if !files.iter().any(|(path, _)| PROJECT_FILES.iter().any(|name| path == Path::new(name))) {
+        anyhow::bail!("{} is not an env-coach backup: it has no project.json or project.toml", archive.display());
+    }
+    Ok(files)
+}
+
+/// Replace the project in `root` with the files of a backup. Files in scope that the backup
+/// does not have (items added since, a project file in the other format) are removed.
+fn restore_files(root: &Path, files: &[(PathBuf, Vec<u8>)]) -> Result<()> {
+    for current in backed_up_files(root)? {
+        let path = root.join(&current);
+        std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    for (relative, content) in files {
+        let path = root.join(relative);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        std::fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Whether a `restore` argument names a backup archive rather than a file.
+pub fn is_archive_name(argument: &str) -> bool {
+    Path::new(argument).extension().is_some_and(|ext| ext == ARCHIVE_EXTENSION)
+}
+
+/// `restore` argument as a backup archive: a `.zip` path, or the name of one in
+/// `.env-coach/backups/`. `None` means it names a file to restore from suggestion backups.
+pub fn find_archive(root: &Path, argument: &str) -> Option<PathBuf> {
+    if !is_archive_name(argument) {
+        return None;
+    }
+    let path = Path::new(argument);
+    [root.join(path), root.join(BACKUP_DIR).join(path)].into_iter().find(|candidate| candidate.is_file())
+}
+
+fn display(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).display().to_string()
+}
+
+pub fn run(ctx: &AppContext, keep: usize, list: bool) -> Result<()> {
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    if list {
+        let archives = archives(&root)?;
+        if archives.is_empty() {
+            println!("ℹ️ No backups in {} yet", BACKUP_DIR);
+            return Ok(());
+        }
+        println!("🗂️  Backups (oldest first):");
+        for path in &archives {
+            let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            println!("   - {} ({} KB)", display(&root, path), size.div_ceil(1024));
+        }
+        return Ok(());
+    }
+    if keep == 0 {
+        anyhow::bail!("--keep must be at least 1");
+    }
+    if ctx.dry_run {
+        println!("🔍 Dry run: would back up {} file(s) to {}", backed_up_files(&root)?.len(), BACKUP_DIR);
+        return Ok(());
+    }
+
+    let (archive, count) = create(&root)?;
+    println!("✅ Backed up {} file(s) to {}", count, display(&root, &archive));
+    for removed in rotate(&root, keep)? {
+        println!("🗑️  Removed old backup {} (keeping {})", display(&root, &removed), keep);
+    }
+    let restore = format!("env-coach restore {}", archive.file_name().unwrap_or_default().to_string_lossy());
+    hints::print_next_steps(None, &[
+        (restore.as_str(), "Go back to this state"),
+    ]);
+    Ok(())
+}
+
+pub fn restore(ctx: &AppContext, archive: &Path) -> Result<()> {
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let files = read_archive(archive)?;
+    println!("📦 {} holds {} file(s):", display(&root, archive), files.len());
+    for (path, _) in files.iter().filter(|(path, _)| path.components().count() <= 2).take(10) {
+        println!("   - {}", path.display());
+    }
+    if !ctx.prompter.confirm("👉 Replace the project with this backup? The current state is backed up first")? {
+        println!("❌ Restore cancelled");
+        return Ok(());
+    }
+    if ctx.dry_run {
+        println!("🔍 Dry run: project not restored");
+        return Ok(());
+    }
+
+    // Not rotated, so the archive being restored stays even when it is the oldest
+    let (safety, _) = create(&root)?;
+    restore_files(&root, &files)?;
+    println!("✅ Restored {} file(s) from {}", files.len(), display(&root, archive));
+    println!("💾 The state before the restore is in {}", display(&root, &safety));
+    hints::print_next_steps(None, &[
+        ("env-coach validate", "Check the restored project"),
+        ("env-coach status", "See where the project stands"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(dir: &Path) {
+        std::fs::create_dir_all(dir.join(".env-coach/prompts")).unwrap();
+        std::fs::create_dir_all(dir.join(".env-coach/cache")).unwrap();
+        std::fs::write(dir.join("project.json"), "{\"v\": 1}").unwrap();
+        std::fs::write(dir.join(".env-coach/prompts/task_assistant.md"), "v1").unwrap();
+        std::fs::write(dir.join(".env-coach/cache/index.json"), "{}").unwrap();
+        std::fs::write(dir.join(".env-coach/.lock"), "{}").unwrap();
+        std::fs::write(dir.join("README.md"), "not project state").unwrap();
+    }
+
+    #[test]
+    fn test_backup_round_trip_replaces_project_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        project(root);
+        let (archive, count) = create(root).unwrap();
+        assert_eq!(count, 2);
+
+        // Experiment: edit, add a file, switch formats
+        std::fs::write(root.join(".env-coach/prompts/task_assistant.md"), "v2").unwrap();
+        std::fs::write(root.join(".env-coach/history.jsonl"), "{}").unwrap();
+        std::fs::rename(root.join("project.json"), root.join("project.toml")).unwrap();
+
+        let files = read_archive(&archive).unwrap();
+        restore_files(root, &files).unwrap();
+        assert_eq!(std::fs::read_to_string(root.join(".env-coach/prompts/task_assistant.md")).unwrap(), "v1");
+        assert!(root.join("project.json").is_file());
+        assert!(!root.join("project.toml").exists());
+        assert!(!root.join(".env-coach/history.jsonl").exists());
+        // Out of scope: left alone
+        assert!(root.join(".env-coach/.lock").is_file());
+        assert!(root.join(".env-coach/cache/index.json").is_file());
+        assert!(root.join("README.md").is_file());
+        assert_eq!(find_archive(root, &archive.file_name().unwrap().to_string_lossy()), Some(archive));
+        assert_eq!(find_archive(root, "src/main.rs"), None);
+    }
+
+    #[test]
+    fn test_rotation_keeps_the_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        project(dir.path());
+        let created: Vec<PathBuf> = (0..3).map(|_| {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            create(dir.path()).unwrap().0
+        }).collect();
+        // Per-file suggestion backups share the directory and are not rotated
+        std::fs::write(dir.path().join(BACKUP_DIR).join("main.rs.20260101-000000"), "").unwrap();
+
+        assert_eq!(rotate(dir.path(), 2).unwrap(), vec![created[0].clone()]);
+        assert_eq!(archives(dir.path()).unwrap(), created[1..].to_vec());
+        assert!(dir.path().join(BACKUP_DIR).join("main.rs.20260101-000000").exists());
+    }
+
+    #[test]
+    fn test_foreign_archives_are_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("other.zip");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        zip.start_file("../escape.txt", SimpleFileOptions::default()).unwrap();
+        zip.write_all(b"x").unwrap();
+        zip.finish().unwrap();
+        assert!(read_archive(&path).unwrap_err().to_string().contains("not an env-coach backup"));
+    }
+}
@@ -0,0 +1,181 @@
+// src/scripts/bench.rs
+use anyhow::{Context, Result};
+use std::time::{Duration, Instant};
+
+use crate::auto_update::llm_parsers::{parse_assist_task_response, LlmUserStoryResponse};
+use crate::config::FinalLlmConfig;
+use crate::context::AppContext;
+use crate::output;
+use crate::ollama;
+use crate::templates::Templates;
+
+/// One standardized prompt plus a check that the response is usable by env-coach.
+struct BenchCase {
+    name: &'static str,
+    prompt: String,
+    check: fn(&str) -> bool,
+}
+
+#[derive(Debug)]
+struct BenchResult {
+    model: String,
+    latency: Duration,
+    /// None when the request itself failed
+    parsed: Option<bool>,
+}
+
+fn check_requirement_analysis(response: &str) -> bool {
+    serde_json::from_str::<LlmUserStoryResponse>(response)
+        .map(|parsed| !parsed.user_stories.is_empty())
+        .unwrap_or(false)
+}
+
+fn check_code_suggestion(response: &str) -> bool {
+    parse_assist_task_response(response)
+        .map(|parsed| !parsed.suggestions.is_empty())
+        .unwrap_or(false)
+}
+
+fn check_json_adherence(response: &str) -> bool {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(response) else { return false };
+    value.get("name").and_then(|v| v.as_str()) == Some("env-coach")
+        && value.get("version").and_then(|v| v.as_u64()) == Some(1)
+        && value.get("tags").and_then(|v| v.as_array()).is_some_and(|tags| tags.len() == 2)
+}
+
+/// The suite is independent of the current project so results are comparable across projects.
+fn bench_cases() -> Vec<BenchCase> {
+    let fill_context = |template: String| {
+        template
+            .replace("{{project_name}}", "bench-todo")
+            .replace("{{project_description}}", "A command-line todo list manager")
+            .replace("{{tech_stack}}", "rust")
+            .replace("{{primary_language}}", "Rust")
+            .replace("{{tags}}", "cli")
+            .replace("{{project_summary}}", "A single-binary todo CLI: src/main.rs is the entry point.")
+            .replace("{{code_context}}", "### src/main.rs\n```\nfn main() {\n    println!(\"todo\");\n}\n```")
+    };
+
+    let requirement_prompt = fill_context(Templates::default_requirements_analyst_prompt_content())
+        .replace("{{requirement}}", "Users can add, list and complete todo items from the terminal");
+
+    let code_prompt = fill_context(Templates::default_task_assistant_prompt_content())
+        .replace("{{task_id}}", "US-001")
+        .replace("{{task_title}}", "Add a todo item")
+        .replace("{{task_story}}", "As a user, I want to add a todo item so that I can track my work.")
+        .replace("{{#each task_acceptance_criteria}}", "")
+        .replace("{{/each}}", "")
+        .replace("{{this}}", "`todo add <text>` stores the item")
+        .replace("{{relevant_code}}", "None.")
+        .replace("{{task_history}}", "None.")
+        .replace("{{work_in_progress}}", "None.")
+        .replace("{{user_prompt}}", "Implement the add command.");
+
+    let json_prompt = "Return only a JSON object with exactly these fields: \"name\" set to the string \"env-coach\", \"version\" set to the number 1, and \"tags\" set to an array of two short strings. No other text.".to_string();
+
+    vec![
+        BenchCase { name: "requirement analysis", prompt: requirement_prompt, check: check_requirement_analysis },
+        BenchCase { name: "code suggestion", prompt: code_prompt, check: check_code_suggestion },
+        BenchCase { name: "json adherence", prompt: json_prompt, check: check_json_adherence },
+    ]
+}
+
+/// (average latency over successful requests, parse successes, attempts) for one model
+fn summarize(results: &[BenchResult], model: &str) -> (Option<Duration>, usize, usize) {
+    let model_results: Vec<&BenchResult> = results.iter().filter(|r| r.model == model).collect();
+    let completed: Vec<&&BenchResult> = model_results.iter().filter(|r| r.parsed.is_some()).collect();
+    let average = if completed.is_empty() {
+        None
+    } else {
+        Some(completed.iter().map(|r| r.latency).sum::<Duration>() / completed.len() as u32)
+    };
+    let successes = model_results.iter().filter(|r| r.parsed == Some(true)).count();
+    (average, successes, model_results.len())
+}
+
+pub async fn run(ctx: &AppContext, models: Vec<String>, runs: u32) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let models = if models.is_empty() { vec![project.llm().model.clone()] } else { models };
+    let cases = bench_cases();
+
+    println!("🏁 Benchmarking {} model(s) on {} ({} case(s) × {} run(s))",
+        models.len(), project.llm().base_url(), cases.len(), runs);
+
+    let mut results = Vec::new();
+    for model in &models {
+        let cfg = FinalLlmConfig { model: model.clone(), ..project.llm().clone() };
+        println!();
+        println!("🤖 {}", model);
+        for case in &cases {
+            for run in 1..=runs {
+                let started = Instant::now();
+                let response = ollama::send_generation_prompt(&cfg, &case.prompt).await;
+                let latency = started.elapsed();
+                let parsed = match &response {
+                    Ok(text) => Some((case.check)(text)),
+                    Err(e) => {
+                        println!("   ❌ {} (run {}): {}", case.name, run, e);
+                        None
+                    }
+                };
+                if let Some(ok) = parsed {
+                    println!("   {} {:<22} {:>7.1}s", if ok { "✅" } else { "⚠️ " }, case.name, latency.as_secs_f64());
+                }
+                results.push(BenchResult { model: model.clone(), latency, parsed });
+            }
+        }
+    }
+
+    println!();
+    println!("📊 Summary:");
+    println!("   {:<30} {:>12} {:>12}", "Model", "Avg latency", "Parse rate");
+    for model in &models {
+        let (average, successes, attempts) = summarize(&results, model);
+        let latency = average.map(|d| format!("{:.1}s", d.as_secs_f64())).unwrap_or_else(|| "n/a".to_string());
+        println!("   {:<30} {:>12} {:>9}/{:<2}", model, latency, successes, attempts);
+    }
+
+    if output::show_hints() {
+        println!();
+        println!("💡 Set the winner as your default in ~/.config/env-coach/config.json (\"llm\": {{ \"model\": \"...\" }})");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checks_accept_expected_shapes() {
+        assert!(check_json_adherence(r#"{"name": "env-coach", "version": 1, "tags": ["a", "b"]}"#));
+        assert!(!check_json_adherence(r#"{"name": "env-coach", "version": "1", "tags": ["a", "b"]}"#));
+        assert!(!check_json_adherence("Sure! Here is the JSON: {}"));
+
+        assert!(check_requirement_analysis(r#"{"user_stories": [{"title": "t", "story": "s", "priority": "High", "effort": 3, "acceptance_criteria": []}]}"#));
+        assert!(!check_requirement_analysis(r#"{"user_stories": []}"#));
+
+        assert!(check_code_suggestion(r#"{"suggestions": [{"type": "general_advice", "content": "Use clap"}]}"#));
+        assert!(!check_code_suggestion("```rust\nfn main() {}\n```"));
+    }
+
+    #[test]
+    fn test_bench_cases_have_no_unfilled_placeholders() {
+        for case in bench_cases() {
+            assert!(!case.prompt.contains("{{"), "Case '{}' has unfilled placeholders", case.name);
+        }
+    }
+
+    #[test]
+    fn test_summarize_ignores_failed_requests_for_latency() {
+        let results = vec![
+            BenchResult { model: "a".to_string(), latency: Duration::from_secs(2), parsed: Some(true) },
+            BenchResult { model: "a".to_string(), latency: Duration::from_secs(4), parsed: Some(false) },
+            BenchResult { model: "a".to_string(), latency: Duration::from_secs(60), parsed: None },
+            BenchResult { model: "b".to_string(), latency: Duration::from_secs(1), parsed: None },
+        ];
+        assert_eq!(summarize(&results, "a"), (Some(Duration::from_secs(3)), 1, 3));
+        assert_eq!(summarize(&results, "b"), (None, 0, 1));
+    }
+}
@@ -0,0 +1,242 @@
+// src/scripts/breakdown.rs
+//! `breakdown <id>`: have the LLM split a large story into `Task` items that belong to it
+//! and that it depends on.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::config::{BacklogItem, ItemType, LlmRole, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::scripts::import;
+use crate::templates::Templates;
+
+#[derive(Debug, Deserialize)]
+struct LlmTask {
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    acceptance_criteria: Vec<String>,
+    effort: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct LlmBreakdown {
+    tasks: Vec<LlmTask>,
+    #[serde(default)]
+    summary: Option<String>,
+}
+
+pub(crate) fn parse_breakdown_response(response: &str) -> Result<LlmBreakdown> {
+    let breakdown: LlmBreakdown = serde_json::from_str(response.trim())
+        .with_context(|| format!("The LLM did not return a task list: {}", response.chars().take(300).collect::<String>()))?;
+    if breakdown.tasks.is_empty() {
+        anyhow::bail!("The LLM returned no tasks");
+    }
+    Ok(breakdown)
+}
+
+fn build_task_breakdown_prompt(template: &str, project: &Project, item: &BacklogItem) -> String {
+    let criteria = match item.acceptance_criteria.is_empty() {
+        true => "None listed.".to_string(),
+        false => item.acceptance_criteria.iter().map(|criterion| format!("- {}", criterion)).collect::<Vec<_>>().join("\n"),
+    };
+    template
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{tech_stack}}", &project.meta.tech_stack.join(", "))
+        .replace("{{item_id}}", &item.id)
+        .replace("{{item_title}}", &item.title)
+        .replace("{{item_effort}}", &item.effort.to_string())
+        .replace("{{item_story}}", &item.story)
+        .replace("{{extra}}", &item.extra_display())
+        .replace("{{item_acceptance_criteria}}", &criteria)
+}
+
+/// Add the tasks as children of `story_id`, each depending on the one before it, and make
+/// the story depend on all of them with its effort the sum of its tasks'. The story's
+/// sprint points follow the new effort. Returns the new task IDs.
+fn add_tasks(project: &mut Project, story_id: &str, tasks: Vec<LlmTask>) -> Result<Vec<String>> {
+    let story = project.backlog.iter()
+        .find(|item| item.id == story_id)
+        .ok_or_else(|| anyhow::anyhow!("Backlog item '{}' not found", story_id))?
+        .clone();
+
+    let mut ids: Vec<String> = vec![];
+    for task in tasks {
+        let id = import::next_id(project, import::id_prefix(&ItemType::Task));
+        // `edit-item` refuses an empty story, so a task without a description repeats its title
+        let story_text = match task.description.trim().is_empty() {
+            true => task.title.clone(),
+            false => task.description,
+        };
+        project.backlog.push(BacklogItem {
+            id: id.clone(),
+            item_type: ItemType::Task,
+            title: task.title,
+            story: story_text,
+            acceptance_criteria: task.acceptance_criteria,
+            priority: story.priority.clone(),
+            effort: task.effort.clamp(1, 100),
+            status: Status::Todo,
+            created: Utc::now(),
+            sprint: None,
+            dependencies: ids.last().cloned().into_iter().collect(),
+            started_at: None,
+            completed_at: None,
+            component: story.component.clone(),
+            parent: Some(story.id.clone()),
+            tags: vec![],
+            external_ref: None,
+            history: vec![],
+            review: None,
+            criteria_check: None,
+            extra: Default::default(),
+        });
+        ids.push(id);
+    }
+
+    let effort: u32 = project.backlog.iter()
+        .filter(|item| item.parent.as_deref() == Some(story_id) && matches!(item.item_type, ItemType::Task))
+        .map(|item| item.effort)
+        .sum();
+    if let Some(sprint) = story.sprint.as_ref().and_then(|id| project.sprints.iter_mut().find(|sprint| &sprint.id == id)) {
+        sprint.total_points = (sprint.total_points + effort).saturating_sub(story.effort);
+    }
+    let story = project.backlog.iter_mut().find(|item| item.id == story_id).expect("story found above");
+    story.effort = effort;
+    story.dependencies.extend(ids.iter().cloned());
+    Ok(ids)
+}
+
+/// Ask the LLM for a task breakdown of a story and, once confirmed, add the tasks.
+pub async fn run(ctx: &AppContext, item_id: String) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let item = project.backlog.iter()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| anyhow::anyhow!("Backlog item '{}' not found", item_id))?;
+    match item.item_type {
+        ItemType::Epic => anyhow::bail!("'{}' is an epic; add stories to it with: env-coach add-requirement \"...\" --epic {}", item_id, item_id),
+        ItemType::Task => anyhow::bail!("'{}' is already a task", item_id),
+        ItemType::UserStory | ItemType::Bug => {}
+    }
+    if matches!(item.status, Status::Done) {
+        anyhow::bail!("'{}' is already done", item_id);
+    }
+    let existing = project.backlog.iter()
+        .filter(|other| other.parent.as_deref() == Some(item_id.as_str()) && matches!(other.item_type, ItemType::Task))
+        .count();
+    if existing > 0 && !ctx.prompter.confirm(&format!("👉 {} already has {} task(s). Add more?", item_id, existing))? {
+        return Ok(());
+    }
+
+    let prompt_template_path = Path::new(".env-coach/prompts/task_breakdown.md");
+    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
+        Ok(template) => template,
+        Err(_) => {
+            println!("⚠️ Task breakdown prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_task_breakdown_prompt_content()
+        }
+    };
+    let prompt_template = prompts::fill_project_context(&prompt_template, &project, Some(item));
+    let prompt = build_task_breakdown_prompt(&prompt_template, &project, item);
+
+    println!("🤖 Asking LLM to break down {} - {} [{}pts]...", item.id, item.title, item.effort);
+    let response = ollama::send_generation_prompt(&project.llm_for(LlmRole::Planner), &prompt)
+        .await
+        .context("Failed to get a task breakdown from LLM")?;
+    let breakdown = parse_breakdown_response(&response)?;
+
+    println!();
+    println!("🧩 Proposed tasks for {}:", item_id);
+    for (i, task) in breakdown.tasks.iter().enumerate() {
+        println!("   {}. {} [{}pts]", i + 1, task.title, task.effort.clamp(1, 100));
+        if !task.description.is_empty() {
+            println!("      {}", task.description);
+        }
+        for criterion in &task.acceptance_criteria {
+            println!("      - {}", criterion);
+        }
+    }
+    if let Some(summary) = &breakdown.summary {
+        println!("ℹ️ {}", summary);
+    }
+    if !ctx.prompter.confirm("👉 Add these tasks to the backlog?")? {
+        println!("❌ Breakdown discarded");
+        return Ok(());
+    }
+
+    let old_effort = item.effort;
+    let ids = add_tasks(&mut project, &item_id, breakdown.tasks)?;
+    ctx.save_project(&project).context("Failed to save project")?;
+    let story = project.backlog.iter().find(|item| item.id == item_id).expect("story still in backlog");
+    println!("✅ Added {} ({} now depends on them)", ids.join(", "), item_id);
+    println!("📏 {} effort: {} → {} points (sum of its tasks)", item_id, old_effort, story.effort);
+
+    let start = format!("env-coach start-task {}", ids[0]);
+    let mut next_steps = vec![(start.as_str(), "Start with the first task")];
+    let add_to_sprint = story.sprint.as_ref().map(|sprint| format!("env-coach sprint add-item {} {}", sprint, ids.join(",")));
+    if let Some(command) = &add_to_sprint {
+        next_steps.insert(0, (command.as_str(), "Plan the tasks into the story's sprint"));
+    }
+    hints::print_next_steps(Some(&project), &next_steps);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Priority, Sprint, SprintStatus};
+
+    #[test]
+    fn test_add_tasks_links_and_sums_effort() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog.push(BacklogItem::test("US-005")
+            .with_title("Login")
+            .with_story("As a user I want to log in")
+            .with_priority(Priority::High)
+            .with_effort(8)
+            .with_sprint("S-001")
+            .with_dependencies(&["US-001"]));
+        project.sprints.push(Sprint {
+            id: "S-001".to_string(),
+            goal: "Auth".to_string(),
+            start_date: Utc::now(),
+            end_date: Utc::now(),
+            status: SprintStatus::Active,
+            stories: vec!["US-005".to_string()],
+            tasks: vec![],
+            total_points: 10,
+            completed_points: 0,
+            planned_velocity: 10,
+            actual_velocity: 0,
+            retrospective: None,
+        });
+
+        let breakdown = parse_breakdown_response(r#"{"tasks": [
+            {"title": "Store sessions", "description": "Table and repository", "effort": 2},
+            {"title": "Login endpoint", "acceptance_criteria": ["Bad passwords get 401"], "effort": 0}
+        ]}"#).unwrap();
+        let ids = add_tasks(&mut project, "US-005", breakdown.tasks).unwrap();
+        assert_eq!(ids, vec!["TASK-001", "TASK-002"]);
+
+        let task = project.backlog.iter().find(|item| item.id == "TASK-002").unwrap();
+        assert_eq!(task.parent.as_deref(), Some("US-005"));
+        assert_eq!(task.dependencies, vec!["TASK-001"]);
+        assert_eq!(task.effort, 1);
+        assert_eq!(task.story, "Login endpoint");
+        assert_eq!(task.priority, Priority::High);
+
+        let story = &project.backlog[0];
+        assert_eq!(story.effort, 3);
+        assert_eq!(story.dependencies, vec!["US-001", "TASK-001", "TASK-002"]);
+        assert_eq!(project.sprints[0].total_points, 5);
+
+        assert!(parse_breakdown_response(r#"{"tasks": []}"#).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+// src/scripts/bugs.rs
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::config::{BacklogItem, ItemType, LlmRole, Priority, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::scripts::edit_item::validate_item;
+use crate::templates::Templates;
+
+/// Structured bug report as returned by the bug analyst prompt.
+#[derive(Deserialize, Debug)]
+pub(crate) struct LlmBugReport {
+    summary: String,
+    #[serde(default)]
+    repro_steps: Vec<String>,
+    #[serde(default)]
+    expected: String,
+    #[serde(default)]
+    actual: String,
+    #[serde(default)]
+    acceptance_criteria: Vec<String>,
+    effort: u32,
+}
+
+/// Next free `BUG-NNN` ID.
+fn next_bug_id(project: &Project) -> String {
+    let highest = project.backlog.iter()
+        .chain(project.archive.iter().map(|archived| &archived.item))
+        .filter_map(|item| item.id.strip_prefix("BUG-").and_then(|n| n.parse::<u32>().ok()))
+        .max()
+        .unwrap_or(0);
+    format!("BUG-{:03}", highest + 1)
+}
+
+fn build_bug_prompt(template: &str, project: &Project, title: &str, report: &str, severity: &Priority) -> String {
+    template
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{tech_stack}}", &project.meta.tech_stack.join(", "))
+        .replace("{{bug_title}}", title)
+        .replace("{{bug_report}}", report)
+        .replace("{{severity}}", &format!("{:?}", severity))
+}
+
+pub(crate) fn parse_bug_response(response: &str) -> Result<LlmBugReport> {
+    serde_json::from_str(response).with_context(|| {
+        let snippet = response.chars().take(500).collect::<String>();
+        format!("Failed to parse LLM bug analysis. Response (first 500 chars): '{}'", snippet)
+    })
+}
+
+/// The item description: summary, numbered repro steps and expected/actual behaviour.
+fn format_bug_story(report: &LlmBugReport) -> String {
+    let mut story = report.summary.trim().to_string();
+    if !report.repro_steps.is_empty() {
+        story.push_str("\n\nSteps to reproduce:");
+        for (i, step) in report.repro_steps.iter().enumerate() {
+            story.push_str(&format!("\n{}. {}", i + 1, step));
+        }
+    }
+    if !report.expected.is_empty() {
+        story.push_str(&format!("\n\nExpected: {}", report.expected));
+    }
+    if !report.actual.is_empty() {
+        story.push_str(&format!("\n\nActual: {}", report.actual));
+    }
+    story
+}
+
+/// Used when the LLM is skipped or unavailable: keep the report as written.
+fn unanalyzed_report(title: &str, report: &str) -> LlmBugReport {
+    LlmBugReport {
+        summary: if report.is_empty() { title.to_string() } else { report.to_string() },
+        repro_steps: vec![],
+        expected: String::new(),
+        actual: String::new(),
+        acceptance_criteria: vec!["The reported problem no longer reproduces".to_string()],
+        effort: 3,
+    }
+}
+
+fn bug_item(id: String, title: String, severity: Priority, component: Option<String>, report: &LlmBugReport) -> BacklogItem {
+    BacklogItem {
+        id,
+        item_type: ItemType::Bug,
+        title,
+        story: format_bug_story(report),
+        acceptance_criteria: report.acceptance_criteria.clone(),
+        priority: severity,
+        effort: report.effort.clamp(1, 100),
+        status: Status::Todo,
+        created: Utc::now(),
+        sprint: None,
+        dependencies: vec![],
+        started_at: None,
+        completed_at: None,
+        component,
+        parent: None,
+        tags: vec![],
+        external_ref: None,
+        history: vec![],
+        review: None,
+        criteria_check: None,
+        extra: Default::default(),
+    }
+}
+
+/// Create a `BUG-NNN` backlog item, letting the LLM turn the free-text report into
+/// repro steps and acceptance criteria.
+pub async fn run(ctx: &AppContext, title: String, repro: Option<String>, severity: Priority, component: Option<String>, no_llm: bool) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let report_text = repro.unwrap_or_default();
+
+    let report = if no_llm {
+        unanalyzed_report(&title, &report_text)
+    } else {
+        let prompt_template_path = std::path::Path::new(".env-coach/prompts/bug_analyst.md");
+        let prompt_template = match std::fs::read_to_string(prompt_template_path) {
+            Ok(template) => template,
+            Err(_) => {
+                println!("⚠️ Bug analyst prompt not found at {:?}. Using default.", prompt_template_path);
+                Templates::default_bug_analyst_prompt_content()
+            }
+        };
+        let prompt_template = prompts::fill_project_context(&prompt_template, &project, None);
+        let prompt = build_bug_prompt(&prompt_template, &project, &title, &report_text, &severity);
+
+        println!("🤖 Asking LLM to structure the bug report...");
+        let analysis = ollama::send_generation_prompt(&project.llm_for(LlmRole::Coder), &prompt).await
+            .and_then(|response| parse_bug_response(&response));
+        match analysis {
+            Ok(report) => report,
+            Err(e) => {
+                println!("⚠️ LLM analysis failed, recording the report as written: {}", e);
+                unanalyzed_report(&title, &report_text)
+            }
+        }
+    };
+
+    let id = next_bug_id(&project);
+    let item = bug_item(id.clone(), title, severity, component, &report);
+    validate_item(&project, &item)?;
+
+    println!("🐛 {} - {} [{:?}, {}pts]", item.id, item.title, item.priority, item.effort);
+    println!("{}", item.story);
+    if !item.acceptance_criteria.is_empty() {
+        println!("   Acceptance criteria:");
+        for criterion in &item.acceptance_criteria {
+            println!("   - {}", criterion);
+        }
+    }
+    project.backlog.push(item);
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    println!("✅ Added {} to the backlog", id);
+    hints::print_next_steps(Some(&project), &[
+        (format!("env-coach edit-item {} --editor", id).as_str(), "Refine the report"),
+        (format!("env-coach sprint add-item <sprint> {}", id).as_str(), "Schedule the fix"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bug_response_and_format_story() {
+        let json = r#"{"summary": "Login fails for emails with a plus sign", "repro_steps": ["Open /login", "Enter a+b@x.io"], "expected": "User is logged in", "actual": "400 error", "acceptance_criteria": ["Emails with + can log in"], "effort": 2}"#;
+        let report = parse_bug_response(json).unwrap();
+        assert_eq!(report.effort, 2);
+        assert_eq!(
+            format_bug_story(&report),
+            "Login fails for emails with a plus sign\n\nSteps to reproduce:\n1. Open /login\n2. Enter a+b@x.io\n\nExpected: User is logged in\n\nActual: 400 error"
+        );
+        assert!(parse_bug_response("not json").is_err());
+    }
+
+    #[test]
+    fn test_bug_ids_are_numbered_separately_from_stories() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        assert_eq!(next_bug_id(&project), "BUG-001");
+
+        let report = unanalyzed_report("Crash on save", "");
+        project.backlog.push(bug_item("BUG-004".to_string(), "Crash on save".to_string(), Priority::High, None, &report));
+        assert_eq!(next_bug_id(&project), "BUG-005");
+        assert_eq!(project.backlog[0].item_type, ItemType::Bug);
+        assert_eq!(project.backlog[0].story, "Crash on save");
+    }
+}
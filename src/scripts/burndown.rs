@@ -0,0 +1,173 @@
+// src/scripts/burndown.rs
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::config::{BacklogItem, Sprint, SprintStatus, Status};
+use crate::context::AppContext;
+use crate::output;
+
+const CHART_WIDTH: u32 = 40;
+
+/// Remaining points at the end of each sprint day, up to and including `today`.
+/// Items that are Done but have no `completed_at` (older projects) are not burned down.
+fn remaining_points_by_day(sprint: &Sprint, items: &[&BacklogItem], today: NaiveDate) -> Vec<(NaiveDate, u32)> {
+    let start = sprint.start_date.date_naive();
+    let end = sprint.end_date.date_naive().min(today);
+
+    let mut days = Vec::new();
+    let mut day = start;
+    while day <= end {
+        let burned: u32 = items.iter()
+            .filter(|item| matches!(item.status, Status::Done))
+            .filter(|item| item.completed_at.is_some_and(|done| done.date_naive() <= day))
+            .map(|item| item.effort)
+            .sum();
+        days.push((day, sprint.total_points.saturating_sub(burned)));
+        day += Duration::days(1);
+    }
+    days
+}
+
+/// Render the burndown as ASCII bars, one line per day, with `|` marking the ideal line.
+fn render_burndown(sprint: &Sprint, items: &[&BacklogItem], today: DateTime<Utc>) -> Vec<String> {
+    let total = sprint.total_points;
+    let sprint_days = (sprint.end_date.date_naive() - sprint.start_date.date_naive()).num_days().max(1) as f64;
+    let scale = |points: f64| -> usize {
+        if total == 0 { 0 } else { ((points / total as f64) * CHART_WIDTH as f64).round() as usize }
+    };
+
+    remaining_points_by_day(sprint, items, today.date_naive())
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (day, remaining))| {
+            let ideal = total as f64 * (1.0 - idx as f64 / sprint_days).max(0.0);
+            let bar_len = scale(remaining as f64);
+            let ideal_pos = scale(ideal);
+
+            let mut bar: Vec<char> = (0..=CHART_WIDTH as usize)
+                .map(|pos| if pos < bar_len { '#' } else { ' ' })
+                .collect();
+            if bar[ideal_pos] == ' ' {
+                bar[ideal_pos] = '|';
+            }
+            let bar: String = bar.into_iter().collect();
+            format!("  {} {} {:>3} (ideal {:.1})", day.format("%m-%d"), bar, remaining, ideal)
+        })
+        .collect()
+}
+
+pub fn run(ctx: &AppContext, sprint_id: Option<String>) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    let sprint = match &sprint_id {
+        Some(id) => project.sprints.iter()
+            .find(|s| &s.id == id)
+            .ok_or_else(|| anyhow::anyhow!("Sprint '{}' not found", id))?,
+        None => match project.sprints.iter().find(|s| matches!(s.status, SprintStatus::Active)) {
+            Some(sprint) => sprint,
+            None => {
+                println!("📭 No active sprint");
+                if output::show_hints() {
+                    println!("💡 Pass a sprint ID to chart a past sprint: env-coach burndown S-001");
+                }
+                return Ok(());
+            }
+        },
+    };
+
+    let items: Vec<&BacklogItem> = project.backlog.iter()
+        .filter(|item| item.sprint.as_ref() == Some(&sprint.id))
+        .collect();
+
+    println!("📉 Burndown for {} ({} points, {} to {})",
+        sprint.id,
+        sprint.total_points,
+        sprint.start_date.format("%Y-%m-%d"),
+        sprint.end_date.format("%Y-%m-%d")
+    );
+    println!("🎯 Goal: {}", sprint.goal);
+    println!();
+
+    let lines = render_burndown(sprint, &items, Utc::now());
+    if lines.is_empty() {
+        println!("   Sprint has not started yet.");
+    }
+    for line in lines {
+        println!("{}", line);
+    }
+
+    let undated = items.iter()
+        .filter(|item| matches!(item.status, Status::Done) && item.completed_at.is_none())
+        .count();
+    if undated > 0 {
+        println!();
+        println!("⚠️  {} done item(s) have no completion date and are not shown in the chart.", undated);
+    }
+
+    println!();
+    println!("   # remaining points    | ideal burndown");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn item(id: &str, effort: u32, completed_at: Option<DateTime<Utc>>) -> BacklogItem {
+        BacklogItem::test(id)
+            .with_effort(effort)
+            .with_status(if completed_at.is_some() { Status::Done } else { Status::Todo })
+            .with_created(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+            .with_sprint("S-001")
+            .with_completed_at(completed_at)
+    }
+
+    fn sprint() -> Sprint {
+        Sprint {
+            id: "S-001".to_string(),
+            goal: "Goal".to_string(),
+            start_date: Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap(),
+            end_date: Utc.with_ymd_and_hms(2024, 1, 5, 9, 0, 0).unwrap(),
+            status: SprintStatus::Active,
+            total_points: 10,
+            completed_points: 0,
+            tasks: vec![],
+            stories: vec![],
+            planned_velocity: 0,
+            actual_velocity: 0,
+            retrospective: None,
+        }
+    }
+
+    #[test]
+    fn test_remaining_points_by_day_burns_on_completion_date() {
+        let a = item("US-001", 3, Some(Utc.with_ymd_and_hms(2024, 1, 2, 15, 0, 0).unwrap()));
+        let b = item("US-002", 5, Some(Utc.with_ymd_and_hms(2024, 1, 4, 10, 0, 0).unwrap()));
+        let c = item("US-003", 2, None);
+        let items = vec![&a, &b, &c];
+
+        let days = remaining_points_by_day(&sprint(), &items, NaiveDate::from_ymd_opt(2024, 1, 10).unwrap());
+        let remaining: Vec<u32> = days.iter().map(|(_, r)| *r).collect();
+        assert_eq!(remaining, vec![10, 7, 7, 2, 2]);
+    }
+
+    #[test]
+    fn test_remaining_points_by_day_stops_at_today() {
+        let items: Vec<&BacklogItem> = vec![];
+        let days = remaining_points_by_day(&sprint(), &items, NaiveDate::from_ymd_opt(2024, 1, 2).unwrap());
+        assert_eq!(days.len(), 2);
+    }
+
+    #[test]
+    fn test_render_burndown_draws_bars_and_ideal_marker() {
+        let a = item("US-001", 5, Some(Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()));
+        let items = vec![&a];
+        let lines = render_burndown(&sprint(), &items, Utc.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap());
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("  01-01 ####################"));
+        assert!(lines[0].contains('|'), "Ideal marker should be drawn past the bar on day one");
+        assert!(lines[0].ends_with("  5 (ideal 10.0)"));
+    }
+}
@@ -0,0 +1,301 @@
+// src/scripts/chat.rs
+//! `chat [--task <id>]`: a conversation with the project's model that keeps its context
+//! between messages. It starts from the project's metadata and open backlog, and the task
+//! in focus with what was suggested for it before. Slash commands turn the conversation
+//! into backlog items (`/add-story`) or code suggestions for the task (`/apply`).
+
+use anyhow::{Context, Result};
+use std::future::Future;
+use std::io::Write;
+
+use crate::auto_commit::AutoCommit;
+use crate::auto_update::llm_parsers::parse_assist_task_response;
+use crate::code_index;
+use crate::config::{BacklogItem, Project, Status};
+use crate::context::AppContext;
+use crate::lock::ProjectLock;
+use crate::ollama::{self, Message};
+use crate::prompts;
+use crate::scripts::session::{self, Session};
+use crate::scripts::{requirements, tasks};
+use crate::templates::Templates;
+use crate::tokens;
+
+const HELP: &str = "\
+   /task <id>        Focus the conversation on a task (/task none to leave it)
+   /add-story [text] Add backlog items from the text, or from the last answer
+   /apply            Turn the last answer into code suggestions for the task in focus
+   /clear            Start the conversation over
+   /help             Show this list
+   /quit             Leave (so do Ctrl-D and /exit)";
+
+#[derive(Debug, PartialEq)]
+enum ChatInput {
+    Message(String),
+    Task(Option<String>),
+    AddStory(Option<String>),
+    Apply,
+    Clear,
+    Help,
+    Quit,
+    Unknown(String),
+    Empty,
+}
+
+fn parse_input(line: &str) -> ChatInput {
+    let line = line.trim();
+    if line.is_empty() {
+        return ChatInput::Empty;
+    }
+    let Some(command) = line.strip_prefix('/') else {
+        return ChatInput::Message(line.to_string());
+    };
+    let (name, argument) = command.split_once(char::is_whitespace).unwrap_or((command, ""));
+    let argument = Some(argument.trim().to_string()).filter(|argument| !argument.is_empty());
+    match name {
+        "task" => ChatInput::Task(argument.filter(|id| id != "none")),
+        "add-story" => ChatInput::AddStory(argument),
+        "apply" => ChatInput::Apply,
+        "clear" => ChatInput::Clear,
+        "help" | "?" => ChatInput::Help,
+        "quit" | "exit" => ChatInput::Quit,
+        _ => ChatInput::Unknown(name.to_string()),
+    }
+}
+
+/// The system message: `chat.md` filled with the project, its open items and the task in
+/// focus (with its condensed history).
+fn build_system_prompt(template: &str, project: &Project, task: Option<&BacklogItem>, history: &str) -> String {
+    let backlog = project.backlog.iter()
+        .filter(|item| !matches!(item.status, Status::Done))
+        .map(|item| format!("- {} [{:?}, {:?}] {}", item.id, item.status, item.priority, item.title))
+        .collect::<Vec<_>>();
+    let task = match task {
+        Some(task) => format!(
+            "{}: {}\n{}\nAcceptance criteria:\n{}\nEarlier suggestions:\n{}",
+            task.id, task.title, task.story,
+            task.acceptance_criteria.iter().map(|ac| format!("- {}", ac)).collect::<Vec<_>>().join("\n"),
+            history
+        ),
+        None => "None; the conversation is about the project as a whole.".to_string(),
+    };
+    template
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{tech_stack}}", &project.meta.tech_stack.join(", "))
+        .replace("{{primary_language}}", &crate::auto_update::code_gen::get_primary_language(&project.meta))
+        .replace("{{tags}}", &project.get_tags_display())
+        .replace("{{backlog}}", &if backlog.is_empty() { "None.".to_string() } else { backlog.join("\n") })
+        .replace("{{task}}", &task)
+}
+
+/// Drop the oldest messages after the system message until the conversation fits `budget`
+/// tokens, keeping at least the latest message. Returns how many were dropped.
+fn fit_conversation(messages: &mut Vec<Message>, budget: usize) -> usize {
+    let size = |messages: &[Message]| messages.iter().map(|message| tokens::estimate_tokens(&message.content) + 4).sum::<usize>();
+    let mut dropped = 0;
+    while messages.len() > 2 && size(messages) > budget {
+        messages.remove(1);
+        dropped += 1;
+    }
+    dropped
+}
+
+fn system_message(project: &Project, task_id: Option<&str>) -> Result<Message> {
+    let template_path = std::path::Path::new(".env-coach/prompts/chat.md");
+    let template = match std::fs::read_to_string(template_path) {
+        Ok(template) => template,
+        Err(_) => Templates::default_chat_prompt_content(),
+    };
+    let task = match task_id {
+        Some(id) => Some(project.backlog.iter().find(|item| item.id == id)
+            .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in backlog.", id))?),
+        None => None,
+    };
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let history = task.map(|task| session::condensed_history(&session::task_sessions(&root, &task.id))).unwrap_or_default();
+    let template = prompts::fill_project_context(&template, project, task);
+    Ok(Message::new("system", &build_system_prompt(&template, project, task, &history)))
+}
+
+/// Run a slash command that changes the project the way a mutating command runs: under
+/// the project lock and committed afterwards when auto_commit is on. `chat` itself counts
+/// as read-only so that it does not hold the lock for the whole conversation.
+async fn as_mutating_command(ctx: &AppContext, change: impl Future<Output = Result<()>>) -> Result<()> {
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let _lock = if root.join(".env-coach").is_dir() { Some(ProjectLock::acquire(&root, false)?) } else { None };
+    let auto_commit = AutoCommit::start(ctx);
+    change.await?;
+    if let Some(auto_commit) = auto_commit {
+        auto_commit.finish();
+    }
+    Ok(())
+}
+
+/// Ask for structured suggestions implementing `answer` for `task_id`, save them as a session
+/// of the task and go through them as `apply-session` does.
+async fn apply_answer(ctx: &AppContext, task_id: &str, answer: &str) -> Result<()> {
+    let project = ctx.load_project()?;
+    let task = project.backlog.iter()
+        .find(|item| item.id == task_id)
+        .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in backlog.", task_id))?;
+    let request = format!("Turn this answer from our chat into changes:\n\n{}", answer);
+    let relevant_code = code_index::relevant_code(&project, task).await;
+    let prompts = tasks::build_task_assistant_prompts(&project, task, Some(request), relevant_code, None).await?;
+    let response = tasks::ask_task_assistant(&project, &prompts)
+        .await
+        .context("Failed to get suggestions from LLM")?;
+    let parsed = match parse_assist_task_response(&response) {
+        Ok(parsed) if !parsed.suggestions.is_empty() => parsed,
+        _ => {
+            println!("⚠️ The LLM did not return structured suggestions; ask for the change more precisely and /apply again.");
+            return Ok(());
+        }
+    };
+
+    let session = Session::new(task_id, response).with_prompt(Some("/apply from chat".to_string()));
+    if ctx.dry_run {
+        println!("🔍 Dry run: {} suggestion(s) not saved or applied:", parsed.suggestions.len());
+        for suggestion in &parsed.suggestions {
+            println!("   - {}", suggestion.change_summary().unwrap_or_else(|| "Advice".to_string()));
+        }
+        return Ok(());
+    }
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let path = session.review_path(&root);
+    session.save(&path)?;
+    session::apply(ctx, &path, None, false)
+}
+
+pub async fn run(ctx: &AppContext, task: Option<String>) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let mut task_id = task;
+    let mut messages = vec![system_message(&project, task_id.as_deref())?];
+
+    println!("💬 Chatting with {} about {}{}", project.llm().model, project.meta.name,
+        task_id.as_ref().map(|id| format!(" ({})", id)).unwrap_or_default());
+    println!("   Type /help for commands, /quit to leave.");
+
+    loop {
+        print!("\nyou> ");
+        std::io::stdout().flush().ok();
+        // stdin is not held between lines: /add-story and /apply ask questions on it too
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line).context("Failed to read from stdin")? == 0 {
+            println!();
+            break;
+        }
+        let last_answer = messages.iter().rev()
+            .find(|message| message.role == "assistant")
+            .map(|message| message.content.clone());
+
+        match parse_input(&line) {
+            ChatInput::Empty => {}
+            ChatInput::Quit => break,
+            ChatInput::Help => println!("{}", HELP),
+            ChatInput::Unknown(name) => println!("⚠️ Unknown command /{}; type /help for the list", name),
+            ChatInput::Clear => {
+                messages.truncate(1);
+                println!("🧹 Conversation cleared");
+            }
+            ChatInput::Task(id) => {
+                match system_message(&project, id.as_deref()) {
+                    Ok(message) => {
+                        messages[0] = message;
+                        task_id = id;
+                        match &task_id {
+                            Some(id) => println!("🎯 Focused on {}", id),
+                            None => println!("🎯 No task in focus"),
+                        }
+                    }
+                    Err(e) => println!("⚠️ {}", e),
+                }
+            }
+            ChatInput::AddStory(text) => {
+                let Some(requirement) = text.or(last_answer) else {
+                    println!("⚠️ Nothing to add yet: give the story text, or ask something first");
+                    continue;
+                };
+                if let Err(e) = as_mutating_command(ctx, requirements::process_requirement(ctx, requirement, None, None)).await {
+                    println!("⚠️ {:#}", e);
+                }
+                // New items belong in the context of the rest of the conversation
+                project = ctx.load_project()?;
+                messages[0] = system_message(&project, task_id.as_deref())?;
+            }
+            ChatInput::Apply => {
+                let (Some(id), Some(answer)) = (&task_id, &last_answer) else {
+                    println!("⚠️ /apply needs a task in focus (/task <id>) and an answer to turn into changes");
+                    continue;
+                };
+                if let Err(e) = as_mutating_command(ctx, apply_answer(ctx, id, answer)).await {
+                    println!("⚠️ {:#}", e);
+                }
+                project = ctx.load_project()?;
+                messages[0] = system_message(&project, task_id.as_deref())?;
+            }
+            ChatInput::Message(text) => {
+                messages.push(Message::new("user", &text));
+                let dropped = fit_conversation(&mut messages, tokens::prompt_budget(project.llm()));
+                if dropped > 0 {
+                    println!("📏 Left out the {} oldest message(s) to fit the model's context window", dropped);
+                }
+                match ollama::send_chat(project.llm(), &messages).await {
+                    Ok(reply) => {
+                        println!("\n🤖 {}", reply.trim());
+                        messages.push(Message::new("assistant", &reply));
+                    }
+                    Err(e) => {
+                        // The question stays out of the context so it can be asked again
+                        messages.pop();
+                        println!("⚠️ {:#}", e);
+                    }
+                }
+            }
+        }
+    }
+    println!("👋 Chat ended");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(parse_input("  how do we parse JSON? "), ChatInput::Message("how do we parse JSON?".to_string()));
+        assert_eq!(parse_input(""), ChatInput::Empty);
+        assert_eq!(parse_input("/task US-002"), ChatInput::Task(Some("US-002".to_string())));
+        assert_eq!(parse_input("/task none"), ChatInput::Task(None));
+        assert_eq!(parse_input("/add-story"), ChatInput::AddStory(None));
+        assert_eq!(parse_input("/add-story Export to CSV"), ChatInput::AddStory(Some("Export to CSV".to_string())));
+        assert_eq!(parse_input("/apply"), ChatInput::Apply);
+        assert_eq!(parse_input("/exit"), ChatInput::Quit);
+        assert_eq!(parse_input("/deploy now"), ChatInput::Unknown("deploy".to_string()));
+    }
+
+    #[test]
+    fn test_system_prompt_and_fit_conversation() {
+        let mut project = Project::new("Todo".to_string(), "A todo CLI".to_string(), None);
+        project.meta.tech_stack = vec!["rust".to_string()];
+        let template = Templates::default_chat_prompt_content();
+        let prompt = build_system_prompt(&template, &project, None, "");
+        assert!(!prompt.replace(crate::project_summary::PLACEHOLDER, "").contains("{{"));
+        assert!(prompt.contains("- Name: Todo"));
+        assert!(prompt.contains("**Open Backlog Items:**\nNone."));
+        assert!(prompt.contains("None; the conversation is about the project as a whole."));
+
+        let mut messages = vec![Message::new("system", &prompt)];
+        for i in 0..6 {
+            messages.push(Message::new("user", &format!("question {} about the todo list", i)));
+        }
+        let system = tokens::estimate_tokens(&prompt) + 4;
+        assert_eq!(fit_conversation(&mut messages, 10_000), 0);
+        assert_eq!(fit_conversation(&mut messages, system + 25), 4);
+        assert_eq!(messages[0].role, "system");
+        assert_eq!(messages[1].content, "question 4 about the todo list");
+        assert_eq!(fit_conversation(&mut messages, 0), 1);
+        assert_eq!(messages.len(), 2);
+    }
+}
@@ -0,0 +1,68 @@
+// src/scripts/coach.rs
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::context::AppContext;
+use crate::hints;
+
+/// `--format json|yaml` output of `coach --rules`.
+#[derive(Serialize)]
+struct RuleState {
+    id: &'static str,
+    description: &'static str,
+    enabled: bool,
+}
+
+/// Print every hint for the project, most urgent first. Unlike the "Next steps" blocks this
+/// ignores the hints verbosity, since the user asked for it; only the project's own
+/// `meta.hints` settings apply.
+pub fn run(ctx: &AppContext, rules: bool) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let disabled = project.meta.hints.as_ref().map(|config| config.disabled.clone()).unwrap_or_default();
+
+    if rules {
+        if ctx.format.is_structured() {
+            let rules: Vec<RuleState> = hints::RULES.iter()
+                .map(|(id, description, _)| RuleState {
+                    id,
+                    description,
+                    enabled: !hints::all_disabled(&project) && !disabled.iter().any(|rule| rule == id),
+                })
+                .collect();
+            return ctx.print_structured(&rules);
+        }
+        println!("📏 Hint rules (disable with meta.hints.disabled in project.json):");
+        for (id, description, _) in hints::RULES {
+            let marker = if hints::all_disabled(&project) || disabled.iter().any(|rule| rule == id) { "🔕" } else { "✅" };
+            println!("   {} {:<18} {}", marker, id, description);
+        }
+        let custom = project.meta.hints.as_ref().map(|config| config.custom.len()).unwrap_or(0);
+        if custom > 0 {
+            println!("   ➕ {} custom hint(s) from meta.hints.custom", custom);
+        }
+        return Ok(());
+    }
+
+    if ctx.format.is_structured() {
+        // Empty when all hints are disabled
+        return ctx.print_structured(&hints::collect(&project, Utc::now()));
+    }
+    if hints::all_disabled(&project) {
+        println!("🔕 Hints are disabled for this project (meta.hints.disabled contains \"{}\")", hints::DISABLE_ALL);
+        return Ok(());
+    }
+
+    let suggestions = hints::collect(&project, Utc::now());
+    if suggestions.is_empty() {
+        println!("✅ Nothing to suggest: no open reviews, tasks or planning gaps in {}", project.meta.name);
+        return Ok(());
+    }
+    println!("🧭 Recommended next actions for {}:", project.meta.name);
+    hints::print_ranked(&suggestions);
+    if !disabled.is_empty() {
+        println!();
+        println!("🔕 Disabled rules: {}", disabled.join(", "));
+    }
+    Ok(())
+}
@@ -0,0 +1,161 @@
+// src/scripts/commit_msg.rs
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::config::{BacklogItem, ItemType};
+use crate::context::AppContext;
+use crate::output;
+use crate::git;
+
+const SUBJECT_MAX_LEN: usize = 72;
+const BODY_WIDTH: usize = 72;
+
+fn commit_type(item_type: &ItemType) -> &'static str {
+    match item_type {
+        ItemType::Bug => "fix",
+        ItemType::UserStory | ItemType::Epic => "feat",
+        ItemType::Task => "chore",
+    }
+}
+
+/// Component for the conventional-commit scope: the module most of the staged files belong to.
+/// `src/scripts/sprint.rs` -> `sprint`, `src/auto_update/updater.rs` -> `auto_update`, `README.md` -> `docs`.
+fn component_for_path(path: &str) -> String {
+    let path = Path::new(path);
+    let parts: Vec<&str> = path.iter().filter_map(|p| p.to_str()).collect();
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+
+    match parts.as_slice() {
+        [file] if file.ends_with(".md") => "docs".to_string(),
+        [_] => stem.to_string(),
+        ["src", _] if matches!(stem, "main" | "lib" | "mod") => "core".to_string(),
+        ["src", _] => stem.to_string(),
+        ["src", "scripts", _] => stem.to_string(),
+        ["src", dir, ..] => dir.to_string(),
+        [dir, ..] => dir.to_string(),
+        [] => String::new(),
+    }
+}
+
+fn scope_from_paths(paths: &[String]) -> Option<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for path in paths {
+        let component = component_for_path(path);
+        if !component.is_empty() {
+            *counts.entry(component).or_insert(0) += 1;
+        }
+    }
+    // Highest count wins; ties are broken alphabetically so the output is stable
+    counts.into_iter()
+        .max_by(|(a_name, a_count), (b_name, b_count)| a_count.cmp(b_count).then(b_name.cmp(a_name)))
+        .map(|(name, _)| name)
+}
+
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.len() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn format_commit_message(item: &BacklogItem, staged_files: &[String]) -> String {
+    let prefix = match scope_from_paths(staged_files) {
+        Some(scope) => format!("{}({})", commit_type(&item.item_type), scope),
+        None => commit_type(&item.item_type).to_string(),
+    };
+
+    let mut description: String = item.title.trim().trim_end_matches('.').to_string();
+    if let Some(first) = description.chars().next() {
+        description.replace_range(..first.len_utf8(), &first.to_lowercase().to_string());
+    }
+    let mut subject = format!("{}: {}", prefix, description);
+    if subject.chars().count() > SUBJECT_MAX_LEN {
+        subject = subject.chars().take(SUBJECT_MAX_LEN - 3).collect::<String>() + "...";
+    }
+
+    let mut message = subject;
+    let body = wrap_text(&item.story, BODY_WIDTH);
+    if !body.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&body.join("\n"));
+    }
+    message.push_str(&format!("\n\nRefs: {}\n", item.id));
+    message
+}
+
+/// Print a commit message for `item_id` on stdout; hints go to stderr so the output can be
+/// piped straight into `git commit -F -`.
+pub fn run(ctx: &AppContext, item_id: String) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    let item = project.backlog.iter()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| anyhow::anyhow!("Backlog item '{}' not found", item_id))?;
+
+    let files = git::staged_files()?;
+    if files.is_empty() {
+        anyhow::bail!("No staged changes. Stage your work with 'git add' first.");
+    }
+
+    eprintln!("📝 Drafting commit message for {} from {} staged file(s)", item.id, files.len());
+    print!("{}", format_commit_message(item, &files));
+    if output::show_hints() {
+        eprintln!("💡 Use it directly: env-coach commit-msg {} | git commit -F -", item.id);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_from_paths_picks_most_common_component() {
+        let files = vec![
+            "src/scripts/sprint.rs".to_string(),
+            "src/scripts/sprint.rs".to_string(),
+            "src/config.rs".to_string(),
+            "README.md".to_string(),
+        ];
+        assert_eq!(scope_from_paths(&files), Some("sprint".to_string()));
+        assert_eq!(component_for_path("src/auto_update/updater.rs"), "auto_update");
+        assert_eq!(component_for_path("src/main.rs"), "core");
+        assert_eq!(scope_from_paths(&[]), None);
+    }
+
+    #[test]
+    fn test_format_commit_message_for_story() {
+        let story = BacklogItem::test("US-003").with_title("User Login Feature.")
+            .with_story("As a registered user, I want to log in so that I can access my personalized content.");
+        let message = format_commit_message(&story, &["src/auth.rs".to_string()]);
+        let lines: Vec<&str> = message.lines().collect();
+        assert_eq!(lines[0], "feat(auth): user Login Feature");
+        assert_eq!(lines[1], "");
+        assert!(lines[2].starts_with("As a registered user"));
+        assert!(lines.iter().all(|l| l.len() <= BODY_WIDTH));
+        assert!(message.ends_with("\n\nRefs: US-003\n"));
+    }
+
+    #[test]
+    fn test_format_commit_message_for_bug_truncates_subject() {
+        let bug = BacklogItem::test("US-003").with_type(ItemType::Bug).with_title(&"Crash ".repeat(20)).with_story("");
+        let message = format_commit_message(&bug, &[]);
+        let subject = message.lines().next().unwrap();
+        assert!(subject.starts_with("fix: crash"));
+        assert_eq!(subject.chars().count(), SUBJECT_MAX_LEN);
+        assert!(!message.contains("\n\n\n"));
+    }
+}
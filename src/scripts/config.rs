@@ -0,0 +1,393 @@
+// src/scripts/config.rs
+//! `config get|set|show`: read and change settings of the global config
+//! (`~/.config/env-coach/config.json`) and of the `meta` section of project.json by dotted
+//! key, e.g. `llm.model`, `links.jira` or `output.verbosity`. New values are read back
+//! through the config types before anything is written, and keys are checked against the
+//! settings the types' JSON Schema lists, so a typo in a key or a value of the wrong type is
+//! an error instead of a broken file.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::config::{self, FinalLlmConfig, GlobalConfig, PartialLlmConfig, Project};
+use crate::context::AppContext;
+
+/// Which file a setting is read from or written to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scope {
+    Global,
+    Project,
+}
+
+impl Scope {
+    /// `--global` or `--local`; otherwise the project when there is one.
+    pub fn from_flags(global: bool, local: bool) -> Option<Self> {
+        match (global, local) {
+            (true, _) => Some(Scope::Global),
+            (_, true) => Some(Scope::Project),
+            _ => None,
+        }
+    }
+
+    fn file(self) -> &'static str {
+        match self {
+            Scope::Global => "the global config",
+            Scope::Project => "project.json",
+        }
+    }
+}
+
+fn pointer(key: &str) -> String {
+    format!("/{}", key.split('.').collect::<Vec<_>>().join("/"))
+}
+
+fn lookup<'a>(settings: &'a Value, key: &str) -> Option<&'a Value> {
+    settings.pointer(&pointer(key)).filter(|value| !value.is_null())
+}
+
+/// Set `key` in `settings`, creating the objects on the way (or replacing nulls with them).
+/// A null value removes the key, so optional settings and map entries are unset alike.
+fn assign(settings: &mut Value, key: &str, new_value: Value) -> Result<()> {
+    let mut current = settings;
+    let parts: Vec<&str> = key.split('.').collect();
+    for (i, part) in parts.iter().enumerate() {
+        if current.is_null() {
+            *current = Value::Object(Default::default());
+        }
+        let Some(object) = current.as_object_mut() else {
+            anyhow::bail!("'{}' is not a group of settings, so '{}' cannot be set", parts[..i].join("."), key);
+        };
+        if i + 1 == parts.len() {
+            if new_value.is_null() {
+                object.remove(*part);
+            } else {
+                object.insert(part.to_string(), new_value);
+            }
+            return Ok(());
+        }
+        current = object.entry(part.to_string()).or_insert(Value::Null);
+    }
+    Ok(())
+}
+
+/// Strings as they are, anything else as compact JSON.
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Every set leaf of `settings` as `key = value`, sorted by key.
+fn flatten(settings: &Value, prefix: &str, lines: &mut Vec<String>) {
+    match settings {
+        Value::Object(object) => {
+            for (name, value) in object {
+                let key = if prefix.is_empty() { name.clone() } else { format!("{}.{}", prefix, name) };
+                flatten(value, &key, lines);
+            }
+        }
+        Value::Null => {}
+        value => lines.push(format!("{} = {}", prefix, display(value))),
+    }
+}
+
+/// Adds the dotted key of every setting below `schema` to `keys`; map entries (like
+/// `links.<tracker>`) are listed with `*` in place of the entry name.
+fn collect_settings(schema: &Value, definitions: &Map<String, Value>, prefix: &str, keys: &mut Vec<String>) {
+    let schema = match schema.get("$ref").and_then(Value::as_str).and_then(|r| r.strip_prefix("#/definitions/")) {
+        Some(name) => match definitions.get(name) {
+            Some(definition) => definition,
+            None => return,
+        },
+        None => schema,
+    };
+    // Option<T> is `anyOf: [T, null]`, flattened structs come as `allOf`
+    for combinator in ["allOf", "anyOf", "oneOf"] {
+        for branch in schema.get(combinator).and_then(Value::as_array).into_iter().flatten() {
+            collect_settings(branch, definitions, prefix, keys);
+        }
+    }
+    let child = |name: &str| if prefix.is_empty() { name.to_string() } else { format!("{}.{}", prefix, name) };
+    for (name, property) in schema.get("properties").and_then(Value::as_object).into_iter().flatten() {
+        keys.push(child(name));
+        collect_settings(property, definitions, &child(name), keys);
+    }
+    if let Some(entries) = schema.get("additionalProperties").filter(|entries| entries.is_object()) {
+        keys.push(child("*"));
+        collect_settings(entries, definitions, &child("*"), keys);
+    }
+}
+
+/// The table of keys `config get|set` accepts for the settings type `T`.
+fn known_settings<T: JsonSchema>() -> Vec<String> {
+    let schema = serde_json::to_value(schemars::schema_for!(T)).expect("schema serializes to JSON");
+    let definitions = schema.get("definitions").and_then(Value::as_object).cloned().unwrap_or_default();
+    let mut keys = vec![];
+    collect_settings(&schema, &definitions, "", &mut keys);
+    keys
+}
+
+fn is_known(table: &[String], key: &str) -> bool {
+    let parts: Vec<&str> = key.split('.').collect();
+    table.iter().any(|known| {
+        let known: Vec<&str> = known.split('.').collect();
+        known.len() == parts.len() && known.iter().zip(&parts).all(|(k, p)| *k == "*" || k == p)
+    })
+}
+
+/// `settings` with `key` set to `text`, which is taken as JSON (numbers, booleans, lists,
+/// `null` to unset) when that fits the setting's type and as a string otherwise.
+fn with_setting<T: Serialize + DeserializeOwned + JsonSchema>(settings: &T, key: &str, text: &str) -> Result<T> {
+    if !is_known(&known_settings::<T>(), key) {
+        anyhow::bail!("Unknown setting '{}'", key);
+    }
+    let current = serde_json::to_value(settings).context("Failed to serialize settings")?;
+    let mut candidates = vec![];
+    if let Ok(parsed) = serde_json::from_str::<Value>(text) {
+        candidates.push(parsed);
+    }
+    if text != "null" {
+        candidates.push(Value::String(text.to_string()));
+    }
+
+    let mut error = None;
+    for candidate in candidates {
+        let mut edited = current.clone();
+        assign(&mut edited, key, candidate)?;
+        match serde_json::from_value::<T>(edited) {
+            Ok(updated) => return Ok(updated),
+            Err(e) => error = Some(e),
+        }
+    }
+    Err(anyhow::anyhow!("Invalid value '{}' for {}: {}", text, key, error.map(|e| e.to_string()).unwrap_or_default()))
+}
+
+/// Whether setting `key` touches `setting`: itself, a parent (`llm`) or an entry (`llm.headers.X`).
+fn affects(key: &str, setting: &str) -> bool {
+    key == setting || setting.starts_with(&format!("{}.", key)) || key.starts_with(&format!("{}.", setting))
+}
+
+/// The credential that setting `key` wrote into project.json as plain text, if any:
+/// `llm.api_key` or a proxy URL with a password. project.json is tracked in git (and
+/// committed by auto-commit), so those must be `${VAR}` references.
+fn literal_credential(llm: Option<&PartialLlmConfig>, key: &str) -> Option<&'static str> {
+    let llm = llm?;
+    let is_literal = |value: &str| !value.contains("${");
+    if llm.api_key.as_deref().is_some_and(is_literal) && affects(key, "llm.api_key") {
+        return Some("llm.api_key");
+    }
+    let proxy_password = llm.proxy.as_deref().filter(|proxy| proxy.contains('@'));
+    if proxy_password.is_some_and(is_literal) && affects(key, "llm.proxy") {
+        return Some("llm.proxy");
+    }
+    None
+}
+
+/// The LLM settings in effect, with the project's when there is one.
+fn resolved_llm(ctx: &AppContext) -> Result<(FinalLlmConfig, Option<Project>)> {
+    if Project::is_initialized() {
+        let project = ctx.load_project()?;
+        return Ok((project.llm().clone(), Some(project)));
+    }
+    let mut resolved = config::resolve_llm_config(ctx.global_config.llm.as_ref(), None);
+    resolved.apply_overrides(&ctx.llm_overrides);
+    Ok((resolved, None))
+}
+
+fn scope_settings(ctx: &AppContext, scope: Scope) -> Result<Value> {
+    match scope {
+        Scope::Global => serde_json::to_value(&ctx.global_config).context("Failed to serialize the global config"),
+        Scope::Project => {
+            let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+            serde_json::to_value(&project.meta).context("Failed to serialize project.json")
+        }
+    }
+}
+
+pub fn get(ctx: &AppContext, key: &str, scope: Option<Scope>) -> Result<()> {
+    if let Some(scope) = scope {
+        let settings = scope_settings(ctx, scope)?;
+        let value = lookup(&settings, key).ok_or_else(|| anyhow::anyhow!("'{}' is not set in {}", key, scope.file()))?;
+        println!("{}", display(value));
+        return Ok(());
+    }
+
+    // LLM settings as every command sees them: project over global over defaults
+    if let Some(field) = key.strip_prefix("llm.") {
+        let (resolved, project) = resolved_llm(ctx)?;
+        let sources = ctx.llm_setting_sources(project.as_ref(), &resolved);
+        let setting = sources.iter()
+            .find(|setting| setting.key == field)
+            .ok_or_else(|| anyhow::anyhow!("Unknown setting '{}'", key))?;
+        println!("{}", setting.value);
+        return Ok(());
+    }
+
+    let mut scopes = vec![Scope::Global];
+    if Project::is_initialized() {
+        scopes.insert(0, Scope::Project);
+    }
+    for scope in scopes {
+        if let Some(value) = lookup(&scope_settings(ctx, scope)?, key) {
+            println!("{}", display(value));
+            return Ok(());
+        }
+    }
+    anyhow::bail!("'{}' is not set", key)
+}
+
+pub fn set(ctx: &AppContext, key: &str, value: &str, scope: Option<Scope>) -> Result<()> {
+    let scope = scope.unwrap_or(if Project::is_initialized() { Scope::Project } else { Scope::Global });
+    match scope {
+        Scope::Project => {
+            let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+            project.meta = with_setting(&project.meta, key, value)?;
+            if let Some(setting) = literal_credential(project.meta.llm.as_ref(), key) {
+                anyhow::bail!(
+                    "{} would be stored in plain text in project.json, which is committed to git. \
+                     Use an environment variable reference such as '${{LLM_API_KEY}}', or set it with --global.",
+                    setting
+                );
+            }
+            if affects(key, "llm.headers") && !value.contains("${") {
+                println!("⚠️  project.json is committed to git; use a '${{VAR}}' reference for any header that holds a secret");
+            }
+            if let Some(preset) = &project.meta.preset {
+                ctx.global_config.preset(preset)?;
+            }
+            let mut resolved = config::resolve_llm_config(ctx.global_config.llm.as_ref(), project.meta.llm.as_ref());
+            resolved.apply_overrides(&ctx.llm_overrides);
+            project.resolved_llm_config = resolved;
+            project.validate()?;
+            ctx.save_project(&project)?;
+        }
+        Scope::Global => {
+            let updated = with_setting(&ctx.global_config, key, value)?;
+            config::resolve_llm_config(updated.llm.as_ref(), None).validate()?;
+            if ctx.dry_run {
+                println!("🔍 Dry run: {} not written", GlobalConfig::path()?.display());
+                return Ok(());
+            }
+            updated.save()?;
+            if let Some(project_value) = Project::is_initialized()
+                .then(|| scope_settings(ctx, Scope::Project).ok())
+                .flatten()
+                .and_then(|settings| lookup(&settings, key).cloned())
+            {
+                println!("⚠️  project.json sets {} = {}, which wins over the global config here", key, display(&project_value));
+            }
+        }
+    }
+    if ctx.dry_run {
+        return Ok(());
+    }
+    if value == "null" {
+        println!("✅ {} unset in {}", key, scope.file());
+    } else {
+        println!("✅ {} = {} in {}", key, value, scope.file());
+    }
+    Ok(())
+}
+
+pub fn show(ctx: &AppContext, resolved: bool) -> Result<()> {
+    if resolved {
+        let (llm, project) = resolved_llm(ctx)?;
+        println!("🤖 LLM settings (resolved):");
+        if let Some(preset) = ctx.active_preset(project.as_ref()) {
+            println!("   Preset: {}", preset);
+        }
+        for setting in ctx.llm_setting_sources(project.as_ref(), &llm) {
+            println!("   llm.{:<27} {:<24} (Source: {})", setting.key, setting.value, setting.source);
+        }
+        return Ok(());
+    }
+
+    let mut scopes = vec![(Scope::Global, GlobalConfig::path()?.display().to_string())];
+    if Project::is_initialized() {
+        scopes.push((Scope::Project, "project.json (meta)".to_string()));
+    }
+    for (scope, title) in scopes {
+        let mut lines = vec![];
+        flatten(&scope_settings(ctx, scope)?, "", &mut lines);
+        println!("📄 {}:", title);
+        if lines.is_empty() {
+            println!("   (nothing set)");
+        }
+        for line in lines {
+            println!("   {}", line);
+        }
+    }
+    println!("\n💡 env-coach config show --resolved shows the LLM settings in effect and where each comes from");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_credentials_are_refused_for_project_json() {
+        let llm = PartialLlmConfig {
+            api_key: Some("sk-live-SECRET123".to_string()),
+            headers: [("X-Token".to_string(), "${TOKEN}".to_string())].into_iter().collect(),
+            proxy: Some("http://user:pw@proxy.corp:3128".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(literal_credential(Some(&llm), "llm.api_key"), Some("llm.api_key"));
+        assert_eq!(literal_credential(Some(&llm), "llm.proxy"), Some("llm.proxy"));
+        assert_eq!(literal_credential(Some(&llm), "llm"), Some("llm.api_key"));
+        assert_eq!(literal_credential(Some(&llm), "llm.headers.X-Token"), None);
+        assert_eq!(literal_credential(Some(&llm), "llm.model"), None);
+
+        let referenced = PartialLlmConfig { api_key: Some("${LLM_API_KEY}".to_string()), proxy: Some("http://proxy.corp:3128".to_string()), ..Default::default() };
+        assert_eq!(literal_credential(Some(&referenced), "llm"), None);
+    }
+
+    #[test]
+    fn test_with_setting_checks_keys_and_types() {
+        let global = GlobalConfig::default();
+        let updated = with_setting(&global, "llm.model", "llama3:8b").unwrap();
+        assert_eq!(updated.llm.as_ref().unwrap().model.as_deref(), Some("llama3:8b"));
+        let updated = with_setting(&updated, "llm.timeout_ms", "60000").unwrap();
+        assert_eq!(updated.llm.as_ref().unwrap().timeout_ms, Some(60000));
+        assert_eq!(with_setting(&updated, "user", "true").unwrap().user.as_deref(), Some("true"));
+        let closing = with_setting(&updated, "auto_close_expired_sprints", "true").unwrap();
+        assert!(closing.auto_close_expired_sprints);
+        // `false` is not written out, but is still a known setting
+        assert!(!with_setting(&closing, "auto_close_expired_sprints", "false").unwrap().auto_close_expired_sprints);
+        assert_eq!(with_setting(&updated, "llm.model", "null").unwrap().llm.unwrap().model, None);
+        let linked = with_setting(&global, "links.jira", "https://acme.atlassian.net/browse/{id}").unwrap();
+        assert!(with_setting(&linked, "links.jira", "null").unwrap().links.is_empty());
+
+        assert!(with_setting(&global, "llm.timeout_ms", "soon").unwrap_err().to_string().starts_with("Invalid value 'soon' for llm.timeout_ms"));
+        assert_eq!(with_setting(&global, "llm.modle", "x").unwrap_err().to_string(), "Unknown setting 'llm.modle'");
+        assert!(with_setting(&global, "user.name", "x").is_err());
+        assert_eq!(with_setting(&global, "llm.modle", "null").unwrap_err().to_string(), "Unknown setting 'llm.modle'");
+    }
+
+    #[test]
+    fn test_known_settings_come_from_the_schema() {
+        let table = known_settings::<GlobalConfig>();
+        assert!(is_known(&table, "auto_close_expired_sprints"));
+        assert!(is_known(&table, "llm.model"));
+        assert!(is_known(&table, "links.jira"));
+        assert!(is_known(&table, "output.verbosity"));
+        assert!(!is_known(&table, "llm.model.name"));
+        assert!(!is_known(&table, "output.colour"));
+        assert!(is_known(&known_settings::<config::ProjectMeta>(), "prd.problem"));
+    }
+
+    #[test]
+    fn test_lookup_and_flatten() {
+        let settings = serde_json::json!({"llm": {"model": "m", "port": 11434, "host": null}, "links": {}, "user": "ana"});
+        assert_eq!(lookup(&settings, "llm.port"), Some(&serde_json::json!(11434)));
+        assert_eq!(lookup(&settings, "llm.host"), None);
+        let mut lines = vec![];
+        flatten(&settings, "", &mut lines);
+        assert_eq!(lines, vec!["llm.model = m", "llm.port = 11434", "user = ana"]);
+    }
+}
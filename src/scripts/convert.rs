@@ -0,0 +1,97 @@
+// src/scripts/convert.rs
+//! `convert <json|toml> [--global]`: rewrite project.json as project.toml (or back), or the
+//! global config.json as config.toml. The new file is read back before the old one is
+//! removed, and the project's two files are journaled like any other save.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::config::{FileFormat, GlobalConfig, Project};
+use crate::context::AppContext;
+use crate::recovery;
+
+/// `value` in `format`, checked to read back as the same type.
+fn converted<T: Serialize + DeserializeOwned>(value: &T, format: FileFormat, path: &Path) -> Result<String> {
+    let content = format.serialize(value)
+        .with_context(|| format!("Failed to write {} as {}", path.display(), format.extension()))?;
+    format.parse::<T>(&content)
+        .with_context(|| format!("{} would not read back after conversion", path.display()))?;
+    Ok(content)
+}
+
+/// Write `content` to `to` and remove `from`.
+fn replace_file(from: &Path, to: &Path, content: &str) -> Result<()> {
+    std::fs::write(to, content).with_context(|| format!("Failed to write {}", to.display()))?;
+    std::fs::remove_file(from).with_context(|| format!("Failed to remove {}", from.display()))
+}
+
+pub fn run(ctx: &AppContext, format: FileFormat, global: bool) -> Result<()> {
+    let from = if global {
+        GlobalConfig::path()?
+    } else {
+        if !Project::is_initialized() {
+            anyhow::bail!("No project.json or project.toml here. Run 'env-coach init' first.");
+        }
+        Project::file()?
+    };
+    if FileFormat::of(&from) == format {
+        println!("ℹ️  {} is already {}", from.display(), format.extension());
+        return Ok(());
+    }
+    let to: PathBuf = from.with_extension(format.extension());
+
+    if global {
+        if !from.exists() {
+            anyhow::bail!("There is no global config at {} yet; 'env-coach config set --global' creates one", from.display());
+        }
+        let content = converted(&GlobalConfig::load()?, format, &from)?;
+        if ctx.dry_run {
+            println!("🔍 Dry run: {} not written", to.display());
+            return Ok(());
+        }
+        replace_file(&from, &to, &content)?;
+    } else {
+        let content = converted(&Project::read_file(&from)?, format, &from)?;
+        if ctx.dry_run {
+            println!("🔍 Dry run: {} not written", to.display());
+            return Ok(());
+        }
+        let root = std::env::current_dir().context("Failed to determine project directory")?;
+        let transaction = recovery::Transaction::begin(&root, &[from.clone(), to.clone()])?;
+        replace_file(&from, &to, &content)?;
+        transaction.commit()?;
+    }
+    println!("✅ Converted {} to {}", from.display(), to.display());
+    if !global {
+        println!("💡 Fields env-coach does not know are not carried over; 'env-coach validate' lists any before converting back");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BacklogItem, Priority};
+
+    #[test]
+    fn test_project_round_trips_through_toml() {
+        let mut project = Project::new("Shop".to_string(), "Online shop".to_string(), None);
+        project.meta.links.insert("jira".to_string(), "https://acme.atlassian.net/browse/{id}".to_string());
+        project.backlog.push(BacklogItem::test("US-001")
+            .with_title("Pay by card")
+            .with_story("As a buyer I want to pay by card")
+            .with_criteria(&["Visa and Mastercard work"])
+            .with_priority(Priority::High)
+            .with_tags(&["payments"]));
+
+        let toml = converted(&project, FileFormat::Toml, Path::new("project.json")).unwrap();
+        assert!(toml.contains("[[backlog]]"));
+        let back: Project = FileFormat::Toml.parse(&toml).unwrap();
+        assert_eq!(
+            FileFormat::Json.serialize(&back).unwrap(),
+            FileFormat::Json.serialize(&project).unwrap()
+        );
+    }
+}
@@ -0,0 +1,165 @@
+// src/scripts/criteria_check.rs
+//! `complete-task --verify`: the LLM checks each acceptance criterion of an item against
+//! the changes made for it and the test results, giving a pass/fail checklist.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::auto_update::code_gen::get_primary_language;
+use crate::config::{BacklogItem, CriteriaCheck, CriterionCheck, LlmRole, Project};
+use crate::context::AppContext;
+use crate::git;
+use crate::ollama;
+use crate::prompts;
+use crate::scripts::fix_tests;
+use crate::templates::Templates;
+
+/// Size kept of the diff sent to the LLM; the start of the work is kept.
+const MAX_DIFF_CHARS: usize = 12000;
+
+#[derive(Debug, Deserialize)]
+struct LlmCriterionCheck {
+    /// 1-based criterion number
+    index: usize,
+    passed: bool,
+    #[serde(default)]
+    evidence: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmCriteriaCheck {
+    criteria: Vec<LlmCriterionCheck>,
+}
+
+/// The checklist for `criteria`; a criterion the LLM did not answer for fails.
+pub(crate) fn parse_criteria_response(response: &str, criteria: &[String]) -> Result<CriteriaCheck> {
+    let parsed: LlmCriteriaCheck = serde_json::from_str(response.trim())
+        .with_context(|| format!("The LLM did not return a criteria checklist: {}", response.chars().take(300).collect::<String>()))?;
+    let checks = criteria.iter()
+        .enumerate()
+        .map(|(i, criterion)| match parsed.criteria.iter().find(|check| check.index == i + 1) {
+            Some(check) => CriterionCheck { criterion: criterion.clone(), passed: check.passed, evidence: check.evidence.clone() },
+            None => CriterionCheck { criterion: criterion.clone(), passed: false, evidence: "Not assessed by the LLM.".to_string() },
+        })
+        .collect();
+    Ok(CriteriaCheck { checked_at: Utc::now(), checks })
+}
+
+fn build_criteria_checker_prompt(template: &str, project: &Project, item: &BacklogItem, diff: &str, test_results: &str) -> String {
+    let criteria = item.acceptance_criteria.iter()
+        .enumerate()
+        .map(|(i, criterion)| format!("{}. {}", i + 1, criterion))
+        .collect::<Vec<_>>()
+        .join("\n");
+    template
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{tech_stack}}", &project.meta.tech_stack.join(", "))
+        .replace("{{task_id}}", &item.id)
+        .replace("{{task_title}}", &item.title)
+        .replace("{{task_story}}", &item.story)
+        .replace("{{extra}}", &item.extra_display())
+        .replace("{{acceptance_criteria}}", &criteria)
+        .replace("{{diff}}", diff)
+        .replace("{{test_results}}", test_results)
+}
+
+pub(crate) fn print_checklist(check: &CriteriaCheck) {
+    for check in &check.checks {
+        println!("   {} {}", if check.passed { "✅" } else { "❌" }, check.criterion);
+        if !check.evidence.is_empty() {
+            println!("      {}", check.evidence);
+        }
+    }
+}
+
+/// Ask the LLM to check the item's acceptance criteria against its diff (commits that
+/// mention the ID and uncommitted changes) and the test results.
+pub(crate) async fn check_criteria(project: &Project, item: &BacklogItem, root: &Path) -> Result<CriteriaCheck> {
+    let diff = git::diff_for(&item.id).unwrap_or_default();
+    let diff = match diff.trim().is_empty() {
+        true => "No changes found for this item.".to_string(),
+        false => diff.chars().take(MAX_DIFF_CHARS).collect(),
+    };
+    let test_results = match fix_tests::detect_test_command(root) {
+        Some(command) => {
+            let (passed, output) = fix_tests::run_tests(command)?;
+            println!("{} {}", if passed { "✅" } else { "❌" }, command);
+            format!("`{}` {}:\n{}", command, if passed { "passed" } else { "failed" }, fix_tests::tail(&output, fix_tests::MAX_OUTPUT_CHARS))
+        }
+        None => "No test command detected for this project.".to_string(),
+    };
+
+    let prompt_template_path = Path::new(".env-coach/prompts/criteria_checker.md");
+    let prompt_template = match prompts::read_template(prompt_template_path, &get_primary_language(&project.meta)) {
+        Some(template) => template,
+        None => {
+            println!("⚠️ Criteria checker prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_criteria_checker_prompt_content()
+        }
+    };
+    let prompt_template = prompts::fill_project_context(&prompt_template, project, Some(item));
+    let prompt = build_criteria_checker_prompt(&prompt_template, project, item, &diff, &test_results);
+
+    println!("🔍 Asking LLM to check {} acceptance criteria of {}...", item.acceptance_criteria.len(), item.id);
+    let response = ollama::send_generation_prompt(&project.llm_for(LlmRole::Reviewer), &prompt)
+        .await
+        .context("Failed to get the criteria check from LLM")?;
+    parse_criteria_response(&response, &item.acceptance_criteria)
+}
+
+/// Check the criteria and record the checklist on the item. Returns whether the item may
+/// be completed: every criterion passed, or the user chose to complete it anyway.
+pub(crate) async fn verify_before_completion(ctx: &AppContext, project: &mut Project, id: &str) -> Result<bool> {
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let item = project.backlog.iter()
+        .find(|item| item.id == id)
+        .ok_or_else(|| anyhow::anyhow!("Task '{}' not found", id))?;
+    if item.acceptance_criteria.is_empty() {
+        println!("ℹ️ {} has no acceptance criteria to verify", id);
+        return Ok(true);
+    }
+
+    let check = check_criteria(project, item, &root).await?;
+    println!();
+    println!("📋 Acceptance criteria of {}:", id);
+    print_checklist(&check);
+    let passed = check.passed();
+    let failed = check.checks.iter().filter(|check| !check.passed).count();
+    let total = check.checks.len();
+    if let Some(item) = project.backlog.iter_mut().find(|item| item.id == id) {
+        item.criteria_check = Some(check);
+    }
+    if passed {
+        return Ok(true);
+    }
+    println!();
+    println!("⚠️ {} of {} criteria not met", failed, total);
+    ctx.prompter.confirm(&format!("👉 Mark {} as Done anyway?", id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_criteria_response() {
+        let criteria = vec!["Wrong passwords are rejected".to_string(), "Accounts lock after 5 tries".to_string(), "Logins are audited".to_string()];
+        let response = r#"{"criteria": [
+            {"index": 2, "passed": false, "evidence": "No lockout code."},
+            {"index": 1, "passed": true, "evidence": "test_rejects_wrong_password passes."}
+        ]}"#;
+        let check = parse_criteria_response(response, &criteria).unwrap();
+        let verdicts: Vec<(&str, bool)> = check.checks.iter().map(|c| (c.criterion.as_str(), c.passed)).collect();
+        assert_eq!(verdicts, vec![
+            ("Wrong passwords are rejected", true),
+            ("Accounts lock after 5 tries", false),
+            ("Logins are audited", false),
+        ]);
+        assert_eq!(check.checks[2].evidence, "Not assessed by the LLM.");
+        assert!(!check.passed());
+        assert!(parse_criteria_response("All good!", &criteria).is_err());
+    }
+}
@@ -0,0 +1,309 @@
+// src/scripts/doctor.rs
+//! `doctor`: check everything env-coach depends on — project.json, the prompt templates,
+//! the LLM server and its models, git and the project's manifests — and say how to fix
+//! each problem found. Exits with an error when something is broken, so it can run in CI.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::config::{self, FinalLlmConfig, Project};
+use crate::context::AppContext;
+use crate::git;
+use crate::ollama;
+use crate::prompts::{self, UpgradeStatus, DEFAULT_PROMPTS};
+use crate::scripts::validate;
+
+const PROMPTS_DIR: &str = ".env-coach/prompts";
+
+/// Manifests `doctor` can read, with the `tech_stack` entry each one implies.
+const MANIFESTS: &[(&str, &str)] = &[
+    ("Cargo.toml", "rust"),
+    ("package.json", "nodejs"),
+    ("pyproject.toml", "python"),
+    ("go.mod", "go"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Outcome {
+    Pass,
+    /// Works, but probably not as intended
+    Warning,
+    Failure,
+}
+
+#[derive(Debug, PartialEq)]
+struct Check {
+    outcome: Outcome,
+    subject: String,
+    detail: String,
+    /// What to do about a warning or failure
+    fix: Option<String>,
+}
+
+impl Check {
+    fn pass(subject: &str, detail: String) -> Self {
+        Check { outcome: Outcome::Pass, subject: subject.to_string(), detail, fix: None }
+    }
+
+    fn warning(subject: &str, detail: String, fix: String) -> Self {
+        Check { outcome: Outcome::Warning, subject: subject.to_string(), detail, fix: Some(fix) }
+    }
+
+    fn failure(subject: &str, detail: String, fix: String) -> Self {
+        Check { outcome: Outcome::Failure, subject: subject.to_string(), detail, fix: Some(fix) }
+    }
+
+    fn print(&self) {
+        let icon = match self.outcome {
+            Outcome::Pass => "✅",
+            Outcome::Warning => "⚠️ ",
+            Outcome::Failure => "❌",
+        };
+        println!("{} {}: {}", icon, self.subject, self.detail);
+        if let Some(fix) = &self.fix {
+            println!("   💡 {}", fix);
+        }
+    }
+}
+
+/// project.json parses, passes `validate` and holds a usable configuration. The project is
+/// returned when it loads, for the checks that need it.
+fn check_project(ctx: &AppContext) -> (Check, Option<Project>) {
+    if !Project::is_initialized() {
+        return (Check::failure("project.json", "not found in this directory".to_string(),
+            "Run 'env-coach init' here, or pass --project <dir>".to_string()), None);
+    }
+    let path = match Project::file() {
+        Ok(path) => path,
+        Err(e) => return (Check::failure("project.json", e.to_string(), "Delete or rename one of them".to_string()), None),
+    };
+    let mut document = match validate::read_document(&path) {
+        Ok((document, _)) => document,
+        Err(e) => return (Check::failure("project.json", e.to_string(),
+            format!("Fix the syntax there, or bring back a backup with 'env-coach restore {} --list'", path.display())), None),
+    };
+    if let Err(e) = config::migrate_project(&mut document) {
+        return (Check::failure("project.json", e.to_string(), "Install the latest env-coach".to_string()), None);
+    }
+    let project = match ctx.load_project().and_then(|project| project.validate().map(|_| project)) {
+        Ok(project) => project,
+        Err(e) => return (Check::failure("project.json", format!("{:#}", e),
+            "Run 'env-coach validate' for the exact places to fix".to_string()), None),
+    };
+    let check = match validate::problem_counts(ctx, &document) {
+        (0, 0) => Check::pass("project.json", format!("valid ({} backlog item(s), {} sprint(s))", project.backlog.len(), project.sprints.len())),
+        (0, warnings) => Check::warning("project.json", format!("{} warning(s), e.g. misspelled fields that are ignored", warnings),
+            "Run 'env-coach validate' to see them".to_string()),
+        (errors, _) => Check::failure("project.json", format!("{} schema error(s)", errors),
+            "Run 'env-coach validate' for the exact places to fix".to_string()),
+    };
+    (check, Some(project))
+}
+
+/// The prompt templates exist and none is behind the defaults of this version.
+fn check_prompts(prompts_dir: &Path) -> Check {
+    if !prompts_dir.is_dir() {
+        return Check::failure("Prompts", format!("{} not found", PROMPTS_DIR),
+            "Run 'env-coach prompts upgrade' to install the default prompts".to_string());
+    }
+    let mut missing = vec![];
+    let mut outdated = vec![];
+    for prompt in DEFAULT_PROMPTS {
+        let current = std::fs::read_to_string(prompts_dir.join(prompt.file_name)).ok();
+        let base = prompts::installed_base(prompts_dir, prompt.file_name);
+        match prompts::upgrade_status(current.as_deref(), base.as_deref(), &(prompt.content)()) {
+            UpgradeStatus::UpToDate | UpgradeStatus::Customized => {}
+            UpgradeStatus::Missing => missing.push(prompt.file_name),
+            _ => outdated.push(prompt.file_name),
+        }
+    }
+    match (missing.is_empty(), outdated.is_empty()) {
+        (true, true) => Check::pass("Prompts", format!("all {} templates are current", DEFAULT_PROMPTS.len())),
+        (false, _) => Check::failure("Prompts", format!("missing {}", missing.join(", ")),
+            "Run 'env-coach prompts upgrade' to create them".to_string()),
+        (true, false) => Check::warning("Prompts", format!("{} behind the defaults of this version", outdated.join(", ")),
+            "Run 'env-coach prompts upgrade' (your edits are kept or merged)".to_string()),
+    }
+}
+
+/// Whether `model` is one of the `installed` names; an untagged model means `:latest`.
+fn model_installed(model: &str, installed: &[String]) -> bool {
+    installed.iter().any(|name| name == model || (!model.contains(':') && *name == format!("{}:latest", model)))
+}
+
+/// The LLM server answers and has the base model and every role model.
+async fn check_llm(llm: &FinalLlmConfig) -> Vec<Check> {
+    if let Err(e) = llm.validate() {
+        return vec![Check::failure("LLM config", e.to_string(), "Fix it with 'env-coach config set llm.<key> <value>'".to_string())];
+    }
+    let installed = match ollama::list_models(llm).await {
+        Ok(installed) => installed,
+        Err(e) => return vec![Check::failure("Ollama", format!("not reachable at {} ({:#})", llm.base_url(), e),
+            "Start it with 'ollama serve', or point env-coach at it with 'env-coach config set llm.host <host>' and llm.port".to_string())],
+    };
+    let mut checks = vec![Check::pass("Ollama", format!("reachable at {} ({} model(s) installed)", llm.base_url(), installed.len()))];
+    let mut models = vec![llm.model.clone()];
+    for model in llm.roles.values() {
+        if !models.contains(model) {
+            models.push(model.clone());
+        }
+    }
+    for model in models {
+        checks.push(if model_installed(&model, &installed) {
+            Check::pass("Model", format!("{} is installed", model))
+        } else {
+            Check::failure("Model", format!("{} is not installed on the server", model),
+                format!("Run 'ollama pull {}' on the Ollama server, or pick an installed one with 'env-coach config set llm.model <name>'", model))
+        });
+    }
+    checks
+}
+
+fn check_git() -> Check {
+    match git::run(&["rev-parse", "--show-toplevel"]) {
+        Ok(top) => Check::pass("Git", format!("repository at {}", top.trim())),
+        Err(e) => Check::warning("Git", format!("{:#}", e),
+            "Run 'git init': sync-git, review-task and task diffs need a repository".to_string()),
+    }
+}
+
+/// The manifests under `root` parse, and `tech_stack` names the languages they imply and no
+/// language whose manifest is missing.
+fn check_manifests(root: &Path, tech_stack: &[String]) -> Vec<Check> {
+    let present = |file: &str| root.join(file).is_file();
+    let mut suggested: Vec<String> = tech_stack.iter()
+        .filter(|entry| MANIFESTS.iter().all(|(file, language)| *language != entry.as_str() || present(file)))
+        .cloned()
+        .collect();
+    for (file, language) in MANIFESTS {
+        if present(file) && !suggested.iter().any(|entry| entry == language) {
+            suggested.push(language.to_string());
+        }
+    }
+    let fix = format!("Run 'env-coach config set tech_stack '{}''", serde_json::to_string(&suggested).unwrap_or_default());
+
+    let mut checks = vec![];
+    for (file, language) in MANIFESTS {
+        let listed = tech_stack.iter().any(|entry| entry == language);
+        if !present(file) {
+            if listed {
+                checks.push(Check::warning(file, format!("tech_stack lists {} but there is no {}", language, file), fix.clone()));
+            }
+            continue;
+        }
+        let content = std::fs::read_to_string(root.join(file)).unwrap_or_default();
+        let parsed = match *file {
+            "Cargo.toml" | "pyproject.toml" => toml::from_str::<toml::Value>(&content).map(|_| ()).map_err(|e| e.to_string()),
+            "package.json" => serde_json::from_str::<serde_json::Value>(&content).map(|_| ()).map_err(|e| e.to_string()),
+            _ => Ok(()),
+        };
+        checks.push(match parsed {
+            Err(e) => Check::failure(file, format!("does not parse: {}", e.lines().next().unwrap_or_default()),
+                format!("Fix {}; the build and the codebase context rely on it", file)),
+            Ok(()) if !listed => Check::warning(file, format!("found, but tech_stack does not list {}", language), fix.clone()),
+            Ok(()) => Check::pass(file, format!("parses, and tech_stack lists {}", language)),
+        });
+    }
+    checks
+}
+
+pub async fn run(ctx: &AppContext) -> Result<()> {
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    println!("🩺 Checking the env-coach setup in {}\n", root.display());
+
+    let mut checks = vec![];
+    let (project_check, project) = check_project(ctx);
+    project_check.print();
+    checks.push(project_check);
+
+    let mut report = |new_checks: Vec<Check>| {
+        for check in new_checks {
+            check.print();
+            checks.push(check);
+        }
+    };
+    report(vec![check_prompts(&root.join(PROMPTS_DIR))]);
+    let llm = match &project {
+        Some(project) => project.llm().clone(),
+        None => {
+            let mut llm = config::resolve_llm_config(ctx.global_config.llm.as_ref(), None);
+            llm.apply_overrides(&ctx.llm_overrides);
+            llm
+        }
+    };
+    report(check_llm(&llm).await);
+    report(vec![check_git()]);
+    if let Some(project) = &project {
+        report(check_manifests(&root, &project.meta.tech_stack));
+    }
+
+    let failures = checks.iter().filter(|check| check.outcome == Outcome::Failure).count();
+    let warnings = checks.iter().filter(|check| check.outcome == Outcome::Warning).count();
+    println!();
+    if failures == 0 && warnings == 0 {
+        println!("🎉 Everything looks healthy");
+        return Ok(());
+    }
+    println!("📊 {} problem(s), {} warning(s)", failures, warnings);
+    if failures > 0 {
+        anyhow::bail!("doctor found {} problem(s)", failures);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_prompts() {
+        let dir = tempfile::tempdir().unwrap();
+        let prompts_dir = dir.path().join("prompts");
+        assert_eq!(check_prompts(&prompts_dir).outcome, Outcome::Failure);
+
+        std::fs::create_dir_all(&prompts_dir).unwrap();
+        for prompt in DEFAULT_PROMPTS {
+            prompts::install_default(&prompts_dir, prompt).unwrap();
+        }
+        assert_eq!(check_prompts(&prompts_dir).outcome, Outcome::Pass);
+
+        // A customized prompt is fine; one installed from an older default is behind
+        std::fs::write(prompts_dir.join("chat.md"), "My own chat prompt").unwrap();
+        assert_eq!(check_prompts(&prompts_dir).outcome, Outcome::Pass);
+        std::fs::write(prompts_dir.join(".defaults/sprint_goal.md"), "Old default").unwrap();
+        std::fs::write(prompts_dir.join("sprint_goal.md"), "Old default").unwrap();
+        let check = check_prompts(&prompts_dir);
+        assert_eq!((check.outcome, check.detail.as_str()), (Outcome::Warning, "sprint_goal.md behind the defaults of this version"));
+    }
+
+    #[test]
+    fn test_model_installed() {
+        let installed = vec!["llama3:latest".to_string(), "qwen2.5-coder:7b".to_string()];
+        assert!(model_installed("llama3", &installed));
+        assert!(model_installed("qwen2.5-coder:7b", &installed));
+        assert!(!model_installed("qwen2.5-coder", &installed));
+        assert!(!model_installed("llama3:8b", &installed));
+    }
+
+    #[test]
+    fn test_check_manifests() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        std::fs::write(root.join("package.json"), "{ \"name\": ").unwrap();
+        let tech_stack = vec!["python".to_string(), "git".to_string()];
+
+        let checks = check_manifests(root, &tech_stack);
+        let summary: Vec<(&str, Outcome)> = checks.iter().map(|check| (check.subject.as_str(), check.outcome)).collect();
+        assert_eq!(summary, vec![
+            ("Cargo.toml", Outcome::Warning),
+            ("package.json", Outcome::Failure),
+            ("pyproject.toml", Outcome::Warning),
+        ]);
+        assert_eq!(checks[0].fix.as_deref(), Some("Run 'env-coach config set tech_stack '[\"git\",\"rust\",\"nodejs\"]''"));
+
+        let fixed: Vec<String> = ["git", "rust", "nodejs"].iter().map(|s| s.to_string()).collect();
+        std::fs::write(root.join("package.json"), "{ \"name\": \"demo\" }").unwrap();
+        assert!(check_manifests(root, &fixed).iter().all(|check| check.outcome == Outcome::Pass));
+    }
+}
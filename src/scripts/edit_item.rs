@@ -0,0 +1,374 @@
+// src/scripts/edit_item.rs
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use crate::config::{BacklogItem, ItemType, Priority, Project};
+use crate::context::AppContext;
+use crate::hints;
+use crate::scripts::set_field::resync_sprint_points;
+
+/// Field changes requested on the command line.
+#[derive(Debug, Default)]
+pub struct ItemEdits {
+    pub title: Option<String>,
+    pub story: Option<String>,
+    pub effort: Option<u32>,
+    pub priority: Option<Priority>,
+    pub component: Option<String>,
+    /// Epic to attach the item to (empty string detaches it)
+    pub epic: Option<String>,
+    /// Issue in an external tracker, e.g. `github:42` (empty string clears it)
+    pub external_ref: Option<String>,
+    pub add_ac: Vec<String>,
+    /// 1-based acceptance criterion numbers, as shown by `show-item`/`list-backlog`
+    pub remove_ac: Vec<usize>,
+    /// Custom fields as `key=value`; the value is read as JSON when it parses, `null` removes the field
+    pub fields: Vec<String>,
+}
+
+impl ItemEdits {
+    fn is_empty(&self) -> bool {
+        self.title.is_none() && self.story.is_none() && self.effort.is_none() && self.priority.is_none()
+            && self.component.is_none() && self.epic.is_none() && self.external_ref.is_none() && self.add_ac.is_empty() && self.remove_ac.is_empty()
+            && self.fields.is_empty()
+    }
+}
+
+/// The part of a backlog item that can be edited in `$EDITOR`. IDs, status, sprint and
+/// timestamps are managed by other commands and stay out of the file.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct EditableItem {
+    title: String,
+    story: String,
+    priority: Priority,
+    effort: u32,
+    #[serde(default)]
+    acceptance_criteria: Vec<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    component: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    epic: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    external_ref: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl EditableItem {
+    fn from_item(item: &BacklogItem) -> Self {
+        Self {
+            title: item.title.clone(),
+            story: item.story.clone(),
+            priority: item.priority.clone(),
+            effort: item.effort,
+            acceptance_criteria: item.acceptance_criteria.clone(),
+            dependencies: item.dependencies.clone(),
+            component: item.component.clone(),
+            epic: item.parent.clone(),
+            tags: item.tags.clone(),
+            external_ref: item.external_ref.clone(),
+            extra: item.extra.clone(),
+        }
+    }
+
+    fn apply_to(self, item: &mut BacklogItem) {
+        item.title = self.title;
+        item.story = self.story;
+        item.priority = self.priority;
+        item.effort = self.effort;
+        item.acceptance_criteria = self.acceptance_criteria;
+        item.dependencies = self.dependencies;
+        item.component = self.component.filter(|c| !c.trim().is_empty());
+        item.parent = self.epic.filter(|e| !e.trim().is_empty());
+        item.tags = self.tags.into_iter().filter(|t| !t.trim().is_empty()).collect();
+        item.external_ref = self.external_ref.filter(|r| !r.trim().is_empty());
+        item.extra = self.extra;
+    }
+}
+
+/// Reject edits that would leave the item unusable for planning.
+pub(crate) fn validate_item(project: &Project, item: &BacklogItem) -> Result<()> {
+    if item.title.trim().is_empty() {
+        anyhow::bail!("Title cannot be empty");
+    }
+    if item.story.trim().is_empty() {
+        anyhow::bail!("Story cannot be empty");
+    }
+    // Epics are sized by their children and carry no effort of their own
+    let effort_range = if matches!(item.item_type, ItemType::Epic) { 0..=100 } else { 1..=100 };
+    if !effort_range.contains(&item.effort) {
+        anyhow::bail!("Effort must be between 1 and 100 points (got {})", item.effort);
+    }
+    if let Some(parent) = &item.parent {
+        if parent == &item.id {
+            anyhow::bail!("'{}' cannot be its own epic", item.id);
+        }
+        // Tasks from `breakdown` belong to the story they were split from
+        match item.item_type {
+            ItemType::Task if project.backlog.iter().any(|other| &other.id == parent) => {}
+            _ => crate::scripts::epics::require_epic(project, parent)?,
+        }
+    }
+    for dependency in &item.dependencies {
+        if dependency == &item.id {
+            anyhow::bail!("'{}' cannot depend on itself", item.id);
+        }
+        if !project.backlog.iter().any(|other| &other.id == dependency) {
+            anyhow::bail!("Dependency '{}' is not in the backlog", dependency);
+        }
+    }
+    Ok(())
+}
+
+/// Apply command-line edits, returning a description of each change.
+fn apply_edits(item: &mut BacklogItem, edits: ItemEdits) -> Result<Vec<String>> {
+    let mut changes = Vec::new();
+    if let Some(title) = edits.title {
+        changes.push(format!("title: '{}' → '{}'", item.title, title));
+        item.title = title;
+    }
+    if let Some(story) = edits.story {
+        changes.push("story updated".to_string());
+        item.story = story;
+    }
+    if let Some(effort) = edits.effort {
+        changes.push(format!("effort: {} → {}", item.effort, effort));
+        item.effort = effort;
+    }
+    if let Some(priority) = edits.priority {
+        changes.push(format!("priority: {:?} → {:?}", item.priority, priority));
+        item.priority = priority;
+    }
+    if let Some(component) = edits.component {
+        changes.push(format!("component: {}", component));
+        item.component = Some(component).filter(|c| !c.trim().is_empty());
+    }
+    if let Some(epic) = edits.epic {
+        changes.push(format!("epic: {}", if epic.trim().is_empty() { "none" } else { &epic }));
+        item.parent = Some(epic).filter(|e| !e.trim().is_empty());
+    }
+    if let Some(external_ref) = edits.external_ref {
+        changes.push(format!("external ref: {}", if external_ref.trim().is_empty() { "none" } else { &external_ref }));
+        item.external_ref = Some(external_ref).filter(|r| !r.trim().is_empty());
+    }
+
+    // Remove from the highest number down so earlier removals don't shift later ones
+    let mut remove = edits.remove_ac;
+    remove.sort_unstable();
+    remove.dedup();
+    for n in remove.into_iter().rev() {
+        if n == 0 || n > item.acceptance_criteria.len() {
+            anyhow::bail!("'{}' has no acceptance criterion #{} (it has {})", item.id, n, item.acceptance_criteria.len());
+        }
+        let removed = item.acceptance_criteria.remove(n - 1);
+        changes.push(format!("removed AC #{}: {}", n, removed));
+    }
+    for criterion in edits.add_ac {
+        changes.push(format!("added AC: {}", criterion));
+        item.acceptance_criteria.push(criterion);
+    }
+    for spec in edits.fields {
+        let (key, value) = parse_field(&spec)?;
+        if value.is_null() {
+            if item.extra.remove(&key).is_some() {
+                changes.push(format!("removed field {}", key));
+            }
+        } else {
+            changes.push(format!("{}: {}", key, value));
+            item.extra.insert(key, value);
+        }
+    }
+    Ok(changes)
+}
+
+/// `key=value` as a custom field. The value is JSON when it parses as such (`3`, `true`,
+/// `["a","b"]`) and plain text otherwise.
+fn parse_field(spec: &str) -> Result<(String, serde_json::Value)> {
+    let (key, value) = spec.split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("--field expects key=value (got '{}')", spec))?;
+    let key = key.trim();
+    if key.is_empty() {
+        anyhow::bail!("--field needs a name before '=' (got '{}')", spec);
+    }
+    let value = serde_json::from_str(value.trim()).unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+    Ok((key.to_string(), value))
+}
+
+pub(crate) fn editor_command() -> String {
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string())
+}
+
+/// Open the item as TOML in `$EDITOR` until it parses and validates, or the user gives up.
+/// Returns `None` when the file was left unchanged or editing was abandoned.
+fn edit_in_editor(ctx: &AppContext, project: &Project, item: &BacklogItem) -> Result<Option<EditableItem>> {
+    let original = EditableItem::from_item(item);
+    let path = std::env::temp_dir().join(format!("env-coach-{}.toml", item.id));
+    let header = format!("# Editing {} — save and close the editor to apply. ID, status and sprint are not editable here.\n", item.id);
+    let content = toml::to_string_pretty(&original).context("Failed to serialize item as TOML")?;
+    std::fs::write(&path, format!("{}{}", header, content)).with_context(|| format!("Failed to write {:?}", path))?;
+
+    let editor = editor_command();
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let result = loop {
+        let status = Command::new(program)
+            .args(parts.clone())
+            .arg(&path)
+            .status()
+            .with_context(|| format!("Failed to launch editor '{}'. Set $EDITOR.", editor))?;
+        if !status.success() {
+            break Err(anyhow::anyhow!("Editor '{}' exited with {}", editor, status));
+        }
+
+        let edited = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        let checked = toml::from_str::<EditableItem>(&edited)
+            .map_err(anyhow::Error::from)
+            .and_then(|parsed| {
+                let mut candidate = item.clone();
+                parsed.apply_to(&mut candidate);
+                validate_item(project, &candidate)?;
+                Ok(EditableItem::from_item(&candidate))
+            });
+        match checked {
+            Ok(parsed) if parsed == original => break Ok(None),
+            Ok(parsed) => break Ok(Some(parsed)),
+            Err(e) => {
+                println!("❌ {}", e);
+                // With --yes the same invalid file would be reopened forever
+                if ctx.prompter.assume_yes() || !ctx.prompter.confirm("👉 Re-open the editor to fix it?")? {
+                    break Ok(None);
+                }
+            }
+        }
+    };
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+pub fn run(ctx: &AppContext, item_id: String, edits: ItemEdits, editor: bool) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let item = project.backlog.iter()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| anyhow::anyhow!("Backlog item '{}' not found", item_id))?
+        .clone();
+
+    if editor && !edits.is_empty() {
+        anyhow::bail!("--editor cannot be combined with field flags");
+    }
+    if !editor && edits.is_empty() {
+        anyhow::bail!("Nothing to change. Pass field flags (e.g. --title, --effort) or --editor.");
+    }
+
+    let mut updated = item.clone();
+    let changes = if editor {
+        match edit_in_editor(ctx, &project, &item)? {
+            Some(edited) => {
+                edited.apply_to(&mut updated);
+                vec!["updated in editor".to_string()]
+            }
+            None => {
+                println!("ℹ️ No changes made to {}", item_id);
+                return Ok(());
+            }
+        }
+    } else {
+        let changes = apply_edits(&mut updated, edits)?;
+        validate_item(&project, &updated)?;
+        changes
+    };
+
+    resync_sprint_points(&mut project, &item, &updated);
+    if let Some(slot) = project.backlog.iter_mut().find(|i| i.id == item_id) {
+        *slot = updated;
+    }
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    println!("✏️  Updated {}:", item_id);
+    for change in changes {
+        println!("   - {}", change);
+    }
+    hints::print_next_steps(Some(&project), &[("env-coach list-backlog", "Review the backlog")]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item() -> BacklogItem {
+        BacklogItem::test("US-001").with_title("Login").with_story("As a user I want to log in").with_criteria(&["a", "b", "c"])
+    }
+
+    #[test]
+    fn test_apply_edits_removes_then_adds_criteria() {
+        let mut edited = item();
+        let edits = ItemEdits {
+            effort: Some(5),
+            priority: Some(Priority::High),
+            remove_ac: vec![1, 3],
+            add_ac: vec!["d".to_string()],
+            ..Default::default()
+        };
+        let changes = apply_edits(&mut edited, edits).unwrap();
+        assert_eq!(edited.acceptance_criteria, vec!["b", "d"]);
+        assert_eq!(edited.effort, 5);
+        assert_eq!(edited.priority, Priority::High);
+        assert_eq!(changes.len(), 5);
+
+        let mut edited = item();
+        assert!(apply_edits(&mut edited, ItemEdits { remove_ac: vec![4], ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn test_fields_are_set_as_json_or_text_and_removed_with_null() {
+        let mut edited = item();
+        let fields = |specs: &[&str]| ItemEdits { fields: specs.iter().map(|s| s.to_string()).collect(), ..Default::default() };
+        apply_edits(&mut edited, fields(&["customer=Acme Corp", "severity=2", "labels=[\"a\",\"b\"]"])).unwrap();
+        assert_eq!(edited.extra["customer"], serde_json::json!("Acme Corp"));
+        assert_eq!(edited.extra["severity"], serde_json::json!(2));
+        assert_eq!(edited.extra["labels"], serde_json::json!(["a", "b"]));
+
+        let changes = apply_edits(&mut edited, fields(&["severity=null", "missing=null"])).unwrap();
+        assert_eq!(changes, vec!["removed field severity"]);
+        assert!(apply_edits(&mut edited, fields(&["customer"])).is_err());
+        assert!(apply_edits(&mut edited, fields(&["=x"])).is_err());
+
+        // Fields survive a round trip through the editor's TOML
+        let toml_text = toml::to_string_pretty(&EditableItem::from_item(&edited)).unwrap();
+        let parsed: EditableItem = toml::from_str(&toml_text).unwrap();
+        assert_eq!(parsed.extra, edited.extra);
+    }
+
+    #[test]
+    fn test_validate_item_rejects_bad_values() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog.push(item());
+        let mut edited = item();
+        assert!(validate_item(&project, &edited).is_ok());
+
+        edited.effort = 0;
+        assert!(validate_item(&project, &edited).is_err());
+        edited.effort = 3;
+        edited.dependencies = vec!["US-404".to_string()];
+        assert!(validate_item(&project, &edited).is_err());
+        edited.dependencies = vec!["US-001".to_string()];
+        assert!(validate_item(&project, &edited).is_err());
+    }
+
+    #[test]
+    fn test_editable_item_toml_round_trip() {
+        let editable = EditableItem::from_item(&item());
+        let toml_text = toml::to_string_pretty(&editable).unwrap();
+        assert!(toml_text.contains("priority = \"Medium\""));
+        let parsed: EditableItem = toml::from_str(&format!("# comment\n{}", toml_text)).unwrap();
+        assert_eq!(parsed, editable);
+    }
+}
@@ -0,0 +1,102 @@
+// src/scripts/epics.rs
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::config::{BacklogItem, ItemType, Priority, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::stats;
+
+/// Next free `EPIC-NNN` ID.
+fn next_epic_id(project: &Project) -> String {
+    let highest = project.backlog.iter()
+        .chain(project.archive.iter().map(|archived| &archived.item))
+        .filter_map(|item| item.id.strip_prefix("EPIC-").and_then(|n| n.parse::<u32>().ok()))
+        .max()
+        .unwrap_or(0);
+    format!("EPIC-{:03}", highest + 1)
+}
+
+pub fn epics(project: &Project) -> impl Iterator<Item = &BacklogItem> {
+    project.backlog.iter().filter(|item| matches!(item.item_type, ItemType::Epic))
+}
+
+/// One line per epic with its rollup, for `list-backlog` and `status`.
+pub fn print_epic_rollup(project: &Project) {
+    for epic in epics(project) {
+        let progress = stats::epic_progress(project, &epic.id);
+        println!("   🎯 {} - {}: {} / {} points ({}%), {} / {} items done",
+            epic.id, epic.title, progress.done_points, progress.total_points, progress.percent(),
+            progress.done_items, progress.total_items);
+    }
+}
+
+/// Fail unless `epic_id` names an epic in the backlog.
+pub fn require_epic(project: &Project, epic_id: &str) -> Result<()> {
+    match project.backlog.iter().find(|item| item.id == epic_id) {
+        Some(item) if matches!(item.item_type, ItemType::Epic) => Ok(()),
+        Some(_) => anyhow::bail!("'{}' is not an epic", epic_id),
+        None => anyhow::bail!("Epic '{}' is not in the backlog", epic_id),
+    }
+}
+
+pub fn add_epic(ctx: &AppContext, title: String, description: String, priority: Priority) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    let epic_id = next_epic_id(&project);
+    project.backlog.push(BacklogItem {
+        id: epic_id.clone(),
+        item_type: ItemType::Epic,
+        title: title.clone(),
+        story: description,
+        acceptance_criteria: vec![],
+        priority,
+        // An epic is sized by its children
+        effort: 0,
+        status: Status::Todo,
+        created: Utc::now(),
+        sprint: None,
+        dependencies: vec![],
+        started_at: None,
+        completed_at: None,
+        component: None,
+        parent: None,
+        tags: vec![],
+        external_ref: None,
+        history: vec![],
+        review: None,
+        criteria_check: None,
+        extra: Default::default(),
+    });
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    println!("✅ Added epic {}: {}", epic_id, title);
+    hints::print_next_steps(Some(&project), &[
+        (format!("env-coach add-requirement \"...\" --epic {}", epic_id).as_str(), "Generate stories for the epic"),
+        (format!("env-coach add-story --title \"...\" --description \"...\" --epic {}", epic_id).as_str(), "Add a story by hand"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_epic_id() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog.push(BacklogItem::test("EPIC-001").with_type(ItemType::Epic).with_effort(0));
+        project.backlog.push(BacklogItem::test("US-001").with_parent("EPIC-001").with_status(Status::Done));
+        assert_eq!(next_epic_id(&project), "EPIC-002");
+    }
+
+    #[test]
+    fn test_require_epic() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog.push(BacklogItem::test("EPIC-001").with_type(ItemType::Epic).with_effort(0));
+        project.backlog.push(BacklogItem::test("US-001"));
+        assert!(require_epic(&project, "EPIC-001").is_ok());
+        assert!(require_epic(&project, "US-001").is_err());
+        assert!(require_epic(&project, "EPIC-002").is_err());
+    }
+}
@@ -0,0 +1,240 @@
+// src/scripts/estimate.rs
+//! `estimate [ids...]`: LLM-suggested story points for backlog items, based on the team's
+//! velocity and completed items that look alike, accepted or skipped one item at a time.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::config::{BacklogItem, ItemType, LlmRole, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::scripts::{item_ids, set_field};
+use crate::stats;
+use crate::templates::Templates;
+
+/// The points scale suggestions are snapped to.
+const FIBONACCI: &[u32] = &[1, 2, 3, 5, 8, 13, 21];
+/// Completed items sent as reference, at most.
+const MAX_REFERENCE_ITEMS: usize = 10;
+/// Completed sprints the velocity summary covers.
+const VELOCITY_SPRINTS: usize = 3;
+
+#[derive(Debug, Deserialize)]
+struct LlmEstimate {
+    id: String,
+    effort: u32,
+    #[serde(default)]
+    reasoning: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct LlmEstimates {
+    estimates: Vec<LlmEstimate>,
+}
+
+pub(crate) fn parse_estimate_response(response: &str) -> Result<LlmEstimates> {
+    serde_json::from_str(response.trim())
+        .with_context(|| format!("The LLM did not return estimates: {}", response.chars().take(300).collect::<String>()))
+}
+
+/// The nearest value on the Fibonacci scale, rounding ties up.
+fn snap_to_fibonacci(points: u32) -> u32 {
+    *FIBONACCI.iter()
+        .min_by_key(|value| (value.abs_diff(points), u32::MAX - **value))
+        .expect("scale is not empty")
+}
+
+/// Lowercase words of four letters or more, enough to tell what an item is about.
+fn words(item: &BacklogItem) -> HashSet<String> {
+    format!("{} {}", item.title, item.story)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() >= 4)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Days from start to completion of a finished item.
+fn cycle_days(item: &BacklogItem) -> Option<f64> {
+    let (started, completed) = (item.started_at?, item.completed_at?);
+    Some((completed - started).num_minutes().max(0) as f64 / (60.0 * 24.0))
+}
+
+/// Done items most alike the items to estimate (by shared title and story words),
+/// best matches first; items sharing nothing are left out.
+fn reference_items<'a>(project: &'a Project, targets: &[&BacklogItem]) -> Vec<&'a BacklogItem> {
+    let wanted: HashSet<String> = targets.iter().flat_map(|item| words(item)).collect();
+    let mut scored: Vec<(usize, &BacklogItem)> = project.backlog.iter()
+        .filter(|item| matches!(item.status, Status::Done) && !matches!(item.item_type, ItemType::Epic))
+        .map(|item| (words(item).intersection(&wanted).count(), item))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().take(MAX_REFERENCE_ITEMS).map(|(_, item)| item).collect()
+}
+
+fn format_velocity(project: &Project) -> String {
+    let sprints = stats::completed_sprints(project, VELOCITY_SPRINTS);
+    match stats::average_velocity(&sprints) {
+        Some(average) => format!(
+            "{:.1} points per sprint on average over the last {} sprint(s) ({})",
+            average,
+            sprints.len(),
+            sprints.iter().map(|sprint| format!("{}: {}", sprint.id, sprint.actual_velocity)).collect::<Vec<_>>().join(", ")
+        ),
+        None => "No completed sprints yet.".to_string(),
+    }
+}
+
+fn build_estimate_prompt(template: &str, project: &Project, targets: &[&BacklogItem], references: &[&BacklogItem]) -> String {
+    let items = targets.iter()
+        .map(|item| {
+            let mut line = format!("- {} ({} points now): {}", item.id, item.effort, item.title);
+            if !item.story.trim().is_empty() {
+                line.push_str(&format!("\n  {}", item.story));
+            }
+            for criterion in &item.acceptance_criteria {
+                line.push_str(&format!("\n  - {}", criterion));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let reference = match references.is_empty() {
+        true => "None yet.".to_string(),
+        false => references.iter()
+            .map(|item| {
+                let days = cycle_days(item).map(|days| format!(", {:.1} days", days)).unwrap_or_default();
+                format!("- {} ({} points{}): {}", item.id, item.effort, days, item.title)
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    template
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{tech_stack}}", &project.meta.tech_stack.join(", "))
+        .replace("{{velocity}}", &format_velocity(project))
+        .replace("{{reference_items}}", &reference)
+        .replace("{{items}}", &items)
+}
+
+/// Suggest efforts for the given items (all open, non-epic items by default) and apply
+/// the ones the user accepts.
+pub async fn run(ctx: &AppContext, item_args: Vec<String>) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let ids = match item_args.is_empty() {
+        true => project.backlog.iter()
+            .filter(|item| matches!(item.status, Status::Todo) && !matches!(item.item_type, ItemType::Epic))
+            .map(|item| item.id.clone())
+            .collect(),
+        false => item_ids::expand(&item_args, &project)?,
+    };
+    let mut targets: Vec<&BacklogItem> = vec![];
+    for id in &ids {
+        let item = project.backlog.iter()
+            .find(|item| &item.id == id)
+            .ok_or_else(|| anyhow::anyhow!("Backlog item '{}' not found", id))?;
+        if matches!(item.item_type, ItemType::Epic) {
+            anyhow::bail!("'{}' is an epic; its size is the sum of its children", id);
+        }
+        targets.push(item);
+    }
+    if targets.is_empty() {
+        println!("✅ No Todo items to estimate");
+        return Ok(());
+    }
+
+    let references = reference_items(&project, &targets);
+    let prompt_template_path = Path::new(".env-coach/prompts/effort_estimator.md");
+    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
+        Ok(template) => template,
+        Err(_) => {
+            println!("⚠️ Effort estimator prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_effort_estimator_prompt_content()
+        }
+    };
+    let prompt_template = prompts::fill_project_context(&prompt_template, &project, None);
+    let prompt = build_estimate_prompt(&prompt_template, &project, &targets, &references);
+
+    println!("🤖 Asking LLM to estimate {} item(s) ({} completed item(s) as reference)...", targets.len(), references.len());
+    let response = ollama::send_generation_prompt(&project.llm_for(LlmRole::Planner), &prompt)
+        .await
+        .context("Failed to get estimates from LLM")?;
+    let estimates = parse_estimate_response(&response)?;
+
+    let mut changed: Vec<(String, u32, u32)> = vec![];
+    for estimate in estimates.estimates {
+        let Some(item) = project.backlog.iter().find(|item| item.id == estimate.id && ids.contains(&item.id)) else {
+            println!("⚠️ Ignoring estimate for {}, which was not asked for", estimate.id);
+            continue;
+        };
+        let suggested = snap_to_fibonacci(estimate.effort);
+        println!();
+        println!("📏 {} - {}: {} → {} points", item.id, item.title, item.effort, suggested);
+        if !estimate.reasoning.is_empty() {
+            println!("   {}", estimate.reasoning);
+        }
+        if suggested == item.effort {
+            println!("   ✅ Current estimate kept");
+            continue;
+        }
+        if !ctx.prompter.confirm(&format!("👉 Set {} to {} points?", item.id, suggested))? {
+            continue;
+        }
+        let (before, after) = set_field::update_item(&mut project, &estimate.id, |item| item.effort = suggested)?;
+        changed.push((after.id, before.effort, after.effort));
+    }
+
+    println!();
+    if changed.is_empty() {
+        println!("ℹ️ No estimates changed");
+        return Ok(());
+    }
+    ctx.save_project(&project).context("Failed to save project")?;
+    println!("✅ Updated {} estimate(s):", changed.len());
+    for (id, before, after) in &changed {
+        println!("   - {}: {} → {} points", id, before, after);
+    }
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach list-backlog", "Review the backlog with the new estimates"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_snap_to_fibonacci() {
+        assert_eq!(snap_to_fibonacci(0), 1);
+        assert_eq!(snap_to_fibonacci(4), 5);
+        assert_eq!(snap_to_fibonacci(10), 8);
+        assert_eq!(snap_to_fibonacci(11), 13);
+        assert_eq!(snap_to_fibonacci(40), 21);
+    }
+
+    #[test]
+    fn test_reference_items_and_prompt() {
+        let mut project = Project::new("Shop".to_string(), "".to_string(), None);
+        let now = Utc::now();
+        project.backlog = vec![
+            BacklogItem::test("US-001").with_title("Export orders as CSV").with_story("").with_status(Status::Done).with_effort(5)
+                .with_started_at(Some(now - Duration::days(3))).with_completed_at(Some(now)),
+            BacklogItem::test("US-002").with_title("Dark mode").with_story("").with_status(Status::Done).with_effort(2),
+            BacklogItem::test("US-003").with_title("Export invoices as CSV").with_story(""),
+        ];
+        let targets = vec![&project.backlog[2]];
+        let references = reference_items(&project, &targets);
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].id, "US-001");
+
+        let prompt = build_estimate_prompt("{{velocity}}|{{reference_items}}|{{items}}", &project, &targets, &references);
+        assert_eq!(prompt, "No completed sprints yet.|- US-001 (5 points, 3.0 days): Export orders as CSV|- US-003 (3 points now): Export invoices as CSV");
+    }
+}
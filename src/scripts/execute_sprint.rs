@@ -0,0 +1,190 @@
+// src/scripts/execute_sprint.rs
+//! `execute-sprint <id>`: run `execute-task` for each open item of a sprint, dependencies
+//! first, and report what was applied per story.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+
+use crate::config::{BacklogItem, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::scripts::tasks::{self, ExecuteOptions, ExecutionSummary};
+
+/// How one item of the sprint went.
+enum ItemRun {
+    Done(ExecutionSummary),
+    Error(String),
+    NotRun(String),
+}
+
+/// Items of the sprint still to execute (Todo, or In Progress with an interrupted run),
+/// each after the sprint items it depends on, in backlog order otherwise. Items waiting for
+/// work outside this list, or caught in a dependency cycle, come back as blocked.
+fn execution_order<'a>(project: &'a Project, sprint_id: &str, interrupted: &HashSet<String>) -> (Vec<&'a BacklogItem>, Vec<(&'a BacklogItem, String)>) {
+    let mut pending: Vec<&BacklogItem> = project.backlog.iter()
+        .filter(|item| item.sprint.as_deref() == Some(sprint_id))
+        .filter(|item| matches!(item.status, Status::Todo) || interrupted.contains(&item.id))
+        .collect();
+    let is_done = |id: &str| project.backlog.iter().any(|item| item.id == id && matches!(item.status, Status::Done));
+
+    let mut ordered: Vec<&BacklogItem> = vec![];
+    while let Some(pos) = pending.iter().position(|item| {
+        item.dependencies.iter().all(|dep| is_done(dep) || ordered.iter().any(|done| done.id == *dep))
+    }) {
+        ordered.push(pending.remove(pos));
+    }
+    let blocked = pending.iter()
+        .map(|item| {
+            let waiting: Vec<&str> = item.dependencies.iter()
+                .filter(|dep| !is_done(dep) && !ordered.iter().any(|done| done.id == **dep))
+                .map(String::as_str)
+                .collect();
+            (*item, format!("waits for {}", waiting.join(", ")))
+        })
+        .collect();
+    (ordered, blocked)
+}
+
+/// Execute the sprint's open items one by one. A story that fails, leaves failed
+/// suggestions or a broken build pauses the run until the user chooses to go on; stories
+/// depending on one that did not finish are not run.
+pub async fn run(ctx: &AppContext, sprint_id: String, options: ExecuteOptions) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let sprint = project.sprints.iter()
+        .find(|sprint| sprint.id == sprint_id)
+        .ok_or_else(|| anyhow::anyhow!("Sprint '{}' not found", sprint_id))?;
+
+    let interrupted: HashSet<String> = project.backlog.iter()
+        .filter(|item| tasks::progress_path(&root, &item.id).exists())
+        .map(|item| item.id.clone())
+        .collect();
+    let (order, blocked) = execution_order(&project, &sprint.id, &interrupted);
+    if order.is_empty() && blocked.is_empty() {
+        println!("✅ Sprint {} has no open items to execute", sprint_id);
+        return Ok(());
+    }
+    println!("🏃 Executing sprint {}: {}", sprint.id, sprint.goal);
+    println!("📋 Order: {}", order.iter().map(|item| item.id.as_str()).collect::<Vec<_>>().join(" → "));
+    let auto_approved: Vec<&str> = [(options.auto_approve.code, "code"), (options.auto_approve.dependencies, "dependencies")]
+        .into_iter()
+        .filter_map(|(on, kind)| on.then_some(kind))
+        .collect();
+    if !auto_approved.is_empty() {
+        println!("🤖 Auto-approving: {}", auto_approved.join(", "));
+    }
+
+    let mut runs: Vec<(&BacklogItem, ItemRun)> = blocked.into_iter().map(|(item, reason)| (item, ItemRun::NotRun(reason))).collect();
+    let mut finished: HashSet<&str> = HashSet::new();
+    let mut stopped = false;
+    for (i, item) in order.iter().enumerate() {
+        let unfinished: Vec<&str> = item.dependencies.iter()
+            .map(String::as_str)
+            .filter(|dep| order.iter().any(|other| other.id == *dep) && !finished.contains(dep))
+            .collect();
+        if stopped || !unfinished.is_empty() {
+            let reason = match stopped {
+                true => "run stopped".to_string(),
+                false => format!("{} did not finish", unfinished.join(", ")),
+            };
+            runs.push((item, ItemRun::NotRun(reason)));
+            continue;
+        }
+
+        println!();
+        println!("━━━ [{}/{}] {} - {}", i + 1, order.len(), item.id, item.title);
+        let item_options = ExecuteOptions { resume: interrupted.contains(&item.id), ..options.clone() };
+        let trouble = match tasks::execute_task(ctx, item.id.clone(), item_options).await {
+            Ok(summary) => {
+                let trouble = if summary.failed > 0 {
+                    Some(format!("{} suggestion(s) failed", summary.failed))
+                } else if summary.needs_follow_up {
+                    Some("the build or checks fail".to_string())
+                } else {
+                    None
+                };
+                runs.push((item, ItemRun::Done(summary)));
+                trouble
+            }
+            Err(e) => {
+                let error = format!("{:#}", e);
+                runs.push((item, ItemRun::Error(error.clone())));
+                Some(error)
+            }
+        };
+        match trouble {
+            None => {
+                finished.insert(item.id.as_str());
+            }
+            Some(trouble) => {
+                println!();
+                println!("⏸️  {} needs attention: {}", item.id, trouble);
+                if i + 1 < order.len() && !ctx.prompter.confirm("👉 Continue with the next story?")? {
+                    stopped = true;
+                }
+            }
+        }
+    }
+
+    println!();
+    println!("📊 Sprint {} execution summary:", sprint_id);
+    for (item, run) in &runs {
+        match run {
+            ItemRun::Done(summary) => {
+                let status = if summary.failed > 0 || summary.needs_follow_up { "⚠️" } else { "✅" };
+                let mut counts = format!("{} applied", summary.applied.len());
+                if summary.skipped > 0 {
+                    counts.push_str(&format!(", {} skipped", summary.skipped));
+                }
+                if summary.failed > 0 {
+                    counts.push_str(&format!(", {} failed", summary.failed));
+                }
+                if summary.needs_follow_up {
+                    counts.push_str(", needs follow-up");
+                }
+                println!("   {} {} {}: {}", status, item.id, item.title, counts);
+                for change in &summary.applied {
+                    println!("      - {}", change);
+                }
+            }
+            ItemRun::Error(e) => println!("   ❌ {} {}: {}", item.id, item.title, e),
+            ItemRun::NotRun(reason) => println!("   ⏭️  {} {}: not run ({})", item.id, item.title, reason),
+        }
+    }
+
+    let project = ctx.load_project()?;
+    let rerun = format!("env-coach execute-sprint {}", sprint_id);
+    let mut next_steps = vec![("git diff", "Review the applied changes")];
+    if runs.iter().any(|(_, run)| !matches!(run, ItemRun::Done(_))) {
+        next_steps.push((rerun.as_str(), "Continue with the stories not run"));
+    }
+    hints::print_next_steps(Some(&project), &next_steps);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execution_order() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        let item = |id: &str| BacklogItem::test(id).with_effort(1).with_sprint("S-001");
+        project.backlog = vec![
+            item("US-001").with_dependencies(&["US-003"]),
+            item("US-002").with_status(Status::Done),
+            item("US-003").with_dependencies(&["US-002"]),
+            item("US-004").with_dependencies(&["US-009"]),
+            item("US-005").with_status(Status::InProgress),
+            item("US-006").with_status(Status::InProgress),
+            BacklogItem::test("US-009"),
+        ];
+
+        let interrupted = HashSet::from(["US-006".to_string()]);
+        let (order, blocked) = execution_order(&project, "S-001", &interrupted);
+        let ids: Vec<&str> = order.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["US-003", "US-001", "US-006"]);
+        assert_eq!(blocked.len(), 1);
+        assert_eq!((blocked[0].0.id.as_str(), blocked[0].1.as_str()), ("US-004", "waits for US-009"));
+    }
+}
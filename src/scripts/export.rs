@@ -0,0 +1,314 @@
+// src/scripts/export.rs
+use anyhow::{Context, Result};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use crate::config::{Project, SprintStatus};
+use crate::context::AppContext;
+use crate::hints;
+use crate::scripts::notes;
+use crate::templates::Templates;
+
+/// What a credential becomes in an anonymized export.
+const REDACTED: &str = "***";
+
+/// Stable placeholder for `text`: equal inputs map to equal placeholders, so relationships
+/// (e.g. duplicated titles) survive anonymization. Empty text stays empty.
+fn placeholder(kind: &str, text: &str) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    format!("{}-{:08x}", kind, hasher.finish() as u32)
+}
+
+/// Replace free text with placeholders while keeping structure, IDs, counts, points,
+/// statuses and timestamps, so the export can reproduce a bug without leaking content.
+fn anonymize(project: &Project) -> Project {
+    let mut project = project.clone();
+
+    let meta = &mut project.meta;
+    meta.name = placeholder("project", &meta.name);
+    meta.description = placeholder("description", &meta.description);
+    meta.tags = meta.tags.iter().map(|tag| placeholder("tag", tag)).collect();
+    if let Some(llm) = meta.llm.as_mut() {
+        llm.host = llm.host.as_ref().map(|host| placeholder("host", host));
+        // Credentials are dropped rather than hashed; only whether one was set is kept
+        llm.api_key = llm.api_key.as_ref().map(|_| REDACTED.to_string());
+        for value in llm.headers.values_mut() {
+            *value = REDACTED.to_string();
+        }
+        llm.proxy = llm.proxy.as_ref().map(|_| REDACTED.to_string());
+        llm.ca_bundle = llm.ca_bundle.as_ref().map(|path| placeholder("file", path));
+    }
+    meta.links = meta.links.iter().map(|(tracker, template)| (tracker.clone(), placeholder("link", template))).collect();
+    if let Some(prd) = meta.prd.as_mut() {
+        prd.problem = placeholder("problem", &prd.problem);
+        prd.success_metrics = prd.success_metrics.iter().map(|m| placeholder("metric", m)).collect();
+    }
+    // Same placeholder for a name wherever it appears, so maintainers still match history
+    if let Some(team) = meta.team.as_mut() {
+        team.maintainers = team.maintainers.iter().map(|user| placeholder("user", user)).collect();
+    }
+    if let Some(register) = meta.risks.as_mut() {
+        for risk in register.risks.iter_mut() {
+            risk.description = placeholder("risk", &risk.description);
+            risk.mitigation = placeholder("mitigation", &risk.mitigation);
+        }
+    }
+
+    for item in project.backlog.iter_mut().chain(project.archive.iter_mut().map(|entry| &mut entry.item)) {
+        item.title = placeholder("title", &item.title);
+        item.story = placeholder("story", &item.story);
+        item.acceptance_criteria = item.acceptance_criteria.iter().map(|ac| placeholder("criterion", ac)).collect();
+        item.external_ref = item.external_ref.as_ref().map(|external_ref| placeholder("ref", external_ref));
+        if let Some(review) = item.review.as_mut() {
+            review.files = review.files.iter().map(|file| placeholder("file", file)).collect();
+            review.summary = placeholder("summary", &review.summary);
+            review.strengths = review.strengths.iter().map(|strength| placeholder("note", strength)).collect();
+            for issue in review.issues.iter_mut() {
+                issue.file = issue.file.as_ref().map(|file| placeholder("file", file));
+                issue.description = placeholder("issue", &issue.description);
+            }
+            review.suggested_fixes = review.suggested_fixes.iter().map(|fix| placeholder("fix", fix)).collect();
+        }
+        item.component = item.component.as_ref().map(|component| placeholder("component", component));
+        item.tags = item.tags.iter().map(|tag| placeholder("tag", tag)).collect();
+        // Custom field names are structure; their values are content
+        for value in item.extra.values_mut().filter(|value| !value.is_null()) {
+            let text = value.as_str().map(String::from).unwrap_or_else(|| value.to_string());
+            *value = placeholder("field", &text).into();
+        }
+        for event in item.history.iter_mut() {
+            event.user = placeholder("user", &event.user);
+            // Changes are joined with "; " (see `team::describe_changes`); only the component is text
+            event.change = event.change.split("; ")
+                .map(|change| match change.strip_prefix("component → ").filter(|component| *component != "none") {
+                    Some(component) => format!("component → {}", placeholder("component", component)),
+                    None => change.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+        }
+        // Same placeholder as the acceptance criterion, so each check still lines up with it
+        for check in item.criteria_check.iter_mut().flat_map(|criteria_check| criteria_check.checks.iter_mut()) {
+            check.criterion = placeholder("criterion", &check.criterion);
+            check.evidence = placeholder("evidence", &check.evidence);
+        }
+    }
+
+    for sprint in project.sprints.iter_mut() {
+        sprint.goal = placeholder("goal", &sprint.goal);
+        if let Some(retro) = sprint.retrospective.as_mut() {
+            retro.highlights = placeholder("highlights", &retro.highlights);
+            retro.blockers = placeholder("blockers", &retro.blockers);
+            retro.summary = placeholder("summary", &retro.summary);
+            for list in [&mut retro.went_well, &mut retro.to_improve, &mut retro.action_items] {
+                *list = list.iter().map(|entry| placeholder("note", entry)).collect();
+            }
+        }
+    }
+
+    project
+}
+
+/// Write BACKLOG.md and a SPRINT-<id>.md per open sprint into `dir`.
+fn export_markdown(ctx: &AppContext, project: &Project, dir: &Path) -> Result<()> {
+    let mut files = vec![(dir.join("BACKLOG.md"), Templates::backlog_markdown(project))];
+    for sprint in project.sprints.iter().filter(|s| !matches!(s.status, SprintStatus::Completed | SprintStatus::Complete)) {
+        files.push((dir.join(format!("SPRINT-{}.md", sprint.id)), Templates::sprint_markdown(project, sprint)));
+    }
+
+    if ctx.dry_run {
+        for (path, _) in &files {
+            println!("🔍 Dry run: {} not written", path.display());
+        }
+        return Ok(());
+    }
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+    for (path, content) in &files {
+        std::fs::write(path, content).with_context(|| format!("Failed to write {:?}", path))?;
+        println!("📄 Wrote {}", path.display());
+    }
+    let paths: Vec<String> = files.iter().map(|(path, _)| path.display().to_string()).collect();
+    hints::print_next_steps(Some(project), &[
+        (format!("git add {}", paths.join(" ")).as_str(), "Share the plan with the team"),
+    ]);
+    Ok(())
+}
+
+/// What `export` writes.
+pub enum ExportFormat {
+    Json,
+    /// BACKLOG.md and SPRINT-<id>.md
+    Markdown,
+    /// A single org-mode file
+    Org,
+    /// One note per item and sprint under a folder of this vault
+    Obsidian(PathBuf),
+}
+
+/// Write the org-mode document to `path`.
+fn export_org(ctx: &AppContext, project: &Project, path: &Path) -> Result<()> {
+    if ctx.dry_run {
+        println!("🔍 Dry run: {} not written", path.display());
+        return Ok(());
+    }
+    std::fs::write(path, notes::org_document(project)).with_context(|| format!("Failed to write {:?}", path))?;
+    println!("📄 Wrote {}", path.display());
+    Ok(())
+}
+
+/// Write the Obsidian notes into `<vault>/<project name>/`, overwriting notes of earlier exports.
+fn export_obsidian(ctx: &AppContext, project: &Project, vault: &Path) -> Result<()> {
+    if !vault.is_dir() {
+        anyhow::bail!("{} is not a directory. Pass the folder of your Obsidian vault.", vault.display());
+    }
+    let dir = vault.join(&project.meta.name);
+    let notes = notes::obsidian_notes(project);
+    if ctx.dry_run {
+        println!("🔍 Dry run: {} note(s) not written to {}", notes.len(), dir.display());
+        return Ok(());
+    }
+    for (path, content) in &notes {
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+        }
+        std::fs::write(&path, content).with_context(|| format!("Failed to write {:?}", path))?;
+    }
+    println!("📄 Wrote {} note(s) to {}", notes.len(), dir.display());
+    Ok(())
+}
+
+/// Export project.json to `--output` (stdout when omitted). Status messages go to stderr so
+/// stdout can be redirected. For Markdown, `--output` is the directory for the files (the
+/// current directory when omitted); for org-mode, the file (BACKLOG.org by default).
+pub fn run(ctx: &AppContext, anonymized: bool, format: ExportFormat) -> Result<()> {
+    let output = ctx.output.path().map(PathBuf::from);
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    let exported = if anonymized { anonymize(&project) } else { project };
+    match format {
+        ExportFormat::Json => {}
+        ExportFormat::Markdown => return export_markdown(ctx, &exported, &output.unwrap_or_else(|| PathBuf::from("."))),
+        ExportFormat::Org => return export_org(ctx, &exported, &output.unwrap_or_else(|| PathBuf::from("BACKLOG.org"))),
+        ExportFormat::Obsidian(vault) => return export_obsidian(ctx, &exported, &vault),
+    }
+    let content = serde_json::to_string_pretty(&exported).context("Failed to serialize project")?;
+
+    ctx.output.write(&content)?;
+    if let Some(path) = output {
+        eprintln!("📦 Exported {} to {}", if anonymized { "anonymized project" } else { "project" }, path.display());
+        if anonymized {
+            eprintln!("💡 Attach this file to your env-coach bug report");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BacklogItem, CodeReview, CriteriaCheck, CriterionCheck, ItemEvent, PartialLlmConfig, Priority, ReviewIssue, Status, TeamConfig};
+    use chrono::Utc;
+
+    #[test]
+    fn test_placeholder_is_stable_and_keeps_empty() {
+        assert_eq!(placeholder("title", "Secret feature"), placeholder("title", "Secret feature"));
+        assert_ne!(placeholder("title", "Secret feature"), placeholder("title", "Other feature"));
+        assert!(placeholder("title", "Secret feature").starts_with("title-"));
+        assert_eq!(placeholder("title", ""), "");
+    }
+
+    #[test]
+    fn test_anonymize_strips_text_and_keeps_structure() {
+        let mut project = Project::new("AcmeSecret".to_string(), "Internal billing engine".to_string(), None);
+        project.meta.team = Some(TeamConfig { maintainers: vec!["Ana Acme".to_string()], require_acknowledgment: true });
+        let created = Utc::now();
+        project.backlog.push(BacklogItem {
+            component: Some("acme-billing".to_string()),
+            history: vec![
+                ItemEvent { at: created, user: "Ana Acme".to_string(), change: "status Todo → InProgress".to_string() },
+                ItemEvent { at: created, user: "Ana Acme".to_string(), change: "effort 3 → 5; component → acme-billing".to_string() },
+            ],
+            review: Some(CodeReview {
+                reviewed_at: created,
+                files: vec!["src/acme_pay.rs".to_string()],
+                score: 7,
+                summary: "AcmePay client is solid".to_string(),
+                strengths: vec!["Retries AcmePay calls".to_string()],
+                issues: vec![ReviewIssue { severity: "high".to_string(), file: Some("src/acme_pay.rs".to_string()), description: "charge() ignores errors".to_string() }],
+                suggested_fixes: vec!["Return the AcmePay error".to_string()],
+            }),
+            criteria_check: Some(CriteriaCheck {
+                checked_at: created,
+                checks: vec![CriterionCheck { criterion: "fn charge() works".to_string(), passed: false, evidence: "AcmePay mock returned 500".to_string() }],
+            }),
+            extra: [("customer".to_string(), "AcmeBank".into()), ("contract".to_string(), 250000.into())].into_iter().collect(),
+            ..BacklogItem::test("US-001")
+                .with_title("Integrate AcmePay")
+                .with_story("As finance I want AcmePay invoices")
+                .with_criteria(&["fn charge() works", "Refunds"])
+                .with_priority(Priority::High)
+                .with_effort(5)
+                .with_status(Status::InProgress)
+                .with_created(created)
+                .with_sprint("S-001")
+                .with_dependencies(&["US-000"])
+                .with_tags(&["acme-launch", "tech-debt"])
+        });
+
+        let anonymized = anonymize(&project);
+        let json = serde_json::to_string(&anonymized).unwrap();
+        assert!(!json.contains("Acme"));
+        assert!(!json.contains("billing"));
+        assert!(!json.contains("charge"));
+        assert!(!json.contains("250000"));
+        assert!(!json.contains("acme"));
+
+        let item = &anonymized.backlog[0];
+        assert_eq!(item.id, "US-001");
+        assert_eq!(item.effort, 5);
+        assert_eq!(item.created, created);
+        assert_eq!(item.acceptance_criteria.len(), 2);
+        assert_eq!(item.dependencies, vec!["US-000"]);
+        assert_eq!(item.sprint.as_deref(), Some("S-001"));
+        let review = item.review.as_ref().unwrap();
+        assert_eq!((review.score, review.issues.len(), review.issues[0].severity.as_str()), (7, 1, "high"));
+        assert_eq!(item.tags.len(), 2);
+        assert!(!json.contains("tech-debt"));
+        assert_eq!(item.extra.keys().collect::<Vec<_>>(), vec!["contract", "customer"]);
+        assert_eq!(item.history[0].user, anonymized.meta.team.as_ref().unwrap().maintainers[0]);
+        assert_eq!(item.history[0].change, "status Todo → InProgress");
+        assert_eq!(item.history[1].change, format!("effort 3 → 5; component → {}", item.component.as_ref().unwrap()));
+        let check = &item.criteria_check.as_ref().unwrap().checks[0];
+        assert_eq!(check.criterion, item.acceptance_criteria[0]);
+        assert!(!check.passed);
+    }
+
+    #[test]
+    fn test_anonymize_drops_llm_credentials() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.meta.llm = Some(PartialLlmConfig {
+            api_key: Some("sk-live-SECRET123".to_string()),
+            headers: [("X-Token".to_string(), "tok-SECRET".to_string())].into_iter().collect(),
+            proxy: Some("http://user:pw@proxy.corp:3128".to_string()),
+            ca_bundle: Some("/etc/corp/SECRET-ca.pem".to_string()),
+            ..Default::default()
+        });
+
+        let anonymized = anonymize(&project);
+        let json = serde_json::to_string(&anonymized).unwrap();
+        assert!(!json.contains("SECRET"));
+        assert!(!json.contains("pw@"));
+        assert!(!json.contains("corp"));
+        let llm = anonymized.meta.llm.as_ref().unwrap();
+        assert_eq!(llm.api_key.as_deref(), Some(REDACTED));
+        assert_eq!(llm.headers["X-Token"], REDACTED);
+        assert_eq!(llm.proxy.as_deref(), Some(REDACTED));
+    }
+}
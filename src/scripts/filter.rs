@@ -0,0 +1,151 @@
+// src/scripts/filter.rs
+use crate::config::{BacklogItem, ItemType, Priority, Status};
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum SortKey {
+    /// Largest items first
+    Effort,
+    /// Most urgent first
+    Priority,
+    /// Oldest first
+    Created,
+}
+
+/// Filtering and sorting flags shared by the commands that list backlog items.
+#[derive(clap::Args, Debug, Default, Clone)]
+pub struct BacklogFilter {
+    /// Only items with this status (todo, in-progress, review, done)
+    #[arg(long)]
+    pub status: Option<Status>,
+    /// Only items with this priority (critical, high, medium, low)
+    #[arg(long)]
+    pub priority: Option<Priority>,
+    /// Only items assigned to this sprint
+    #[arg(long)]
+    pub sprint: Option<String>,
+    /// Only items of this type (story, bug, epic, task)
+    #[arg(long = "type")]
+    pub item_type: Option<ItemType>,
+    /// Only items carrying this tag
+    #[arg(long)]
+    pub tag: Option<String>,
+    /// Only items whose custom field KEY equals VALUE (or, given just KEY, that have it) -
+    /// can be specified multiple times
+    #[arg(long = "field", value_name = "KEY=VALUE")]
+    pub fields: Vec<String>,
+    /// Sort the listed items
+    #[arg(long, value_enum)]
+    pub sort: Option<SortKey>,
+}
+
+impl BacklogFilter {
+    /// True when no flag narrows the list, so callers can keep their full layout.
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.priority.is_none()
+            && self.sprint.is_none()
+            && self.item_type.is_none()
+            && self.tag.is_none()
+            && self.fields.is_empty()
+    }
+
+    pub fn matches(&self, item: &BacklogItem) -> bool {
+        self.status.as_ref().is_none_or(|status| &item.status == status)
+            && self.priority.as_ref().is_none_or(|priority| &item.priority == priority)
+            && self.sprint.as_deref().is_none_or(|sprint| item.sprint.as_deref() == Some(sprint))
+            && self.item_type.as_ref().is_none_or(|item_type| &item.item_type == item_type)
+            && self.tag.as_deref().is_none_or(|tag| item.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            && self.field_filters().all(|(key, value)| match (item.field_text(key), value) {
+                (Some(actual), Some(wanted)) => actual.eq_ignore_ascii_case(wanted),
+                (found, None) => found.is_some(),
+                (None, _) => false,
+            })
+    }
+
+    /// The `--field` filters as key and wanted value; a bare key only asks for the field.
+    pub fn field_filters(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.fields.iter().map(|spec| match spec.split_once('=') {
+            Some((key, value)) => (key.trim(), Some(value.trim())),
+            None => (spec.trim(), None),
+        })
+    }
+
+    /// The matching items, sorted by `--sort` (backlog order otherwise).
+    pub fn apply<'a>(&self, items: impl IntoIterator<Item = &'a BacklogItem>) -> Vec<&'a BacklogItem> {
+        let mut matching: Vec<&BacklogItem> = items.into_iter().filter(|item| self.matches(item)).collect();
+        self.sort(&mut matching, |item| item);
+        matching
+    }
+
+    /// Sort anything that wraps a backlog item (e.g. archive entries) by `--sort`.
+    /// The sort is stable, so ties keep their backlog order.
+    pub fn sort<T>(&self, entries: &mut [T], item: impl Fn(&T) -> &BacklogItem) {
+        match self.sort {
+            Some(SortKey::Effort) => entries.sort_by_key(|entry| std::cmp::Reverse(item(entry).effort)),
+            Some(SortKey::Priority) => entries.sort_by_key(|entry| item(entry).priority.rank()),
+            Some(SortKey::Created) => entries.sort_by_key(|entry| item(entry).created),
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn backlog() -> Vec<BacklogItem> {
+        vec![
+            BacklogItem::test("US-001").with_priority(Priority::Low).with_tags(&["UX"]).with_sprint("S-001")
+                .with_created(Utc::now() - Duration::days(2)),
+            BacklogItem::test("BUG-001").with_type(ItemType::Bug).with_priority(Priority::Critical).with_effort(1),
+            BacklogItem::test("US-002").with_priority(Priority::High).with_effort(8).with_status(Status::Done)
+                .with_created(Utc::now() - Duration::days(5)),
+        ]
+    }
+
+    fn ids(items: Vec<&BacklogItem>) -> Vec<&str> {
+        items.iter().map(|item| item.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filter_flags_combine() {
+        let items = backlog();
+        assert!(BacklogFilter::default().is_empty());
+        assert_eq!(BacklogFilter::default().apply(&items).len(), 3);
+
+        let todo_stories = BacklogFilter { status: Some(Status::Todo), item_type: Some(ItemType::UserStory), ..Default::default() };
+        assert!(!todo_stories.is_empty());
+        assert_eq!(ids(todo_stories.apply(&items)), vec!["US-001"]);
+
+        let tagged = BacklogFilter { tag: Some("ux".to_string()), ..Default::default() };
+        assert_eq!(ids(tagged.apply(&items)), vec!["US-001"]);
+        let in_sprint = BacklogFilter { sprint: Some("S-002".to_string()), ..Default::default() };
+        assert!(in_sprint.apply(&items).is_empty());
+    }
+
+    #[test]
+    fn test_field_filters() {
+        let mut items = backlog();
+        items[0].extra.insert("customer".to_string(), serde_json::json!("Acme"));
+        items[0].extra.insert("severity".to_string(), serde_json::json!(2));
+        items[1].extra.insert("customer".to_string(), serde_json::json!("Globex"));
+        let filter = |fields: &[&str]| BacklogFilter { fields: fields.iter().map(|f| f.to_string()).collect(), ..Default::default() };
+
+        assert!(!filter(&["customer"]).is_empty());
+        assert_eq!(ids(filter(&["customer"]).apply(&items)), vec!["US-001", "BUG-001"]);
+        assert_eq!(ids(filter(&["customer=acme"]).apply(&items)), vec!["US-001"]);
+        // Non-string values compare by their JSON text
+        assert_eq!(ids(filter(&["customer=Acme", "severity=2"]).apply(&items)), vec!["US-001"]);
+        assert!(filter(&["severity=3"]).apply(&items).is_empty());
+    }
+
+    #[test]
+    fn test_sort_keys() {
+        let items = backlog();
+        let sorted = |sort| ids(BacklogFilter { sort: Some(sort), ..Default::default() }.apply(&items));
+        assert_eq!(sorted(SortKey::Effort), vec!["US-002", "US-001", "BUG-001"]);
+        assert_eq!(sorted(SortKey::Priority), vec!["BUG-001", "US-002", "US-001"]);
+        assert_eq!(sorted(SortKey::Created), vec!["US-002", "US-001", "BUG-001"]);
+    }
+}
@@ -0,0 +1,103 @@
+// src/scripts/find.rs
+//! `find "<query>"`: semantic search over the backlog and the code, using the embeddings
+//! index built by `env-coach index`, to find prior art before adding stories.
+
+use anyhow::{Context, Result};
+
+use crate::auto_update::duplicates::cosine_similarity;
+use crate::code_index::CodeIndex;
+use crate::config::{BacklogItem, Project};
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+
+/// The `limit` backlog items most similar to `query`, best first. Items without an
+/// embedding in the index are left out.
+fn top_items<'a>(project: &'a Project, index: &CodeIndex, query: &[f32], limit: usize) -> Vec<(&'a BacklogItem, f32)> {
+    let mut scored: Vec<(&BacklogItem, f32)> = index.items.iter()
+        .filter_map(|indexed| {
+            let item = project.backlog.iter().find(|item| item.id == indexed.id)?;
+            Some((item, cosine_similarity(query, &indexed.embedding)))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(limit);
+    scored
+}
+
+fn format_ranges(ranges: &[(usize, usize)]) -> String {
+    ranges.iter().map(|(start, end)| format!("{}-{}", start, end)).collect::<Vec<_>>().join(", ")
+}
+
+pub async fn run(ctx: &AppContext, query: String, limit: usize) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let mut index = CodeIndex::load(&root)?;
+    if index.model != project.llm().model {
+        anyhow::bail!("The index was built with {}, not {}; run `env-coach index --full` first", index.model, project.llm().model);
+    }
+
+    // Items added or edited since the last `index` run are embedded now and kept in the index
+    let embedded = index.embed_items(&project).await?;
+    if embedded > 0 && !ctx.dry_run {
+        index.save(&root)?;
+    }
+
+    let query_embedding = ollama::generate_embedding(project.llm(), &query)
+        .await
+        .context("Failed to embed the query")?;
+
+    let items = top_items(&project, &index, &query_embedding, limit);
+    println!("🔎 Backlog items for \"{}\":", query);
+    if items.is_empty() {
+        println!("   (none)");
+    }
+    for (item, score) in &items {
+        println!("   {:.2}  {} [{:?}] {}", score, item.id, item.status, item.title);
+    }
+
+    let files = index.top_files(&query_embedding, limit);
+    println!();
+    println!("📄 Source files:");
+    if files.is_empty() {
+        println!("   (none indexed)");
+    }
+    for file in &files {
+        println!("   {:.2}  {} (lines {})", file.score, file.path, format_ranges(&file.ranges));
+    }
+
+    let show = items.first().map(|(item, _)| format!("env-coach show-item {}", item.id));
+    let mut next_steps = vec![("env-coach add-requirement \"...\"", "Add the story if nothing covers it yet")];
+    if let Some(command) = &show {
+        next_steps.insert(0, (command.as_str(), "Look at the closest item"));
+    }
+    hints::print_next_steps(Some(&project), &next_steps);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code_index::IndexedItem;
+    use crate::config::Status;
+
+    #[test]
+    fn test_top_items() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog = vec![
+            BacklogItem::test("US-001").with_title("Parse LLM JSON").with_status(Status::Done),
+            BacklogItem::test("US-002").with_title("Dark mode").with_status(Status::Done),
+        ];
+        let index = CodeIndex {
+            items: vec![
+                IndexedItem { id: "US-001".to_string(), hash: 1, embedding: vec![1.0, 0.0] },
+                IndexedItem { id: "US-002".to_string(), hash: 2, embedding: vec![0.0, 1.0] },
+                IndexedItem { id: "US-009".to_string(), hash: 3, embedding: vec![1.0, 0.0] },
+            ],
+            ..CodeIndex::default()
+        };
+        let found: Vec<&str> = top_items(&project, &index, &[0.9, 0.1], 5).iter().map(|(item, _)| item.id.as_str()).collect();
+        assert_eq!(found, vec!["US-001", "US-002"]);
+        assert_eq!(format_ranges(&[(1, 40), (36, 75)]), "1-40, 36-75");
+    }
+}
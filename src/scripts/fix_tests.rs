@@ -0,0 +1,199 @@
+// src/scripts/fix_tests.rs
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::auto_update::code_gen::get_primary_language;
+use crate::auto_update::llm_parsers::parse_assist_task_response;
+use crate::auto_update::{AutoUpdater, SuggestionOutcome};
+use crate::config::{LlmRole, Project};
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::templates::Templates;
+
+/// Test output sent to the LLM; failures are summarized at the end, so the tail is kept.
+pub(crate) const MAX_OUTPUT_CHARS: usize = 8000;
+/// Source files sent along with the output, and the size kept of each.
+pub(crate) const MAX_SOURCE_FILES: usize = 5;
+const MAX_SOURCE_CHARS: usize = 6000;
+
+/// Test command for the project's build system, judged by its manifest files.
+pub(crate) fn detect_test_command(root: &Path) -> Option<&'static str> {
+    [
+        ("Cargo.toml", "cargo test"),
+        ("package.json", "npm test"),
+        ("go.mod", "go test ./..."),
+        ("pyproject.toml", "pytest"),
+        ("setup.py", "pytest"),
+    ]
+    .into_iter()
+    .find(|(manifest, _)| root.join(manifest).exists())
+    .map(|(_, command)| command)
+}
+
+/// Run `command` through the shell, returning whether it passed and its combined output.
+pub(crate) fn run_tests(command: &str) -> Result<(bool, String)> {
+    println!("🧪 Running {}...", command);
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .with_context(|| format!("Failed to run '{}'", command))?;
+    let text = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    Ok((output.status.success(), text))
+}
+
+/// The last `max_chars` characters of `text`, starting at a line boundary.
+pub(crate) fn tail(text: &str, max_chars: usize) -> &str {
+    let Some((start, _)) = text.char_indices().rev().nth(max_chars.saturating_sub(1)) else { return text };
+    let rest = &text[start..];
+    rest.find('\n').map(|newline| &rest[newline + 1..]).unwrap_or(rest)
+}
+
+/// Project files named in the test output (`src/lib.rs:42:5`, `at tests/api.py:10`), in
+/// order of first mention.
+pub(crate) fn referenced_files(output: &str, root: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = vec![];
+    for token in output.split(|c: char| c.is_whitespace() || "()[]{}<>,'\"`".contains(c)) {
+        // Drop `:line:column` suffixes
+        let path = token.split(':').next().unwrap_or_default().trim_start_matches("./");
+        if path.is_empty() || !path.contains('.') || Path::new(path).is_absolute() || path.contains("..") {
+            continue;
+        }
+        let path = PathBuf::from(path);
+        if root.join(&path).is_file() && !files.contains(&path) {
+            files.push(path);
+        }
+        if files.len() == MAX_SOURCE_FILES {
+            break;
+        }
+    }
+    files
+}
+
+pub(crate) fn format_source_files(root: &Path, files: &[PathBuf]) -> String {
+    if files.is_empty() {
+        return "No project files are referenced in the output.".to_string();
+    }
+    files.iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(root.join(path)).ok()?;
+            let shown: String = content.chars().take(MAX_SOURCE_CHARS).collect();
+            let truncated = if shown.len() < content.len() { "\n... (truncated)" } else { "" };
+            Some(format!("--- {} ---\n```\n{}{}\n```", path.display(), shown, truncated))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn build_fix_tests_prompt(template: &str, project: &Project, test_command: &str, test_output: &str, source_files: &str) -> String {
+    template
+        .replace("{{primary_language}}", &get_primary_language(&project.meta))
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{tech_stack}}", &project.meta.tech_stack.join(", "))
+        .replace("{{test_command}}", test_command)
+        .replace("{{test_output}}", tail(test_output, MAX_OUTPUT_CHARS))
+        .replace("{{source_files}}", source_files)
+}
+
+/// Run the test suite and, when it fails, ask the LLM for fixes based on the failure
+/// output and the files it mentions. Fixes go through the same per-change approval as
+/// `execute-task`; the tests can then be re-run once.
+pub async fn run(ctx: &AppContext, command: Option<String>) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let test_command = match command {
+        Some(command) => command,
+        None => detect_test_command(&root)
+            .context("Could not tell how to run the tests here. Pass one with --command \"...\"")?
+            .to_string(),
+    };
+
+    let (passed, output) = run_tests(&test_command)?;
+    if passed {
+        println!("✅ Tests pass, nothing to fix");
+        return Ok(());
+    }
+    println!("❌ Tests failed");
+
+    let project = diagnose_and_fix(ctx, project, &root, &test_command, &output).await?;
+    hints::print_next_steps(Some(&project), &[
+        ("git diff", "Review the applied fixes"),
+        ("env-coach fix-tests", "Try again with the new failures"),
+    ]);
+    Ok(())
+}
+
+/// Send failing test output and the files it mentions to the LLM and apply its fixes one
+/// by one; the tests can then be re-run once. Returns the project as updated.
+pub(crate) async fn diagnose_and_fix(ctx: &AppContext, project: Project, root: &Path, test_command: &str, output: &str) -> Result<Project> {
+    let files = referenced_files(output, root);
+    if !files.is_empty() {
+        println!("📂 Sending {} referenced file(s): {}", files.len(),
+            files.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "));
+    }
+
+    let prompt_template_path = Path::new(".env-coach/prompts/fix_tests.md");
+    let prompt_template = match prompts::read_template(prompt_template_path, &get_primary_language(&project.meta)) {
+        Some(template) => template,
+        None => {
+            println!("⚠️ Fix-tests prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_fix_tests_prompt_content()
+        }
+    };
+    let prompt_template = prompts::fill_project_context(&prompt_template, &project, None);
+    let prompt = build_fix_tests_prompt(&prompt_template, &project, test_command, output, &format_source_files(root, &files));
+
+    println!("🔍 Asking LLM to diagnose the failures...");
+    let response = ollama::send_generation_prompt(&project.llm_for(LlmRole::Coder), &prompt)
+        .await
+        .context("Failed to get fix suggestions from LLM")?;
+    let parsed = parse_assist_task_response(&response).context("The LLM did not return structured fix suggestions")?;
+    if let Some(summary) = &parsed.overall_summary {
+        println!("ℹ️ {}", summary);
+    }
+
+    let mut updater = AutoUpdater::with_context(project, ctx);
+    let outcomes = updater.apply_suggestions(root, &parsed.suggestions, &HashSet::new(), |_, _| Ok(()))?;
+    let applied = outcomes.iter().filter(|(_, outcome)| matches!(outcome, SuggestionOutcome::Applied)).count();
+    println!();
+    println!("📊 {} of {} suggestion(s) applied", applied, parsed.suggestions.len());
+
+    if applied > 0 && !ctx.dry_run && ctx.prompter.confirm("👉 Re-run the tests?")? {
+        let (passed, _) = run_tests(test_command)?;
+        println!("{}", if passed { "✅ Tests pass now" } else { "❌ Tests still fail" });
+    }
+    Ok(updater.get_project().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_test_command() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(detect_test_command(dir.path()), None);
+        std::fs::write(dir.path().join("package.json"), "{}").unwrap();
+        assert_eq!(detect_test_command(dir.path()), Some("npm test"));
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        assert_eq!(detect_test_command(dir.path()), Some("cargo test"));
+    }
+
+    #[test]
+    fn test_referenced_files_and_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/parser.rs"), "fn parse() {}").unwrap();
+        let output = "thread 'parser::tests::t' panicked at src/parser.rs:42:5:\n\
+                      assertion failed (./src/parser.rs:50)\n  at /rustc/abc/library/core/src/panicking.rs:1\n  in src/missing.rs:3";
+        assert_eq!(referenced_files(output, dir.path()), vec![PathBuf::from("src/parser.rs")]);
+
+        assert_eq!(tail("one\ntwo\nthree", 7), "three");
+        assert_eq!(tail("short", 100), "short");
+    }
+}
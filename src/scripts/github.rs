@@ -0,0 +1,231 @@
+// src/scripts/github.rs
+//! Two-way sync between the backlog and GitHub Issues. Items are linked through
+//! `external_ref = "github:<number>"`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+
+use crate::config::{BacklogItem, Project, Status};
+use crate::context::AppContext;
+use crate::github::{self, GitHubClient, Issue};
+use crate::hints;
+use crate::scripts::tasks::mark_item_done;
+
+/// A difference between a linked item and its issue, and how it is resolved.
+#[derive(Debug, PartialEq)]
+enum Change {
+    /// The issue was closed on GitHub
+    MarkDone { id: String, closed_at: DateTime<Utc> },
+    /// The item was completed here while the issue stayed open
+    CloseIssue { id: String, number: u64 },
+    /// Labels changed on GitHub; they replace the item's tags
+    SetTags { id: String, tags: Vec<String> },
+}
+
+impl Change {
+    fn describe(&self) -> String {
+        match self {
+            Change::MarkDone { id, .. } => format!("{}: issue closed on GitHub → mark Done", id),
+            Change::CloseIssue { id, number } => format!("{}: Done here → close issue #{}", id, number),
+            Change::SetTags { id, tags } if tags.is_empty() => format!("{}: labels removed on GitHub → clear tags", id),
+            Change::SetTags { id, tags } => format!("{}: labels on GitHub → tags {}", id, tags.join(", ")),
+        }
+    }
+}
+
+fn pull_changes(item: &BacklogItem, issue: &Issue) -> Vec<Change> {
+    let mut changes = vec![];
+    let done = matches!(item.status, Status::Done);
+    if issue.is_closed() && !done {
+        changes.push(Change::MarkDone { id: item.id.clone(), closed_at: issue.closed_at.unwrap_or_else(Utc::now) });
+    } else if !issue.is_closed() && done {
+        changes.push(Change::CloseIssue { id: item.id.clone(), number: issue.number });
+    }
+    let mut labels: Vec<String> = issue.labels.iter().map(|label| label.name.clone()).collect();
+    let mut tags = item.tags.clone();
+    labels.sort();
+    tags.sort();
+    if labels != tags {
+        changes.push(Change::SetTags { id: item.id.clone(), tags: labels });
+    }
+    changes
+}
+
+/// Issue description: the story, its acceptance criteria as a checklist, and the item ID.
+pub(crate) fn issue_body(item: &BacklogItem) -> String {
+    let mut sections = vec![];
+    if !item.story.trim().is_empty() {
+        sections.push(item.story.trim().to_string());
+    }
+    if !item.acceptance_criteria.is_empty() {
+        let checklist: Vec<String> = item.acceptance_criteria.iter().map(|criterion| format!("- [ ] {}", criterion)).collect();
+        sections.push(format!("### Acceptance criteria\n{}", checklist.join("\n")));
+    }
+    sections.push(format!("_Tracked in env-coach as {}._", item.id));
+    sections.join("\n\n")
+}
+
+fn linked_issue(item: &BacklogItem) -> Option<u64> {
+    item.external_ref.as_deref().and_then(github::issue_number)
+}
+
+async fn apply_change(ctx: &AppContext, client: &GitHubClient, project: &mut Project, change: &Change) -> Result<()> {
+    match change {
+        Change::MarkDone { id, closed_at } => {
+            mark_item_done(project, id, *closed_at)?;
+        }
+        Change::CloseIssue { number, .. } => {
+            if ctx.dry_run {
+                println!("🔍 Dry run: issue #{} not closed", number);
+            } else {
+                client.close_issue(*number).await?;
+            }
+        }
+        Change::SetTags { id, tags } => {
+            if let Some(item) = project.backlog.iter_mut().find(|item| &item.id == id) {
+                item.tags = tags.clone();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Open issues for items that have none yet, stopping at the first failure. Returns the
+/// IDs linked so far together with the error, if any.
+async fn create_issues(client: &GitHubClient, project: &mut Project, ids: &[String]) -> (Vec<String>, Option<anyhow::Error>) {
+    let mut created = vec![];
+    for id in ids {
+        let Some(item) = project.backlog.iter_mut().find(|item| &item.id == id) else { continue };
+        match client.create_issue(&item.title, &issue_body(item), &item.tags).await {
+            Ok(issue) => {
+                println!("✅ {} → #{} {}", id, issue.number, issue.html_url);
+                item.external_ref = Some(github::issue_ref(issue.number));
+                created.push(id.clone());
+            }
+            Err(e) => return (created, Some(e)),
+        }
+    }
+    (created, None)
+}
+
+/// `github sync`: pull closed issues and label changes into the backlog, close issues of
+/// items completed here, and open issues for items that have none.
+pub async fn sync(ctx: &AppContext) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let client = GitHubClient::from_env()?;
+    println!("🐙 Syncing the backlog with {}", client.repo());
+
+    let mut changes = vec![];
+    for item in &project.backlog {
+        let Some(number) = linked_issue(item) else { continue };
+        let issue = client.issue(number).await.with_context(|| format!("Failed to read the issue linked to {}", item.id))?;
+        changes.extend(pull_changes(item, &issue));
+    }
+    let unlinked: Vec<String> = project.backlog.iter()
+        .filter(|item| item.external_ref.is_none() && !matches!(item.status, Status::Done))
+        .map(|item| item.id.clone())
+        .collect();
+
+    if changes.is_empty() && unlinked.is_empty() {
+        println!("✅ Backlog and GitHub issues are in sync");
+        return Ok(());
+    }
+
+    if !changes.is_empty() {
+        println!("🔄 Changes ({}):", changes.len());
+        for change in &changes {
+            println!("   - {}", change.describe());
+        }
+        if ctx.prompter.confirm("👉 Apply these changes?")? {
+            for change in &changes {
+                apply_change(ctx, &client, &mut project, change).await?;
+            }
+        }
+    }
+
+    let mut error = None;
+    if !unlinked.is_empty() {
+        println!("📤 Items without an issue ({}):", unlinked.len());
+        for id in &unlinked {
+            let title = project.backlog.iter().find(|item| &item.id == id).map(|item| item.title.as_str()).unwrap_or_default();
+            println!("   - {} {}", id, title);
+        }
+        if ctx.prompter.confirm(&format!("👉 Create {} GitHub issue(s)?", unlinked.len()))? {
+            if ctx.dry_run {
+                println!("🔍 Dry run: no issues created");
+            } else {
+                // Save the issues created before a failure, or a re-run would duplicate them
+                let (created, failure) = create_issues(&client, &mut project, &unlinked).await;
+                println!("📊 Created {} issue(s)", created.len());
+                error = failure;
+            }
+        }
+    }
+
+    ctx.save_project(&project).context("Failed to save project")?;
+    if let Some(e) = error {
+        return Err(e);
+    }
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach list-backlog", "See the items with their issue links"),
+    ]);
+    Ok(())
+}
+
+/// Close the GitHub issue linked to a completed item. Failures are reported but do not
+/// undo the completion.
+pub(crate) async fn close_linked_issue(ctx: &AppContext, item: &BacklogItem) {
+    let Some(number) = linked_issue(item) else { return };
+    if ctx.dry_run {
+        println!("🔍 Dry run: issue #{} not closed", number);
+        return;
+    }
+    let closed = match GitHubClient::from_env() {
+        Ok(client) => client.close_issue(number).await,
+        Err(e) => Err(e),
+    };
+    match closed {
+        Ok(()) => println!("🐙 Closed GitHub issue #{}", number),
+        Err(e) => println!("⚠️ Could not close GitHub issue #{}: {}. 'env-coach github sync' will retry.", number, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::Label;
+
+    fn item(status: Status, tags: &[&str]) -> BacklogItem {
+        BacklogItem::test("US-001").with_title("Login").with_story("As a user, I want to log in")
+            .with_criteria(&["Wrong passwords are rejected"]).with_status(status).with_tags(tags).with_external_ref("github:7")
+    }
+
+    fn issue(state: &str, labels: &[&str]) -> Issue {
+        Issue {
+            number: 7,
+            state: state.to_string(),
+            labels: labels.iter().map(|name| Label { name: name.to_string() }).collect(),
+            closed_at: None,
+            html_url: "https://github.com/acme/api/issues/7".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_pull_changes() {
+        assert_eq!(pull_changes(&item(Status::Todo, &["ux", "api"]), &issue("open", &["api", "ux"])), vec![]);
+
+        let changes = pull_changes(&item(Status::InProgress, &[]), &issue("closed", &["ux"]));
+        assert!(matches!(&changes[0], Change::MarkDone { id, .. } if id == "US-001"));
+        assert_eq!(changes[1], Change::SetTags { id: "US-001".to_string(), tags: vec!["ux".to_string()] });
+
+        assert_eq!(pull_changes(&item(Status::Done, &[]), &issue("open", &[])), vec![
+            Change::CloseIssue { id: "US-001".to_string(), number: 7 },
+        ]);
+    }
+
+    #[test]
+    fn test_issue_body() {
+        assert_eq!(issue_body(&item(Status::Todo, &[])),
+            "As a user, I want to log in\n\n### Acceptance criteria\n- [ ] Wrong passwords are rejected\n\n_Tracked in env-coach as US-001._");
+    }
+}
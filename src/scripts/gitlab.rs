@@ -0,0 +1,145 @@
+// src/scripts/gitlab.rs
+//! `gitlab push`: create GitLab issues (and epics) for backlog items. Created items are
+//! linked through `external_ref` (`gitlab:<iid>` for issues, `gitlab-epic:<iid>` for epics).
+
+use anyhow::{Context, Result};
+
+use crate::config::{BacklogItem, ItemType, Project, Status};
+use crate::context::AppContext;
+use crate::gitlab::{self, GitLabClient};
+use crate::hints;
+use crate::scripts::github::issue_body;
+
+/// Where a child's epic lives on GitLab once pushed.
+#[derive(Debug, PartialEq)]
+enum ParentLink {
+    Epic(u64),
+    /// Epic pushed with `--epics-as-issues`
+    Issue(u64),
+}
+
+/// Open items without an external reference, epics first so children can be linked to them.
+fn push_order(project: &Project) -> Vec<String> {
+    let pending = |item: &&BacklogItem| item.external_ref.is_none() && !matches!(item.status, Status::Done);
+    let (epics, others): (Vec<&BacklogItem>, Vec<&BacklogItem>) = project.backlog.iter()
+        .filter(pending)
+        .partition(|item| matches!(item.item_type, ItemType::Epic));
+    epics.into_iter().chain(others).map(|item| item.id.clone()).collect()
+}
+
+fn parent_link(project: &Project, item: &BacklogItem) -> Option<ParentLink> {
+    let parent = project.backlog.iter().find(|other| Some(&other.id) == item.parent.as_ref())?;
+    let external_ref = parent.external_ref.as_deref()?;
+    gitlab::ref_iid(external_ref, "gitlab-epic").map(ParentLink::Epic)
+        .or_else(|| gitlab::ref_iid(external_ref, "gitlab").map(ParentLink::Issue))
+}
+
+/// Create the issue or epic for one item and return its new reference.
+async fn push_item(client: &GitLabClient, item: &BacklogItem, parent: Option<ParentLink>, epics_as_issues: bool) -> Result<String> {
+    let mut description = issue_body(item);
+    if let Some(ParentLink::Issue(iid)) = parent {
+        description.push_str(&format!("\n\nPart of #{}", iid));
+    }
+
+    if matches!(item.item_type, ItemType::Epic) && !epics_as_issues {
+        let epic = client.create_epic(&item.title, &description, &item.tags).await
+            .context("Epics need GitLab Premium; re-run with --epics-as-issues to create them as issues")?;
+        println!("✅ {} → epic &{} {}", item.id, epic.iid, epic.web_url);
+        return Ok(gitlab::epic_ref(epic.iid));
+    }
+
+    let mut labels = item.tags.clone();
+    if matches!(item.item_type, ItemType::Epic) {
+        labels.push("epic".to_string());
+    }
+    let issue = client.create_issue(&item.title, &description, &labels).await?;
+    println!("✅ {} → #{} {}", item.id, issue.iid, issue.web_url);
+    if let Some(ParentLink::Epic(epic_iid)) = parent {
+        if let Err(e) = client.add_issue_to_epic(epic_iid, issue.id).await {
+            println!("⚠️ #{} was created but not added to epic &{}: {}", issue.iid, epic_iid, e);
+        }
+    }
+    Ok(gitlab::issue_ref(issue.iid))
+}
+
+/// Create GitLab issues for open items that are not linked to a tracker yet. Epics become
+/// group epics, or labelled issues with `--epics-as-issues`.
+pub async fn push(ctx: &AppContext, epics_as_issues: bool) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let client = GitLabClient::from_env()?;
+    println!("🦊 Pushing the backlog to {}", client.project());
+
+    let pending = push_order(&project);
+    if pending.is_empty() {
+        println!("✅ Every open item is already linked to an issue");
+        return Ok(());
+    }
+    println!("📤 Items without an issue ({}):", pending.len());
+    for id in &pending {
+        if let Some(item) = project.backlog.iter().find(|item| &item.id == id) {
+            println!("   - {} [{:?}] {}", id, item.item_type, item.title);
+        }
+    }
+    if !ctx.prompter.confirm(&format!("👉 Create {} GitLab issue(s)/epic(s)?", pending.len()))? {
+        println!("❌ Push cancelled");
+        return Ok(());
+    }
+    if ctx.dry_run {
+        println!("🔍 Dry run: nothing created on GitLab");
+        return Ok(());
+    }
+
+    let mut created = 0;
+    let mut failure = None;
+    for id in &pending {
+        let Some(item) = project.backlog.iter().find(|item| &item.id == id) else { continue };
+        let parent = parent_link(&project, item);
+        match push_item(&client, item, parent, epics_as_issues).await {
+            Ok(external_ref) => {
+                if let Some(item) = project.backlog.iter_mut().find(|item| &item.id == id) {
+                    item.external_ref = Some(external_ref);
+                }
+                created += 1;
+            }
+            Err(e) => {
+                failure = Some(e.context(format!("Failed to push {}", id)));
+                break;
+            }
+        }
+    }
+
+    // Save what was created before a failure, or a re-run would duplicate it
+    ctx.save_project(&project).context("Failed to save project")?;
+    println!("📊 Created {} of {} item(s)", created, pending.len());
+    if let Some(e) = failure {
+        return Err(e);
+    }
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach list-backlog", "See the items with their issue links"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_order_and_parent_links() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog = vec![
+            BacklogItem::test("US-001").with_parent("EPIC-001"),
+            BacklogItem::test("US-002").with_status(Status::Done),
+            BacklogItem::test("US-003").with_parent("EPIC-002").with_external_ref("jira:SHOP-3"),
+            BacklogItem::test("EPIC-001").with_type(ItemType::Epic),
+            BacklogItem::test("EPIC-002").with_type(ItemType::Epic).with_external_ref("gitlab-epic:4"),
+            BacklogItem::test("EPIC-003").with_type(ItemType::Epic).with_external_ref("gitlab:9"),
+            BacklogItem::test("US-004").with_parent("EPIC-003"),
+        ];
+        assert_eq!(push_order(&project), vec!["EPIC-001", "US-001", "US-004"]);
+
+        assert_eq!(parent_link(&project, &project.backlog[0]), None);
+        assert_eq!(parent_link(&project, &project.backlog[2]), Some(ParentLink::Epic(4)));
+        assert_eq!(parent_link(&project, &project.backlog[6]), Some(ParentLink::Issue(9)));
+    }
+}
@@ -0,0 +1,152 @@
+// src/scripts/graph.rs
+//! Dependency graph of the backlog, as text or as a Mermaid flowchart for docs and PRs.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+
+use crate::config::{BacklogItem, ItemType, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::scripts::epics;
+
+/// Mermaid node IDs cannot contain `-`, so `US-001` becomes `US_001`.
+fn node_id(item_id: &str) -> String {
+    item_id.replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+}
+
+/// Quoted Mermaid label; `"` would end the string early.
+fn label(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "#quot;"))
+}
+
+fn node(item: &BacklogItem) -> String {
+    format!("{}[{}]", node_id(&item.id), label(&format!("{}: {}<br/>{} pts · {:?}", item.id, item.title, item.effort, item.status)))
+}
+
+/// An epic that has no subgraph (no children) but is depended on, as a hexagon.
+fn epic_node(epic: &BacklogItem) -> String {
+    format!("{}{{{{{}}}}}", node_id(&epic.id), label(&format!("Epic {}: {}", epic.id, epic.title)))
+}
+
+fn status_class(status: &Status) -> &'static str {
+    match status {
+        Status::Todo => "todo",
+        Status::InProgress => "inProgress",
+        Status::Review => "review",
+        Status::Done => "done",
+    }
+}
+
+/// Mermaid flowchart: one node per work item, epics as subgraphs around their children
+/// (or a hexagon when an epic without children is depended on), and an edge from each
+/// dependency to the item waiting on it.
+fn render_mermaid(project: &Project) -> String {
+    let work: Vec<&BacklogItem> = project.backlog.iter().filter(|item| !matches!(item.item_type, ItemType::Epic)).collect();
+    let mut out = String::from("flowchart LR\n");
+
+    for epic in epics::epics(project) {
+        let children: Vec<&&BacklogItem> = work.iter().filter(|item| item.parent.as_deref() == Some(epic.id.as_str())).collect();
+        if children.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("    subgraph {}[{}]\n", node_id(&epic.id), label(&format!("{}: {}", epic.id, epic.title))));
+        for child in children {
+            out.push_str(&format!("        {}\n", node(child)));
+        }
+        out.push_str("    end\n");
+    }
+    let has_epic = |item: &BacklogItem| {
+        item.parent.as_ref().is_some_and(|parent| epics::epics(project).any(|epic| &epic.id == parent))
+    };
+    for item in work.iter().filter(|item| !has_epic(item)) {
+        out.push_str(&format!("    {}\n", node(item)));
+    }
+
+    let dependencies: BTreeSet<&String> = work.iter().flat_map(|item| &item.dependencies).collect();
+    for dependency in dependencies {
+        if work.iter().any(|item| &item.id == dependency) {
+            continue;
+        }
+        match epics::epics(project).find(|epic| &epic.id == dependency) {
+            Some(epic) if !work.iter().any(|item| item.parent.as_ref() == Some(&epic.id)) => out.push_str(&format!("    {}\n", epic_node(epic))),
+            // Its subgraph is already a node
+            Some(_) => {}
+            // Archived or removed: still show what the item was waiting on
+            None => out.push_str(&format!("    {}[{}]\n", node_id(dependency), label(&format!("{} (not in backlog)", dependency)))),
+        }
+    }
+    for item in &work {
+        for dependency in &item.dependencies {
+            out.push_str(&format!("    {} --> {}\n", node_id(dependency), node_id(&item.id)));
+        }
+    }
+
+    out.push_str("    classDef todo fill:#f8f9fa,stroke:#6c757d\n");
+    out.push_str("    classDef inProgress fill:#fff3cd,stroke:#d39e00\n");
+    out.push_str("    classDef review fill:#cfe2ff,stroke:#0d6efd\n");
+    out.push_str("    classDef done fill:#d1e7dd,stroke:#198754\n");
+    for status in [Status::Todo, Status::InProgress, Status::Review, Status::Done] {
+        let ids: Vec<String> = work.iter().filter(|item| item.status == status).map(|item| node_id(&item.id)).collect();
+        if !ids.is_empty() {
+            out.push_str(&format!("    class {} {}\n", ids.join(","), status_class(&status)));
+        }
+    }
+    out
+}
+
+/// Print the dependency graph; `--mermaid` emits a flowchart to paste into Markdown.
+pub fn run(ctx: &AppContext, mermaid: bool) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    if mermaid {
+        // Only the chart goes to stdout, so it can be redirected into a file
+        print!("{}", render_mermaid(&project));
+        return Ok(());
+    }
+
+    let with_dependencies: Vec<&BacklogItem> = project.backlog.iter().filter(|item| !item.dependencies.is_empty()).collect();
+    if with_dependencies.is_empty() {
+        println!("🔗 No backlog item has dependencies");
+    } else {
+        println!("🔗 Dependencies ({} items):", with_dependencies.len());
+        for item in with_dependencies {
+            println!("   {} [{:?}] ← {}", item.id, item.status, item.dependencies.join(", "));
+        }
+    }
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach graph --mermaid > docs/plan.mmd", "Export the graph for docs and PRs"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_mermaid() {
+        let item = |id: &str| BacklogItem::test(id).with_title(&format!("Title \"{}\"", id));
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog = vec![
+            item("EPIC-001").with_type(ItemType::Epic),
+            item("US-001").with_status(Status::Done).with_parent("EPIC-001"),
+            item("US-002").with_parent("EPIC-001").with_dependencies(&["US-001"]),
+            item("BUG-001").with_type(ItemType::Bug).with_status(Status::InProgress).with_dependencies(&["US-002", "US-000"]),
+            item("EPIC-002").with_type(ItemType::Epic),
+            item("US-003").with_dependencies(&["US-000", "EPIC-001", "EPIC-002"]),
+        ];
+
+        let chart = render_mermaid(&project);
+        assert!(chart.starts_with("flowchart LR\n    subgraph EPIC_001[\"EPIC-001: Title #quot;EPIC-001#quot;\"]\n        US_001["));
+        assert!(chart.contains("    end\n    BUG_001[\"BUG-001: Title #quot;BUG-001#quot;<br/>3 pts · InProgress\"]\n"));
+        assert!(chart.contains("    US_001 --> US_002\n"));
+        assert_eq!(chart.matches("US_000[\"US-000 (not in backlog)\"]").count(), 1);
+        assert!(chart.contains("    US_000 --> BUG_001\n"));
+        assert!(chart.contains("    US_000 --> US_003\n"));
+        assert!(!chart.contains("EPIC_001[\"EPIC-001 (not in backlog)"));
+        assert!(chart.contains("    EPIC_001 --> US_003\n"));
+        assert!(chart.contains("    EPIC_002{{\"Epic EPIC-002: Title #quot;EPIC-002#quot;\"}}\n"));
+        assert!(chart.contains("    class US_002,US_003 todo\n"));
+        assert!(chart.contains("    class US_001 done\n"));
+    }
+}
@@ -0,0 +1,288 @@
+// src/scripts/groom.rs
+//! `groom`: an LLM refinement pass over the Todo backlog that flags vague stories, missing
+//! acceptance criteria, outdated items and priorities that look wrong, each applied only
+//! once accepted.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::config::{BacklogItem, ItemType, LlmRole, Priority, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::scripts::{archive, prd, set_field};
+use crate::templates::Templates;
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum LlmFinding {
+    Vague { id: String, #[serde(default)] reason: String, story: String },
+    MissingCriteria { id: String, #[serde(default)] reason: String, acceptance_criteria: Vec<String> },
+    Outdated { id: String, #[serde(default)] reason: String },
+    Priority { id: String, #[serde(default)] reason: String, priority: String },
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct LlmGrooming {
+    #[serde(default)]
+    findings: Vec<LlmFinding>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Change {
+    Story(String),
+    AddCriteria(Vec<String>),
+    Archive,
+    Priority(Priority),
+}
+
+/// A change the LLM proposes for one Todo item, with its reason.
+#[derive(Debug)]
+struct Finding {
+    item_id: String,
+    reason: String,
+    change: Change,
+}
+
+impl Finding {
+    fn kind(&self) -> &'static str {
+        match self.change {
+            Change::Story(_) => "vague story",
+            Change::AddCriteria(_) => "missing acceptance criteria",
+            Change::Archive => "outdated",
+            Change::Priority(_) => "priority",
+        }
+    }
+
+    fn emoji(&self) -> &'static str {
+        match self.change {
+            Change::Story(_) => "✍️ ",
+            Change::AddCriteria(_) => "✅",
+            Change::Archive => "📦",
+            Change::Priority(_) => "🔺",
+        }
+    }
+}
+
+fn build_groom_prompt(template: &str, project: &Project, todo: &[&BacklogItem]) -> String {
+    let now = Utc::now();
+    let backlog = todo.iter()
+        .map(|item| {
+            let mut line = format!("- {} [{:?}, {} pts, {} days] {}: {}",
+                item.id, item.priority, item.effort, (now - item.created).num_days(), item.title, item.story);
+            for criterion in &item.acceptance_criteria {
+                line.push_str(&format!("\n  - {}", criterion));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    template
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{today}}", &now.format("%Y-%m-%d").to_string())
+        .replace("{{completed}}", &prd::format_items(&prd::completed_items(project), "Nothing completed yet."))
+        .replace("{{backlog}}", &backlog)
+}
+
+pub(crate) fn parse_groom_response(response: &str) -> Result<LlmGrooming> {
+    serde_json::from_str(response.trim()).with_context(|| {
+        let snippet = response.chars().take(500).collect::<String>();
+        format!("Failed to parse LLM backlog grooming. Response (first 500 chars): '{}'", snippet)
+    })
+}
+
+/// Keep the findings that would change something: the item must be a Todo item, new
+/// criteria must not be there already, and a priority must be valid and different.
+fn check_findings(project: &Project, grooming: LlmGrooming) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = vec![];
+    for finding in grooming.findings {
+        let (item_id, reason, change) = match finding {
+            LlmFinding::Vague { id, reason, story } => (id, reason, Change::Story(story.trim().to_string())),
+            LlmFinding::MissingCriteria { id, reason, acceptance_criteria } => (id, reason, Change::AddCriteria(acceptance_criteria)),
+            LlmFinding::Outdated { id, reason } => (id, reason, Change::Archive),
+            LlmFinding::Priority { id, reason, priority } => match priority.parse::<Priority>() {
+                Ok(priority) => (id, reason, Change::Priority(priority)),
+                Err(_) => continue,
+            },
+        };
+        let Some(item) = project.backlog.iter().find(|item| {
+            item.id == item_id && matches!(item.status, Status::Todo) && !matches!(item.item_type, ItemType::Epic)
+        }) else { continue };
+        let change = match change {
+            Change::Story(story) if story.is_empty() || story == item.story => continue,
+            Change::AddCriteria(criteria) => {
+                let new: Vec<String> = criteria.into_iter()
+                    .map(|criterion| criterion.trim().to_string())
+                    .filter(|criterion| !criterion.is_empty() && !item.acceptance_criteria.contains(criterion))
+                    .collect();
+                if new.is_empty() {
+                    continue;
+                }
+                Change::AddCriteria(new)
+            }
+            Change::Priority(priority) if priority == item.priority => continue,
+            change => change,
+        };
+        if !findings.iter().any(|other| other.item_id == item_id && other.change == change) {
+            findings.push(Finding { item_id, reason, change });
+        }
+    }
+    findings
+}
+
+fn print_findings(project: &Project, findings: &[Finding]) {
+    println!("🧹 Backlog grooming: {} finding(s)", findings.len());
+    for (i, finding) in findings.iter().enumerate() {
+        let Some(item) = project.backlog.iter().find(|item| item.id == finding.item_id) else { continue };
+        println!();
+        println!("   {}. {} {}: {} - {}", i + 1, finding.emoji(), finding.kind(), item.id, item.title);
+        if !finding.reason.is_empty() {
+            println!("      {}", finding.reason);
+        }
+        match &finding.change {
+            Change::Story(story) => println!("      → {}", story),
+            Change::AddCriteria(criteria) => {
+                for criterion in criteria {
+                    println!("      + {}", criterion);
+                }
+            }
+            Change::Archive => println!("      → archive"),
+            Change::Priority(priority) => println!("      → {:?} → {:?}", item.priority, priority),
+        }
+    }
+}
+
+/// Apply one accepted finding. An item archived by an earlier finding is skipped.
+fn apply_finding(project: &mut Project, finding: &Finding) -> Result<bool> {
+    if !project.backlog.iter().any(|item| item.id == finding.item_id) {
+        return Ok(false);
+    }
+    match &finding.change {
+        Change::Archive => {
+            let (_, notes) = archive::archive_item(project, &finding.item_id)?;
+            println!("📦 Archived {}", finding.item_id);
+            archive::print_notes(&notes);
+        }
+        change => {
+            set_field::update_item(project, &finding.item_id, |item| match change {
+                Change::Story(story) => item.story = story.clone(),
+                Change::AddCriteria(criteria) => item.acceptance_criteria.extend(criteria.iter().cloned()),
+                Change::Priority(priority) => item.priority = priority.clone(),
+                Change::Archive => {}
+            })?;
+        }
+    }
+    Ok(true)
+}
+
+/// Review the Todo backlog with the LLM, then accept all findings, pick them one by one,
+/// or skip them, as when reviewing a sprint plan.
+pub async fn run(ctx: &AppContext) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let todo: Vec<&BacklogItem> = project.backlog.iter()
+        .filter(|item| matches!(item.status, Status::Todo) && !matches!(item.item_type, ItemType::Epic))
+        .collect();
+    if todo.is_empty() {
+        println!("✅ No Todo items to groom");
+        return Ok(());
+    }
+
+    let prompt_template_path = std::path::Path::new(".env-coach/prompts/backlog_groomer.md");
+    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
+        Ok(template) => template,
+        Err(_) => {
+            println!("⚠️ Backlog groomer prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_backlog_groomer_prompt_content()
+        }
+    };
+    let prompt_template = prompts::fill_project_context(&prompt_template, &project, None);
+    let prompt = build_groom_prompt(&prompt_template, &project, &todo);
+
+    println!("🤖 Asking LLM to groom {} Todo item(s)...", todo.len());
+    let response = ollama::send_generation_prompt(&project.llm_for(LlmRole::Planner), &prompt)
+        .await
+        .context("LLM call for backlog grooming failed")?;
+    let findings = check_findings(&project, parse_groom_response(&response)?);
+    if findings.is_empty() {
+        println!("✅ The LLM found nothing to groom");
+        return Ok(());
+    }
+    print_findings(&project, &findings);
+
+    let user_choice = if ctx.prompter.assume_yes() {
+        println!("\n👉 Accepting findings [--yes]");
+        "yes".to_string()
+    } else {
+        ctx.prompter.ask("\n👉 Do you want to apply these changes? (yes/no/edit): ")?
+    };
+    let accepted: Vec<&Finding> = match user_choice.to_lowercase().as_str() {
+        "yes" | "y" => findings.iter().collect(),
+        "edit" | "e" => {
+            let mut accepted = vec![];
+            for (i, finding) in findings.iter().enumerate() {
+                if ctx.prompter.confirm(&format!("   Apply {}. {} for {}?", i + 1, finding.kind(), finding.item_id))? {
+                    accepted.push(finding);
+                }
+            }
+            accepted
+        }
+        _ => vec![],
+    };
+    if accepted.is_empty() {
+        println!("❌ No changes applied");
+        return Ok(());
+    }
+
+    let mut applied = 0;
+    for finding in accepted {
+        if apply_finding(&mut project, finding)? {
+            applied += 1;
+        }
+    }
+    ctx.save_project(&project).context("Failed to save project")?;
+    println!();
+    println!("✅ Applied {} of {} finding(s)", applied, findings.len());
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach list-backlog --status todo", "Review the groomed backlog"),
+        ("env-coach estimate", "Re-estimate the refined items"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_and_apply_findings() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        let item = |id: &str| BacklogItem::test(id).with_story("Improve search").with_criteria(&["Write unit tests"]);
+        project.backlog = vec![item("US-001"), item("US-002").with_status(Status::Done), item("US-003")];
+        let grooming = parse_groom_response(r#"{"findings": [
+            {"kind": "vague", "id": "US-001", "reason": "Too short.", "story": "As a shopper, I want partial matches"},
+            {"kind": "missing_criteria", "id": "US-001", "acceptance_criteria": ["Write unit tests", "'lapt' finds laptops"]},
+            {"kind": "priority", "id": "US-001", "priority": "medium"},
+            {"kind": "priority", "id": "US-003", "priority": "urgent"},
+            {"kind": "outdated", "id": "US-002"},
+            {"kind": "outdated", "id": "US-003", "reason": "Duplicate of US-001."},
+            {"kind": "priority", "id": "US-003", "priority": "High"}
+        ]}"#).unwrap();
+        let findings = check_findings(&project, grooming);
+        let changes: Vec<(&str, &Change)> = findings.iter().map(|f| (f.item_id.as_str(), &f.change)).collect();
+        assert_eq!(changes, vec![
+            ("US-001", &Change::Story("As a shopper, I want partial matches".to_string())),
+            ("US-001", &Change::AddCriteria(vec!["'lapt' finds laptops".to_string()])),
+            ("US-003", &Change::Archive),
+            ("US-003", &Change::Priority(Priority::High)),
+        ]);
+
+        let applied: Vec<bool> = findings.iter().map(|finding| apply_finding(&mut project, finding).unwrap()).collect();
+        assert_eq!(applied, vec![true, true, true, false]);
+        assert_eq!(project.backlog[0].story, "As a shopper, I want partial matches");
+        assert_eq!(project.backlog[0].acceptance_criteria.len(), 2);
+        assert_eq!(project.archive[0].item.id, "US-003");
+    }
+}
@@ -0,0 +1,46 @@
+// src/scripts/history.rs
+//! `history <id>`: an item's timeline from `.env-coach/history.jsonl`.
+
+use anyhow::{Context, Result};
+
+use crate::audit::{self, HistoryEvent};
+use crate::context::AppContext;
+use crate::scripts::show_item::find_item;
+
+fn describe(event: &HistoryEvent) -> String {
+    match (event.field.as_str(), &event.from) {
+        ("created", _) => format!("created as {}", event.to),
+        (field, Some(from)) => format!("{} {} → {}", field, from, event.to),
+        (field, None) => format!("{} → {}", field, event.to),
+    }
+}
+
+pub fn run(ctx: &AppContext, item_id: String) -> Result<()> {
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let log = audit::load(&root)?;
+    let events: Vec<&HistoryEvent> = log.iter().filter(|event| event.item == item_id).collect();
+    if ctx.format.is_structured() {
+        return ctx.print_structured(&events);
+    }
+
+    // Items removed since are still in the log, so the project is only asked for a title
+    let project = ctx.load_project().ok();
+    match project.as_ref().and_then(|project| find_item(project, &item_id)) {
+        Some((item, _)) => println!("📜 History of {} - {}", item.id, item.title),
+        None => println!("📜 History of {}", item_id),
+    }
+    if events.is_empty() {
+        println!("ℹ️  No recorded changes. Status, priority and effort changes are logged to {} from now on.", audit::HISTORY_FILE);
+        return Ok(());
+    }
+    println!();
+    for event in &events {
+        println!("   {}  {}  ({}, 'env-coach {}')",
+            event.at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"), describe(event), event.user, event.command);
+    }
+    if let Some(days) = audit::cycle_time_days(&events) {
+        println!();
+        println!("⏱️  Cycle time: {:.1} days (first in progress → done)", days);
+    }
+    Ok(())
+}
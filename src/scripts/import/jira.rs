@@ -0,0 +1,279 @@
+// src/scripts/import/jira.rs
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use super::ImportedItem;
+use crate::config::{ItemType, Priority, Status};
+use crate::context::AppContext;
+use crate::hints;
+
+/// Story points used when the export has none for an issue.
+const DEFAULT_EFFORT: u32 = 3;
+
+/// Backlog fields filled from a Jira CSV export.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Key,
+    IssueType,
+    Summary,
+    Description,
+    Priority,
+    StoryPoints,
+    Status,
+    Parent,
+}
+
+impl Field {
+    const ALL: [Field; 8] = [
+        Field::Key, Field::IssueType, Field::Summary, Field::Description,
+        Field::Priority, Field::StoryPoints, Field::Status, Field::Parent,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Field::Key => "issue key",
+            Field::IssueType => "issue type",
+            Field::Summary => "title",
+            Field::Description => "story",
+            Field::Priority => "priority",
+            Field::StoryPoints => "story points",
+            Field::Status => "status",
+            Field::Parent => "epic",
+        }
+    }
+
+    /// Column names Jira uses for the field, in order of preference (compared case-insensitively).
+    fn candidates(self) -> &'static [&'static str] {
+        match self {
+            Field::Key => &["Issue key", "Key"],
+            Field::IssueType => &["Issue Type", "Type"],
+            Field::Summary => &["Summary", "Title"],
+            Field::Description => &["Description"],
+            Field::Priority => &["Priority"],
+            Field::StoryPoints => &["Custom field (Story Points)", "Story Points", "Custom field (Story point estimate)", "Story point estimate"],
+            Field::Status => &["Status"],
+            Field::Parent => &["Parent", "Custom field (Epic Link)", "Epic Link", "Parent id"],
+        }
+    }
+}
+
+/// Column index per field; `None` leaves the field at its default.
+#[derive(Debug, Clone, PartialEq)]
+struct ColumnMapping {
+    columns: [Option<usize>; Field::ALL.len()],
+}
+
+impl ColumnMapping {
+    fn detect(headers: &[String]) -> Self {
+        let mut columns = [None; Field::ALL.len()];
+        for (slot, field) in columns.iter_mut().zip(Field::ALL) {
+            *slot = field.candidates().iter()
+                .find_map(|candidate| headers.iter().position(|header| header.trim().eq_ignore_ascii_case(candidate)));
+        }
+        Self { columns }
+    }
+
+    fn get(&self, field: Field) -> Option<usize> {
+        self.columns[field as usize]
+    }
+
+    fn set(&mut self, field: Field, column: Option<usize>) {
+        self.columns[field as usize] = column;
+    }
+
+    fn value<'a>(&self, record: &'a csv::StringRecord, field: Field) -> &'a str {
+        self.get(field).and_then(|column| record.get(column)).map(str::trim).unwrap_or_default()
+    }
+}
+
+fn print_mapping(headers: &[String], mapping: &ColumnMapping) {
+    println!("🗺️  Column mapping:");
+    for field in Field::ALL {
+        let column = mapping.get(field).map(|column| format!("{}. {}", column + 1, headers[column])).unwrap_or_else(|| "(not imported)".to_string());
+        println!("   {:<13} ← {}", field.label(), column);
+    }
+}
+
+/// Parse an answer to "which column?": a 1-based number or a header name; empty keeps `current`
+/// and `-` means none.
+fn parse_column(answer: &str, headers: &[String], current: Option<usize>) -> Result<Option<usize>> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return Ok(current);
+    }
+    if answer == "-" {
+        return Ok(None);
+    }
+    if let Ok(number) = answer.parse::<usize>() {
+        return match number.checked_sub(1).filter(|&column| column < headers.len()) {
+            Some(column) => Ok(Some(column)),
+            None => anyhow::bail!("There is no column {} (the file has {})", number, headers.len()),
+        };
+    }
+    headers.iter()
+        .position(|header| header.trim().eq_ignore_ascii_case(answer))
+        .map(Some)
+        .ok_or_else(|| anyhow::anyhow!("No column named '{}'", answer))
+}
+
+/// Show the detected mapping and let the user correct it field by field.
+fn confirm_mapping(ctx: &AppContext, headers: &[String], mut mapping: ColumnMapping) -> Result<ColumnMapping> {
+    print_mapping(headers, &mapping);
+    if ctx.prompter.confirm("👉 Use this mapping?")? {
+        return Ok(mapping);
+    }
+    println!("📋 Columns: {}", headers.iter().enumerate().map(|(i, h)| format!("{}. {}", i + 1, h)).collect::<Vec<_>>().join(", "));
+    for field in Field::ALL {
+        loop {
+            let current = mapping.get(field).map(|column| headers[column].as_str()).unwrap_or("none");
+            let answer = ctx.prompter.ask(&format!("   Column for {} (number or name, - for none) [{}]: ", field.label(), current))?;
+            match parse_column(&answer, headers, mapping.get(field)) {
+                Ok(column) => {
+                    mapping.set(field, column);
+                    break;
+                }
+                Err(e) => println!("❌ {}", e),
+            }
+        }
+    }
+    print_mapping(headers, &mapping);
+    Ok(mapping)
+}
+
+fn map_issue_type(value: &str) -> ItemType {
+    match value.to_lowercase().as_str() {
+        "bug" | "defect" => ItemType::Bug,
+        "epic" => ItemType::Epic,
+        "task" | "sub-task" | "subtask" => ItemType::Task,
+        _ => ItemType::UserStory,
+    }
+}
+
+fn map_priority(value: &str) -> Priority {
+    match value.to_lowercase().as_str() {
+        "highest" | "blocker" | "critical" => Priority::Critical,
+        "high" | "major" => Priority::High,
+        "low" | "lowest" | "minor" | "trivial" => Priority::Low,
+        _ => Priority::Medium,
+    }
+}
+
+fn map_status(value: &str) -> Status {
+    match value.to_lowercase().as_str() {
+        "done" | "closed" | "resolved" => Status::Done,
+        "in progress" | "in development" => Status::InProgress,
+        "in review" | "code review" | "review" | "in qa" => Status::Review,
+        _ => Status::Todo,
+    }
+}
+
+/// Jira keeps fractional points (e.g. `2.5`); they are rounded up.
+fn map_story_points(value: &str) -> u32 {
+    value.parse::<f64>().ok().filter(|points| *points > 0.0).map(|points| points.ceil() as u32).unwrap_or(DEFAULT_EFFORT)
+}
+
+/// Read issues from a CSV export; rows without a title are skipped and rows without a key
+/// are referenced by their row number.
+fn parse_issues(reader: &mut csv::Reader<impl std::io::Read>, mapping: &ColumnMapping) -> Result<Vec<ImportedItem>> {
+    let mut issues = vec![];
+    for (row, record) in reader.records().enumerate() {
+        let record = record.with_context(|| format!("Failed to read row {} of the CSV file", row + 2))?;
+        let title = mapping.value(&record, Field::Summary);
+        if title.is_empty() {
+            continue;
+        }
+        let key = mapping.value(&record, Field::Key);
+        let parent = mapping.value(&record, Field::Parent);
+        issues.push(ImportedItem {
+            external_ref: format!("jira:{}", if key.is_empty() { format!("row-{}", row + 2) } else { key.to_string() }),
+            item_type: map_issue_type(mapping.value(&record, Field::IssueType)),
+            title: title.to_string(),
+            story: mapping.value(&record, Field::Description).to_string(),
+            priority: map_priority(mapping.value(&record, Field::Priority)),
+            effort: map_story_points(mapping.value(&record, Field::StoryPoints)),
+            status: map_status(mapping.value(&record, Field::Status)),
+            parent_ref: (!parent.is_empty()).then(|| format!("jira:{}", parent)),
+        });
+    }
+    Ok(issues)
+}
+
+/// Import issues from a Jira CSV export (Filters → Export → CSV).
+pub fn run(ctx: &AppContext, file: &Path) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(file)
+        .with_context(|| format!("Failed to open Jira export {:?}", file))?;
+    let headers: Vec<String> = reader.headers().context("Failed to read the CSV header row")?.iter().map(String::from).collect();
+
+    let mapping = confirm_mapping(ctx, &headers, ColumnMapping::detect(&headers))?;
+    if mapping.get(Field::Summary).is_none() {
+        anyhow::bail!("A column for the title is required (Jira's 'Summary')");
+    }
+    let issues = parse_issues(&mut reader, &mapping)?;
+    let new_issues = issues.iter().filter(|issue| !super::already_imported(&project, &issue.external_ref)).count();
+    println!("📥 {} issue(s) read, {} not imported yet", issues.len(), new_issues);
+    if new_issues == 0 {
+        return Ok(());
+    }
+    if !ctx.prompter.confirm(&format!("👉 Import {} issue(s) into the backlog?", new_issues))? {
+        println!("❌ Import cancelled");
+        return Ok(());
+    }
+
+    let added = super::add_to_backlog(&mut project, issues);
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    println!("✅ Imported {} item(s): {}", added.len(), added.join(", "));
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach list-backlog", "Review the imported items"),
+        (format!("env-coach edit-item {} --editor", added[0]).as_str(), "Add acceptance criteria, which Jira exports lack"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = "Summary,Issue key,Issue Type,Status,Priority,Description,Custom field (Story Points),Parent\n\
+        Checkout,SHOP-1,Epic,To Do,High,,,\n\
+        Pay by card,SHOP-2,Story,In Progress,Highest,\"As a buyer, I want to pay\",2.5,SHOP-1\n\
+        Crash on empty cart,SHOP-3,Bug,Done,Low,,,\n\
+        ,SHOP-4,Story,To Do,Medium,,,\n";
+
+    #[test]
+    fn test_parse_jira_export() {
+        let mut reader = csv::Reader::from_reader(EXPORT.as_bytes());
+        let headers: Vec<String> = reader.headers().unwrap().iter().map(String::from).collect();
+        let mapping = ColumnMapping::detect(&headers);
+        assert_eq!(mapping.get(Field::Key), Some(1));
+        assert_eq!(mapping.get(Field::StoryPoints), Some(6));
+
+        let issues = parse_issues(&mut reader, &mapping).unwrap();
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[1], ImportedItem {
+            external_ref: "jira:SHOP-2".to_string(),
+            item_type: ItemType::UserStory,
+            title: "Pay by card".to_string(),
+            story: "As a buyer, I want to pay".to_string(),
+            priority: Priority::Critical,
+            effort: 3,
+            status: Status::InProgress,
+            parent_ref: Some("jira:SHOP-1".to_string()),
+        });
+        assert_eq!((issues[2].item_type.clone(), issues[2].status.clone(), issues[2].effort), (ItemType::Bug, Status::Done, DEFAULT_EFFORT));
+    }
+
+    #[test]
+    fn test_parse_column_answers() {
+        let headers = vec!["Summary".to_string(), "Points".to_string()];
+        assert_eq!(parse_column("", &headers, Some(0)).unwrap(), Some(0));
+        assert_eq!(parse_column("2", &headers, None).unwrap(), Some(1));
+        assert_eq!(parse_column("points", &headers, None).unwrap(), Some(1));
+        assert_eq!(parse_column("-", &headers, Some(1)).unwrap(), None);
+        assert!(parse_column("3", &headers, None).is_err());
+        assert!(parse_column("Estimate", &headers, None).is_err());
+    }
+}
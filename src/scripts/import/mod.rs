@@ -0,0 +1,140 @@
+// src/scripts/import/mod.rs
+//! Import backlog items from other trackers.
+//!
+//! Imported items keep their original ID in `external_ref` (e.g. `jira:PROJ-7`), which is
+//! also how re-running an import skips the items brought in before.
+
+pub mod jira;
+
+use chrono::Utc;
+
+use crate::config::{BacklogItem, ItemType, Priority, Project, Status};
+
+/// Next free `<prefix>-NNN` ID among backlog and archived items.
+pub(crate) fn next_id(project: &Project, prefix: &str) -> String {
+    let highest = project.backlog.iter()
+        .chain(project.archive.iter().map(|archived| &archived.item))
+        .filter_map(|item| item.id.strip_prefix(prefix)?.strip_prefix('-')?.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0);
+    format!("{}-{:03}", prefix, highest + 1)
+}
+
+/// ID prefix for new items of `item_type`.
+pub(crate) fn id_prefix(item_type: &ItemType) -> &'static str {
+    match item_type {
+        ItemType::UserStory => "US",
+        ItemType::Bug => "BUG",
+        ItemType::Epic => "EPIC",
+        ItemType::Task => "TASK",
+    }
+}
+
+/// True when an item (possibly archived) was already imported from `external_ref`.
+pub(crate) fn already_imported(project: &Project, external_ref: &str) -> bool {
+    project.backlog.iter()
+        .chain(project.archive.iter().map(|archived| &archived.item))
+        .any(|item| item.external_ref.as_deref() == Some(external_ref))
+}
+
+/// An item read from another tracker, before it gets an env-coach ID.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ImportedItem {
+    pub external_ref: String,
+    pub item_type: ItemType,
+    pub title: String,
+    pub story: String,
+    pub priority: Priority,
+    pub effort: u32,
+    pub status: Status,
+    /// `external_ref` of the epic the item belongs to
+    pub parent_ref: Option<String>,
+}
+
+/// Add `imported` to the backlog, skipping items imported before, and attach children to
+/// epics that came from the same tracker. Returns the new IDs.
+pub(crate) fn add_to_backlog(project: &mut Project, imported: Vec<ImportedItem>) -> Vec<String> {
+    let mut added = vec![];
+    let mut parents = vec![];
+    for entry in imported {
+        if already_imported(project, &entry.external_ref) {
+            continue;
+        }
+        let id = next_id(project, id_prefix(&entry.item_type));
+        // Epics are sized by their children
+        let effort = if matches!(entry.item_type, ItemType::Epic) { 0 } else { entry.effort.clamp(1, 100) };
+        parents.push((id.clone(), entry.parent_ref));
+        project.backlog.push(BacklogItem {
+            id: id.clone(),
+            item_type: entry.item_type,
+            story: if entry.story.trim().is_empty() { entry.title.clone() } else { entry.story },
+            title: entry.title,
+            acceptance_criteria: vec![],
+            priority: entry.priority,
+            effort,
+            status: entry.status,
+            created: Utc::now(),
+            sprint: None,
+            dependencies: vec![],
+            started_at: None,
+            completed_at: None,
+            component: None,
+            parent: None,
+            tags: vec![],
+            external_ref: Some(entry.external_ref),
+            history: vec![],
+            review: None,
+            criteria_check: None,
+            extra: Default::default(),
+        });
+        added.push(id);
+    }
+
+    for (id, parent_ref) in parents {
+        let Some(parent_ref) = parent_ref else { continue };
+        let epic = project.backlog.iter()
+            .find(|item| matches!(item.item_type, ItemType::Epic) && item.external_ref.as_deref() == Some(parent_ref.as_str()))
+            .map(|epic| epic.id.clone());
+        if let Some(item) = project.backlog.iter_mut().find(|item| item.id == id) {
+            item.parent = epic;
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn imported(external_ref: &str, item_type: ItemType, parent_ref: Option<&str>) -> ImportedItem {
+        ImportedItem {
+            external_ref: external_ref.to_string(),
+            item_type,
+            title: format!("Title {}", external_ref),
+            story: String::new(),
+            priority: Priority::Medium,
+            effort: 0,
+            status: Status::Todo,
+            parent_ref: parent_ref.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_add_to_backlog_assigns_ids_and_epics() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        let added = add_to_backlog(&mut project, vec![
+            imported("jira:P-2", ItemType::UserStory, Some("jira:P-1")),
+            imported("jira:P-1", ItemType::Epic, None),
+            imported("jira:P-3", ItemType::Bug, Some("jira:P-99")),
+        ]);
+        assert_eq!(added, vec!["US-001", "EPIC-001", "BUG-001"]);
+        assert_eq!(project.backlog[0].parent.as_deref(), Some("EPIC-001"));
+        assert_eq!(project.backlog[0].effort, 1);
+        assert_eq!(project.backlog[0].story, "Title jira:P-2");
+        assert_eq!(project.backlog[2].parent, None);
+
+        // A second run only adds what is new
+        let added = add_to_backlog(&mut project, vec![imported("jira:P-2", ItemType::UserStory, None), imported("jira:P-4", ItemType::Task, None)]);
+        assert_eq!(added, vec!["TASK-001"]);
+    }
+}
@@ -0,0 +1,71 @@
+// src/scripts/index.rs
+//! `index [--full]`: embed the codebase and the backlog into `.env-coach/cache/embeddings.bin`.
+//! Only files and items changed since the last run are embedded again, unless `--full` is
+//! given or the model changed.
+
+use anyhow::{Context, Result};
+
+use crate::code_context::{self, ContextConfig};
+use crate::code_index::{self, Chunk, CodeIndex, IndexedFile};
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+
+pub async fn run(ctx: &AppContext, full: bool) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let config = ContextConfig::load(&root)?;
+    let files = code_context::collect_files(&root, &config);
+    if files.is_empty() {
+        println!("⚠️ No source files matched {}", code_context::CONTEXT_FILE);
+        return Ok(());
+    }
+
+    let model = project.llm().model.clone();
+    let previous = match CodeIndex::load(&root) {
+        Ok(index) if !full && index.model == model => index,
+        Ok(_) => {
+            println!("♻️  Rebuilding the whole index");
+            CodeIndex::default()
+        }
+        Err(_) => CodeIndex::default(),
+    };
+
+    println!("📇 Indexing {} file(s) with {}...", files.len(), model);
+    let mut index = CodeIndex { model, files: vec![], chunks: vec![], items: previous.items.clone() };
+    let mut embedded_files = 0;
+    for path in &files {
+        let Ok(content) = std::fs::read_to_string(path) else { continue };
+        let relative = code_context::relative(&root, path);
+        let hash = code_index::content_hash(&content);
+        if previous.files.iter().any(|file| file.path == relative && file.hash == hash) {
+            index.chunks.extend(previous.chunks.iter().filter(|chunk| chunk.path == relative).cloned());
+        } else {
+            for (start_line, end_line, text) in code_index::chunk_lines(&content) {
+                let embedding = ollama::generate_embedding(project.llm(), &format!("{}\n{}", relative, text))
+                    .await
+                    .with_context(|| format!("Failed to embed {} (lines {}-{}); does the model support embeddings?", relative, start_line, end_line))?;
+                index.chunks.push(Chunk { path: relative.clone(), start_line, end_line, text, embedding });
+            }
+            embedded_files += 1;
+            println!("   ✅ {}", relative);
+        }
+        index.files.push(IndexedFile { path: relative, hash });
+    }
+
+    let embedded_items = index.embed_items(&project).await?;
+
+    if ctx.dry_run {
+        println!("🔍 Dry run: {} not written", code_index::INDEX_FILE);
+        return Ok(());
+    }
+    index.save(&root)?;
+    println!("✅ Indexed {} file(s) in {} chunk(s) ({} embedded, {} unchanged) and {} backlog item(s) ({} embedded) → {}",
+        index.files.len(), index.chunks.len(), embedded_files, index.files.len() - embedded_files,
+        index.items.len(), embedded_items, code_index::INDEX_FILE);
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach assist-task <id>", "Get help with the relevant code in the prompt"),
+        ("env-coach find \"...\"", "Search the backlog and code"),
+    ]);
+    Ok(())
+}
@@ -2,19 +2,22 @@
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
-use crate::config::{Project, GlobalConfig, Prd}; // Added Prd
+use crate::config::{Project, Prd}; // Added Prd
+use crate::context::AppContext;
+use crate::hints;
+use crate::output;
 use crate::templates::Templates;
 
 pub fn run(
+    ctx: &AppContext,
     name: Option<String>,
     description: Option<String>,
     problem: Option<String>,
     metrics: Vec<String>,
     description_file: Option<String>, // Added description_file
 ) -> Result<()> {
-    // Load global config first to pass to Project::new or Project::create_in_current_dir
-    let global_config = GlobalConfig::load().context("Failed to load global env-coach configuration")?;
-    let global_llm_cfg_ref = global_config.llm.as_ref();
+    // Global config is loaded once by main and passed to Project::new
+    let global_llm_cfg_ref = ctx.global_config.llm.as_ref();
 
     // Check if project is already initialized
     if Project::is_initialized() {
@@ -74,10 +77,13 @@ pub fn run(
 
     // Populate PRD if provided
     if problem.is_some() || !metrics.is_empty() {
-        let prd_content = Prd {
+        let mut prd_content = Prd {
             problem: problem.unwrap_or_default(),
             success_metrics: metrics,
+            reconciled: None,
         };
+        // The backlog is empty, so it trivially matches the PRD
+        prd_content.reconciled = Some(prd_content.fingerprint());
         project.meta.prd = Some(prd_content);
         println!("📄 PRD information captured.");
     }
@@ -86,6 +92,11 @@ pub fn run(
     project.validate()
         .context("Project validation failed")?;
 
+    if ctx.dry_run {
+        println!("🔍 Dry run: project.json, README.md, .gitignore and .env-coach/ were not written");
+        return Ok(());
+    }
+
     // Save project.json
     project.save()
         .context("Failed to save project.json")?;
@@ -142,41 +153,31 @@ pub fn run(
         fs::create_dir_all(&prompts_dir)
             .context("Failed to create .env-coach/prompts directory")?;
 
-        Templates::create_default_prompt_if_missing(
-            &prompts_dir,
-            "requirements_analyst.md",
-            Templates::default_requirements_analyst_prompt_content()
-        ).context("Failed to create default requirements_analyst.md prompt")?;
-
-        Templates::create_default_prompt_if_missing(
-            &prompts_dir,
-            "sprint_planner.md",
-            Templates::default_sprint_planner_prompt_content()
-        ).context("Failed to create default sprint_planner.md prompt")?;
-
-        Templates::create_default_prompt_if_missing(
-            &prompts_dir,
-            "task_assistant.md",
-            Templates::default_task_assistant_prompt_content()
-        ).context("Failed to create default task_assistant.md prompt")?;
+        // New default prompts are registered in prompts::DEFAULT_PROMPTS
+        for prompt in crate::prompts::DEFAULT_PROMPTS {
+            crate::prompts::install_default(&prompts_dir, prompt)?;
+        }
 
-        // TODO: Add other default prompts here in the future e.g. code_reviewer.md
+        fs::write(crate::code_context::CONTEXT_FILE, crate::code_context::ContextConfig::default_file_content())
+            .context("Failed to create .env-coach/context.toml")?;
 
-        println!("✅ Created .env-coach/ directory structure and default prompts.");
+        println!("✅ Created .env-coach/ directory structure, default prompts and context.toml.");
     }
 
     println!();
     println!("🎉 Project '{}' initialized successfully!", project_name);
-    println!();
-    println!("🎯 Next steps:");
-    println!("   env-coach status                    # Check LLM connectivity");
-    println!("   env-coach add-requirement \"...\"     # Add your first requirement");
-    println!("   env-coach list-backlog              # View generated backlog");
-    println!("   env-coach plan-sprint --goal \"...\"  # Plan your first sprint");
-    println!();
-    println!("📚 Learn more:");
-    println!("   env-coach --help                    # View all commands");
-    println!("   cat README.md                       # Read project documentation");
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach status", "Check LLM connectivity"),
+        ("env-coach add-requirement \"...\"", "Add your first requirement"),
+        ("env-coach list-backlog", "View generated backlog"),
+        ("env-coach plan-sprint --goal \"...\"", "Plan your first sprint"),
+    ]);
+    if output::show_hints() {
+        println!();
+        println!("📚 Learn more:");
+        println!("   env-coach --help                    # View all commands");
+        println!("   cat README.md                       # Read project documentation");
+    }
 
     Ok(())
 }
@@ -194,12 +195,12 @@ mod tests {
         let original_dir = std::env::current_dir().unwrap();
         std::env::set_current_dir(temp_dir.path()).unwrap();
 
-        let project_name = Some("TestPRDProject".to_string());
+        let project_name = "TestPRDProject".to_string();
         let description = Some("A project to test PRD init".to_string());
-        let problem = Some("The main problem is testing this feature.".to_string());
+        let problem = "The main problem is testing this feature.".to_string();
         let metrics = vec!["Metric1".to_string(), "Metric2".to_string()];
 
-        run(project_name.clone(), description.clone(), problem.clone(), metrics.clone(), None).unwrap(); // Added None for description_file
+        run(&AppContext::default(), Some(project_name.clone()), description.clone(), Some(problem.clone()), metrics.clone(), None).unwrap(); // Added None for description_file
 
         // Load the created project.json and verify its contents
         let project_json_path = temp_dir.path().join("project.json");
@@ -208,10 +209,10 @@ mod tests {
         let loaded_project: Project = serde_json::from_str(&project_content_str)
             .expect("Test: Failed to parse project.json content");
 
-        assert_eq!(loaded_project.meta.name, project_name.unwrap());
+        assert_eq!(loaded_project.meta.name, project_name);
         assert!(loaded_project.meta.prd.is_some());
         let prd = loaded_project.meta.prd.unwrap();
-        assert_eq!(prd.problem, problem.unwrap());
+        assert_eq!(prd.problem, problem);
         assert_eq!(prd.success_metrics, metrics);
 
         // Cleanup: remove project.json and restore original directory
@@ -225,11 +226,11 @@ mod tests {
         let original_dir = std::env::current_dir().unwrap();
         std::env::set_current_dir(temp_dir.path()).unwrap();
 
-        let project_name = Some("TestNoPRDProject".to_string());
+        let project_name = "TestNoPRDProject".to_string();
         let description = Some("A project to test no PRD init".to_string());
 
         // No PRD info provided
-        run(project_name.clone(), description.clone(), None, vec![], None).unwrap(); // Added None for description_file
+        run(&AppContext::default(), Some(project_name.clone()), description.clone(), None, vec![], None).unwrap(); // Added None for description_file
 
         // ---- Debug Start ----
         let project_json_content = fs::read_to_string("project.json")
@@ -239,7 +240,7 @@ mod tests {
 
         let loaded_project = Project::load().expect("Failed to load project.json in test");
 
-        assert_eq!(loaded_project.meta.name, project_name.unwrap());
+        assert_eq!(loaded_project.meta.name, project_name);
         assert!(loaded_project.meta.prd.is_none(), "PRD should be None when not provided");
 
         fs::remove_file("project.json").unwrap();
@@ -252,8 +253,8 @@ mod tests {
         let original_dir = std::env::current_dir().unwrap();
         std::env::set_current_dir(temp_dir.path()).unwrap();
 
-        let problem = Some("Only a problem statement.".to_string());
-        run(Some("ProblemOnly".to_string()), None, problem.clone(), vec![], None).unwrap(); // Added None for description_file
+        let problem = "Only a problem statement.".to_string();
+        run(&AppContext::default(), Some("ProblemOnly".to_string()), None, Some(problem.clone()), vec![], None).unwrap(); // Added None for description_file
 
         let project_json_path = temp_dir.path().join("project.json");
         let project_content_str = fs::read_to_string(&project_json_path)
@@ -262,7 +263,7 @@ mod tests {
             .expect("Test: Failed to parse project.json content");
 
         assert!(loaded_project.meta.prd.is_some());
-        assert_eq!(loaded_project.meta.prd.as_ref().unwrap().problem, problem.unwrap());
+        assert_eq!(loaded_project.meta.prd.as_ref().unwrap().problem, problem);
         assert!(loaded_project.meta.prd.as_ref().unwrap().success_metrics.is_empty());
 
         fs::remove_file("project.json").unwrap();
@@ -276,7 +277,7 @@ mod tests {
         std::env::set_current_dir(temp_dir.path()).unwrap();
 
         let metrics = vec!["Metric A".to_string()];
-        run(Some("MetricsOnly".to_string()), None, None, metrics.clone(), None).unwrap(); // Added None for description_file
+        run(&AppContext::default(), Some("MetricsOnly".to_string()), None, None, metrics.clone(), None).unwrap(); // Added None for description_file
 
         let project_json_path = temp_dir.path().join("project.json");
         let project_content_str = fs::read_to_string(&project_json_path)
@@ -303,6 +304,7 @@ mod tests {
         fs::write(&desc_file_path, &desc_content).unwrap();
 
         run(
+            &AppContext::default(),
             Some("DescFileProject".to_string()),
             None, // No direct --description
             None,
@@ -333,6 +335,7 @@ mod tests {
         fs::write(&desc_file_path, &file_desc).unwrap();
 
         run(
+            &AppContext::default(),
             Some("DescFilePrecedence".to_string()),
             Some(direct_desc),
             None,
@@ -362,6 +365,7 @@ mod tests {
 
         // Expect a warning to be printed, but the run should succeed using direct_desc
         run(
+            &AppContext::default(),
             Some("MissingDescFile".to_string()),
             Some(direct_desc.clone()),
             None,
@@ -390,6 +394,7 @@ mod tests {
         fs::write(&desc_file_path, "").unwrap(); // Empty file
 
         run(
+            &AppContext::default(),
             Some("EmptyDescFile".to_string()),
             Some(direct_desc.clone()),
             None,
@@ -414,8 +419,8 @@ mod tests {
         let original_dir = std::env::current_dir().unwrap();
         std::env::set_current_dir(temp_dir.path()).unwrap();
 
-        let project_name = Some("DefaultLLMProject".to_string());
-        run(project_name.clone(), None, None, vec![], None).unwrap();
+        let project_name = "DefaultLLMProject".to_string();
+        run(&AppContext::default(), Some(project_name.clone()), None, None, vec![], None).unwrap();
 
         let project_json_path = temp_dir.path().join("project.json");
         let project_content_str = fs::read_to_string(&project_json_path)
@@ -423,7 +428,7 @@ mod tests {
         let loaded_project: Project = serde_json::from_str(&project_content_str)
             .expect("Test: Failed to parse project.json content");
 
-        assert_eq!(loaded_project.meta.name, project_name.unwrap());
+        assert_eq!(loaded_project.meta.name, project_name);
         assert!(loaded_project.meta.llm.is_some(), "meta.llm should be Some");
 
         let llm_config = loaded_project.meta.llm.unwrap();
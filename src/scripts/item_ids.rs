@@ -0,0 +1,116 @@
+// src/scripts/item_ids.rs
+//! ID lists for commands that act on several items at once.
+//!
+//! Each argument may hold several IDs separated by commas or spaces, a range such as
+//! `US-001..US-005`, or `-` to read IDs from stdin (one per line, or separated as above).
+
+use anyhow::{Context, Result};
+use std::io::Read;
+
+use crate::config::Project;
+
+/// Split `US-003` into `("US", 3)`.
+fn split_id(id: &str) -> Option<(&str, u32)> {
+    let (prefix, number) = id.rsplit_once('-')?;
+    Some((prefix, number.parse().ok()?))
+}
+
+/// IDs of backlog items from `start` to `end` inclusive, in number order. Only IDs in the
+/// backlog are considered, so gaps (e.g. removed items) and wide ranges cost nothing.
+fn expand_range(start: &str, end: &str, backlog_ids: &[&str]) -> Result<Vec<String>> {
+    let (Some((prefix, from)), Some((end_prefix, to))) = (split_id(start), split_id(end)) else {
+        anyhow::bail!("Invalid range '{}..{}' (expected e.g. US-001..US-005)", start, end);
+    };
+    if !prefix.eq_ignore_ascii_case(end_prefix) || from > to {
+        anyhow::bail!("Invalid range '{}..{}': both ends need the same prefix, lowest first", start, end);
+    }
+    let mut in_range: Vec<(u32, &str)> = backlog_ids.iter()
+        .filter_map(|id| split_id(id).map(|(id_prefix, number)| (id_prefix, number, *id)))
+        .filter(|(id_prefix, number, _)| id_prefix.eq_ignore_ascii_case(prefix) && (from..=to).contains(number))
+        .map(|(_, number, id)| (number, id))
+        .collect();
+    in_range.sort();
+    let ids: Vec<String> = in_range.into_iter().map(|(_, id)| id.to_string()).collect();
+    if ids.is_empty() {
+        anyhow::bail!("No backlog items in range '{}..{}'", start, end);
+    }
+    Ok(ids)
+}
+
+fn expand_with(args: &[String], read_stdin: impl FnOnce() -> Result<String>, backlog_ids: &[&str]) -> Result<Vec<String>> {
+    let mut read_stdin = Some(read_stdin);
+    let mut tokens: Vec<String> = vec![];
+    for arg in args {
+        if arg == "-" {
+            // stdin can only be read once; a second `-` adds nothing
+            let Some(read) = read_stdin.take() else { continue };
+            tokens.extend(read()?.split([',', ' ', '\t', '\n', '\r']).map(String::from));
+        } else {
+            tokens.extend(arg.split([',', ' ']).map(String::from));
+        }
+    }
+
+    let mut ids: Vec<String> = vec![];
+    for token in tokens.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        let expanded = match token.split_once("..") {
+            Some((start, end)) => expand_range(start, end, backlog_ids)?,
+            None => vec![token.to_string()],
+        };
+        for id in expanded {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    if ids.is_empty() {
+        anyhow::bail!("No item IDs given");
+    }
+    Ok(ids)
+}
+
+/// Expand the ID arguments of a bulk command, in the order given and without duplicates.
+pub fn expand(args: &[String], project: &Project) -> Result<Vec<String>> {
+    let read_stdin = || {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text).context("Failed to read item IDs from stdin")?;
+        Ok(text)
+    };
+    let backlog_ids: Vec<&str> = project.backlog.iter().map(|item| item.id.as_str()).collect();
+    expand_with(args, read_stdin, &backlog_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn no_stdin() -> Result<String> {
+        panic!("stdin should not be read")
+    }
+
+    #[test]
+    fn test_expand_lists_and_ranges() {
+        let backlog = ["US-005", "US-001", "US-002", "US-004", "BUG-001"];
+
+        let ids = expand_with(&args(&["US-001,BUG-001", "US-002..US-005", "US-001"]), no_stdin, &backlog).unwrap();
+        assert_eq!(ids, vec!["US-001", "BUG-001", "US-002", "US-004", "US-005"]);
+
+        assert!(expand_with(&args(&["US-005..US-001"]), no_stdin, &backlog).is_err());
+        assert!(expand_with(&args(&["US-001..BUG-003"]), no_stdin, &backlog).is_err());
+        assert!(expand_with(&args(&["US-010..US-020"]), no_stdin, &backlog).is_err());
+        assert!(expand_with(&args(&[" , "]), no_stdin, &backlog).is_err());
+
+        // Only backlog IDs are checked, so a huge range is as quick as a small one
+        let ids = expand_with(&args(&["US-003..US-999999999"]), no_stdin, &backlog).unwrap();
+        assert_eq!(ids, vec!["US-004", "US-005"]);
+    }
+
+    #[test]
+    fn test_expand_reads_stdin_for_dash() {
+        let ids = expand_with(&args(&["-", "US-009"]), || Ok("US-001\nUS-002, US-003\n".to_string()), &[]).unwrap();
+        assert_eq!(ids, vec!["US-001", "US-002", "US-003", "US-009"]);
+    }
+}
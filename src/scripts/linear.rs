@@ -0,0 +1,100 @@
+// src/scripts/linear.rs
+//! `linear push`: create Linear issues for user stories, linked back through
+//! `external_ref = "linear:<identifier>"` (e.g. `linear:ENG-123`).
+
+use anyhow::{Context, Result};
+
+use crate::config::{BacklogItem, ItemType, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::linear::{self, LinearClient};
+use crate::scripts::github::issue_body;
+
+/// Open user stories not linked to a tracker yet.
+fn pending_stories(project: &Project) -> Vec<&BacklogItem> {
+    project.backlog.iter()
+        .filter(|item| matches!(item.item_type, ItemType::UserStory))
+        .filter(|item| item.external_ref.is_none() && !matches!(item.status, Status::Done))
+        .collect()
+}
+
+/// Link template for the workspace, from an issue URL such as
+/// `https://linear.app/acme/issue/ENG-1/login-form`.
+fn link_template(issue_url: &str) -> Option<String> {
+    let (workspace, _) = issue_url.split_once("/issue/")?;
+    Some(format!("{}/issue/{{id}}", workspace))
+}
+
+/// Push open user stories to a Linear team, mapping priority and effort onto Linear's
+/// fields. The first push also records the workspace's link template in project.json.
+pub async fn push(ctx: &AppContext, team_key: &str) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let client = LinearClient::from_env()?;
+    let team = client.team(team_key).await?;
+    let scale = team.estimate_scale();
+    println!("📐 Pushing user stories to Linear team {} ({})", team.name, team.key);
+
+    let pending: Vec<String> = pending_stories(&project).iter().map(|item| item.id.clone()).collect();
+    if pending.is_empty() {
+        println!("✅ Every open user story is already linked to an issue");
+        return Ok(());
+    }
+    println!("📤 User stories without an issue ({}):", pending.len());
+    for item in pending_stories(&project) {
+        let estimate = linear::nearest_estimate(item.effort, scale)
+            .map(|estimate| format!(", estimate {}", estimate))
+            .unwrap_or_default();
+        println!("   - {} {} [{:?}{}]", item.id, item.title, item.priority, estimate);
+    }
+    if !ctx.prompter.confirm(&format!("👉 Create {} Linear issue(s) in {}?", pending.len(), team.key))? {
+        println!("❌ Push cancelled");
+        return Ok(());
+    }
+    if ctx.dry_run {
+        println!("🔍 Dry run: nothing created in Linear");
+        return Ok(());
+    }
+
+    let mut created = 0;
+    let mut failure = None;
+    for id in &pending {
+        let Some(item) = project.backlog.iter_mut().find(|item| &item.id == id) else { continue };
+        let estimate = linear::nearest_estimate(item.effort, scale);
+        match client.create_issue(&team, &item.title, &issue_body(item), linear::priority_value(&item.priority), estimate).await {
+            Ok(issue) => {
+                println!("✅ {} → {} {}", id, issue.identifier, issue.url);
+                item.external_ref = Some(format!("linear:{}", issue.identifier));
+                created += 1;
+                if let Some(template) = link_template(&issue.url) {
+                    project.meta.links.entry("linear".to_string()).or_insert(template);
+                }
+            }
+            Err(e) => {
+                failure = Some(e);
+                break;
+            }
+        }
+    }
+
+    // Save what was created before a failure, or a re-run would duplicate it
+    ctx.save_project(&project).context("Failed to save project")?;
+    println!("📊 Created {} of {} issue(s)", created, pending.len());
+    if let Some(e) = failure {
+        return Err(e);
+    }
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach list-backlog", "See the stories with their Linear links"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_template() {
+        assert_eq!(link_template("https://linear.app/acme/issue/ENG-1/login-form").as_deref(), Some("https://linear.app/acme/issue/{id}"));
+        assert_eq!(link_template("https://example.com/ENG-1"), None);
+    }
+}
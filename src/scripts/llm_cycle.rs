@@ -1,12 +1,13 @@
 // src/scripts/llm_cycle.rs
 use std::fs;
 use log::{info, debug, error};
+use crate::context::AppContext;
 
-pub async fn run(prompt: String) -> anyhow::Result<()> {  // Make async
+pub async fn run(ctx: &AppContext, prompt: String) -> anyhow::Result<()> {  // Make async
     info!("Running LLM cycle script with prompt: {}", prompt);
     
     // Try to load project configuration
-    let project = match crate::config::Project::load() {
+    let project = match ctx.load_project() {
         Ok(project) => {
             println!("📋 Using project LLM configuration from project.json");
             project
@@ -14,16 +15,10 @@ pub async fn run(prompt: String) -> anyhow::Result<()> {  // Make async
         Err(_) => {
             println!("⚠️  No env-coach project found (project.json missing or invalid).");
             println!("Attempting to use global/default LLM configuration for this cycle.");
-            // Load global config to pass to create_in_current_dir for default project setup
-            let global_config = crate::config::GlobalConfig::load()
-                .map_err(|e| {
-                    // Log this error but proceed, as create_in_current_dir can handle None for global_llm_config
-                    error!("Failed to load global config for llm_cycle fallback: {}", e);
-                    e
-                })
-                .unwrap_or_default(); // Proceed with default if global load fails catastrophically
-
-            crate::config::Project::create_in_current_dir(global_config.llm.as_ref())?
+            // Global config (already loaded by main) drives the default project setup
+            let mut project = crate::config::Project::create_in_current_dir(ctx.global_config.llm.as_ref())?;
+            project.resolved_llm_config.apply_overrides(&ctx.llm_overrides);
+            project
         }
     };
     
@@ -48,7 +43,7 @@ pub async fn run(prompt: String) -> anyhow::Result<()> {  // Make async
     debug!("Prompt text loaded successfully. Length: {} characters", prompt_text.len());
 
     println!("🤖 Sending prompt to LLM...");
-    crate::ollama::send_prompt(&cfg, &prompt_text).await?;  // Add .await
+    crate::ollama::send_prompt(cfg, &prompt_text).await?;  // Add .await
     info!("LLM cycle completed successfully.");
     Ok(())
 }
\ No newline at end of file
@@ -6,4 +6,58 @@ pub mod status;
 pub mod sprint;
 pub mod tasks;
 pub mod stories;
-pub mod llm_cycle;
\ No newline at end of file
+pub mod llm_cycle;
+pub mod burndown;
+pub mod risks;
+pub mod velocity;
+pub mod commit_msg;
+pub mod sync_git;
+pub mod retro;
+pub mod ask;
+pub mod sprint_report;
+pub mod export;
+pub mod bench;
+pub mod workspace;
+pub mod edit_item;
+pub mod archive;
+pub mod set_field;
+pub mod bugs;
+pub mod epics;
+pub mod prompts;
+pub mod prompt_test;
+pub mod sprint_goal;
+pub mod filter;
+pub mod search;
+pub mod prd;
+pub mod show_item;
+pub mod item_ids;
+pub mod coach;
+pub mod stats;
+pub mod reconcile;
+pub mod graph;
+pub mod fix_tests;
+pub mod import;
+pub mod github;
+pub mod gitlab;
+pub mod linear;
+pub mod notes;
+pub mod validate;
+pub mod undo;
+pub mod restore;
+pub mod backup;
+pub mod session;
+pub mod test_task;
+pub mod verify_task;
+pub mod review_task;
+pub mod execute_sprint;
+pub mod breakdown;
+pub mod estimate;
+pub mod groom;
+pub mod criteria_check;
+pub mod index;
+pub mod find;
+pub mod chat;
+pub mod config;
+pub mod doctor;
+pub mod convert;
+pub mod history;
@@ -0,0 +1,220 @@
+// src/scripts/notes.rs
+//! The project as notes for org-mode and Obsidian, linked so epics, stories and sprints
+//! show up in each other's backlinks.
+
+use std::path::PathBuf;
+
+use crate::config::{BacklogItem, ItemType, Project, Sprint, Status};
+
+fn org_keyword(status: &Status) -> &'static str {
+    match status {
+        Status::Todo => "TODO",
+        Status::InProgress => "IN-PROGRESS",
+        Status::Review => "REVIEW",
+        Status::Done => "DONE",
+    }
+}
+
+fn org_link(id: &str) -> String {
+    format!("[[id:{}][{}]]", id, id)
+}
+
+fn children<'a>(project: &'a Project, epic: &BacklogItem) -> Vec<&'a BacklogItem> {
+    project.backlog.iter().filter(|item| item.parent.as_deref() == Some(epic.id.as_str())).collect()
+}
+
+fn org_item(out: &mut String, project: &Project, item: &BacklogItem) {
+    out.push_str(&format!("** {} {}: {}\n", org_keyword(&item.status), item.id, item.title));
+    out.push_str(":PROPERTIES:\n");
+    out.push_str(&format!(":ID: {}\n:TYPE: {:?}\n:PRIORITY: {:?}\n:POINTS: {}\n", item.id, item.item_type, item.priority, item.effort));
+    if let Some(sprint) = &item.sprint {
+        out.push_str(&format!(":SPRINT: {}\n", org_link(sprint)));
+    }
+    if let Some(parent) = &item.parent {
+        out.push_str(&format!(":EPIC: {}\n", org_link(parent)));
+    }
+    out.push_str(":END:\n");
+    if !item.story.trim().is_empty() {
+        out.push_str(&format!("{}\n", item.story.trim()));
+    }
+    if !item.acceptance_criteria.is_empty() {
+        out.push_str("*** Acceptance criteria\n");
+        for criterion in &item.acceptance_criteria {
+            out.push_str(&format!("- [ ] {}\n", criterion));
+        }
+    }
+    if !item.dependencies.is_empty() {
+        let links: Vec<String> = item.dependencies.iter().map(|id| org_link(id)).collect();
+        out.push_str(&format!("Depends on: {}\n", links.join(", ")));
+    }
+    if matches!(item.item_type, ItemType::Epic) {
+        let links: Vec<String> = children(project, item).iter().map(|child| org_link(&child.id)).collect();
+        if !links.is_empty() {
+            out.push_str(&format!("Stories: {}\n", links.join(", ")));
+        }
+    }
+}
+
+/// A single org file: backlog items and sprints as headings with `:ID:` properties, so
+/// `[[id:US-001]]` links resolve inside the file (and with org-roam across files).
+pub fn org_document(project: &Project) -> String {
+    let mut out = format!("#+TITLE: {}\n#+TODO: TODO IN-PROGRESS REVIEW | DONE\n\n", project.meta.name);
+    if !project.meta.description.is_empty() {
+        out.push_str(&format!("{}\n\n", project.meta.description));
+    }
+    out.push_str("* Backlog\n");
+    for item in &project.backlog {
+        org_item(&mut out, project, item);
+    }
+    out.push_str("* Sprints\n");
+    for sprint in &project.sprints {
+        out.push_str(&format!("** {}: {}\n", sprint.id, sprint.goal));
+        out.push_str(&format!(":PROPERTIES:\n:ID: {}\n:STATUS: {:?}\n:START_DATE: {}\n:END_DATE: {}\n:POINTS: {}/{}\n:END:\n",
+            sprint.id, sprint.status, sprint.start_date.format("%Y-%m-%d"), sprint.end_date.format("%Y-%m-%d"),
+            sprint.completed_points, sprint.total_points));
+        for id in &sprint.stories {
+            out.push_str(&format!("- {}\n", org_link(id)));
+        }
+    }
+    out
+}
+
+/// YAML string, quoted so links and colons survive.
+fn yaml_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn item_note(project: &Project, item: &BacklogItem) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("id: {}\ntype: {:?}\nstatus: {:?}\npriority: {:?}\npoints: {}\n", item.id, item.item_type, item.status, item.priority, item.effort));
+    if let Some(sprint) = &item.sprint {
+        out.push_str(&format!("sprint: {}\n", yaml_string(&format!("[[{}]]", sprint))));
+    }
+    if let Some(parent) = &item.parent {
+        out.push_str(&format!("epic: {}\n", yaml_string(&format!("[[{}]]", parent))));
+    }
+    if !item.tags.is_empty() {
+        let tags: Vec<String> = item.tags.iter().map(|tag| yaml_string(tag)).collect();
+        out.push_str(&format!("tags: [{}]\n", tags.join(", ")));
+    }
+    out.push_str("---\n");
+    out.push_str(&format!("# {}: {}\n", item.id, item.title));
+    if !item.story.trim().is_empty() {
+        out.push_str(&format!("\n{}\n", item.story.trim()));
+    }
+    if !item.acceptance_criteria.is_empty() {
+        out.push_str("\n## Acceptance criteria\n");
+        for criterion in &item.acceptance_criteria {
+            out.push_str(&format!("- [ ] {}\n", criterion));
+        }
+    }
+    if !item.dependencies.is_empty() {
+        out.push_str("\n## Depends on\n");
+        for id in &item.dependencies {
+            out.push_str(&format!("- [[{}]]\n", id));
+        }
+    }
+    if matches!(item.item_type, ItemType::Epic) {
+        let children = children(project, item);
+        if !children.is_empty() {
+            out.push_str("\n## Stories\n");
+            for child in children {
+                out.push_str(&format!("- [[{}]] {}\n", child.id, child.title));
+            }
+        }
+    }
+    out
+}
+
+fn sprint_note(project: &Project, sprint: &Sprint) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("id: {}\nstatus: {:?}\nstart: {}\nend: {}\npoints: {}\ncompleted_points: {}\n---\n",
+        sprint.id, sprint.status, sprint.start_date.format("%Y-%m-%d"), sprint.end_date.format("%Y-%m-%d"),
+        sprint.total_points, sprint.completed_points));
+    out.push_str(&format!("# {}: {}\n\n## Items\n", sprint.id, sprint.goal));
+    for id in &sprint.stories {
+        let title = project.backlog.iter().find(|item| &item.id == id).map(|item| item.title.as_str()).unwrap_or_default();
+        out.push_str(&format!("- [[{}]] {}\n", id, title));
+    }
+    out
+}
+
+/// One note per backlog item and sprint, named by ID so `[[US-001]]` links resolve, plus
+/// an index note named after the project. Paths are relative to the vault folder.
+pub fn obsidian_notes(project: &Project) -> Vec<(PathBuf, String)> {
+    let mut notes = vec![];
+    let mut index = format!("# {}\n\n{}\n\n## Backlog\n", project.meta.name, project.meta.description);
+    for item in &project.backlog {
+        notes.push((PathBuf::from("items").join(format!("{}.md", item.id)), item_note(project, item)));
+        index.push_str(&format!("- [[{}]] {} ({:?})\n", item.id, item.title, item.status));
+    }
+    index.push_str("\n## Sprints\n");
+    for sprint in &project.sprints {
+        notes.push((PathBuf::from("sprints").join(format!("{}.md", sprint.id)), sprint_note(project, sprint)));
+        index.push_str(&format!("- [[{}]] {}\n", sprint.id, sprint.goal));
+    }
+    notes.push((PathBuf::from(format!("{}.md", project.meta.name)), index));
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Priority, SprintStatus};
+    use chrono::Utc;
+
+    fn project() -> Project {
+        let mut project = Project::new("Shop".to_string(), "Online shop".to_string(), None);
+        for (id, item_type, parent) in [("EPIC-001", ItemType::Epic, None), ("US-001", ItemType::UserStory, Some("EPIC-001"))] {
+            project.backlog.push(BacklogItem {
+                sprint: parent.map(|_| "S-001".to_string()),
+                parent: parent.map(String::from),
+                ..BacklogItem::test(id)
+                    .with_type(item_type)
+                    .with_title(&format!("Title {}", id))
+                    .with_story("As a buyer...")
+                    .with_criteria(&["Card payments work"])
+                    .with_priority(Priority::High)
+                    .with_effort(5)
+                    .with_status(Status::InProgress)
+                    .with_tags(&["payments"])
+            });
+        }
+        project.sprints.push(Sprint {
+            id: "S-001".to_string(),
+            goal: "Take payments".to_string(),
+            start_date: Utc::now(),
+            end_date: Utc::now(),
+            status: SprintStatus::Active,
+            total_points: 5,
+            completed_points: 0,
+            tasks: vec![],
+            stories: vec!["US-001".to_string()],
+            planned_velocity: 5,
+            actual_velocity: 0,
+            retrospective: None,
+        });
+        project
+    }
+
+    #[test]
+    fn test_org_document() {
+        let org = org_document(&project());
+        assert!(org.contains("** IN-PROGRESS US-001: Title US-001\n:PROPERTIES:\n:ID: US-001\n"));
+        assert!(org.contains(":SPRINT: [[id:S-001][S-001]]\n:EPIC: [[id:EPIC-001][EPIC-001]]\n"));
+        assert!(org.contains("Stories: [[id:US-001][US-001]]\n"));
+        assert!(org.contains("** S-001: Take payments\n:PROPERTIES:\n:ID: S-001\n"));
+    }
+
+    #[test]
+    fn test_obsidian_notes() {
+        let notes = obsidian_notes(&project());
+        let note = |path: &str| notes.iter().find(|(p, _)| p == &PathBuf::from(path)).map(|(_, content)| content.as_str()).unwrap();
+
+        let story = note("items/US-001.md");
+        assert!(story.starts_with("---\nid: US-001\ntype: UserStory\nstatus: InProgress\npriority: High\npoints: 5\nsprint: \"[[S-001]]\"\nepic: \"[[EPIC-001]]\"\ntags: [\"payments\"]\n---\n# US-001: Title US-001\n"));
+        assert!(note("items/EPIC-001.md").contains("## Stories\n- [[US-001]] Title US-001\n"));
+        assert!(note("sprints/S-001.md").contains("## Items\n- [[US-001]] Title US-001\n"));
+        assert!(note("Shop.md").contains("- [[S-001]] Take payments\n"));
+    }
+}
@@ -0,0 +1,292 @@
+// src/scripts/prd.rs
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::config::{BacklogItem, ItemType, Priority, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::scripts::import;
+use crate::templates::Templates;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+enum MetricStatus {
+    OnTrack,
+    AtRisk,
+    Uncovered,
+}
+
+#[derive(Deserialize, Debug)]
+struct MetricAssessment {
+    /// 1-based index into the PRD's success metrics
+    metric: usize,
+    status: MetricStatus,
+    #[serde(default)]
+    story_ids: Vec<String>,
+    #[serde(default)]
+    assessment: String,
+}
+
+/// A story proposed by the LLM to serve a PRD metric.
+#[derive(Deserialize, Debug)]
+pub(crate) struct Recommendation {
+    pub title: String,
+    pub story: String,
+    /// 1-based index into the PRD's success metrics
+    #[serde(default)]
+    pub metric: Option<usize>,
+    #[serde(default = "default_effort")]
+    pub effort: u32,
+}
+
+fn default_effort() -> u32 {
+    3
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct LlmMetricsResponse {
+    metrics: Vec<MetricAssessment>,
+    #[serde(default)]
+    recommendations: Vec<Recommendation>,
+}
+
+/// One row of the report, after checking the LLM's answer against the project.
+#[derive(Debug, PartialEq)]
+struct MetricReport {
+    metric: String,
+    status: MetricStatus,
+    story_ids: Vec<String>,
+    assessment: String,
+}
+
+/// Done items, including archived ones, which still count toward the metrics.
+pub(crate) fn completed_items(project: &Project) -> Vec<&BacklogItem> {
+    project.backlog.iter()
+        .chain(project.archive.iter().map(|archived| &archived.item))
+        .filter(|item| matches!(item.status, Status::Done) && !matches!(item.item_type, ItemType::Epic))
+        .collect()
+}
+
+pub(crate) fn remaining_items(project: &Project) -> Vec<&BacklogItem> {
+    project.backlog.iter()
+        .filter(|item| !matches!(item.status, Status::Done) && !matches!(item.item_type, ItemType::Epic))
+        .collect()
+}
+
+pub(crate) fn format_items(items: &[&BacklogItem], empty: &str) -> String {
+    if items.is_empty() {
+        return empty.to_string();
+    }
+    items.iter()
+        .map(|item| format!("- {} [{:?}, {} pts] {}: {}", item.id, item.status, item.effort, item.title, item.story))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn build_metrics_prompt(template: &str, project: &Project, metrics: &[String]) -> String {
+    let problem = project.meta.prd.as_ref().map(|prd| prd.problem.as_str()).unwrap_or_default();
+    let numbered = metrics.iter().enumerate()
+        .map(|(i, metric)| format!("{}. {}", i + 1, metric))
+        .collect::<Vec<_>>()
+        .join("\n");
+    template
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{problem}}", if problem.is_empty() { "Not recorded." } else { problem })
+        .replace("{{metrics}}", &numbered)
+        .replace("{{completed}}", &format_items(&completed_items(project), "Nothing completed yet."))
+        .replace("{{remaining}}", &format_items(&remaining_items(project), "Backlog is empty."))
+}
+
+pub(crate) fn parse_metrics_response(response: &str) -> Result<LlmMetricsResponse> {
+    serde_json::from_str(response).with_context(|| {
+        let snippet = response.chars().take(500).collect::<String>();
+        format!("Failed to parse LLM metrics check. Response (first 500 chars): '{}'", snippet)
+    })
+}
+
+/// One report row per PRD metric. Unknown story IDs are dropped, a metric left with no
+/// stories is uncovered whatever the LLM said, and metrics the LLM skipped are uncovered.
+fn reconcile(project: &Project, metrics: &[String], assessments: Vec<MetricAssessment>) -> Vec<MetricReport> {
+    let known = |id: &String| {
+        project.backlog.iter().chain(project.archive.iter().map(|archived| &archived.item)).any(|item| &item.id == id)
+    };
+    let mut reports: Vec<MetricReport> = metrics.iter()
+        .map(|metric| MetricReport {
+            metric: metric.clone(),
+            status: MetricStatus::Uncovered,
+            story_ids: vec![],
+            assessment: "Not assessed by the LLM.".to_string(),
+        })
+        .collect();
+    for assessment in assessments {
+        let Some(report) = assessment.metric.checked_sub(1).and_then(|i| reports.get_mut(i)) else { continue };
+        report.story_ids = assessment.story_ids.into_iter().filter(known).collect();
+        report.status = if report.story_ids.is_empty() { MetricStatus::Uncovered } else { assessment.status };
+        report.assessment = assessment.assessment;
+    }
+    reports
+}
+
+pub(crate) fn recommended_item(project: &Project, metrics: &[String], recommendation: &Recommendation) -> BacklogItem {
+    let metric = recommendation.metric.and_then(|n| n.checked_sub(1)).and_then(|i| metrics.get(i));
+    BacklogItem {
+        id: import::next_id(project, import::id_prefix(&ItemType::UserStory)),
+        item_type: ItemType::UserStory,
+        title: recommendation.title.clone(),
+        story: recommendation.story.clone(),
+        acceptance_criteria: metric.map(|m| vec![format!("Moves the success metric: {}", m)]).unwrap_or_default(),
+        priority: Priority::Medium,
+        effort: recommendation.effort.clamp(1, 13),
+        status: Status::Todo,
+        created: Utc::now(),
+        sprint: None,
+        dependencies: vec![],
+        started_at: None,
+        completed_at: None,
+        component: None,
+        parent: None,
+        tags: vec![],
+        external_ref: None,
+        history: vec![],
+        review: None,
+        criteria_check: None,
+        extra: Default::default(),
+    }
+}
+
+fn print_report(reports: &[MetricReport]) {
+    println!("📏 PRD Success Metrics Check");
+    for (i, report) in reports.iter().enumerate() {
+        let (marker, label) = match report.status {
+            MetricStatus::OnTrack => ("✅", "On track"),
+            MetricStatus::AtRisk => ("🟡", "At risk"),
+            MetricStatus::Uncovered => ("⚪", "No stories"),
+        };
+        println!();
+        println!("{} {}. {} ({})", marker, i + 1, report.metric, label);
+        if !report.story_ids.is_empty() {
+            println!("   Stories: {}", report.story_ids.join(", "));
+        }
+        if !report.assessment.is_empty() {
+            println!("   {}", report.assessment);
+        }
+    }
+    let count = |status| reports.iter().filter(|r| r.status == status).count();
+    println!();
+    println!("📊 Summary: {} on track, {} at risk, {} with no stories",
+        count(MetricStatus::OnTrack), count(MetricStatus::AtRisk), count(MetricStatus::Uncovered));
+}
+
+/// Map completed and planned work onto the PRD's success metrics and offer to add the
+/// stories the LLM recommends for the gaps.
+pub async fn check(ctx: &AppContext) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let metrics = project.meta.prd.as_ref().map(|prd| prd.success_metrics.clone()).unwrap_or_default();
+    if metrics.is_empty() {
+        anyhow::bail!("The PRD has no success metrics. Record them with 'env-coach init --metric \"...\"' (repeatable).");
+    }
+
+    let prompt_template_path = std::path::Path::new(".env-coach/prompts/metrics_check.md");
+    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
+        Ok(template) => template,
+        Err(_) => {
+            println!("⚠️ Metrics check prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_metrics_check_prompt_content()
+        }
+    };
+    let prompt_template = prompts::fill_project_context(&prompt_template, &project, None);
+    let prompt = build_metrics_prompt(&prompt_template, &project, &metrics);
+
+    println!("🤖 Asking LLM to map the work onto the success metrics...");
+    let response = ollama::send_generation_prompt(project.llm(), &prompt)
+        .await
+        .context("LLM call for the metrics check failed")?;
+    let parsed = parse_metrics_response(&response)?;
+
+    let reports = reconcile(&project, &metrics, parsed.metrics);
+    print_report(&reports);
+
+    if parsed.recommendations.is_empty() {
+        hints::print_next_steps(Some(&project), &[
+            ("env-coach plan-sprint --suggest-goal", "Plan the next sprint around the metrics"),
+        ]);
+        return Ok(());
+    }
+
+    println!();
+    println!("💡 Recommended backlog additions:");
+    for (i, recommendation) in parsed.recommendations.iter().enumerate() {
+        let serves = recommendation.metric.map(|n| format!(" (metric {})", n)).unwrap_or_default();
+        println!("   {}. {} [{} pts]{}", i + 1, recommendation.title, recommendation.effort.clamp(1, 13), serves);
+        println!("      {}", recommendation.story);
+    }
+    println!();
+    if !ctx.prompter.confirm(&format!("👉 Add these {} stories to the backlog?", parsed.recommendations.len()))? {
+        println!("⏭️  No stories added");
+        return Ok(());
+    }
+
+    let mut added = vec![];
+    for recommendation in &parsed.recommendations {
+        let item = recommended_item(&project, &metrics, recommendation);
+        added.push(item.id.clone());
+        project.backlog.push(item);
+    }
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    println!("✅ Added {}", added.join(", "));
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach list-backlog --status todo", "Review the new stories"),
+        (format!("env-coach edit-item {} --editor", added[0]).as_str(), "Refine acceptance criteria"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Prd;
+
+    fn project() -> Project {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.meta.prd = Some(Prd { problem: "Slow onboarding".to_string(), success_metrics: vec!["Setup under 5 min".to_string(), "Page load under 1s".to_string()], reconciled: None });
+        let item = recommended_item(&project, &[], &Recommendation { title: "Wizard".to_string(), story: "As a user...".to_string(), metric: None, effort: 40 });
+        assert_eq!(item.effort, 13);
+        project.backlog.push(item.with_status(Status::Done));
+        project
+    }
+
+    #[test]
+    fn test_build_metrics_prompt_numbers_metrics_and_lists_work() {
+        let project = project();
+        let metrics = project.meta.prd.clone().unwrap().success_metrics;
+        let prompt = build_metrics_prompt(&Templates::default_metrics_check_prompt_content(), &project, &metrics);
+        assert!(prompt.contains("1. Setup under 5 min\n2. Page load under 1s"));
+        assert!(prompt.contains("- US-001 [Done, 13 pts] Wizard"));
+        assert!(prompt.contains("Backlog is empty."));
+        assert!(!prompt.replace(crate::project_summary::PLACEHOLDER, "").contains("{{"));
+    }
+
+    #[test]
+    fn test_reconcile_checks_the_llm_answer() {
+        let project = project();
+        let metrics = project.meta.prd.clone().unwrap().success_metrics;
+        let parsed = parse_metrics_response(r#"{"metrics": [
+            {"metric": 2, "status": "OnTrack", "story_ids": ["US-404"], "assessment": "Invented"},
+            {"metric": 1, "status": "AtRisk", "story_ids": ["US-001"], "assessment": "Wizard only"},
+            {"metric": 7, "status": "OnTrack", "story_ids": ["US-001"]}
+        ]}"#).unwrap();
+        assert!(parsed.recommendations.is_empty());
+
+        let reports = reconcile(&project, &metrics, parsed.metrics);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].status, MetricStatus::AtRisk);
+        assert_eq!(reports[0].story_ids, vec!["US-001"]);
+        // The only story ID was unknown, so the metric has no stories
+        assert_eq!(reports[1].status, MetricStatus::Uncovered);
+        assert!(parse_metrics_response(r#"{"metrics": [{"metric": 1, "status": "Great"}]}"#).is_err());
+    }
+}
@@ -0,0 +1,328 @@
+// src/scripts/prompt_test.rs
+//! `prompt-test`: render a prompt template against sample data and measure how often the
+//! model's answer parses, for tuning prompts to smaller models.
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::auto_update::llm_parsers::{parse_assist_task_response, LlmUserStoryResponse};
+use crate::config::LlmRole;
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::scripts::{breakdown, bugs, criteria_check, estimate, groom, prd, reconcile, retro, review_task, risks, sprint, sprint_goal};
+use crate::tokens;
+
+/// How a response is checked: with the parser of the command that sends the prompt.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum ParserKind {
+    /// `{"user_stories": [...]}`, as `add-requirement` expects
+    Stories,
+    /// `{"suggestions": [...]}`, as `assist-task`, `execute-task` and `chat` expect
+    Suggestions,
+    /// The story IDs `plan-sprint` suggests
+    SprintPlan,
+    /// How `complete-sprint` splits a partially done story
+    StorySplit,
+    /// The goals `plan-sprint --suggest-goal` offers
+    SprintGoal,
+    /// The risk register of `risks`
+    Risks,
+    /// The retrospective `retro` records
+    Retro,
+    /// The report `add-bug` turns into a bug item
+    Bug,
+    /// The metrics check of `prd check`
+    Metrics,
+    /// The findings of `reconcile`
+    Reconcile,
+    /// The findings of `groom`
+    Groom,
+    /// The tasks of `breakdown`
+    Breakdown,
+    /// The estimates of `estimate`
+    Estimates,
+    /// The review of `review-task`
+    Review,
+    /// The acceptance criteria checklist of `complete-task --verify`
+    CriteriaCheck,
+    /// Any non-empty text, for prompts answered in prose such as `ask`
+    Text,
+    /// Any JSON value
+    Json,
+}
+
+impl ParserKind {
+    /// The parser of the command that sends `file_name`; `Json` for files that are not a
+    /// known prompt.
+    fn for_prompt(file_name: &str) -> Self {
+        match file_name.split('.').next().unwrap_or_default() {
+            "requirements_analyst" => Self::Stories,
+            "task_assistant" | "fix_build" | "fix_tests" | "test_writer" | "chat" => Self::Suggestions,
+            "sprint_planner" => Self::SprintPlan,
+            "story_splitter" => Self::StorySplit,
+            "sprint_goal" => Self::SprintGoal,
+            "risk_analyst" => Self::Risks,
+            "retrospective" => Self::Retro,
+            "bug_analyst" => Self::Bug,
+            "metrics_check" => Self::Metrics,
+            "backlog_reconcile" => Self::Reconcile,
+            "backlog_groomer" => Self::Groom,
+            "task_breakdown" => Self::Breakdown,
+            "effort_estimator" => Self::Estimates,
+            "code_reviewer" => Self::Review,
+            "criteria_checker" => Self::CriteriaCheck,
+            "project_qa" | "project_summary" => Self::Text,
+            _ => Self::Json,
+        }
+    }
+
+    /// Why `response` is not usable, or `None` when it is.
+    fn check(self, response: &str) -> Option<String> {
+        let result = match self {
+            Self::Stories => match serde_json::from_str::<LlmUserStoryResponse>(response) {
+                Ok(parsed) if parsed.user_stories.is_empty() => return Some("no user stories".to_string()),
+                Ok(_) => return None,
+                Err(e) => return Some(e.to_string()),
+            },
+            Self::Suggestions => match parse_assist_task_response(response) {
+                Ok(parsed) if parsed.suggestions.is_empty() => return Some("no suggestions".to_string()),
+                result => result.map(drop),
+            },
+            Self::SprintPlan => sprint::parse_sprint_plan_response(response).map(drop),
+            Self::StorySplit => sprint::parse_split_response(response).map(drop),
+            Self::SprintGoal => sprint_goal::parse_goal_response(response).map(drop),
+            Self::Risks => risks::parse_risk_response(response).map(drop),
+            Self::Retro => retro::parse_retro_response(response).map(drop),
+            Self::Bug => bugs::parse_bug_response(response).map(drop),
+            Self::Metrics => prd::parse_metrics_response(response).map(drop),
+            Self::Reconcile => reconcile::parse_reconcile_response(response).map(drop),
+            Self::Groom => groom::parse_groom_response(response).map(drop),
+            Self::Breakdown => breakdown::parse_breakdown_response(response).map(drop),
+            Self::Estimates => estimate::parse_estimate_response(response).map(drop),
+            Self::Review => review_task::parse_review_response(response, &[]).map(drop),
+            Self::CriteriaCheck => criteria_check::parse_criteria_response(response, &[]).map(drop),
+            Self::Text => match response.trim().is_empty() {
+                true => return Some("empty answer".to_string()),
+                false => return None,
+            },
+            Self::Json => match serde_json::from_str::<Value>(response) {
+                Ok(_) => return None,
+                Err(e) => return Some(e.to_string()),
+            },
+        };
+        result.err().map(|e| e.root_cause().to_string())
+    }
+
+    fn role(self) -> LlmRole {
+        match self {
+            Self::Suggestions | Self::Bug => LlmRole::Coder,
+            Self::Review | Self::CriteriaCheck => LlmRole::Reviewer,
+            _ => LlmRole::Planner,
+        }
+    }
+}
+
+/// A file path, or the name of a prompt: the project's copy, else the built-in default.
+/// Returns the template and the file name its parser is chosen by.
+fn load_template(template: &str) -> Result<(String, String)> {
+    let path = Path::new(template);
+    if path.is_file() {
+        let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let file_name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        return Ok((content, file_name));
+    }
+    let prompt = prompts::find_default(template)
+        .ok_or_else(|| anyhow::anyhow!("'{}' is neither a file nor a known prompt (see `env-coach prompts list`)", template))?;
+    let installed = Path::new(".env-coach/prompts").join(prompt.file_name);
+    let content = std::fs::read_to_string(&installed).unwrap_or_else(|_| (prompt.content)());
+    Ok((content, prompt.file_name.to_string()))
+}
+
+/// How a fixture value is written into the prompt: strings as they are, lists one entry
+/// per line, anything else as JSON.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(entries) => entries.iter().map(|entry| format!("- {}", value_text(entry))).collect::<Vec<_>>().join("\n"),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) | Some(Value::Bool(false)) => false,
+        Some(Value::String(text)) => !text.is_empty(),
+        Some(Value::Array(entries)) => !entries.is_empty(),
+        Some(_) => true,
+    }
+}
+
+/// The first `{{#<tag> key}}...{{/<tag>}}` block: (start, end, key, body).
+fn find_block<'a>(template: &'a str, tag: &str) -> Option<(usize, usize, &'a str, &'a str)> {
+    let open = format!("{{{{#{} ", tag);
+    let close = format!("{{{{/{}}}}}", tag);
+    let start = template.find(&open)?;
+    let key_end = start + template[start..].find("}}")?;
+    let key = template[start + open.len()..key_end].trim();
+    let body_end = key_end + template[key_end..].find(&close)?;
+    Some((start, body_end + close.len(), key, &template[key_end + 2..body_end]))
+}
+
+/// Fill `template` from the fixture's fields: `{{key}}`, `{{#if key}}...{{/if}}` and
+/// `{{#each key}}...{{/each}}` with `{{this}}` and `{{this.field}}` inside.
+pub fn render(template: &str, fixture: &serde_json::Map<String, Value>) -> String {
+    let mut rendered = template.to_string();
+    while let Some((start, end, key, body)) = find_block(&rendered, "each") {
+        let entries = match fixture.get(key) {
+            Some(Value::Array(entries)) => entries.clone(),
+            _ => vec![],
+        };
+        let expanded: String = entries.iter().map(|entry| {
+            let mut text = body.replace("{{this}}", &value_text(entry));
+            if let Value::Object(fields) = entry {
+                for (field, value) in fields {
+                    text = text.replace(&format!("{{{{this.{}}}}}", field), &value_text(value));
+                }
+            }
+            text
+        }).collect();
+        rendered.replace_range(start..end, &expanded);
+    }
+    while let Some((start, end, key, body)) = find_block(&rendered, "if") {
+        let kept = if is_truthy(fixture.get(key)) { body.to_string() } else { String::new() };
+        rendered.replace_range(start..end, &kept);
+    }
+    for (key, value) in fixture {
+        rendered = rendered.replace(&format!("{{{{{}}}}}", key), &value_text(value));
+        // Objects fill dotted placeholders too, e.g. {"prd": {"problem": ...}} → {{prd.problem}}
+        if let Value::Object(fields) = value {
+            for (field, value) in fields {
+                rendered = rendered.replace(&format!("{{{{{}.{}}}}}", key, field), &value_text(value));
+            }
+        }
+    }
+    rendered
+}
+
+/// Placeholders the fixture did not fill, in order of appearance.
+fn unfilled(rendered: &str) -> Vec<String> {
+    let mut names: Vec<String> = vec![];
+    let mut rest = rendered;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else { break };
+        let name = rest[start + 2..start + len].trim().to_string();
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+        rest = &rest[start + len + 2..];
+    }
+    names
+}
+
+pub async fn run(ctx: &AppContext, template: String, fixture: &Path, runs: u32, parser: Option<ParserKind>) -> Result<()> {
+    let (content, file_name) = load_template(&template)?;
+    let fixture_text = std::fs::read_to_string(fixture).with_context(|| format!("Failed to read fixture {}", fixture.display()))?;
+    let Value::Object(fields) = serde_json::from_str(&fixture_text).with_context(|| format!("Failed to parse fixture {}", fixture.display()))? else {
+        anyhow::bail!("The fixture must be a JSON object of placeholder values, e.g. {{\"project_name\": \"demo\"}}");
+    };
+    let prompt = render(&content, &fields);
+    let parser = parser.unwrap_or_else(|| ParserKind::for_prompt(&file_name));
+
+    let missing = unfilled(&prompt);
+    if !missing.is_empty() {
+        println!("⚠️  Placeholders not in the fixture: {}", missing.join(", "));
+    }
+    if runs == 0 {
+        println!("{}", prompt);
+        println!("📏 ~{} tokens", tokens::estimate_tokens(&prompt));
+        hints::print_next_steps(None, &[
+            (format!("env-coach prompt-test {} --fixture {} --runs 5", template, fixture.display()).as_str(), "Send it to the model and measure the parse rate"),
+        ]);
+        return Ok(());
+    }
+
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let llm = project.llm_for(parser.role());
+    println!("🧪 Sending {} (~{} tokens) to {} {} time(s), checking answers as {:?}",
+        file_name, tokens::estimate_tokens(&prompt), llm.model, runs, parser);
+    let mut parsed = 0;
+    for run in 1..=runs {
+        let started = Instant::now();
+        let response = ollama::send_generation_prompt(&llm, &prompt).await;
+        let seconds = started.elapsed().as_secs_f64();
+        match response.map(|text| parser.check(&text)) {
+            Ok(None) => {
+                parsed += 1;
+                println!("   ✅ run {:<3} {:>7.1}s", run, seconds);
+            }
+            Ok(Some(problem)) => println!("   ⚠️  run {:<3} {:>7.1}s  {}", run, seconds, problem),
+            Err(e) => println!("   ❌ run {:<3} {}", run, e),
+        }
+    }
+    println!("📊 {}/{} answers parsed ({:.0}%)", parsed, runs, parsed as f64 * 100.0 / runs as f64);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::templates::Templates;
+
+    fn fixture(json: Value) -> serde_json::Map<String, Value> {
+        match json {
+            Value::Object(fields) => fields,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_render_fills_placeholders_blocks_and_lists() {
+        let fields = fixture(serde_json::json!({
+            "name": "demo",
+            "criteria": ["fast", "safe"],
+            "items": [{"id": "US-001", "title": "Login"}],
+            "tags": [],
+            "effort": 3,
+            "prd": {"problem": "Slow"},
+        }));
+        let template = "{{prd.problem}} {{name}} ({{effort}})\n{{#each criteria}}  * {{this}}\n{{/each}}{{#each items}}{{this.id}}: {{this.title}}\n{{/each}}{{#if tags}}Tags: {{tags}}{{/if}}{{#if name}}Named{{/if}} {{missing}}";
+        let rendered = render(template, &fields);
+        assert_eq!(rendered, "Slow demo (3)\n  * fast\n  * safe\nUS-001: Login\nNamed {{missing}}");
+        assert_eq!(unfilled(&rendered), vec!["missing"]);
+        assert_eq!(value_text(&serde_json::json!(["a", "b"])), "- a\n- b");
+    }
+
+    #[test]
+    fn test_parsers_follow_the_prompt() {
+        assert_eq!(ParserKind::for_prompt("requirements_analyst.md"), ParserKind::Stories);
+        assert_eq!(ParserKind::for_prompt("task_assistant.python.md"), ParserKind::Suggestions);
+        assert_eq!(ParserKind::for_prompt("sprint_planner.md"), ParserKind::SprintPlan);
+        assert_eq!(ParserKind::for_prompt("backlog_groomer.md"), ParserKind::Groom);
+        assert_eq!(ParserKind::for_prompt("my_prompt.md"), ParserKind::Json);
+
+        assert_eq!(ParserKind::Stories.check(r#"{"user_stories": [{"title": "t", "story": "s", "priority": "High", "effort": 3, "acceptance_criteria": []}]}"#), None);
+        assert!(ParserKind::Stories.check(r#"{"user_stories": []}"#).is_some());
+        assert_eq!(ParserKind::Suggestions.check(r#"{"suggestions": [{"type": "general_advice", "content": "Use clap"}]}"#), None);
+        assert!(ParserKind::Suggestions.check("```rust\nfn main() {}\n```").is_some());
+        assert!(ParserKind::Json.check("Sure! {}").is_some());
+        assert_eq!(ParserKind::SprintPlan.check(r#"{"suggested_story_ids": ["US-001"]}"#), None);
+        assert!(ParserKind::SprintPlan.check(r#"{"stories": ["US-001"]}"#).is_some());
+        assert!(ParserKind::Breakdown.check(r#"{"tasks": []}"#).is_some());
+        assert!(ParserKind::Text.check("  ").is_some());
+    }
+
+    #[test]
+    fn test_default_prompts_render_from_a_fixture() {
+        let fields = fixture(serde_json::json!({
+            "requirement": "Users can log in",
+            "project_name": "demo",
+        }));
+        let rendered = render(&Templates::default_requirements_analyst_prompt_content(), &fields);
+        assert!(rendered.contains("Users can log in"));
+        assert!(!unfilled(&rendered).contains(&"requirement".to_string()));
+    }
+}
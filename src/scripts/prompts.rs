@@ -0,0 +1,327 @@
+// src/scripts/prompts.rs
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+use crate::context::AppContext;
+use crate::hints;
+use crate::prompts::{self, DefaultPrompt, UpgradeStatus, DEFAULT_PROMPTS};
+use crate::scripts::edit_item::editor_command;
+
+const PROMPTS_DIR: &str = ".env-coach/prompts";
+
+fn write_prompt(ctx: &AppContext, prompts_dir: &Path, prompt: &DefaultPrompt, content: &str) -> Result<()> {
+    if ctx.dry_run {
+        return Ok(());
+    }
+    std::fs::write(prompts_dir.join(prompt.file_name), content)
+        .with_context(|| format!("Failed to write {}", prompt.file_name))?;
+    prompts::record_base(prompts_dir, prompt)
+}
+
+/// Ask what to do with a customized prompt that cannot be upgraded automatically.
+/// Under `--yes` the new default is written next to the user's file, which loses nothing.
+fn resolve_conflict(ctx: &AppContext, prompts_dir: &Path, prompt: &DefaultPrompt, merged: Option<String>) -> Result<()> {
+    let side_file = if merged.is_some() { format!("{}.merged", prompt.file_name) } else { format!("{}.new", prompt.file_name) };
+    let answer = if ctx.prompter.assume_yes() {
+        "w".to_string()
+    } else {
+        ctx.prompter.ask(&format!("👉 [k]eep yours, [t]ake the new default, or [w]rite {} to merge by hand? ", side_file))?
+    };
+    match answer.to_lowercase().as_str() {
+        "t" | "take" => {
+            write_prompt(ctx, prompts_dir, prompt, &(prompt.content)())?;
+            println!("   ✅ {} replaced with the new default", prompt.file_name);
+        }
+        "w" | "write" => {
+            if !ctx.dry_run {
+                let content = merged.unwrap_or_else(|| (prompt.content)());
+                std::fs::write(prompts_dir.join(&side_file), content)
+                    .with_context(|| format!("Failed to write {}", side_file))?;
+                prompts::record_base(prompts_dir, prompt)?;
+            }
+            println!("   📝 Wrote {}; merge it into {} and delete it when done", side_file, prompt.file_name);
+        }
+        _ => println!("   ⏭️  Kept your {}", prompt.file_name),
+    }
+    Ok(())
+}
+
+/// Compare every installed prompt with the default shipped in this version and help
+/// bring customized prompts up to date.
+pub fn upgrade(ctx: &AppContext) -> Result<()> {
+    let prompts_dir = Path::new(PROMPTS_DIR);
+    if !prompts_dir.exists() {
+        anyhow::bail!("{} not found. Run 'env-coach init' first.", PROMPTS_DIR);
+    }
+    let versions = prompts::installed_versions(prompts_dir);
+
+    for prompt in DEFAULT_PROMPTS {
+        let current = std::fs::read_to_string(prompts_dir.join(prompt.file_name)).ok();
+        let base = prompts::installed_base(prompts_dir, prompt.file_name);
+        let new_default = (prompt.content)();
+        let installed = versions.get(prompt.file_name)
+            .map(|v| format!("v{}", v))
+            .unwrap_or_else(|| "unversioned".to_string());
+
+        match prompts::upgrade_status(current.as_deref(), base.as_deref(), &new_default) {
+            UpgradeStatus::UpToDate => {
+                // Keep the recorded base in step so later upgrades can merge
+                if base.as_deref() != Some(new_default.as_str()) && !ctx.dry_run {
+                    prompts::record_base(prompts_dir, prompt)?;
+                }
+                println!("✅ {} is up to date (v{})", prompt.file_name, prompt.version);
+            }
+            UpgradeStatus::Customized => {
+                println!("✏️  {} has local edits; the default has not changed since {}", prompt.file_name, installed);
+            }
+            UpgradeStatus::Missing => {
+                write_prompt(ctx, prompts_dir, prompt, &new_default)?;
+                println!("📄 {} created (v{})", prompt.file_name, prompt.version);
+            }
+            UpgradeStatus::Unmodified => {
+                write_prompt(ctx, prompts_dir, prompt, &new_default)?;
+                println!("⬆️  {} upgraded {} → v{} (it had no local edits)", prompt.file_name, installed, prompt.version);
+            }
+            UpgradeStatus::Mergeable(merged) => {
+                println!("🔀 {} ({} → v{}) has local edits that merge cleanly with the new default:", prompt.file_name, installed, prompt.version);
+                println!("{}", prompts::diff(current.as_deref().unwrap_or_default(), &merged));
+                if ctx.prompter.confirm(&format!("👉 Apply the merged {}?", prompt.file_name))? {
+                    write_prompt(ctx, prompts_dir, prompt, &merged)?;
+                    println!("   ✅ {} upgraded, your edits kept", prompt.file_name);
+                } else {
+                    println!("   ⏭️  Kept your {}", prompt.file_name);
+                }
+            }
+            UpgradeStatus::Conflict(merged) => {
+                println!("⚠️  {} ({} → v{}) is customized and differs from the new default:", prompt.file_name, installed, prompt.version);
+                println!("{}", prompts::diff(current.as_deref().unwrap_or_default(), &new_default));
+                resolve_conflict(ctx, prompts_dir, prompt, merged)?;
+            }
+        }
+    }
+
+    if ctx.dry_run {
+        println!("🔍 Dry run: no prompt files written");
+    }
+    hints::print_next_steps(None, &[
+        (format!("git diff {}", PROMPTS_DIR).as_str(), "Review the prompt changes"),
+    ]);
+    Ok(())
+}
+
+/// The default prompt called `name`, or an error listing the known ones.
+/// A name like `task_assistant.python` selects the language variant of a prompt; only
+/// the prompts in `VARIANT_PROMPTS` have variants, and only for `VARIANT_LANGUAGES`.
+fn lookup(name: &str) -> Result<(&'static DefaultPrompt, Option<&str>)> {
+    if let Some(prompt) = prompts::find_default(name) {
+        return Ok((prompt, None));
+    }
+    let stem = name.strip_suffix(".md").unwrap_or(name);
+    let (prompt, language) = stem.rsplit_once('.')
+        .filter(|(_, language)| !language.is_empty())
+        .and_then(|(base, language)| prompts::find_default(base).map(|prompt| (prompt, language)))
+        .ok_or_else(|| {
+            let known: Vec<&str> = DEFAULT_PROMPTS.iter().map(|prompt| prompt.file_name.trim_end_matches(".md")).collect();
+            anyhow::anyhow!("Unknown prompt '{}'. Known prompts: {}", name, known.join(", "))
+        })?;
+    if !prompts::VARIANT_PROMPTS.contains(&prompt.file_name) {
+        let supported: Vec<&str> = prompts::VARIANT_PROMPTS.iter().map(|file_name| file_name.trim_end_matches(".md")).collect();
+        anyhow::bail!("{} has no language variants; it is always read as {}. Prompts with variants: {}",
+            prompt.file_name.trim_end_matches(".md"), prompt.file_name, supported.join(", "));
+    }
+    if !prompts::VARIANT_LANGUAGES.contains(&language) {
+        anyhow::bail!("Unknown language '{}'. Variants exist for: {}", language, prompts::VARIANT_LANGUAGES.join(", "));
+    }
+    Ok((prompt, Some(language)))
+}
+
+/// Languages with an installed variant of `prompt`, e.g. `["go", "python"]`.
+fn variants(prompts_dir: &Path, prompt: &DefaultPrompt) -> Vec<String> {
+    let prefix = format!("{}.", prompt.file_name.trim_end_matches(".md"));
+    let mut languages: Vec<String> = std::fs::read_dir(prompts_dir).into_iter().flatten().flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let language = file_name.strip_prefix(&prefix)?.strip_suffix(".md")?;
+            (!language.is_empty() && !language.contains('.')).then(|| language.to_string())
+        })
+        .collect();
+    languages.sort();
+    languages
+}
+
+/// One-word state of the installed copy, for `prompts list`.
+fn state(prompts_dir: &Path, prompt: &DefaultPrompt) -> &'static str {
+    let current = std::fs::read_to_string(prompts_dir.join(prompt.file_name)).ok();
+    let base = prompts::installed_base(prompts_dir, prompt.file_name);
+    match prompts::upgrade_status(current.as_deref(), base.as_deref(), &(prompt.content)()) {
+        UpgradeStatus::Missing => "missing",
+        UpgradeStatus::UpToDate => "default",
+        UpgradeStatus::Unmodified => "outdated",
+        UpgradeStatus::Customized | UpgradeStatus::Mergeable(_) | UpgradeStatus::Conflict(_) => "customized",
+    }
+}
+
+/// Every default prompt and whether the project's copy is customized.
+pub fn list() -> Result<()> {
+    let prompts_dir = Path::new(PROMPTS_DIR);
+    println!("📝 Prompts in {}:", PROMPTS_DIR);
+    for prompt in DEFAULT_PROMPTS {
+        let state = state(prompts_dir, prompt);
+        let icon = match state {
+            "customized" => "✏️ ",
+            "default" => "✅",
+            _ => "⚠️ ",
+        };
+        println!("   {} {:<22} {} (v{})", icon, prompt.file_name.trim_end_matches(".md"), state, prompt.version);
+        for language in variants(prompts_dir, prompt) {
+            println!("      ↳ {}", prompts::variant_file_name(prompt.file_name, &language));
+        }
+    }
+    hints::print_next_steps(None, &[
+        ("env-coach prompts show <name>", "Read a prompt"),
+        ("env-coach prompts edit <name>", "Customize it in $EDITOR"),
+        ("env-coach prompts upgrade", "Bring outdated or missing prompts up to date"),
+    ]);
+    Ok(())
+}
+
+/// Print the project's copy of a prompt, or the built-in default.
+pub fn show(name: &str, default: bool) -> Result<()> {
+    let (prompt, language) = lookup(name)?;
+    let prompts_dir = Path::new(PROMPTS_DIR);
+    if let (Some(language), false) = (language, default) {
+        let path = prompts_dir.join(prompts::variant_file_name(prompt.file_name, language));
+        match std::fs::read_to_string(&path) {
+            Ok(content) => print!("{}", content),
+            Err(_) => {
+                println!("ℹ️  {} not found; {} projects use {}", path.display(), language, prompt.file_name);
+                println!();
+                return show(prompt.file_name, false);
+            }
+        }
+        return Ok(());
+    }
+    let path = prompts_dir.join(prompt.file_name);
+    match std::fs::read_to_string(&path) {
+        Ok(content) if !default => print!("{}", content),
+        _ => {
+            if !default {
+                println!("ℹ️  {} not found; the built-in default (v{}) is:", path.display(), prompt.version);
+                println!();
+            }
+            print!("{}", (prompt.content)());
+        }
+    }
+    Ok(())
+}
+
+/// Open a prompt in `$EDITOR`, installing the default first when it is missing.
+pub fn edit(ctx: &AppContext, name: &str) -> Result<()> {
+    let (prompt, language) = lookup(name)?;
+    let prompts_dir = Path::new(PROMPTS_DIR);
+    if !prompts_dir.exists() {
+        anyhow::bail!("{} not found. Run 'env-coach init' first.", PROMPTS_DIR);
+    }
+    let path = match language {
+        Some(language) => prompts_dir.join(prompts::variant_file_name(prompt.file_name, language)),
+        None => prompts_dir.join(prompt.file_name),
+    };
+    if ctx.dry_run {
+        println!("🔍 Dry run: {} not opened", path.display());
+        return Ok(());
+    }
+    prompts::install_default(prompts_dir, prompt)?;
+    if !path.exists() {
+        // A new variant starts as a copy of the generic prompt
+        std::fs::copy(prompts_dir.join(prompt.file_name), &path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+    }
+    let before = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let editor = editor_command();
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to launch editor '{}'. Set $EDITOR.", editor))?;
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}", editor, status);
+    }
+
+    let after = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if after == before {
+        println!("ℹ️ No changes made to {}", path.display());
+    } else {
+        println!("✅ Saved {}", path.display());
+        hints::print_next_steps(None, &[
+            (format!("env-coach prompts reset {}", name).as_str(), "Go back to the built-in default"),
+        ]);
+    }
+    Ok(())
+}
+
+/// Replace the project's copy of a prompt with the built-in default, after showing what
+/// would be lost. Resetting a language variant deletes it, so the generic prompt is used.
+pub fn reset(ctx: &AppContext, name: &str) -> Result<()> {
+    let (prompt, language) = lookup(name)?;
+    let prompts_dir = Path::new(PROMPTS_DIR);
+    if !prompts_dir.exists() {
+        anyhow::bail!("{} not found. Run 'env-coach init' first.", PROMPTS_DIR);
+    }
+    if let Some(language) = language {
+        let file_name = prompts::variant_file_name(prompt.file_name, language);
+        let path = prompts_dir.join(&file_name);
+        if !path.exists() {
+            println!("✅ {} does not exist; {} projects already use {}", file_name, language, prompt.file_name);
+            return Ok(());
+        }
+        if !ctx.prompter.confirm(&format!("👉 Delete {}? {} projects will use {} again", file_name, language, prompt.file_name))? {
+            println!("❌ Reset cancelled");
+            return Ok(());
+        }
+        if ctx.dry_run {
+            println!("🔍 Dry run: {} not deleted", file_name);
+        } else {
+            std::fs::remove_file(&path).with_context(|| format!("Failed to delete {}", path.display()))?;
+            println!("🗑️  Deleted {}", file_name);
+        }
+        return Ok(());
+    }
+    let default = (prompt.content)();
+    if let Ok(current) = std::fs::read_to_string(prompts_dir.join(prompt.file_name)) {
+        if current == default {
+            println!("✅ {} is already the built-in default (v{})", prompt.file_name, prompt.version);
+            return Ok(());
+        }
+        println!("{}", prompts::diff(&current, &default));
+        if !ctx.prompter.confirm(&format!("👉 Replace {} with the built-in default? Your edits above are lost", prompt.file_name))? {
+            println!("❌ Reset cancelled");
+            return Ok(());
+        }
+    }
+    write_prompt(ctx, prompts_dir, prompt, &default)?;
+    if ctx.dry_run {
+        println!("🔍 Dry run: {} not written", prompt.file_name);
+    } else {
+        println!("✅ {} reset to the built-in default (v{})", prompt.file_name, prompt.version);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_accepts_only_variants_that_are_read() {
+        assert_eq!(lookup("task_assistant.python").unwrap().1, Some("python"));
+        assert_eq!(lookup("code_reviewer.go.md").unwrap().1, Some("go"));
+        assert_eq!(lookup("sprint_planner").unwrap().1, None);
+        assert!(lookup("sprint_planner.python").is_err());
+        assert!(lookup("task_assistant.pyhton").is_err());
+        assert!(lookup("no_such_prompt.python").is_err());
+    }
+}
@@ -0,0 +1,264 @@
+// src/scripts/reconcile.rs
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::config::{ItemType, Prd, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::scripts::archive;
+use crate::scripts::prd::{self, Recommendation};
+use crate::templates::Templates;
+
+#[derive(Deserialize, Debug)]
+struct OutOfScope {
+    story_id: String,
+    #[serde(default)]
+    reason: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct UncoveredMetric {
+    /// 1-based index into the PRD's success metrics
+    metric: usize,
+    #[serde(default)]
+    reason: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct LlmReconcileResponse {
+    #[serde(default)]
+    out_of_scope: Vec<OutOfScope>,
+    #[serde(default)]
+    uncovered_metrics: Vec<UncoveredMetric>,
+    #[serde(default)]
+    new_stories: Vec<Recommendation>,
+}
+
+/// The LLM's answer after checking it against the project.
+#[derive(Debug)]
+struct ReconcilePlan {
+    /// Open backlog items, with the reason they no longer fit
+    out_of_scope: Vec<(String, String)>,
+    /// Metric text and why nothing covers it
+    uncovered_metrics: Vec<(String, String)>,
+    new_stories: Vec<Recommendation>,
+}
+
+fn build_reconcile_prompt(template: &str, project: &Project, prd: &Prd) -> String {
+    let numbered = prd.success_metrics.iter().enumerate()
+        .map(|(i, metric)| format!("{}. {}", i + 1, metric))
+        .collect::<Vec<_>>()
+        .join("\n");
+    template
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{problem}}", if prd.problem.is_empty() { "Not recorded." } else { &prd.problem })
+        .replace("{{metrics}}", if numbered.is_empty() { "None recorded." } else { &numbered })
+        .replace("{{completed}}", &prd::format_items(&prd::completed_items(project), "Nothing completed yet."))
+        .replace("{{remaining}}", &prd::format_items(&prd::remaining_items(project), "Backlog is empty."))
+}
+
+pub(crate) fn parse_reconcile_response(response: &str) -> Result<LlmReconcileResponse> {
+    serde_json::from_str(response).with_context(|| {
+        let snippet = response.chars().take(500).collect::<String>();
+        format!("Failed to parse LLM backlog reconciliation. Response (first 500 chars): '{}'", snippet)
+    })
+}
+
+/// Keep only changes that make sense for the project: out-of-scope IDs must be open,
+/// non-epic backlog items, and metric numbers must exist. Duplicates are dropped.
+fn check_response(project: &Project, prd: &Prd, response: LlmReconcileResponse) -> ReconcilePlan {
+    let mut out_of_scope: Vec<(String, String)> = vec![];
+    for entry in response.out_of_scope {
+        let open = project.backlog.iter().any(|item| {
+            item.id == entry.story_id && !matches!(item.status, Status::Done) && !matches!(item.item_type, ItemType::Epic)
+        });
+        if open && !out_of_scope.iter().any(|(id, _)| *id == entry.story_id) {
+            out_of_scope.push((entry.story_id, entry.reason));
+        }
+    }
+
+    let mut uncovered: Vec<usize> = vec![];
+    let mut uncovered_metrics = vec![];
+    for entry in response.uncovered_metrics {
+        let Some(metric) = entry.metric.checked_sub(1).and_then(|i| prd.success_metrics.get(i)) else { continue };
+        if !uncovered.contains(&entry.metric) {
+            uncovered.push(entry.metric);
+            uncovered_metrics.push((metric.clone(), entry.reason));
+        }
+    }
+
+    ReconcilePlan { out_of_scope, uncovered_metrics, new_stories: response.new_stories }
+}
+
+fn print_plan(project: &Project, plan: &ReconcilePlan) {
+    println!("🔄 Backlog vs. PRD");
+    println!();
+    if plan.out_of_scope.is_empty() {
+        println!("✅ Every open item still fits the PRD");
+    } else {
+        println!("🚫 Out of scope ({}):", plan.out_of_scope.len());
+        for (id, reason) in &plan.out_of_scope {
+            let title = project.backlog.iter().find(|item| &item.id == id).map(|item| item.title.as_str()).unwrap_or_default();
+            println!("   {} - {}", id, title);
+            if !reason.is_empty() {
+                println!("      {}", reason);
+            }
+        }
+    }
+
+    println!();
+    if plan.uncovered_metrics.is_empty() {
+        println!("✅ Every success metric has stories");
+    } else {
+        println!("⚪ Metrics without coverage ({}):", plan.uncovered_metrics.len());
+        for (metric, reason) in &plan.uncovered_metrics {
+            println!("   {}", metric);
+            if !reason.is_empty() {
+                println!("      {}", reason);
+            }
+        }
+    }
+
+    if !plan.new_stories.is_empty() {
+        println!();
+        println!("💡 Proposed stories ({}):", plan.new_stories.len());
+        for (i, story) in plan.new_stories.iter().enumerate() {
+            let serves = story.metric.map(|n| format!(" (metric {})", n)).unwrap_or_default();
+            println!("   {}. {} [{} pts]{}", i + 1, story.title, story.effort.clamp(1, 13), serves);
+            println!("      {}", story.story);
+        }
+    }
+}
+
+/// Re-check the backlog against an edited PRD: report out-of-scope items, uncovered
+/// metrics and new stories, and apply each change only after confirmation.
+pub async fn run(ctx: &AppContext) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let prd = project.meta.prd.clone()
+        .filter(|prd| !prd.problem.trim().is_empty() || !prd.success_metrics.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("The project has no PRD. Record one with 'env-coach init --problem \"...\" --metric \"...\"'."))?;
+
+    let prompt_template_path = std::path::Path::new(".env-coach/prompts/backlog_reconcile.md");
+    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
+        Ok(template) => template,
+        Err(_) => {
+            println!("⚠️ Backlog reconcile prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_backlog_reconcile_prompt_content()
+        }
+    };
+    let prompt_template = prompts::fill_project_context(&prompt_template, &project, None);
+    let prompt = build_reconcile_prompt(&prompt_template, &project, &prd);
+
+    println!("🤖 Asking LLM to re-check the backlog against the PRD...");
+    let response = ollama::send_generation_prompt(project.llm(), &prompt)
+        .await
+        .context("LLM call for the backlog reconciliation failed")?;
+    let plan = check_response(&project, &prd, parse_reconcile_response(&response)?);
+    print_plan(&project, &plan);
+
+    let mut archived = vec![];
+    let mut added = vec![];
+    if !plan.out_of_scope.is_empty() || !plan.new_stories.is_empty() {
+        println!();
+    }
+    for (id, _) in &plan.out_of_scope {
+        if ctx.prompter.confirm(&format!("👉 Archive {} as out of scope?", id))? {
+            let (_, notes) = archive::archive_item(&mut project, id)?;
+            println!("📦 Archived {}", id);
+            archive::print_notes(&notes);
+            archived.push(id.clone());
+        }
+    }
+    for story in &plan.new_stories {
+        if ctx.prompter.confirm(&format!("👉 Add story '{}'?", story.title))? {
+            let item = prd::recommended_item(&project, &prd.success_metrics, story);
+            println!("✅ Added {}", item.id);
+            added.push(item.id.clone());
+            project.backlog.push(item);
+        }
+    }
+
+    // Declined changes count as reviewed: the backlog now reflects this version of the PRD
+    if let Some(prd) = project.meta.prd.as_mut() {
+        prd.reconciled = Some(prd.fingerprint());
+    }
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    println!();
+    println!("📊 Archived {}, added {} item(s)", archived.len(), added.len());
+    match added.first() {
+        Some(first) => hints::print_next_steps(Some(&project), &[
+            (format!("env-coach edit-item {} --editor", first).as_str(), "Refine the new story"),
+            ("env-coach prd check", "See how the work maps onto the metrics"),
+        ]),
+        None => hints::print_next_steps(Some(&project), &[
+            ("env-coach prd check", "See how the work maps onto the metrics"),
+        ]),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BacklogItem;
+
+    fn project() -> Project {
+        let mut project = Project::new("Shop".to_string(), "".to_string(), None);
+        project.meta.prd = Some(Prd {
+            problem: "Domestic sellers struggle to list products".to_string(),
+            success_metrics: vec!["First listing under 5 min".to_string(), "Time to first sale under a week".to_string()],
+            reconciled: None,
+        });
+        for (id, status) in [("US-001", Status::Done), ("US-002", Status::Todo), ("US-003", Status::Todo)] {
+            project.backlog.push(BacklogItem::test(id).with_title(&format!("Story {}", id)).with_story("As a seller...").with_status(status));
+        }
+        project
+    }
+
+    #[test]
+    fn test_build_reconcile_prompt() {
+        let project = project();
+        let prd = project.meta.prd.clone().unwrap();
+        let prompt = build_reconcile_prompt(&Templates::default_backlog_reconcile_prompt_content(), &project, &prd);
+        assert!(prompt.contains("1. First listing under 5 min\n2. Time to first sale under a week"));
+        assert!(prompt.contains("- US-001 [Done, 3 pts] Story US-001"));
+        assert!(prompt.contains("- US-002 [Todo, 3 pts] Story US-002"));
+        assert!(!prompt.replace(crate::project_summary::PLACEHOLDER, "").contains("{{"));
+    }
+
+    #[test]
+    fn test_check_response_drops_unknown_and_done_items() {
+        let project = project();
+        let prd = project.meta.prd.clone().unwrap();
+        let response = parse_reconcile_response(r#"{
+            "out_of_scope": [
+                {"story_id": "US-001", "reason": "Done already"},
+                {"story_id": "US-003", "reason": "Multi-currency is dropped"},
+                {"story_id": "US-003", "reason": "Again"},
+                {"story_id": "US-404", "reason": "Invented"}
+            ],
+            "uncovered_metrics": [{"metric": 2, "reason": "Nothing measures sales"}, {"metric": 9}],
+            "new_stories": [{"title": "Sales funnel", "story": "As a seller...", "metric": 2}]
+        }"#).unwrap();
+
+        let plan = check_response(&project, &prd, response);
+        assert_eq!(plan.out_of_scope, vec![("US-003".to_string(), "Multi-currency is dropped".to_string())]);
+        assert_eq!(plan.uncovered_metrics, vec![("Time to first sale under a week".to_string(), "Nothing measures sales".to_string())]);
+        assert_eq!(plan.new_stories[0].effort, 3);
+        assert!(parse_reconcile_response("not json").is_err());
+    }
+
+    #[test]
+    fn test_prd_fingerprint_tracks_edits() {
+        let mut prd = project().meta.prd.unwrap();
+        assert!(!prd.changed_since_reconcile());
+        prd.reconciled = Some(prd.fingerprint());
+        assert!(!prd.changed_since_reconcile());
+        prd.success_metrics.push("Churn under 5%".to_string());
+        assert!(prd.changed_since_reconcile());
+    }
+}
@@ -1,19 +1,31 @@
 // src/scripts/requirements.rs
 use anyhow::{Context, Result};
-use reqwest;
 use serde_json::Value;
-use crate::config::{FinalLlmConfig, Project}; // Changed LlmConfig to FinalLlmConfig
+use std::path::Path;
+use crate::code_context;
+use crate::config::{FinalLlmConfig, LlmRole, Project}; // Changed LlmConfig to FinalLlmConfig
 use crate::auto_update::{AutoUpdater, UpdateContext}; // NEW: Import auto-update
+use crate::auto_update::duplicates;
+use crate::auto_update::llm_parsers::LlmUserStoryResponse;
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+use crate::project_summary;
+use crate::prompts;
+use crate::tokens;
 
-pub async fn process_requirement(requirement: String) -> Result<()> {
+pub async fn process_requirement(ctx: &AppContext, requirement: String, epic: Option<String>, prompt_file: Option<&Path>) -> Result<()> {
     println!("🔍 Processing requirement: {}", requirement);
     
     // Load project configuration
-    let project = Project::load()
+    let project = ctx.load_project()
         .context("Failed to load project. Run 'env-coach init <n>' first")?;
+    if let Some(epic) = &epic {
+        crate::scripts::epics::require_epic(&project, epic)?;
+    }
     
     // Send requirement to LLM for analysis
-    let llm_response = send_llm_request(&requirement, project.llm(), &project)
+    let llm_response = send_llm_request(&requirement, &project.llm_for(LlmRole::Planner), &project, prompt_file)
         .await
         .context("Failed to get LLM analysis")?;
     
@@ -21,30 +33,39 @@ pub async fn process_requirement(requirement: String) -> Result<()> {
     println!("{}", llm_response);
     
     // NEW: Auto-update project.json instead of manual edit message
-    let mut updater = AutoUpdater::new(project);
+    let new_stories: Vec<(String, String)> = serde_json::from_str::<LlmUserStoryResponse>(&llm_response)
+        .map(|parsed| parsed.user_stories.into_iter().map(|story| (story.title, story.story)).collect())
+        .unwrap_or_default();
+    let embeddings = duplicates::embed_for_check(&project, &new_stories).await;
+    let mut updater = AutoUpdater::with_context(project, ctx).with_epic(epic).with_embeddings(embeddings);
     updater.process_llm_response(&llm_response, UpdateContext::RequirementAnalysis)
         .context("Failed to auto-update project files")?;
     
     println!("✅ Requirement processed and project.json auto-updated!");
-    println!("🎯 Next steps:");
-    println!("   env-coach list-backlog              # View updated backlog");
-    println!("   env-coach plan-sprint --goal \"...\"  # Plan development sprint");
+    hints::print_next_steps(Some(updater.get_project()), &[
+        ("env-coach list-backlog", "View updated backlog"),
+        ("env-coach plan-sprint --goal \"...\"", "Plan development sprint"),
+    ]);
     
     Ok(())
 }
 
-async fn send_llm_request(requirement: &str, llm_config: &FinalLlmConfig, project: &Project) -> Result<String> { // Changed LlmConfig to FinalLlmConfig
-    let client = reqwest::Client::new();
+async fn send_llm_request(requirement: &str, llm_config: &FinalLlmConfig, project: &Project, prompt_file: Option<&Path>) -> Result<String> { // Changed LlmConfig to FinalLlmConfig
+    let client = ollama::http_client(llm_config)?;
     
     // Get primary programming language for context
     let primary_language = get_primary_language(&project.meta.tech_stack);
 
     // Load prompt from file
-    let prompt_template_path = std::path::Path::new(".env-coach/prompts/requirements_analyst.md");
-    let mut prompt_template = std::fs::read_to_string(prompt_template_path)
-        .context(format!("Failed to read prompt template from {:?}", prompt_template_path))?;
+    let prompt_template_path = Path::new(".env-coach/prompts/requirements_analyst.md");
+    let mut prompt_template = match prompt_file {
+        Some(path) => prompts::read_override(path)?,
+        None => std::fs::read_to_string(prompt_template_path)
+            .context(format!("Failed to read prompt template from {:?}", prompt_template_path))?,
+    };
 
     // Perform replacements
+    prompt_template = prompts::fill_project_context(&prompt_template, project, None);
     prompt_template = prompt_template.replace("{{project_name}}", &project.meta.name);
     prompt_template = prompt_template.replace("{{project_description}}", &project.meta.description);
     prompt_template = prompt_template.replace("{{tech_stack}}", &project.meta.tech_stack.join(", "));
@@ -52,7 +73,10 @@ async fn send_llm_request(requirement: &str, llm_config: &FinalLlmConfig, projec
     prompt_template = prompt_template.replace("{{tags}}", &project.get_tags_display());
     prompt_template = prompt_template.replace("{{requirement}}", requirement);
 
-    let final_prompt = prompt_template;
+    let final_prompt = tokens::fill_within_budget(llm_config, &prompt_template, &[
+        ("{{code_context}}", code_context::code_context()),
+    ]);
+    let final_prompt = project_summary::fill(llm_config, &final_prompt).await;
 
     let request_body = serde_json::json!({
         "model": llm_config.model,
@@ -65,7 +89,7 @@ async fn send_llm_request(requirement: &str, llm_config: &FinalLlmConfig, projec
     });
 
     let response = client
-        .post(&format!("{}/api/generate", llm_config.base_url()))
+        .post(format!("{}/api/generate", llm_config.base_url()))
         .json(&request_body)
         .timeout(std::time::Duration::from_millis(llm_config.timeout_ms))
         .send()
@@ -0,0 +1,64 @@
+// src/scripts/restore.rs
+//! `restore <file>`: put back the newest backup taken before a suggestion changed the file.
+//! Given a `backup` archive instead, restores the whole project (see `scripts::backup`).
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::auto_update::backup;
+use crate::auto_update::file_change::FileChange;
+use crate::context::AppContext;
+use crate::hints;
+
+pub fn run(ctx: &AppContext, file: &str, list: bool) -> Result<()> {
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    if let Some(archive) = crate::scripts::backup::find_archive(&root, file) {
+        if list {
+            anyhow::bail!("--list shows a file's backups; 'env-coach backup --list' lists the project backups");
+        }
+        return crate::scripts::backup::restore(ctx, &archive);
+    }
+    let backups = backup::backups_of(&root, Path::new(file))?;
+    let Some(latest) = backups.last() else {
+        println!("ℹ️ No backups of {} in {}", file, backup::BACKUP_DIR);
+        return Ok(());
+    };
+
+    if list {
+        println!("🗂️  Backups of {} (newest last, {} kept per file):", file, backup::KEEP_PER_FILE);
+        for path in &backups {
+            println!("   - {}", path.strip_prefix(&root).unwrap_or(path).display());
+        }
+        return Ok(());
+    }
+
+    let path = root.join(file);
+    let change = FileChange {
+        before: std::fs::read_to_string(&path).ok(),
+        after: std::fs::read_to_string(latest).with_context(|| format!("Failed to read backup {:?}", latest))?,
+        path,
+    };
+    println!("↩️  Newest backup: {}", latest.strip_prefix(&root).unwrap_or(latest).display());
+    if change.is_noop() {
+        println!("✅ {} already matches its newest backup", file);
+        return Ok(());
+    }
+    println!("{}", change.diff().trim_end());
+    if !ctx.prompter.confirm(&format!("👉 Restore {} from this backup?", file))? {
+        println!("❌ Restore cancelled");
+        return Ok(());
+    }
+    if ctx.dry_run {
+        println!("🔍 Dry run: {} not written", file);
+        return Ok(());
+    }
+
+    change.write()?;
+    // The backup has been used; the next restore goes one step further back
+    std::fs::remove_file(latest).with_context(|| format!("Failed to remove used backup {:?}", latest))?;
+    println!("✅ Restored {} ({} older backup(s) left)", file, backups.len() - 1);
+    hints::print_next_steps(None, &[
+        ("git diff", "Check the restored file"),
+    ]);
+    Ok(())
+}
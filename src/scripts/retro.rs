@@ -0,0 +1,232 @@
+// src/scripts/retro.rs
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::config::{Project, Retrospective, Sprint, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::templates::Templates;
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct LlmRetroResponse {
+    #[serde(default)]
+    went_well: Vec<String>,
+    #[serde(default)]
+    to_improve: Vec<String>,
+    #[serde(default)]
+    action_items: Vec<String>,
+    #[serde(default)]
+    summary: String,
+}
+
+pub(crate) fn parse_retro_response(response: &str) -> Result<LlmRetroResponse> {
+    serde_json::from_str(response).with_context(|| format!("Failed to parse LLM retrospective. Raw response: {}", response))
+}
+
+#[derive(Debug, PartialEq)]
+struct SprintStats {
+    planned_points: u32,
+    completed_points: u32,
+    completed_items: Vec<String>,
+    carried_over: Vec<String>,
+    /// (item ID, cycle time in days) for completed items
+    cycle_times: Vec<(String, f64)>,
+}
+
+impl SprintStats {
+    fn average_cycle_days(&self) -> Option<f64> {
+        if self.cycle_times.is_empty() {
+            return None;
+        }
+        Some(self.cycle_times.iter().map(|(_, days)| days).sum::<f64>() / self.cycle_times.len() as f64)
+    }
+}
+
+/// Completed vs planned, carry-overs and cycle times for a sprint. Items without a
+/// `started_at` are measured from the sprint start.
+fn gather_sprint_stats(project: &Project, sprint: &Sprint) -> SprintStats {
+    let mut stats = SprintStats {
+        planned_points: sprint.total_points,
+        completed_points: sprint.completed_points,
+        completed_items: vec![],
+        carried_over: vec![],
+        cycle_times: vec![],
+    };
+
+    for id in &sprint.stories {
+        let Some(item) = project.backlog.iter().find(|item| &item.id == id) else { continue };
+        let done_here = matches!(item.status, Status::Done) && item.sprint.as_ref() == Some(&sprint.id);
+        if !done_here {
+            stats.carried_over.push(item.id.clone());
+            continue;
+        }
+        stats.completed_items.push(item.id.clone());
+        if let Some(completed_at) = item.completed_at {
+            let started_at = item.started_at.unwrap_or(sprint.start_date);
+            let days = (completed_at - started_at).num_minutes().max(0) as f64 / (60.0 * 24.0);
+            stats.cycle_times.push((item.id.clone(), days));
+        }
+    }
+    stats
+}
+
+fn format_stats(stats: &SprintStats) -> String {
+    let completion = crate::stats::percent(stats.completed_points, stats.planned_points).unwrap_or(0);
+    let mut out = format!(
+        "- Planned: {} points\n- Completed: {} points ({}%)\n- Completed items: {}\n- Carried over: {}",
+        stats.planned_points,
+        stats.completed_points,
+        completion,
+        if stats.completed_items.is_empty() { "none".to_string() } else { stats.completed_items.join(", ") },
+        if stats.carried_over.is_empty() { "none".to_string() } else { stats.carried_over.join(", ") },
+    );
+    if let Some(average) = stats.average_cycle_days() {
+        out.push_str(&format!("\n- Average cycle time: {:.1} days", average));
+        for (id, days) in &stats.cycle_times {
+            out.push_str(&format!("\n  - {}: {:.1} days", id, days));
+        }
+    }
+    out
+}
+
+fn print_retrospective(retro: &Retrospective) {
+    if !retro.summary.is_empty() {
+        println!("📝 {}", retro.summary);
+    }
+    for (heading, entries) in [("✅ Went well", &retro.went_well), ("🔧 To improve", &retro.to_improve), ("🎯 Action items", &retro.action_items)] {
+        if entries.is_empty() {
+            continue;
+        }
+        println!();
+        println!("{}:", heading);
+        for entry in entries {
+            println!("   - {}", entry);
+        }
+    }
+}
+
+pub async fn run(ctx: &AppContext, sprint_id: String) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    let sprint = project.sprints.iter()
+        .find(|s| s.id == sprint_id)
+        .ok_or_else(|| anyhow::anyhow!("Sprint '{}' not found", sprint_id))?;
+
+    let stats = gather_sprint_stats(&project, sprint);
+    let stats_text = format_stats(&stats);
+
+    println!("🔁 Retrospective for {}: {}", sprint.id, sprint.goal);
+    println!("{}", stats_text);
+    if sprint.retrospective.is_some() {
+        println!("ℹ️  This sprint already has a retrospective; it will be replaced.");
+    }
+    println!();
+
+    let highlights = ctx.prompter.ask("🌟 Highlights (what went well?): ")?;
+    let blockers = ctx.prompter.ask("🚧 Blockers (what slowed you down?): ")?;
+
+    let prompt_template_path = std::path::Path::new(".env-coach/prompts/retrospective.md");
+    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
+        Ok(template) => template,
+        Err(_) => {
+            println!("⚠️ Retrospective prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_retrospective_prompt_content()
+        }
+    };
+    let none_given = |text: &str| if text.is_empty() { "None given.".to_string() } else { text.to_string() };
+    let prompt = prompts::fill_project_context(&prompt_template, &project, None)
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{sprint_id}}", &sprint.id)
+        .replace("{{sprint_goal}}", &sprint.goal)
+        .replace("{{sprint_stats}}", &stats_text)
+        .replace("{{highlights}}", &none_given(&highlights))
+        .replace("{{blockers}}", &none_given(&blockers));
+
+    println!("\n🤖 Asking LLM to synthesize the retrospective...");
+    let response = ollama::send_generation_prompt(project.llm(), &prompt)
+        .await
+        .context("LLM call for retrospective failed")?;
+    let parsed = parse_retro_response(&response)?;
+
+    let retro = Retrospective {
+        held: Utc::now(),
+        highlights,
+        blockers,
+        went_well: parsed.went_well,
+        to_improve: parsed.to_improve,
+        action_items: parsed.action_items,
+        summary: parsed.summary,
+    };
+    println!();
+    print_retrospective(&retro);
+
+    if let Some(sprint) = project.sprints.iter_mut().find(|s| s.id == sprint_id) {
+        sprint.retrospective = Some(retro);
+    }
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    println!();
+    println!("✅ Retrospective saved to sprint {}", sprint_id);
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach plan-sprint --goal \"...\"", "Carry the action items into the next sprint"),
+    ]);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BacklogItem, SprintStatus};
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_gather_sprint_stats() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut project = Project::new("Retro".to_string(), "".to_string(), None);
+        project.backlog = vec![
+            BacklogItem::test("US-001").with_created(start).with_sprint("S-001").with_status(Status::Done)
+                .with_started_at(Some(start + Duration::days(1))).with_completed_at(Some(start + Duration::days(3))),
+            BacklogItem::test("US-002").with_created(start).with_sprint("S-001").with_status(Status::Done)
+                .with_completed_at(Some(start + Duration::days(4))),
+            BacklogItem::test("US-003").with_created(start).with_sprint("S-002").with_status(Status::InProgress),
+        ];
+        let sprint = Sprint {
+            id: "S-001".to_string(),
+            goal: "Goal".to_string(),
+            start_date: start,
+            end_date: start + Duration::days(14),
+            status: SprintStatus::Completed,
+            total_points: 9,
+            completed_points: 6,
+            tasks: vec![],
+            stories: vec!["US-001".to_string(), "US-002".to_string(), "US-003".to_string()],
+            planned_velocity: 9,
+            actual_velocity: 6,
+            retrospective: None,
+        };
+
+        let stats = gather_sprint_stats(&project, &sprint);
+        assert_eq!(stats.completed_items, vec!["US-001", "US-002"]);
+        assert_eq!(stats.carried_over, vec!["US-003"]);
+        assert_eq!(stats.cycle_times, vec![("US-001".to_string(), 2.0), ("US-002".to_string(), 4.0)]);
+        assert_eq!(stats.average_cycle_days(), Some(3.0));
+
+        let text = format_stats(&stats);
+        assert!(text.contains("Completed: 6 points (66%)"));
+        assert!(text.contains("Carried over: US-003"));
+        assert!(text.contains("Average cycle time: 3.0 days"));
+    }
+
+    #[test]
+    fn test_parse_llm_retro_response_defaults_missing_fields() {
+        let parsed: LlmRetroResponse = serde_json::from_str(r#"{"went_well": ["Shipped"], "summary": "Good sprint."}"#).unwrap();
+        assert_eq!(parsed.went_well, vec!["Shipped"]);
+        assert!(parsed.action_items.is_empty());
+        assert_eq!(parsed.summary, "Good sprint.");
+    }
+}
@@ -0,0 +1,198 @@
+// src/scripts/review_task.rs
+//! `review-task <id> [files...]`: an LLM code review of the files written for an item,
+//! against its story and acceptance criteria, stored on the item.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::auto_update::code_gen::get_primary_language;
+use crate::config::{BacklogItem, CodeReview, LlmRole, Project, ReviewIssue};
+use crate::context::AppContext;
+use crate::git;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::scripts::fix_tests;
+use crate::templates::Templates;
+
+#[derive(Deserialize)]
+struct LlmReview {
+    score: u8,
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    strengths: Vec<String>,
+    #[serde(default)]
+    issues: Vec<ReviewIssue>,
+    #[serde(default)]
+    suggested_fixes: Vec<String>,
+}
+
+pub(crate) fn parse_review_response(response: &str, files: &[PathBuf]) -> Result<CodeReview> {
+    let review: LlmReview = serde_json::from_str(response.trim())
+        .with_context(|| format!("The LLM did not return a structured review: {}", response.chars().take(300).collect::<String>()))?;
+    Ok(CodeReview {
+        reviewed_at: Utc::now(),
+        files: files.iter().map(|path| path.display().to_string()).collect(),
+        score: review.score.clamp(1, 10),
+        summary: review.summary,
+        strengths: review.strengths,
+        issues: review.issues,
+        suggested_fixes: review.suggested_fixes,
+    })
+}
+
+/// Files to review: the given ones, otherwise those changed by commits mentioning the
+/// item, otherwise uncommitted changes. env-coach's own files are left out.
+fn files_to_review(root: &Path, item_id: &str, files: Vec<String>) -> Vec<PathBuf> {
+    let candidates = match files.is_empty() {
+        false => files,
+        true => match git::files_changed_for(item_id).unwrap_or_default() {
+            committed if !committed.is_empty() => committed,
+            _ => git::changed_files().unwrap_or_default(),
+        },
+    };
+    let mut paths: Vec<PathBuf> = vec![];
+    for path in candidates.into_iter().map(PathBuf::from) {
+        let own = path.starts_with(".env-coach") || path == Path::new("project.json") || path == Path::new("project.toml")
+            || path.extension().is_some_and(|ext| ext == "lock");
+        if !own && root.join(&path).is_file() && !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+fn build_code_reviewer_prompt(template: &str, project: &Project, item: &BacklogItem, files: &str) -> String {
+    let criteria = match item.acceptance_criteria.is_empty() {
+        true => "None listed.".to_string(),
+        false => item.acceptance_criteria.iter().map(|criterion| format!("- {}", criterion)).collect::<Vec<_>>().join("\n"),
+    };
+    template
+        .replace("{{primary_language}}", &get_primary_language(&project.meta))
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{tech_stack}}", &project.meta.tech_stack.join(", "))
+        .replace("{{task_id}}", &item.id)
+        .replace("{{task_title}}", &item.title)
+        .replace("{{task_story}}", &item.story)
+        .replace("{{extra}}", &item.extra_display())
+        .replace("{{acceptance_criteria}}", &criteria)
+        .replace("{{files}}", files)
+}
+
+fn severity_emoji(severity: &str) -> &'static str {
+    match severity.to_lowercase().as_str() {
+        "high" => "🔴",
+        "medium" => "🟡",
+        "low" => "🟢",
+        _ => "⚪",
+    }
+}
+
+fn print_review(item_id: &str, review: &CodeReview) {
+    println!("🧐 Review of {}: {}/10", item_id, review.score);
+    if !review.summary.is_empty() {
+        println!("   {}", review.summary);
+    }
+    if !review.strengths.is_empty() {
+        println!();
+        println!("👍 Strengths:");
+        for strength in &review.strengths {
+            println!("   - {}", strength);
+        }
+    }
+    if !review.issues.is_empty() {
+        println!();
+        println!("⚠️ Issues:");
+        for issue in &review.issues {
+            let location = issue.file.as_deref().map(|file| format!("{}: ", file)).unwrap_or_default();
+            println!("   {} [{}] {}{}", severity_emoji(&issue.severity), issue.severity, location, issue.description);
+        }
+    }
+    if !review.suggested_fixes.is_empty() {
+        println!();
+        println!("🔧 Suggested fixes:");
+        for (i, fix) in review.suggested_fixes.iter().enumerate() {
+            println!("   {}. {}", i + 1, fix);
+        }
+    }
+}
+
+/// Review the code written for an item and keep the result on the item, replacing the
+/// previous review.
+pub async fn run(ctx: &AppContext, task_id: String, files: Vec<String>) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let item = project.backlog.iter()
+        .find(|item| item.id == task_id)
+        .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in backlog.", task_id))?;
+
+    let mut files = files_to_review(&root, &task_id, files);
+    if files.is_empty() {
+        anyhow::bail!("No changed files found for {}. Name the files to review: env-coach review-task {} src/...", task_id, task_id);
+    }
+    if files.len() > fix_tests::MAX_SOURCE_FILES {
+        println!("⚠️ Reviewing the first {} of {} files; name the files to review the others", fix_tests::MAX_SOURCE_FILES, files.len());
+        files.truncate(fix_tests::MAX_SOURCE_FILES);
+    }
+    println!("📂 Reviewing {} file(s): {}", files.len(),
+        files.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "));
+
+    let prompt_template_path = Path::new(".env-coach/prompts/code_reviewer.md");
+    let prompt_template = match prompts::read_template(prompt_template_path, &get_primary_language(&project.meta)) {
+        Some(template) => template,
+        None => {
+            println!("⚠️ Code-reviewer prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_code_reviewer_prompt_content()
+        }
+    };
+    let prompt_template = prompts::fill_project_context(&prompt_template, &project, Some(item));
+    let prompt = build_code_reviewer_prompt(&prompt_template, &project, item, &fix_tests::format_source_files(&root, &files));
+
+    println!("🔍 Asking LLM to review {}: {}", item.id, item.title);
+    let response = ollama::send_generation_prompt(&project.llm_for(LlmRole::Reviewer), &prompt)
+        .await
+        .context("Failed to get a review from LLM")?;
+    let review = parse_review_response(&response, &files)?;
+
+    println!();
+    print_review(&task_id, &review);
+    let has_issues = !review.issues.is_empty();
+    if let Some(item) = project.backlog.iter_mut().find(|item| item.id == task_id) {
+        item.review = Some(review);
+    }
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    let fix_command = format!("env-coach execute-task {} --prompt \"Address the review issues\"", task_id);
+    let complete_command = format!("env-coach complete-task {}", task_id);
+    let next_steps: &[(&str, &str)] = match has_issues {
+        true => &[(fix_command.as_str(), "Let the LLM address the issues")],
+        false => &[(complete_command.as_str(), "Mark the task as done")],
+    };
+    hints::print_next_steps(Some(&project), next_steps);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_review_response() {
+        let response = r#"{
+            "score": 12,
+            "summary": "Mostly fine.",
+            "strengths": ["Clear names"],
+            "issues": [{"severity": "high", "file": "src/lib.rs", "description": "Panics on empty input"}],
+            "suggested_fixes": ["Return an error"]
+        }"#;
+        let review = parse_review_response(response, &[PathBuf::from("src/lib.rs")]).unwrap();
+        assert_eq!(review.score, 10);
+        assert_eq!(review.files, vec!["src/lib.rs"]);
+        assert_eq!(review.issues[0].file.as_deref(), Some("src/lib.rs"));
+        assert!(parse_review_response("Looks good to me!", &[]).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+// src/scripts/risks.rs
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::config::{Project, Risk, RiskRegister};
+use crate::context::AppContext;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::templates::Templates;
+
+#[derive(Deserialize, Debug)]
+struct LlmRiskResponse {
+    risks: Vec<Risk>,
+}
+
+pub(crate) fn format_prd_for_prompt(project: &Project) -> String {
+    match &project.meta.prd {
+        Some(prd) if !prd.problem.is_empty() || !prd.success_metrics.is_empty() => {
+            let mut out = format!("- Problem: {}", prd.problem);
+            for metric in &prd.success_metrics {
+                out.push_str(&format!("\n- Success metric: {}", metric));
+            }
+            out
+        }
+        _ => "No PRD recorded.".to_string(),
+    }
+}
+
+fn format_backlog_for_prompt(project: &Project) -> String {
+    if project.backlog.is_empty() {
+        return "Backlog is empty.".to_string();
+    }
+    project.backlog.iter()
+        .map(|item| {
+            let deps = if item.dependencies.is_empty() {
+                String::new()
+            } else {
+                format!(" (depends on {})", item.dependencies.join(", "))
+            };
+            format!("- {} [{:?}, {:?}, {} pts] {}{}", item.id, item.status, item.priority, item.effort, item.title, deps)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn format_sprint_history_for_prompt(project: &Project) -> String {
+    if project.sprints.is_empty() {
+        return "No sprints yet.".to_string();
+    }
+    project.sprints.iter()
+        .map(|sprint| format!(
+            "- {} [{:?}] {}: {} / {} points completed",
+            sprint.id, sprint.status, sprint.goal, sprint.completed_points, sprint.total_points
+        ))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn build_risk_prompt(template: &str, project: &Project) -> String {
+    template
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{tech_stack}}", &project.meta.tech_stack.join(", "))
+        .replace("{{prd}}", &format_prd_for_prompt(project))
+        .replace("{{backlog}}", &format_backlog_for_prompt(project))
+        .replace("{{sprint_history}}", &format_sprint_history_for_prompt(project))
+}
+
+pub(crate) fn parse_risk_response(response: &str) -> Result<Vec<Risk>> {
+    let parsed: LlmRiskResponse = serde_json::from_str(response)
+        .with_context(|| {
+            let snippet = response.chars().take(500).collect::<String>();
+            format!("Failed to parse LLM risk register. Response (first 500 chars): '{}'", snippet)
+        })?;
+    Ok(parsed.risks)
+}
+
+/// Risks ordered from highest to lowest score.
+pub fn sorted_risks(register: &RiskRegister) -> Vec<&Risk> {
+    let mut risks: Vec<&Risk> = register.risks.iter().collect();
+    risks.sort_by_key(|risk| std::cmp::Reverse(risk.score()));
+    risks
+}
+
+fn print_register(register: &RiskRegister) {
+    println!("⚠️  Risk Register (generated {})", register.generated.format("%Y-%m-%d %H:%M UTC"));
+    if register.risks.is_empty() {
+        println!("   No risks identified.");
+        return;
+    }
+    for (i, risk) in sorted_risks(register).into_iter().enumerate() {
+        let marker = if risk.is_high() { "🔴" } else { "🟡" };
+        println!();
+        println!("{} {}. [{:?}] {}", marker, i + 1, risk.category, risk.description);
+        println!("   Likelihood: {:?} | Impact: {:?} | Score: {}/9", risk.likelihood, risk.impact, risk.score());
+        println!("   Mitigation: {}", risk.mitigation);
+    }
+}
+
+pub async fn run(ctx: &AppContext, refresh: bool) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    if let (Some(register), false) = (&project.meta.risks, refresh) {
+        print_register(register);
+        hints::print_next_steps(Some(&project), &[
+            ("env-coach risks --refresh", "Re-assess risks from the current backlog"),
+        ]);
+        return Ok(());
+    }
+
+    let prompt_template_path = std::path::Path::new(".env-coach/prompts/risk_analyst.md");
+    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
+        Ok(template) => template,
+        Err(_) => {
+            println!("⚠️ Risk analyst prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_risk_analyst_prompt_content()
+        }
+    };
+    let prompt_template = prompts::fill_project_context(&prompt_template, &project, None);
+    let prompt = build_risk_prompt(&prompt_template, &project);
+
+    println!("🤖 Asking LLM to assess project risks...");
+    let response = ollama::send_generation_prompt(project.llm(), &prompt)
+        .await
+        .context("LLM call for risk assessment failed")?;
+
+    let risks = parse_risk_response(&response)?;
+    let register = RiskRegister { generated: Utc::now(), risks };
+    print_register(&register);
+
+    project.meta.risks = Some(register);
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    println!();
+    println!("✅ Risk register saved to project.json");
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach status", "High risks are shown in the project status"),
+    ]);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Prd, RiskCategory, RiskLevel};
+
+    fn risk(likelihood: RiskLevel, impact: RiskLevel) -> Risk {
+        Risk {
+            category: RiskCategory::Technical,
+            description: "Risk".to_string(),
+            likelihood,
+            impact,
+            mitigation: "Mitigate".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_risk_response_valid() {
+        let json = r#"{"risks": [{"category": "Schedule", "description": "US-003 is large", "likelihood": "High", "impact": "Medium", "mitigation": "Split it"}]}"#;
+        let risks = parse_risk_response(json).unwrap();
+        assert_eq!(risks.len(), 1);
+        assert_eq!(risks[0].category, RiskCategory::Schedule);
+        assert!(risks[0].is_high());
+    }
+
+    #[test]
+    fn test_parse_risk_response_rejects_unknown_level() {
+        let json = r#"{"risks": [{"category": "Schedule", "description": "d", "likelihood": "Extreme", "impact": "Low", "mitigation": "m"}]}"#;
+        assert!(parse_risk_response(json).is_err());
+    }
+
+    #[test]
+    fn test_sorted_risks_orders_by_score() {
+        let register = RiskRegister {
+            generated: Utc::now(),
+            risks: vec![risk(RiskLevel::Low, RiskLevel::Low), risk(RiskLevel::High, RiskLevel::High), risk(RiskLevel::Medium, RiskLevel::High)],
+        };
+        let scores: Vec<u8> = sorted_risks(&register).iter().map(|r| r.score()).collect();
+        assert_eq!(scores, vec![9, 6, 1]);
+    }
+
+    #[test]
+    fn test_build_risk_prompt_includes_prd() {
+        let mut project = Project::new("RiskProject".to_string(), "desc".to_string(), None);
+        project.meta.prd = Some(Prd { problem: "Slow onboarding".to_string(), success_metrics: vec!["< 5 min setup".to_string()], reconciled: None });
+        let prompt = build_risk_prompt(&Templates::default_risk_analyst_prompt_content(), &project);
+        assert!(prompt.contains("Problem: Slow onboarding"));
+        assert!(prompt.contains("Success metric: < 5 min setup"));
+        assert!(prompt.contains("Backlog is empty."));
+        assert!(!prompt.replace(crate::project_summary::PLACEHOLDER, "").contains("{{"));
+    }
+}
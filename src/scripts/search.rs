@@ -0,0 +1,158 @@
+// src/scripts/search.rs
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::config::BacklogItem;
+use crate::context::AppContext;
+use crate::hints;
+use crate::scripts::backlog::item_badges;
+use crate::scripts::filter::BacklogFilter;
+
+/// Characters of context kept on each side of a match.
+const SNIPPET_CONTEXT: usize = 30;
+
+/// Where a query matched in an item, with a snippet around the first hit.
+#[derive(Debug, PartialEq, Serialize)]
+struct FieldMatch {
+    field: String,
+    snippet: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SearchHit<'a> {
+    item: &'a BacklogItem,
+    fields: Vec<FieldMatch>,
+    #[serde(skip)]
+    title_match: bool,
+}
+
+/// Byte offset of the first ASCII case-insensitive occurrence of `term`.
+fn find_term(text: &str, term: &str) -> Option<usize> {
+    text.to_ascii_lowercase().find(&term.to_ascii_lowercase())
+}
+
+/// `text` around the match at `start..start + len`, with the match marked as `[...]`.
+fn snippet(text: &str, start: usize, len: usize) -> String {
+    let end = start + len;
+    let from = text[..start].char_indices().rev().nth(SNIPPET_CONTEXT - 1).map(|(i, _)| i).unwrap_or(0);
+    let to = text[end..].char_indices().nth(SNIPPET_CONTEXT).map(|(i, _)| end + i).unwrap_or(text.len());
+    let marked = format!("{}[{}]{}", &text[from..start], &text[start..end], &text[end..to]);
+    format!(
+        "{}{}{}",
+        if from > 0 { "…" } else { "" },
+        marked.split_whitespace().collect::<Vec<_>>().join(" "),
+        if to < text.len() { "…" } else { "" },
+    )
+}
+
+/// An item matches when every term appears in its title, story or acceptance criteria.
+fn match_item<'a>(item: &'a BacklogItem, terms: &[&str]) -> Option<SearchHit<'a>> {
+    let mut fields: Vec<(String, &str)> = vec![("title".to_string(), item.title.as_str()), ("story".to_string(), item.story.as_str())];
+    for (i, criterion) in item.acceptance_criteria.iter().enumerate() {
+        fields.push((format!("AC {}", i + 1), criterion.as_str()));
+    }
+
+    let everything = fields.iter().map(|(_, text)| *text).collect::<Vec<_>>().join("\n");
+    if !terms.iter().all(|term| find_term(&everything, term).is_some()) {
+        return None;
+    }
+
+    let matches: Vec<FieldMatch> = fields.iter()
+        .filter_map(|(field, text)| {
+            terms.iter()
+                .filter_map(|term| find_term(text, term).map(|start| (start, term.len())))
+                .min()
+                .map(|(start, len)| FieldMatch { field: field.clone(), snippet: snippet(text, start, len) })
+        })
+        .collect();
+    let title_match = matches.iter().any(|m| m.field == "title");
+    Some(SearchHit { item, fields: matches, title_match })
+}
+
+/// Matching items, in the order given.
+fn search<'a>(items: Vec<&'a BacklogItem>, query: &str) -> Vec<SearchHit<'a>> {
+    let terms: Vec<&str> = query.split_whitespace().collect();
+    if terms.is_empty() {
+        return vec![];
+    }
+    items.into_iter().filter_map(|item| match_item(item, &terms)).collect()
+}
+
+/// Title hits first, then by how many fields matched.
+fn rank_by_relevance(hits: &mut [SearchHit]) {
+    hits.sort_by_key(|hit| (!hit.title_match, std::cmp::Reverse(hit.fields.len())));
+}
+
+/// Full-text search over titles, stories and acceptance criteria. The list-backlog
+/// filters narrow the items searched; `--sort` replaces the relevance order.
+pub fn run(ctx: &AppContext, query: &str, filter: &BacklogFilter) -> Result<()> {
+    if query.trim().is_empty() {
+        anyhow::bail!("Search query is empty");
+    }
+    // An indexed storage returns just the items containing every term
+    let terms: Vec<&str> = query.split_whitespace().collect();
+    let (project, partial) = match ctx.load_matching(filter, &terms)? {
+        Some((project, _)) => (project, true),
+        None => (ctx.load_project()?, false),
+    };
+    // Hints look at the whole backlog
+    let hint_project = (!partial).then_some(&project);
+
+    let mut hits = search(filter.apply(&project.backlog), query);
+    if filter.sort.is_none() {
+        rank_by_relevance(&mut hits);
+    }
+    if ctx.format.is_structured() {
+        return ctx.print_structured(&hits);
+    }
+
+    if hits.is_empty() {
+        println!("🔍 No items match \"{}\"", query);
+        hints::print_next_steps(hint_project, &[("env-coach list-backlog", "Browse the whole backlog")]);
+        return Ok(());
+    }
+
+    println!("🔍 {} item(s) match \"{}\":", hits.len(), query);
+    println!();
+    for hit in &hits {
+        let (priority_emoji, type_emoji) = item_badges(hit.item);
+        println!("  {} {} {} - {} [{}pts, {:?}]", priority_emoji, type_emoji, hit.item.id, hit.item.title, hit.item.effort, hit.item.status);
+        for field in hit.fields.iter().filter(|field| field.field != "title") {
+            println!("     {}: {}", field.field, field.snippet);
+        }
+    }
+
+    hints::print_next_steps(hint_project, &[
+        (format!("env-coach edit-item {} --editor", hits[0].item.id).as_str(), "Open the best match"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_needs_every_term_and_ranks_title_hits_first() {
+        let items = [
+            BacklogItem::test("US-001").with_title("Profile page").with_story("As a user I want to see my auth details")
+                .with_criteria(&["Shows the current token expiry"]),
+            BacklogItem::test("US-002").with_title("Refresh auth token").with_story("As a user I stay logged in"),
+            BacklogItem::test("US-003").with_title("Auth settings").with_story("As an admin I configure providers"),
+        ];
+        let mut hits = search(items.iter().collect(), "AUTH token");
+        rank_by_relevance(&mut hits);
+        let ids: Vec<&str> = hits.iter().map(|hit| hit.item.id.as_str()).collect();
+        assert_eq!(ids, vec!["US-002", "US-001"]);
+        assert_eq!(hits[1].fields.iter().map(|f| f.field.as_str()).collect::<Vec<_>>(), vec!["story", "AC 1"]);
+        assert!(search(items.iter().collect(), "   ").is_empty());
+    }
+
+    #[test]
+    fn test_snippet_marks_the_match_with_context() {
+        let text = "As a user I want the session token to be refreshed before it expires so I am not logged out";
+        let start = find_term(text, "TOKEN").unwrap();
+        assert_eq!(snippet(text, start, 5), "As a user I want the session [token] to be refreshed before it exp…");
+        assert_eq!(snippet("token", 0, 5), "[token]");
+    }
+}
@@ -0,0 +1,306 @@
+// src/scripts/session.rs
+//! LLM suggestions for a task saved to disk with what was decided about each one, so they
+//! can be reviewed offline and applied later (`apply-session`), or an interrupted
+//! `execute-task` run can be resumed. A task's saved sessions are also its conversation
+//! history: later `assist-task`/`execute-task` prompts get them condensed (`condensed_history`).
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::auto_update::llm_parsers::{parse_assist_task_response, LlmSingleSuggestion};
+use crate::auto_update::{verify, AutoUpdater, SuggestionOutcome};
+use crate::config::Project;
+use crate::context::AppContext;
+use crate::hints;
+
+pub const SESSIONS_DIR: &str = ".env-coach/sessions";
+/// Earlier sessions of a task shown in its prompts, the most recent ones.
+pub const HISTORY_SESSIONS: usize = 5;
+/// Characters shown of advice and free-form answers in the condensed history.
+const HISTORY_SNIPPET_CHARS: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Session {
+    pub task_id: String,
+    pub started: DateTime<Utc>,
+    /// What the user asked for, when more than general assistance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    /// Raw LLM response the suggestions were parsed from
+    pub llm_response: String,
+    /// Suggestion index -> outcome, for every suggestion already decided
+    pub outcomes: BTreeMap<usize, SuggestionOutcome>,
+    /// Applied suggestion index -> the checks that failed after it was applied
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub follow_up: BTreeMap<usize, String>,
+}
+
+impl Session {
+    pub fn new(task_id: &str, llm_response: String) -> Self {
+        Self { task_id: task_id.to_string(), started: Utc::now(), prompt: None, llm_response, outcomes: BTreeMap::new(), follow_up: BTreeMap::new() }
+    }
+
+    pub fn with_prompt(mut self, prompt: Option<String>) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
+    /// Where `assist-task` keeps the suggestions it received:
+    /// `.env-coach/sessions/<task-id>/<timestamp>.json`.
+    pub fn review_path(&self, root: &Path) -> PathBuf {
+        root.join(SESSIONS_DIR).join(&self.task_id).join(format!("{}.json", self.started.format("%Y%m%dT%H%M%SZ")))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read session from {:?}", path))?;
+        serde_json::from_str(&content).with_context(|| format!("Corrupt session file {:?}", path))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write session to {:?}", path))
+    }
+
+    pub fn count(&self, wanted: fn(&SuggestionOutcome) -> bool) -> usize {
+        self.outcomes.values().filter(|outcome| wanted(outcome)).count()
+    }
+}
+
+/// The task's saved sessions, oldest first. Unreadable files are skipped.
+pub(crate) fn task_sessions(root: &Path, task_id: &str) -> Vec<Session> {
+    let Ok(entries) = std::fs::read_dir(root.join(SESSIONS_DIR).join(task_id)) else { return vec![] };
+    let mut sessions: Vec<Session> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| Session::load(&path).ok())
+        .collect();
+    sessions.sort_by_key(|session| session.started);
+    sessions
+}
+
+fn snippet(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.chars().count() > HISTORY_SNIPPET_CHARS {
+        true => format!("{}...", text.chars().take(HISTORY_SNIPPET_CHARS).collect::<String>()),
+        false => text,
+    }
+}
+
+/// `{{task_history}}` for a task prompt: what the last `HISTORY_SESSIONS` sessions
+/// suggested, one line each, with what became of it.
+pub(crate) fn condensed_history(sessions: &[Session]) -> String {
+    if sessions.is_empty() {
+        return "None; this is the first request for this task.".to_string();
+    }
+    let mut lines = vec![];
+    for session in &sessions[sessions.len().saturating_sub(HISTORY_SESSIONS)..] {
+        let asked = session.prompt.as_deref().map(|prompt| format!("asked \"{}\"", snippet(prompt))).unwrap_or_else(|| "asked for general assistance".to_string());
+        lines.push(format!("- {} ({})", session.started.format("%Y-%m-%d %H:%M"), asked));
+        let Ok(parsed) = parse_assist_task_response(&session.llm_response) else {
+            lines.push(format!("  - Answer: {}", snippet(&session.llm_response)));
+            continue;
+        };
+        for (idx, suggestion) in parsed.suggestions.iter().enumerate() {
+            let what = match suggestion {
+                LlmSingleSuggestion::GeneralAdvice(advice) => format!("Advice: {}", snippet(&advice.content)),
+                _ => suggestion.change_summary().unwrap_or_default(),
+            };
+            let outcome = match session.outcomes.get(&idx) {
+                Some(SuggestionOutcome::Applied) if session.follow_up.contains_key(&idx) => "applied, checks failed".to_string(),
+                Some(SuggestionOutcome::Applied) => "applied".to_string(),
+                Some(SuggestionOutcome::Skipped) => "rejected".to_string(),
+                Some(SuggestionOutcome::Failed(reason)) => format!("failed: {}", snippet(reason)),
+                None => "not applied".to_string(),
+            };
+            lines.push(format!("  - {} ({})", what, outcome));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Run the post-apply checks when asked for (`--verify` or `verify.always` in project.json)
+/// and this run applied code. When a check fails, the code suggestions applied in this run
+/// are marked as needing follow-up in `session`; when all pass, those marks are cleared.
+/// Returns whether the checks passed (or were not run).
+pub(crate) fn verify_applied(
+    ctx: &AppContext,
+    project: &Project,
+    root: &Path,
+    verify: bool,
+    suggestions: &[LlmSingleSuggestion],
+    outcomes: &[(usize, SuggestionOutcome)],
+    session: &mut Session,
+) -> Result<bool> {
+    let config = project.meta.verify.as_ref();
+    if !verify && !config.is_some_and(|config| config.always) {
+        return Ok(true);
+    }
+    let applied: Vec<usize> = outcomes.iter()
+        .filter(|(idx, outcome)| {
+            *outcome == SuggestionOutcome::Applied
+                && !matches!(suggestions.get(*idx), Some(LlmSingleSuggestion::GeneralAdvice(_)) | None)
+        })
+        .map(|(idx, _)| *idx)
+        .collect();
+    if applied.is_empty() {
+        return Ok(true);
+    }
+    let commands = verify::commands_for(root, config);
+    println!();
+    if commands.is_empty() {
+        println!("ℹ️ No checks known for this project; set verify.commands in project.json");
+        return Ok(true);
+    }
+    if ctx.dry_run {
+        println!("🔍 Dry run: checks not run ({})", commands.join(", "));
+        return Ok(true);
+    }
+
+    let results = verify::run_checks(root, &commands)?;
+    let failed: Vec<&verify::CheckResult> = results.iter().filter(|result| !result.passed).collect();
+    for result in &results {
+        if result.passed {
+            println!("✅ {}", result.command);
+            continue;
+        }
+        println!("❌ {}", result.command);
+        for line in result.summary() {
+            println!("   {}", line);
+        }
+    }
+    if failed.is_empty() {
+        for idx in &applied {
+            session.follow_up.remove(idx);
+        }
+        return Ok(true);
+    }
+    let reason = format!("failed: {}", failed.iter().map(|result| result.command.as_str()).collect::<Vec<_>>().join(", "));
+    for idx in &applied {
+        session.follow_up.insert(*idx, reason.clone());
+    }
+    println!("⚠️ Suggestion(s) {} marked as needing follow-up",
+        applied.iter().map(|idx| format!("#{}", idx + 1)).collect::<Vec<_>>().join(", "));
+    Ok(false)
+}
+
+/// 1-based suggestion numbers such as `1,3` or `2-4` as 0-based indices.
+fn parse_only(only: &str, total: usize) -> Result<HashSet<usize>> {
+    let mut selected = HashSet::new();
+    for part in only.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let (from, to) = part.split_once('-').unwrap_or((part, part));
+        let parse = |n: &str| n.trim().parse::<usize>().ok().filter(|n| (1..=total).contains(n));
+        let (Some(from), Some(to)) = (parse(from), parse(to)) else {
+            anyhow::bail!("'{}' is not a suggestion number between 1 and {}", part, total);
+        };
+        selected.extend(from - 1..to);
+    }
+    Ok(selected)
+}
+
+/// Apply the suggestions saved in a session file, optionally only `only` (e.g. `1,3`).
+/// Suggestions applied before are not offered again; the file records every decision.
+pub fn apply(ctx: &AppContext, file: &Path, only: Option<&str>, verify: bool) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let mut session = Session::load(file)?;
+    let parsed = parse_assist_task_response(&session.llm_response)
+        .with_context(|| format!("{:?} does not contain structured suggestions", file))?;
+    let total = parsed.suggestions.len();
+
+    let selected = match only {
+        Some(only) => parse_only(only, total)?,
+        None => (0..total).collect(),
+    };
+    let already_decided: HashSet<usize> = (0..total)
+        .filter(|idx| !selected.contains(idx) || session.outcomes.get(idx) == Some(&SuggestionOutcome::Applied))
+        .collect();
+    println!("🗂️  Session for {} from {}: {} suggestion(s), {} to review",
+        session.task_id, session.started.format("%Y-%m-%d %H:%M UTC"), total, total - already_decided.len());
+    if let Some(summary) = &parsed.overall_summary {
+        println!("ℹ️ {}", summary);
+    }
+
+    let mut updater = AutoUpdater::with_context(project, ctx);
+    let outcomes = updater.apply_suggestions(&root, &parsed.suggestions, &already_decided, |idx, outcome| {
+        session.outcomes.insert(idx, outcome.clone());
+        if ctx.dry_run { Ok(()) } else { session.save(file) }
+    })?;
+    verify_applied(ctx, updater.get_project(), &root, verify, &parsed.suggestions, &outcomes, &mut session)?;
+    if !ctx.dry_run {
+        session.save(file)?;
+    }
+
+    println!();
+    println!("📊 {} applied, {} skipped, {} failed (of {} suggestion(s))",
+        session.count(|o| matches!(o, SuggestionOutcome::Applied)),
+        session.count(|o| matches!(o, SuggestionOutcome::Skipped)),
+        session.count(|o| matches!(o, SuggestionOutcome::Failed(_))),
+        total);
+    if !session.follow_up.is_empty() {
+        println!("⚠️ {} applied suggestion(s) need follow-up; see {}", session.follow_up.len(), file.display());
+    }
+    hints::print_next_steps(Some(updater.get_project()), &[
+        ("cargo build && cargo test", "Verify the changes"),
+        (format!("env-coach complete-task {}", session.task_id).as_str(), "Mark the task as done"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_only() {
+        assert_eq!(parse_only("1,3", 3).unwrap(), HashSet::from([0, 2]));
+        assert_eq!(parse_only("2-4, 1", 5).unwrap(), HashSet::from([0, 1, 2, 3]));
+        assert!(parse_only("4", 3).is_err());
+        assert!(parse_only("0", 3).is_err());
+        assert!(parse_only("x", 3).is_err());
+    }
+
+    #[test]
+    fn test_condensed_history() {
+        assert!(condensed_history(&[]).starts_with("None"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut first = Session::new("US-001", r#"{"suggestions": [
+            {"type": "cargo_dependency", "dependency_lines": ["serde = \"1.0\""]},
+            {"type": "source_code", "target_file": "src/auth.rs", "action": "create", "content": "pub fn login() {}"},
+            {"type": "general_advice", "content": "Hash passwords before storing them."}
+        ]}"#.to_string());
+        first.started = "2026-10-01T09:30:00Z".parse().unwrap();
+        first.outcomes.insert(0, SuggestionOutcome::Applied);
+        first.outcomes.insert(1, SuggestionOutcome::Skipped);
+        first.save(&first.review_path(dir.path())).unwrap();
+        let second = Session::new("US-001", "Use argon2.".to_string()).with_prompt(Some("Which hash?".to_string()));
+        second.save(&second.review_path(dir.path())).unwrap();
+
+        let sessions = task_sessions(dir.path(), "US-001");
+        assert_eq!(sessions.len(), 2);
+        assert!(task_sessions(dir.path(), "US-002").is_empty());
+        let history = condensed_history(&sessions);
+        let lines: Vec<&str> = history.lines().collect();
+        assert_eq!(lines[0], "- 2026-10-01 09:30 (asked for general assistance)");
+        assert_eq!(lines[1], "  - Cargo.toml: serde (applied)");
+        assert_eq!(lines[2], "  - Create src/auth.rs (rejected)");
+        assert_eq!(lines[3], "  - Advice: Hash passwords before storing them. (not applied)");
+        assert!(lines[4].ends_with("(asked \"Which hash?\")"));
+        assert_eq!(lines[5], "  - Answer: Use argon2.");
+    }
+
+    #[test]
+    fn test_review_path() {
+        let session = Session::new("US-001", "{}".to_string());
+        let path = session.review_path(Path::new("/project"));
+        assert!(path.starts_with("/project/.env-coach/sessions/US-001"));
+        assert_eq!(path.extension().and_then(|ext| ext.to_str()), Some("json"));
+    }
+}
@@ -0,0 +1,181 @@
+// src/scripts/set_field.rs
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::config::{BacklogItem, Priority, Project, SprintStatus, Status};
+use crate::context::AppContext;
+use crate::scripts::edit_item::validate_item;
+use crate::scripts::item_ids;
+
+/// Keep the item's sprint totals in line after an edit: effort changes move
+/// `total_points`, and effort counted as completed follows the Done status.
+/// Completed sprints are left alone so recorded velocity does not change.
+pub(crate) fn resync_sprint_points(project: &mut Project, before: &BacklogItem, after: &BacklogItem) {
+    let Some(sprint) = after.sprint.as_ref().and_then(|id| project.sprints.iter_mut().find(|s| &s.id == id)) else { return };
+    if matches!(sprint.status, SprintStatus::Completed | SprintStatus::Complete) {
+        return;
+    }
+    sprint.total_points = (sprint.total_points + after.effort).saturating_sub(before.effort);
+
+    let done_points = |item: &BacklogItem| if matches!(item.status, Status::Done) { item.effort } else { 0 };
+    sprint.completed_points = (sprint.completed_points + done_points(after)).saturating_sub(done_points(before));
+}
+
+/// Apply `change` to a copy of the item, validate it, update sprint points and store it.
+/// Returns the item before and after the change.
+pub(crate) fn update_item(project: &mut Project, item_id: &str, change: impl FnOnce(&mut BacklogItem)) -> Result<(BacklogItem, BacklogItem)> {
+    let before = project.backlog.iter()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| anyhow::anyhow!("Backlog item '{}' not found", item_id))?
+        .clone();
+    let mut after = before.clone();
+    change(&mut after);
+    validate_item(project, &after)?;
+
+    resync_sprint_points(project, &before, &after);
+    if let Some(slot) = project.backlog.iter_mut().find(|item| item.id == item_id) {
+        *slot = after.clone();
+    }
+    Ok((before, after))
+}
+
+fn set_status_in(project: &mut Project, item_id: &str, status: Status) -> Result<(BacklogItem, BacklogItem)> {
+    update_item(project, item_id, |item| {
+        match status {
+            Status::Done => {
+                item.completed_at.get_or_insert_with(Utc::now);
+            }
+            Status::InProgress | Status::Review => {
+                item.started_at.get_or_insert_with(Utc::now);
+                item.completed_at = None;
+            }
+            Status::Todo => item.completed_at = None,
+        }
+        item.status = status;
+    })
+}
+
+/// Print the points of every sprint the changed items belong to, once each.
+fn print_sprint_points(project: &Project, items: &[BacklogItem]) {
+    let mut shown: Vec<&str> = vec![];
+    for sprint_id in items.iter().filter_map(|item| item.sprint.as_deref()) {
+        if shown.contains(&sprint_id) {
+            continue;
+        }
+        shown.push(sprint_id);
+        if let Some(sprint) = project.sprints.iter().find(|s| s.id == sprint_id) {
+            println!("📊 Sprint {}: {} / {} points", sprint.id, sprint.completed_points, sprint.total_points);
+        }
+    }
+}
+
+/// Load the project, apply `change` to each of the items named by `item_args` (see
+/// `item_ids`) and save once. Nothing is saved if any item fails.
+fn update_items(
+    ctx: &AppContext,
+    item_args: &[String],
+    mut change: impl FnMut(&mut Project, &str) -> Result<(BacklogItem, BacklogItem)>,
+) -> Result<(Project, Vec<(BacklogItem, BacklogItem)>)> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let item_ids = item_ids::expand(item_args, &project)?;
+    let changes = item_ids.iter()
+        .map(|item_id| change(&mut project, item_id))
+        .collect::<Result<Vec<_>>>()
+        .context("No items were changed")?;
+    ctx.save_project(&project).context("Failed to save project")?;
+    Ok((project, changes))
+}
+
+pub fn set_status(ctx: &AppContext, item_ids: Vec<String>, status: Status) -> Result<()> {
+    let (project, changes) = update_items(ctx, &item_ids, |project, item_id| set_status_in(project, item_id, status.clone()))?;
+    for (before, after) in &changes {
+        println!("✅ {} status: {:?} → {:?}", after.id, before.status, after.status);
+    }
+    print_sprint_points(&project, &changes.into_iter().map(|(_, after)| after).collect::<Vec<_>>());
+    Ok(())
+}
+
+pub fn set_priority(ctx: &AppContext, item_ids: Vec<String>, priority: Priority) -> Result<()> {
+    let (_, changes) = update_items(ctx, &item_ids, |project, item_id| update_item(project, item_id, |item| item.priority = priority.clone()))?;
+    for (before, after) in &changes {
+        println!("✅ {} priority: {:?} → {:?}", after.id, before.priority, after.priority);
+    }
+    Ok(())
+}
+
+pub fn set_effort(ctx: &AppContext, item_ids: Vec<String>, effort: u32) -> Result<()> {
+    let (project, changes) = update_items(ctx, &item_ids, |project, item_id| update_item(project, item_id, |item| item.effort = effort))?;
+    for (before, after) in &changes {
+        println!("✅ {} effort: {} → {} points", after.id, before.effort, after.effort);
+    }
+    print_sprint_points(&project, &changes.into_iter().map(|(_, after)| after).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Sprint;
+
+    fn project() -> Project {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog.push(BacklogItem::test("US-001").with_title("Login").with_criteria(&["Works"]).with_sprint("S-001"));
+        project.sprints.push(Sprint {
+            id: "S-001".to_string(),
+            goal: "Goal".to_string(),
+            start_date: Utc::now(),
+            end_date: Utc::now(),
+            status: SprintStatus::Active,
+            total_points: 3,
+            completed_points: 0,
+            tasks: vec!["US-001".to_string()],
+            stories: vec!["US-001".to_string()],
+            planned_velocity: 3,
+            actual_velocity: 0,
+            retrospective: None,
+        });
+        project
+    }
+
+    #[test]
+    fn test_status_changes_move_completed_points() {
+        let mut project = project();
+        set_status_in(&mut project, "US-001", Status::Done).unwrap();
+        assert_eq!(project.sprints[0].completed_points, 3);
+        assert!(project.backlog[0].completed_at.is_some());
+
+        // Setting Done twice must not double count
+        set_status_in(&mut project, "US-001", Status::Done).unwrap();
+        assert_eq!(project.sprints[0].completed_points, 3);
+
+        set_status_in(&mut project, "US-001", Status::Review).unwrap();
+        assert_eq!(project.sprints[0].completed_points, 0);
+        assert!(project.backlog[0].completed_at.is_none());
+        assert!(project.backlog[0].started_at.is_some());
+    }
+
+    #[test]
+    fn test_effort_changes_recalculate_sprint_points() {
+        let mut project = project();
+        set_status_in(&mut project, "US-001", Status::Done).unwrap();
+        update_item(&mut project, "US-001", |item| item.effort = 5).unwrap();
+        assert_eq!(project.sprints[0].total_points, 5);
+        assert_eq!(project.sprints[0].completed_points, 5);
+
+        assert!(update_item(&mut project, "US-001", |item| item.effort = 0).is_err());
+        assert_eq!(project.backlog[0].effort, 5);
+
+        project.sprints[0].status = SprintStatus::Completed;
+        update_item(&mut project, "US-001", |item| item.effort = 8).unwrap();
+        assert_eq!(project.sprints[0].total_points, 5);
+    }
+
+    #[test]
+    fn test_status_and_priority_parse_from_cli_values() {
+        assert_eq!("in-progress".parse::<Status>(), Ok(Status::InProgress));
+        assert_eq!("Done".parse::<Status>(), Ok(Status::Done));
+        assert!("finished".parse::<Status>().is_err());
+        assert_eq!("HIGH".parse::<Priority>(), Ok(Priority::High));
+        assert!("urgent".parse::<Priority>().is_err());
+    }
+}
@@ -0,0 +1,295 @@
+// src/scripts/show_item.rs
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::config::{BacklogItem, ItemType, Project};
+use crate::context::AppContext;
+use crate::hints;
+use crate::git;
+use crate::links::Linker;
+use crate::scripts::backlog::item_badges;
+use crate::stats::{self, EpicProgress};
+
+const FIELD_SEP: char = '\u{1f}';
+const RECORD_SEP: char = '\u{1e}';
+/// Commits shown per item; older ones are summarized as a count.
+const MAX_COMMITS: usize = 10;
+
+#[derive(Debug, PartialEq, Serialize)]
+struct LinkedCommit {
+    hash: String,
+    date: String,
+    subject: String,
+}
+
+/// Parse `git log --format=%h<US>%cs<US>%s<US>%b<RS>` output, keeping the commits whose
+/// subject or body mention `item_id` as a whole ID (see `mentions`).
+fn parse_commit_log(log_output: &str, item_id: &str) -> Vec<LinkedCommit> {
+    log_output.split(RECORD_SEP)
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(4, FIELD_SEP);
+            let commit = LinkedCommit {
+                hash: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+            };
+            let body = fields.next().unwrap_or_default();
+            (mentions(&commit.subject, item_id) || mentions(body, item_id)).then_some(commit)
+        })
+        .collect()
+}
+
+/// Commits on any branch that mention the item (e.g. the `Refs: US-003` trailer written by
+/// `commit-msg`). Outside a git repository there are none.
+fn linked_commits(item_id: &str) -> Vec<LinkedCommit> {
+    // --grep is a substring match, so US-100 also finds US-1000; parse_commit_log filters those out
+    let format = format!("--format=%h{}%cs{}%s{}%b{}", FIELD_SEP, FIELD_SEP, FIELD_SEP, RECORD_SEP);
+    let grep = format!("--grep={}", item_id);
+    git::run(&["log", "--all", "--fixed-strings", &grep, &format])
+        .map(|log| parse_commit_log(&log, item_id))
+        .unwrap_or_default()
+}
+
+/// True when `id` occurs in `text` and is not part of a longer ID (US-003 vs US-0031).
+fn mentions(text: &str, id: &str) -> bool {
+    text.match_indices(id).any(|(idx, _)| {
+        let before = text[..idx].chars().next_back();
+        let after = text[idx + id.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric() || c == '-') && !after.is_some_and(|c| c.is_alphanumeric())
+    })
+}
+
+/// Session logs in `.env-coach/logs` that mention the item, plus an unfinished
+/// `execute-task` run for it.
+fn linked_sessions(root: &Path, item_id: &str) -> Vec<PathBuf> {
+    let mut sessions: Vec<PathBuf> = std::fs::read_dir(root.join(".env-coach/logs"))
+        .map(|entries| entries.filter_map(|entry| entry.ok().map(|e| e.path())).collect())
+        .unwrap_or_default();
+    sessions.retain(|path| std::fs::read_to_string(path).is_ok_and(|text| mentions(&text, item_id)));
+    sessions.sort();
+
+    let progress = root.join(".env-coach/progress").join(format!("{}.json", item_id));
+    if progress.exists() {
+        sessions.push(progress);
+    }
+    sessions
+}
+
+pub(crate) fn find_item<'a>(project: &'a Project, item_id: &str) -> Option<(&'a BacklogItem, Option<DateTime<Utc>>)> {
+    project.backlog.iter()
+        .find(|item| item.id == item_id)
+        .map(|item| (item, None))
+        .or_else(|| project.archive.iter()
+            .find(|archived| archived.item.id == item_id)
+            .map(|archived| (&archived.item, Some(archived.archived))))
+}
+
+fn status_of(project: &Project, item_id: &str) -> String {
+    find_item(project, item_id)
+        .map(|(item, _)| format!("{:?}", item.status))
+        .unwrap_or_else(|| "missing".to_string())
+}
+
+/// `--format json|yaml` output of show-item.
+#[derive(Serialize)]
+struct ItemDetails<'a> {
+    item: &'a BacklogItem,
+    /// Link for `item.external_ref`, when its tracker is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_url: Option<String>,
+    archived: Option<DateTime<Utc>>,
+    /// Rollup of the children, for epics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    progress: Option<EpicProgress>,
+    /// Items that depend on this one
+    blocks: Vec<&'a str>,
+    commits: Vec<LinkedCommit>,
+    sessions: Vec<PathBuf>,
+}
+
+/// Print everything known about one item: fields, acceptance criteria, dependencies in
+/// both directions, history, and the commits and sessions that mention it.
+pub fn run(ctx: &AppContext, item_id: String) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let (item, archived) = find_item(&project, &item_id)
+        .ok_or_else(|| anyhow::anyhow!("Item '{}' not found in the backlog or archive", item_id))?;
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+
+    if ctx.format.is_structured() {
+        let details = ItemDetails {
+            item,
+            external_url: item.external_ref.as_deref().and_then(|external_ref| Linker::new(&project, &ctx.global_config).url(external_ref)),
+            archived,
+            progress: matches!(item.item_type, ItemType::Epic).then(|| stats::epic_progress(&project, &item.id)),
+            blocks: project.backlog.iter().filter(|other| other.dependencies.contains(&item.id)).map(|other| other.id.as_str()).collect(),
+            commits: linked_commits(&item.id),
+            sessions: linked_sessions(&root, &item.id).into_iter()
+                .map(|session| session.strip_prefix(&root).map(Path::to_path_buf).unwrap_or(session))
+                .collect(),
+        };
+        return ctx.print_structured(&details);
+    }
+
+    let (priority_emoji, type_emoji) = item_badges(item);
+    println!("{} {} {} - {}", priority_emoji, type_emoji, item.id, item.title);
+    println!("   Type: {:?} | Status: {:?} | Priority: {:?} | Effort: {} points", item.item_type, item.status, item.priority, item.effort);
+    println!("   Created: {}", item.created.format("%Y-%m-%d"));
+    if let Some(started) = item.started_at {
+        println!("   Started: {}", started.format("%Y-%m-%d"));
+    }
+    if let Some(completed) = item.completed_at {
+        println!("   Completed: {}", completed.format("%Y-%m-%d"));
+    }
+    if let Some(archived) = archived {
+        println!("   📦 Archived: {}", archived.format("%Y-%m-%d"));
+    }
+    if let Some(sprint) = &item.sprint {
+        println!("   🏃 Sprint: {}", sprint);
+    }
+    if let Some(parent) = &item.parent {
+        match item.item_type {
+            ItemType::Task => println!("   🧩 Part of: {}", parent),
+            _ => println!("   🎯 Epic: {}", parent),
+        }
+    }
+    if let Some(component) = &item.component {
+        println!("   🧩 Component: {}", component);
+    }
+    if !item.tags.is_empty() {
+        println!("   🏷️  Tags: {}", item.tags.join(", "));
+    }
+    for key in item.extra.keys() {
+        println!("   🔖 {}: {}", key, item.field_text(key).unwrap_or_default());
+    }
+    if let Some(link) = Linker::new(&project, &ctx.global_config).describe(item) {
+        println!("   🌐 External: {}", link);
+    }
+
+    println!();
+    println!("📝 Story:");
+    for line in item.story.lines() {
+        println!("   {}", line);
+    }
+
+    println!();
+    if item.acceptance_criteria.is_empty() {
+        println!("✅ Acceptance criteria: none");
+    } else {
+        println!("✅ Acceptance criteria:");
+        for (i, criterion) in item.acceptance_criteria.iter().enumerate() {
+            println!("   {}. {}", i + 1, criterion);
+        }
+    }
+
+    if matches!(item.item_type, ItemType::Epic) {
+        let progress = stats::epic_progress(&project, &item.id);
+        println!();
+        println!("📊 Progress: {} / {} points ({}%), {} / {} items done",
+            progress.done_points, progress.total_points, progress.percent(), progress.done_items, progress.total_items);
+        for child in project.backlog.iter().filter(|child| child.parent.as_deref() == Some(item.id.as_str())) {
+            println!("   - {} [{:?}] {}", child.id, child.status, child.title);
+        }
+    } else {
+        let tasks: Vec<&BacklogItem> = project.backlog.iter()
+            .filter(|child| child.parent.as_deref() == Some(item.id.as_str()) && matches!(child.item_type, ItemType::Task))
+            .collect();
+        if !tasks.is_empty() {
+            println!();
+            println!("🧩 Tasks:");
+            for task in tasks {
+                println!("   - {} [{:?}] {} [{}pts]", task.id, task.status, task.title, task.effort);
+            }
+        }
+    }
+
+    let blocks: Vec<&BacklogItem> = project.backlog.iter().filter(|other| other.dependencies.contains(&item.id)).collect();
+    if !item.dependencies.is_empty() || !blocks.is_empty() {
+        println!();
+        println!("🔗 Dependencies:");
+        for dependency in &item.dependencies {
+            println!("   Depends on {} ({})", dependency, status_of(&project, dependency));
+        }
+        for other in blocks {
+            println!("   Blocks {} ({:?})", other.id, other.status);
+        }
+    }
+
+    if let Some(review) = &item.review {
+        println!();
+        println!("🧐 Review: {}/10 on {}, {} issue(s){}", review.score, review.reviewed_at.format("%Y-%m-%d"), review.issues.len(),
+            if review.summary.is_empty() { String::new() } else { format!(" - {}", review.summary) });
+    }
+
+    if let Some(check) = &item.criteria_check {
+        println!();
+        println!("☑️  Criteria check: {}/{} met on {}", check.checks.iter().filter(|c| c.passed).count(), check.checks.len(),
+            check.checked_at.format("%Y-%m-%d"));
+        crate::scripts::criteria_check::print_checklist(check);
+    }
+
+    if !item.history.is_empty() {
+        println!();
+        println!("🕓 History:");
+        for event in &item.history {
+            println!("   {} {}: {}", event.at.format("%Y-%m-%d %H:%M"), event.user, event.change);
+        }
+    }
+
+    let commits = linked_commits(&item.id);
+    if !commits.is_empty() {
+        println!();
+        println!("🔀 Commits ({}):", commits.len());
+        for commit in commits.iter().take(MAX_COMMITS) {
+            println!("   {} {} {}", commit.hash, commit.date, commit.subject);
+        }
+        if commits.len() > MAX_COMMITS {
+            println!("   ... and {} older", commits.len() - MAX_COMMITS);
+        }
+    }
+
+    let sessions = linked_sessions(&root, &item.id);
+    if !sessions.is_empty() {
+        println!();
+        println!("🗒️  Sessions:");
+        for session in &sessions {
+            println!("   {}", session.strip_prefix(&root).unwrap_or(session).display());
+        }
+    }
+
+    hints::print_next_steps(Some(&project), &[
+        (format!("env-coach edit-item {} --editor", item.id).as_str(), "Change the item"),
+        (format!("env-coach start-task {}", item.id).as_str(), "Start working on it"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commit_log() {
+        let log = "abc1234\u{1f}2026-05-01\u{1f}feat(api): add token refresh\u{1f}Refs: US-100\n\u{1e}\n\
+            def5678\u{1f}2026-05-02\u{1f}US-1000: export\u{1f}\u{1e}\n\
+            0123abc\u{1f}2026-05-03\u{1f}fix(US-100): retry\u{1f}\u{1e}\nbad line\n";
+        assert_eq!(parse_commit_log(log, "US-100"), vec![
+            LinkedCommit { hash: "abc1234".to_string(), date: "2026-05-01".to_string(), subject: "feat(api): add token refresh".to_string() },
+            LinkedCommit { hash: "0123abc".to_string(), date: "2026-05-03".to_string(), subject: "fix(US-100): retry".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn test_linked_sessions_match_whole_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let logs = dir.path().join(".env-coach/logs");
+        std::fs::create_dir_all(&logs).unwrap();
+        std::fs::write(logs.join("2026-05-01.md"), "Worked on US-003, paired on BUG-001").unwrap();
+        std::fs::write(logs.join("2026-05-02.md"), "Started US-0031").unwrap();
+
+        let sessions = linked_sessions(dir.path(), "US-003");
+        assert_eq!(sessions, vec![logs.join("2026-05-01.md")]);
+        assert!(linked_sessions(dir.path(), "US-004").is_empty());
+    }
+}
@@ -1,40 +1,87 @@
 // src/scripts/sprint.rs
 use anyhow::{Context, Result};
-use chrono::{Utc, Duration};
-use serde::Deserialize; // For parsing LLM response
-use std::io::{self, Write}; // For user input
-
-use crate::config::{Project, Sprint, SprintStatus, Status, BacklogItem};
+use chrono::{DateTime, Utc, Duration};
+use serde::{Deserialize, Serialize}; // For parsing LLM response
+
+use crate::code_context::{self, ContextConfig};
+use crate::config::{ItemType, LlmRole, Project, Sprint, SprintStatus, Status, BacklogItem};
+use crate::context::AppContext;
+use crate::hints;
+use crate::output;
+use crate::prompts;
+use crate::scripts::{import, item_ids};
+use crate::stats;
+use crate::tokens::{self, Section};
 // Assuming ollama.rs will have a suitable function, or we'll add one.
 // For now, let's define a placeholder for the LLM call.
 use crate::ollama; // Placeholder, may need a specific function
 use crate::templates::Templates; // To access default prompt if file missing (though init should create it)
 
 #[derive(Deserialize, Debug)]
-struct LlmSprintPlanResponse {
+pub(crate) struct LlmSprintPlanResponse {
     suggested_story_ids: Vec<String>,
     #[serde(default)]
     reasoning: String,
 }
 
-// Helper to format backlog items for the prompt
-fn format_backlog_for_prompt(backlog: &[BacklogItem]) -> String {
-    backlog
+pub(crate) fn parse_sprint_plan_response(response: &str) -> Result<LlmSprintPlanResponse> {
+    serde_json::from_str(response).context("Failed to parse LLM sprint plan response")
+}
+
+// Helper to format backlog items for the prompt, one entry per item, most important first
+fn backlog_entries(backlog: &[BacklogItem]) -> Vec<String> {
+    let mut todo: Vec<&BacklogItem> = backlog
         .iter()
         .filter(|item| matches!(item.status, Status::Todo)) // Only consider 'Todo' items
+        .collect();
+    todo.sort_by_key(|item| item.priority.rank());
+    todo.into_iter()
         .map(|item| {
-            format!(
-                "- ID: {}\n  - Title: {}\n  - Priority: {:?}\n  - Effort: {} points\n  - Story: {:.100}...", // Summary of story
-                item.id, item.title, item.priority, item.effort, item.story.chars().take(100).collect::<String>()
-            )
+            let mut entry = format!(
+                "- ID: {}\n  - Title: {}\n  - Priority: {:?}\n  - Effort: {} points\n  - Story: {}",
+                item.id, item.title, item.priority, item.effort, item.story
+            );
+            for key in item.extra.keys() {
+                entry.push_str(&format!("\n  - {}: {}", key, item.field_text(key).unwrap_or_default()));
+            }
+            entry
         })
-        .collect::<Vec<String>>()
-        .join("\n")
+        .collect()
+}
+
+
+/// One plan from the plans for each part of the backlog: every suggested ID (once, in
+/// order) and the reasonings joined.
+fn merge_plans(plans: Vec<LlmSprintPlanResponse>) -> LlmSprintPlanResponse {
+    let mut merged = LlmSprintPlanResponse { suggested_story_ids: vec![], reasoning: String::new() };
+    for plan in plans {
+        for id in plan.suggested_story_ids {
+            if !merged.suggested_story_ids.contains(&id) {
+                merged.suggested_story_ids.push(id);
+            }
+        }
+        if !plan.reasoning.is_empty() {
+            if !merged.reasoning.is_empty() {
+                merged.reasoning.push(' ');
+            }
+            merged.reasoning.push_str(&plan.reasoning);
+        }
+    }
+    merged
 }
 
+/// Todo items that fail the project's definition of ready, with the reasons.
+fn unready_items(project: &Project) -> Vec<(String, Vec<String>)> {
+    let dor = project.meta.definition_of_ready.clone().unwrap_or_default();
+    project.backlog.iter()
+        .filter(|item| matches!(item.status, Status::Todo))
+        .map(|item| (item.id.clone(), dor.unmet(item, &project.backlog)))
+        .filter(|(_, reasons)| !reasons.is_empty())
+        .collect()
+}
 
-pub async fn plan(goal: String, days: u32) -> Result<()> { // Made async
-    let mut project = Project::load().context("Failed to load project. Run 'env-coach init' first.")?;
+pub async fn plan(ctx: &AppContext, goal: String, days: u32, include_unready: bool, prompt_file: Option<&std::path::Path>) -> Result<()> { // Made async
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
 
     println!("🚀 Planning new sprint...");
     println!("🎯 Goal: {}", goal);
@@ -42,9 +89,9 @@ pub async fn plan(goal: String, days: u32) -> Result<()> { // Made async
 
     // 1. Load Sprint Planner Prompt
     let prompt_template_path = std::path::Path::new(".env-coach/prompts/sprint_planner.md");
-    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
-        Ok(template) => template,
-        Err(_) => {
+    let prompt_template = match prompt_file.map(prompts::read_override).transpose()?.or_else(|| std::fs::read_to_string(prompt_template_path).ok()) {
+        Some(template) => template,
+        None => {
             println!("⚠️ Sprint planner prompt not found at {:?}. Using default.", prompt_template_path);
             // In a real scenario, init should ensure this exists.
             // For robustness here, load from Templates if missing.
@@ -63,9 +110,30 @@ pub async fn plan(goal: String, days: u32) -> Result<()> { // Made async
         return Ok(());
     }
 
-    let backlog_summary = format_backlog_for_prompt(&project.backlog);
+    // Definition of ready: unready items stay out of the LLM prompt unless overridden
+    let unready = unready_items(&project);
+    let unready_reasons = |id: &str| unready.iter().find(|(uid, _)| uid == id).map(|(_, reasons)| reasons.join(", "));
+    if !unready.is_empty() {
+        if include_unready {
+            println!("⚠️  {} item(s) do not meet the definition of ready (included by --include-unready):", unready.len());
+        } else {
+            println!("⛔ {} item(s) do not meet the definition of ready and are not offered to the LLM:", unready.len());
+        }
+        for (id, reasons) in &unready {
+            println!("   - {}: {}", id, reasons.join(", "));
+        }
+        if !include_unready {
+            println!("💡 Fix them with `env-coach edit-item <id>`, or pass --include-unready to plan them anyway");
+        }
+    }
+    let prompt_items: Vec<BacklogItem> = project.backlog.iter()
+        .filter(|item| include_unready || unready_reasons(&item.id).is_none())
+        .cloned()
+        .collect();
+
+    let backlog = backlog_entries(&prompt_items);
 
-    let mut filled_prompt = prompt_template.replace("{{sprint_goal}}", &goal);
+    let mut filled_prompt = prompts::fill_project_context(&prompt_template, &project, None).replace("{{sprint_goal}}", &goal);
     // Simple replacement for optional fields; a real templating engine would be better.
     filled_prompt = filled_prompt.replace("{{#if sprint_duration_days}}", "");
     filled_prompt = filled_prompt.replace("{{/if}}", "");
@@ -86,12 +154,21 @@ pub async fn plan(goal: String, days: u32) -> Result<()> { // Made async
   {{/if}}
   - **Priority:** {{this.priority}}
   - **Effort:** {{this.effort}} points"#,
-        &backlog_summary
+        "{{backlog_items}}"
     );
+    // Stories are sent in full; when the prompt would not fit the model's context, the
+    // configured strategy applies to the code first, then the backlog
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let context_config = ContextConfig::load_or_default(&root);
+    let prompts = tokens::build_prompts(&project.llm_for(LlmRole::Planner), context_config.strategy, context_config.summary_model.as_deref(), &filled_prompt, vec![
+        Section::new("{{backlog_items}}", backlog, "\n"),
+        Section::new("{{code_context}}", code_context::code_context_entries(&root, &context_config), "\n\n"),
+    ]).await;
 
 
     // 3. Send to LLM (Placeholder for actual LLM call)
-    println!("\n🤖 Asking LLM for sprint plan suggestions (using prompt from sprint_planner.md)...");
+    let prompt_source = prompt_file.map_or_else(|| "sprint_planner.md".to_string(), |path| path.display().to_string());
+    println!("\n🤖 Asking LLM for sprint plan suggestions (using prompt from {})...", prompt_source);
     // let llm_response_str = call_llm_for_sprint_planning(project.llm(), &filled_prompt).await?;
     // For now, using a mock response. Replace with actual LLM call.
     // This part will require an async function if the LLM call is async.
@@ -99,35 +176,46 @@ pub async fn plan(goal: String, days: u32) -> Result<()> { // Made async
 
     // The MOCK LLM RESPONSE logic has been removed as we are now making a real call.
     // For testing purposes, we can re-introduce a mock path.
-    let llm_response_str = if goal.to_lowercase().contains("test-auth-mock") { // Specific trigger for test
+    let llm_responses = if goal.to_lowercase().contains("test-auth-mock") { // Specific trigger for test
         println!("--- USING MOCK LLM RESPONSE FOR SPRINT TEST ---");
-        r#"{
+        vec![r#"{
           "suggested_story_ids": ["US-001", "US-002"],
           "reasoning": "Mocked: Focused on core authentication features."
-        }"#.to_string()
+        }"#.to_string()]
+    } else if !prompt_items.iter().any(|item| matches!(item.status, Status::Todo)) {
+        println!("ℹ️ No ready items to suggest; skipping the LLM.");
+        vec![r#"{ "suggested_story_ids": [] }"#.to_string()]
     } else if goal.to_lowercase().contains("test-empty-mock") {
         println!("--- USING MOCK EMPTY LLM RESPONSE FOR SPRINT TEST ---");
-        r#"{
+        vec![r#"{
           "suggested_story_ids": [],
           "reasoning": "Mocked: No stories fit."
-        }"#.to_string()
+        }"#.to_string()]
     }
     else {
-        ollama::send_generation_prompt(project.llm(), &filled_prompt).await.context("LLM call for sprint planning failed")?
+        // One call per prompt: the `chunk` strategy spreads a large backlog over several
+        let mut responses = vec![];
+        for (i, prompt) in prompts.iter().enumerate() {
+            if prompts.len() > 1 {
+                println!("   Part {}/{}...", i + 1, prompts.len());
+            }
+            responses.push(ollama::send_generation_prompt(&project.llm_for(LlmRole::Planner), prompt).await.context("LLM call for sprint planning failed")?);
+        }
+        responses
     };
 
     // println!("LLM Raw Prompt Sent (simplified for brevity):\n{{sprint_goal: {}}} \nBacklog Summary: {} items\n...", goal, todo_backlog_items.len());
 
-    // 4. Parse LLM Response
-    let llm_plan: LlmSprintPlanResponse = match serde_json::from_str(&llm_response_str) {
+    // 4. Parse LLM Response(s); a backlog split over several prompts gets the suggestions merged
+    let llm_plan = merge_plans(llm_responses.iter().map(|response| match parse_sprint_plan_response(response) {
         Ok(plan) => plan,
         Err(e) => {
-            println!("⚠️ Failed to parse LLM sprint plan response: {}", e);
-            println!("Raw LLM response: {}", llm_response_str);
+            println!("⚠️ {}: {}", e, e.root_cause());
+            println!("Raw LLM response: {}", response);
             println!("Proceeding with manual story selection.");
             LlmSprintPlanResponse { suggested_story_ids: vec![], reasoning: String::new() }
         }
-    };
+    }).collect());
 
     // 5. Display Suggestions & User Confirmation
     println!("\n🧠 LLM Suggestion Review:");
@@ -136,31 +224,47 @@ pub async fn plan(goal: String, days: u32) -> Result<()> { // Made async
     }
 
     let mut confirmed_story_ids: Vec<String> = Vec::new();
+    // Question for the manual picker; `None` when the suggestions were accepted as they are
+    let mut manual_question: Option<&str> = None;
 
     if !llm_plan.suggested_story_ids.is_empty() {
         println!("   Suggested Stories for Sprint:");
         for id in &llm_plan.suggested_story_ids {
             if let Some(item) = project.backlog.iter().find(|i| &i.id == id && matches!(i.status, Status::Todo)) {
-                println!("     - {} ({} pts, {:?}) - {}", item.id, item.effort, item.priority, item.title);
+                let flag = unready_reasons(&item.id).map(|r| format!(" ⛔ not ready: {}", r)).unwrap_or_default();
+                println!("     - {} ({} pts, {:?}) - {}{}", item.id, item.effort, item.priority, item.title, flag);
             } else {
                 println!("     - {} (Warning: Not found in 'Todo' backlog or details missing)", id);
             }
         }
 
-        print!("\n👉 Do you want to accept these suggestions? (yes/no/manual): ");
-        io::stdout().flush()?;
-        let mut user_choice = String::new();
-        io::stdin().read_line(&mut user_choice)?;
+        let user_choice = if ctx.prompter.assume_yes() {
+            println!("\n👉 Accepting suggestions [--yes]");
+            "yes".to_string()
+        } else {
+            ctx.prompter.ask("\n👉 Do you want to accept these suggestions? (yes/no/edit/manual): ")?
+        };
 
-        match user_choice.trim().to_lowercase().as_str() {
+        match user_choice.to_lowercase().as_str() {
             "yes" | "y" => {
                 confirmed_story_ids = llm_plan.suggested_story_ids.iter()
                     .filter(|id| project.backlog.iter().any(|item| &item.id == *id && matches!(item.status, Status::Todo)))
+                    .filter(|id| include_unready || unready_reasons(id).is_none())
                     .cloned()
                     .collect();
                 if confirmed_story_ids.len() != llm_plan.suggested_story_ids.len() {
-                    println!("⚠️ Some suggested stories were not found in the 'Todo' backlog and were excluded.");
+                    println!("⚠️ Some suggested stories were not found in the 'Todo' backlog or are not ready, and were excluded.");
+                }
+            }
+            "edit" | "e" => {
+                // Keep or drop each suggestion, then top up in the manual picker
+                for id in &llm_plan.suggested_story_ids {
+                    let Some(item) = project.backlog.iter().find(|i| &i.id == id && matches!(i.status, Status::Todo)) else { continue };
+                    if ctx.prompter.confirm(&format!("   Keep {} - {}?", item.id, item.title))? {
+                        confirmed_story_ids.push(item.id.clone());
+                    }
                 }
+                manual_question = Some("\nAdd more stories (comma-separated numbers or IDs, blank for none): ");
             }
             "manual" | "m" => {
                 // Manual selection logic will be handled below
@@ -173,45 +277,38 @@ pub async fn plan(goal: String, days: u32) -> Result<()> { // Made async
         println!("   LLM did not suggest any specific stories. Proceeding with manual selection.");
     }
 
-    // Manual selection if chosen or if LLM suggestions were skipped/empty
-    if confirmed_story_ids.is_empty() { // Also true if user chose "manual" or "no" to non-empty suggestions
+    // Manual selection if chosen, after editing the suggestions, or if they were skipped/empty
+    if confirmed_story_ids.is_empty() && manual_question.is_none() {
+        manual_question = Some("\nEnter comma-separated numbers or IDs of stories to include (e.g., 1,US-003,4): ");
+    }
+    if let Some(question) = manual_question {
         println!("\n📝 Available 'Todo' stories for manual selection:");
         let mut available_effort = 0;
         for (idx, item) in todo_backlog_items.iter().enumerate() {
-            println!("   {}. {} ({} pts, {:?}) - {}", idx + 1, item.id, item.effort, item.priority, item.title);
+            let flag = unready_reasons(&item.id).map(|r| format!(" ⛔ not ready: {}", r)).unwrap_or_default();
+            let selected = if confirmed_story_ids.contains(&item.id) { " ✓" } else { "" };
+            println!("   {}. {} ({} pts, {:?}) - {}{}{}", idx + 1, item.id, item.effort, item.priority, item.title, selected, flag);
             available_effort += item.effort;
         }
         println!("   Total available effort in 'Todo': {} points", available_effort);
 
-        print!("\nEnter comma-separated numbers or IDs of stories to include (e.g., 1,US-003,4): ");
-        io::stdout().flush()?;
-        let mut manual_selection = String::new();
-        io::stdin().read_line(&mut manual_selection)?;
-
-        for part in manual_selection.trim().split(',') {
-            let part = part.trim();
-            if part.is_empty() { continue; }
-            // Try parsing as number (index)
-            if let Ok(num_idx) = part.parse::<usize>() {
-                if num_idx > 0 && num_idx <= todo_backlog_items.len() {
-                    if let Some(item) = todo_backlog_items.get(num_idx - 1) {
-                        confirmed_story_ids.push(item.id.clone());
-                    } else {
-                        println!("⚠️ Invalid selection index: {}", num_idx);
+        let manual_selection = ctx.prompter.ask(question)?;
+        confirmed_story_ids.extend(parse_story_selection(&manual_selection, &todo_backlog_items));
+        confirmed_story_ids.sort();
+        confirmed_story_ids.dedup(); // Remove duplicates
+
+        if !include_unready {
+            let mut ready_ids = Vec::new();
+            for id in confirmed_story_ids {
+                match unready_reasons(&id) {
+                    Some(reasons) if !ctx.prompter.confirm(&format!("⛔ {} is not ready ({}). Include it anyway?", id, reasons))? => {
+                        println!("   Skipping {}", id);
                     }
-                } else {
-                     println!("⚠️ Invalid selection index: {}", num_idx);
-                }
-            } else { // Try as ID
-                if todo_backlog_items.iter().any(|item| item.id == part) {
-                    confirmed_story_ids.push(part.to_string());
-                } else {
-                    println!("⚠️ Story ID not found in 'Todo' backlog: {}", part);
+                    _ => ready_ids.push(id),
                 }
             }
+            confirmed_story_ids = ready_ids;
         }
-        confirmed_story_ids.sort();
-        confirmed_story_ids.dedup(); // Remove duplicates
     }
 
     if confirmed_story_ids.is_empty() {
@@ -228,6 +325,13 @@ pub async fn plan(goal: String, days: u32) -> Result<()> { // Made async
         }
     }
     println!("   Total estimated effort: {} points", total_sprint_points);
+    let recent_sprints = stats::completed_sprints(&project, 3);
+    if let Some(average) = stats::average_velocity(&recent_sprints) {
+        println!("   Recent average velocity: {:.1} points", average);
+        if total_sprint_points as f64 > average * 1.2 {
+            println!("⚠️  This sprint is more than 20% above your recent velocity.");
+        }
+    }
 
     // 6. Create Sprint Object
     let sprint_id_num = project.sprints.len() + 1;
@@ -245,8 +349,9 @@ pub async fn plan(goal: String, days: u32) -> Result<()> { // Made async
         completed_points: 0,
         tasks: confirmed_story_ids.clone(), // Storing story IDs as tasks for now
         stories: confirmed_story_ids.clone(), // Also store here, might differentiate later
-        planned_velocity: 0, // Could be estimated based on past sprints later
+        planned_velocity: u8::try_from(total_sprint_points).unwrap_or(u8::MAX),
         actual_velocity: 0,
+        retrospective: None,
     };
 
     project.sprints.push(new_sprint);
@@ -258,12 +363,574 @@ pub async fn plan(goal: String, days: u32) -> Result<()> { // Made async
         }
     }
 
-    project.save().context("Failed to save updated project configuration")?;
+    ctx.save_project(&project).context("Failed to save updated project configuration")?;
 
     println!("\n🎉 Sprint '{}' planned successfully!", sprint_id);
-    println!("💡 To start the sprint, run: env-coach start-sprint {}", sprint_id);
-    println!("💡 Or view sprint details: env-coach show-sprint (after starting)");
+    if output::show_hints() {
+        println!("💡 To start the sprint, run: env-coach start-sprint {}", sprint_id);
+        println!("💡 Or view sprint details: env-coach show-sprint (after starting)");
+    }
+
+    Ok(())
+}
+
+/// Resolve a comma-separated list of 1-based numbers or IDs against the Todo items shown
+/// in the manual picker, warning about entries that match nothing.
+fn parse_story_selection(input: &str, todo_items: &[&BacklogItem]) -> Vec<String> {
+    let mut ids = Vec::new();
+    for part in input.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        if let Ok(num_idx) = part.parse::<usize>() {
+            match num_idx.checked_sub(1).and_then(|idx| todo_items.get(idx)) {
+                Some(item) => ids.push(item.id.clone()),
+                None => println!("⚠️ Invalid selection index: {}", num_idx),
+            }
+        } else if todo_items.iter().any(|item| item.id == part) {
+            ids.push(part.to_string());
+        } else {
+            println!("⚠️ Story ID not found in 'Todo' backlog: {}", part);
+        }
+    }
+    ids
+}
+
+pub fn start_sprint(_ctx: &AppContext, _sprint_id: String) -> Result<()> {
+    println!("🏃 Sprint start functionality coming soon!");
+    println!("💡 For now, you can:");
+    println!("   env-coach start-task <task-id>      # Start working on tasks");
+    Ok(())
+}
+
+/// The active sprint whose end date is before `now`, if any.
+fn find_expired_sprint(project: &Project, now: DateTime<Utc>) -> Option<&Sprint> {
+    project.sprints.iter()
+        .find(|s| matches!(s.status, SprintStatus::Active) && s.end_date < now)
+}
+
+/// Where unfinished work of an expired sprint goes: the earliest sprint still in planning.
+fn next_planned_sprint<'a>(project: &'a Project, after: &str) -> Option<&'a Sprint> {
+    project.sprints.iter()
+        .filter(|s| s.id != after && matches!(s.status, SprintStatus::Planning))
+        .min_by_key(|s| s.start_date)
+}
+
+/// Offer to close an active sprint whose end date has passed, so commands don't show a
+/// stale "Active" sprint. Closes without asking when `auto_close_expired_sprints` is set
+/// in the global config. Returns true if a sprint was closed.
+pub fn check_sprint_expiry(ctx: &AppContext) -> Result<bool> {
+    let project = ctx.load_project()?;
+    let Some(sprint) = find_expired_sprint(&project, Utc::now()) else { return Ok(false) };
+
+    let days_over = (Utc::now() - sprint.end_date).num_days();
+    println!("⏰ Sprint {} ended on {} ({} day(s) ago) but is still Active",
+        sprint.id, sprint.end_date.format("%Y-%m-%d"), days_over);
+
+    // Closing is a maintainer operation; contributors only get the hint, even when the
+    // team config would let them close it with a warning
+    if !ctx.acts_as_maintainer(&project) {
+        println!("💡 Ask a maintainer to close it: env-coach complete-sprint {}", sprint.id);
+        println!();
+        return Ok(false);
+    }
+
+    let carry_over = next_planned_sprint(&project, &sprint.id).map(|s| s.id.clone());
+    let destination = carry_over.as_deref().map(|id| format!("sprint {}", id)).unwrap_or_else(|| "the backlog".to_string());
+
+    let close = if ctx.global_config.auto_close_expired_sprints {
+        println!("🔒 auto_close_expired_sprints is enabled; closing it now");
+        true
+    } else {
+        ctx.prompter.confirm(&format!("👉 Close {} and move unfinished items to {}?", sprint.id, destination))?
+    };
+    if !close {
+        println!("💡 Close it later with: env-coach complete-sprint {}", sprint.id);
+        println!();
+        return Ok(false);
+    }
+
+    let sprint_id = sprint.id.clone();
+    complete_sprint(ctx, sprint_id, carry_over)?;
+    println!();
+    Ok(true)
+}
+
+/// `--format json|yaml` output of show-sprint; `sprint` is null without an active sprint.
+#[derive(Serialize)]
+struct CurrentSprint<'a> {
+    sprint: Option<&'a Sprint>,
+    items: Vec<&'a BacklogItem>,
+}
+
+pub fn show_current_sprint(ctx: &AppContext) -> Result<()> {
+    // Structured output is for scripts, which must not be asked to close an expired sprint
+    if !ctx.format.is_structured() {
+        check_sprint_expiry(ctx)?;
+    }
+    let project = ctx.load_project()?;
+    
+    let active_sprint = project.sprints.iter().find(|s| matches!(s.status, SprintStatus::Active));
+    if ctx.format.is_structured() {
+        let items = active_sprint
+            .map(|sprint| project.backlog.iter().filter(|item| item.sprint.as_ref() == Some(&sprint.id)).collect())
+            .unwrap_or_default();
+        return ctx.print_structured(&CurrentSprint { sprint: active_sprint, items });
+    }
+    
+    match active_sprint {
+        Some(sprint) => {
+            println!("🏃 Current Sprint: {}", sprint.id);
+            println!("🎯 Goal: {}", sprint.goal);
+            println!("📅 Duration: {} to {}", 
+                sprint.start_date.format("%Y-%m-%d"),
+                sprint.end_date.format("%Y-%m-%d")
+            );
+            let sprint_stats = stats::sprint_stats(&project, sprint, Utc::now());
+            println!("📊 Progress: {} / {} points", sprint_stats.completed_points, sprint_stats.committed_points);
+            println!("📈 Completion: {}%", sprint_stats.completion_percent);
+            
+            // Show sprint backlog
+            let sprint_items: Vec<_> = project.backlog
+                .iter()
+                .filter(|item| item.sprint.as_ref() == Some(&sprint.id))
+                .collect();
+                
+            if !sprint_items.is_empty() {
+                println!();
+                println!("📋 Sprint Backlog ({} items):", sprint_items.len());
+                
+                for item in sprint_items {
+                    let status_emoji = match item.status {
+                        Status::Todo => { "⏳" },
+                        Status::InProgress => { "🚧" },
+                        Status::Review => { "👀" },
+                        Status::Done => { "✅" },
+                    };
+                    
+                    let priority_emoji = match item.priority {
+                        crate::config::Priority::Critical => "🔴",
+                        crate::config::Priority::High => "🟠",
+                        crate::config::Priority::Medium => "🟡",
+                        crate::config::Priority::Low => "🟢",
+                    };
+                    
+                    println!("  {} {} {} - {} [{}pts]", 
+                        status_emoji, priority_emoji, item.id, item.title, item.effort);
+                }
+                
+                println!();
+                println!("📊 Sprint Status:");
+                println!("   ⏳ To Do: {}", sprint_stats.work.items.todo);
+                println!("   🚧 In Progress: {}", sprint_stats.work.items.in_progress);
+                println!("   ✅ Done: {}", sprint_stats.work.items.done);
+            }
+        }
+        None => {
+            println!("📭 No active sprint");
+            if output::show_hints() {
+                println!();
+                println!("🎯 Start planning:");
+                println!("   env-coach plan-sprint --goal \"Sprint objective\"  # Plan new sprint");
+                println!("   env-coach list-backlog                           # View available tasks");
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// Result of closing a sprint, used for the console output and the summary report.
+#[derive(Debug)]
+struct SprintClosure {
+    sprint: Sprint,
+    done_items: Vec<BacklogItem>,
+    carried_items: Vec<BacklogItem>,
+    carry_over: Option<String>,
+}
+
+/// Mark a sprint as completed, record its velocity and move unfinished items either back
+/// to the backlog or into `carry_over`. Only mutates `project`; persisting is up to the caller.
+fn close_sprint(project: &mut Project, sprint_id: &str, carry_over: Option<&str>) -> Result<SprintClosure> {
+    let sprint = project.sprints.iter()
+        .find(|s| s.id == sprint_id)
+        .ok_or_else(|| anyhow::anyhow!("Sprint '{}' not found", sprint_id))?;
+    if matches!(sprint.status, SprintStatus::Completed | SprintStatus::Complete) {
+        anyhow::bail!("Sprint '{}' is already completed", sprint_id);
+    }
+
+    if let Some(next_id) = carry_over {
+        if next_id == sprint_id {
+            anyhow::bail!("Cannot carry items over into the sprint being completed ('{}')", sprint_id);
+        }
+        let next = project.sprints.iter()
+            .find(|s| s.id == next_id)
+            .ok_or_else(|| anyhow::anyhow!("Carry-over sprint '{}' not found", next_id))?;
+        if matches!(next.status, SprintStatus::Completed | SprintStatus::Complete) {
+            anyhow::bail!("Carry-over sprint '{}' is already completed", next_id);
+        }
+    }
+
+    let (done_items, carried_items): (Vec<BacklogItem>, Vec<BacklogItem>) = project.backlog.iter()
+        .filter(|item| item.sprint.as_deref() == Some(sprint_id))
+        .cloned()
+        .partition(|item| matches!(item.status, Status::Done));
+
+    // Move unfinished items out of the closed sprint
+    for item in project.backlog.iter_mut().filter(|item| carried_items.iter().any(|c| c.id == item.id)) {
+        item.sprint = carry_over.map(String::from);
+    }
+    if let Some(next_id) = carry_over {
+        if let Some(next) = project.sprints.iter_mut().find(|s| s.id == next_id) {
+            for item in &carried_items {
+                if !next.stories.contains(&item.id) {
+                    next.stories.push(item.id.clone());
+                    next.tasks.push(item.id.clone());
+                    next.total_points += item.effort;
+                }
+            }
+        }
+    }
+
+    let completed_points: u32 = done_items.iter().map(|item| item.effort).sum();
+    let sprint = project.sprints.iter_mut()
+        .find(|s| s.id == sprint_id)
+        .expect("sprint existence checked above");
+    sprint.status = SprintStatus::Completed;
+    sprint.completed_points = completed_points;
+    sprint.actual_velocity = u8::try_from(completed_points).unwrap_or(u8::MAX);
+    if sprint.planned_velocity == 0 {
+        // Sprints planned before velocity tracking: the commitment is the sprint's total points
+        sprint.planned_velocity = u8::try_from(sprint.total_points).unwrap_or(u8::MAX);
+    }
+    let sprint = sprint.clone();
+
+    if project.current_sprint.as_deref() == Some(sprint_id) {
+        project.current_sprint = None;
+    }
+
+    Ok(SprintClosure { sprint, done_items, carried_items, carry_over: carry_over.map(String::from) })
+}
+
+/// Markdown summary written to `.env-coach/reports/` when a sprint is completed.
+fn format_sprint_summary(closure: &SprintClosure) -> String {
+    let sprint = &closure.sprint;
+    let mut summary = format!(
+        "# Sprint {} Summary\n\n- **Goal:** {}\n- **Dates:** {} to {}\n- **Closed:** {}\n- **Planned:** {} points\n- **Completed:** {} points\n- **Velocity:** {}\n",
+        sprint.id,
+        sprint.goal,
+        sprint.start_date.format("%Y-%m-%d"),
+        sprint.end_date.format("%Y-%m-%d"),
+        Utc::now().format("%Y-%m-%d"),
+        sprint.total_points,
+        sprint.completed_points,
+        sprint.actual_velocity,
+    );
+
+    summary.push_str(&format!("\n## Completed ({})\n\n", closure.done_items.len()));
+    if closure.done_items.is_empty() {
+        summary.push_str("- None\n");
+    }
+    for item in &closure.done_items {
+        summary.push_str(&format!("- {} - {} [{}pts]\n", item.id, item.title, item.effort));
+    }
+
+    let destination = match &closure.carry_over {
+        Some(next_id) => format!("carried over to {}", next_id),
+        None => "returned to backlog".to_string(),
+    };
+    summary.push_str(&format!("\n## Unfinished ({}, {})\n\n", closure.carried_items.len(), destination));
+    if closure.carried_items.is_empty() {
+        summary.push_str("- None\n");
+    }
+    for item in &closure.carried_items {
+        summary.push_str(&format!("- {} - {} [{}pts, {:?}]\n", item.id, item.title, item.effort, item.status));
+    }
+    summary
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct LlmStorySplit {
+    #[serde(default)]
+    done_acceptance_criteria: Vec<String>,
+    done_effort: u32,
+    remaining_title: String,
+    remaining_story: String,
+    #[serde(default)]
+    remaining_acceptance_criteria: Vec<String>,
+    remaining_effort: u32,
+}
+
+pub(crate) fn parse_split_response(response: &str) -> Result<LlmStorySplit> {
+    serde_json::from_str(response).context("Failed to parse LLM story split")
+}
+
+/// Split a partially done item: the original keeps the delivered part and is marked Done,
+/// and a new Todo item with the remaining work stays in the same sprint so that
+/// `close_sprint` carries it over. Returns the new item's ID.
+fn split_item(project: &mut Project, item_id: &str, split: LlmStorySplit) -> Result<String> {
+    if split.done_effort == 0 || split.remaining_effort == 0 {
+        anyhow::bail!("Split of '{}' must give both parts at least 1 point", item_id);
+    }
+    let new_id = import::next_id(project, import::id_prefix(&ItemType::UserStory));
+    let item = project.backlog.iter_mut()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| anyhow::anyhow!("Backlog item '{}' not found", item_id))?;
+
+    let remaining = BacklogItem {
+        id: new_id.clone(),
+        item_type: item.item_type.clone(),
+        title: split.remaining_title,
+        story: split.remaining_story,
+        acceptance_criteria: split.remaining_acceptance_criteria,
+        priority: item.priority.clone(),
+        effort: split.remaining_effort,
+        status: Status::Todo,
+        created: Utc::now(),
+        sprint: item.sprint.clone(),
+        dependencies: item.dependencies.clone(),
+        started_at: None,
+        completed_at: None,
+        component: item.component.clone(),
+        parent: item.parent.clone(),
+        tags: vec![],
+        external_ref: None,
+        history: vec![],
+        review: None,
+        criteria_check: None,
+        extra: Default::default(),
+    };
+
+    if !split.done_acceptance_criteria.is_empty() {
+        item.acceptance_criteria = split.done_acceptance_criteria;
+    }
+    item.effort = split.done_effort;
+    item.status = Status::Done;
+    item.completed_at = Some(Utc::now());
+
+    project.backlog.push(remaining);
+    Ok(new_id)
+}
+
+/// Offer to split each In Progress item of the sprint into a delivered part and a
+/// "remaining work" story, using the LLM to propose the split.
+async fn split_unfinished_items(ctx: &AppContext, project: &mut Project, sprint_id: &str) -> Result<()> {
+    let candidates: Vec<BacklogItem> = project.backlog.iter()
+        .filter(|item| item.sprint.as_deref() == Some(sprint_id) && matches!(item.status, Status::InProgress))
+        .cloned()
+        .collect();
+    if candidates.is_empty() {
+        println!("ℹ️ No partially done items to split in {}", sprint_id);
+        return Ok(());
+    }
+
+    let prompt_template_path = std::path::Path::new(".env-coach/prompts/story_splitter.md");
+    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
+        Ok(template) => template,
+        Err(_) => {
+            println!("⚠️ Story splitter prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_story_splitter_prompt_content()
+        }
+    };
+
+    for item in candidates {
+        println!();
+        println!("✂️  {} - {} [{}pts] is In Progress", item.id, item.title, item.effort);
+        if !ctx.prompter.confirm(&format!("👉 Split {} into done and remaining work?", item.id))? {
+            continue;
+        }
+        let notes = ctx.prompter.ask("📝 What was finished? (optional): ")?;
+
+        let criteria = item.acceptance_criteria.iter().map(|c| format!("- {}", c)).collect::<Vec<_>>().join("\n");
+        let prompt = prompts::fill_project_context(&prompt_template, project, Some(&item))
+            .replace("{{project_name}}", &project.meta.name)
+            .replace("{{project_description}}", &project.meta.description)
+            .replace("{{item_id}}", &item.id)
+            .replace("{{item_title}}", &item.title)
+            .replace("{{item_effort}}", &item.effort.to_string())
+            .replace("{{item_story}}", &item.story)
+            .replace("{{extra}}", &item.extra_display())
+            .replace("{{item_acceptance_criteria}}", &criteria)
+            .replace("{{progress_notes}}", if notes.is_empty() { "None given." } else { &notes });
+
+        println!("🤖 Asking LLM to split {}...", item.id);
+        let response = match ollama::send_generation_prompt(&project.llm_for(LlmRole::Planner), &prompt).await {
+            Ok(response) => response,
+            Err(e) => {
+                println!("⚠️ LLM call failed, {} is carried over unchanged: {}", item.id, e);
+                continue;
+            }
+        };
+        let split = match parse_split_response(&response) {
+            Ok(split) => split,
+            Err(e) => {
+                println!("⚠️ Could not parse the LLM split, {} is carried over unchanged: {}", item.id, e.root_cause());
+                continue;
+            }
+        };
+
+        println!("   ✅ Done now: {} [{}pts]", item.title, split.done_effort);
+        println!("   ➡️  Remaining: {} [{}pts]", split.remaining_title, split.remaining_effort);
+        for criterion in &split.remaining_acceptance_criteria {
+            println!("      - {}", criterion);
+        }
+        if !ctx.prompter.confirm("👉 Apply this split?")? {
+            continue;
+        }
+        match split_item(project, &item.id, split) {
+            Ok(new_id) => println!("   ✂️  {} closed; remaining work is {}", item.id, new_id),
+            Err(e) => println!("⚠️ {}", e),
+        }
+    }
+    println!();
+    Ok(())
+}
+
+pub fn complete_sprint(ctx: &AppContext, sprint_id: String, carry_over: Option<String>) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    finish_sprint(ctx, &mut project, sprint_id, carry_over)
+}
+
+/// `complete-sprint --split`: split partially done stories before closing the sprint.
+pub async fn complete_sprint_with_split(ctx: &AppContext, sprint_id: String, carry_over: Option<String>) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    if !project.sprints.iter().any(|s| s.id == sprint_id) {
+        anyhow::bail!("Sprint '{}' not found", sprint_id);
+    }
+    split_unfinished_items(ctx, &mut project, &sprint_id).await?;
+    finish_sprint(ctx, &mut project, sprint_id, carry_over)
+}
+
+fn finish_sprint(ctx: &AppContext, project: &mut Project, sprint_id: String, carry_over: Option<String>) -> Result<()> {
+    let closure = close_sprint(project, &sprint_id, carry_over.as_deref())?;
+    let sprint = &closure.sprint;
+
+    println!("🏁 Completing sprint: {}", sprint.id);
+    println!("🎯 Goal: {}", sprint.goal);
+    println!("📊 Completed: {} / {} points", sprint.completed_points, sprint.total_points);
+    println!("🚀 Velocity: {}", sprint.actual_velocity);
+
+    if !closure.done_items.is_empty() {
+        println!();
+        println!("✅ Done ({}):", closure.done_items.len());
+        for item in &closure.done_items {
+            println!("   - {} - {} [{}pts]", item.id, item.title, item.effort);
+        }
+    }
+    if !closure.carried_items.is_empty() {
+        println!();
+        match &closure.carry_over {
+            Some(next_id) => println!("➡️  Carried over to {} ({}):", next_id, closure.carried_items.len()),
+            None => println!("↩️  Returned to backlog ({}):", closure.carried_items.len()),
+        }
+        for item in &closure.carried_items {
+            println!("   - {} - {} [{}pts]", item.id, item.title, item.effort);
+        }
+    }
+
+    ctx.save_project(project).context("Failed to save updated project configuration")?;
+
+    let report_path = std::path::Path::new(".env-coach/reports").join(format!("{}-summary.md", sprint.id));
+    if ctx.dry_run {
+        println!("🔍 Dry run: {} not written", report_path.display());
+    } else {
+        if let Some(parent) = report_path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create .env-coach/reports directory")?;
+        }
+        std::fs::write(&report_path, format_sprint_summary(&closure))
+            .with_context(|| format!("Failed to write sprint summary to {:?}", report_path))?;
+        println!();
+        println!("📝 Sprint summary written to {}", report_path.display());
+    }
+
+    hints::print_next_steps(Some(project), &[
+        ("env-coach plan-sprint --goal \"...\"", "Plan the next sprint"),
+        ("env-coach status", "Review overall progress"),
+    ]);
+
+    Ok(())
+}
+
+/// Add a backlog item to a sprint that is not yet completed, keeping points in sync.
+fn add_item_to_sprint(project: &mut Project, sprint_id: &str, item_id: &str) -> Result<()> {
+    let sprint = project.sprints.iter()
+        .find(|s| s.id == sprint_id)
+        .ok_or_else(|| anyhow::anyhow!("Sprint '{}' not found", sprint_id))?;
+    if matches!(sprint.status, SprintStatus::Completed | SprintStatus::Complete) {
+        anyhow::bail!("Sprint '{}' is already completed", sprint_id);
+    }
+
+    let item = project.backlog.iter_mut()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| anyhow::anyhow!("Backlog item '{}' not found", item_id))?;
+    match item.sprint.as_deref() {
+        Some(current) if current == sprint_id => anyhow::bail!("'{}' is already in sprint '{}'", item_id, sprint_id),
+        Some(current) => anyhow::bail!("'{}' is already in sprint '{}'; remove it from there first", item_id, current),
+        None => {}
+    }
+    item.sprint = Some(sprint_id.to_string());
+    let (effort, done) = (item.effort, matches!(item.status, Status::Done));
+
+    let sprint = project.sprints.iter_mut()
+        .find(|s| s.id == sprint_id)
+        .expect("sprint existence checked above");
+    sprint.stories.push(item_id.to_string());
+    sprint.tasks.push(item_id.to_string());
+    sprint.total_points += effort;
+    if done {
+        sprint.completed_points += effort;
+    }
+    Ok(())
+}
+
+/// Take a backlog item out of a sprint and return it to the backlog.
+pub(crate) fn remove_item_from_sprint(project: &mut Project, sprint_id: &str, item_id: &str) -> Result<()> {
+    let sprint = project.sprints.iter_mut()
+        .find(|s| s.id == sprint_id)
+        .ok_or_else(|| anyhow::anyhow!("Sprint '{}' not found", sprint_id))?;
+    if matches!(sprint.status, SprintStatus::Completed | SprintStatus::Complete) {
+        anyhow::bail!("Sprint '{}' is already completed", sprint_id);
+    }
+    if !sprint.stories.iter().chain(sprint.tasks.iter()).any(|id| id == item_id) {
+        anyhow::bail!("'{}' is not part of sprint '{}'", item_id, sprint_id);
+    }
+    sprint.stories.retain(|id| id != item_id);
+    sprint.tasks.retain(|id| id != item_id);
+
+    if let Some(item) = project.backlog.iter_mut().find(|item| item.id == item_id) {
+        sprint.total_points = sprint.total_points.saturating_sub(item.effort);
+        if matches!(item.status, Status::Done) {
+            sprint.completed_points = sprint.completed_points.saturating_sub(item.effort);
+        }
+        item.sprint = None;
+    }
+    Ok(())
+}
+
+/// Add several items at once; nothing is saved unless every item can be added.
+pub fn add_items(ctx: &AppContext, sprint_id: String, item_args: Vec<String>) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let item_ids = item_ids::expand(&item_args, &project)?;
+    for item_id in &item_ids {
+        add_item_to_sprint(&mut project, &sprint_id, item_id).context("No items were added")?;
+    }
+    ctx.save_project(&project).context("Failed to save updated project configuration")?;
+
+    let sprint = project.sprints.iter().find(|s| s.id == sprint_id).expect("sprint was just updated");
+    println!("➕ Added {} to sprint {}", item_ids.join(", "), sprint_id);
+    println!("📊 Sprint scope: {} points ({} items)", sprint.total_points, sprint.stories.len());
+    Ok(())
+}
 
+/// Remove several items at once; nothing is saved unless every item can be removed.
+pub fn remove_items(ctx: &AppContext, sprint_id: String, item_args: Vec<String>) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let item_ids = item_ids::expand(&item_args, &project)?;
+    for item_id in &item_ids {
+        remove_item_from_sprint(&mut project, &sprint_id, item_id).context("No items were removed")?;
+    }
+    ctx.save_project(&project).context("Failed to save updated project configuration")?;
+
+    let sprint = project.sprints.iter().find(|s| s.id == sprint_id).expect("sprint was just updated");
+    println!("➖ Removed {} from sprint {} (returned to backlog)", item_ids.join(", "), sprint_id);
+    println!("📊 Sprint scope: {} points ({} items)", sprint.total_points, sprint.stories.len());
     Ok(())
 }
 
@@ -271,39 +938,38 @@ pub async fn plan(goal: String, days: u32) -> Result<()> { // Made async
 mod tests {
     use super::*;
     // Removed ProjectMeta, FinalLlmConfig, Prd as they are not directly used by these specific tests
-    use crate::config::{BacklogItem, ItemType, Priority, Status};
+    use crate::config::{BacklogItem, Priority, Status};
     use chrono::{Utc, TimeZone};
 
     fn create_sample_backlog_item(id: &str, title: &str, story: &str, priority: Priority, effort: u32, status: Status) -> BacklogItem {
-        BacklogItem {
-            id: id.to_string(),
-            title: title.to_string(),
-            story: story.to_string(),
-            priority,
-            effort,
-            status,
-            item_type: ItemType::UserStory,
-            acceptance_criteria: vec!["AC1".to_string()],
-            created: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
-            sprint: None,
-            dependencies: vec![],
-        }
+        BacklogItem::test(id)
+            .with_title(title)
+            .with_story(story)
+            .with_criteria(&["AC1"])
+            .with_priority(priority)
+            .with_effort(effort)
+            .with_status(status)
+            .with_created(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
     }
 
     #[test]
-    fn test_format_backlog_for_prompt_filters_and_formats() {
-        let backlog = vec![
+    fn test_backlog_entries_filters_and_formats() {
+        let mut backlog = vec![
             create_sample_backlog_item("US-001", "Login Feature", "User wants to log in.", Priority::High, 5, Status::Todo),
             create_sample_backlog_item("US-002", "Logout Feature", "User wants to log out securely from their current session.", Priority::Medium, 3, Status::Done),
             create_sample_backlog_item("US-003", "Profile Page", "User wants to see their profile.", Priority::Low, 2, Status::Todo),
         ];
-        let formatted_string = format_backlog_for_prompt(&backlog);
+        backlog[2].extra.insert("customer".to_string(), serde_json::json!("Acme"));
+        let entries = backlog_entries(&backlog);
+        assert_eq!(entries.len(), 2);
+        assert!(entries[1].ends_with("Story: User wants to see their profile.\n  - customer: Acme"));
+        let formatted_string = entries.join("\n");
 
         assert!(formatted_string.contains("ID: US-001"));
         assert!(formatted_string.contains("Title: Login Feature"));
         assert!(formatted_string.contains("Priority: High"));
         assert!(formatted_string.contains("Effort: 5 points"));
-        assert!(formatted_string.contains("Story: User wants to log in....")); // Truncated if long enough
+        assert!(formatted_string.contains("Story: User wants to log in.\n")); // Sent in full; tokens::build_prompts trims
 
         assert!(!formatted_string.contains("ID: US-002")); // Should be filtered out (Status::Done)
 
@@ -312,31 +978,49 @@ mod tests {
         assert!(formatted_string.contains("Priority: Low"));
         assert!(formatted_string.contains("Effort: 2 points"));
 
-        let long_story = "This is a very long story description that definitely exceeds one hundred characters to ensure that the truncation logic is properly applied and tested correctly.";
+        let long_story = "This is a very long story description that exceeds one hundred characters, which used to be cut off before the budget-based trimming was added.";
         let backlog_long_story = vec![
              create_sample_backlog_item("US-004", "Long Story", long_story, Priority::High, 5, Status::Todo),
         ];
-        let formatted_long_story = format_backlog_for_prompt(&backlog_long_story);
-        let expected_truncated_story = format!("Story: {}...", long_story.chars().take(100).collect::<String>());
-        assert!(formatted_long_story.contains(&expected_truncated_story));
+        let formatted_long_story = backlog_entries(&backlog_long_story).join("\n");
+        assert!(formatted_long_story.ends_with(&format!("Story: {}", long_story)));
 
     }
 
     #[test]
-    fn test_format_backlog_for_prompt_empty() {
+    fn test_backlog_entries_empty() {
         let backlog: Vec<BacklogItem> = vec![];
-        let formatted_string = format_backlog_for_prompt(&backlog);
-        assert!(formatted_string.is_empty());
+        assert!(backlog_entries(&backlog).is_empty());
     }
 
     #[test]
-    fn test_format_backlog_for_prompt_no_todo_items() {
+    fn test_backlog_entries_no_todo_items() {
         let backlog = vec![
             create_sample_backlog_item("US-001", "Login Feature", "User wants to log in.", Priority::High, 5, Status::Done),
             create_sample_backlog_item("US-002", "Logout Feature", "User wants to log out.", Priority::Medium, 3, Status::InProgress),
         ];
-        let formatted_string = format_backlog_for_prompt(&backlog);
-        assert!(formatted_string.is_empty());
+        assert!(backlog_entries(&backlog).is_empty());
+    }
+
+    #[test]
+    fn test_backlog_entries_most_important_first_and_merge_plans() {
+        let backlog = vec![
+            create_sample_backlog_item("US-001", "Polish", "", Priority::Low, 1, Status::Todo),
+            create_sample_backlog_item("US-002", "Outage", "", Priority::Critical, 3, Status::Todo),
+            create_sample_backlog_item("US-003", "Login", "", Priority::High, 5, Status::Todo),
+        ];
+        let entries = backlog_entries(&backlog);
+        assert!(entries[0].starts_with("- ID: US-002"));
+        assert!(entries[1].starts_with("- ID: US-003"));
+        assert!(entries[2].starts_with("- ID: US-001"));
+
+        let merged = merge_plans(vec![
+            LlmSprintPlanResponse { suggested_story_ids: vec!["US-002".to_string(), "US-003".to_string()], reasoning: "Fix the outage.".to_string() },
+            LlmSprintPlanResponse { suggested_story_ids: vec![], reasoning: String::new() },
+            LlmSprintPlanResponse { suggested_story_ids: vec!["US-003".to_string(), "US-001".to_string()], reasoning: "Then polish.".to_string() },
+        ]);
+        assert_eq!(merged.suggested_story_ids, vec!["US-002", "US-003", "US-001"]);
+        assert_eq!(merged.reasoning, "Fix the outage. Then polish.");
     }
 
     #[test]
@@ -454,7 +1138,7 @@ mod tests {
         // For now, the `plan` function still has the MOCK LLM RESPONSE path.
         // We are testing this mock path.
 
-        if let Err(e) = plan(goal.clone(), days).await {
+        if let Err(e) = plan(&AppContext::default(), goal.clone(), days, false, None).await {
              eprintln!("Test `test_plan_sprint_llm_suggests_and_user_accepts_mocked_input` failed during `plan` call: {:?}", e);
              // Depending on how stdin is handled in test environment, this might not even be reached if it hangs.
         }
@@ -469,7 +1153,7 @@ mod tests {
 
             if !loaded_project.sprints.is_empty() {
                 assert_eq!(loaded_project.sprints.len(), 1);
-                let sprint = loaded_project.sprints.get(0).unwrap();
+                let sprint = loaded_project.sprints.first().unwrap();
                 assert_eq!(sprint.goal, goal); // Use the goal variable
                 assert_eq!(sprint.status, SprintStatus::Planning);
                 // Assertions for stories depend on the mock response path being taken for "auth"
@@ -484,94 +1168,218 @@ mod tests {
             } else {
                 // This branch will be taken if the sprint was not created, likely due to user input part.
                 println!("Sprint was not created, possibly due to user input prompt in test.");
-                assert!(true, "Sprint not created; input simulation needed for full test or function refactor.");
             }
         } else {
-            println!("project.json not found after plan call in test.");
-            assert!(false, "project.json was not created by plan function.");
+            panic!("project.json was not created by plan function.");
         }
         std::env::set_current_dir(original_dir).unwrap();
     }
-}
-
 
-pub fn start_sprint(_sprint_id: String) -> Result<()> {
-    println!("🏃 Sprint start functionality coming soon!");
-    println!("💡 For now, you can:");
-    println!("   env-coach start-task <task-id>      # Start working on tasks");
-    Ok(())
-}
-
-pub fn show_current_sprint() -> Result<()> {
-    let project = Project::load()?;
-    
-    let active_sprint = project.sprints.iter().find(|s| matches!(s.status, SprintStatus::Active));
-    
-    match active_sprint {
-        Some(sprint) => {
-            println!("🏃 Current Sprint: {}", sprint.id);
-            println!("🎯 Goal: {}", sprint.goal);
-            println!("📅 Duration: {} to {}", 
-                sprint.start_date.format("%Y-%m-%d"),
-                sprint.end_date.format("%Y-%m-%d")
-            );
-            println!("📊 Progress: {} / {} points", sprint.completed_points, sprint.total_points);
-            
-            let progress_percent = if sprint.total_points > 0 {
-                (sprint.completed_points * 100) / sprint.total_points
-            } else {
-                0
-            };
-            println!("📈 Completion: {}%", progress_percent);
-            
-            // Show sprint backlog
-            let sprint_items: Vec<_> = project.backlog
-                .iter()
-                .filter(|item| item.sprint.as_ref() == Some(&sprint.id))
-                .collect();
-                
-            if !sprint_items.is_empty() {
-                println!();
-                println!("📋 Sprint Backlog ({} items):", sprint_items.len());
-                
-                let todo_count = sprint_items.iter().filter(|item| matches!(item.status, Status::Todo)).count();
-                let in_progress_count = sprint_items.iter().filter(|item| matches!(item.status, Status::InProgress)).count();
-                let done_count = sprint_items.iter().filter(|item| matches!(item.status, Status::Done)).count();
-                
-                for item in sprint_items {
-                    let status_emoji = match item.status {
-                        Status::Todo => { "⏳" },
-                        Status::InProgress => { "🚧" },
-                        Status::Review => { "👀" },
-                        Status::Done => { "✅" },
-                    };
-                    
-                    let priority_emoji = match item.priority {
-                        crate::config::Priority::Critical => "🔴",
-                        crate::config::Priority::High => "🟠",
-                        crate::config::Priority::Medium => "🟡",
-                        crate::config::Priority::Low => "🟢",
-                    };
-                    
-                    println!("  {} {} {} - {} [{}pts]", 
-                        status_emoji, priority_emoji, item.id, item.title, item.effort);
-                }
-                
-                println!();
-                println!("📊 Sprint Status:");
-                println!("   ⏳ To Do: {}", todo_count);
-                println!("   🚧 In Progress: {}", in_progress_count);
-                println!("   ✅ Done: {}", done_count);
-            }
+    fn create_sample_sprint(id: &str, status: SprintStatus, stories: &[&str], total_points: u32) -> Sprint {
+        Sprint {
+            id: id.to_string(),
+            goal: format!("Goal for {}", id),
+            start_date: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            end_date: Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap(),
+            status,
+            total_points,
+            completed_points: 0,
+            tasks: stories.iter().map(|s| s.to_string()).collect(),
+            stories: stories.iter().map(|s| s.to_string()).collect(),
+            planned_velocity: 0,
+            actual_velocity: 0,
+            retrospective: None,
         }
-        None => {
-            println!("📭 No active sprint");
-            println!();
-            println!("🎯 Start planning:");
-            println!("   env-coach plan-sprint --goal \"Sprint objective\"  # Plan new sprint");
-            println!("   env-coach list-backlog                           # View available tasks");
+    }
+
+    fn create_project_with_active_sprint() -> Project {
+        let mut project = Project::new("CloseSprint".to_string(), "Desc".to_string(), None);
+        for (id, effort, status) in [("US-001", 5, Status::Done), ("US-002", 3, Status::InProgress), ("US-003", 2, Status::Todo)] {
+            let mut item = create_sample_backlog_item(id, id, "Story", Priority::High, effort, status);
+            item.sprint = Some("S-001".to_string());
+            project.backlog.push(item);
         }
+        project.sprints.push(create_sample_sprint("S-001", SprintStatus::Active, &["US-001", "US-002", "US-003"], 10));
+        project.current_sprint = Some("S-001".to_string());
+        project
     }
-    
-    Ok(())
-}
\ No newline at end of file
+
+    #[test]
+    fn test_close_sprint_returns_unfinished_items_to_backlog() {
+        let mut project = create_project_with_active_sprint();
+        let closure = close_sprint(&mut project, "S-001", None).unwrap();
+
+        assert_eq!(closure.sprint.status, SprintStatus::Completed);
+        assert_eq!(closure.sprint.completed_points, 5);
+        assert_eq!(closure.sprint.actual_velocity, 5);
+        assert_eq!(closure.sprint.planned_velocity as u32, closure.sprint.total_points);
+        assert_eq!(closure.done_items.len(), 1);
+        assert_eq!(closure.carried_items.len(), 2);
+        assert!(project.current_sprint.is_none());
+
+        let us001 = project.backlog.iter().find(|i| i.id == "US-001").unwrap();
+        assert_eq!(us001.sprint.as_deref(), Some("S-001")); // Done items stay with their sprint
+        let us002 = project.backlog.iter().find(|i| i.id == "US-002").unwrap();
+        assert!(us002.sprint.is_none());
+
+        let summary = format_sprint_summary(&closure);
+        assert!(summary.contains("# Sprint S-001 Summary"));
+        assert!(summary.contains("returned to backlog"));
+        assert!(summary.contains("- US-003 - US-003 [2pts, Todo]"));
+    }
+
+    #[test]
+    fn test_unready_items_uses_project_definition_of_ready() {
+        let mut project = create_project_with_active_sprint();
+        let mut todo = create_sample_backlog_item("US-010", "New", "Story", Priority::Low, 2, Status::Todo);
+        todo.acceptance_criteria = vec![];
+        project.backlog.push(todo);
+
+        let unready = unready_items(&project);
+        assert_eq!(unready.len(), 1);
+        assert_eq!(unready[0].0, "US-010");
+        assert_eq!(unready[0].1, vec!["no acceptance criteria"]);
+
+        project.meta.definition_of_ready = Some(crate::config::DefinitionOfReady {
+            require_acceptance_criteria: false,
+            ..Default::default()
+        });
+        assert!(unready_items(&project).is_empty());
+    }
+
+    #[test]
+    fn test_split_item_closes_done_part_and_carries_remaining_work() {
+        let mut project = create_project_with_active_sprint();
+        project.sprints.push(create_sample_sprint("S-002", SprintStatus::Planning, &[], 0));
+        let split = LlmStorySplit {
+            done_acceptance_criteria: vec!["Login works".to_string()],
+            done_effort: 2,
+            remaining_title: "Password reset".to_string(),
+            remaining_story: "As a user, I want to reset my password".to_string(),
+            remaining_acceptance_criteria: vec!["Reset email is sent".to_string()],
+            remaining_effort: 2,
+        };
+
+        let new_id = split_item(&mut project, "US-002", split).unwrap();
+        assert_eq!(new_id, "US-004");
+
+        let closure = close_sprint(&mut project, "S-001", Some("S-002")).unwrap();
+        assert_eq!(closure.sprint.completed_points, 7);
+        let us002 = project.backlog.iter().find(|i| i.id == "US-002").unwrap();
+        assert_eq!(us002.status, Status::Done);
+        assert_eq!(us002.effort, 2);
+        assert_eq!(us002.acceptance_criteria, vec!["Login works"]);
+        assert_eq!(us002.sprint.as_deref(), Some("S-001"));
+
+        let next = project.sprints.iter().find(|s| s.id == "S-002").unwrap();
+        assert_eq!(next.stories, vec!["US-003", "US-004"]);
+        assert_eq!(next.total_points, 4);
+    }
+
+    #[test]
+    fn test_split_item_skips_archived_ids() {
+        let mut project = create_project_with_active_sprint();
+        crate::scripts::archive::archive_item(&mut project, "US-003").unwrap();
+        let split = LlmStorySplit {
+            done_acceptance_criteria: vec![],
+            done_effort: 1,
+            remaining_title: "Rest".to_string(),
+            remaining_story: "As a user, I want the rest".to_string(),
+            remaining_acceptance_criteria: vec![],
+            remaining_effort: 1,
+        };
+        assert_eq!(split_item(&mut project, "US-002", split).unwrap(), "US-004");
+    }
+
+    #[test]
+    fn test_split_item_rejects_zero_point_parts() {
+        let mut project = create_project_with_active_sprint();
+        let split = LlmStorySplit {
+            done_acceptance_criteria: vec![],
+            done_effort: 0,
+            remaining_title: "Rest".to_string(),
+            remaining_story: "Story".to_string(),
+            remaining_acceptance_criteria: vec![],
+            remaining_effort: 3,
+        };
+        assert!(split_item(&mut project, "US-002", split).is_err());
+        assert_eq!(project.backlog.len(), 3);
+    }
+
+    #[test]
+    fn test_close_sprint_carries_over_into_next_sprint() {
+        let mut project = create_project_with_active_sprint();
+        project.sprints.push(create_sample_sprint("S-002", SprintStatus::Planning, &[], 0));
+
+        let closure = close_sprint(&mut project, "S-001", Some("S-002")).unwrap();
+        assert_eq!(closure.carry_over.as_deref(), Some("S-002"));
+
+        let next = project.sprints.iter().find(|s| s.id == "S-002").unwrap();
+        assert_eq!(next.stories, vec!["US-002", "US-003"]);
+        assert_eq!(next.total_points, 5);
+        let us003 = project.backlog.iter().find(|i| i.id == "US-003").unwrap();
+        assert_eq!(us003.sprint.as_deref(), Some("S-002"));
+    }
+
+    #[test]
+    fn test_close_sprint_rejects_invalid_requests() {
+        let mut project = create_project_with_active_sprint();
+        assert!(close_sprint(&mut project, "S-999", None).is_err());
+        assert!(close_sprint(&mut project, "S-001", Some("S-001")).is_err());
+        assert!(close_sprint(&mut project, "S-001", Some("S-404")).is_err());
+
+        close_sprint(&mut project, "S-001", None).unwrap();
+        assert!(close_sprint(&mut project, "S-001", None).is_err(), "Completing twice should fail");
+    }
+
+    #[test]
+    fn test_add_and_remove_item_updates_scope() {
+        let mut project = create_project_with_active_sprint();
+        project.backlog.push(create_sample_backlog_item("US-004", "Extra", "Story", Priority::Low, 4, Status::Todo));
+
+        add_item_to_sprint(&mut project, "S-001", "US-004").unwrap();
+        assert_eq!(project.sprints[0].total_points, 14);
+        assert!(project.sprints[0].stories.contains(&"US-004".to_string()));
+        assert_eq!(project.backlog[3].sprint.as_deref(), Some("S-001"));
+        assert!(add_item_to_sprint(&mut project, "S-001", "US-004").is_err(), "Adding twice should fail");
+
+        remove_item_from_sprint(&mut project, "S-001", "US-001").unwrap();
+        assert_eq!(project.sprints[0].total_points, 9);
+        assert!(!project.sprints[0].stories.contains(&"US-001".to_string()));
+        assert_eq!(project.backlog[0].sprint, None);
+        assert!(remove_item_from_sprint(&mut project, "S-001", "US-001").is_err(), "Removing twice should fail");
+    }
+
+    #[test]
+    fn test_add_item_rejects_completed_sprint() {
+        let mut project = create_project_with_active_sprint();
+        project.backlog.push(create_sample_backlog_item("US-004", "Extra", "Story", Priority::Low, 4, Status::Todo));
+        close_sprint(&mut project, "S-001", None).unwrap();
+        assert!(add_item_to_sprint(&mut project, "S-001", "US-004").is_err());
+    }
+
+    #[test]
+    fn test_find_expired_sprint_and_next_planned() {
+        let mut project = create_project_with_active_sprint();
+        let mut next = create_sample_sprint("S-002", SprintStatus::Planning, &[], 0);
+        next.start_date = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        project.sprints.push(next);
+
+        let before_end = Utc.with_ymd_and_hms(2024, 1, 10, 0, 0, 0).unwrap();
+        let after_end = Utc.with_ymd_and_hms(2024, 1, 20, 0, 0, 0).unwrap();
+        assert!(find_expired_sprint(&project, before_end).is_none());
+        assert_eq!(find_expired_sprint(&project, after_end).map(|s| s.id.as_str()), Some("S-001"));
+        assert_eq!(next_planned_sprint(&project, "S-001").map(|s| s.id.as_str()), Some("S-002"));
+    }
+
+    #[test]
+    fn test_parse_story_selection_accepts_numbers_and_ids() {
+        let items = [
+            create_sample_backlog_item("US-001", "Login", "Story", Priority::High, 3, Status::Todo),
+            create_sample_backlog_item("US-007", "Search", "Story", Priority::Low, 2, Status::Todo),
+        ];
+        let refs: Vec<&BacklogItem> = items.iter().collect();
+        assert_eq!(parse_story_selection("2, US-001", &refs), vec!["US-007", "US-001"]);
+        assert_eq!(parse_story_selection("0,3,US-404,,", &refs), Vec::<String>::new());
+    }
+}
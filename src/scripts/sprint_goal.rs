@@ -0,0 +1,159 @@
+// src/scripts/sprint_goal.rs
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::config::{BacklogItem, ItemType, LlmRole, Project, Status};
+use crate::context::AppContext;
+use crate::ollama;
+use crate::prompts;
+use crate::scripts::risks::format_prd_for_prompt;
+use crate::templates::Templates;
+
+/// How many Todo items the LLM sees when proposing goals.
+const TOP_ITEMS: usize = 15;
+
+#[derive(Deserialize, Debug, PartialEq)]
+pub(crate) struct GoalSuggestion {
+    goal: String,
+    #[serde(default)]
+    rationale: String,
+    #[serde(default)]
+    story_ids: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LlmGoalResponse {
+    goals: Vec<GoalSuggestion>,
+}
+
+/// Todo work items, most urgent first.
+fn top_backlog_items(project: &Project) -> Vec<&BacklogItem> {
+    let mut items: Vec<&BacklogItem> = project.backlog.iter()
+        .filter(|item| matches!(item.status, Status::Todo) && !matches!(item.item_type, ItemType::Epic))
+        .collect();
+    items.sort_by_key(|item| item.priority.rank());
+    items.truncate(TOP_ITEMS);
+    items
+}
+
+fn build_goal_prompt(template: &str, project: &Project, days: u32) -> String {
+    let backlog = top_backlog_items(project).iter()
+        .map(|item| format!("- {} [{:?}, {} pts] {}", item.id, item.priority, item.effort, item.title))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let previous_goals = project.sprints.iter()
+        .map(|sprint| format!("- {}: {}", sprint.id, sprint.goal))
+        .collect::<Vec<_>>()
+        .join("\n");
+    template
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{prd}}", &format_prd_for_prompt(project))
+        .replace("{{previous_goals}}", if previous_goals.is_empty() { "None yet." } else { &previous_goals })
+        .replace("{{backlog}}", &backlog)
+        .replace("{{sprint_duration_days}}", &days.to_string())
+}
+
+pub(crate) fn parse_goal_response(response: &str) -> Result<Vec<GoalSuggestion>> {
+    let parsed: LlmGoalResponse = serde_json::from_str(response).with_context(|| {
+        let snippet = response.chars().take(500).collect::<String>();
+        format!("Failed to parse LLM sprint goal suggestions. Response (first 500 chars): '{}'", snippet)
+    })?;
+    Ok(parsed.goals.into_iter().filter(|g| !g.goal.trim().is_empty()).collect())
+}
+
+/// Turn the user's answer into a goal: a number picks a suggestion, anything else is
+/// taken as the goal itself. Empty input picks nothing.
+fn pick_goal(answer: &str, suggestions: &[GoalSuggestion]) -> Option<String> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return None;
+    }
+    match answer.parse::<usize>() {
+        Ok(n) => n.checked_sub(1).and_then(|i| suggestions.get(i)).map(|s| s.goal.clone()),
+        Err(_) => Some(answer.to_string()),
+    }
+}
+
+/// Ask the LLM for 2-3 candidate sprint goals and let the user pick, edit or replace one.
+pub async fn suggest_goal(ctx: &AppContext, days: u32) -> Result<String> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    if top_backlog_items(&project).is_empty() {
+        anyhow::bail!("The backlog has no 'Todo' items to build a sprint goal from. Add stories first, or pass --goal.");
+    }
+
+    let prompt_template_path = std::path::Path::new(".env-coach/prompts/sprint_goal.md");
+    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
+        Ok(template) => template,
+        Err(_) => {
+            println!("⚠️ Sprint goal prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_sprint_goal_prompt_content()
+        }
+    };
+    let prompt_template = prompts::fill_project_context(&prompt_template, &project, None);
+    let prompt = build_goal_prompt(&prompt_template, &project, days);
+
+    println!("🤖 Asking LLM for sprint goal suggestions...");
+    let response = ollama::send_generation_prompt(&project.llm_for(LlmRole::Planner), &prompt)
+        .await
+        .context("LLM call for sprint goal suggestions failed")?;
+    let suggestions = parse_goal_response(&response)?;
+
+    println!("\n🎯 Suggested sprint goals:");
+    for (i, suggestion) in suggestions.iter().enumerate() {
+        println!("   {}. {}", i + 1, suggestion.goal);
+        if !suggestion.rationale.is_empty() {
+            println!("      Why: {}", suggestion.rationale);
+        }
+        if !suggestion.story_ids.is_empty() {
+            println!("      Stories: {}", suggestion.story_ids.join(", "));
+        }
+    }
+
+    let goal = if ctx.prompter.assume_yes() {
+        let first = suggestions.first().map(|s| s.goal.clone());
+        println!("\n👉 Using suggestion 1 [--yes]");
+        first
+    } else {
+        let answer = ctx.prompter.ask("\n👉 Pick a goal by number, or type your own: ")?;
+        match pick_goal(&answer, &suggestions) {
+            Some(goal) => {
+                let edited = ctx.prompter.ask(&format!("✏️  Goal: {}\n   Press Enter to keep it, or type a new wording: ", goal))?;
+                Some(if edited.is_empty() { goal } else { edited })
+            }
+            None => None,
+        }
+    };
+    goal.ok_or_else(|| anyhow::anyhow!("No sprint goal chosen. Sprint planning aborted."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestions() -> Vec<GoalSuggestion> {
+        parse_goal_response(r#"{"goals": [
+            {"goal": "Users can log in", "rationale": "Blocks everything", "story_ids": ["US-001"]},
+            {"goal": "", "rationale": "dropped"},
+            {"goal": "Search works"}
+        ]}"#).unwrap()
+    }
+
+    #[test]
+    fn test_parse_goal_response_drops_empty_goals() {
+        let goals = suggestions();
+        assert_eq!(goals.len(), 2);
+        assert_eq!(goals[1].goal, "Search works");
+        assert!(goals[1].story_ids.is_empty());
+        assert!(parse_goal_response("[]").is_err());
+    }
+
+    #[test]
+    fn test_pick_goal_by_number_or_text() {
+        let goals = suggestions();
+        assert_eq!(pick_goal("2", &goals), Some("Search works".to_string()));
+        assert_eq!(pick_goal("Ship the export", &goals), Some("Ship the export".to_string()));
+        assert_eq!(pick_goal("9", &goals), None);
+        assert_eq!(pick_goal("  ", &goals), None);
+    }
+}
@@ -0,0 +1,232 @@
+// src/scripts/sprint_report.rs
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::path::PathBuf;
+
+use crate::config::{BacklogItem, Project, Sprint, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::links::Linker;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ReportFormat {
+    Md,
+    Html,
+}
+
+impl ReportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Md => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+/// Items delivered in the sprint (Done and still assigned to it), in sprint order.
+fn completed_items<'a>(project: &'a Project, sprint: &Sprint) -> Vec<&'a BacklogItem> {
+    sprint.stories.iter()
+        .filter_map(|id| project.backlog.iter().find(|item| &item.id == id))
+        .filter(|item| matches!(item.status, Status::Done) && item.sprint.as_ref() == Some(&sprint.id))
+        .collect()
+}
+
+/// Demo notes come from the sprint retrospective, when one was held.
+fn demo_notes(sprint: &Sprint) -> Vec<String> {
+    let Some(retro) = &sprint.retrospective else { return vec![] };
+    let mut notes = Vec::new();
+    if !retro.summary.is_empty() {
+        notes.push(retro.summary.clone());
+    }
+    if !retro.highlights.is_empty() {
+        notes.push(retro.highlights.clone());
+    }
+    notes.extend(retro.went_well.iter().cloned());
+    notes
+}
+
+fn render_markdown(project: &Project, sprint: &Sprint, linker: &Linker) -> String {
+    let items = completed_items(project, sprint);
+    let mut out = format!("# {} — Sprint {} Review\n\n", project.meta.name, sprint.id);
+    out.push_str(&format!("- **Goal:** {}\n", sprint.goal));
+    out.push_str(&format!("- **Dates:** {} to {}\n", sprint.start_date.format("%Y-%m-%d"), sprint.end_date.format("%Y-%m-%d")));
+    out.push_str(&format!("- **Points:** {} completed of {} planned\n", sprint.completed_points, sprint.total_points));
+
+    out.push_str(&format!("\n## Completed Stories ({})\n", items.len()));
+    if items.is_empty() {
+        out.push_str("\nNo stories were completed in this sprint.\n");
+    }
+    for item in items {
+        out.push_str(&format!("\n### {} — {} ({} pts)\n\n{}\n", item.id, item.title, item.effort, item.story));
+        if let Some(external_ref) = &item.external_ref {
+            match linker.url(external_ref) {
+                Some(url) => out.push_str(&format!("\nTracked in [{}]({})\n", external_ref, url)),
+                None => out.push_str(&format!("\nTracked in {}\n", external_ref)),
+            }
+        }
+        if !item.acceptance_criteria.is_empty() {
+            out.push('\n');
+            for criterion in &item.acceptance_criteria {
+                out.push_str(&format!("- [x] {}\n", criterion));
+            }
+        }
+    }
+
+    let notes = demo_notes(sprint);
+    if !notes.is_empty() {
+        out.push_str("\n## Demo Notes\n\n");
+        for note in notes {
+            out.push_str(&format!("- {}\n", note));
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_html(project: &Project, sprint: &Sprint, linker: &Linker) -> String {
+    let items = completed_items(project, sprint);
+    let title = format!("{} — Sprint {} Review", escape_html(&project.meta.name), escape_html(&sprint.id));
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n<ul>\n",
+        title, title
+    );
+    out.push_str(&format!("<li><strong>Goal:</strong> {}</li>\n", escape_html(&sprint.goal)));
+    out.push_str(&format!("<li><strong>Dates:</strong> {} to {}</li>\n", sprint.start_date.format("%Y-%m-%d"), sprint.end_date.format("%Y-%m-%d")));
+    out.push_str(&format!("<li><strong>Points:</strong> {} completed of {} planned</li>\n</ul>\n", sprint.completed_points, sprint.total_points));
+
+    out.push_str(&format!("<h2>Completed Stories ({})</h2>\n", items.len()));
+    if items.is_empty() {
+        out.push_str("<p>No stories were completed in this sprint.</p>\n");
+    }
+    for item in items {
+        out.push_str(&format!(
+            "<h3>{} — {} ({} pts)</h3>\n<p>{}</p>\n",
+            escape_html(&item.id), escape_html(&item.title), item.effort, escape_html(&item.story)
+        ));
+        if let Some(external_ref) = &item.external_ref {
+            match linker.url(external_ref) {
+                Some(url) => out.push_str(&format!("<p>Tracked in <a href=\"{}\">{}</a></p>\n", escape_html(&url), escape_html(external_ref))),
+                None => out.push_str(&format!("<p>Tracked in {}</p>\n", escape_html(external_ref))),
+            }
+        }
+        if !item.acceptance_criteria.is_empty() {
+            out.push_str("<ul>\n");
+            for criterion in &item.acceptance_criteria {
+                out.push_str(&format!("<li>✅ {}</li>\n", escape_html(criterion)));
+            }
+            out.push_str("</ul>\n");
+        }
+    }
+
+    let notes = demo_notes(sprint);
+    if !notes.is_empty() {
+        out.push_str("<h2>Demo Notes</h2>\n<ul>\n");
+        for note in notes {
+            out.push_str(&format!("<li>{}</li>\n", escape_html(&note)));
+        }
+        out.push_str("</ul>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Write the report to `--output`, or to .env-coach/reports/<id>-review.<ext>.
+pub fn run(ctx: &AppContext, sprint_id: String, format: ReportFormat) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    let sprint = project.sprints.iter()
+        .find(|s| s.id == sprint_id)
+        .ok_or_else(|| anyhow::anyhow!("Sprint '{}' not found", sprint_id))?;
+
+    let linker = Linker::new(&project, &ctx.global_config);
+    let report = match format {
+        ReportFormat::Md => render_markdown(&project, sprint, &linker),
+        ReportFormat::Html => render_html(&project, sprint, &linker),
+    };
+
+    let report_path = ctx.output.path().map(PathBuf::from).unwrap_or_else(|| {
+        PathBuf::from(".env-coach/reports").join(format!("{}-review.{}", sprint.id, format.extension()))
+    });
+    if ctx.dry_run {
+        println!("🔍 Dry run: {} not written", report_path.display());
+        println!("{}", report);
+        return Ok(());
+    }
+    if let Some(parent) = report_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+    }
+    std::fs::write(&report_path, report)
+        .with_context(|| format!("Failed to write sprint report to {:?}", report_path))?;
+
+    println!("📄 Sprint review report for {} written to {}", sprint.id, report_path.display());
+    hints::print_next_steps(Some(&project), &[
+        (format!("env-coach retro {}", sprint.id).as_str(), "Add demo notes via a retrospective"),
+    ]);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Priority, Retrospective, SprintStatus};
+    use chrono::Utc;
+
+    fn project_with_sprint() -> Project {
+        let mut project = Project::new("Shop".to_string(), "".to_string(), None);
+        for (id, status) in [("US-001", Status::Done), ("US-002", Status::InProgress)] {
+            project.backlog.push(BacklogItem::test(id)
+                .with_title(&format!("Checkout <{}>", id))
+                .with_story("As a buyer I want to pay")
+                .with_criteria(&["Card payments work"])
+                .with_priority(Priority::High)
+                .with_status(status)
+                .with_sprint("S-001"));
+        }
+        project.sprints.push(Sprint {
+            id: "S-001".to_string(),
+            goal: "Payments".to_string(),
+            start_date: Utc::now(),
+            end_date: Utc::now(),
+            status: SprintStatus::Completed,
+            total_points: 6,
+            completed_points: 3,
+            tasks: vec![],
+            stories: vec!["US-001".to_string(), "US-002".to_string()],
+            planned_velocity: 6,
+            actual_velocity: 3,
+            retrospective: Some(Retrospective { summary: "Payments demoed to finance.".to_string(), ..Default::default() }),
+        });
+        project
+    }
+
+    #[test]
+    fn test_render_markdown_lists_only_completed_stories() {
+        let mut project = project_with_sprint();
+        project.backlog[0].external_ref = Some("https://tracker.example/t/1".to_string());
+        let report = render_markdown(&project, &project.sprints[0], &Linker::default());
+        assert!(report.contains("## Completed Stories (1)"));
+        assert!(report.contains("### US-001"));
+        assert!(!report.contains("US-002"));
+        assert!(report.contains("- [x] Card payments work"));
+        assert!(report.contains("## Demo Notes\n\n- Payments demoed to finance."));
+        assert!(report.contains("3 completed of 6 planned"));
+        assert!(report.contains("Tracked in [https://tracker.example/t/1](https://tracker.example/t/1)"));
+    }
+
+    #[test]
+    fn test_render_html_escapes_content() {
+        let project = project_with_sprint();
+        let report = render_html(&project, &project.sprints[0], &Linker::default());
+        assert!(report.starts_with("<!DOCTYPE html>"));
+        assert!(report.contains("Checkout &lt;US-001&gt;"));
+        assert!(!report.contains("<US-001>"));
+        assert!(report.trim_end().ends_with("</html>"));
+    }
+}
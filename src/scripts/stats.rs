@@ -0,0 +1,57 @@
+// src/scripts/stats.rs
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::context::AppContext;
+use crate::stats::{self, StatusCounts, Trend};
+
+fn print_row(label: &str, items: &StatusCounts, points: &StatusCounts, pick: fn(&StatusCounts) -> u32) {
+    println!("   {:<16} {:>4} items {:>5} pts", label, pick(items), pick(points));
+}
+
+/// Print the derived backlog, sprint and velocity figures; with `--format json|yaml` this is
+/// the data other tools should report from.
+pub fn run(ctx: &AppContext) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let stats = stats::project_stats(&project, Utc::now());
+    if ctx.format.is_structured() {
+        return ctx.print_structured(&stats);
+    }
+
+    let backlog = &stats.backlog;
+    println!("📈 Statistics: {}", project.meta.name);
+    println!();
+    println!("📋 Backlog: {} items, {} points ({}% done)",
+        backlog.items.total, backlog.points.total, backlog.completion_percent.unwrap_or(0));
+    print_row("⏳ To Do", &backlog.items, &backlog.points, |c| c.todo);
+    print_row("🚧 In Progress", &backlog.items, &backlog.points, |c| c.in_progress);
+    print_row("👀 In Review", &backlog.items, &backlog.points, |c| c.review);
+    print_row("✅ Done", &backlog.items, &backlog.points, |c| c.done);
+    println!("   🎯 Epics: {} | 📦 Archived: {}", stats.epics, stats.archived_items);
+
+    println!();
+    println!("🏃 Sprints: {} ({} planning, {} active, {} completed)",
+        stats.sprints.total, stats.sprints.planning, stats.sprints.active, stats.sprints.completed);
+    if let Some(sprint) = &stats.active_sprint {
+        println!("   📌 {}: {} / {} points ({}%), day {} of {}",
+            sprint.id, sprint.completed_points, sprint.committed_points, sprint.completion_percent, sprint.days_elapsed, sprint.days_total);
+        println!("      {} items: {} to do, {} in progress, {} in review, {} done",
+            sprint.work.items.total, sprint.work.items.todo, sprint.work.items.in_progress, sprint.work.items.review, sprint.work.items.done);
+    }
+
+    println!();
+    match stats.velocity.average {
+        Some(average) => {
+            let trend = match stats.velocity.trend {
+                Some(Trend::Rising) => "rising",
+                Some(Trend::Falling) => "falling",
+                Some(Trend::Stable) => "stable",
+                None => "n/a",
+            };
+            println!("🚀 Velocity: {:.1} points per sprint over the last {} sprint(s), last {}, trend {}",
+                average, stats.velocity.sprints, stats.velocity.last.unwrap_or(0), trend);
+        }
+        None => println!("🚀 Velocity: no completed sprints yet"),
+    }
+    Ok(())
+}
@@ -1,9 +1,107 @@
 // src/scripts/status.rs
 use anyhow::{Context, Result};
-use crate::config::{Project, Status, SprintStatus};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::config::{Project, Sprint, SprintStatus};
+use crate::context::AppContext;
+use crate::hints::{self, Hint};
+use crate::scripts::epics;
+use crate::stats::{self, EpicProgress, SprintCounts, StatusCounts, WorkStats};
+use crate::output;
 use crate::ollama;
 
-pub async fn run() -> Result<()> {  // Make async
+/// Suggestions shown in the status; `env-coach coach` lists them all.
+const SUGGESTED_ACTIONS: usize = 3;
+
+#[derive(Serialize)]
+struct LlmStatus<'a> {
+    model: &'a str,
+    host: &'a str,
+    port: u16,
+    timeout_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context_tokens: Option<u32>,
+    base_url: String,
+    connected: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct EpicStatus<'a> {
+    id: &'a str,
+    title: &'a str,
+    #[serde(flatten)]
+    progress: EpicProgress,
+}
+
+#[derive(Serialize)]
+struct SprintSummary<'a> {
+    #[serde(flatten)]
+    counts: SprintCounts,
+    current: Option<&'a Sprint>,
+}
+
+#[derive(Serialize)]
+struct RiskCounts {
+    total: usize,
+    high: usize,
+    assessed: DateTime<Utc>,
+}
+
+/// `--format json|yaml` output of `status`.
+#[derive(Serialize)]
+struct StatusReport<'a> {
+    name: &'a str,
+    description: &'a str,
+    tech_stack: &'a [String],
+    llm: LlmStatus<'a>,
+    /// Item counts per status, epics excluded
+    backlog: StatusCounts,
+    epics: Vec<EpicStatus<'a>>,
+    sprints: SprintSummary<'a>,
+    risks: Option<RiskCounts>,
+    suggestions: Vec<Hint>,
+}
+
+async fn status_report(project: &Project) -> StatusReport<'_> {
+    let llm = project.llm();
+    let connection = ollama::ping(llm).await;
+    StatusReport {
+        name: &project.meta.name,
+        description: &project.meta.description,
+        tech_stack: &project.meta.tech_stack,
+        llm: LlmStatus {
+            model: &llm.model,
+            host: &llm.host,
+            port: llm.port,
+            timeout_ms: llm.timeout_ms,
+            temperature: llm.temperature,
+            context_tokens: llm.context_tokens,
+            base_url: llm.base_url(),
+            connected: connection.is_ok(),
+            error: connection.err().map(|e| e.to_string()),
+        },
+        backlog: WorkStats::of(&project.backlog).items,
+        epics: epics::epics(project)
+            .map(|epic| EpicStatus { id: &epic.id, title: &epic.title, progress: stats::epic_progress(project, &epic.id) })
+            .collect(),
+        sprints: SprintSummary {
+            counts: stats::sprint_counts(project),
+            current: project.sprints.iter().find(|s| matches!(s.status, SprintStatus::Active)),
+        },
+        risks: project.meta.risks.as_ref().map(|register| RiskCounts {
+            total: register.risks.len(),
+            high: register.risks.iter().filter(|risk| risk.is_high()).count(),
+            assessed: register.generated,
+        }),
+        suggestions: hints::collect(project, Utc::now()),
+    }
+}
+
+pub async fn run(ctx: &AppContext) -> Result<()> {  // Make async
     // Check if project is initialized
     if !Project::is_initialized() {
         println!("❌ No env-coach project found");
@@ -13,7 +111,13 @@ pub async fn run() -> Result<()> {  // Make async
         return Ok(());
     }
 
-    let project = Project::load()
+    if ctx.format.is_structured() {
+        let project = ctx.load_project().context("Failed to load project configuration")?;
+        return ctx.print_structured(&status_report(&project).await);
+    }
+
+    crate::scripts::sprint::check_sprint_expiry(ctx)?;
+    let project = ctx.load_project()
         .context("Failed to load project configuration")?;
 
     println!("📋 Project Status: {}", project.meta.name);
@@ -23,83 +127,36 @@ pub async fn run() -> Result<()> {  // Make async
     println!();
 
     // Test LLM connectivity
-    // Load global config to show sources
-    let global_config = crate::config::GlobalConfig::load().unwrap_or_default(); // Handle error better if needed
-
     println!("🤖 LLM Configuration (resolved):");
     let resolved_llm = project.llm();
-    let project_llm_override = project.meta.llm.as_ref();
-    let global_llm_settings = global_config.llm.as_ref();
-
-    // Helper to determine source
-    fn get_source_info(
-        p_val: Option<&str>, g_val: Option<&str>, def_val: &str, actual_val: &str,
-        p_source_name: &str, g_source_name: &str, def_source_name: &str
-    ) -> String {
-        if p_val.map_or(false, |v| v == actual_val) {
-            p_source_name.to_string()
-        } else if g_val.map_or(false, |v| v == actual_val) {
-            g_source_name.to_string()
-        } else if actual_val == def_val {
-            def_source_name.to_string()
-        } else {
-            "Unknown".to_string() // Should not happen if logic is correct
-        }
+    let sources = ctx.llm_setting_sources(Some(&project), resolved_llm);
+    if let Some(preset) = ctx.active_preset(Some(&project)) {
+        println!("   Preset:     {}", preset);
     }
-    
-    // Using a more specific default check for numeric types like port/timeout
-    fn get_source_info_numeric<T: PartialEq + std::fmt::Display>(
-        p_opt_val: Option<T>, g_opt_val: Option<T>, def_val: T, actual_val: &T,
-        p_source_name: &str, g_source_name: &str, def_source_name: &str
-    ) -> String {
-        if p_opt_val.as_ref().map_or(false, |v| v == actual_val) {
-            p_source_name.to_string()
-        } else if g_opt_val.as_ref().map_or(false, |v| v == actual_val) {
-            g_source_name.to_string()
-        } else if *actual_val == def_val {
-            def_source_name.to_string()
-        } else {
-            "Unknown".to_string() // Should ideally not be reached
-        }
+    let source = |key: &str| sources.iter().find(|setting| setting.key == key).map(|setting| setting.source).unwrap_or_default();
+    println!("   Model:      {} (Source: {})", resolved_llm.model, source("model"));
+    println!("   Host:       {} (Source: {})", resolved_llm.host, source("host"));
+    println!("   Port:       {} (Source: {})", resolved_llm.port, source("port"));
+    println!("   Timeout:    {}ms (Source: {})", resolved_llm.timeout_ms, source("timeout_ms"));
+    for (role, model) in &resolved_llm.roles {
+        println!("   {:<11} {} (Source: {})", format!("{:?}:", role), model, source(role.model_key()));
+    }
+    if let Some(temperature) = resolved_llm.temperature {
+        println!("   Temperature: {}", temperature);
+    }
+    if let Some(context_tokens) = resolved_llm.context_tokens {
+        println!("   Context:    {} tokens", context_tokens);
     }
-
-
-    let model_source = get_source_info(
-        project_llm_override.and_then(|p| p.model.as_deref()),
-        global_llm_settings.and_then(|g| g.model.as_deref()),
-        crate::config::DEFAULT_LLM_MODEL,
-        &resolved_llm.model,
-        "Project (project.json)", "Global (~/.config/env-coach/config.json)", "Default"
-    );
-    println!("   Model:      {} (Source: {})", resolved_llm.model, model_source);
-
-    let host_source = get_source_info(
-        project_llm_override.and_then(|p| p.host.as_deref()),
-        global_llm_settings.and_then(|g| g.host.as_deref()),
-        crate::config::DEFAULT_LLM_HOST,
-        &resolved_llm.host,
-        "Project (project.json)", "Global (~/.config/env-coach/config.json)", "Default"
-    );
-    println!("   Host:       {} (Source: {})", resolved_llm.host, host_source);
-
-    let port_source = get_source_info_numeric(
-        project_llm_override.and_then(|p| p.port),
-        global_llm_settings.and_then(|g| g.port),
-        crate::config::DEFAULT_LLM_PORT,
-        &resolved_llm.port,
-        "Project (project.json)", "Global (~/.config/env-coach/config.json)", "Default"
-    );
-    println!("   Port:       {} (Source: {})", resolved_llm.port, port_source);
-
-    let timeout_source = get_source_info_numeric(
-        project_llm_override.and_then(|p| p.timeout_ms),
-        global_llm_settings.and_then(|g| g.timeout_ms),
-        crate::config::DEFAULT_LLM_TIMEOUT_MS,
-        &resolved_llm.timeout_ms,
-        "Project (project.json)", "Global (~/.config/env-coach/config.json)", "Default"
-    );
-    println!("   Timeout:    {}ms (Source: {})", resolved_llm.timeout_ms, timeout_source);
     println!("   Base URL:   {}", resolved_llm.base_url());
+    if let Some(proxy) = &resolved_llm.proxy {
+        println!("   Proxy:      {} (Source: {})", crate::config::redact_credentials(proxy), source("proxy"));
+    }
+    if let Some(ca_bundle) = &resolved_llm.ca_bundle {
+        println!("   CA bundle:  {}", ca_bundle);
+    }
+    if resolved_llm.danger_accept_invalid_certs {
+        println!("   ⚠️  TLS certificates are not checked (llm.danger_accept_invalid_certs)");
+    }
     println!("   Tags:       {}", project.get_tags_display()); // Tags are not part of LLM config sources
 
     match ollama::check_status(resolved_llm).await { // Pass resolved_llm explicitly
@@ -125,31 +182,31 @@ pub async fn run() -> Result<()> {  // Make async
 
     // Show backlog summary
     println!("📋 Backlog Summary:");
-    let total_items = project.backlog.len();
-    let todo_items = project.backlog.iter().filter(|item| matches!(item.status, Status::Todo)).count();
-    let in_progress_items = project.backlog.iter().filter(|item| matches!(item.status, Status::InProgress)).count();
-    let review_items = project.backlog.iter().filter(|item| matches!(item.status, Status::Review)).count();
-    let done_items = project.backlog.iter().filter(|item| matches!(item.status, Status::Done)).count();
+    let project_stats = stats::project_stats(&project, Utc::now());
+    let items = project_stats.backlog.items;
 
-    if total_items == 0 {
+    if project.backlog.is_empty() {
         println!("   No items in backlog");
         println!("💡 Add requirements to get started:");
         println!("   env-coach add-requirement \"I want to build...\"");
     } else {
-        println!("   Total items: {}", total_items);
-        println!("   📋 To Do: {}", todo_items);
-        println!("   🚧 In Progress: {}", in_progress_items);
-        println!("   👀 In Review: {}", review_items);
-        println!("   ✅ Done: {}", done_items);
-        
-        // Calculate completion percentage
-        if total_items > 0 {
-            let completion_percent = (done_items * 100) / total_items;
-            println!("   📊 Completion: {}%", completion_percent);
+        println!("   Total items: {}", items.total);
+        println!("   📋 To Do: {}", items.todo);
+        println!("   🚧 In Progress: {}", items.in_progress);
+        println!("   👀 In Review: {}", items.review);
+        println!("   ✅ Done: {}", items.done);
+        if let Some(completion_percent) = project_stats.backlog.completion_percent {
+            println!("   📊 Completion: {}% of {} points", completion_percent, project_stats.backlog.points.total);
         }
     }
     println!();
 
+    if crate::scripts::epics::epics(&project).next().is_some() {
+        println!("🎯 Epics:");
+        crate::scripts::epics::print_epic_rollup(&project);
+        println!();
+    }
+
     // Show sprint information
     println!("🏃 Sprint Information:");
     if project.sprints.is_empty() {
@@ -157,47 +214,46 @@ pub async fn run() -> Result<()> {  // Make async
         println!("💡 Plan your first sprint:");
         println!("   env-coach plan-sprint --goal \"Sprint objective\"");
     } else {
-        let active_sprints = project.sprints.iter().filter(|s| matches!(s.status, SprintStatus::Active)).count();
-        let completed_sprints = project.sprints.iter().filter(|s| matches!(s.status, SprintStatus::Completed | SprintStatus::Complete)).count();
-        
-        println!("   Total sprints: {}", project.sprints.len());
-        println!("   🏃 Active: {}", active_sprints);
-        println!("   ✅ Completed: {}", completed_sprints);
+        println!("   Total sprints: {}", project_stats.sprints.total);
+        println!("   🏃 Active: {}", project_stats.sprints.active);
+        println!("   ✅ Completed: {}", project_stats.sprints.completed);
 
         // Show current sprint details
-        if let Some(active_sprint) = project.sprints.iter().find(|s| matches!(s.status, SprintStatus::Active)) {
+        if let Some(active_sprint) = &project_stats.active_sprint {
             println!();
             println!("📌 Current Sprint: {}", active_sprint.id);
             println!("   Goal: {}", active_sprint.goal);
-            println!("   Progress: {} / {} points", active_sprint.completed_points, active_sprint.total_points);
-            let sprint_progress = if active_sprint.total_points > 0 {
-                (active_sprint.completed_points * 100) / active_sprint.total_points
-            } else {
-                0
-            };
-            println!("   📊 Sprint Completion: {}%", sprint_progress);
+            println!("   Progress: {} / {} points", active_sprint.completed_points, active_sprint.committed_points);
+            println!("   📊 Sprint Completion: {}%", active_sprint.completion_percent);
         }
     }
     println!();
 
-    // Show next suggested actions
-    println!("🎯 Suggested Next Actions:");
-    if project.backlog.is_empty() {
-        println!("   1. env-coach add-requirement \"...\"     # Add your first requirement");
-    } else if project.sprints.is_empty() {
-        println!("   1. env-coach plan-sprint --goal \"...\"  # Plan your first sprint");
-    } else if todo_items > 0 {
-        if let Some(next_task) = project.backlog.iter().find(|item| matches!(item.status, Status::Todo)) {
-            println!("   1. env-coach start-task {}           # Start next task", next_task.id);
+    // Surface high risks from the last `env-coach risks` run
+    if let Some(register) = &project.meta.risks {
+        let high_risks: Vec<_> = crate::scripts::risks::sorted_risks(register)
+            .into_iter()
+            .filter(|risk| risk.is_high())
+            .collect();
+        println!("⚠️  Risks: {} recorded, {} high (assessed {})",
+            register.risks.len(), high_risks.len(), register.generated.format("%Y-%m-%d"));
+        for risk in high_risks {
+            println!("   🔴 [{:?}] {}", risk.category, risk.description);
         }
-    } else if in_progress_items > 0 {
-        if let Some(current_task) = project.backlog.iter().find(|item| matches!(item.status, Status::InProgress)) {
-            println!("   1. env-coach assist-task {}          # Get help with current task", current_task.id);
-            println!("   2. env-coach complete-task {}        # Mark task as done", current_task.id);
+        println!();
+    }
+
+    // Show next suggested actions
+    if !output::show_hints() {
+        return Ok(());
+    }
+    let suggestions = hints::collect(&project, Utc::now());
+    if !suggestions.is_empty() {
+        println!("🎯 Suggested Next Actions:");
+        hints::print_ranked(&suggestions[..suggestions.len().min(SUGGESTED_ACTIONS)]);
+        if suggestions.len() > SUGGESTED_ACTIONS {
+            println!("   ... run 'env-coach coach' for all {} suggestions", suggestions.len());
         }
-    } else {
-        println!("   1. env-coach add-requirement \"...\"     # Add more requirements");
-        println!("   2. env-coach plan-sprint --goal \"...\"  # Plan next sprint");
     }
 
     Ok(())
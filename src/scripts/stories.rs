@@ -1,24 +1,46 @@
 // src/scripts/stories.rs
-use crate::config::{Project, BacklogItem, ItemType, Priority, Status};
+use crate::config::{BacklogItem, ItemType, Priority, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::output;
+use crate::scripts::backlog::Listing;
+use crate::scripts::filter::BacklogFilter;
+use crate::scripts::import;
 use chrono::Utc;
 
-pub fn add_manual_story(title: String, description: String) -> anyhow::Result<()> {
+pub fn add_manual_story(ctx: &AppContext, title: String, description: String, epic: Option<String>) -> anyhow::Result<()> {
     println!("📝 Adding user story manually...");
     
-    let mut project = Project::load()?;
+    let mut project = ctx.load_project()?;
+    if let Some(epic) = &epic {
+        crate::scripts::epics::require_epic(&project, epic)?;
+    }
     
-    // Generate sequential ID
-    let story_count = project.backlog
-        .iter()
-        .filter(|item| matches!(item.item_type, ItemType::UserStory))
-        .count();
-    let story_id = format!("US-{:03}", story_count + 1);
+    let story = new_story(&project, title.clone(), description, epic);
+    let story_id = story.id.clone();
+    
+    project.backlog.push(story);
+    ctx.save_project(&project)?;
     
-    // Create the backlog item
-    let story = BacklogItem {
-        id: story_id.clone(),
+    println!("✅ Added story {}: {}", story_id, title);
+    if output::show_hints() {
+        println!("💡 Refine acceptance criteria and effort with: env-coach edit-item {} --editor", story_id);
+    }
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach list-backlog", "View updated backlog"),
+        ("env-coach plan-sprint --goal \"...\"", "Plan development sprint"),
+    ]);
+    
+    Ok(())
+}
+
+/// A new Todo story with placeholder criteria and the next free `US-NNN` ID (archived items
+/// included, so an ID is never handed out twice).
+fn new_story(project: &Project, title: String, description: String, epic: Option<String>) -> BacklogItem {
+    BacklogItem {
+        id: import::next_id(project, import::id_prefix(&ItemType::UserStory)),
         item_type: ItemType::UserStory,
-        title: title.clone(),
+        title,
         story: if description.starts_with("As a") || description.starts_with("As an") {
             description
         } else {
@@ -35,40 +57,52 @@ pub fn add_manual_story(title: String, description: String) -> anyhow::Result<()
         created: Utc::now(),
         sprint: None,
         dependencies: Vec::new(),
-    };
-    
-    project.backlog.push(story);
-    project.save()?;
-    
-    println!("✅ Added story {}: {}", story_id, title);
-    println!("💡 Edit project.json to refine acceptance criteria and effort estimate");
-    println!();
-    println!("🎯 Next steps:");
-    println!("   env-coach list-backlog              # View updated backlog");
-    println!("   env-coach plan-sprint --goal \"...\"  # Plan development sprint");
-    
-    Ok(())
+        started_at: None,
+        completed_at: None,
+        component: None,
+        parent: epic,
+        tags: vec![],
+        external_ref: None,
+        history: vec![],
+        review: None,
+        criteria_check: None,
+        extra: Default::default(),
+    }
 }
 
-pub fn list_stories() -> anyhow::Result<()> {
-    let project = Project::load()?;
+pub fn list_stories(ctx: &AppContext, filter: &BacklogFilter) -> anyhow::Result<()> {
+    let project = ctx.load_project()?;
     
-    let stories: Vec<_> = project.backlog
+    let all_stories: Vec<_> = project.backlog
         .iter()
         .filter(|item| matches!(item.item_type, ItemType::UserStory))
         .collect();
+    let stories = filter.apply(all_stories.iter().copied());
+    if ctx.format.is_structured() {
+        return ctx.print_structured(&Listing { total: all_stories.len(), items: stories });
+    }
     
-    if stories.is_empty() {
+    if all_stories.is_empty() {
         println!("📖 No user stories found");
-        println!();
-        println!("🎯 Add stories:");
-        println!("   env-coach add-requirement \"I want to build...\"");
-        println!("   env-coach add-story --title \"Title\" --description \"Description\"");
+        if output::show_hints() {
+            println!();
+            println!("🎯 Add stories:");
+            println!("   env-coach add-requirement \"I want to build...\"");
+            println!("   env-coach add-story --title \"Title\" --description \"Description\"");
+        }
         return Ok(());
     }
     
-    println!("📖 User Stories ({} total):", stories.len());
+    if filter.is_empty() {
+        println!("📖 User Stories ({} total):", stories.len());
+    } else {
+        println!("📖 User Stories ({} of {} match):", stories.len(), all_stories.len());
+    }
     println!();
+    if stories.is_empty() {
+        println!("🔍 No stories match the given filters");
+        return Ok(());
+    }
     
     // Group by status - simple approach to avoid pattern matching complexity
     let in_progress_stories: Vec<_> = stories.iter().filter(|s| matches!(s.status, Status::InProgress)).collect();
@@ -128,4 +162,23 @@ fn print_story_detail(story: &BacklogItem) {
     if !story.dependencies.is_empty() {
         println!("     🔗 Dependencies: {}", story.dependencies.join(", "));
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_story_ids_skip_archived_items() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        for title in ["a", "b", "c"] {
+            let story = new_story(&project, title.to_string(), title.to_string(), None);
+            project.backlog.push(story);
+        }
+        crate::scripts::archive::archive_item(&mut project, "US-001").unwrap();
+
+        let story = new_story(&project, "d".to_string(), "As a user, I want d".to_string(), None);
+        assert_eq!(story.id, "US-004");
+        assert_eq!(story.story, "As a user, I want d");
+    }
+}
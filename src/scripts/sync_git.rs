@@ -0,0 +1,160 @@
+// src/scripts/sync_git.rs
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+use crate::config::Status;
+use crate::context::AppContext;
+use crate::hints;
+use crate::git;
+use crate::scripts::tasks::mark_item_done;
+
+const FIELD_SEP: char = '\u{1f}';
+
+/// Extract a backlog ID from text containing a `task/US-003` style branch name.
+fn extract_task_id(text: &str) -> Option<String> {
+    text.match_indices("task/").find_map(|(idx, _)| {
+        let rest = &text[idx + "task/".len()..];
+        let prefix: String = rest.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+        let after_prefix = rest[prefix.len()..].strip_prefix('-')?;
+        let number: String = after_prefix.chars().take_while(|c| c.is_ascii_digit()).collect();
+        if prefix.is_empty() || number.is_empty() {
+            return None;
+        }
+        Some(format!("{}-{}", prefix.to_uppercase(), number))
+    })
+}
+
+/// Parse `git log --format=%cI<US>%s` output into the earliest merge date per task ID.
+fn merged_task_ids(log_output: &str) -> BTreeMap<String, DateTime<Utc>> {
+    let mut merged: BTreeMap<String, DateTime<Utc>> = BTreeMap::new();
+    for line in log_output.lines() {
+        let Some((date, subject)) = line.split_once(FIELD_SEP) else { continue };
+        let (Some(id), Ok(date)) = (extract_task_id(subject), DateTime::parse_from_rfc3339(date.trim())) else { continue };
+        let date = date.with_timezone(&Utc);
+        merged.entry(id)
+            .and_modify(|existing| *existing = (*existing).min(date))
+            .or_insert(date);
+    }
+    merged
+}
+
+/// Commits made on `branch` since it was created: from the oldest entry of its reflog, or
+/// from where it forked off `default_branch` when it has none.
+fn own_commit_count(repo: &str, default_branch: &str, branch: &str) -> Result<usize> {
+    let reflog = git::run(&["-C", repo, "reflog", "show", "--format=%H", branch]).unwrap_or_default();
+    let created = match reflog.lines().last() {
+        Some(created) => created.to_string(),
+        None => git::run(&["-C", repo, "merge-base", "--fork-point", default_branch, branch])?.trim().to_string(),
+    };
+    let count = git::run(&["-C", repo, "rev-list", "--count", &format!("{}..{}", created, branch)])?;
+    Ok(count.trim().parse().unwrap_or(0))
+}
+
+/// Task branches in `repo` merged into the default branch, with the date they landed.
+fn find_merged_tasks(repo: &str, default_branch: &str) -> Result<BTreeMap<String, DateTime<Utc>>> {
+    let format = format!("--format=%cI{}%s", FIELD_SEP);
+    let mut merged = merged_task_ids(&git::run(&["-C", repo, "log", default_branch, "--merges", &format])?);
+
+    // Fast-forward merges leave no merge commit; use the branch tip date instead. A branch
+    // without commits of its own is listed as merged too, but nothing has landed from it.
+    let branches = git::run(&["-C", repo, "branch", "--merged", default_branch, "--format=%(refname:short)"])?;
+    for branch in branches.lines().filter(|b| b.starts_with("task/")) {
+        let Some(id) = extract_task_id(branch) else { continue };
+        if merged.contains_key(&id) || own_commit_count(repo, default_branch, branch)? == 0 {
+            continue;
+        }
+        let tip_date = git::run(&["-C", repo, "log", "-1", "--format=%cI", branch])?;
+        if let Ok(date) = DateTime::parse_from_rfc3339(tip_date.trim()) {
+            merged.insert(id, date.with_timezone(&Utc));
+        }
+    }
+    Ok(merged)
+}
+
+pub fn run(ctx: &AppContext) -> Result<()> {
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    let default_branch = git::default_branch()?;
+    println!("🔄 Looking for task/<ID> branches merged into '{}'...", default_branch);
+
+    let merged = find_merged_tasks(".", &default_branch)?;
+    let candidates: Vec<(String, DateTime<Utc>)> = merged.into_iter()
+        .filter(|(id, _)| project.backlog.iter().any(|item| &item.id == id && !matches!(item.status, Status::Done)))
+        .collect();
+
+    if candidates.is_empty() {
+        println!("✅ Board is in sync: no merged task branches with open backlog items");
+        return Ok(());
+    }
+
+    println!("📋 Merged but not marked Done:");
+    for (id, date) in &candidates {
+        if let Some(item) = project.backlog.iter().find(|item| &item.id == id) {
+            println!("   - {} ({:?}) {} — merged {}", id, item.status, item.title, date.format("%Y-%m-%d"));
+        }
+    }
+
+    if !ctx.prompter.confirm("\n👉 Mark these items as Done?")? {
+        println!("❌ No changes made");
+        return Ok(());
+    }
+
+    for (id, date) in &candidates {
+        mark_item_done(&mut project, id, *date)?;
+        println!("✅ {} marked as Done", id);
+    }
+    ctx.save_project(&project).context("Failed to save project")?;
+
+    hints::print_next_steps(Some(&project), &[("env-coach show-sprint", "Review updated sprint progress")]);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_task_id() {
+        assert_eq!(extract_task_id("Merge branch 'task/US-003' into main"), Some("US-003".to_string()));
+        assert_eq!(extract_task_id("Merge pull request #12 from alice/task/us-010-login"), Some("US-010".to_string()));
+        assert_eq!(extract_task_id("Merge branch 'feature/US-003'"), None);
+        assert_eq!(extract_task_id("Merge branch 'task/cleanup'"), None);
+    }
+
+    #[test]
+    fn test_merged_task_ids_keeps_earliest_merge() {
+        let log = [
+            "2024-03-05T10:00:00+00:00\u{1f}Merge branch 'task/US-003'",
+            "2024-03-01T09:00:00+02:00\u{1f}Merge branch 'task/US-003'",
+            "2024-03-02T12:00:00+00:00\u{1f}Merge branch 'task/BUG-001'",
+            "not a log line",
+        ].join("\n");
+        let merged = merged_task_ids(&log);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged["US-003"].to_rfc3339(), "2024-03-01T07:00:00+00:00");
+        assert!(merged.contains_key("BUG-001"));
+    }
+
+    #[test]
+    fn test_find_merged_tasks_skips_branches_without_commits() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().to_str().unwrap();
+        let git = |args: &[&str]| {
+            let mut full = vec!["-C", repo, "-c", "user.name=Test", "-c", "user.email=test@example.com"];
+            full.extend_from_slice(args);
+            git::run(&full).unwrap();
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "Initial commit"]);
+        git(&["branch", "task/US-001"]);
+        git(&["checkout", "-q", "-b", "task/US-002"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "Login form"]);
+        git(&["checkout", "-q", "main"]);
+        git(&["merge", "-q", "--ff-only", "task/US-002"]);
+
+        let merged = find_merged_tasks(repo, "main").unwrap();
+        assert_eq!(merged.keys().collect::<Vec<_>>(), vec!["US-002"]);
+    }
+}
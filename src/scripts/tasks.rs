@@ -2,11 +2,28 @@
 use anyhow::{Context, Result};
 // use reqwest; // Unused
 // use serde_json::Value; // Unused
-use crate::config::{Project, Status}; // Removed FinalLlmConfig as it's not directly used here
-use crate::auto_update::{AutoUpdater, UpdateContext}; // NEW: Import auto-update
-
-pub fn start_task(id: String) -> Result<()> {
-    let mut project = Project::load()
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use crate::code_context::{self, ContextConfig};
+use crate::code_index;
+use crate::config::{BacklogItem, LlmRole, Project, Status}; // Removed FinalLlmConfig as it's not directly used here
+use crate::auto_update::{verify, AutoApprove, AutoUpdater, SuggestionOutcome, UpdateContext}; // NEW: Import auto-update
+use crate::auto_update::llm_parsers::parse_assist_task_response;
+use crate::context::AppContext;
+use crate::git;
+use crate::hints;
+use crate::scripts::{criteria_check, fix_tests};
+use crate::scripts::session::{self, Session};
+use crate::stats;
+use crate::tokens::{self, Section};
+use crate::output::{self, Level, Topic};
+use crate::ollama;
+use crate::prompts;
+use crate::templates::Templates;
+
+pub fn start_task(ctx: &AppContext, id: String) -> Result<()> {
+    let mut project = ctx.load_project()
         .context("Failed to load project. Run 'env-coach init <n>' first")?;
 
     // Find the task
@@ -17,6 +34,7 @@ pub fn start_task(id: String) -> Result<()> {
 
     // Update status to In Progress
     project.backlog[task_index].status = Status::InProgress;
+    project.backlog[task_index].started_at.get_or_insert_with(Utc::now);
 
     // Store task details for printing (before saving)
     let task_title = project.backlog[task_index].title.clone();
@@ -25,7 +43,7 @@ pub fn start_task(id: String) -> Result<()> {
     let task_effort = project.backlog[task_index].effort;
     let task_criteria = project.backlog[task_index].acceptance_criteria.clone();
 
-    project.save()
+    ctx.save_project(&project)
         .context("Failed to save project")?;
 
     println!("🚀 Starting task: {}", id);
@@ -48,55 +66,66 @@ pub fn start_task(id: String) -> Result<()> {
     Ok(())
 }
 
-pub async fn assist_task(task_id: String, user_prompt_override: Option<String>) -> Result<()> {
-    use crate::templates::Templates; // For default prompt
-    use crate::ollama; // For send_generation_prompt
-    use crate::config::BacklogItem; // To type hint `task`
-
-    let project = Project::load().context("Failed to load project. Run 'env-coach init' first.")?;
-
-    println!("🤖 Providing LLM assistance for task: {}", task_id);
-
-    let task: &BacklogItem = project.backlog.iter()
-        .find(|item| item.id == task_id)
-        .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in backlog.", task_id))?;
-
-    println!("📋 Task Details:");
-    println!("   Title: {}", task.title);
-    println!("   Story: {}", task.story); // Assuming BacklogItem has a story field
-    // ... print other task details as before ...
-    println!("   Priority: {:?}", task.priority);
-    println!("   Effort: {} points", task.effort);
-    if !task.acceptance_criteria.is_empty() {
-        println!("   Acceptance Criteria:");
-        for (i, criteria) in task.acceptance_criteria.iter().enumerate() {
-            println!("     {}. {}", i + 1, criteria);
+/// `git status` and the uncommitted diff (one entry per file) as prompt entries.
+fn format_work_in_progress(status: Option<&str>, diff: Option<&str>) -> Vec<String> {
+    let mut entries = vec![];
+    if let Some(status) = status.filter(|status| !status.trim().is_empty()) {
+        entries.push(format!("git status --short:\n```\n{}\n```", status.trim_end()));
+    }
+    let mut files: Vec<String> = vec![];
+    for line in diff.unwrap_or_default().lines() {
+        if line.starts_with("diff --git ") || files.is_empty() {
+            files.push(String::new());
+        }
+        if let Some(file) = files.last_mut() {
+            file.push_str(line);
+            file.push('\n');
         }
     }
+    entries.extend(files.iter().map(|file| format!("```diff\n{}```", file)));
+    if entries.is_empty() {
+        return vec!["No uncommitted changes.".to_string()];
+    }
+    entries
+}
 
-    println!("🔍 Preparing prompt and asking LLM for assistance...");
+/// `{{work_in_progress}}` for a task: what has been written for an In Progress task and not
+/// committed yet, as `.env-coach/context.toml` allows, so suggestions build on it.
+fn work_in_progress(task: &BacklogItem, config: &ContextConfig) -> Vec<String> {
+    if !matches!(task.status, Status::InProgress) || !(config.git_diff || config.git_status) {
+        return vec!["None; the task has not been started.".to_string()];
+    }
+    // env-coach's own files change with every command and say nothing about the code
+    let excluded = [".env-coach", "project.json", "project.toml"];
+    let status = config.git_status.then(|| git::status_short(&excluded)).and_then(Result::ok);
+    let diff = config.git_diff.then(|| git::uncommitted_diff(&excluded)).and_then(Result::ok);
+    let changed = diff.as_deref().map(|diff| diff.matches("diff --git ").count()).unwrap_or_default();
+    if changed > 0 {
+        println!("🧩 Adding the uncommitted changes to {} file(s)", changed);
+    }
+    format_work_in_progress(status.as_deref(), diff.as_deref())
+}
 
-    // 1. Load Prompt Template
+/// Fill the task assistant prompt (from `.env-coach/prompts/task_assistant.md` or its
+/// variant for the project's language, or the built-in default) with the project context, task details and what was suggested for
+/// the task before. More than one prompt is
+/// returned when the `chunk` strategy spreads the relevant code over several.
+pub(crate) async fn build_task_assistant_prompts(project: &Project, task: &BacklogItem, user_prompt: Option<String>, relevant_code: Vec<String>, prompt_file: Option<&Path>) -> Result<Vec<String>> {
+    let primary_language = crate::auto_update::code_gen::get_primary_language(&project.meta);
     let prompt_template_path = std::path::Path::new(".env-coach/prompts/task_assistant.md");
-    let prompt_template = match std::fs::read_to_string(prompt_template_path) {
-        Ok(template) => template,
-        Err(_) => {
+    let prompt_template = match prompt_file.map(prompts::read_override).transpose()?.or_else(|| prompts::read_template(prompt_template_path, &primary_language)) {
+        Some(template) => template,
+        None => {
             println!("⚠️ Task assistant prompt not found at {:?}. Using default.", prompt_template_path);
             Templates::default_task_assistant_prompt_content()
         }
     };
 
-    // 2. Format Prompt
-    // Determine primary language (this function needs to be accessible, e.g. from auto_update::code_gen or a shared util)
-    // For now, let's assume it's available via project or a new helper here.
-    // We'll use the one from auto_update::code_gen for consistency.
-    let primary_language = crate::auto_update::code_gen::get_primary_language(&project.meta);
-
     let ac_string = task.acceptance_criteria.iter()
         .map(|ac| format!("  - {}", ac))
         .collect::<Vec<String>>().join("\n");
 
-    let mut filled_prompt = prompt_template;
+    let mut filled_prompt = prompts::fill_project_context(&prompt_template, project, Some(task));
     filled_prompt = filled_prompt.replace("{{project_name}}", &project.meta.name);
     filled_prompt = filled_prompt.replace("{{project_description}}", &project.meta.description);
     filled_prompt = filled_prompt.replace("{{tech_stack}}", &project.meta.tech_stack.join(", "));
@@ -105,79 +134,432 @@ pub async fn assist_task(task_id: String, user_prompt_override: Option<String>)
     filled_prompt = filled_prompt.replace("{{task_id}}", &task.id);
     filled_prompt = filled_prompt.replace("{{task_title}}", &task.title);
     filled_prompt = filled_prompt.replace("{{task_story}}", &task.story);
+    filled_prompt = filled_prompt.replace("{{extra}}", &task.extra_display());
     filled_prompt = filled_prompt.replace("{{#each task_acceptance_criteria}}", ""); // Remove loop markers
     filled_prompt = filled_prompt.replace("{{/each}}", "");
     filled_prompt = filled_prompt.replace("  - {{this}}", &ac_string); // Replace the iterated part
 
-    let user_query = user_prompt_override.unwrap_or_else(|| "Provide general assistance and next steps for this task.".to_string());
+    let user_query = user_prompt.unwrap_or_else(|| "Provide general assistance and next steps for this task.".to_string());
     filled_prompt = filled_prompt.replace("{{user_prompt}}", &user_query);
+    // The code found for the task is kept over the general context when trimming
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let context_config = ContextConfig::load_or_default(&root);
+    let history = session::condensed_history(&session::task_sessions(&root, &task.id));
+    Ok(tokens::build_prompts(&project.llm_for(LlmRole::Coder), context_config.strategy, context_config.summary_model.as_deref(), &filled_prompt, vec![
+        Section::new("{{relevant_code}}", relevant_code, "\n\n"),
+        Section::new("{{work_in_progress}}", work_in_progress(task, &context_config), "\n\n"),
+        Section::new("{{task_history}}", vec![history], "\n"),
+        Section::new("{{code_context}}", code_context::code_context_entries(&root, &context_config), "\n\n"),
+    ]).await)
+}
+
+/// One assist-task response from the responses to each prompt: their suggestions in order
+/// and their summaries joined. A single response is returned as it is, and so are the
+/// responses, joined, when none of them is structured.
+fn merge_assist_responses(mut responses: Vec<String>) -> String {
+    if responses.len() == 1 {
+        return responses.remove(0);
+    }
+    let mut suggestions: Vec<serde_json::Value> = vec![];
+    let mut summaries: Vec<String> = vec![];
+    let mut structured = false;
+    for response in &responses {
+        let Ok(serde_json::Value::Object(parsed)) = serde_json::from_str::<serde_json::Value>(response) else {
+            println!("⚠️ Ignoring a part of the answer that is not structured JSON");
+            continue;
+        };
+        structured = true;
+        if let Some(serde_json::Value::Array(part)) = parsed.get("suggestions") {
+            suggestions.extend(part.iter().cloned());
+        }
+        if let Some(serde_json::Value::String(summary)) = parsed.get("overall_summary") {
+            summaries.push(summary.clone());
+        }
+    }
+    if !structured {
+        return responses.join("\n\n");
+    }
+    serde_json::json!({ "suggestions": suggestions, "overall_summary": summaries.join(" ") }).to_string()
+}
+
+/// Send the task assistant prompts and merge the answers.
+pub(crate) async fn ask_task_assistant(project: &Project, prompts: &[String]) -> Result<String> {
+    let mut responses = vec![];
+    for (i, prompt) in prompts.iter().enumerate() {
+        if prompts.len() > 1 {
+            println!("   Part {}/{}...", i + 1, prompts.len());
+        }
+        responses.push(ollama::send_generation_prompt(&project.llm_for(LlmRole::Coder), prompt).await?);
+    }
+    Ok(merge_assist_responses(responses))
+}
+
+pub async fn assist_task(ctx: &AppContext, task_id: String, user_prompt_override: Option<String>, prompt_file: Option<&Path>) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    println!("🤖 Providing LLM assistance for task: {}", task_id);
+
+    let task: &BacklogItem = project.backlog.iter()
+        .find(|item| item.id == task_id)
+        .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in backlog.", task_id))?;
+
+    println!("📋 Task Details:");
+    println!("   Title: {}", task.title);
+    println!("   Story: {}", task.story); // Assuming BacklogItem has a story field
+    // ... print other task details as before ...
+    println!("   Priority: {:?}", task.priority);
+    println!("   Effort: {} points", task.effort);
+    if !task.acceptance_criteria.is_empty() {
+        println!("   Acceptance Criteria:");
+        for (i, criteria) in task.acceptance_criteria.iter().enumerate() {
+            println!("     {}. {}", i + 1, criteria);
+        }
+    }
+
+    println!("🔍 Preparing prompt and asking LLM for assistance...");
+
+    // 1-2. Load and fill the task assistant prompt
+    let relevant_code = code_index::relevant_code(&project, task).await;
+    let prompts = build_task_assistant_prompts(&project, task, user_prompt_override.clone(), relevant_code, prompt_file).await?;
 
     // 3. Send to LLM
-    let llm_response_str = ollama::send_generation_prompt(project.llm(), &filled_prompt)
+    let llm_response_str = ask_task_assistant(&project, &prompts)
         .await
         .context("Failed to get LLM assistance for task")?;
 
     // 4. Process with AutoUpdater
     // We print the raw response for now, AutoUpdater will handle parsing and actions.
-    println!("\n🤖 LLM Raw Response (JSON expected):");
-    println!("{}", llm_response_str);
+    // At verbose LLM output it has already been printed by the Ollama client.
+    if output::enabled(Topic::Llm, Level::Normal) && !output::enabled(Topic::Llm, Level::Verbose) {
+        println!("\n🤖 LLM Raw Response (JSON expected):");
+        println!("{}", llm_response_str);
+    }
 
-    let mut updater = AutoUpdater::new(project); // project is moved here
+    // Every exchange is kept as the task's history; structured suggestions can also be
+    // reviewed and applied later
+    let applicable = parse_assist_task_response(&llm_response_str).is_ok_and(|parsed| !parsed.suggestions.is_empty());
+    let mut session = Session::new(&task_id, llm_response_str.clone()).with_prompt(user_prompt_override);
+
+    let mut updater = AutoUpdater::with_context(project, ctx); // project is moved here
     updater.process_llm_response(&llm_response_str, UpdateContext::CodeGeneration(task_id.clone()))
         .context("Failed to process LLM suggestions or auto-update files")?;
+    session.outcomes = updater.outcomes().clone();
+
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let path = session.review_path(&root);
+    let relative = path.strip_prefix(&root).unwrap_or(&path).display().to_string();
+    if ctx.dry_run {
+        println!("🔍 Dry run: session not saved to {}", relative);
+    } else {
+        session.save(&path)?;
+        if applicable {
+            println!("💾 Suggestions saved to {}", relative);
+            println!("   Apply them later with: env-coach apply-session {} [--only 1,3]", relative);
+        } else {
+            println!("💾 Answer saved to {}", relative);
+        }
+    }
     // Note: `project` is consumed by AutoUpdater. If we need it afterwards, AutoUpdater must return it or operate on &mut.
     // Current AutoUpdater::new takes ownership, and save is called internally.
 
-    println!("\n💡 Review the LLM suggestions and generated/modified files (if any).");
-    println!("💡 When ready to mark task complete: env-coach complete-task {}", task_id);
+    if output::show_hints() {
+        println!("\n💡 Review the LLM suggestions and generated/modified files (if any).");
+        println!("💡 When ready to mark task complete: env-coach complete-task {}", task_id);
+    }
 
     Ok(())
 }
 
-pub fn complete_task(id: String) -> Result<()> {
-    let mut project = Project::load()
-        .context("Failed to load project. Run 'env-coach init <n>' first")?;
+/// Where an `execute-task` run keeps its session, so an interrupted run can continue with
+/// `--resume` without calling the LLM again.
+pub(crate) fn progress_path(root: &Path, task_id: &str) -> PathBuf {
+    root.join(".env-coach/progress").join(format!("{}.json", task_id))
+}
 
-    // Find the task
+fn build_fix_build_prompt(template: &str, project: &Project, task: &BacklogItem, build_command: &str, build_output: &str, source_files: &str, attempt: u32) -> String {
+    prompts::fill_project_context(template, project, Some(task))
+        .replace("{{primary_language}}", &crate::auto_update::code_gen::get_primary_language(&project.meta))
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{tech_stack}}", &project.meta.tech_stack.join(", "))
+        .replace("{{task_id}}", &task.id)
+        .replace("{{task_title}}", &task.title)
+        .replace("{{task_story}}", &task.story)
+        .replace("{{extra}}", &task.extra_display())
+        .replace("{{build_command}}", build_command)
+        .replace("{{build_output}}", fix_tests::tail(build_output, fix_tests::MAX_OUTPUT_CHARS))
+        .replace("{{source_files}}", source_files)
+        .replace("{{attempt}}", &attempt.to_string())
+}
+
+/// Build the project and, while it fails, send the errors and the files they point at to
+/// the LLM and apply its fixes, at most `max_iterations` times. Returns whether the build
+/// ended green.
+async fn auto_fix_build(ctx: &AppContext, updater: &mut AutoUpdater, root: &Path, task_id: &str, max_iterations: u32) -> Result<bool> {
+    let Some(build_command) = verify::build_command(root) else {
+        println!("ℹ️ No build command known for this project; auto-fix skipped");
+        return Ok(true);
+    };
+    if ctx.dry_run {
+        println!("🔍 Dry run: nothing was written, so auto-fix is skipped");
+        return Ok(true);
+    }
+    let prompt_template_path = Path::new(".env-coach/prompts/fix_build.md");
+    let prompt_template = match prompts::read_template(prompt_template_path, &crate::auto_update::code_gen::get_primary_language(&updater.get_project().meta)) {
+        Some(template) => template,
+        None => {
+            println!("⚠️ Fix-build prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_fix_build_prompt_content()
+        }
+    };
+
+    let mut attempt = 0;
+    loop {
+        println!();
+        let check = verify::run_check(root, build_command)?;
+        if check.passed {
+            println!("✅ Build is green");
+            return Ok(true);
+        }
+        for line in check.summary() {
+            println!("   {}", line);
+        }
+        if attempt == max_iterations {
+            println!("❌ Build still fails after {} auto-fix attempt(s)", max_iterations);
+            return Ok(false);
+        }
+        attempt += 1;
+
+        println!("🔁 Auto-fix {}/{}: asking LLM to fix the build errors...", attempt, max_iterations);
+        let files = fix_tests::referenced_files(&check.output, root);
+        let project = updater.get_project();
+        let task = project.backlog.iter()
+            .find(|item| item.id == task_id)
+            .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in backlog.", task_id))?;
+        let prompt = build_fix_build_prompt(&prompt_template, project, task, build_command, &check.output,
+            &fix_tests::format_source_files(root, &files), attempt);
+        // The task's own changes are already applied, so a failed round only ends the loop
+        let parsed = match ollama::send_generation_prompt(&project.llm_for(LlmRole::Coder), &prompt).await {
+            Ok(response) => match parse_assist_task_response(&response) {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    println!("⚠️ The LLM did not return structured fixes ({}); stopping auto-fix", err);
+                    return Ok(false);
+                }
+            },
+            Err(err) => {
+                println!("⚠️ Failed to get build fixes from LLM ({:#}); stopping auto-fix", err);
+                return Ok(false);
+            }
+        };
+        if let Some(summary) = &parsed.overall_summary {
+            println!("ℹ️ {}", summary);
+        }
+        let outcomes = updater.apply_suggestions(root, &parsed.suggestions, &HashSet::new(), |_, _| Ok(()))?;
+        if !outcomes.iter().any(|(_, outcome)| *outcome == SuggestionOutcome::Applied) {
+            println!("⚠️ No fix was applied; stopping auto-fix");
+            return Ok(false);
+        }
+    }
+}
+
+/// How `execute-task` runs, from its flags (or `execute-sprint`'s).
+#[derive(Debug, Clone, Default)]
+pub struct ExecuteOptions {
+    /// Specific request for the LLM
+    pub prompt: Option<String>,
+    /// Continue an interrupted run without calling the LLM again
+    pub resume: bool,
+    /// Run the post-apply checks
+    pub verify: bool,
+    /// Rounds of LLM build fixes after applying
+    pub auto_fix: Option<u32>,
+    pub auto_approve: AutoApprove,
+}
+
+/// What an `execute-task` run did, for `execute-sprint`'s report.
+#[derive(Debug, Default)]
+pub struct ExecutionSummary {
+    /// One line per applied change, e.g. `ReplaceFunction src/lib.rs`
+    pub applied: Vec<String>,
+    pub skipped: usize,
+    pub failed: usize,
+    /// The build or checks failed after applying
+    pub needs_follow_up: bool,
+}
+
+/// Ask the LLM for an implementation plan and apply its suggestions one by one.
+/// Progress is persisted so `--resume` can pick up after a crash or Ctrl-C. With
+/// `auto_fix`, compile errors left afterwards get up to that many rounds of LLM fixes.
+pub async fn execute_task(ctx: &AppContext, task_id: String, options: ExecuteOptions) -> Result<ExecutionSummary> {
+    let ExecuteOptions { prompt: user_prompt, resume, verify, auto_fix, auto_approve } = options;
+    let mut project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let progress_path = progress_path(&root, &task_id);
+
+    let task = project.backlog.iter()
+        .find(|item| item.id == task_id)
+        .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in backlog.", task_id))?;
+
+    let mut progress = if resume {
+        let progress = Session::load(&progress_path)
+            .with_context(|| format!("No interrupted execute-task run to resume for {}", task_id))?;
+        println!("⏯️  Resuming execute-task for {} (started {}, {} suggestion(s) already handled)",
+            task_id, progress.started.format("%Y-%m-%d %H:%M UTC"), progress.outcomes.len());
+        progress
+    } else {
+        if progress_path.exists() {
+            anyhow::bail!(
+                "An interrupted execute-task run exists for {}. Continue it with 'env-coach execute-task {} --resume', or delete {} to start over.",
+                task_id, task_id, progress_path.display()
+            );
+        }
+        println!("🛠️  Executing task {}: {}", task.id, task.title);
+        println!("🔍 Asking LLM for an implementation plan...");
+        let relevant_code = code_index::relevant_code(&project, task).await;
+        let prompts = build_task_assistant_prompts(&project, task, user_prompt.clone(), relevant_code, None).await?;
+        let llm_response = ask_task_assistant(&project, &prompts)
+            .await
+            .context("Failed to get implementation suggestions from LLM")?;
+        Session::new(&task_id, llm_response).with_prompt(user_prompt)
+    };
+
+    let parsed = parse_assist_task_response(&progress.llm_response)
+        .context("execute-task needs structured suggestions; try 'env-coach assist-task' for free-form help")?;
+    if let Some(summary) = &parsed.overall_summary {
+        println!("ℹ️ {}", summary);
+    }
+    if !ctx.dry_run {
+        progress.save(&progress_path)?;
+    }
+
+    // Executing a task means work has started on it
+    if let Some(item) = project.backlog.iter_mut().find(|item| item.id == task_id) {
+        if matches!(item.status, Status::Todo) {
+            item.status = Status::InProgress;
+            item.started_at.get_or_insert_with(Utc::now);
+            ctx.save_project(&project).context("Failed to save project")?;
+        }
+    }
+
+    let already_decided: HashSet<usize> = progress.outcomes.keys().copied().collect();
+    let mut updater = AutoUpdater::with_context(project, ctx).with_auto_approve(auto_approve);
+    let outcomes = updater.apply_suggestions(&root, &parsed.suggestions, &already_decided, |idx, outcome| {
+        progress.outcomes.insert(idx, outcome.clone());
+        if ctx.dry_run { Ok(()) } else { progress.save(&progress_path) }
+    })?;
+    let build_ok = match auto_fix {
+        Some(max_iterations) => auto_fix_build(ctx, &mut updater, &root, &task_id, max_iterations).await?,
+        None => true,
+    };
+    // Auto-fix implies verification, so a build it could not fix is recorded as follow-up
+    let checks_ok = session::verify_applied(ctx, updater.get_project(), &root, verify || auto_fix.is_some(), &parsed.suggestions, &outcomes, &mut progress)?;
+
+    let applied = progress.count(|o| matches!(o, SuggestionOutcome::Applied));
+    let skipped = progress.count(|o| matches!(o, SuggestionOutcome::Skipped));
+    let failed = progress.count(|o| matches!(o, SuggestionOutcome::Failed(_)));
+
+    println!();
+    println!("📊 {} applied, {} skipped, {} failed (of {} suggestion(s))", applied, skipped, failed, parsed.suggestions.len());
+    for (idx, outcome) in &progress.outcomes {
+        if let SuggestionOutcome::Failed(reason) = outcome {
+            println!("   ⚠️  #{}: {}", idx + 1, reason);
+        }
+    }
+    if !ctx.dry_run && progress.outcomes.len() >= parsed.suggestions.len() && progress_path.exists() {
+        std::fs::remove_file(&progress_path)
+            .with_context(|| format!("Failed to remove finished progress file {:?}", progress_path))?;
+        // The finished run stays on record as a session: the task's history, and the
+        // suggestions needing follow-up
+        let log = progress.review_path(&root);
+        progress.save(&log)?;
+        if !progress.follow_up.is_empty() {
+            println!("⚠️ {} applied suggestion(s) need follow-up; see {}", progress.follow_up.len(),
+                log.strip_prefix(&root).unwrap_or(&log).display());
+        }
+    } else if !ctx.dry_run && progress_path.exists() {
+        progress.save(&progress_path)?;
+    }
+
+    hints::print_next_steps(Some(updater.get_project()), &[
+        ("cargo build && cargo test", "Verify the changes"),
+        (format!("env-coach complete-task {}", task_id).as_str(), "Mark the task as done"),
+    ]);
+
+    Ok(ExecutionSummary {
+        applied: progress.outcomes.iter()
+            .filter(|(_, outcome)| **outcome == SuggestionOutcome::Applied)
+            .filter_map(|(idx, _)| parsed.suggestions.get(*idx).and_then(|suggestion| suggestion.change_summary()))
+            .collect(),
+        skipped,
+        failed,
+        needs_follow_up: !build_ok || !checks_ok,
+    })
+}
+
+/// Mark a backlog item Done as of `completed_at` and credit its points to its sprint.
+/// Returns the item title.
+pub(crate) fn mark_item_done(project: &mut Project, id: &str, completed_at: DateTime<Utc>) -> Result<String> {
     let task = project.backlog
         .iter_mut()
         .find(|item| item.id == id)
         .ok_or_else(|| anyhow::anyhow!("Task '{}' not found", id))?;
 
-    // Update status to Done
     task.status = Status::Done;
-    
+    task.completed_at = Some(completed_at);
+
     // Update sprint progress if task is in a sprint
     if let Some(sprint_id) = &task.sprint {
         if let Some(sprint) = project.sprints.iter_mut().find(|s| s.id == *sprint_id) {
             sprint.completed_points += task.effort;
         }
     }
+    Ok(task.title.clone())
+}
+
+pub async fn complete_task(ctx: &AppContext, id: String, verify: bool) -> Result<()> {
+    let mut project = ctx.load_project()
+        .context("Failed to load project. Run 'env-coach init <n>' first")?;
+
+    if verify && !criteria_check::verify_before_completion(ctx, &mut project, &id).await? {
+        // Keep the checklist so `show-item` tells what is left to do
+        ctx.save_project(&project).context("Failed to save project")?;
+        println!("❌ {} not marked as Done", id);
+        let execute = format!("env-coach execute-task {}", id);
+        let recheck = format!("env-coach complete-task {} --verify", id);
+        hints::print_next_steps(Some(&project), &[
+            (execute.as_str(), "Work on the unmet criteria"),
+            (recheck.as_str(), "Check the criteria again"),
+        ]);
+        return Ok(());
+    }
+
+    let title = mark_item_done(&mut project, &id, Utc::now())?;
 
     println!("✅ Completing task: {}", id);
-    println!("📋 Task '{}' marked as Done", task.title);
+    println!("📋 Task '{}' marked as Done", title);
 
     // NEW: Auto-update documentation
-    let mut updater = AutoUpdater::new(project);
+    let mut updater = AutoUpdater::with_context(project, ctx);
     updater.process_llm_response("", UpdateContext::TaskCompletion(id.clone()))
         .context("Failed to auto-update documentation")?;
 
     // Save the updated project
     let updated_project = updater.get_project();
-    updated_project.save()
+    ctx.save_project(updated_project)
         .context("Failed to save project")?;
 
     println!("📝 Documentation auto-updated (README.md, CHANGELOG.md)");
+
+    if let Some(task) = updated_project.backlog.iter().find(|item| item.id == id) {
+        crate::scripts::github::close_linked_issue(ctx, task).await;
+    }
     
     // Show updated sprint progress if applicable
     if let Some(task) = updated_project.backlog.iter().find(|item| item.id == id) {
         if let Some(sprint_id) = &task.sprint {
             if let Some(sprint) = updated_project.sprints.iter().find(|s| s.id == *sprint_id) {
-                let progress_percent = if sprint.total_points > 0 {
-                    (sprint.completed_points * 100) / sprint.total_points
-                } else {
-                    0
-                };
+                let progress_percent = stats::percent(sprint.completed_points, sprint.total_points).unwrap_or(0);
                 
                 println!("📊 Sprint Progress: {} / {} points ({}%)", 
                     sprint.completed_points, sprint.total_points, progress_percent);
@@ -185,9 +567,7 @@ pub fn complete_task(id: String) -> Result<()> {
         }
     }
 
-    println!("🎯 Next steps:");
-    println!("   env-coach show-sprint               # View current sprint status");
-    println!("   env-coach start-task <next-id>      # Start next task");
+    hints::print_next_steps(Some(updated_project), &[("env-coach show-sprint", "View current sprint status")]);
 
     Ok(())
 }
@@ -197,4 +577,52 @@ pub fn complete_task(id: String) -> Result<()> {
 // The new `assist_task` directly loads the prompt template, formats it,
 // calls `ollama::send_generation_prompt`, and then passes the response
 // to `AutoUpdater`. The `get_primary_language` logic is now centralized
-// in `auto_update::code_gen`.
\ No newline at end of file
+// in `auto_update::code_gen`.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execution_progress_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = progress_path(dir.path(), "US-001");
+        assert!(path.ends_with(".env-coach/progress/US-001.json"));
+
+        let mut progress = Session::new("US-001", r#"{"suggestions": []}"#.to_string());
+        progress.outcomes.insert(0, SuggestionOutcome::Applied);
+        progress.outcomes.insert(2, SuggestionOutcome::Failed("file exists".to_string()));
+        progress.save(&path).unwrap();
+
+        let loaded = Session::load(&path).unwrap();
+        assert_eq!(loaded.task_id, "US-001");
+        assert_eq!(loaded.llm_response, progress.llm_response);
+        assert_eq!(loaded.outcomes.keys().copied().collect::<Vec<_>>(), vec![0, 2]);
+        assert!(matches!(loaded.outcomes[&2], SuggestionOutcome::Failed(ref reason) if reason == "file exists"));
+    }
+
+    #[test]
+    fn test_merge_assist_responses() {
+        assert_eq!(merge_assist_responses(vec!["free-form help".to_string()]), "free-form help");
+        let merged = merge_assist_responses(vec![
+            r#"{"suggestions": [{"type": "general_advice", "content": "one"}], "overall_summary": "First part."}"#.to_string(),
+            "not json".to_string(),
+            r#"{"suggestions": [{"type": "general_advice", "content": "two"}], "overall_summary": "Second part."}"#.to_string(),
+        ]);
+        let parsed = parse_assist_task_response(&merged).unwrap();
+        assert_eq!(parsed.suggestions.len(), 2);
+        assert_eq!(parsed.overall_summary.as_deref(), Some("First part. Second part."));
+        assert_eq!(merge_assist_responses(vec!["a".to_string(), "b".to_string()]), "a\n\nb");
+    }
+
+    #[test]
+    fn test_format_work_in_progress() {
+        let diff = "diff --git a/src/auth.rs b/src/auth.rs\n--- a/src/auth.rs\n+++ b/src/auth.rs\n@@ -1 +1,2 @@\n pub fn login() {}\n+pub fn logout() {}\ndiff --git a/Cargo.toml b/Cargo.toml\n+bcrypt = \"0.15\"\n";
+        let entries = format_work_in_progress(Some(" M src/auth.rs\n?? src/session.rs\n"), Some(diff));
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0], "git status --short:\n```\n M src/auth.rs\n?? src/session.rs\n```");
+        assert!(entries[1].starts_with("```diff\ndiff --git a/src/auth.rs"));
+        assert!(entries[1].ends_with("+pub fn logout() {}\n```"));
+        assert!(entries[2].contains("bcrypt"));
+        assert_eq!(format_work_in_progress(Some(""), Some("")), vec!["No uncommitted changes."]);
+    }
+}
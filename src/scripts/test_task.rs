@@ -0,0 +1,177 @@
+// src/scripts/test_task.rs
+//! `test-task <id>`: ask the LLM for tests of a backlog item's acceptance criteria and
+//! apply them through the `execute-task` suggestion pipeline.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::auto_update::code_gen::get_primary_language;
+use crate::auto_update::llm_parsers::parse_assist_task_response;
+use crate::auto_update::{AutoUpdater, SuggestionOutcome};
+use crate::config::{BacklogItem, LlmRole, Project};
+use crate::context::AppContext;
+use crate::git;
+use crate::hints;
+use crate::ollama;
+use crate::prompts;
+use crate::scripts::fix_tests;
+use crate::templates::Templates;
+
+/// Files that say nothing about how the code behaves
+const SKIPPED_EXTENSIONS: &[&str] = &["md", "json", "lock", "toml", "txt", "yml", "yaml"];
+
+/// Source files the tests are about: the `--file` ones when given, otherwise files changed
+/// by commits that mention the item, then files its story or criteria name.
+fn relevant_files(root: &Path, item: &BacklogItem, explicit: &[String], changed: &[String]) -> Vec<PathBuf> {
+    if !explicit.is_empty() {
+        return explicit.iter().map(PathBuf::from).filter(|path| root.join(path).is_file()).collect();
+    }
+    let text = format!("{}\n{}", item.story, item.acceptance_criteria.join("\n"));
+    let mut files: Vec<PathBuf> = vec![];
+    let candidates = changed.iter().map(PathBuf::from).chain(fix_tests::referenced_files(&text, root));
+    for path in candidates {
+        let skipped = path.starts_with(".env-coach")
+            || path.extension().and_then(|ext| ext.to_str()).is_none_or(|ext| SKIPPED_EXTENSIONS.contains(&ext));
+        if !skipped && root.join(&path).is_file() && !files.contains(&path) {
+            files.push(path);
+        }
+    }
+    if files.is_empty() {
+        // Nothing links the item to code yet; show the crate or package entry point
+        files.extend(["src/lib.rs", "src/main.rs"].map(PathBuf::from).into_iter().filter(|path| root.join(path).is_file()));
+    }
+    files.truncate(fix_tests::MAX_SOURCE_FILES);
+    files
+}
+
+fn existing_tests(root: &Path) -> String {
+    let Ok(entries) = std::fs::read_dir(root.join("tests")) else { return "None".to_string() };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| format!("- tests/{}", entry.file_name().to_string_lossy()))
+        .collect();
+    names.sort();
+    if names.is_empty() { "None".to_string() } else { names.join("\n") }
+}
+
+fn build_test_writer_prompt(template: &str, project: &Project, item: &BacklogItem, test_command: &str, source_files: &str, existing_tests: &str) -> String {
+    let criteria = match item.acceptance_criteria.is_empty() {
+        true => "None listed; derive the tests from the story.".to_string(),
+        false => item.acceptance_criteria.iter().map(|criterion| format!("- {}", criterion)).collect::<Vec<_>>().join("\n"),
+    };
+    template
+        .replace("{{primary_language}}", &get_primary_language(&project.meta))
+        .replace("{{project_name}}", &project.meta.name)
+        .replace("{{project_description}}", &project.meta.description)
+        .replace("{{tech_stack}}", &project.meta.tech_stack.join(", "))
+        .replace("{{test_command}}", test_command)
+        .replace("{{task_id}}", &item.id)
+        .replace("{{task_title}}", &item.title)
+        .replace("{{task_story}}", &item.story)
+        .replace("{{acceptance_criteria}}", &criteria)
+        .replace("{{source_files}}", source_files)
+        .replace("{{existing_tests}}", existing_tests)
+}
+
+/// Generate tests for a backlog item from its acceptance criteria and the code it touches,
+/// applying them one by one after confirmation as `execute-task` does.
+pub async fn run(ctx: &AppContext, task_id: String, files: Vec<String>) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let item = project.backlog.iter()
+        .find(|item| item.id == task_id)
+        .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in backlog.", task_id))?;
+    if item.acceptance_criteria.is_empty() {
+        println!("⚠️ {} has no acceptance criteria; the tests will be based on its story only", task_id);
+    }
+
+    // Not being in a git repository just means no commits point at the code
+    let changed = if files.is_empty() { git::files_changed_for(&task_id).unwrap_or_default() } else { vec![] };
+    let sources = relevant_files(&root, item, &files, &changed);
+    if sources.is_empty() {
+        println!("⚠️ No source files found for {}; pass them with --file", task_id);
+    } else {
+        println!("📂 Sending {} source file(s): {}", sources.len(),
+            sources.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "));
+    }
+    let test_command = fix_tests::detect_test_command(&root).unwrap_or("the project's test runner");
+
+    let prompt_template_path = Path::new(".env-coach/prompts/test_writer.md");
+    let prompt_template = match prompts::read_template(prompt_template_path, &get_primary_language(&project.meta)) {
+        Some(template) => template,
+        None => {
+            println!("⚠️ Test-writer prompt not found at {:?}. Using default.", prompt_template_path);
+            Templates::default_test_writer_prompt_content()
+        }
+    };
+    let prompt_template = prompts::fill_project_context(&prompt_template, &project, Some(item));
+    let prompt = build_test_writer_prompt(&prompt_template, &project, item, test_command,
+        &fix_tests::format_source_files(&root, &sources), &existing_tests(&root));
+
+    println!("🧪 Asking LLM for tests of {}: {}", item.id, item.title);
+    let response = ollama::send_generation_prompt(&project.llm_for(LlmRole::Coder), &prompt)
+        .await
+        .context("Failed to get tests from LLM")?;
+    let parsed = parse_assist_task_response(&response).context("The LLM did not return structured test suggestions")?;
+    if let Some(summary) = &parsed.overall_summary {
+        println!("ℹ️ {}", summary);
+    }
+
+    let mut updater = AutoUpdater::with_context(project, ctx);
+    let outcomes = updater.apply_suggestions(&root, &parsed.suggestions, &HashSet::new(), |_, _| Ok(()))?;
+    let applied = outcomes.iter().filter(|(_, outcome)| matches!(outcome, SuggestionOutcome::Applied)).count();
+    println!();
+    println!("📊 {} of {} suggestion(s) applied", applied, parsed.suggestions.len());
+
+    if applied > 0 && !ctx.dry_run {
+        if let Some(command) = fix_tests::detect_test_command(&root) {
+            if ctx.prompter.confirm("👉 Run the tests?")? {
+                let (passed, _) = fix_tests::run_tests(command)?;
+                println!("{}", if passed { "✅ Tests pass" } else { "❌ Tests fail" });
+            }
+        }
+    }
+
+    hints::print_next_steps(Some(updater.get_project()), &[
+        ("git diff", "Review the new tests"),
+        ("env-coach fix-tests", "Let the LLM fix failing tests"),
+        (format!("env-coach complete-task {}", task_id).as_str(), "Mark the task as done"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(story: &str, acceptance_criteria: &[&str]) -> BacklogItem {
+        BacklogItem::test("US-002").with_title("Parse durations").with_story(story).with_criteria(acceptance_criteria)
+    }
+
+    #[test]
+    fn test_relevant_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        for file in ["src/main.rs", "src/parser.rs", "src/units.rs", "README.md"] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+        let story = item("Durations in src/units.rs are parsed", &["Unknown units are rejected"]);
+        let changed = vec!["README.md".to_string(), "src/parser.rs".to_string(), "src/gone.rs".to_string()];
+
+        assert_eq!(relevant_files(root, &story, &[], &changed), vec![PathBuf::from("src/parser.rs"), PathBuf::from("src/units.rs")]);
+        assert_eq!(relevant_files(root, &story, &["src/main.rs".to_string()], &changed), vec![PathBuf::from("src/main.rs")]);
+        assert_eq!(relevant_files(root, &item("", &[]), &[], &[]), vec![PathBuf::from("src/main.rs")]);
+    }
+
+    #[test]
+    fn test_prompt_fills_every_placeholder() {
+        let project = Project::new("P".to_string(), "".to_string(), None);
+        let prompt = build_test_writer_prompt(&Templates::default_test_writer_prompt_content(), &project,
+            &item("As a user...", &["5s parses to 5"]), "cargo test", "--- src/lib.rs ---", "None");
+        assert!(!prompt.replace(crate::project_summary::PLACEHOLDER, "").contains("{{"), "{}", prompt);
+        assert!(prompt.contains("- 5s parses to 5"));
+    }
+}
@@ -0,0 +1,38 @@
+// src/scripts/undo.rs
+//! `undo`: revert the last auto-update (files and project.json) from its snapshot.
+
+use anyhow::{Context, Result};
+
+use crate::auto_update::undo;
+use crate::context::AppContext;
+use crate::hints;
+
+pub fn run(ctx: &AppContext) -> Result<()> {
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let Some((dir, manifest)) = undo::last(&root)? else {
+        println!("ℹ️ Nothing to undo: no auto-update snapshots in {}", undo::UNDO_DIR);
+        return Ok(());
+    };
+
+    println!("↩️  Last auto-update: {} ({})", manifest.label, manifest.created.format("%Y-%m-%d %H:%M:%S UTC"));
+    for file in &manifest.files {
+        let action = if file.existed { "restore" } else { "delete" };
+        println!("   - {} {}", action, file.path.display());
+    }
+    if !ctx.prompter.confirm(&format!("👉 Revert these {} file(s)?", manifest.files.len()))? {
+        println!("❌ Undo cancelled");
+        return Ok(());
+    }
+    if ctx.dry_run {
+        println!("🔍 Dry run: no files restored");
+        return Ok(());
+    }
+
+    undo::restore(&dir, &manifest)?;
+    println!("✅ Reverted {} file(s) from {}", manifest.files.len(), manifest.label);
+    hints::print_next_steps(None, &[
+        ("git status", "Check the working tree"),
+        ("env-coach undo", "Revert the auto-update before this one"),
+    ]);
+    Ok(())
+}
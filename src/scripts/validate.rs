@@ -0,0 +1,553 @@
+// src/scripts/validate.rs
+//! `env-coach validate`: check project.json against the JSON Schema generated from
+//! `Project`, plus references the schema cannot express (sprints, dependencies, epics).
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::config::{self, FileFormat, Project};
+use crate::context::AppContext;
+use crate::hints;
+
+/// JSON Schema of project.json.
+pub fn project_schema() -> Value {
+    serde_json::to_value(schemars::schema_for!(Project)).expect("schema serializes to JSON")
+}
+
+#[derive(Debug, PartialEq)]
+enum Severity {
+    Error,
+    /// Accepted by env-coach but probably a mistake (e.g. a misspelled field, which is ignored)
+    Warning,
+}
+
+#[derive(Debug, PartialEq)]
+struct Problem {
+    severity: Severity,
+    /// JSON Pointer to the offending value, e.g. `/backlog/3/status`
+    pointer: String,
+    message: String,
+}
+
+fn problem(severity: Severity, pointer: &str, message: String) -> Problem {
+    Problem { severity, pointer: pointer.to_string(), message }
+}
+
+/// RFC 6901 escaping of one pointer segment.
+fn pointer_segment(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(value: &Value, expected: &str) -> bool {
+    let actual = type_name(value);
+    actual == expected || (expected == "number" && actual == "integer")
+}
+
+/// Walks a value alongside the (schemars-generated) schema node describing it.
+struct SchemaChecker<'a> {
+    definitions: &'a Map<String, Value>,
+}
+
+impl SchemaChecker<'_> {
+    fn resolve<'s>(&'s self, schema: &'s Value) -> &'s Value {
+        match schema.get("$ref").and_then(Value::as_str).and_then(|r| r.strip_prefix("#/definitions/")) {
+            Some(name) => self.definitions.get(name).map(|def| self.resolve(def)).unwrap_or(schema),
+            None => schema,
+        }
+    }
+
+    fn check(&self, value: &Value, schema: &Value, pointer: &str, problems: &mut Vec<Problem>) {
+        let schema = self.resolve(schema);
+
+        if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+            for part in all_of {
+                self.check(value, part, pointer, problems);
+            }
+        }
+        // Option<T> is `anyOf: [T, null]`; report against the branch that fits best
+        if let Some(branches) = schema.get("anyOf").or_else(|| schema.get("oneOf")).and_then(Value::as_array) {
+            let best = branches.iter()
+                .map(|branch| {
+                    let mut found = vec![];
+                    self.check(value, branch, pointer, &mut found);
+                    found
+                })
+                .min_by_key(|found| found.iter().filter(|p| p.severity == Severity::Error).count());
+            problems.extend(best.unwrap_or_default());
+        }
+
+        if let Some(expected) = schema.get("type") {
+            let allowed: Vec<&str> = match expected {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+                _ => vec![],
+            };
+            if !allowed.is_empty() && !allowed.iter().any(|t| matches_type(value, t)) {
+                problems.push(problem(Severity::Error, pointer, format!("expected {}, found {}", allowed.join(" or "), type_name(value))));
+                return;
+            }
+        }
+
+        if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+            if !options.contains(value) {
+                let names: Vec<String> = options.iter().map(|o| o.as_str().map(String::from).unwrap_or_else(|| o.to_string())).collect();
+                problems.push(problem(Severity::Error, pointer, format!("unknown value {} (expected one of: {})", value, names.join(", "))));
+            }
+        }
+
+        if let (Some(minimum), Some(number)) = (schema.get("minimum").and_then(Value::as_f64), value.as_f64()) {
+            if number < minimum {
+                problems.push(problem(Severity::Error, pointer, format!("{} is below the minimum of {}", number, minimum)));
+            }
+        }
+
+        if schema.get("format").and_then(Value::as_str) == Some("date-time") {
+            if let Some(text) = value.as_str() {
+                if chrono::DateTime::parse_from_rfc3339(text).is_err() {
+                    problems.push(problem(Severity::Error, pointer, format!("\"{}\" is not an RFC 3339 date-time (e.g. 2025-01-31T09:00:00Z)", text)));
+                }
+            }
+        }
+
+        match value {
+            Value::Object(fields) => self.check_object(fields, schema, pointer, problems),
+            Value::Array(elements) => {
+                if let Some(items) = schema.get("items") {
+                    for (i, element) in elements.iter().enumerate() {
+                        self.check(element, items, &format!("{}/{}", pointer, i), problems);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_object(&self, fields: &Map<String, Value>, schema: &Value, pointer: &str, problems: &mut Vec<Problem>) {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !fields.contains_key(name) {
+                    problems.push(problem(Severity::Error, pointer, format!("missing required field '{}'", name)));
+                }
+            }
+        }
+        for (name, field) in fields {
+            let field_pointer = format!("{}/{}", pointer, pointer_segment(name));
+            match (properties.and_then(|p| p.get(name)), schema.get("additionalProperties")) {
+                (Some(property), _) => self.check(field, property, &field_pointer, problems),
+                (None, Some(additional)) if additional.is_object() => self.check(field, additional, &field_pointer, problems),
+                (None, Some(Value::Bool(true))) => {}
+                (None, _) if properties.is_some() => {
+                    let known: Vec<&str> = properties.map(|p| p.keys().map(String::as_str).collect()).unwrap_or_default();
+                    let hint = closest(name, &known).map(|k| format!("; did you mean '{}'?", k)).unwrap_or_default();
+                    problems.push(problem(Severity::Warning, &field_pointer, format!("unknown field '{}' is ignored{}", name, hint)));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Known field within two edits of `name`, for typo hints.
+fn closest<'a>(name: &str, known: &[&'a str]) -> Option<&'a str> {
+    known.iter().copied()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            current.push((previous[j] + usize::from(ca != *cb)).min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+fn check_schema(document: &Value, schema: &Value) -> Vec<Problem> {
+    let empty = Map::new();
+    let checker = SchemaChecker { definitions: schema.get("definitions").and_then(Value::as_object).unwrap_or(&empty) };
+    let mut problems = vec![];
+    checker.check(document, schema, "", &mut problems);
+    problems
+}
+
+/// References between items and sprints that must point at something that exists, and item
+/// IDs (stories, bugs, epics and tasks, archived or not) that must be unique. Sprints may
+/// still list archived items; a backlog item's epic and dependencies must be in the backlog,
+/// as `edit-item` requires. `extra_ids` are items known from elsewhere (a shared workspace).
+fn check_references(document: &Value, extra_ids: &HashSet<String>) -> Vec<Problem> {
+    let ids_in = |list: &str, id_of: fn(&Value) -> Option<&Value>| -> HashSet<String> {
+        document.get(list).and_then(Value::as_array).into_iter().flatten()
+            .filter_map(|entry| id_of(entry).and_then(Value::as_str).map(String::from))
+            .collect()
+    };
+    let mut backlog_ids = ids_in("backlog", |item| item.get("id"));
+    backlog_ids.extend(extra_ids.iter().cloned());
+    let mut item_ids = backlog_ids.clone();
+    item_ids.extend(ids_in("archive", |entry| entry.get("id")));
+    let epic_ids: HashSet<String> = document.get("backlog").and_then(Value::as_array).into_iter().flatten()
+        .filter(|item| item.get("item_type").and_then(Value::as_str) == Some("Epic"))
+        .filter_map(|item| item.get("id").and_then(Value::as_str).map(String::from))
+        .chain(extra_ids.iter().cloned())
+        .collect();
+    let sprint_ids = ids_in("sprints", |sprint| sprint.get("id"));
+
+    let mut problems = vec![];
+    let mut first_use: HashMap<&str, String> = HashMap::new();
+    for list in ["backlog", "archive"] {
+        for (i, item) in document.get(list).and_then(Value::as_array).into_iter().flatten().enumerate() {
+            let Some(id) = item.get("id").and_then(Value::as_str) else { continue };
+            let pointer = format!("/{}/{}/id", list, i);
+            match first_use.get(id) {
+                Some(first) => problems.push(problem(Severity::Error, &pointer, format!("'{}' is already the ID of {}", id, first.trim_end_matches("/id")))),
+                None => {
+                    first_use.insert(id, pointer);
+                }
+            }
+        }
+    }
+
+    let mut check = |pointer: String, value: Option<&Value>, known: &HashSet<String>, what: &str| {
+        if let Some(id) = value.and_then(Value::as_str) {
+            if !known.contains(id) {
+                problems.push(problem(Severity::Error, &pointer, format!("'{}' is not {}", id, what)));
+            }
+        }
+    };
+
+    check("/current_sprint".to_string(), document.get("current_sprint"), &sprint_ids, "a sprint");
+    for (i, item) in document.get("backlog").and_then(Value::as_array).into_iter().flatten().enumerate() {
+        check(format!("/backlog/{}/sprint", i), item.get("sprint"), &sprint_ids, "a sprint");
+        // Tasks from `breakdown` belong to the story they were split from; the rest to an epic
+        let parent = item.get("parent").filter(|parent| !parent.is_null());
+        check(format!("/backlog/{}/parent", i), parent, &backlog_ids, "in the backlog");
+        if item.get("item_type").and_then(Value::as_str) != Some("Task")
+            && parent.and_then(Value::as_str).is_some_and(|parent| backlog_ids.contains(parent)) {
+            check(format!("/backlog/{}/parent", i), parent, &epic_ids, "an epic");
+        }
+        for (j, dependency) in item.get("dependencies").and_then(Value::as_array).into_iter().flatten().enumerate() {
+            check(format!("/backlog/{}/dependencies/{}", i, j), Some(dependency), &backlog_ids, "in the backlog");
+        }
+    }
+    for (i, sprint) in document.get("sprints").and_then(Value::as_array).into_iter().flatten().enumerate() {
+        for list in ["stories", "tasks"] {
+            for (j, id) in sprint.get(list).and_then(Value::as_array).into_iter().flatten().enumerate() {
+                check(format!("/sprints/{}/{}/{}", i, list, j), Some(id), &item_ids, "a backlog item");
+            }
+        }
+    }
+    problems
+}
+
+/// Line (1-based) where each value starts, keyed by JSON Pointer; for object members the
+/// line of the key. Only called on text serde_json already parsed.
+fn value_lines(text: &str) -> HashMap<String, usize> {
+    struct Scanner<'a> {
+        bytes: &'a [u8],
+        pos: usize,
+        line: usize,
+        lines: HashMap<String, usize>,
+    }
+
+    impl Scanner<'_> {
+        fn skip_whitespace(&mut self) {
+            while let Some(&b) = self.bytes.get(self.pos) {
+                if b == b'\n' {
+                    self.line += 1;
+                } else if !b.is_ascii_whitespace() {
+                    break;
+                }
+                self.pos += 1;
+            }
+        }
+
+        fn string(&mut self) -> String {
+            let mut out = vec![];
+            self.pos += 1; // opening quote
+            while let Some(&b) = self.bytes.get(self.pos) {
+                self.pos += 1;
+                match b {
+                    b'"' => break,
+                    b'\\' => {
+                        if let Some(&escaped) = self.bytes.get(self.pos) {
+                            out.push(escaped);
+                            self.pos += 1;
+                        }
+                    }
+                    _ => out.push(b),
+                }
+            }
+            String::from_utf8_lossy(&out).into_owned()
+        }
+
+        fn value(&mut self, pointer: String) {
+            self.skip_whitespace();
+            self.lines.entry(pointer.clone()).or_insert(self.line);
+            match self.bytes.get(self.pos) {
+                Some(b'{') => {
+                    self.pos += 1;
+                    loop {
+                        self.skip_whitespace();
+                        match self.bytes.get(self.pos) {
+                            Some(b'"') => {
+                                let line = self.line;
+                                let key = self.string();
+                                let member = format!("{}/{}", pointer, pointer_segment(&key));
+                                self.lines.insert(member.clone(), line);
+                                self.skip_whitespace();
+                                self.pos += 1; // colon
+                                self.value(member);
+                            }
+                            Some(b',') => self.pos += 1,
+                            Some(_) => {
+                                self.pos += 1; // closing brace
+                                break;
+                            }
+                            None => break,
+                        }
+                    }
+                }
+                Some(b'[') => {
+                    self.pos += 1;
+                    let mut index = 0;
+                    loop {
+                        self.skip_whitespace();
+                        match self.bytes.get(self.pos) {
+                            Some(b']') => {
+                                self.pos += 1;
+                                break;
+                            }
+                            Some(b',') => self.pos += 1,
+                            Some(_) => {
+                                self.value(format!("{}/{}", pointer, index));
+                                index += 1;
+                            }
+                            None => break,
+                        }
+                    }
+                }
+                Some(b'"') => {
+                    self.string();
+                }
+                Some(_) => {
+                    while self.bytes.get(self.pos).is_some_and(|b| !b",]}".contains(b) && !b.is_ascii_whitespace()) {
+                        self.pos += 1;
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+
+    let mut scanner = Scanner { bytes: text.as_bytes(), pos: 0, line: 1, lines: HashMap::new() };
+    scanner.value(String::new());
+    scanner.lines
+}
+
+/// Check project.json (or print the schema with `--schema`). Exits with an error when
+/// there are errors, so it can run in CI; unknown fields are only warnings.
+/// The project file as a JSON value, with the line of each value's pointer. Lines are only
+/// known for project.json; problems in project.toml are reported by pointer alone.
+pub(crate) fn read_document(path: &Path) -> Result<(Value, HashMap<String, usize>)> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}. Run 'env-coach init' first.", path.display()))?;
+    let (mut document, lines) = match FileFormat::of(path) {
+        FileFormat::Json => match serde_json::from_str(&text) {
+            Ok(document) => (document, value_lines(&text)),
+            Err(e) => anyhow::bail!("{} is not valid JSON (line {}, column {}): {}", path.display(), e.line(), e.column(), e),
+        },
+        FileFormat::Toml => match toml::from_str::<Value>(&text) {
+            Ok(document) => (document, HashMap::new()),
+            Err(e) => anyhow::bail!("{} is not valid TOML: {}", path.display(), e.message()),
+        },
+    };
+    // Items kept outside the project file by its storage layout are checked along with it
+    let layout = document.pointer("/meta/storage").and_then(Value::as_str).unwrap_or("single");
+    if layout != "single" {
+        let project = Project::read_file(path)?;
+        document["backlog"] = serde_json::to_value(&project.backlog)?;
+        document["sprints"] = serde_json::to_value(&project.sprints)?;
+    }
+    Ok((document, lines))
+}
+
+fn find_problems(ctx: &AppContext, document: &Value) -> Vec<Problem> {
+    // Items shared through a workspace are only known once the project is attached to it
+    let workspace_ids: HashSet<String> = ctx.load_project()
+        .map(|project| project.backlog.into_iter().map(|item| item.id).collect())
+        .unwrap_or_default();
+    let mut problems = check_schema(document, &project_schema());
+    problems.extend(check_references(document, &workspace_ids));
+    problems
+}
+
+/// Errors and warnings `validate` would report for the parsed project.json, for `doctor`.
+pub(crate) fn problem_counts(ctx: &AppContext, document: &Value) -> (usize, usize) {
+    let problems = find_problems(ctx, document);
+    let errors = problems.iter().filter(|p| p.severity == Severity::Error).count();
+    (errors, problems.len() - errors)
+}
+
+pub fn run(ctx: &AppContext, print_schema: bool) -> Result<()> {
+    if print_schema {
+        println!("{}", serde_json::to_string_pretty(&project_schema())?);
+        return Ok(());
+    }
+
+    let path = Project::file()?;
+    let (mut document, lines) = read_document(&path)?;
+    println!("🔎 Validating {} against the project schema...", path.display());
+    let upgrades = config::migrate_project(&mut document)?;
+    if !upgrades.is_empty() {
+        println!("🔄 Checked as upgraded to schema version {} ({}); the next save writes it that way",
+            config::PROJECT_SCHEMA_VERSION, upgrades.join("; "));
+    }
+
+    let mut problems = find_problems(ctx, &document);
+
+    if problems.is_empty() {
+        println!("✅ {} is valid", path.display());
+        return Ok(());
+    }
+
+    problems.sort_by_key(|p| lines.get(&p.pointer).copied().unwrap_or(usize::MAX));
+    let errors = problems.iter().filter(|p| p.severity == Severity::Error).count();
+    for p in &problems {
+        let icon = if p.severity == Severity::Error { "❌" } else { "⚠️" };
+        let line = lines.get(&p.pointer).map(|line| format!("line {}: ", line)).unwrap_or_default();
+        let pointer = if p.pointer.is_empty() { "/" } else { &p.pointer };
+        println!("{} {}{}: {}", icon, line, pointer, p.message);
+    }
+    println!();
+    println!("📊 {} error(s), {} warning(s)", errors, problems.len() - errors);
+    if errors > 0 {
+        hints::print_next_steps(None, &[
+            ("env-coach validate --schema > project.schema.json", "Get editor completion and checks for project.json"),
+        ]);
+        anyhow::bail!("{} has {} error(s)", path.display(), errors);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_document() -> Value {
+        let project = Project::new("Shop".to_string(), "Online shop".to_string(), None);
+        let mut document = serde_json::to_value(&project).unwrap();
+        document["backlog"] = serde_json::json!([{
+            "id": "US-001", "item_type": "UserStory", "title": "Pay", "story": "As a buyer...",
+            "acceptance_criteria": [], "priority": "High", "effort": 3, "status": "Todo",
+            "created": "2025-01-31T09:00:00Z", "sprint": null, "dependencies": []
+        }]);
+        document
+    }
+
+    #[test]
+    fn test_saved_project_is_valid() {
+        assert_eq!(check_schema(&project_document(), &project_schema()), vec![]);
+        assert_eq!(check_references(&project_document(), &HashSet::new()), vec![]);
+    }
+
+    #[test]
+    fn test_reports_bad_values_unknown_fields_and_dangling_references() {
+        let mut document = project_document();
+        document["backlog"][0]["status"] = "Blocked".into();
+        document["backlog"][0]["efort"] = 5.into();
+        document["backlog"][0]["created"] = "yesterday".into();
+        document["backlog"][0]["dependencies"] = serde_json::json!(["US-404"]);
+        document["backlog"][0]["sprint"] = "S-009".into();
+
+        let problems = check_schema(&document, &project_schema());
+        assert!(problems.contains(&problem(Severity::Error, "/backlog/0/status",
+            "unknown value \"Blocked\" (expected one of: Todo, InProgress, Review, Done)".to_string())));
+        assert!(problems.contains(&problem(Severity::Warning, "/backlog/0/efort",
+            "unknown field 'efort' is ignored; did you mean 'effort'?".to_string())));
+        assert!(problems.iter().any(|p| p.pointer == "/backlog/0/created" && p.severity == Severity::Error));
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+
+        let references = check_references(&document, &HashSet::new());
+        assert_eq!(references, vec![
+            problem(Severity::Error, "/backlog/0/sprint", "'S-009' is not a sprint".to_string()),
+            problem(Severity::Error, "/backlog/0/dependencies/0", "'US-404' is not in the backlog".to_string()),
+        ]);
+        assert!(check_references(&document, &HashSet::from(["US-404".to_string()])).len() == 1);
+    }
+
+    #[test]
+    fn test_reports_duplicate_ids_across_backlog_and_archive() {
+        let mut document = project_document();
+        let item = document["backlog"][0].clone();
+        document["backlog"].as_array_mut().unwrap().push(item.clone());
+        let mut archived = item;
+        archived["archived"] = "2025-02-01T09:00:00Z".into();
+        document["archive"] = serde_json::json!([archived]);
+
+        assert_eq!(check_references(&document, &HashSet::new()), vec![
+            problem(Severity::Error, "/backlog/1/id", "'US-001' is already the ID of /backlog/0".to_string()),
+            problem(Severity::Error, "/archive/0/id", "'US-001' is already the ID of /backlog/0".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_parents_and_dependencies_must_be_in_the_backlog() {
+        let mut document = project_document();
+        let story = document["backlog"][0].clone();
+        let mut archived_epic = story.clone();
+        archived_epic["id"] = "EPIC-001".into();
+        archived_epic["item_type"] = "Epic".into();
+        archived_epic["archived"] = "2025-02-01T09:00:00Z".into();
+        document["archive"] = serde_json::json!([archived_epic]);
+        let mut task = story.clone();
+        task["id"] = "TASK-001".into();
+        task["item_type"] = "Task".into();
+        task["parent"] = "US-001".into();
+        let mut child = story;
+        child["id"] = "US-002".into();
+        child["parent"] = "EPIC-001".into();
+        child["dependencies"] = serde_json::json!(["EPIC-001"]);
+        let mut misfiled = child.clone();
+        misfiled["id"] = "US-003".into();
+        misfiled["parent"] = "US-001".into();
+        misfiled["dependencies"] = serde_json::json!([]);
+        document["backlog"].as_array_mut().unwrap().extend([task, child, misfiled]);
+
+        assert_eq!(check_references(&document, &HashSet::new()), vec![
+            problem(Severity::Error, "/backlog/2/parent", "'EPIC-001' is not in the backlog".to_string()),
+            problem(Severity::Error, "/backlog/2/dependencies/0", "'EPIC-001' is not in the backlog".to_string()),
+            problem(Severity::Error, "/backlog/3/parent", "'US-001' is not an epic".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_value_lines() {
+        let text = "{\n  \"meta\": {\"name\": \"x\"},\n  \"backlog\": [\n    {\n      \"id\": \"US-001\",\n      \"a/b\": 1\n    }\n  ]\n}";
+        let lines = value_lines(text);
+        assert_eq!(lines.get("/meta/name"), Some(&2));
+        assert_eq!(lines.get("/backlog/0"), Some(&4));
+        assert_eq!(lines.get("/backlog/0/id"), Some(&5));
+        assert_eq!(lines.get("/backlog/0/a~1b"), Some(&6));
+    }
+}
@@ -0,0 +1,56 @@
+// src/scripts/velocity.rs
+use anyhow::{Context, Result};
+
+use crate::context::AppContext;
+use crate::hints;
+use crate::output;
+use crate::stats::{self, Trend};
+
+const BAR_WIDTH: u32 = 30;
+
+pub fn run(ctx: &AppContext, last: usize) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+
+    let sprints = stats::completed_sprints(&project, last);
+    if sprints.is_empty() {
+        println!("📭 No completed sprints yet");
+        if output::show_hints() {
+            println!("💡 Velocity is recorded when a sprint is closed: env-coach complete-sprint <id>");
+        }
+        return Ok(());
+    }
+
+    println!("🚀 Velocity (last {} completed sprint(s))", sprints.len());
+    println!();
+
+    let max_points = sprints.iter()
+        .map(|s| (s.planned_velocity as u32).max(s.actual_velocity as u32))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for sprint in &sprints {
+        let bar_len = (sprint.actual_velocity as u32 * BAR_WIDTH / max_points) as usize;
+        let hit_rate = stats::percent(sprint.actual_velocity as u32, sprint.planned_velocity as u32)
+            .map(|p| format!("{}%", p))
+            .unwrap_or_else(|| "n/a".to_string());
+        println!("   {:<6} {:<30} {:>3} / {:>3} planned ({})",
+            sprint.id, "#".repeat(bar_len), sprint.actual_velocity, sprint.planned_velocity, hit_rate);
+    }
+
+    println!();
+    if let Some(average) = stats::average_velocity(&sprints) {
+        println!("📊 Rolling average: {:.1} points per sprint", average);
+    }
+    match stats::velocity_trend(&sprints) {
+        Some(Trend::Rising) => println!("📈 Trend: rising"),
+        Some(Trend::Falling) => println!("📉 Trend: falling"),
+        Some(Trend::Stable) => println!("➡️  Trend: stable"),
+        None => println!("ℹ️  Trend: need at least 2 completed sprints"),
+    }
+
+    hints::print_next_steps(Some(&project), &[
+        ("env-coach plan-sprint --goal \"...\"", "Plan against your average velocity"),
+    ]);
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+// src/scripts/verify_task.rs
+//! `verify-task <id>`: run the test suite and report, per acceptance criterion of the item,
+//! whether the tests that look like they cover it pass.
+
+use anyhow::{Context, Result};
+
+use crate::context::AppContext;
+use crate::hints;
+use crate::scripts::fix_tests;
+
+/// Words that say nothing about which behavior a criterion or test is about
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "are", "with", "that", "this", "when", "then", "should", "must", "can",
+    "from", "into", "not", "has", "have", "user", "users", "test", "tests", "given", "its", "all",
+];
+
+#[derive(Debug, PartialEq)]
+struct TestResult {
+    name: String,
+    passed: bool,
+}
+
+/// Test names and results from cargo, pytest, go and jest output. A test reported both
+/// ways (e.g. a retried test) counts as failed.
+fn parse_test_results(output: &str) -> Vec<TestResult> {
+    let mut results: Vec<TestResult> = vec![];
+    for line in output.lines().map(str::trim) {
+        let parsed = if let Some(rest) = line.strip_prefix("test ") {
+            // cargo: `test parser::tests::parses ... ok`
+            rest.rsplit_once(" ... ").and_then(|(name, status)| match status.trim() {
+                "ok" => Some((name, true)),
+                status if status.starts_with("FAILED") => Some((name, false)),
+                _ => None,
+            })
+        } else if let Some(rest) = line.strip_prefix("FAILED ") {
+            // pytest summary: `FAILED tests/test_x.py::test_name - AssertionError`
+            Some((rest.split(" - ").next().unwrap_or(rest), false))
+        } else if let Some(name) = line.strip_suffix(" PASSED").or_else(|| line.split(" PASSED ").next().filter(|n| *n != line)) {
+            Some((name, true))
+        } else if let Some(rest) = line.strip_prefix("--- FAIL: ").or_else(|| line.strip_prefix("--- PASS: ")) {
+            // go: `--- FAIL: TestParse (0.00s)`
+            Some((rest.split(" (").next().unwrap_or(rest), line.starts_with("--- PASS")))
+        } else {
+            // jest: `✓ parses seconds (3 ms)`
+            line.strip_prefix("✓ ").or_else(|| line.strip_prefix("✕ "))
+                .map(|rest| (rest.rsplit_once(" (").map_or(rest, |(name, _)| name), line.starts_with('✓')))
+        };
+        let Some((name, passed)) = parsed else { continue };
+        match results.iter_mut().find(|result| result.name == name) {
+            Some(existing) => existing.passed &= passed,
+            None => results.push(TestResult { name: name.trim().to_string(), passed }),
+        }
+    }
+    results
+}
+
+/// Meaningful words of a criterion or test name, lowercased and stemmed;
+/// `snake_case`, `camelCase` and `a::path` all split into words.
+fn keywords(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in text.chars() {
+        if !c.is_alphanumeric() || (c.is_uppercase() && previous_lower) {
+            words.push(std::mem::take(&mut current));
+        }
+        if c.is_alphanumeric() {
+            current.extend(c.to_lowercase());
+        }
+        previous_lower = c.is_lowercase();
+    }
+    words.push(current);
+    words.into_iter()
+        .filter(|word| word.len() >= 3 && !STOPWORDS.contains(&word.as_str()))
+        .map(|word| stem(&word).to_string())
+        .collect()
+}
+
+/// `word` without a common inflection, so `parse`, `parsed` and `parses` compare equal.
+fn stem(word: &str) -> &str {
+    ["ing", "ed", "es", "s", "e"].iter()
+        .find_map(|suffix| word.strip_suffix(suffix).filter(|rest| rest.len() >= 3))
+        .unwrap_or(word)
+}
+
+/// Tests whose names share enough words with `criterion` to be about it: two words (or
+/// every word of a shorter criterion), and at most one fewer than the best match, so a
+/// test that merely touches the same feature does not count for every criterion.
+fn tests_for<'a>(criterion: &str, results: &'a [TestResult]) -> Vec<&'a TestResult> {
+    let wanted = keywords(criterion);
+    let needed = wanted.len().min(2);
+    if needed == 0 {
+        return vec![];
+    }
+    let scored: Vec<(usize, &TestResult)> = results.iter()
+        .map(|result| {
+            let name = keywords(&result.name);
+            (wanted.iter().filter(|word| name.contains(word)).count(), result)
+        })
+        .filter(|(score, _)| *score >= needed)
+        .collect();
+    let best = scored.iter().map(|(score, _)| *score).max().unwrap_or(0);
+    scored.into_iter().filter(|(score, _)| *score + 1 >= best).map(|(_, result)| result).collect()
+}
+
+/// Run the tests and map the results onto the item's acceptance criteria; on failure,
+/// offer to have the LLM diagnose and fix them as `fix-tests` does.
+pub async fn run(ctx: &AppContext, task_id: String, command: Option<String>) -> Result<()> {
+    let project = ctx.load_project().context("Failed to load project. Run 'env-coach init' first.")?;
+    let root = std::env::current_dir().context("Failed to determine project directory")?;
+    let item = project.backlog.iter()
+        .find(|item| item.id == task_id)
+        .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in backlog.", task_id))?;
+    let test_command = match command {
+        Some(command) => command,
+        None => fix_tests::detect_test_command(&root)
+            .context("Could not tell how to run the tests here. Pass one with --command \"...\"")?
+            .to_string(),
+    };
+
+    println!("🔎 Verifying {}: {}", item.id, item.title);
+    let (passed, output) = fix_tests::run_tests(&test_command)?;
+    let results = parse_test_results(&output);
+
+    println!();
+    let mut uncovered = 0;
+    let mut matched: Vec<&str> = vec![];
+    if item.acceptance_criteria.is_empty() {
+        println!("ℹ️ {} has no acceptance criteria to check the tests against", task_id);
+    } else {
+        println!("📋 Acceptance criteria:");
+    }
+    for criterion in &item.acceptance_criteria {
+        let tests = tests_for(criterion, &results);
+        matched.extend(tests.iter().map(|test| test.name.as_str()));
+        let failing: Vec<&str> = tests.iter().filter(|test| !test.passed).map(|test| test.name.as_str()).collect();
+        if tests.is_empty() {
+            uncovered += 1;
+            println!("   ❔ {} (no matching test found)", criterion);
+        } else if failing.is_empty() {
+            println!("   ✅ {} ({} passing test(s))", criterion, tests.len());
+        } else {
+            println!("   ❌ {} (failing: {})", criterion, failing.join(", "));
+        }
+    }
+    let unmatched_failures: Vec<&str> = results.iter()
+        .filter(|result| !result.passed && !matched.contains(&result.name.as_str()))
+        .map(|result| result.name.as_str())
+        .collect();
+    if !unmatched_failures.is_empty() {
+        println!("❌ Failing tests not matched to a criterion: {}", unmatched_failures.join(", "));
+    }
+
+    println!();
+    if passed {
+        println!("✅ {} passed ({} test(s))", test_command, results.len());
+        let mut next_steps = vec![];
+        if uncovered > 0 {
+            next_steps.push((format!("env-coach test-task {}", task_id), format!("Write tests for {} uncovered criteria", uncovered)));
+        }
+        next_steps.push((format!("env-coach complete-task {}", task_id), "Mark the task as done".to_string()));
+        let next_steps: Vec<(&str, &str)> = next_steps.iter().map(|(command, why)| (command.as_str(), why.as_str())).collect();
+        hints::print_next_steps(Some(&project), &next_steps);
+        return Ok(());
+    }
+
+    println!("❌ {} failed", test_command);
+    if !ctx.prompter.confirm("👉 Ask the LLM for a fix?")? {
+        hints::print_next_steps(Some(&project), &[
+            ("env-coach fix-tests", "Let the LLM fix the failing tests later"),
+        ]);
+        return Ok(());
+    }
+    let project = fix_tests::diagnose_and_fix(ctx, project.clone(), &root, &test_command, &output).await?;
+    hints::print_next_steps(Some(&project), &[
+        ("git diff", "Review the applied fixes"),
+        (format!("env-coach verify-task {}", task_id).as_str(), "Check the criteria again"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_test_results() {
+        let output = "\
+running 2 tests
+test parser::tests::parses_seconds ... ok
+test parser::tests::rejects_unknown_units ... FAILED
+test slow ... ignored
+tests/test_api.py::test_login PASSED                [ 50%]
+FAILED tests/test_api.py::test_logout - AssertionError
+--- FAIL: TestRefresh (0.00s)
+  ✓ renders header (3 ms)
+";
+        let results = parse_test_results(output);
+        let summary: Vec<(&str, bool)> = results.iter().map(|r| (r.name.as_str(), r.passed)).collect();
+        assert_eq!(summary, vec![
+            ("parser::tests::parses_seconds", true),
+            ("parser::tests::rejects_unknown_units", false),
+            ("tests/test_api.py::test_login", true),
+            ("tests/test_api.py::test_logout", false),
+            ("TestRefresh", false),
+            ("renders header", true),
+        ]);
+    }
+
+    #[test]
+    fn test_tests_for_criterion() {
+        let results = parse_test_results("test parser::tests::rejects_unknown_units ... FAILED\ntest parser::tests::parses_seconds ... ok\n--- PASS: TestUnknownUnitsRejected (0.00s)\n");
+        let names = |criterion: &str| tests_for(criterion, &results).iter().map(|r| r.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names("Unknown units are rejected with an error"), vec!["parser::tests::rejects_unknown_units", "TestUnknownUnitsRejected"]);
+        assert_eq!(names("Durations in seconds are parsed"), vec!["parser::tests::parses_seconds"]);
+        assert!(names("Works offline").is_empty());
+        // A weaker overlap loses to the test that is clearly about the criterion
+        let results = parse_test_results("test doubling_returns_twice_the_value ... ok\ntest doubles_negative_numbers ... ok\n");
+        assert_eq!(tests_for("Doubling a number returns twice its value", &results).len(), 1);
+        assert_eq!(keywords("parsesSeconds and parsed"), vec!["pars", "second", "pars"]);
+    }
+}
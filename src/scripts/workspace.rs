@@ -0,0 +1,205 @@
+// src/scripts/workspace.rs
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+
+use crate::config::{BacklogItem, ItemType, Priority, Project, Status};
+use crate::context::AppContext;
+use crate::hints;
+use crate::workspace::{self, Workspace, WorkspaceItem, WorkspaceRepo, WORKSPACE_FILE};
+
+fn current_workspace() -> Result<(PathBuf, Workspace)> {
+    let cwd = std::env::current_dir().context("Failed to determine current directory")?;
+    let path = workspace::find_workspace_file(&cwd)
+        .ok_or_else(|| anyhow::anyhow!("No {} found here or in a parent directory. Run 'env-coach workspace init' first.", WORKSPACE_FILE))?;
+    let ws = Workspace::load(&path)?;
+    Ok((path, ws))
+}
+
+/// Create a workspace file in the current directory, registering every subdirectory
+/// that already has a project.json.
+pub fn init(ctx: &AppContext, name: Option<String>) -> Result<()> {
+    let cwd = std::env::current_dir().context("Failed to determine current directory")?;
+    let path = cwd.join(WORKSPACE_FILE);
+    if path.exists() {
+        anyhow::bail!("{} already exists in {}", WORKSPACE_FILE, cwd.display());
+    }
+
+    let mut repos: Vec<WorkspaceRepo> = std::fs::read_dir(&cwd)
+        .context("Failed to list current directory")?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| Project::file_in(&entry.path()).is_ok_and(|path| path.is_file()))
+        .map(|entry| WorkspaceRepo {
+            name: workspace::read_repo_project(&entry.path())
+                .map(|project| project.meta.name)
+                .unwrap_or_else(|_| entry.file_name().to_string_lossy().into_owned()),
+            path: PathBuf::from(entry.file_name()),
+        })
+        .collect();
+    repos.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let name = name.unwrap_or_else(|| {
+        cwd.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| "workspace".to_string())
+    });
+    let ws = Workspace { name, repos, backlog: vec![] };
+
+    println!("🗂️  Workspace '{}' with {} repositor{}", ws.name, ws.repos.len(), if ws.repos.len() == 1 { "y" } else { "ies" });
+    for repo in &ws.repos {
+        println!("   - {} ({})", repo.name, repo.path.display());
+    }
+    if ctx.dry_run {
+        println!("🔍 Dry run: {} not written", WORKSPACE_FILE);
+        return Ok(());
+    }
+    ws.save(&path)?;
+
+    println!("✅ Created {}", path.display());
+    hints::print_next_steps(None, &[
+        ("env-coach workspace add-item <repo> \"<title>\"", "Add a shared backlog item"),
+        ("env-coach portfolio", "Overview of all repositories"),
+    ]);
+    Ok(())
+}
+
+/// Add an item to the shared backlog, tagged with its target repository.
+pub fn add_item(ctx: &AppContext, repo: String, title: String, description: Option<String>, effort: u32) -> Result<()> {
+    let (path, mut ws) = current_workspace()?;
+    if !ws.repos.iter().any(|r| r.name == repo) {
+        println!("⚠️  '{}' is not a registered repository of workspace '{}'; it will only show up in a project named '{}'", repo, ws.name, repo);
+    }
+
+    let id = ws.next_item_id();
+    let item = BacklogItem {
+        id: id.clone(),
+        item_type: ItemType::UserStory,
+        title: title.clone(),
+        story: description.unwrap_or_else(|| title.clone()),
+        acceptance_criteria: vec![],
+        priority: Priority::Medium,
+        effort,
+        status: Status::Todo,
+        created: Utc::now(),
+        sprint: None,
+        dependencies: vec![],
+        started_at: None,
+        completed_at: None,
+        component: None,
+        parent: None,
+        tags: vec![],
+        external_ref: None,
+        history: vec![],
+        review: None,
+        criteria_check: None,
+        extra: Default::default(),
+    };
+    ws.backlog.push(WorkspaceItem { repo: repo.clone(), item });
+
+    if ctx.dry_run {
+        println!("🔍 Dry run: {} not written", WORKSPACE_FILE);
+    } else {
+        ws.save(&path)?;
+    }
+    println!("✅ Added {} to the shared backlog for {}: {}", id, repo, title);
+    hints::print_next_steps(None, &[
+        (format!("cd <{}> && env-coach list-backlog", repo).as_str(), "The item appears in the repository's backlog"),
+    ]);
+    Ok(())
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct RepoSummary {
+    todo: usize,
+    in_progress: usize,
+    done: usize,
+    done_points: u32,
+    total_points: u32,
+}
+
+impl RepoSummary {
+    fn add(&mut self, item: &BacklogItem) {
+        match item.status {
+            Status::Todo => self.todo += 1,
+            Status::InProgress | Status::Review => self.in_progress += 1,
+            Status::Done => {
+                self.done += 1;
+                self.done_points += item.effort;
+            }
+        }
+        self.total_points += item.effort;
+    }
+}
+
+fn summarize<'a>(items: impl Iterator<Item = &'a BacklogItem>) -> RepoSummary {
+    let mut summary = RepoSummary::default();
+    items.for_each(|item| summary.add(item));
+    summary
+}
+
+fn print_summary_row(label: &str, summary: &RepoSummary, note: &str) {
+    println!("   {:<24} {:>5} {:>7} {:>5} {:>9}  {}",
+        label, summary.todo, summary.in_progress, summary.done,
+        format!("{}/{}", summary.done_points, summary.total_points), note);
+}
+
+/// Aggregate the shared backlog and every repository's own backlog.
+pub fn portfolio(_ctx: &AppContext) -> Result<()> {
+    let (path, ws) = current_workspace()?;
+    let workspace_dir = path.parent().unwrap_or(Path::new("."));
+
+    println!("📊 Portfolio: {}", ws.name);
+    println!("   {:<24} {:>5} {:>7} {:>5} {:>9}", "Repository", "Todo", "Active", "Done", "Points");
+
+    let mut repo_names: Vec<String> = ws.repos.iter().map(|r| r.name.clone()).collect();
+    for entry in &ws.backlog {
+        if !repo_names.contains(&entry.repo) {
+            repo_names.push(entry.repo.clone());
+        }
+    }
+
+    let mut total = RepoSummary::default();
+    for name in &repo_names {
+        let shared: Vec<&BacklogItem> = ws.items_for(name).collect();
+        let repo_project = ws.repos.iter()
+            .find(|r| &r.name == name)
+            .map(|r| workspace::read_repo_project(&workspace_dir.join(&r.path)));
+
+        let (local, note) = match &repo_project {
+            Some(Ok(project)) => {
+                let sprint = project.current_sprint.as_deref().map(|id| format!("sprint {}", id)).unwrap_or_default();
+                let local: Vec<&BacklogItem> = project.backlog.iter()
+                    .filter(|item| !shared.iter().any(|s| s.id == item.id))
+                    .collect();
+                (local, format!("{} shared {}", shared.len(), sprint).trim_end().to_string())
+            }
+            Some(Err(_)) => (vec![], "⚠️ project.json unreadable".to_string()),
+            None => (vec![], "not registered".to_string()),
+        };
+
+        let summary = summarize(shared.iter().copied().chain(local.iter().copied()));
+        print_summary_row(name, &summary, &note);
+        for item in shared.iter().chain(local.iter()) {
+            total.add(item);
+        }
+    }
+    println!("   {}", "-".repeat(54));
+    print_summary_row("Total", &total, "");
+
+    hints::print_next_steps(None, &[
+        ("env-coach workspace add-item <repo> \"<title>\"", "Add a shared backlog item"),
+    ]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_counts_review_as_active() {
+        let mut items = Vec::new();
+        for (status, effort) in [(Status::Todo, 3), (Status::Review, 2), (Status::Done, 5)] {
+            items.push(BacklogItem::test(&format!("WS-{}", effort)).with_effort(effort).with_status(status));
+        }
+        assert_eq!(summarize(items.iter()), RepoSummary { todo: 1, in_progress: 1, done: 1, done_points: 5, total_points: 10 });
+    }
+}
@@ -0,0 +1,352 @@
+// src/stats.rs
+//! Derived numbers about the backlog and sprints.
+//!
+//! Counts, point sums, completion percentages, epic rollups and velocity are computed here
+//! only, so `status`, `list-backlog`, `show-sprint`, `velocity` and `stats --format json`
+//! report the same figures. Epics are left out of the work statistics: their effort is covered by their
+//! children.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::config::{BacklogItem, ItemType, Project, Sprint, SprintStatus, Status};
+
+/// Completed sprints included in the velocity figures, as in `env-coach velocity`.
+pub const VELOCITY_WINDOW: usize = 5;
+
+/// `part` as a whole-number percentage of `whole`; `None` when `whole` is 0.
+pub fn percent(part: u32, whole: u32) -> Option<u32> {
+    (part * 100).checked_div(whole)
+}
+
+/// Items or points per status.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct StatusCounts {
+    pub total: u32,
+    pub todo: u32,
+    pub in_progress: u32,
+    pub review: u32,
+    pub done: u32,
+}
+
+impl StatusCounts {
+    fn add(&mut self, status: &Status, amount: u32) {
+        self.total += amount;
+        match status {
+            Status::Todo => self.todo += amount,
+            Status::InProgress => self.in_progress += amount,
+            Status::Review => self.review += amount,
+            Status::Done => self.done += amount,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct WorkStats {
+    pub items: StatusCounts,
+    pub points: StatusCounts,
+    /// Done points as a share of all points
+    pub completion_percent: Option<u32>,
+}
+
+impl WorkStats {
+    /// Statistics over `items`, skipping epics.
+    pub fn of<'a>(items: impl IntoIterator<Item = &'a BacklogItem>) -> Self {
+        let mut stats = WorkStats::default();
+        for item in items.into_iter().filter(|item| !matches!(item.item_type, ItemType::Epic)) {
+            stats.items.add(&item.status, 1);
+            stats.points.add(&item.status, item.effort);
+        }
+        stats.completion_percent = percent(stats.points.done, stats.points.total);
+        stats
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SprintStats {
+    pub id: String,
+    pub goal: String,
+    pub status: SprintStatus,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    /// Points committed at planning and credited since, as recorded on the sprint
+    pub committed_points: u32,
+    pub completed_points: u32,
+    pub completion_percent: u32,
+    /// The sprint's items as they are now
+    pub work: WorkStats,
+    pub days_total: i64,
+    /// Days since the start, capped at the sprint length
+    pub days_elapsed: i64,
+}
+
+pub fn sprint_stats(project: &Project, sprint: &Sprint, now: DateTime<Utc>) -> SprintStats {
+    let days_total = (sprint.end_date - sprint.start_date).num_days().max(0);
+    SprintStats {
+        id: sprint.id.clone(),
+        goal: sprint.goal.clone(),
+        status: sprint.status.clone(),
+        start_date: sprint.start_date,
+        end_date: sprint.end_date,
+        committed_points: sprint.total_points,
+        completed_points: sprint.completed_points,
+        completion_percent: percent(sprint.completed_points, sprint.total_points).unwrap_or(0),
+        work: WorkStats::of(project.backlog.iter().filter(|item| item.sprint.as_deref() == Some(sprint.id.as_str()))),
+        days_total,
+        days_elapsed: (now - sprint.start_date).num_days().clamp(0, days_total),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Trend {
+    Rising,
+    Falling,
+    Stable,
+}
+
+/// The last `count` completed sprints, oldest first.
+pub fn completed_sprints(project: &Project, count: usize) -> Vec<&Sprint> {
+    let mut sprints: Vec<&Sprint> = project.sprints.iter()
+        .filter(|s| matches!(s.status, SprintStatus::Completed | SprintStatus::Complete))
+        .collect();
+    sprints.sort_by_key(|s| s.end_date);
+    let skip = sprints.len().saturating_sub(count);
+    sprints.into_iter().skip(skip).collect()
+}
+
+pub fn average_velocity(sprints: &[&Sprint]) -> Option<f64> {
+    if sprints.is_empty() {
+        return None;
+    }
+    let total: u32 = sprints.iter().map(|s| s.actual_velocity as u32).sum();
+    Some(total as f64 / sprints.len() as f64)
+}
+
+/// Compare the average of the newer half of the sprints with the older half.
+/// A change of less than 10% is reported as stable.
+pub fn velocity_trend(sprints: &[&Sprint]) -> Option<Trend> {
+    if sprints.len() < 2 {
+        return None;
+    }
+    let (older, newer) = sprints.split_at(sprints.len() / 2);
+    let older_avg = average_velocity(older)?;
+    let newer_avg = average_velocity(newer)?;
+
+    let threshold = (older_avg * 0.1).max(0.5);
+    Some(if newer_avg - older_avg > threshold {
+        Trend::Rising
+    } else if older_avg - newer_avg > threshold {
+        Trend::Falling
+    } else {
+        Trend::Stable
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VelocityStats {
+    /// Completed sprints the figures are based on (at most `VELOCITY_WINDOW`)
+    pub sprints: usize,
+    pub average: Option<f64>,
+    pub last: Option<u32>,
+    pub trend: Option<Trend>,
+}
+
+pub fn velocity_stats(project: &Project) -> VelocityStats {
+    let sprints = completed_sprints(project, VELOCITY_WINDOW);
+    VelocityStats {
+        sprints: sprints.len(),
+        average: average_velocity(&sprints),
+        last: sprints.last().map(|sprint| sprint.actual_velocity as u32),
+        trend: velocity_trend(&sprints),
+    }
+}
+
+/// Points and item counts of an epic's children.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct EpicProgress {
+    pub done_items: usize,
+    pub total_items: usize,
+    pub done_points: u32,
+    pub total_points: u32,
+}
+
+impl EpicProgress {
+    pub fn percent(&self) -> u32 {
+        percent(self.done_points, self.total_points).unwrap_or(0)
+    }
+}
+
+/// Roll up the backlog items whose `parent` is `epic_id`.
+pub fn epic_progress(project: &Project, epic_id: &str) -> EpicProgress {
+    let mut progress = EpicProgress::default();
+    for child in project.backlog.iter().filter(|item| item.parent.as_deref() == Some(epic_id)) {
+        progress.total_items += 1;
+        progress.total_points += child.effort;
+        if matches!(child.status, Status::Done) {
+            progress.done_items += 1;
+            progress.done_points += child.effort;
+        }
+    }
+    progress
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct SprintCounts {
+    pub total: usize,
+    pub planning: usize,
+    pub active: usize,
+    pub completed: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectStats {
+    pub backlog: WorkStats,
+    pub archived_items: usize,
+    pub epics: usize,
+    pub sprints: SprintCounts,
+    pub active_sprint: Option<SprintStats>,
+    pub velocity: VelocityStats,
+}
+
+pub fn sprint_counts(project: &Project) -> SprintCounts {
+    let mut counts = SprintCounts { total: project.sprints.len(), ..Default::default() };
+    for sprint in &project.sprints {
+        match sprint.status {
+            SprintStatus::Planning => counts.planning += 1,
+            SprintStatus::Active => counts.active += 1,
+            SprintStatus::Completed | SprintStatus::Complete => counts.completed += 1,
+            SprintStatus::Review => {}
+        }
+    }
+    counts
+}
+
+pub fn project_stats(project: &Project, now: DateTime<Utc>) -> ProjectStats {
+    ProjectStats {
+        backlog: WorkStats::of(&project.backlog),
+        archived_items: project.archive.len(),
+        epics: project.backlog.iter().filter(|item| matches!(item.item_type, ItemType::Epic)).count(),
+        sprints: sprint_counts(project),
+        active_sprint: project.sprints.iter()
+            .find(|sprint| matches!(sprint.status, SprintStatus::Active))
+            .map(|sprint| sprint_stats(project, sprint, now)),
+        velocity: velocity_stats(project),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_work_stats_skip_epics() {
+        let items = [
+            BacklogItem::test("EPIC-001").with_type(ItemType::Epic).with_effort(40),
+            BacklogItem::test("US-001").with_status(Status::Done).with_effort(5),
+            BacklogItem::test("US-002").with_status(Status::InProgress),
+            BacklogItem::test("BUG-001").with_type(ItemType::Bug).with_effort(2),
+        ];
+        let stats = WorkStats::of(&items);
+        assert_eq!(stats.items, StatusCounts { total: 3, todo: 1, in_progress: 1, review: 0, done: 1 });
+        assert_eq!(stats.points, StatusCounts { total: 10, todo: 2, in_progress: 3, review: 0, done: 5 });
+        assert_eq!(stats.completion_percent, Some(50));
+        assert_eq!(WorkStats::of(&[]).completion_percent, None);
+    }
+
+    #[test]
+    fn test_project_stats() {
+        let now = Utc::now();
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog = vec![
+            BacklogItem::test("US-001").with_status(Status::Done).with_effort(5).with_sprint("SPRINT-002"),
+            BacklogItem::test("US-002").with_sprint("SPRINT-002"),
+            BacklogItem::test("US-003").with_effort(8),
+        ];
+        let sprint = |id: &str, status, days_ago: i64, velocity: u8| Sprint {
+            id: id.to_string(),
+            goal: String::new(),
+            start_date: now - Duration::days(days_ago),
+            end_date: now - Duration::days(days_ago) + Duration::days(14),
+            status,
+            total_points: 8,
+            completed_points: 5,
+            tasks: vec![],
+            stories: vec![],
+            planned_velocity: 8,
+            actual_velocity: velocity,
+            retrospective: None,
+        };
+        project.sprints = vec![sprint("SPRINT-001", SprintStatus::Completed, 20, 6), sprint("SPRINT-002", SprintStatus::Active, 4, 0)];
+
+        let stats = project_stats(&project, now);
+        assert_eq!(stats.backlog.points.total, 16);
+        assert_eq!(stats.sprints, SprintCounts { total: 2, planning: 0, active: 1, completed: 1 });
+        let active = stats.active_sprint.unwrap();
+        assert_eq!(active.id, "SPRINT-002");
+        assert_eq!(active.completion_percent, 62);
+        assert_eq!(active.work.items.total, 2);
+        assert_eq!((active.days_elapsed, active.days_total), (4, 14));
+        assert_eq!(stats.velocity, VelocityStats { sprints: 1, average: Some(6.0), last: Some(6), trend: None });
+    }
+
+    fn completed_sprint(id: &str, day: i64, planned: u8, actual: u8, status: SprintStatus) -> Sprint {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::days(day);
+        Sprint {
+            id: id.to_string(),
+            goal: "Goal".to_string(),
+            start_date: start,
+            end_date: start + Duration::days(14),
+            status,
+            total_points: planned as u32,
+            completed_points: actual as u32,
+            tasks: vec![],
+            stories: vec![],
+            planned_velocity: planned,
+            actual_velocity: actual,
+            retrospective: None,
+        }
+    }
+
+    #[test]
+    fn test_completed_sprints_takes_latest_in_date_order() {
+        let mut project = Project::new("Velocity".to_string(), "".to_string(), None);
+        project.sprints = vec![
+            completed_sprint("S-003", 28, 10, 9, SprintStatus::Completed),
+            completed_sprint("S-001", 0, 10, 5, SprintStatus::Completed),
+            completed_sprint("S-002", 14, 10, 7, SprintStatus::Complete),
+            completed_sprint("S-004", 42, 10, 0, SprintStatus::Active),
+        ];
+        let ids: Vec<&str> = completed_sprints(&project, 2).iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["S-002", "S-003"]);
+    }
+
+    #[test]
+    fn test_average_and_trend() {
+        let s1 = completed_sprint("S-001", 0, 10, 4, SprintStatus::Completed);
+        let s2 = completed_sprint("S-002", 14, 10, 6, SprintStatus::Completed);
+        let s3 = completed_sprint("S-003", 28, 10, 8, SprintStatus::Completed);
+        let s4 = completed_sprint("S-004", 42, 10, 10, SprintStatus::Completed);
+
+        assert_eq!(average_velocity(&[&s1, &s2, &s3, &s4]), Some(7.0));
+        assert_eq!(velocity_trend(&[&s1, &s2, &s3, &s4]), Some(Trend::Rising));
+        assert_eq!(velocity_trend(&[&s4, &s3, &s2, &s1]), Some(Trend::Falling));
+        assert_eq!(velocity_trend(&[&s2, &s2]), Some(Trend::Stable));
+        assert_eq!(velocity_trend(&[&s1]), None);
+        assert_eq!(average_velocity(&[]), None);
+    }
+
+    #[test]
+    fn test_epic_progress_rolls_up_children() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        project.backlog.push(BacklogItem::test("EPIC-001").with_type(ItemType::Epic).with_effort(0));
+        project.backlog.push(BacklogItem::test("US-001").with_parent("EPIC-001").with_status(Status::Done));
+        project.backlog.push(BacklogItem::test("US-002").with_parent("EPIC-001").with_effort(5).with_status(Status::InProgress));
+        project.backlog.push(BacklogItem::test("US-003").with_effort(8).with_status(Status::Done));
+
+        let progress = epic_progress(&project, "EPIC-001");
+        assert_eq!(progress, EpicProgress { done_items: 1, total_items: 2, done_points: 3, total_points: 8 });
+        assert_eq!(progress.percent(), 37);
+        assert_eq!(epic_progress(&project, "EPIC-404").percent(), 0);
+    }
+}
@@ -0,0 +1,318 @@
+// src/storage/mod.rs
+//! Where a project's backlog items and sprints are kept.
+//!
+//! By default everything lives in project.json. With `"storage": "split"` in the meta
+//! section, every backlog item and sprint gets its own file under `.env-coach/backlog/` and
+//! `.env-coach/sprints/` (in the project file's format), so a change to one item is a
+//! change to one small file in git, and two branches editing different items merge cleanly.
+//! With `"storage": "sqlite"` they live in `.env-coach/project.db`, indexed so large backlogs
+//! can be filtered and searched without reading every item. In both cases project.json
+//! keeps the meta section, the current sprint and the archive.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{self, BacklogItem, FileFormat, Project, ProjectMeta, Sprint};
+use crate::scripts::filter::BacklogFilter;
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub const BACKLOG_DIR: &str = ".env-coach/backlog";
+pub const SPRINTS_DIR: &str = ".env-coach/sprints";
+pub const DATABASE_FILE: &str = ".env-coach/project.db";
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, JsonSchema, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageLayout {
+    /// Everything in project.json
+    #[default]
+    Single,
+    /// One file per backlog item and sprint under .env-coach/
+    Split,
+    /// Backlog items and sprints in .env-coach/project.db
+    Sqlite,
+}
+
+/// Backlog items found by `Storage::query`.
+#[derive(Debug)]
+pub struct Matches {
+    /// In the order `--sort` asks for, backlog order otherwise
+    pub items: Vec<BacklogItem>,
+    /// Items in the whole backlog
+    pub total: usize,
+}
+
+/// Reads and writes the parts of a project kept outside the project file's meta section.
+pub trait Storage {
+    /// Complete `project`, as parsed from the project file, with what is kept elsewhere.
+    fn load(&self, project: &mut Project) -> Result<()>;
+    /// Write `project` out, the project file last.
+    fn save(&self, project: &Project) -> Result<()>;
+    /// Every file a save of `project` may create, change or remove, for crash recovery and undo.
+    fn files(&self, project: &Project) -> Result<Vec<PathBuf>>;
+    /// The backlog items matching `filter` whose title, story or acceptance criteria contain
+    /// every one of `terms` (ignoring ASCII case), when the storage can find them without
+    /// reading every item. `None` means load the project and filter it in memory.
+    fn query(&self, _filter: &BacklogFilter, _terms: &[&str]) -> Result<Option<Matches>> {
+        Ok(None)
+    }
+}
+
+/// The storage for the project file at `path`, as its meta section asks for.
+pub fn for_project(meta: &ProjectMeta, path: &Path) -> Result<Box<dyn Storage>> {
+    let root = path.parent().unwrap_or(Path::new("")).to_path_buf();
+    Ok(match meta.storage.unwrap_or_default() {
+        StorageLayout::Single => Box::new(SingleFile { path: path.to_path_buf(), root }),
+        StorageLayout::Split => Box::new(SplitFiles { path: path.to_path_buf(), root }),
+        #[cfg(feature = "sqlite")]
+        StorageLayout::Sqlite => Box::new(sqlite::Database { path: path.to_path_buf(), root }),
+        #[cfg(not(feature = "sqlite"))]
+        StorageLayout::Sqlite => anyhow::bail!(
+            "{} keeps its backlog in SQLite, but this env-coach was built without the `sqlite` feature",
+            path.display()
+        ),
+    })
+}
+
+/// The item and sprint files under `root`, in either format.
+fn split_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for dir in [BACKLOG_DIR, SPRINTS_DIR] {
+        let dir = root.join(dir);
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+            let path = entry?.path();
+            let known = FileFormat::ALL.iter().any(|format| path.extension().is_some_and(|ext| ext == format.extension()));
+            if path.is_file() && known {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Files under `root` that belong to layouts other than `layout`, e.g. after switching.
+fn leftovers(root: &Path, layout: StorageLayout) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    if layout != StorageLayout::Split {
+        files.extend(split_files(root)?);
+    }
+    let database = root.join(DATABASE_FILE);
+    if layout != StorageLayout::Sqlite && database.is_file() {
+        files.push(database);
+    }
+    Ok(files)
+}
+
+/// Fail when another layout's files are around: saving would drop them, and with them
+/// whatever they hold.
+fn refuse_leftovers(path: &Path, root: &Path, layout: StorageLayout) -> Result<()> {
+    if let Some(leftover) = leftovers(root, layout)?.first() {
+        let hint = match layout {
+            StorageLayout::Single => "keeps everything itself",
+            StorageLayout::Split | StorageLayout::Sqlite => "keeps its items elsewhere",
+        };
+        anyhow::bail!(
+            "{} {}, but {} exists. Set \"storage\" in its meta section to the layout of that file, or move it away.",
+            path.display(), hint, leftover.display()
+        );
+    }
+    Ok(())
+}
+
+/// Remove another layout's files once their content has been saved in this one.
+fn remove_leftovers(root: &Path, layout: StorageLayout) -> Result<()> {
+    for file in leftovers(root, layout)? {
+        fs::remove_file(&file).with_context(|| format!("Failed to remove {}", file.display()))?;
+    }
+    Ok(())
+}
+
+/// `project` without its backlog items and sprints, as the project file of a layout that
+/// keeps them elsewhere.
+fn without_items(project: &Project) -> Project {
+    let mut rest = project.clone();
+    rest.backlog.clear();
+    rest.sprints.clear();
+    rest
+}
+
+/// Everything in the project file.
+pub struct SingleFile {
+    path: PathBuf,
+    root: PathBuf,
+}
+
+impl Storage for SingleFile {
+    fn load(&self, _project: &mut Project) -> Result<()> {
+        refuse_leftovers(&self.path, &self.root, StorageLayout::Single)
+    }
+
+    fn save(&self, project: &Project) -> Result<()> {
+        let content = FileFormat::of(&self.path).serialize(project).context("Failed to serialize project")?;
+        config::write_atomically(&self.path, &content)?;
+        // Back from another layout: its content is in project.json now
+        remove_leftovers(&self.root, StorageLayout::Single)
+    }
+
+    fn files(&self, _project: &Project) -> Result<Vec<PathBuf>> {
+        let mut files = vec![self.path.clone()];
+        files.extend(leftovers(&self.root, StorageLayout::Single)?);
+        Ok(files)
+    }
+}
+
+/// Backlog items and sprints in a file each.
+pub struct SplitFiles {
+    path: PathBuf,
+    root: PathBuf,
+}
+
+impl SplitFiles {
+    fn format(&self) -> FileFormat {
+        FileFormat::of(&self.path)
+    }
+
+    fn file(&self, dir: &str, id: &str) -> Result<PathBuf> {
+        if id.is_empty() || id.starts_with('.') || id.contains(['/', '\\']) {
+            anyhow::bail!("'{}' cannot be used as a file name, so it cannot be stored in {}", id, dir);
+        }
+        Ok(self.root.join(dir).join(format!("{}.{}", id, self.format().extension())))
+    }
+
+    fn item_files(&self, project: &Project) -> Result<Vec<PathBuf>> {
+        let mut files = vec![];
+        for item in &project.backlog {
+            files.push(self.file(BACKLOG_DIR, &item.id)?);
+        }
+        for sprint in &project.sprints {
+            files.push(self.file(SPRINTS_DIR, &sprint.id)?);
+        }
+        Ok(files)
+    }
+
+    /// The entries in `dir`. Files in the other format (left by `convert`) are read too, unless
+    /// the same entry exists in the project file's format; the next save rewrites them.
+    fn read_dir<T: DeserializeOwned>(&self, dir: &str) -> Result<Vec<T>> {
+        let files = split_files(&self.root)?;
+        let mut values = vec![];
+        for path in files.iter().filter(|path| path.starts_with(self.root.join(dir))) {
+            let format = FileFormat::of(path);
+            if format != self.format() && files.contains(&path.with_extension(self.format().extension())) {
+                continue;
+            }
+            let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+            values.push(format.parse(&content).with_context(|| format!("Failed to parse {}", path.display()))?);
+        }
+        Ok(values)
+    }
+}
+
+impl Storage for SplitFiles {
+    fn load(&self, project: &mut Project) -> Result<()> {
+        refuse_leftovers(&self.path, &self.root, StorageLayout::Split)?;
+        // Entries still in the project file (e.g. right after switching by hand) are kept,
+        // but the separate files win
+        let items: Vec<BacklogItem> = self.read_dir(BACKLOG_DIR)?;
+        project.backlog.retain(|item| !items.iter().any(|stored| stored.id == item.id));
+        project.backlog.extend(items);
+        project.backlog.sort_by(|a, b| (a.created, &a.id).cmp(&(b.created, &b.id)));
+
+        let sprints: Vec<Sprint> = self.read_dir(SPRINTS_DIR)?;
+        project.sprints.retain(|sprint| !sprints.iter().any(|stored| stored.id == sprint.id));
+        project.sprints.extend(sprints);
+        project.sprints.sort_by(|a, b| (a.start_date, &a.id).cmp(&(b.start_date, &b.id)));
+        Ok(())
+    }
+
+    fn save(&self, project: &Project) -> Result<()> {
+        let format = self.format();
+        let mut written = vec![];
+        let contents = project.backlog.iter()
+            .map(|item| Ok((self.file(BACKLOG_DIR, &item.id)?, format.serialize(item)?)))
+            .chain(project.sprints.iter().map(|sprint| Ok((self.file(SPRINTS_DIR, &sprint.id)?, format.serialize(sprint)?))))
+            .collect::<Result<Vec<_>>>()?;
+        for (path, content) in contents {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+            }
+            // Untouched items keep their files as they are
+            if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+                config::replace_atomically(&path, &content)?;
+            }
+            written.push(path);
+        }
+        // Removed, archived or renamed items
+        for file in split_files(&self.root)? {
+            if !written.contains(&file) {
+                fs::remove_file(&file).with_context(|| format!("Failed to remove {}", file.display()))?;
+            }
+        }
+
+        let content = format.serialize(&without_items(project)).context("Failed to serialize project")?;
+        config::write_atomically(&self.path, &content)?;
+        remove_leftovers(&self.root, StorageLayout::Split)
+    }
+
+    fn files(&self, project: &Project) -> Result<Vec<PathBuf>> {
+        let mut files = vec![self.path.clone()];
+        let existing = split_files(&self.root)?.into_iter().chain(leftovers(&self.root, StorageLayout::Split)?);
+        for file in self.item_files(project)?.into_iter().chain(existing) {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Priority;
+
+    #[test]
+    fn test_split_layout_round_trips_and_switches_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("project.json");
+        let mut project = Project::new("Shop".to_string(), "Online shop".to_string(), None);
+        project.meta.storage = Some(StorageLayout::Split);
+        for (id, title) in [("US-001", "Pay by card"), ("BUG-001", "Cart loses items")] {
+            let created = chrono::Utc::now() + chrono::Duration::seconds(project.backlog.len() as i64);
+            project.backlog.push(BacklogItem::test(id).with_title(title).with_story("").with_priority(Priority::High).with_created(created));
+        }
+
+        let storage = for_project(&project.meta, &path).unwrap();
+        storage.save(&project).unwrap();
+        assert!(dir.path().join(BACKLOG_DIR).join("US-001.json").is_file());
+        let saved: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["backlog"], serde_json::json!([]));
+
+        let mut loaded = Project::new("Shop".to_string(), String::new(), None);
+        storage.load(&mut loaded).unwrap();
+        let ids: Vec<&str> = loaded.backlog.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["US-001", "BUG-001"]);
+
+        // A removed item loses its file
+        project.backlog.remove(1);
+        storage.save(&project).unwrap();
+        assert_eq!(split_files(dir.path()).unwrap(), vec![dir.path().join(BACKLOG_DIR).join("US-001.json")]);
+
+        // The single-file layout refuses stray item files, and takes them back in on save
+        project.meta.storage = None;
+        let single = for_project(&project.meta, &path).unwrap();
+        assert!(single.load(&mut loaded).unwrap_err().to_string().contains("US-001.json"));
+        assert_eq!(single.files(&project).unwrap().len(), 2);
+        single.save(&project).unwrap();
+        assert!(split_files(dir.path()).unwrap().is_empty());
+        single.load(&mut loaded).unwrap();
+    }
+}
@@ -0,0 +1,300 @@
+// src/storage/sqlite.rs
+//! The `sqlite` layout: backlog items and sprints in `.env-coach/project.db`. Each row keeps
+//! the entry as JSON next to indexed columns for the list-backlog filters and sort keys, so
+//! a query reads and parses only the rows it returns.
+
+use anyhow::{Context, Result};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, Params};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::{leftovers, refuse_leftovers, remove_leftovers, without_items, Matches, Storage, StorageLayout, DATABASE_FILE};
+use crate::config::{self, BacklogItem, FileFormat, Project, Sprint};
+use crate::scripts::filter::{BacklogFilter, SortKey};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS items (
+    id TEXT PRIMARY KEY,
+    position INTEGER NOT NULL,
+    item_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    priority INTEGER NOT NULL,
+    effort INTEGER NOT NULL,
+    created INTEGER NOT NULL,
+    sprint TEXT,
+    search_text TEXT NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS items_by_status ON items (status, priority);
+CREATE INDEX IF NOT EXISTS items_by_priority ON items (priority);
+CREATE INDEX IF NOT EXISTS items_by_sprint ON items (sprint);
+CREATE INDEX IF NOT EXISTS items_by_type ON items (item_type);
+CREATE TABLE IF NOT EXISTS item_tags (
+    item_id TEXT NOT NULL,
+    tag TEXT NOT NULL COLLATE NOCASE
+);
+CREATE INDEX IF NOT EXISTS item_tags_by_tag ON item_tags (tag);
+CREATE INDEX IF NOT EXISTS item_tags_by_item ON item_tags (item_id);
+CREATE TABLE IF NOT EXISTS sprints (
+    id TEXT PRIMARY KEY,
+    position INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+";
+
+/// How an enum value is stored in its column: its name as serialized, e.g. `InProgress`.
+fn label<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        other => other.map(|value| value.to_string()).unwrap_or_default(),
+    }
+}
+
+/// What `search` looks through: title, story and acceptance criteria.
+fn search_text(item: &BacklogItem) -> String {
+    let mut fields = vec![item.title.as_str(), item.story.as_str()];
+    fields.extend(item.acceptance_criteria.iter().map(String::as_str));
+    fields.join("\n")
+}
+
+/// `term` as a LIKE pattern matching it anywhere, with `%`, `_` and the escape character escaped.
+fn like_pattern(term: &str) -> String {
+    let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// The JSON in the first column of each row, parsed.
+fn rows<T: DeserializeOwned>(connection: &Connection, sql: &str, params: impl Params) -> Result<Vec<T>> {
+    let mut statement = connection.prepare(sql)?;
+    let rows = statement.query_map(params, |row| row.get::<_, String>(0))?;
+    rows.map(|data| Ok(serde_json::from_str(&data?)?)).collect()
+}
+
+/// Backlog items and sprints in an SQLite database next to the project file.
+pub struct Database {
+    pub(super) path: PathBuf,
+    pub(super) root: PathBuf,
+}
+
+impl Database {
+    fn file(&self) -> PathBuf {
+        self.root.join(DATABASE_FILE)
+    }
+
+    fn open(&self) -> Result<Connection> {
+        let file = self.file();
+        if let Some(dir) = file.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let connection = Connection::open(&file).with_context(|| format!("Failed to open {}", file.display()))?;
+        connection.execute_batch(SCHEMA).with_context(|| format!("Failed to set up {}", file.display()))?;
+        Ok(connection)
+    }
+
+    fn write_items(&self, connection: &mut Connection, project: &Project) -> Result<()> {
+        let transaction = connection.transaction()?;
+        let mut stored: HashMap<String, (i64, String)> = HashMap::new();
+        {
+            let mut statement = transaction.prepare("SELECT id, position, data FROM items")?;
+            let rows = statement.query_map([], |row| Ok((row.get::<_, String>(0)?, (row.get(1)?, row.get(2)?))))?;
+            for row in rows {
+                let (id, entry) = row?;
+                stored.insert(id, entry);
+            }
+        }
+
+        for (position, item) in project.backlog.iter().enumerate() {
+            let position = position as i64;
+            let data = serde_json::to_string(item)?;
+            // Untouched items keep their rows as they are
+            if stored.remove(&item.id).is_some_and(|(at, stored_data)| at == position && stored_data == data) {
+                continue;
+            }
+            transaction.execute(
+                "INSERT OR REPLACE INTO items (id, position, item_type, status, priority, effort, created, sprint, search_text, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    item.id, position, label(&item.item_type), label(&item.status), item.priority.rank(),
+                    item.effort, item.created.timestamp_micros(), item.sprint, search_text(item), data,
+                ],
+            )?;
+            transaction.execute("DELETE FROM item_tags WHERE item_id = ?1", [&item.id])?;
+            for tag in &item.tags {
+                transaction.execute("INSERT INTO item_tags (item_id, tag) VALUES (?1, ?2)", [&item.id, tag])?;
+            }
+        }
+        // Removed, archived or renamed items
+        for id in stored.keys() {
+            transaction.execute("DELETE FROM items WHERE id = ?1", [id])?;
+            transaction.execute("DELETE FROM item_tags WHERE item_id = ?1", [id])?;
+        }
+
+        // Sprints are few; they are simply written again
+        transaction.execute("DELETE FROM sprints", [])?;
+        for (position, sprint) in project.sprints.iter().enumerate() {
+            transaction.execute(
+                "INSERT INTO sprints (id, position, data) VALUES (?1, ?2, ?3)",
+                params![sprint.id, position as i64, serde_json::to_string(sprint)?],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
+
+impl Storage for Database {
+    fn load(&self, project: &mut Project) -> Result<()> {
+        refuse_leftovers(&self.path, &self.root, StorageLayout::Sqlite)?;
+        if !self.file().is_file() {
+            return Ok(());
+        }
+        let file = self.file();
+        let connection = self.open()?;
+        let items: Vec<BacklogItem> = rows(&connection, "SELECT data FROM items ORDER BY position", [])
+            .with_context(|| format!("Failed to read the backlog from {}", file.display()))?;
+        let sprints: Vec<Sprint> = rows(&connection, "SELECT data FROM sprints ORDER BY position", [])
+            .with_context(|| format!("Failed to read the sprints from {}", file.display()))?;
+
+        // Entries still in the project file (e.g. right after switching by hand) are kept,
+        // but the database wins
+        project.backlog.retain(|item| !items.iter().any(|stored| stored.id == item.id));
+        project.backlog.splice(0..0, items);
+        project.sprints.retain(|sprint| !sprints.iter().any(|stored| stored.id == sprint.id));
+        project.sprints.splice(0..0, sprints);
+        Ok(())
+    }
+
+    fn save(&self, project: &Project) -> Result<()> {
+        let mut connection = self.open()?;
+        self.write_items(&mut connection, project)
+            .with_context(|| format!("Failed to write the backlog to {}", self.file().display()))?;
+        let content = FileFormat::of(&self.path).serialize(&without_items(project)).context("Failed to serialize project")?;
+        config::write_atomically(&self.path, &content)?;
+        remove_leftovers(&self.root, StorageLayout::Sqlite)
+    }
+
+    fn files(&self, _project: &Project) -> Result<Vec<PathBuf>> {
+        let mut files = vec![self.path.clone(), self.file()];
+        files.extend(leftovers(&self.root, StorageLayout::Sqlite)?);
+        Ok(files)
+    }
+
+    fn query(&self, filter: &BacklogFilter, terms: &[&str]) -> Result<Option<Matches>> {
+        if !self.file().is_file() {
+            return Ok(None);
+        }
+        let connection = self.open()?;
+        let total: i64 = connection.query_row("SELECT COUNT(*) FROM items", [], |row| row.get(0))?;
+
+        let mut conditions = vec![];
+        let mut values = vec![];
+        if let Some(status) = &filter.status {
+            conditions.push("status = ?");
+            values.push(Value::Text(label(status)));
+        }
+        if let Some(priority) = &filter.priority {
+            conditions.push("priority = ?");
+            values.push(Value::Integer(priority.rank().into()));
+        }
+        if let Some(sprint) = &filter.sprint {
+            conditions.push("sprint = ?");
+            values.push(Value::Text(sprint.clone()));
+        }
+        if let Some(item_type) = &filter.item_type {
+            conditions.push("item_type = ?");
+            values.push(Value::Text(label(item_type)));
+        }
+        if let Some(tag) = &filter.tag {
+            conditions.push("id IN (SELECT item_id FROM item_tags WHERE tag = ?)");
+            values.push(Value::Text(tag.clone()));
+        }
+        // Only the key is matched here; callers filter the rows again, which compares the
+        // values the way `--field` does in memory
+        for (key, _) in filter.field_filters() {
+            conditions.push("EXISTS (SELECT 1 FROM json_each(data, '$.extra') WHERE key = ?)");
+            values.push(Value::Text(key.to_string()));
+        }
+        for term in terms {
+            // LIKE ignores ASCII case, as the in-memory search does
+            conditions.push("search_text LIKE ? ESCAPE '\\'");
+            values.push(Value::Text(like_pattern(term)));
+        }
+        let order = match filter.sort {
+            Some(SortKey::Priority) => "priority, position",
+            Some(SortKey::Effort) => "effort DESC, position",
+            Some(SortKey::Created) => "created, position",
+            None => "position",
+        };
+        let condition = if conditions.is_empty() { String::new() } else { format!(" WHERE {}", conditions.join(" AND ")) };
+        let sql = format!("SELECT data FROM items{} ORDER BY {}", condition, order);
+        let items = rows(&connection, &sql, params_from_iter(values))
+            .with_context(|| format!("Failed to query {}", self.file().display()))?;
+        Ok(Some(Matches { items, total: total as usize }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ItemType, Priority, Status};
+
+    #[test]
+    fn test_database_round_trips_and_answers_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = Database { path: dir.path().join("project.json"), root: dir.path().to_path_buf() };
+        let item = |id: &str| BacklogItem::test(id).with_story("As a buyer I want it").with_criteria(&["Works for 100% of buyers"]);
+        let mut project = Project::new("Shop".to_string(), String::new(), None);
+        project.backlog = vec![
+            item("US-001").with_title("Pay by card").with_priority(Priority::Low).with_tags(&["payments"]),
+            item("US-002").with_title("Refund a payment").with_priority(Priority::Critical).with_tags(&["Payments", "admin"]),
+            item("US-003").with_title("Wish list").with_priority(Priority::High).with_status(Status::Done),
+        ];
+        project.backlog[2].extra.insert("customer".to_string(), serde_json::json!("Acme"));
+        database.save(&project).unwrap();
+        let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&database.path).unwrap()).unwrap();
+        assert_eq!(saved["backlog"], serde_json::json!([]));
+
+        let mut loaded = Project::new("Shop".to_string(), String::new(), None);
+        database.load(&mut loaded).unwrap();
+        assert_eq!(loaded.backlog.iter().map(|item| item.id.as_str()).collect::<Vec<_>>(), vec!["US-001", "US-002", "US-003"]);
+
+        let ids = |filter: &BacklogFilter, terms: &[&str]| {
+            let matches = database.query(filter, terms).unwrap().unwrap();
+            assert_eq!(matches.total, 3);
+            matches.items.into_iter().map(|item| item.id).collect::<Vec<_>>()
+        };
+        let todo_by_priority = BacklogFilter { status: Some(Status::Todo), sort: Some(SortKey::Priority), ..Default::default() };
+        assert_eq!(ids(&todo_by_priority, &[]), vec!["US-002", "US-001"]);
+        let tagged = BacklogFilter { tag: Some("PAYMENTS".to_string()), ..Default::default() };
+        assert_eq!(ids(&tagged, &[]), vec!["US-001", "US-002"]);
+        assert_eq!(ids(&BacklogFilter::default(), &["CARD", "buyer"]), vec!["US-001"]);
+        assert_eq!(ids(&BacklogFilter::default(), &["100%"]).len(), 3);
+        assert!(ids(&BacklogFilter::default(), &["1_0"]).is_empty());
+        // Rows need the field; its value is compared again in memory by the caller
+        let for_customer = BacklogFilter { fields: vec!["customer=Globex".to_string()], ..Default::default() };
+        assert_eq!(ids(&for_customer, &[]), vec!["US-003"]);
+
+        // Edits and removals reach the database
+        project.backlog.remove(0);
+        project.backlog[0].status = Status::Done;
+        project.backlog[0].tags.clear();
+        database.save(&project).unwrap();
+        let done = BacklogFilter { status: Some(Status::Done), ..Default::default() };
+        assert_eq!(database.query(&done, &[]).unwrap().unwrap().items.len(), 2);
+        assert!(ids_of(&database, &tagged).is_empty());
+    }
+
+    fn ids_of(database: &Database, filter: &BacklogFilter) -> Vec<String> {
+        database.query(filter, &[]).unwrap().unwrap().items.into_iter().map(|item| item.id).collect()
+    }
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("100%_done\\"), "%100\\%\\_done\\\\%");
+        assert_eq!(label(&ItemType::UserStory), "UserStory");
+        assert_eq!(label(&Status::InProgress), "InProgress");
+    }
+}
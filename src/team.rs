@@ -0,0 +1,121 @@
+// src/team.rs
+//! Lightweight team mode: who is acting, whether they are a maintainer, and the per-item
+//! history recorded whenever the project is saved.
+
+use chrono::{DateTime, Utc};
+
+use crate::config::{BacklogItem, GlobalConfig, ItemEvent, Project, TeamConfig};
+
+/// The acting user: `user` from the global config, then `git config user.name`, then $USER.
+pub fn resolve_user(global_config: &GlobalConfig) -> String {
+    if let Some(user) = global_config.user.as_ref().filter(|u| !u.trim().is_empty()) {
+        return user.trim().to_string();
+    }
+    if let Ok(name) = crate::git::run(&["config", "user.name"]) {
+        if !name.trim().is_empty() {
+            return name.trim().to_string();
+        }
+    }
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Without team mode (or with no maintainers listed) everyone is a maintainer.
+pub fn is_maintainer(team: Option<&TeamConfig>, user: &str) -> bool {
+    match team {
+        Some(team) if !team.maintainers.is_empty() => team.maintainers.iter().any(|m| m.eq_ignore_ascii_case(user)),
+        _ => true,
+    }
+}
+
+/// Human-readable field changes between two versions of an item (history excluded).
+pub fn describe_changes(before: &BacklogItem, after: &BacklogItem) -> Vec<String> {
+    let mut changes = Vec::new();
+    if before.status != after.status {
+        changes.push(format!("status {:?} → {:?}", before.status, after.status));
+    }
+    if before.sprint != after.sprint {
+        changes.push(format!(
+            "sprint {} → {}",
+            before.sprint.as_deref().unwrap_or("none"),
+            after.sprint.as_deref().unwrap_or("none")
+        ));
+    }
+    if before.effort != after.effort {
+        changes.push(format!("effort {} → {}", before.effort, after.effort));
+    }
+    if before.priority != after.priority {
+        changes.push(format!("priority {:?} → {:?}", before.priority, after.priority));
+    }
+    if before.title != after.title {
+        changes.push("title edited".to_string());
+    }
+    if before.story != after.story {
+        changes.push("story edited".to_string());
+    }
+    if before.acceptance_criteria != after.acceptance_criteria {
+        changes.push("acceptance criteria edited".to_string());
+    }
+    if before.dependencies != after.dependencies {
+        changes.push("dependencies edited".to_string());
+    }
+    if before.parent != after.parent {
+        changes.push(format!("epic → {}", after.parent.as_deref().unwrap_or("none")));
+    }
+    if before.tags != after.tags {
+        changes.push("tags edited".to_string());
+    }
+    if before.component != after.component {
+        changes.push(format!("component → {}", after.component.as_deref().unwrap_or("none")));
+    }
+    changes
+}
+
+/// Append a history entry to every item that is new or changed compared to `previous`.
+pub fn record_history(project: &mut Project, previous: &[BacklogItem], user: &str, at: DateTime<Utc>) {
+    for item in project.backlog.iter_mut() {
+        let change = match previous.iter().find(|p| p.id == item.id) {
+            None => "created".to_string(),
+            Some(before) => {
+                let changes = describe_changes(before, item);
+                if changes.is_empty() {
+                    continue;
+                }
+                changes.join("; ")
+            }
+        };
+        item.history.push(ItemEvent { at, user: user.to_string(), change });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Status;
+
+    #[test]
+    fn test_is_maintainer() {
+        let team = TeamConfig { maintainers: vec!["Alice".to_string()], require_acknowledgment: true };
+        assert!(is_maintainer(Some(&team), "alice"));
+        assert!(!is_maintainer(Some(&team), "bob"));
+        assert!(is_maintainer(None, "bob"));
+        assert!(is_maintainer(Some(&TeamConfig { maintainers: vec![], require_acknowledgment: true }), "bob"));
+    }
+
+    #[test]
+    fn test_record_history_only_touches_new_and_changed_items() {
+        let mut project = Project::new("P".to_string(), "".to_string(), None);
+        let previous = vec![BacklogItem::test("US-001"), BacklogItem::test("US-002")];
+        project.backlog = previous.clone();
+        project.backlog[1].status = Status::InProgress;
+        project.backlog[1].effort = 5;
+        project.backlog.push(BacklogItem::test("US-003"));
+
+        record_history(&mut project, &previous, "bob", Utc::now());
+        assert!(project.backlog[0].history.is_empty());
+        assert_eq!(project.backlog[1].history[0].user, "bob");
+        assert_eq!(project.backlog[1].history[0].change, "status Todo → InProgress; effort 3 → 5");
+        assert_eq!(project.backlog[2].history[0].change, "created");
+    }
+}
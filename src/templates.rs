@@ -1,6 +1,30 @@
 // src/templates.rs
+use crate::config::{BacklogItem, Project, Sprint, Status};
+use crate::stats::{self, WorkStats};
+
 pub struct Templates;
 
+/// Escape a value for a Markdown table cell.
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Story text and acceptance criteria as a checklist, ticked once the item is done.
+fn markdown_item_details(item: &BacklogItem) -> String {
+    let mut out = format!("\n### {} — {}\n\n", item.id, item.title);
+    if !item.story.is_empty() {
+        out.push_str(&format!("{}\n", item.story));
+    }
+    if !item.acceptance_criteria.is_empty() {
+        let mark = if matches!(item.status, Status::Done) { "x" } else { " " };
+        out.push('\n');
+        for criterion in &item.acceptance_criteria {
+            out.push_str(&format!("- [{}] {}\n", mark, criterion));
+        }
+    }
+    out
+}
+
 impl Templates {
     #[allow(dead_code)]
     pub fn project_json_template(project_name: &str) -> String {
@@ -54,33 +78,6 @@ Keep stories focused and implementable. Aim for 2-5 user stories.
 Return only valid JSON."#
     }
 
-    #[allow(dead_code)]
-    pub fn code_reviewer_prompt() -> &'static str {
-        r#"You are an experienced Rust code reviewer.
-
-REQUIREMENTS: {requirements}
-
-CODE TO REVIEW:
-```rust
-{code}
-```
-
-Please provide a code review focusing on:
-1. Correctness and adherence to requirements
-2. Rust best practices and idioms
-3. Error handling
-4. Code organization and readability
-5. Performance considerations
-
-Respond with:
-- Overall quality score (1-10)
-- Specific strengths
-- Specific areas for improvement
-- Suggested fixes (if any)
-
-Be constructive and specific in your feedback."#
-    }
-
     #[allow(dead_code)]
     pub fn task_assistant_prompt() -> &'static str {
         r#"You are a Rust development assistant.
@@ -105,6 +102,12 @@ Focus on practical, working code that follows Rust best practices."#
 # env-coach
 .env-coach/cache/
 .env-coach/logs/
+.env-coach/recovery/
+.env-coach/undo/
+.env-coach/backups/
+.env-coach/.lock
+project.json.bak
+project.toml.bak
 "#
     }
 
@@ -196,51 +199,7 @@ Generated by env-coach v0.1.0
     }
 
     pub fn default_requirements_analyst_prompt_content() -> String {
-        // This is the new default prompt content.
-        // Note the change in "priority" to match the enum.
-        r#"You are a software engineering expert analyzing requirements for a project.
-
-PROJECT CONTEXT:
-- Project Name: {{project_name}}
-- Description: {{project_description}}
-- Tech Stack: {{tech_stack}}
-- Primary Language: {{primary_language}}
-- Tags: {{tags}}
-
-REQUIREMENT TO ANALYZE: "{{requirement}}"
-
-Please respond with a JSON object containing a single key "user_stories".
-The value of "user_stories" should be an array of JSON objects, where each object represents a user story.
-Each user story object must have the following fields:
-- "title": A brief, descriptive title for the user story.
-- "story": The user story in the format "As a [user type], I want [goal] so that [reason/benefit]".
-- "priority": The priority of the user story. Valid values are "Critical", "High", "Medium", "Low".
-- "effort": An estimated effort for the user story, as an integer (e.g., 1, 2, 3, 5, 8).
-- "acceptance_criteria": An array of strings, where each string is a specific, testable acceptance criterion.
-
-Focus on:
-1. Breaking down the requirement into appropriate user stories.
-2. Writing clear and concise acceptance criteria.
-3. Estimating effort and assigning priority based on typical software development projects.
-4. Tailoring acceptance criteria to be actionable and testable, considering the project's tech stack if relevant.
-
-Generate 2-5 user stories that comprehensively cover the requirement.
-Return *only* the valid JSON object, starting with `{` and ending with `}`. Do not include any other text or explanations outside the JSON structure.
-
-Example of a single user story object:
-{
-  "title": "User Login Feature",
-  "story": "As a registered user, I want to log in to the application so that I can access my personalized content.",
-  "priority": "High",
-  "effort": 3,
-  "acceptance_criteria": [
-    "User can enter credentials (username/password).",
-    "System validates credentials against stored user data.",
-    "Successful login redirects to the user dashboard.",
-    "Failed login shows an appropriate error message."
-  ]
-}
-"#.to_string()
+        include_str!("../assets/prompts/requirements_analyst.md").to_string()
     }
 
     pub fn create_default_prompt_if_missing(prompts_dir: &std::path::Path, file_name: &str, content: String) -> anyhow::Result<()> {
@@ -248,162 +207,208 @@ Example of a single user story object:
         if !prompt_path.exists() {
             std::fs::write(&prompt_path, content)
                 .map_err(|e| anyhow::anyhow!("Failed to write default prompt file at {:?}: {}", prompt_path, e))?;
-            println!("📄 Created default prompt: {}", prompt_path.display());
+            if crate::output::enabled(crate::output::Topic::Files, crate::output::Level::Normal) {
+                println!("📄 Created default prompt: {}", prompt_path.display());
+            }
         }
         Ok(())
     }
 
     pub fn default_sprint_planner_prompt_content() -> String {
-        r#"You are an expert Agile Sprint Planner. Your task is to help select user stories from the project backlog that best fit the given sprint goal and constraints.
-
-**Sprint Goal:**
-{{sprint_goal}}
-
-{{#if sprint_duration_days}}
-**Sprint Duration:** {{sprint_duration_days}} days
-{{/if}}
-
-{{#if target_capacity_points}}
-**Target Capacity (Story Points):** {{target_capacity_points}}
-{{/if}}
-
-**Project Backlog (User Stories):**
-The following user stories are available in the backlog. Each item is listed with its ID, Title, Priority, and Effort (in story points).
-
-{{#each backlog_items}}
-- **ID:** {{this.id}}
-  - **Title:** {{this.title}}
-  {{#if this.story_summary}}
-  - **Summary:** {{this.story_summary}}
-  {{/if}}
-  - **Priority:** {{this.priority}}
-  - **Effort:** {{this.effort}} points
-{{/each}}
-
-**Instructions:**
-
-1.  **Analyze the Sprint Goal:** Understand the primary objective for this sprint.
-2.  **Review Backlog Items:** Evaluate each user story's relevance to the sprint goal.
-3.  **Consider Constraints:**
-    *   Prioritize higher priority items (Critical > High > Medium > Low).
-    *   If target capacity is provided, try to select stories whose total effort is close to this capacity without significantly exceeding it.
-    *   If sprint duration is provided, consider what can realistically be achieved.
-4.  **Selection:** Choose a set of user story IDs that form a cohesive and achievable plan for the sprint, directly contributing to the sprint goal.
-5.  **Output:** Respond with a JSON object containing a single key: `"suggested_story_ids"`. The value should be an array of strings, where each string is the ID of a suggested user story.
-    Optionally, you can include a `"reasoning"` field with a brief explanation for your selection.
-
-**Example Output Format:**
-```json
-{
-  "suggested_story_ids": [
-    "US-001",
-    "US-003",
-    "US-008"
-  ],
-  "reasoning": "Selected stories directly address the core aspects of the sprint goal 'Implement User Authentication'. US-001 is critical, and US-003 and US-008 are high-priority supporting features with a combined effort that fits the typical capacity."
-}
-```
-
-Return *only* the valid JSON object. Do not include any other text or explanations outside the JSON structure.
-If no stories seem appropriate or fit the capacity, return an empty array for `suggested_story_ids`.
-"#.to_string()
+        include_str!("../assets/prompts/sprint_planner.md").to_string()
     }
 
     pub fn default_task_assistant_prompt_content() -> String {
-        r#"You are an expert pair programmer and software development assistant, specializing in {{primary_language}}.
-Your goal is to provide actionable suggestions, including code, dependency updates, and file modifications, to help implement a given task.
-
-**Project Context:**
-- Project Name: {{project_name}}
-- Description: {{project_description}}
-- Tech Stack: {{tech_stack}}
-- Primary Language: {{primary_language}}
-- Tags: {{tags}}
-
-**Current Task Details:**
-- Task ID: {{task_id}}
-- Title: {{task_title}}
-- Story: {{task_story}}
-- Acceptance Criteria:
-{{#each task_acceptance_criteria}}
-  - {{this}}
-{{/each}}
-
-**User's Specific Request/Question (if any):**
-{{user_prompt}}
-
-**Instructions:**
-
-Provide your assistance as a single JSON object. The root object should have a key "suggestions" which is an array of suggestion objects. Each suggestion object must have a "type" field and other fields relevant to its type.
-
-**Suggestion Types and Formats:**
-
-1.  **`cargo_dependency`**: For adding dependencies to `Cargo.toml`.
-    -   `type`: "cargo_dependency"
-    -   `dependency_lines`: Array of strings. Each string is a complete line to be added under `[dependencies]` in `Cargo.toml` (e.g., "serde = { version = \"1.0\", features = [\"derive\"] }").
-    -   `notes` (optional): Brief explanation.
-
-2.  **`source_code`**: For providing source code for new or existing files.
-    -   `type`: "source_code"
-    -   `target_file`: String. The full suggested path for the file from the project root (e.g., "src/main.rs", "src/module/new_feature.rs").
-    -   `action`: String. One of:
-        -   `create`: Create a new file with the provided content. Should only be used if the file doesn't exist.
-        -   `replace`: Replace the entire content of an existing file. Use with caution.
-        -   `append_to_file`: Add the content to the end of an existing file.
-        -   `replace_function`: Replace an entire existing function. Requires `function_name`.
-        -   `append_to_function`: Add content inside an existing function. Requires `function_name`. (Less common, use carefully).
-        -   `add_import`: Add an import statement. Requires `import_statement`.
-    -   `content` (optional for some actions like `add_import`): String. The actual source code or text to use.
-    -   `function_name` (optional): String. The name of the target function for actions like `replace_function` or `append_to_function`.
-    -   `import_statement` (optional): String. The full import line (e.g., "use crate::my_module::MyStruct;").
-    -   `notes` (optional): Brief explanation about this code or modification.
-
-3.  **`general_advice`**: For textual explanations, architectural suggestions, best practices, or steps the user should take manually.
-    -   `type`: "general_advice"
-    -   `content`: String. The textual advice.
-    -   `notes` (optional): Brief explanation.
-
-**Example JSON Output Structure:**
-```json
-{
-  "suggestions": [
-    {
-      "type": "cargo_dependency",
-      "dependency_lines": [
-        "clap = { version = \"4.0\", features = [\"derive\"] }"
-      ],
-      "notes": "Clap is used for command-line argument parsing."
-    },
-    {
-      "type": "source_code",
-      "target_file": "src/main.rs",
-      "action": "replace",
-      "content": "fn main() {\n    println!(\"Hello, new world!\");\n}",
-      "notes": "Updated main function to reflect new requirements."
-    },
-    {
-      "type": "source_code",
-      "target_file": "src/utils.rs",
-      "action": "create",
-      "content": "pub fn helper_function() -> bool {\n    true\n}",
-      "notes": "A new utility module."
-    },
-    {
-      "type": "general_advice",
-      "content": "Remember to run `cargo fmt` and `cargo clippy` after these changes. Consider adding more unit tests for the new utility functions.",
-      "notes": "Good practices to follow."
-    }
-  ],
-  "overall_summary": "Provided dependencies for CLI parsing, updated main.rs, created a new utils.rs, and gave some general advice."
+        include_str!("../assets/prompts/task_assistant.md").to_string()
+    }
+
+    pub fn default_risk_analyst_prompt_content() -> String {
+        include_str!("../assets/prompts/risk_analyst.md").to_string()
+    }
+
+    pub fn default_retrospective_prompt_content() -> String {
+        include_str!("../assets/prompts/retrospective.md").to_string()
+    }
+
+    pub fn default_project_qa_prompt_content() -> String {
+        include_str!("../assets/prompts/project_qa.md").to_string()
+    }
+
+    pub fn default_story_splitter_prompt_content() -> String {
+        include_str!("../assets/prompts/story_splitter.md").to_string()
+    }
+
+    pub fn default_bug_analyst_prompt_content() -> String {
+        include_str!("../assets/prompts/bug_analyst.md").to_string()
+    }
+
+    pub fn default_sprint_goal_prompt_content() -> String {
+        include_str!("../assets/prompts/sprint_goal.md").to_string()
+    }
+
+    pub fn default_metrics_check_prompt_content() -> String {
+        include_str!("../assets/prompts/metrics_check.md").to_string()
+    }
+
+    pub fn default_backlog_reconcile_prompt_content() -> String {
+        include_str!("../assets/prompts/backlog_reconcile.md").to_string()
+    }
+
+    pub fn default_fix_tests_prompt_content() -> String {
+        include_str!("../assets/prompts/fix_tests.md").to_string()
+    }
+
+    pub fn default_fix_build_prompt_content() -> String {
+        include_str!("../assets/prompts/fix_build.md").to_string()
+    }
+
+    pub fn default_test_writer_prompt_content() -> String {
+        include_str!("../assets/prompts/test_writer.md").to_string()
+    }
+
+    pub fn default_code_reviewer_prompt_content() -> String {
+        include_str!("../assets/prompts/code_reviewer.md").to_string()
+    }
+
+    pub fn default_task_breakdown_prompt_content() -> String {
+        include_str!("../assets/prompts/task_breakdown.md").to_string()
+    }
+
+    pub fn default_effort_estimator_prompt_content() -> String {
+        include_str!("../assets/prompts/effort_estimator.md").to_string()
+    }
+
+    pub fn default_backlog_groomer_prompt_content() -> String {
+        include_str!("../assets/prompts/backlog_groomer.md").to_string()
+    }
+
+    pub fn default_criteria_checker_prompt_content() -> String {
+        include_str!("../assets/prompts/criteria_checker.md").to_string()
+    }
+
+    pub fn default_chat_prompt_content() -> String {
+        include_str!("../assets/prompts/chat.md").to_string()
+    }
+
+    pub fn default_project_summary_prompt_content() -> String {
+        include_str!("../assets/prompts/project_summary.md").to_string()
+    }
+
+    /// BACKLOG.md for `export --md`: items grouped by status, then each item's story and
+    /// acceptance criteria.
+    pub fn backlog_markdown(project: &Project) -> String {
+        let work = WorkStats::of(&project.backlog);
+        let mut out = format!("# {} — Backlog\n\n", project.meta.name);
+        if !project.meta.description.is_empty() {
+            out.push_str(&format!("{}\n\n", project.meta.description));
+        }
+        out.push_str(&format!(
+            "_Generated by env-coach on {}: {} items, {} points, {}% done._\n",
+            chrono::Utc::now().format("%Y-%m-%d"), work.items.total, work.points.total, work.completion_percent.unwrap_or(0)
+        ));
+
+        let groups = [
+            (Status::InProgress, "🚧 In Progress"),
+            (Status::Review, "👀 In Review"),
+            (Status::Todo, "⏳ To Do"),
+            (Status::Done, "✅ Done"),
+        ];
+        for (status, heading) in &groups {
+            let items: Vec<&BacklogItem> = project.backlog.iter().filter(|item| &item.status == status).collect();
+            if items.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n## {} ({})\n\n", heading, items.len()));
+            out.push_str("| ID | Type | Title | Priority | Points | Sprint |\n|----|------|-------|----------|--------|--------|\n");
+            for item in items {
+                out.push_str(&format!(
+                    "| {} | {:?} | {} | {:?} | {} | {} |\n",
+                    item.id, item.item_type, table_cell(&item.title), item.priority, item.effort, item.sprint.as_deref().unwrap_or("—")
+                ));
+            }
+        }
+
+        if !project.backlog.is_empty() {
+            out.push_str("\n## Items\n");
+            for item in &project.backlog {
+                out.push_str(&markdown_item_details(item));
+            }
+        }
+        out
+    }
+
+    /// SPRINT-<id>.md for `export --md`: goal, dates, the sprint's stories and their
+    /// acceptance criteria.
+    pub fn sprint_markdown(project: &Project, sprint: &Sprint) -> String {
+        let sprint_stats = stats::sprint_stats(project, sprint, chrono::Utc::now());
+        let items: Vec<&BacklogItem> = project.backlog.iter().filter(|item| item.sprint.as_ref() == Some(&sprint.id)).collect();
+        let mut out = format!("# {} — Sprint {}\n\n", project.meta.name, sprint.id);
+        out.push_str(&format!("- **Goal:** {}\n", sprint.goal));
+        out.push_str(&format!("- **Dates:** {} to {}\n", sprint.start_date.format("%Y-%m-%d"), sprint.end_date.format("%Y-%m-%d")));
+        out.push_str(&format!("- **Status:** {:?}\n", sprint.status));
+        out.push_str(&format!("- **Points:** {} completed of {} planned ({}%)\n", sprint.completed_points, sprint.total_points, sprint_stats.completion_percent));
+
+        out.push_str(&format!("\n## Stories ({})\n\n", items.len()));
+        if items.is_empty() {
+            out.push_str("No items are planned for this sprint.\n");
+            return out;
+        }
+        out.push_str("| ID | Title | Status | Priority | Points |\n|----|-------|--------|----------|--------|\n");
+        for item in &items {
+            out.push_str(&format!("| {} | {} | {:?} | {:?} | {} |\n", item.id, table_cell(&item.title), item.status, item.priority, item.effort));
+        }
+        out.push_str("\n## Acceptance Criteria\n");
+        for item in items {
+            out.push_str(&markdown_item_details(item));
+        }
+        out
+    }
 }
-```
 
-**Guidelines for your response:**
--   Ensure the output is **only the valid JSON object** as described. Do not include any introductory text, apologies, or sign-offs outside the JSON structure.
--   If suggesting code for `{{primary_language}}`, ensure it is idiomatic and follows best practices for that language.
--   Be specific with file paths and actions. If modifying an existing file, try to be precise (e.g., suggest replacing a specific function if possible, rather than the whole file, unless necessary).
--   If the user's request is unclear or too broad for a direct code solution, provide `general_advice` on how to approach it or break it down.
--   Provide complete and runnable code examples where applicable.
-"#.to_string()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Priority, SprintStatus};
+    use chrono::Utc;
+
+    #[test]
+    fn test_markdown_exports() {
+        let mut project = Project::new("Shop".to_string(), "Online shop".to_string(), None);
+        for (id, status) in [("US-001", Status::Done), ("US-002", Status::Todo)] {
+            project.backlog.push(BacklogItem::test(id)
+                .with_title(&format!("Pay | {}", id))
+                .with_story("As a buyer I want to pay")
+                .with_criteria(&["Card payments work"])
+                .with_priority(Priority::High)
+                .with_status(status)
+                .with_sprint("S-001"));
+        }
+        let sprint = Sprint {
+            id: "S-001".to_string(),
+            goal: "Payments".to_string(),
+            start_date: Utc::now(),
+            end_date: Utc::now(),
+            status: SprintStatus::Active,
+            total_points: 6,
+            completed_points: 3,
+            tasks: vec![],
+            stories: vec!["US-001".to_string(), "US-002".to_string()],
+            planned_velocity: 6,
+            actual_velocity: 0,
+            retrospective: None,
+        };
+
+        let backlog = Templates::backlog_markdown(&project);
+        assert!(backlog.contains("2 items, 6 points, 50% done"));
+        assert!(backlog.contains("## ⏳ To Do (1)"));
+        assert!(backlog.contains("| US-002 | UserStory | Pay \\| US-002 | High | 3 | S-001 |"));
+        assert!(backlog.contains("### US-001 — Pay | US-001\n\nAs a buyer I want to pay\n\n- [x] Card payments work"));
+        assert!(backlog.contains("### US-002 — Pay | US-002\n\nAs a buyer I want to pay\n\n- [ ] Card payments work"));
+
+        let plan = Templates::sprint_markdown(&project, &sprint);
+        assert!(plan.starts_with("# Shop — Sprint S-001\n\n- **Goal:** Payments"));
+        assert!(plan.contains("3 completed of 6 planned (50%)"));
+        assert!(plan.contains("## Stories (2)"));
+        assert!(plan.contains("| US-001 | Pay \\| US-001 | Done | High | 3 |"));
     }
-}
\ No newline at end of file
+}
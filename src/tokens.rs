@@ -0,0 +1,412 @@
+// src/tokens.rs
+//! Token estimates and context-window budgeting for prompts. Ollama silently drops the
+//! start of a prompt that does not fit the model's context, so prompt builders fill their
+//! bulky sections (backlog, code) through `fill_within_budget`, which trims them to fit
+//! and says so, or `build_prompts`, which applies the `TrimStrategy` chosen in
+//! `.env-coach/context.toml`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::FinalLlmConfig;
+use crate::ollama;
+use crate::output::{self, Level, Topic};
+
+/// Ollama's context window when `context_tokens` is not configured.
+pub const DEFAULT_CONTEXT_TOKENS: u32 = 4096;
+/// Tokens kept free for the model's answer.
+pub const RESPONSE_RESERVE_TOKENS: usize = 1024;
+
+/// Prompts the `chunk` strategy sends at most; entries past them are left out.
+pub const MAX_CHUNKS: usize = 8;
+
+const TRIM_MARKER: &str = "... (trimmed to fit the model's context window)";
+const LEFT_OUT_MARKER: &str = "(left out to fit the model's context window)";
+
+/// What to do when a prompt's sections do not fit the model's context window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrimStrategy {
+    /// Cut sections at a line, the last sections first
+    #[default]
+    Truncate,
+    /// Leave out whole entries (backlog items, files, code chunks), least important first
+    DropLowPriority,
+    /// Have a model condense the sections, the last ones first
+    Summarize,
+    /// Spread the first section over several prompts, one LLM call each
+    Chunk,
+}
+
+/// A bulky part of a prompt: its placeholder and its entries, most important first.
+#[derive(Debug, Clone)]
+pub struct Section {
+    pub placeholder: &'static str,
+    pub entries: Vec<String>,
+    pub separator: &'static str,
+}
+
+impl Section {
+    pub fn new(placeholder: &'static str, entries: Vec<String>, separator: &'static str) -> Self {
+        Self { placeholder, entries, separator }
+    }
+
+    fn text(&self) -> String {
+        match self.entries.is_empty() {
+            true => LEFT_OUT_MARKER.to_string(),
+            false => self.entries.join(self.separator),
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.placeholder.trim_matches(|c| c == '{' || c == '}')
+    }
+}
+
+/// Estimated token count of `text`, close to what BPE tokenizers produce for English and
+/// code: a word costs a token per 6 characters, each symbol a token, and each line break
+/// (with the indentation after it) one token.
+pub fn estimate_tokens(text: &str) -> usize {
+    let mut tokens = 0;
+    let mut word_len: usize = 0;
+    let mut in_break = false;
+    for c in text.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            word_len += 1;
+            in_break = false;
+            continue;
+        }
+        tokens += word_len.div_ceil(6);
+        word_len = 0;
+        if c == '\n' {
+            if !in_break {
+                tokens += 1;
+            }
+            in_break = true;
+        } else if !c.is_whitespace() {
+            tokens += 1;
+            in_break = false;
+        }
+    }
+    tokens + word_len.div_ceil(6)
+}
+
+/// Tokens a prompt may use with `cfg`'s model, leaving room for the answer.
+pub fn prompt_budget(cfg: &FinalLlmConfig) -> usize {
+    (cfg.context_tokens.unwrap_or(DEFAULT_CONTEXT_TOKENS) as usize).saturating_sub(RESPONSE_RESERVE_TOKENS)
+}
+
+/// The longest start of `text`, in whole lines, that fits in `max_tokens` (marker included);
+/// nothing when not even the marker fits.
+pub fn trim_to_tokens(text: &str, max_tokens: usize) -> String {
+    if estimate_tokens(text) <= max_tokens {
+        return text.to_string();
+    }
+    if max_tokens <= estimate_tokens(TRIM_MARKER) {
+        return String::new();
+    }
+    let available = max_tokens.saturating_sub(estimate_tokens(TRIM_MARKER) + 1);
+    let mut kept = String::new();
+    let mut used = 0;
+    for line in text.lines() {
+        let cost = estimate_tokens(line) + 1;
+        if used + cost > available {
+            break;
+        }
+        kept.push_str(line);
+        kept.push('\n');
+        used += cost;
+    }
+    kept.push_str(TRIM_MARKER);
+    kept
+}
+
+/// Fill `template`'s `sections` (placeholder, content), trimming them so the prompt fits
+/// `budget` tokens. Sections listed last are trimmed first. Returns the prompt and the
+/// placeholders that were trimmed.
+pub fn fit_sections(template: &str, sections: &[(&str, String)], budget: usize) -> (String, Vec<String>) {
+    let fixed = estimate_tokens(&sections.iter().fold(template.to_string(), |prompt, (placeholder, _)| prompt.replace(placeholder, "")));
+    let sizes: Vec<usize> = sections.iter().map(|(_, content)| estimate_tokens(content)).collect();
+    let mut over = (fixed + sizes.iter().sum::<usize>()).saturating_sub(budget);
+
+    let mut contents: Vec<String> = sections.iter().map(|(_, content)| content.clone()).collect();
+    let mut trimmed = vec![];
+    for i in (0..sections.len()).rev() {
+        if over == 0 {
+            break;
+        }
+        let cut = over.min(sizes[i]);
+        contents[i] = trim_to_tokens(&contents[i], sizes[i] - cut);
+        trimmed.push(sections[i].0.trim_matches(|c| c == '{' || c == '}').to_string());
+        over -= cut;
+    }
+    trimmed.reverse();
+
+    let prompt = sections.iter()
+        .zip(contents)
+        .fold(template.to_string(), |prompt, ((placeholder, _), content)| prompt.replace(placeholder, &content));
+    (prompt, trimmed)
+}
+
+/// `fit_sections` within `cfg`'s context window, reporting the prompt size when sections
+/// had to be trimmed (or at verbose LLM output).
+pub fn fill_within_budget(cfg: &FinalLlmConfig, template: &str, sections: &[(&str, String)]) -> String {
+    let budget = prompt_budget(cfg);
+    let before = estimate_tokens(&fill(template, sections));
+    let (prompt, trimmed) = fit_sections(template, sections, budget);
+    let window = cfg.context_tokens.unwrap_or(DEFAULT_CONTEXT_TOKENS);
+    if !trimmed.is_empty() {
+        println!("📏 This prompt is ~{} tokens and {}'s context is {} ({} kept for the answer); trimmed {} to ~{} tokens",
+            before, cfg.model, window, RESPONSE_RESERVE_TOKENS, trimmed.join(", "), estimate_tokens(&prompt));
+        if estimate_tokens(&prompt) > budget {
+            println!("⚠️  The prompt still does not fit; raise `context_tokens` in the LLM config");
+        }
+    } else if output::enabled(Topic::Llm, Level::Verbose) {
+        println!("📏 Prompt: ~{} of {} tokens", before, window);
+    }
+    prompt
+}
+
+fn fill(template: &str, sections: &[(&str, String)]) -> String {
+    sections.iter().fold(template.to_string(), |prompt, (placeholder, content)| prompt.replace(placeholder, content))
+}
+
+fn fill_sections(template: &str, sections: &[Section]) -> String {
+    sections.iter().fold(template.to_string(), |prompt, section| prompt.replace(section.placeholder, &section.text()))
+}
+
+/// Leave out entries from the end of the last sections until the prompt fits `budget`.
+/// Returns how many entries each section lost.
+fn drop_entries(template: &str, sections: &mut [Section], budget: usize) -> Vec<(String, usize)> {
+    let mut dropped = vec![];
+    for i in (0..sections.len()).rev() {
+        let mut count = 0;
+        while estimate_tokens(&fill_sections(template, sections)) > budget && sections[i].entries.pop().is_some() {
+            count += 1;
+        }
+        if count > 0 {
+            dropped.push((sections[i].name().to_string(), count));
+        }
+    }
+    dropped.reverse();
+    dropped
+}
+
+/// Groups of consecutive entries whose joined text fits `room` tokens, at most `MAX_CHUNKS`.
+/// An entry larger than `room` is cut to fit. Returns the groups and how many entries did
+/// not fit in them.
+fn chunk_entries(section: &Section, room: usize) -> (Vec<Vec<String>>, usize) {
+    let mut groups: Vec<Vec<String>> = vec![];
+    let mut current: Vec<String> = vec![];
+    let mut used = 0;
+    for (i, entry) in section.entries.iter().enumerate() {
+        let entry = trim_to_tokens(entry, room);
+        let cost = estimate_tokens(&entry) + estimate_tokens(section.separator);
+        if !current.is_empty() && used + cost > room {
+            groups.push(std::mem::take(&mut current));
+            used = 0;
+            if groups.len() == MAX_CHUNKS {
+                return (groups, section.entries.len() - i);
+            }
+        }
+        current.push(entry);
+        used += cost;
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+    (groups, 0)
+}
+
+/// Condense `text` to about `target` tokens with the summary model; cut instead when the
+/// model fails. Large texts are condensed in parts that fit the model's context.
+async fn summarize(cfg: &FinalLlmConfig, name: &str, text: &str, target: usize) -> String {
+    let budget = prompt_budget(cfg).saturating_sub(200).max(1);
+    let total = estimate_tokens(text).max(1);
+    let mut parts: Vec<String> = vec![];
+    let mut current = String::new();
+    for line in text.lines() {
+        if !current.is_empty() && estimate_tokens(&current) + estimate_tokens(line) > budget {
+            parts.push(std::mem::take(&mut current));
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    parts.push(current);
+
+    let mut summary = String::new();
+    for part in &parts {
+        let part_target = (target * estimate_tokens(part) / total).max(1);
+        let prompt = format!(
+            "Condense the following {} to at most {} words for another prompt. Keep IDs, file paths, \
+             function and type names. Reply with the condensed text only.\n\n{}",
+            name, part_target * 3 / 4, part
+        );
+        match ollama::send_generation_prompt(cfg, &prompt).await {
+            Ok(condensed) => {
+                summary.push_str(condensed.trim());
+                summary.push('\n');
+            }
+            Err(e) => {
+                println!("⚠️  Could not summarize {} ({}); cutting it instead", name, e);
+                return trim_to_tokens(text, target);
+            }
+        }
+    }
+    trim_to_tokens(summary.trim_end(), target)
+}
+
+/// The prompt(s) for `template` with `sections` filled in, made to fit `cfg`'s context
+/// window with `strategy`. Only `Chunk` gives more than one prompt; the caller sends each
+/// and merges the answers. `summary_model` is the model `Summarize` uses.
+pub async fn build_prompts(cfg: &FinalLlmConfig, strategy: TrimStrategy, summary_model: Option<&str>, template: &str, mut sections: Vec<Section>) -> Vec<String> {
+    let budget = prompt_budget(cfg);
+    let before = estimate_tokens(&fill_sections(template, &sections));
+    let window = cfg.context_tokens.unwrap_or(DEFAULT_CONTEXT_TOKENS);
+    if before <= budget {
+        if output::enabled(Topic::Llm, Level::Verbose) {
+            println!("📏 Prompt: ~{} of {} tokens", before, window);
+        }
+        return vec![fill_sections(template, &sections)];
+    }
+    // Without room for any section, only cutting them all (and saying so) is left
+    let fixed = estimate_tokens(&fill(template, &sections.iter().map(|section| (section.placeholder, String::new())).collect::<Vec<_>>()));
+    if fixed >= budget {
+        let texts: Vec<(&str, String)> = sections.iter().map(|section| (section.placeholder, section.text())).collect();
+        return vec![fill_within_budget(cfg, template, &texts)];
+    }
+    let report = |what: String| println!("📏 This prompt is ~{} tokens and {}'s context is {} ({} kept for the answer); {}",
+        before, cfg.model, window, RESPONSE_RESERVE_TOKENS, what);
+
+    match strategy {
+        TrimStrategy::Truncate => {
+            let texts: Vec<(&str, String)> = sections.iter().map(|section| (section.placeholder, section.text())).collect();
+            vec![fill_within_budget(cfg, template, &texts)]
+        }
+        TrimStrategy::DropLowPriority => {
+            let dropped = drop_entries(template, &mut sections, budget);
+            let dropped = dropped.iter().map(|(name, count)| format!("{} {} entr{}", count, name, if *count == 1 { "y" } else { "ies" })).collect::<Vec<_>>();
+            report(format!("left out {}", dropped.join(", ")));
+            vec![fill_sections(template, &sections)]
+        }
+        TrimStrategy::Summarize => {
+            let summary_cfg = FinalLlmConfig { model: summary_model.unwrap_or(&cfg.model).to_string(), ..cfg.clone() };
+            let mut texts: Vec<(&str, String)> = sections.iter().map(|section| (section.placeholder, section.text())).collect();
+            let mut over = (fixed + texts.iter().map(|(_, text)| estimate_tokens(text)).sum::<usize>()).saturating_sub(budget);
+            let mut condensed = vec![];
+            for i in (0..texts.len()).rev() {
+                if over == 0 {
+                    break;
+                }
+                let size = estimate_tokens(&texts[i].1);
+                let target = size.saturating_sub(over);
+                println!("📝 Summarizing {} (~{} tokens) to ~{} tokens with {}...", sections[i].name(), size, target, summary_cfg.model);
+                texts[i].1 = summarize(&summary_cfg, sections[i].name(), &texts[i].1, target).await;
+                over = over.saturating_sub(size - estimate_tokens(&texts[i].1).min(size));
+                condensed.push(sections[i].name().to_string());
+            }
+            condensed.reverse();
+            let prompt = fill(template, &texts);
+            report(format!("summarized {} to ~{} tokens", condensed.join(", "), estimate_tokens(&prompt)));
+            vec![prompt]
+        }
+        TrimStrategy::Chunk => {
+            // The first section is spread over the prompts; the others may take half the room in each
+            let available = budget - fixed;
+            let first = sections.remove(0);
+            // Cut the other sections to their share, the last ones first
+            let mut over = sections.iter().map(|section| estimate_tokens(&section.text())).sum::<usize>().saturating_sub(available / 2);
+            let mut rest: Vec<(&str, String)> = vec![];
+            for section in sections.iter().rev() {
+                let text = section.text();
+                let size = estimate_tokens(&text);
+                let cut = over.min(size);
+                over -= cut;
+                rest.insert(0, (section.placeholder, if cut > 0 { trim_to_tokens(&text, size - cut) } else { text }));
+            }
+            let room = available.saturating_sub(rest.iter().map(|(_, text)| estimate_tokens(text)).sum::<usize>()).max(1);
+            let (groups, left_out) = chunk_entries(&first, room);
+            let left = if left_out > 0 { format!(" ({} entries past {} prompts left out)", left_out, MAX_CHUNKS) } else { String::new() };
+            report(format!("splitting {} over {} prompts{}", first.name(), groups.len(), left));
+            groups.into_iter()
+                .map(|group| {
+                    let part = Section { entries: group, ..first.clone() };
+                    fill(&template.replace(first.placeholder, &part.text()), &rest)
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("The quick brown fox"), 4);
+        assert_eq!(estimate_tokens("fn main() {\n    println!(\"hi\");\n}"), 17);
+    }
+
+    #[test]
+    fn test_fit_sections_trims_last_sections_first() {
+        let backlog: String = (1..=20).map(|i| format!("- US-{:03}: Story number {}\n", i, i)).collect();
+        let code: String = (1..=50).map(|i| format!("let value_{} = compute({});\n", i, i)).collect();
+        let template = "Plan a sprint.\n{{backlog}}\n{{code}}";
+        let sections = [("{{backlog}}", backlog.clone()), ("{{code}}", code)];
+
+        let (prompt, trimmed) = fit_sections(template, &sections, 10_000);
+        assert!(trimmed.is_empty());
+        assert!(!prompt.contains(TRIM_MARKER));
+
+        let budget = estimate_tokens(&backlog) + 60;
+        let (prompt, trimmed) = fit_sections(template, &sections, budget);
+        assert_eq!(trimmed, vec!["code"]);
+        assert!(prompt.contains("- US-020: Story number 20"));
+        assert!(prompt.ends_with(TRIM_MARKER));
+        assert!(estimate_tokens(&prompt) <= budget);
+
+        let (prompt, trimmed) = fit_sections(template, &sections, 40);
+        assert_eq!(trimmed, vec!["backlog", "code"]);
+        assert!(prompt.starts_with("Plan a sprint.\n- US-001"));
+        assert!(estimate_tokens(&prompt) <= 40);
+    }
+
+    fn entries(prefix: &str, count: usize) -> Vec<String> {
+        (1..=count).map(|i| format!("- {}-{:03}: a story about feature number {}", prefix, i, i)).collect()
+    }
+
+    #[test]
+    fn test_drop_entries_leaves_out_least_important_first() {
+        let template = "Plan.\n{{backlog}}\n{{code}}";
+        let mut sections = vec![Section::new("{{backlog}}", entries("US", 10), "\n"), Section::new("{{code}}", entries("FILE", 10), "\n\n")];
+        let budget = estimate_tokens(&sections[0].entries.join("\n")) + 60;
+        let dropped = drop_entries(template, &mut sections, budget);
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].0, "code");
+        assert_eq!(sections[0].entries.len(), 10);
+        assert!(sections[1].entries.len() < 10);
+        assert_eq!(sections[1].entries[0], "- FILE-001: a story about feature number 1");
+        assert!(estimate_tokens(&fill_sections(template, &sections)) <= budget);
+
+        let dropped = drop_entries(template, &mut sections, 30);
+        assert_eq!(dropped.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(), vec!["backlog", "code"]);
+        assert!(sections[1].entries.is_empty());
+        assert!(fill_sections(template, &sections).ends_with(LEFT_OUT_MARKER));
+    }
+
+    #[test]
+    fn test_chunk_entries() {
+        let section = Section::new("{{backlog}}", entries("US", 10), "\n");
+        let entry_tokens = estimate_tokens(&section.entries[0]) + 1;
+        let (groups, left_out) = chunk_entries(&section, entry_tokens * 3);
+        assert_eq!(groups.iter().map(Vec::len).collect::<Vec<_>>(), vec![3, 3, 3, 1]);
+        assert_eq!(left_out, 0);
+        assert_eq!(groups[3][0], "- US-010: a story about feature number 10");
+
+        let many = Section::new("{{backlog}}", entries("US", MAX_CHUNKS + 3), "\n");
+        let (groups, left_out) = chunk_entries(&many, entry_tokens);
+        assert_eq!(groups.len(), MAX_CHUNKS);
+        assert_eq!(left_out, 3);
+    }
+}
@@ -0,0 +1,217 @@
+// src/workspace.rs
+//! Workspace-level shared backlog for products spread over several repositories.
+//!
+//! An `env-coach-workspace.json` in a parent directory holds items tagged with their
+//! target repository. When a project is loaded, the items for its repository are merged
+//! into its backlog, and saving writes them back to the workspace file, so every command
+//! works on the repository's slice without knowing about the extra scope.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::config::{BacklogItem, Project};
+
+pub const WORKSPACE_FILE: &str = "env-coach-workspace.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Workspace {
+    pub name: String,
+    #[serde(default)]
+    pub repos: Vec<WorkspaceRepo>,
+    #[serde(default)]
+    pub backlog: Vec<WorkspaceItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceRepo {
+    pub name: String,
+    /// Repository directory, relative to the workspace file
+    pub path: PathBuf,
+}
+
+/// A shared backlog item and the repository it belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceItem {
+    pub repo: String,
+    #[serde(flatten)]
+    pub item: BacklogItem,
+}
+
+/// Which part of a loaded project's backlog came from the workspace.
+#[derive(Debug, Clone)]
+pub struct WorkspaceSlice {
+    pub path: PathBuf,
+    pub repo: String,
+    pub item_ids: Vec<String>,
+}
+
+impl Workspace {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read workspace file {:?}", path))?;
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse workspace file {:?}", path))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize workspace")?;
+        std::fs::write(path, content).with_context(|| format!("Failed to write workspace file {:?}", path))
+    }
+
+    /// Next free `WS-NNN` ID. Shared items use their own prefix so they never collide
+    /// with the `US-NNN` items of any repository.
+    pub fn next_item_id(&self) -> String {
+        let highest = self.backlog.iter()
+            .filter_map(|entry| entry.item.id.strip_prefix("WS-").and_then(|n| n.parse::<u32>().ok()))
+            .max()
+            .unwrap_or(0);
+        format!("WS-{:03}", highest + 1)
+    }
+
+    /// The repository name for `project_dir`: the registered repo at that path, otherwise
+    /// the project name.
+    fn repo_name_for(&self, workspace_dir: &Path, project_dir: &Path, project_name: &str) -> String {
+        let project_dir = project_dir.canonicalize().unwrap_or_else(|_| project_dir.to_path_buf());
+        self.repos.iter()
+            .find(|repo| {
+                let repo_dir = workspace_dir.join(&repo.path);
+                repo_dir.canonicalize().unwrap_or(repo_dir) == project_dir
+            })
+            .map(|repo| repo.name.clone())
+            .unwrap_or_else(|| project_name.to_string())
+    }
+
+    /// Shared items of `repo`, in workspace order.
+    pub fn items_for<'a>(&'a self, repo: &'a str) -> impl Iterator<Item = &'a BacklogItem> + 'a {
+        self.backlog.iter().filter(move |entry| entry.repo == repo).map(|entry| &entry.item)
+    }
+}
+
+/// Look for a workspace file in `start` and its ancestors.
+pub fn find_workspace_file(start: &Path) -> Option<PathBuf> {
+    start.ancestors()
+        .map(|dir| dir.join(WORKSPACE_FILE))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Merge the shared items of this repository into the project backlog. Items whose ID
+/// already exists locally are left out, so a local copy always wins.
+pub fn attach(project: &mut Project, workspace_path: &Path, workspace: &Workspace, project_dir: &Path) {
+    let workspace_dir = workspace_path.parent().unwrap_or(Path::new("."));
+    let repo = workspace.repo_name_for(workspace_dir, project_dir, &project.meta.name);
+
+    let mut item_ids = Vec::new();
+    for item in workspace.items_for(&repo) {
+        if project.backlog.iter().any(|local| local.id == item.id) {
+            continue;
+        }
+        item_ids.push(item.id.clone());
+        project.backlog.push(item.clone());
+    }
+    project.workspace = Some(WorkspaceSlice { path: workspace_path.to_path_buf(), repo, item_ids });
+}
+
+/// Split a project into what belongs in project.json and the shared items to write back.
+pub fn detach(project: &Project) -> (Project, Vec<BacklogItem>) {
+    let Some(slice) = &project.workspace else { return (project.clone(), vec![]) };
+    let mut local = project.clone();
+    let (shared, own): (Vec<BacklogItem>, Vec<BacklogItem>) = local.backlog.drain(..)
+        .partition(|item| slice.item_ids.contains(&item.id));
+    local.backlog = own;
+    local.workspace = None;
+    (local, shared)
+}
+
+/// Replace this repository's entries in the workspace with the updated items. Entries of
+/// the slice that are no longer in the project (removed or archived) are dropped.
+pub fn write_back(workspace: &mut Workspace, slice: &WorkspaceSlice, items: Vec<BacklogItem>) {
+    let repo = slice.repo.as_str();
+    workspace.backlog.retain(|entry| {
+        entry.repo != repo || !slice.item_ids.contains(&entry.item.id) || items.iter().any(|item| item.id == entry.item.id)
+    });
+    for item in items {
+        match workspace.backlog.iter_mut().find(|entry| entry.repo == repo && entry.item.id == item.id) {
+            Some(entry) => entry.item = item,
+            None => workspace.backlog.push(WorkspaceItem { repo: repo.to_string(), item }),
+        }
+    }
+}
+
+/// Read another repository's project.json (or project.toml) without resolving its LLM configuration.
+pub fn read_repo_project(repo_dir: &Path) -> Result<Project> {
+    Project::read_file(&Project::file_in(repo_dir)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Status;
+
+    fn workspace() -> Workspace {
+        Workspace {
+            name: "Shop".to_string(),
+            repos: vec![WorkspaceRepo { name: "api".to_string(), path: PathBuf::from("api") }],
+            backlog: vec![
+                WorkspaceItem { repo: "api".to_string(), item: BacklogItem::test("WS-001") },
+                WorkspaceItem { repo: "web".to_string(), item: BacklogItem::test("WS-002") },
+                WorkspaceItem { repo: "api".to_string(), item: BacklogItem::test("WS-004") },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_attach_and_detach_round_trip_the_repo_slice() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("api")).unwrap();
+        let workspace_path = dir.path().join(WORKSPACE_FILE);
+        let mut ws = workspace();
+
+        let mut project = Project::new("api-server".to_string(), "".to_string(), None);
+        project.backlog.push(BacklogItem::test("US-001"));
+        attach(&mut project, &workspace_path, &ws, &dir.path().join("api"));
+
+        let ids: Vec<&str> = project.backlog.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["US-001", "WS-001", "WS-004"]);
+
+        project.backlog[1].status = Status::Done;
+        let (local, shared) = detach(&project);
+        assert_eq!(local.backlog.len(), 1);
+        assert!(local.workspace.is_none());
+        assert_eq!(shared.len(), 2);
+
+        let slice = project.workspace.clone().unwrap();
+        write_back(&mut ws, &slice, shared);
+        assert_eq!(ws.backlog.len(), 3);
+        assert_eq!(ws.backlog[0].item.status, Status::Done);
+        assert_eq!(ws.backlog[1].item.status, Status::Todo);
+
+        // Items removed from the project disappear from the workspace
+        let (_, mut shared) = detach(&project);
+        shared.retain(|item| item.id != "WS-004");
+        write_back(&mut ws, &slice, shared);
+        let ids: Vec<&str> = ws.backlog.iter().map(|e| e.item.id.as_str()).collect();
+        assert_eq!(ids, vec!["WS-001", "WS-002"]);
+    }
+
+    #[test]
+    fn test_unregistered_repo_falls_back_to_project_name_and_ids_skip_gaps() {
+        let dir = tempfile::tempdir().unwrap();
+        let ws = workspace();
+        let mut project = Project::new("web".to_string(), "".to_string(), None);
+        attach(&mut project, &dir.path().join(WORKSPACE_FILE), &ws, dir.path());
+        assert_eq!(project.backlog.len(), 1);
+        assert_eq!(project.workspace.as_ref().unwrap().repo, "web");
+
+        assert_eq!(ws.next_item_id(), "WS-005");
+    }
+
+    #[test]
+    fn test_workspace_item_serializes_flat() {
+        let entry = WorkspaceItem { repo: "api".to_string(), item: BacklogItem::test("WS-001") };
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["repo"], "api");
+        assert_eq!(json["id"], "WS-001");
+        let back: WorkspaceItem = serde_json::from_value(json).unwrap();
+        assert_eq!(back.item.id, "WS-001");
+    }
+}